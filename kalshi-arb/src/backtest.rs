@@ -0,0 +1,651 @@
+//! Historical backtest.
+//!
+//! `--backtest <dir>` replays recorded Kalshi markets, orderbooks, odds and
+//! score snapshots through `SportPipeline::tick` on a simulated clock. Entries
+//! and exits go through the sim `FillSimulator`, exactly as in `--simulate`,
+//! and the run ends with a P&L report per sport and the strategy config it
+//! ran under. No API requests are made.
+//!
+//! A recording is a directory of `*.jsonl` files holding one [`RecordedEvent`]
+//! per line. Files are merged and replayed in timestamp order.
+
+use crate::config::Config;
+use crate::engine::drawdown::DrawdownMonitor;
+use crate::engine::exit_manager::{threshold_exit, ExitReason};
+use crate::engine::fees::calculate_fee;
+use crate::engine::kelly_tuner::KellyTuner;
+use crate::engine::momentum::MomentumScorer;
+use crate::engine::win_prob::GameSegment;
+use crate::engine::{matcher, FillResult, FillSimulator};
+use crate::feed::score_feed::ScoreUpdate;
+use crate::feed::supervisor::FeedSupervisor;
+use crate::feed::types::{ApiQuota, OddsUpdate};
+use crate::feed::OddsFeed;
+use crate::journal::{BookSnapshot, Journal};
+use crate::kalshi::types::Market;
+use crate::locale::NumberFormat;
+use crate::network::Network;
+use crate::pipeline::{self, SportPipeline};
+use crate::tui::state::AppState;
+use crate::{DepthBook, LiveBook};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::watch;
+
+/// One line of a recording.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    pub at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub data: RecordedData,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecordedData {
+    /// Kalshi markets for one sport's series, as returned by the REST API.
+    Markets {
+        sport: String,
+        markets: Vec<Market>,
+        /// Segment label ("1H", "Q3", ...) when the series is a period market.
+        #[serde(default)]
+        segment: Option<String>,
+    },
+    /// Full orderbook of one ticker after a WS update.
+    Book {
+        ticker: String,
+        book: BookSnapshot,
+        /// The WS update that produced it: "SNAPSHOT" or e.g. "YES 45c +10".
+        #[serde(default)]
+        update: String,
+    },
+    /// One odds poll for a sport.
+    Odds {
+        sport: String,
+        updates: Vec<OddsUpdate>,
+    },
+    /// One score poll for a sport, as cached by the pipeline.
+    Scores {
+        sport: String,
+        updates: Vec<ScoreUpdate>,
+    },
+    /// Final yes/no result of a market.
+    Settlement { ticker: String, result: String },
+}
+
+/// Read every `*.jsonl` file in `dir`, merged in timestamp order.
+pub fn load_events(dir: &Path) -> Result<Vec<RecordedEvent>> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("failed to read {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "jsonl"))
+        .collect();
+    paths.sort();
+
+    let mut events = Vec::new();
+    for path in &paths {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        for (i, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event: RecordedEvent = serde_json::from_str(line)
+                .with_context(|| format!("{}:{}: bad recorded event", path.display(), i + 1))?;
+            events.push(event);
+        }
+    }
+    // Stable, so same-timestamp events keep their file order
+    events.sort_by_key(|e| e.at);
+    Ok(events)
+}
+
+/// Odds source serving the latest recorded snapshot for each sport.
+struct ReplayOddsFeed {
+    snapshots: Arc<Mutex<HashMap<String, Vec<OddsUpdate>>>>,
+}
+
+#[async_trait]
+impl OddsFeed for ReplayOddsFeed {
+    async fn fetch_odds(&mut self, sport: &str) -> Result<Vec<OddsUpdate>> {
+        let snapshots = self.snapshots.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(snapshots.get(sport).cloned().unwrap_or_default())
+    }
+
+    fn last_quota(&self) -> Option<ApiQuota> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SportReport {
+    /// Fair value source and strategy thresholds the sport ran under.
+    pub strategy: String,
+    pub entries: u32,
+    pub closed: u32,
+    pub wins: u32,
+    pub pnl_cents: i64,
+    /// Positions still open at the end of the recording.
+    pub open: u32,
+    /// Open positions marked at the last best bid, net of entry cost.
+    pub unrealized_cents: i64,
+}
+
+#[derive(Debug, Default)]
+pub struct BacktestReport {
+    pub events: usize,
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub starting_balance_cents: i64,
+    pub final_balance_cents: i64,
+    pub sports: BTreeMap<String, SportReport>,
+}
+
+struct Backtest<'a> {
+    config: &'a Config,
+    pipelines: Vec<SportPipeline>,
+    market_index: matcher::MarketIndex,
+    live_book: LiveBook,
+    odds: Arc<Mutex<HashMap<String, Vec<OddsUpdate>>>>,
+    feeds: FeedSupervisor,
+    fill_simulator: FillSimulator,
+    scorer: MomentumScorer,
+    state_tx: watch::Sender<AppState>,
+    journal: Journal,
+    api_request_times: Mutex<VecDeque<Instant>>,
+    /// Ticker -> sport key, from the recorded markets.
+    ticker_sport: HashMap<String, String>,
+    drawdown: DrawdownMonitor,
+    report: BacktestReport,
+}
+
+impl<'a> Backtest<'a> {
+    fn new(config: &'a Config) -> Self {
+        let mut entries: Vec<_> = config.sports.iter().collect();
+        entries.sort_by_key(|(_, sc)| sc.hotkey.clone());
+        let pipelines: Vec<SportPipeline> = entries
+            .into_iter()
+            .map(|(key, sc)| {
+                SportPipeline::from_config(
+                    key,
+                    sc,
+                    &config.strategy,
+                    &config.momentum,
+                    &config.matching,
+                    &Network::default(),
+                )
+            })
+            .collect();
+
+        let odds = Arc::new(Mutex::new(HashMap::new()));
+        let mut odds_sources: HashMap<String, Box<dyn OddsFeed>> = HashMap::new();
+        for p in &pipelines {
+            odds_sources.insert(
+                p.odds_source.clone(),
+                Box::new(ReplayOddsFeed {
+                    snapshots: odds.clone(),
+                }),
+            );
+        }
+
+        let mut report = BacktestReport::default();
+        for p in &pipelines {
+            report.sports.insert(
+                p.key.clone(),
+                SportReport {
+                    strategy: strategy_label(p, config),
+                    ..SportReport::default()
+                },
+            );
+        }
+
+        let mut state = AppState::new();
+        state.sim_mode = true;
+        state.cash_reserve_cents = config.risk.min_cash_reserve_cents;
+        state.kelly_tuner = KellyTuner::new(config.kelly_tuning.clone());
+        report.starting_balance_cents = state.sim_balance_cents;
+        let (state_tx, _) = watch::channel(state);
+
+        Self {
+            config,
+            pipelines,
+            market_index: HashMap::new(),
+            live_book: LiveBook::default(),
+            odds,
+            feeds: FeedSupervisor::new(
+                odds_sources,
+                &config.odds_sources,
+                config.adaptive_poll.clone(),
+                config.quota_budget.clone(),
+            )
+            .unthrottled(),
+            fill_simulator: FillSimulator::new(config.simulation.realism.clone()),
+            scorer: MomentumScorer::new(
+                config.momentum.velocity_weight,
+                config.momentum.book_pressure_weight,
+            ),
+            state_tx,
+            journal: Journal::disabled(),
+            api_request_times: Mutex::new(VecDeque::new()),
+            ticker_sport: HashMap::new(),
+            drawdown: DrawdownMonitor::default(),
+            report,
+        }
+    }
+
+    async fn replay(&mut self, events: Vec<RecordedEvent>) {
+        let base = Instant::now();
+        let start = events.first().map(|e| e.at);
+        self.report.events = events.len();
+        self.report.start = start;
+        self.report.end = events.last().map(|e| e.at);
+
+        for event in events {
+            let elapsed = start
+                .and_then(|s| (event.at - s).to_std().ok())
+                .unwrap_or_default();
+            let now = base + elapsed;
+            pipeline::set_simulated_now(Some(event.at));
+
+            match event.data {
+                RecordedData::Markets {
+                    sport,
+                    markets,
+                    segment,
+                } => {
+                    for m in &markets {
+                        self.ticker_sport.insert(m.ticker.clone(), sport.clone());
+                    }
+                    let segment = segment.as_deref().and_then(GameSegment::parse);
+                    crate::index_series_markets(&mut self.market_index, &sport, &markets, segment);
+                }
+                RecordedData::Book { ticker, book, .. } => {
+                    self.live_book
+                        .insert(ticker.clone(), DepthBook::from_snapshot(&book));
+                    self.try_exits(&ticker, now);
+                }
+                RecordedData::Odds { sport, updates } => {
+                    if let Ok(mut odds) = self.odds.lock() {
+                        odds.insert(sport.clone(), updates);
+                    }
+                    for p in self.pipelines.iter_mut().filter(|p| p.key == sport) {
+                        p.last_odds_poll = None;
+                    }
+                    self.tick(&sport, now).await;
+                }
+                RecordedData::Scores { sport, updates } => {
+                    for p in self.pipelines.iter_mut().filter(|p| p.key == sport) {
+                        for u in &updates {
+                            p.last_score_fetch.insert(u.game_id.clone(), now);
+                        }
+                        p.cached_scores = updates.clone();
+                    }
+                    self.tick(&sport, now).await;
+                }
+                RecordedData::Settlement { ticker, result } => {
+                    let price = if result == "yes" { 100 } else { 0 };
+                    self.close_position(&ticker, price, false, "SETTLE");
+                }
+            }
+        }
+        pipeline::set_simulated_now(None);
+
+        let s = self.state_tx.borrow();
+        for pos in &s.sim_positions {
+            let bid = self
+                .live_book
+                .read(&pos.ticker, |d| d.best_bid_ask().0)
+                .unwrap_or(0);
+            let entry_cost = (pos.quantity * pos.entry_price) as i64 + pos.entry_fee as i64;
+            let sport = self
+                .report
+                .sports
+                .entry(self.sport_of(&pos.ticker))
+                .or_default();
+            sport.open += 1;
+            sport.unrealized_cents += (pos.quantity * bid) as i64 - entry_cost;
+        }
+        self.report.final_balance_cents = s.sim_balance_cents;
+    }
+
+    /// Run one pipeline tick for `sport` at simulated time `now`.
+    async fn tick(&mut self, sport: &str, now: Instant) {
+        let before = self.state_tx.borrow().sim_entries_filled;
+        let mut closed = Vec::new();
+        for p in self
+            .pipelines
+            .iter_mut()
+            .filter(|p| p.key == sport && p.enabled)
+        {
+            // Scores come from the recording, never from the live poller
+            if p.score_poller.is_some() {
+                p.last_score_poll = Some(now);
+                p.next_aligned_score_poll = None;
+                p.force_score_refetch = false;
+            }
+            let drawdown_tier = pipeline::observe_drawdown(
+                &mut self.drawdown,
+                &self.state_tx,
+                &self.config.drawdown,
+            );
+            let bankroll_cents = {
+                let s = self.state_tx.borrow();
+                let deployable = crate::engine::risk::deployable_cents(
+                    s.sim_balance_cents,
+                    self.config.risk.min_cash_reserve_cents,
+                );
+                drawdown_tier.sizing_bankroll(deployable, &self.config.drawdown)
+            };
+            self.state_tx
+                .send_modify(|s| s.exposure = pipeline::sim_exposure(&s.sim_positions));
+            let result = p
+                .tick(
+                    now,
+                    &self.market_index,
+                    &self.live_book,
+                    &self.feeds,
+                    &self.scorer,
+                    &self.config.risk,
+                    &self.config.simulation,
+                    true,
+                    &self.state_tx,
+                    bankroll_cents,
+                    &self.api_request_times,
+                    &self.config.odds_sources,
+                    &self.config.adaptive_poll,
+                    &self.config.sprint,
+                    Some(&mut self.fill_simulator),
+                    &self.journal,
+                    None,
+                )
+                .await;
+            closed.extend(result.closed_tickers);
+        }
+        let filled = self.state_tx.borrow().sim_entries_filled - before;
+        self.report
+            .sports
+            .entry(sport.to_string())
+            .or_default()
+            .entries += filled;
+
+        // Settle positions on closed markets at last known fair value
+        for (ticker, fair, _) in closed {
+            self.close_position(&ticker, fair, false, "SETTLE");
+        }
+    }
+
+    /// Try to exit open positions on `ticker` against its current book.
+    fn try_exits(&mut self, ticker: &str, now: Instant) {
+        let yes_bid = self
+            .live_book
+            .read(ticker, |d| d.best_bid_ask().0)
+            .unwrap_or(0);
+        let max_hold = self.fill_simulator.max_hold_seconds();
+        let exits: Vec<(u32, Option<ExitReason>)> = self
+            .state_tx
+            .borrow()
+            .sim_positions
+            .iter()
+            .filter(|p| p.ticker == ticker)
+            .filter_map(|pos| {
+                let held_secs = now.saturating_duration_since(pos.filled_at).as_secs();
+                let forced = if max_hold > 0 && held_secs > max_hold {
+                    Some(ExitReason::Timeout)
+                } else {
+                    threshold_exit(pos.entry_price, yes_bid, &self.config.risk)
+                };
+                let result = if forced.is_some() {
+                    self.fill_simulator.force_taker_exit(yes_bid)
+                } else {
+                    self.fill_simulator.try_maker_exit(pos.sell_price, yes_bid)
+                };
+                match result {
+                    FillResult::Filled { price } => Some((price, forced)),
+                    _ => None,
+                }
+            })
+            .collect();
+        for (price, forced) in exits {
+            let action = forced.map_or("SELL", ExitReason::label);
+            self.close_position(ticker, price, forced.is_some(), action);
+        }
+    }
+
+    /// Close the sim position on `ticker` at `price`, booking its P&L.
+    fn close_position(&mut self, ticker: &str, price: u32, is_taker: bool, action: &str) {
+        let mut pnl = None;
+        self.state_tx.send_modify(|s| {
+            let Some(idx) = s.sim_positions.iter().position(|p| p.ticker == ticker) else {
+                return;
+            };
+            let pos = s.sim_positions.remove(idx);
+            let exit_revenue = (pos.quantity * price) as i64;
+            let exit_fee = calculate_fee(price, pos.quantity, is_taker) as i64;
+            let entry_cost = (pos.quantity * pos.entry_price) as i64 + pos.entry_fee as i64;
+            let trade_pnl = (exit_revenue - exit_fee) - entry_cost;
+
+            s.sim_balance_cents += exit_revenue - exit_fee;
+            s.realized_pnl_cents += trade_pnl;
+            s.record_realized_edge(ticker, trade_pnl);
+            s.total_trades += 1;
+            if trade_pnl > 0 {
+                s.winning_trades += 1;
+            }
+            tracing::debug!(ticker, action, price, pnl = trade_pnl, "backtest exit");
+            pnl = Some(trade_pnl);
+        });
+        let Some(pnl) = pnl else {
+            return;
+        };
+        let sport = self.report.sports.entry(self.sport_of(ticker)).or_default();
+        sport.closed += 1;
+        sport.pnl_cents += pnl;
+        if pnl > 0 {
+            sport.wins += 1;
+        }
+    }
+
+    fn sport_of(&self, ticker: &str) -> String {
+        self.ticker_sport
+            .get(ticker)
+            .cloned()
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+}
+
+fn strategy_label(p: &SportPipeline, config: &Config) -> String {
+    let fair_value = p.fair_value_name();
+    let sc = &p.strategy_config;
+    format!(
+        "{} taker {}c maker {}c min {}c kelly {:.2}",
+        fair_value,
+        sc.taker_edge_threshold,
+        sc.maker_edge_threshold,
+        sc.min_edge_after_fees,
+        config.risk.kelly_fraction
+    )
+}
+
+/// Replay the recording in `dir` and print the per-sport P&L report.
+pub async fn run(config: &Config, dir: &Path) -> Result<BacktestReport> {
+    let fmt = NumberFormat::from_config(&config.locale)?;
+    let events = load_events(dir)?;
+    if events.is_empty() {
+        anyhow::bail!("no recorded events in {}", dir.display());
+    }
+    println!(
+        "  Replaying {} recorded events from {}",
+        fmt.integer(events.len() as i64),
+        dir.display()
+    );
+
+    let mut backtest = Backtest::new(config);
+    backtest
+        .state_tx
+        .send_modify(|s| s.number_format = fmt.clone());
+    backtest.replay(events).await;
+    let report = backtest.report;
+
+    println!();
+    if let (Some(start), Some(end)) = (report.start, report.end) {
+        println!(
+            "  {} -> {} ({} events)",
+            start.format("%Y-%m-%d %H:%M"),
+            end.format("%Y-%m-%d %H:%M UTC"),
+            fmt.integer(report.events as i64)
+        );
+    }
+    println!(
+        "  {:<28} {:<48} {:>7} {:>6} {:>5} {:>10} {:>5} {:>10}",
+        "Sport", "Strategy", "Entries", "Closed", "Win%", "P&L", "Open", "Unreal."
+    );
+    for (sport, r) in &report.sports {
+        if r.entries == 0 && r.closed == 0 && r.open == 0 {
+            continue;
+        }
+        let win_pct = (r.wins * 100).checked_div(r.closed).unwrap_or(0);
+        println!(
+            "  {:<28} {:<48} {:>7} {:>6} {:>4}% {:>10} {:>5} {:>10}",
+            sport,
+            r.strategy,
+            r.entries,
+            r.closed,
+            win_pct,
+            fmt.signed_money(r.pnl_cents),
+            r.open,
+            fmt.signed_money(r.unrealized_cents)
+        );
+    }
+    println!();
+    println!(
+        "  Balance: {} -> {} ({})",
+        fmt.money(report.starting_balance_cents),
+        fmt.money(report.final_balance_cents),
+        fmt.signed_money(report.final_balance_cents - report.starting_balance_cents)
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_events_merges_files_in_time_order() {
+        let dir = std::env::temp_dir().join(format!("backtest-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("books.jsonl"),
+            concat!(
+                r#"{"at":"2026-10-18T01:00:02Z","kind":"book","ticker":"T-1","book":{"yes":[[52,10]],"no":[[46,5]]}}"#,
+                "\n\n",
+                r#"{"at":"2026-10-18T01:00:00Z","kind":"settlement","ticker":"T-1","result":"yes"}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("scores.jsonl"),
+            r#"{"at":"2026-10-18T01:00:01Z","kind":"scores","sport":"basketball","updates":[{"game_id":"1","home_team":"Boston","away_team":"Los Angeles","home_score":50,"away_score":45,"period":3,"clock_seconds":300,"total_elapsed_seconds":2100,"game_status":"Live","source":"Espn"}]}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let events = load_events(&dir).unwrap();
+        let kinds: Vec<&str> = events
+            .iter()
+            .map(|e| match &e.data {
+                RecordedData::Settlement { .. } => "settlement",
+                RecordedData::Scores { .. } => "scores",
+                RecordedData::Book { .. } => "book",
+                _ => "other",
+            })
+            .collect();
+        assert_eq!(kinds, ["settlement", "scores", "book"]);
+        if let RecordedData::Book { book, .. } = &events[2].data {
+            assert_eq!(book.yes, vec![(52, 10)]);
+        }
+
+        std::fs::write(dir.join("bad.jsonl"), "{not json").unwrap();
+        assert!(load_events(&dir).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
+
+/// Replays the bundled capture in `tests/fixtures/backtest` end to end and
+/// holds the aggregate result to `expected.toml`, so a pipeline refactor that
+/// changes what gets traded fails here instead of in the P&L.
+///
+/// `cargo test --features backtest-regression regression`
+#[cfg(all(test, feature = "backtest-regression"))]
+mod regression {
+    use super::*;
+
+    const FIXTURE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/backtest");
+
+    #[derive(Debug, Deserialize)]
+    struct Expected {
+        final_balance_cents: i64,
+        /// Slack on P&L and balance; counts must match exactly.
+        pnl_tolerance_cents: i64,
+        sports: BTreeMap<String, ExpectedSport>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct ExpectedSport {
+        entries: u32,
+        closed: u32,
+        wins: u32,
+        open: u32,
+        pnl_cents: i64,
+    }
+
+    #[tokio::test]
+    async fn test_bundled_capture_matches_baseline() {
+        let dir = Path::new(FIXTURE_DIR);
+        let config = Config::load(&dir.join("config.toml")).unwrap();
+        let expected: Expected =
+            toml::from_str(&std::fs::read_to_string(dir.join("expected.toml")).unwrap()).unwrap();
+
+        let report = run(&config, &dir.join("capture")).await.unwrap();
+
+        for (sport, want) in &expected.sports {
+            let got = report.sports.get(sport).cloned().unwrap_or_default();
+            let counts = (got.entries, got.closed, got.wins, got.open);
+            assert_eq!(
+                counts,
+                (want.entries, want.closed, want.wins, want.open),
+                "{sport}: (entries, closed, wins, open) moved from the baseline"
+            );
+            assert!(
+                (got.pnl_cents - want.pnl_cents).abs() <= expected.pnl_tolerance_cents,
+                "{sport}: P&L {} outside {}±{}",
+                got.pnl_cents,
+                want.pnl_cents,
+                expected.pnl_tolerance_cents
+            );
+        }
+        for (sport, got) in &report.sports {
+            assert!(
+                expected.sports.contains_key(sport) || got.entries == 0,
+                "{sport}: traded but has no baseline"
+            );
+        }
+        assert!(
+            (report.final_balance_cents - expected.final_balance_cents).abs()
+                <= expected.pnl_tolerance_cents,
+            "final balance {} outside {}±{}",
+            report.final_balance_cents,
+            expected.final_balance_cents,
+            expected.pnl_tolerance_cents
+        );
+    }
+}
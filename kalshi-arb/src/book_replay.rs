@@ -0,0 +1,173 @@
+//! Per-ticker orderbook recording and frame-by-frame replay.
+//!
+//! When recording is toggled on for a ticker, every WS snapshot and delta for
+//! it is captured together with the full book after the update. The replay
+//! view steps through these frames to diagnose suspicious fills and queue
+//! position estimates.
+
+use crate::journal::BookSnapshot;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Frames kept per ticker; the oldest are dropped once full.
+const MAX_FRAMES: usize = 10_000;
+
+/// The book immediately after one WS update.
+#[derive(Debug, Clone)]
+pub struct BookFrame {
+    pub at: chrono::DateTime<chrono::Local>,
+    /// What changed: "SNAPSHOT" or e.g. "YES 45c +10".
+    pub event: String,
+    pub book: BookSnapshot,
+}
+
+#[derive(Debug, Default)]
+struct Recording {
+    active: bool,
+    frames: VecDeque<BookFrame>,
+}
+
+/// Recordings keyed by ticker. Stopped recordings stay available for replay.
+#[derive(Debug, Default)]
+pub struct BookRecorder {
+    recordings: HashMap<String, Recording>,
+}
+
+pub type SharedBookRecorder = Arc<Mutex<BookRecorder>>;
+
+impl BookRecorder {
+    /// Start or stop recording `ticker`. Starting discards any earlier frames.
+    /// Returns whether the ticker is now being recorded.
+    pub fn toggle(&mut self, ticker: &str) -> bool {
+        let rec = self.recordings.entry(ticker.to_string()).or_default();
+        rec.active = !rec.active;
+        if rec.active {
+            rec.frames.clear();
+        }
+        rec.active
+    }
+
+    /// Tickers currently being recorded, sorted.
+    pub fn active_tickers(&self) -> Vec<String> {
+        let mut tickers: Vec<String> = self
+            .recordings
+            .iter()
+            .filter(|(_, r)| r.active)
+            .map(|(t, _)| t.clone())
+            .collect();
+        tickers.sort();
+        tickers
+    }
+
+    /// Whether `ticker` is being recorded right now.
+    pub fn is_recording(&self, ticker: &str) -> bool {
+        self.recordings.get(ticker).is_some_and(|r| r.active)
+    }
+
+    /// Capture a frame if `ticker` is being recorded. `book` is only called
+    /// when a frame is actually stored.
+    pub fn record(&mut self, ticker: &str, event: String, book: impl FnOnce() -> BookSnapshot) {
+        let Some(rec) = self.recordings.get_mut(ticker).filter(|r| r.active) else {
+            return;
+        };
+        if rec.frames.len() >= MAX_FRAMES {
+            rec.frames.pop_front();
+        }
+        rec.frames.push_back(BookFrame {
+            at: chrono::Local::now(),
+            event,
+            book: book(),
+        });
+    }
+
+    /// Copy of the recorded frames for `ticker`, if any were captured.
+    pub fn replay(&self, ticker: &str) -> Option<BookReplay> {
+        let rec = self.recordings.get(ticker)?;
+        if rec.frames.is_empty() {
+            return None;
+        }
+        Some(BookReplay {
+            ticker: ticker.to_string(),
+            frames: rec.frames.iter().cloned().collect(),
+        })
+    }
+}
+
+/// Frames handed to the TUI replay view.
+#[derive(Debug, Clone)]
+pub struct BookReplay {
+    pub ticker: String,
+    pub frames: Vec<BookFrame>,
+}
+
+impl BookReplay {
+    /// Quantity change at `price` on `side` ("yes"/"no") from the previous
+    /// frame to frame `idx`; zero for the first frame.
+    pub fn level_change(&self, idx: usize, side: &str, price: u32) -> i64 {
+        let qty = |frame: &BookFrame| {
+            let levels = if side == "yes" {
+                &frame.book.yes
+            } else {
+                &frame.book.no
+            };
+            levels
+                .iter()
+                .find(|(p, _)| *p == price)
+                .map_or(0, |(_, q)| *q)
+        };
+        match (idx.checked_sub(1), self.frames.get(idx)) {
+            (Some(prev), Some(cur)) => qty(cur) - qty(&self.frames[prev]),
+            _ => 0,
+        }
+    }
+}
+
+/// Label for a delta frame, e.g. "YES 45c +10".
+pub fn delta_event(side: &str, price: u32, delta: i64) -> String {
+    format!("{} {}c {:+}", side.to_uppercase(), price, delta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book(yes: Vec<(u32, i64)>) -> BookSnapshot {
+        BookSnapshot { yes, no: vec![] }
+    }
+
+    #[test]
+    fn test_records_only_active_tickers() {
+        let mut rec = BookRecorder::default();
+        rec.record("A", "SNAPSHOT".into(), || book(vec![(45, 10)]));
+        assert!(rec.replay("A").is_none());
+
+        assert!(rec.toggle("A"));
+        rec.record("A", "SNAPSHOT".into(), || book(vec![(45, 10)]));
+        rec.record("B", "SNAPSHOT".into(), || unreachable!());
+        assert_eq!(rec.replay("A").unwrap().frames.len(), 1);
+        assert_eq!(rec.active_tickers(), vec!["A".to_string()]);
+
+        // Stopping keeps frames for replay; restarting clears them.
+        assert!(!rec.toggle("A"));
+        rec.record("A", delta_event("yes", 45, 5), || book(vec![(45, 15)]));
+        assert_eq!(rec.replay("A").unwrap().frames.len(), 1);
+        assert!(rec.toggle("A"));
+        assert!(rec.replay("A").is_none());
+    }
+
+    #[test]
+    fn test_level_change_between_frames() {
+        let mut rec = BookRecorder::default();
+        rec.toggle("A");
+        rec.record("A", "SNAPSHOT".into(), || book(vec![(45, 10)]));
+        rec.record("A", delta_event("yes", 45, -4), || book(vec![(45, 6)]));
+        rec.record("A", delta_event("yes", 46, 3), || {
+            book(vec![(46, 3), (45, 6)])
+        });
+        let replay = rec.replay("A").unwrap();
+        assert_eq!(replay.level_change(0, "yes", 45), 0);
+        assert_eq!(replay.level_change(1, "yes", 45), -4);
+        assert_eq!(replay.level_change(2, "yes", 46), 3);
+        assert_eq!(replay.frames[1].event, "YES 45c -4");
+    }
+}
@@ -0,0 +1,480 @@
+//! Win-prob calibration from the journal.
+//!
+//! `--calibrate` fits each sport's regulation curve (`home_advantage`,
+//! `k_start`, `k_range`) to the game states its score-priced signals saw,
+//! labelled with how the game ended from the journal's settlement entries
+//! (`--load-settlements`). The fit is a logistic regression on the same
+//! curve the live model uses, `P(home) = 1 / (1 + exp(-k(t) * (diff + h)))`,
+//! solved by Fisher scoring. Fitted parameters are written back to the
+//! sport's `[win_prob]` section.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+use crate::config::{Config, WinProbConfig};
+use crate::engine::matcher;
+use crate::engine::win_prob::WinProbTable;
+use crate::journal::Journal;
+
+/// Settled games a sport needs before its parameters are refit.
+const MIN_GAMES: usize = 20;
+
+const MAX_ITERATIONS: usize = 100;
+
+/// Keeps the information matrix invertible when the data barely moves one
+/// parameter (e.g. every signal came late in games).
+const RIDGE: f64 = 1e-6;
+
+/// One regulation game state and how the game ended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Observation {
+    /// Home score minus away score.
+    pub score_diff: i32,
+    pub elapsed_secs: u16,
+    pub home_won: bool,
+}
+
+/// The regulation curve parameters being fit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Params {
+    pub home_advantage: f64,
+    pub k_start: f64,
+    pub k_range: f64,
+}
+
+impl Params {
+    pub fn from_config(config: &WinProbConfig) -> Self {
+        Self {
+            home_advantage: config.home_advantage,
+            k_start: config.k_start,
+            k_range: config.k_range,
+        }
+    }
+
+    /// Home win probability, bucketed like `WinProbTable::lookup`.
+    fn prob(&self, obs: &Observation, regulation_secs: u16) -> f64 {
+        let (k, x, _) = self.curve(obs, regulation_secs);
+        1.0 / (1.0 + (-k * x).exp())
+    }
+
+    /// (k, adjusted diff, cubic time ramp) at `obs`.
+    fn curve(&self, obs: &Observation, regulation_secs: u16) -> (f64, f64, f64) {
+        let regulation_buckets = regulation_secs as f64 / 30.0;
+        let bucket = ((obs.elapsed_secs / 30) as f64).min(regulation_buckets);
+        let ramp = (bucket / regulation_buckets).powi(3);
+        let k = self.k_start + ramp * self.k_range;
+        let x = obs.score_diff.clamp(-40, 40) as f64 + self.home_advantage;
+        (k, x, ramp)
+    }
+
+    fn is_valid(&self) -> bool {
+        self.k_start > 0.0 && self.k_range >= 0.0 && self.home_advantage.is_finite()
+    }
+}
+
+/// Mean negative log-likelihood of the outcomes under `params`.
+pub fn log_loss(params: &Params, observations: &[Observation], regulation_secs: u16) -> f64 {
+    let total: f64 = observations
+        .iter()
+        .map(|obs| {
+            let p = params
+                .prob(obs, regulation_secs)
+                .clamp(f64::EPSILON, 1.0 - f64::EPSILON);
+            if obs.home_won {
+                -p.ln()
+            } else {
+                -(1.0 - p).ln()
+            }
+        })
+        .sum();
+    total / observations.len().max(1) as f64
+}
+
+/// Maximum-likelihood parameters, starting from `start`. Steps that would
+/// make k negative are cut back, so the result is always a usable curve.
+pub fn fit(observations: &[Observation], start: Params, regulation_secs: u16) -> Params {
+    let mut params = start;
+    let mut loss = log_loss(&params, observations, regulation_secs);
+    for _ in 0..MAX_ITERATIONS {
+        let mut grad = [0.0; 3];
+        let mut info = [[0.0; 3]; 3];
+        for obs in observations {
+            let (k, x, ramp) = params.curve(obs, regulation_secs);
+            let p = 1.0 / (1.0 + (-k * x).exp());
+            // d(kx)/d(k_start, k_range, home_advantage)
+            let g = [x, ramp * x, k];
+            let residual = if obs.home_won { 1.0 - p } else { -p };
+            let weight = p * (1.0 - p);
+            for i in 0..3 {
+                grad[i] += residual * g[i];
+                for j in 0..3 {
+                    info[i][j] += weight * g[i] * g[j];
+                }
+            }
+        }
+        for (i, row) in info.iter_mut().enumerate() {
+            row[i] += RIDGE;
+        }
+        let Some(step) = solve3(&info, &grad) else {
+            break;
+        };
+
+        // Halve the step until it improves the fit
+        let mut scale = 1.0;
+        let next = loop {
+            let candidate = Params {
+                k_start: params.k_start + scale * step[0],
+                k_range: params.k_range + scale * step[1],
+                home_advantage: params.home_advantage + scale * step[2],
+            };
+            if candidate.is_valid() {
+                let candidate_loss = log_loss(&candidate, observations, regulation_secs);
+                if candidate_loss <= loss {
+                    break Some((candidate, candidate_loss));
+                }
+            }
+            scale /= 2.0;
+            if scale < 1e-6 {
+                break None;
+            }
+        };
+        let Some((next, next_loss)) = next else {
+            break;
+        };
+        let converged = loss - next_loss < 1e-12;
+        params = next;
+        loss = next_loss;
+        if converged {
+            break;
+        }
+    }
+    params
+}
+
+/// Solve `a * x = b` for a 3x3 system by Cramer's rule.
+fn solve3(a: &[[f64; 3]; 3], b: &[f64; 3]) -> Option<[f64; 3]> {
+    let det = |m: &[[f64; 3]; 3]| {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    };
+    let d = det(a);
+    if d.abs() < 1e-300 || !d.is_finite() {
+        return None;
+    }
+    let mut x = [0.0; 3];
+    for (col, xi) in x.iter_mut().enumerate() {
+        let mut m = *a;
+        for row in 0..3 {
+            m[row][col] = b[row];
+        }
+        *xi = det(&m) / d;
+    }
+    Some(x)
+}
+
+/// Settled outcome per ticker from settlement entries: true if YES paid.
+pub fn settled_outcomes(settlements: &[Value]) -> HashMap<String, bool> {
+    settlements
+        .iter()
+        .filter_map(|s| {
+            let ticker = s["ticker"].as_str()?;
+            let value = s["settlement_value"].as_u64()?;
+            Some((ticker.to_string(), value >= 50))
+        })
+        .collect()
+}
+
+/// Regulation game states from `sport`'s score-priced signals on its
+/// `series` winner markets, each labelled with the settled outcome, keyed by
+/// Kalshi event. A game state seen by several signals counts once.
+pub fn observations(
+    sport: &str,
+    series: &str,
+    table: &WinProbTable,
+    regulation_secs: u16,
+    signals: &[Value],
+    outcomes: &HashMap<String, bool>,
+) -> Vec<(String, Observation)> {
+    let prefix = format!("{}-", series);
+    let mut seen = HashSet::new();
+    let mut observations = Vec::new();
+    for signal in signals {
+        let trace = &signal["trace"];
+        if trace["sport"].as_str() != Some(sport) {
+            continue;
+        }
+        let Some(ticker) = signal["ticker"].as_str() else {
+            continue;
+        };
+        if !ticker.starts_with(&prefix) {
+            continue;
+        }
+        let Some(&yes) = outcomes.get(ticker) else {
+            continue;
+        };
+        let Some(is_away) = matcher::is_away_market(ticker, "", "") else {
+            continue;
+        };
+        let inputs = &trace["inputs"];
+        let Some(state) = inputs.get("score").or_else(|| inputs.get("blended")) else {
+            continue;
+        };
+        let (Some(home), Some(away), Some(elapsed)) = (
+            state["home_score"].as_i64(),
+            state["away_score"].as_i64(),
+            state["elapsed_secs"].as_u64(),
+        ) else {
+            continue;
+        };
+        let in_regulation = state["period"]
+            .as_str()
+            .and_then(|p| p.parse::<u8>().ok())
+            .is_some_and(|p| p >= 1 && p <= table.regulation_periods());
+        if !in_regulation || elapsed >= regulation_secs as u64 {
+            continue;
+        }
+        let event = ticker.rsplit_once('-').map_or(ticker, |(e, _)| e);
+        let obs = Observation {
+            score_diff: (home - away) as i32,
+            elapsed_secs: elapsed as u16,
+            home_won: yes != is_away,
+        };
+        if seen.insert((event.to_string(), obs.score_diff, obs.elapsed_secs)) {
+            observations.push((event.to_string(), obs));
+        }
+    }
+    observations
+}
+
+/// Write fitted parameters into `[sports.<key>.win_prob]` of the config file.
+fn write_params(config_path: &Path, sport_key: &str, params: &Params) -> Result<()> {
+    let content = std::fs::read_to_string(config_path)
+        .with_context(|| format!("failed to read {}", config_path.display()))?;
+    let mut doc: toml::Table = toml::from_str(&content).context("failed to parse config TOML")?;
+    let win_prob = ["sports", sport_key, "win_prob"]
+        .iter()
+        .try_fold(&mut doc, |table, key| {
+            table
+                .entry(key.to_string())
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+                .as_table_mut()
+        })
+        .with_context(|| format!("sports.{}.win_prob is not a table", sport_key))?;
+    let round = |v: f64, places: i32| {
+        let scale = 10f64.powi(places);
+        (v * scale).round() / scale
+    };
+    for (key, value) in [
+        ("home_advantage", round(params.home_advantage, 2)),
+        ("k_start", round(params.k_start, 4)),
+        ("k_range", round(params.k_range, 4)),
+    ] {
+        win_prob.insert(key.to_string(), toml::Value::Float(value));
+    }
+    std::fs::write(config_path, toml::to_string_pretty(&doc)?)
+        .with_context(|| format!("failed to write {}", config_path.display()))
+}
+
+/// Fit every score-modelled sport with enough settled games and save the
+/// results to `config_path`.
+pub fn run(config: &Config, config_path: &Path) -> Result<()> {
+    let journal = Journal::open(Path::new(&config.journal.path))?;
+    let outcomes = settled_outcomes(&journal.entries("settlement")?);
+    let mut signals = journal.entries("order")?;
+    signals.extend(journal.entries("reject")?);
+    println!(
+        "  {} settled markets, {} signals in {}",
+        outcomes.len(),
+        signals.len(),
+        config.journal.path
+    );
+    println!();
+
+    let mut keys: Vec<&String> = config.sports.keys().collect();
+    keys.sort();
+    let mut updated = 0;
+    for key in keys {
+        let sport = &config.sports[key];
+        let Some(wp) = sport
+            .win_prob
+            .as_ref()
+            .filter(|_| sport.soccer_model.is_none())
+        else {
+            continue;
+        };
+        let regulation_secs = wp.regulation_secs.unwrap_or(2880);
+        let table = WinProbTable::from_config(wp);
+        let labelled = observations(
+            key,
+            &sport.kalshi_series,
+            &table,
+            regulation_secs,
+            &signals,
+            &outcomes,
+        );
+        let games: HashSet<&str> = labelled.iter().map(|(e, _)| e.as_str()).collect();
+        if games.len() < MIN_GAMES {
+            println!(
+                "  {:<20} skipped: {} settled games (need {})",
+                key,
+                games.len(),
+                MIN_GAMES
+            );
+            continue;
+        }
+        let observations: Vec<Observation> = labelled.iter().map(|(_, o)| *o).collect();
+        let before = Params::from_config(wp);
+        let after = fit(&observations, before, regulation_secs);
+        println!(
+            "  {:<20} {} games, {} states: log loss {:.4} -> {:.4}",
+            key,
+            games.len(),
+            observations.len(),
+            log_loss(&before, &observations, regulation_secs),
+            log_loss(&after, &observations, regulation_secs)
+        );
+        println!(
+            "  {:<20} home_advantage {:.2} -> {:.2}, k_start {:.4} -> {:.4}, k_range {:.4} -> {:.4}",
+            "",
+            before.home_advantage,
+            after.home_advantage,
+            before.k_start,
+            after.k_start,
+            before.k_range,
+            after.k_range
+        );
+        write_params(config_path, key, &after)?;
+        updated += 1;
+    }
+    println!();
+    println!(
+        "  Updated [win_prob] for {} sport(s) in {}",
+        updated,
+        config_path.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Outcomes in every (diff, time) cell in the proportions `truth` gives.
+    fn synthetic(truth: &Params, regulation_secs: u16) -> Vec<Observation> {
+        let mut observations = Vec::new();
+        for score_diff in (-15..=15).step_by(3) {
+            for elapsed_secs in (0..regulation_secs).step_by(240) {
+                let cell = Observation {
+                    score_diff,
+                    elapsed_secs,
+                    home_won: false,
+                };
+                let wins = (truth.prob(&cell, regulation_secs) * 40.0).round() as usize;
+                for i in 0..40 {
+                    observations.push(Observation {
+                        home_won: i < wins,
+                        ..cell
+                    });
+                }
+            }
+        }
+        observations
+    }
+
+    #[test]
+    fn test_fit_recovers_generating_parameters() {
+        let truth = Params {
+            home_advantage: 3.5,
+            k_start: 0.08,
+            k_range: 0.2,
+        };
+        let observations = synthetic(&truth, 2880);
+        let start = Params::from_config(&WinProbConfig::default());
+        let fitted = fit(&observations, start, 2880);
+        assert!((fitted.home_advantage - 3.5).abs() < 0.3, "{:?}", fitted);
+        assert!((fitted.k_start - 0.08).abs() < 0.01, "{:?}", fitted);
+        assert!((fitted.k_range - 0.2).abs() < 0.05, "{:?}", fitted);
+        assert!(log_loss(&fitted, &observations, 2880) < log_loss(&start, &observations, 2880));
+    }
+
+    #[test]
+    fn test_observations_label_home_outcome_from_settlements() {
+        let signal =
+            |kind: &str, ticker: &str, home: u32, away: u32, elapsed: u32, period: &str| {
+                json!({
+                    "kind": kind,
+                    "ticker": ticker,
+                    "trace": {
+                        "sport": "basketball",
+                        "inputs": { "score": {
+                            "home_score": home,
+                            "away_score": away,
+                            "elapsed_secs": elapsed,
+                            "period": period,
+                            "phase": "Q3",
+                            "win_prob": 0.6,
+                        }},
+                    },
+                })
+            };
+        let signals = vec![
+            // Away market that settled NO: the home team won
+            signal("order", "KXNBAGAME-26MAR10LALBOS-LAL", 60, 50, 1800, "3"),
+            // Same state seen on the home market: counted once
+            signal("reject", "KXNBAGAME-26MAR10LALBOS-BOS", 60, 50, 1800, "3"),
+            // Overtime and segment markets are not regulation game states
+            signal("order", "KXNBAGAME-26MAR10LALBOS-BOS", 100, 98, 3000, "5"),
+            signal("order", "KXNBA1HWINNER-26MAR10LALBOS-BOS", 10, 8, 600, "1H"),
+            // Unsettled game
+            signal("order", "KXNBAGAME-26MAR12NYKMIA-MIA", 40, 45, 1200, "2"),
+        ];
+        let outcomes = settled_outcomes(&[
+            json!({"ticker": "KXNBAGAME-26MAR10LALBOS-LAL", "settlement_value": 0}),
+            json!({"ticker": "KXNBAGAME-26MAR10LALBOS-BOS", "settlement_value": 100}),
+        ]);
+        let table = WinProbTable::from_config(&WinProbConfig::default());
+        let observations =
+            observations("basketball", "KXNBAGAME", &table, 2880, &signals, &outcomes);
+        assert_eq!(
+            observations,
+            vec![(
+                "KXNBAGAME-26MAR10LALBOS".to_string(),
+                Observation {
+                    score_diff: 10,
+                    elapsed_secs: 1800,
+                    home_won: true,
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn test_write_params_updates_sport_win_prob() {
+        let dir = std::env::temp_dir().join(format!("calibrate-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        std::fs::write(
+            &path,
+            "[sports.basketball.win_prob]\nhome_advantage = 3\nk_start = 0.065\nregulation_secs = 2880\n",
+        )
+        .unwrap();
+        let params = Params {
+            home_advantage: 2.8734,
+            k_start: 0.071234,
+            k_range: 0.26,
+        };
+        write_params(&path, "basketball", &params).unwrap();
+        let doc: toml::Table = toml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let wp = &doc["sports"]["basketball"]["win_prob"];
+        assert_eq!(wp["home_advantage"].as_float(), Some(2.87));
+        assert_eq!(wp["k_start"].as_float(), Some(0.0712));
+        assert_eq!(wp["k_range"].as_float(), Some(0.26));
+        assert_eq!(wp["regulation_secs"].as_integer(), Some(2880));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
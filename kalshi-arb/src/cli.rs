@@ -0,0 +1,326 @@
+//! Command-line interface.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::config::Config;
+use crate::headless::Sink;
+use crate::history::{self, DownloadArgs};
+use crate::settlements;
+
+#[derive(Debug, Parser)]
+#[command(name = "kalshi-arb", version, about = "Kalshi sports arbitrage engine")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Trade against a virtual balance instead of placing live orders.
+    #[arg(long)]
+    pub simulate: bool,
+
+    /// Run without the TUI and stream JSON events to stdout, or to a unix
+    /// socket with --headless=SOCKET.
+    #[arg(long, value_name = "SOCKET", require_equals = true)]
+    pub headless: Option<Option<PathBuf>>,
+
+    /// Config file to load; config view edits are saved back to it.
+    #[arg(long, value_name = "PATH", default_value = "config.toml")]
+    pub config: PathBuf,
+
+    /// Named profile merged over the config ([profiles.NAME] or profiles/NAME.toml).
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Only run these sports this session, e.g. --sports basketball,ice-hockey.
+    #[arg(long, value_name = "KEYS", value_delimiter = ',')]
+    pub sports: Vec<String>,
+
+    /// Capture feeds for later backtesting.
+    #[arg(long)]
+    pub record: bool,
+
+    /// Replay a recorded capture directory offline and exit.
+    #[arg(long, value_name = "DIR")]
+    pub backtest: Option<PathBuf>,
+
+    /// Fit each sport's [win_prob] curve to settled games in the journal,
+    /// save it to the config and exit.
+    #[arg(long)]
+    pub calibrate: bool,
+
+    /// Replay a day's session from a journal into the TUI, without credentials.
+    #[arg(long, value_name = "JOURNAL", conflicts_with = "backtest")]
+    pub replay: Option<PathBuf>,
+
+    /// Playback speed for --replay, as a multiple of real time.
+    #[arg(long, value_name = "X", default_value_t = 10.0, requires = "replay")]
+    pub replay_speed: f64,
+
+    /// Day to replay (local, YYYY-MM-DD); defaults to the journal's latest.
+    #[arg(long, value_name = "DATE", value_parser = parse_date, requires = "replay")]
+    pub replay_date: Option<NaiveDate>,
+
+    /// Download settled markets for the last MONTHS months into the journal and exit.
+    #[arg(
+        long,
+        value_name = "MONTHS",
+        require_equals = true,
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub load_settlements: Option<Option<u32>>,
+
+    /// Trade sports outside their configured season.
+    #[arg(long)]
+    pub ignore_season: bool,
+
+    /// Most verbose level written to kalshi-arb.log.
+    #[arg(long, value_enum, default_value_t = LogLevel::Warn)]
+    pub log_level: LogLevel,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Check every integration without starting the engine.
+    Doctor,
+    /// Assemble a backtest capture from historical data.
+    Download(DownloadCommand),
+}
+
+#[derive(Debug, clap::Args)]
+pub struct DownloadCommand {
+    /// First game day (YYYY-MM-DD).
+    #[arg(long, value_parser = parse_date)]
+    pub from: NaiveDate,
+    /// Last game day (YYYY-MM-DD); defaults to --from.
+    #[arg(long, value_parser = parse_date)]
+    pub to: Option<NaiveDate>,
+    /// Sport keys; every enabled sport when absent.
+    #[arg(long, value_delimiter = ',')]
+    pub sports: Vec<String>,
+    /// Capture directory; named after the dates when absent.
+    #[arg(long, value_name = "DIR")]
+    pub out: Option<PathBuf>,
+    /// Minutes between historical odds snapshots (10 credits each).
+    #[arg(
+        long,
+        value_name = "MINUTES",
+        default_value_t = history::DEFAULT_ODDS_EVERY_MINS,
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub odds_every: u32,
+}
+
+impl DownloadCommand {
+    pub fn args(&self) -> Result<DownloadArgs> {
+        let to = self.to.unwrap_or(self.from);
+        anyhow::ensure!(
+            to >= self.from,
+            "--to {} is before --from {}",
+            to,
+            self.from
+        );
+        Ok(DownloadArgs {
+            from: self.from,
+            to,
+            sports: self.sports.clone(),
+            out: self.out.clone(),
+            odds_every_mins: self.odds_every,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    /// `EnvFilter` directive for the crate's own spans and events.
+    pub fn filter(self) -> &'static str {
+        match self {
+            LogLevel::Error => "kalshi_arb=error",
+            LogLevel::Warn => "kalshi_arb=warn",
+            LogLevel::Info => "kalshi_arb=info",
+            LogLevel::Debug => "kalshi_arb=debug",
+            LogLevel::Trace => "kalshi_arb=trace",
+        }
+    }
+}
+
+impl Cli {
+    pub fn headless_sink(&self) -> Option<Sink> {
+        self.headless
+            .as_ref()
+            .map(|socket| socket.clone().map_or(Sink::Stdout, Sink::Socket))
+    }
+
+    pub fn load_settlements_months(&self) -> Option<u32> {
+        self.load_settlements
+            .map(|months| months.unwrap_or(settlements::DEFAULT_MONTHS))
+    }
+
+    /// Enable exactly the `--sports` keys for this session. Nothing is
+    /// written back to the config file.
+    pub fn apply_sports(&self, config: &mut Config) -> Result<()> {
+        if self.sports.is_empty() {
+            return Ok(());
+        }
+        for key in &self.sports {
+            if !config.sports.contains_key(key) {
+                let mut known: Vec<&str> = config.sports.keys().map(String::as_str).collect();
+                known.sort_unstable();
+                anyhow::bail!(
+                    "unknown sport {:?} in --sports (known: {})",
+                    key,
+                    known.join(", ")
+                );
+            }
+        }
+        for (key, sport) in config.sports.iter_mut() {
+            sport.enabled = self.sports.contains(key);
+        }
+        Ok(())
+    }
+}
+
+fn parse_date(s: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map_err(|_| format!("invalid date {:?} (expected YYYY-MM-DD)", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    fn parse(cmd: &str) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("kalshi-arb").chain(cmd.split_whitespace()))
+    }
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_flags() {
+        let cli = parse("").unwrap();
+        assert!(!cli.simulate && !cli.record && !cli.calibrate && cli.command.is_none());
+        assert_eq!(cli.config, PathBuf::from("config.toml"));
+        assert_eq!(cli.headless_sink(), None);
+        assert_eq!(cli.load_settlements_months(), None);
+        assert_eq!(cli.log_level, LogLevel::Warn);
+
+        let cli = parse(
+            "--simulate --headless --config alt.toml --profile paper --sports basketball,ice-hockey \
+             --record --log-level debug --ignore-season",
+        )
+        .unwrap();
+        assert!(cli.simulate && cli.record && cli.ignore_season);
+        assert_eq!(cli.headless_sink(), Some(Sink::Stdout));
+        assert_eq!(cli.config, PathBuf::from("alt.toml"));
+        assert_eq!(cli.profile.as_deref(), Some("paper"));
+        assert_eq!(cli.sports, vec!["basketball", "ice-hockey"]);
+        assert_eq!(cli.log_level.filter(), "kalshi_arb=debug");
+
+        let cli = parse("--headless=/tmp/arb.sock --backtest rec").unwrap();
+        assert_eq!(
+            cli.headless_sink(),
+            Some(Sink::Socket(PathBuf::from("/tmp/arb.sock")))
+        );
+        assert_eq!(cli.backtest, Some(PathBuf::from("rec")));
+        assert!(parse("--calibrate").unwrap().calibrate);
+        let cli = parse("--replay journal.db --replay-speed 2.5 --replay-date 2026-10-17").unwrap();
+        assert_eq!(cli.replay, Some(PathBuf::from("journal.db")));
+        assert_eq!(cli.replay_speed, 2.5);
+        assert_eq!(cli.replay_date, NaiveDate::from_ymd_opt(2026, 10, 17));
+        assert_eq!(parse("--replay j.db").unwrap().replay_speed, 10.0);
+        assert!(parse("--replay-speed 2").is_err());
+        assert!(parse("--replay j.db --replay-date 10/17").is_err());
+        assert!(parse("--replay j.db --backtest rec").is_err());
+
+        assert!(parse("--backtest").is_err());
+        assert!(parse("--backtest --simulate").is_err());
+        assert!(parse("--headless=").is_err());
+        assert!(parse("--profile").is_err());
+        assert!(parse("--log-level loud").is_err());
+        assert!(parse("--bogus").is_err());
+    }
+
+    #[test]
+    fn test_load_settlements_months() {
+        let months = |cmd| parse(cmd).unwrap().load_settlements_months();
+        assert_eq!(
+            months("--load-settlements"),
+            Some(settlements::DEFAULT_MONTHS)
+        );
+        assert_eq!(months("--load-settlements=6"), Some(6));
+        assert!(parse("--load-settlements=0").is_err());
+    }
+
+    #[test]
+    fn test_download() {
+        let download = |cmd| match parse(cmd).unwrap().command {
+            Some(Command::Download(d)) => d.args(),
+            other => panic!("expected download, got {:?}", other),
+        };
+        let parsed = download(
+            "download --from 2026-03-01 --to 2026-03-07 --sports basketball,college-basketball \
+             --out caps --odds-every 30",
+        )
+        .unwrap();
+        assert_eq!(
+            parsed,
+            DownloadArgs {
+                from: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+                to: NaiveDate::from_ymd_opt(2026, 3, 7).unwrap(),
+                sports: vec!["basketball".into(), "college-basketball".into()],
+                out: Some(PathBuf::from("caps")),
+                odds_every_mins: 30,
+            }
+        );
+        let single = download("download --from 2026-03-01").unwrap();
+        assert_eq!(single.to, single.from);
+        assert_eq!(single.odds_every_mins, history::DEFAULT_ODDS_EVERY_MINS);
+
+        assert!(download("download --from 2026-03-07 --to 2026-03-01").is_err());
+        assert!(parse("download").is_err());
+        assert!(parse("download --from 03/01/2026").is_err());
+        assert!(parse("download --from --to 2026-03-01").is_err());
+        assert!(parse("download --from 2026-03-01 --odds-every 0").is_err());
+        assert!(matches!(
+            parse("doctor").unwrap().command,
+            Some(Command::Doctor)
+        ));
+    }
+
+    #[test]
+    fn test_apply_sports() {
+        let mut config = Config::load(std::path::Path::new("config.toml")).unwrap();
+        parse("--sports basketball,mma")
+            .unwrap()
+            .apply_sports(&mut config)
+            .unwrap();
+        let mut enabled: Vec<&str> = config
+            .sports
+            .iter()
+            .filter(|(_, s)| s.enabled)
+            .map(|(k, _)| k.as_str())
+            .collect();
+        enabled.sort_unstable();
+        assert_eq!(enabled, vec!["basketball", "mma"]);
+
+        let err = parse("--sports curling")
+            .unwrap()
+            .apply_sports(&mut config)
+            .unwrap_err();
+        assert!(err.to_string().contains("curling"));
+    }
+}
@@ -1,3 +1,5 @@
+use crate::engine::season::{self, MonthDay, SeasonWindow};
+use crate::kalshi::types::TimeInForce;
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -10,15 +12,59 @@ const ENV_FILE: &str = ".env";
 #[allow(dead_code)]
 pub struct Config {
     pub kalshi: KalshiConfig,
-    pub odds_sources: HashMap<String, OddsSourceConfig>,
+    /// Optional: Kalshi-only setups (`fair_value = "order-flow"`) need none.
+    #[serde(default)]
+    pub odds_sources: OddsSourcesConfig,
     pub strategy: StrategyConfig,
     pub risk: RiskConfig,
     pub momentum: MomentumConfig,
     pub execution: ExecutionConfig,
     #[serde(default)]
+    pub exit: ExitConfig,
+    #[serde(default)]
     pub simulation: SimulationConfig,
     #[serde(default)]
     pub kill_switch: KillSwitchConfig,
+    #[serde(default)]
+    pub journal: JournalConfig,
+    #[serde(default)]
+    pub season: SeasonCalendarConfig,
+    #[serde(default)]
+    pub adaptive_poll: AdaptivePollConfig,
+    #[serde(default)]
+    pub quota_budget: QuotaBudgetConfig,
+    #[serde(default)]
+    pub sprint: SprintConfig,
+    #[serde(default)]
+    pub escalation: EscalationConfig,
+    #[serde(default)]
+    pub edge_persistence: EdgePersistenceConfig,
+    #[serde(default)]
+    pub polymarket: PolymarketConfig,
+    #[serde(default)]
+    pub recorder: RecorderConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub compaction: CompactionConfig,
+    #[serde(default)]
+    pub degradation: DegradationConfig,
+    #[serde(default)]
+    pub drawdown: DrawdownConfig,
+    #[serde(default)]
+    pub equity: EquityConfig,
+    #[serde(default)]
+    pub event_driven: EventDrivenConfig,
+    #[serde(default)]
+    pub kelly_tuning: KellyTuningConfig,
+    #[serde(default)]
+    pub game_filter: GameFilterConfig,
+    #[serde(default)]
+    pub locale: LocaleConfig,
+    #[serde(default)]
+    pub matching: MatchingConfig,
+    #[serde(default)]
+    pub web: WebConfig,
     pub sports: HashMap<String, SportConfig>,
 }
 
@@ -31,18 +77,133 @@ pub struct StrategyConfig {
     pub slippage_buffer_cents: u8,  // Subtracted from edge calculation
     #[serde(default = "default_max_edge_threshold")]
     pub max_edge_threshold: u8,  // Skip trades with edge above this (suspicious)
+    /// Kalshi time-in-force for taker entries: "good-till-canceled",
+    /// "immediate-or-cancel" or "fill-or-kill". The immediate ones never
+    /// leave an unfilled remainder resting when the displayed size was thin.
+    #[serde(default)]
+    pub taker_time_in_force: TimeInForce,
+    /// Size taker entries against every ask level they would sweep rather
+    /// than the best ask alone.
+    #[serde(default = "default_walk_taker_depth")]
+    pub walk_taker_depth: bool,
+    /// Require taker edge above this many standard errors of the fair value
+    /// instead of `taker_edge_threshold`, for models that report one.
+    #[serde(default)]
+    pub taker_stderr_multiple: Option<f64>,
+    /// Same for maker quotes, in place of `maker_edge_threshold`.
+    #[serde(default)]
+    pub maker_stderr_multiple: Option<f64>,
 }
 
 fn default_max_edge_threshold() -> u8 {
     15
 }
 
+fn default_walk_taker_depth() -> bool {
+    true
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct RiskConfig {
     pub max_contracts_per_market: u32,
+    /// Combined cap across all markets on one team (win, spread, series).
+    /// Defaults to `max_contracts_per_market`.
+    #[serde(default)]
+    pub max_contracts_per_entity: Option<u32>,
     pub max_total_exposure_cents: u64,
     pub max_concurrent_markets: u32,
     pub kelly_fraction: f64,
+    /// Balance never deployed by sizing, kept back for fees and adverse maker fills.
+    #[serde(default)]
+    pub min_cash_reserve_cents: u64,
+    /// Per-ticker position cap from the market's typical depth. Off when absent.
+    #[serde(default)]
+    pub liquidity_cap: Option<LiquidityCapConfig>,
+    /// Sell into the bid once it sits this many cents below the entry price.
+    /// Off when absent.
+    #[serde(default)]
+    pub stop_loss_cents: Option<u32>,
+    /// Sell into the bid once it sits this many cents above the entry price,
+    /// without waiting for the sell target. Off when absent.
+    #[serde(default)]
+    pub take_profit_cents: Option<u32>,
+    /// Cost held in one market, including the entry being sized. Off when absent.
+    #[serde(default)]
+    pub max_exposure_per_market_cents: Option<u64>,
+    /// Cost held across the home, away and draw markets of one game, so
+    /// doubled-up or offsetting positions on the same game share a budget.
+    /// Off when absent.
+    #[serde(default)]
+    pub max_exposure_per_game_cents: Option<u64>,
+    /// Cost held across one sport's markets. Off when absent.
+    #[serde(default)]
+    pub max_exposure_per_sport_cents: Option<u64>,
+}
+
+/// Baseline limits that tests build on. `[risk]` has required fields, so a
+/// loaded config never falls back to these.
+impl Default for RiskConfig {
+    fn default() -> Self {
+        Self {
+            max_contracts_per_market: 10,
+            max_contracts_per_entity: None,
+            max_total_exposure_cents: 5000,
+            max_concurrent_markets: 4,
+            kelly_fraction: 0.25,
+            min_cash_reserve_cents: 0,
+            liquidity_cap: None,
+            stop_loss_cents: None,
+            take_profit_cents: None,
+            max_exposure_per_market_cents: None,
+            max_exposure_per_game_cents: None,
+            max_exposure_per_sport_cents: None,
+        }
+    }
+}
+
+/// Caps each market's position at a fraction of its rolling average depth,
+/// on top of `max_contracts_per_market`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct LiquidityCapConfig {
+    /// Largest position as a fraction of typical depth (0.2 = 20%).
+    #[serde(default = "default_liquidity_fraction")]
+    pub fraction: f64,
+    /// Best price levels per side counted as depth.
+    #[serde(default = "default_liquidity_levels")]
+    pub levels: usize,
+    /// Depth samples (one per evaluation) in the rolling average.
+    #[serde(default = "default_liquidity_window")]
+    pub window_size: usize,
+    /// Samples needed before the cap applies; until then only the static caps do.
+    #[serde(default = "default_liquidity_min_samples")]
+    pub min_samples: usize,
+}
+
+fn default_liquidity_fraction() -> f64 {
+    0.2
+}
+
+fn default_liquidity_levels() -> usize {
+    3
+}
+
+fn default_liquidity_window() -> usize {
+    60
+}
+
+fn default_liquidity_min_samples() -> usize {
+    10
+}
+
+impl Default for LiquidityCapConfig {
+    fn default() -> Self {
+        Self {
+            fraction: default_liquidity_fraction(),
+            levels: default_liquidity_levels(),
+            window_size: default_liquidity_window(),
+            min_samples: default_liquidity_min_samples(),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -55,6 +216,28 @@ pub struct ExecutionConfig {
     pub dry_run: bool,
     #[serde(default = "default_order_timeout_secs")]
     pub order_timeout_secs: u64,
+    /// Alert when realized fees exceed expected fees by more than this percentage.
+    #[serde(default = "default_fee_alert_tolerance_pct")]
+    pub fee_alert_tolerance_pct: f64,
+    /// Minimum reconciled trades before fee divergence alerts can fire.
+    #[serde(default = "default_fee_alert_min_samples")]
+    pub fee_alert_min_samples: u32,
+    /// Keep live maker entries pegged to the touch via cancel/replace.
+    #[serde(default)]
+    pub peg_maker_orders: bool,
+    /// "touch" joins the best bid, "improve" bids one cent above it.
+    #[serde(default = "default_peg_mode")]
+    pub peg_mode: String,
+    #[serde(default = "default_min_repeg_interval_ms")]
+    pub min_repeg_interval_ms: u64,
+    #[serde(default = "default_max_repegs")]
+    pub max_repegs: u32,
+    /// Reject an order that duplicates a working one submitted within this
+    /// window (same ticker, side and action, price within the band). 0 disables.
+    #[serde(default = "default_duplicate_window_ms")]
+    pub duplicate_window_ms: u64,
+    #[serde(default = "default_duplicate_band_cents")]
+    pub duplicate_band_cents: u32,
 }
 
 fn default_dry_run() -> bool {
@@ -65,10 +248,924 @@ fn default_order_timeout_secs() -> u64 {
     30 // 30 second default
 }
 
+fn default_fee_alert_tolerance_pct() -> f64 {
+    10.0
+}
+
+fn default_fee_alert_min_samples() -> u32 {
+    10
+}
+
+fn default_peg_mode() -> String {
+    "improve".to_string()
+}
+
+fn default_min_repeg_interval_ms() -> u64 {
+    1000
+}
+
+fn default_max_repegs() -> u32 {
+    10
+}
+
+fn default_duplicate_window_ms() -> u64 {
+    5000
+}
+
+fn default_duplicate_band_cents() -> u32 {
+    1
+}
+
+impl Default for ExecutionConfig {
+    fn default() -> Self {
+        Self {
+            maker_timeout_ms: 500,
+            stale_odds_threshold_ms: 5000,
+            dry_run: default_dry_run(),
+            order_timeout_secs: default_order_timeout_secs(),
+            fee_alert_tolerance_pct: default_fee_alert_tolerance_pct(),
+            fee_alert_min_samples: default_fee_alert_min_samples(),
+            peg_maker_orders: false,
+            peg_mode: default_peg_mode(),
+            min_repeg_interval_ms: default_min_repeg_interval_ms(),
+            max_repegs: default_max_repegs(),
+            duplicate_window_ms: default_duplicate_window_ms(),
+            duplicate_band_cents: default_duplicate_band_cents(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct JournalConfig {
+    #[serde(default = "default_journal_enabled")]
+    pub enabled: bool,
+    /// SQLite database for signals, trades, settlements and their decision-time context.
+    #[serde(default = "default_journal_path")]
+    pub path: String,
+}
+
+impl Default for JournalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_journal_enabled(),
+            path: default_journal_path(),
+        }
+    }
+}
+
+fn default_journal_enabled() -> bool {
+    true
+}
+
+fn default_journal_path() -> String {
+    "journal.db".to_string()
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SeasonCalendarConfig {
+    /// Disable out-of-season sports at startup. `--ignore-season` overrides.
+    #[serde(default = "default_season_auto_disable")]
+    pub auto_disable: bool,
+}
+
+impl Default for SeasonCalendarConfig {
+    fn default() -> Self {
+        Self {
+            auto_disable: default_season_auto_disable(),
+        }
+    }
+}
+
+fn default_season_auto_disable() -> bool {
+    true
+}
+
+/// Feed degradation tiers; see `engine::degradation`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct DegradationConfig {
+    /// Consecutive failed fetches before a sport's feed counts as down.
+    #[serde(default = "default_degradation_failure_threshold")]
+    pub failure_threshold: u32,
+    /// How long feeds must stay healthy before moving back up a tier.
+    #[serde(default = "default_degradation_recover_secs")]
+    pub recover_secs: u64,
+}
+
+impl Default for DegradationConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: default_degradation_failure_threshold(),
+            recover_secs: default_degradation_recover_secs(),
+        }
+    }
+}
+
+fn default_degradation_failure_threshold() -> u32 {
+    3
+}
+
+fn default_degradation_recover_secs() -> u64 {
+    30
+}
+
+/// Drawdown tiers; see `engine::drawdown`. Each limit is a drop in session
+/// realized P&L from its peak, in cents, and is off when absent.
+#[derive(Debug, Deserialize, Clone)]
+pub struct DrawdownConfig {
+    /// Size entries from `reduced_size_fraction` of the bankroll.
+    #[serde(default)]
+    pub reduce_size_cents: Option<u64>,
+    /// Also stop placing taker entries.
+    #[serde(default)]
+    pub maker_only_cents: Option<u64>,
+    /// Stop placing entries for the rest of the session.
+    #[serde(default)]
+    pub halt_cents: Option<u64>,
+    #[serde(default = "default_drawdown_reduced_size_fraction")]
+    pub reduced_size_fraction: f64,
+}
+
+impl Default for DrawdownConfig {
+    fn default() -> Self {
+        Self {
+            reduce_size_cents: None,
+            maker_only_cents: None,
+            halt_cents: None,
+            reduced_size_fraction: default_drawdown_reduced_size_fraction(),
+        }
+    }
+}
+
+fn default_drawdown_reduced_size_fraction() -> f64 {
+    0.5
+}
+
+/// Equity curve sampling; see `engine::equity`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct EquityConfig {
+    #[serde(default = "default_equity_interval_secs")]
+    pub interval_secs: u64,
+    /// Samples kept for the curve; older ones stay in the journal only.
+    #[serde(default = "default_equity_max_samples")]
+    pub max_samples: usize,
+}
+
+impl Default for EquityConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: default_equity_interval_secs(),
+            max_samples: default_equity_max_samples(),
+        }
+    }
+}
+
+fn default_equity_interval_secs() -> u64 {
+    60
+}
+
+fn default_equity_max_samples() -> usize {
+    1440
+}
+
+/// Early engine wakes on book and streamed-odds changes; see
+/// `engine::eval_bus`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct EventDrivenConfig {
+    /// Re-evaluate a sport as soon as one of its books or its streamed odds
+    /// change, instead of on the engine's next timed cycle.
+    #[serde(default = "default_event_driven_enabled")]
+    pub enabled: bool,
+    /// Least time between the starts of two cycles, so a burst of deltas
+    /// is evaluated once.
+    #[serde(default = "default_event_min_interval_ms")]
+    pub min_interval_ms: u64,
+}
+
+impl Default for EventDrivenConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_event_driven_enabled(),
+            min_interval_ms: default_event_min_interval_ms(),
+        }
+    }
+}
+
+fn default_event_driven_enabled() -> bool {
+    true
+}
+
+fn default_event_min_interval_ms() -> u64 {
+    50
+}
+
+/// Fuzzy team-name fallback for feed games whose normalized key misses the
+/// market index; see `engine::matcher::fuzzy_match`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct MatchingConfig {
+    /// Off by default: a fuzzy match binds a feed game to a market, so it
+    /// is an opt-in.
+    #[serde(default = "default_fuzzy_enabled")]
+    pub fuzzy_enabled: bool,
+    /// Least Jaro-Winkler similarity, for the weaker of the two teams, at
+    /// which a same-day indexed game is taken as the feed's game. Closer
+    /// misses are listed in the diagnostic view with their scores.
+    #[serde(default = "default_fuzzy_min_score")]
+    pub fuzzy_min_score: f64,
+}
+
+impl Default for MatchingConfig {
+    fn default() -> Self {
+        Self {
+            fuzzy_enabled: default_fuzzy_enabled(),
+            fuzzy_min_score: default_fuzzy_min_score(),
+        }
+    }
+}
+
+fn default_fuzzy_enabled() -> bool {
+    false
+}
+
+fn default_fuzzy_min_score() -> f64 {
+    0.9
+}
+
+/// Per-sport Kelly fraction auto-tuning from realized edge; see
+/// `engine::kelly_tuner`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct KellyTuningConfig {
+    /// Scale each sport's Kelly fraction by realized over predicted profit.
+    /// Never raises it.
+    #[serde(default)]
+    pub auto_tune: bool,
+    /// Closed trades per sport the ratio is measured over.
+    #[serde(default = "default_kelly_window_trades")]
+    pub window_trades: usize,
+    /// Closed trades needed before a sport is tuned.
+    #[serde(default = "default_kelly_min_trades")]
+    pub min_trades: usize,
+    /// Lowest multiplier tuning will apply.
+    #[serde(default = "default_kelly_min_multiplier")]
+    pub min_multiplier: f64,
+}
+
+impl Default for KellyTuningConfig {
+    fn default() -> Self {
+        Self {
+            auto_tune: false,
+            window_trades: default_kelly_window_trades(),
+            min_trades: default_kelly_min_trades(),
+            min_multiplier: default_kelly_min_multiplier(),
+        }
+    }
+}
+
+/// Games and markets kept out of trading by hand; see `engine::game_filter`.
+/// Entries are Kalshi event tickers (a whole game) or market tickers, and
+/// are edited from the markets and diagnostic views.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct GameFilterConfig {
+    /// Never signalled or entered.
+    #[serde(default)]
+    pub blacklist: Vec<String>,
+    /// Traded while `whitelist_only` is on.
+    #[serde(default)]
+    pub whitelist: Vec<String>,
+    /// Enter only whitelisted games, for cautious live trading.
+    #[serde(default)]
+    pub whitelist_only: bool,
+}
+
+fn default_kelly_window_trades() -> usize {
+    50
+}
+fn default_kelly_min_trades() -> usize {
+    20
+}
+fn default_kelly_min_multiplier() -> f64 {
+    0.25
+}
+
+/// Number and currency display; see `locale::NumberFormat`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct LocaleConfig {
+    /// Preset separators and currency placement, e.g. "en-US", "de-DE".
+    #[serde(default = "default_locale_name")]
+    pub name: String,
+    #[serde(default)]
+    pub thousands_separator: Option<String>,
+    #[serde(default)]
+    pub decimal_separator: Option<String>,
+    /// Shown in place of "$", e.g. "US$" or "USD".
+    #[serde(default)]
+    pub currency_symbol: Option<String>,
+    /// Put the symbol after the amount ("12,50 $").
+    #[serde(default)]
+    pub currency_after: Option<bool>,
+}
+
+impl Default for LocaleConfig {
+    fn default() -> Self {
+        Self {
+            name: default_locale_name(),
+            thousands_separator: None,
+            decimal_separator: None,
+            currency_symbol: None,
+            currency_after: None,
+        }
+    }
+}
+
+fn default_locale_name() -> String {
+    "en-US".to_string()
+}
+
+/// Read-only browser dashboard; see `web`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct WebConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Listen address. Use "0.0.0.0:PORT" to reach it from another device.
+    #[serde(default = "default_web_bind")]
+    pub bind: String,
+    /// When set, the page and the WebSocket require `?token=<token>`.
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+impl Default for WebConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind: default_web_bind(),
+            token: None,
+        }
+    }
+}
+
+fn default_web_bind() -> String {
+    "127.0.0.1:8787".to_string()
+}
+
+/// Live-mode resting exits; see `engine::exit_manager`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ExitConfig {
+    /// Cancel/replace the resting sell once its target moves this many cents.
+    #[serde(default = "default_exit_reprice_threshold")]
+    pub reprice_threshold_cents: u32,
+    /// Sell into the bid once it exceeds fair value by this many cents.
+    #[serde(default = "default_exit_edge_cents")]
+    pub edge_exit_cents: u32,
+    #[serde(default = "default_exit_min_reprice_interval_ms")]
+    pub min_reprice_interval_ms: u64,
+}
+
+impl Default for ExitConfig {
+    fn default() -> Self {
+        Self {
+            reprice_threshold_cents: default_exit_reprice_threshold(),
+            edge_exit_cents: default_exit_edge_cents(),
+            min_reprice_interval_ms: default_exit_min_reprice_interval_ms(),
+        }
+    }
+}
+
+fn default_exit_reprice_threshold() -> u32 {
+    2
+}
+
+fn default_exit_edge_cents() -> u32 {
+    2
+}
+
+fn default_exit_min_reprice_interval_ms() -> u64 {
+    1000
+}
+
+/// Periodic pruning of finished games' state; see `engine::compaction`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct CompactionConfig {
+    #[serde(default = "default_compaction_interval_secs")]
+    pub interval_secs: u64,
+    /// Compact early, and warn, once resident memory passes this (0 = no limit).
+    #[serde(default)]
+    pub memory_limit_mb: u64,
+    /// Days past its game date before an unsettled game is dropped.
+    #[serde(default = "default_compaction_retention_days")]
+    pub retention_days: u32,
+}
+
+impl Default for CompactionConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: default_compaction_interval_secs(),
+            memory_limit_mb: 0,
+            retention_days: default_compaction_retention_days(),
+        }
+    }
+}
+
+fn default_compaction_interval_secs() -> u64 {
+    300
+}
+
+fn default_compaction_retention_days() -> u32 {
+    1
+}
+
+/// Scales live poll intervals by game volatility; see `feed::adaptive_poll`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct AdaptivePollConfig {
+    #[serde(default = "default_adaptive_poll_enabled")]
+    pub enabled: bool,
+    /// Score margin at or below which a game counts as close.
+    #[serde(default = "default_close_margin")]
+    pub close_margin: u32,
+    /// Score margin at or above which a game counts as a blowout.
+    #[serde(default = "default_blowout_margin")]
+    pub blowout_margin: u32,
+    /// Regulation seconds remaining that make a close game critical.
+    #[serde(default = "default_final_window_secs")]
+    pub final_window_secs: u32,
+    /// Odds feeds: favorite probability within this of 0.5 counts as close.
+    #[serde(default = "default_close_prob_margin")]
+    pub close_prob_margin: f64,
+    /// Odds feeds: favorite probability at or above this counts as a blowout.
+    #[serde(default = "default_blowout_prob")]
+    pub blowout_prob: f64,
+    /// Multipliers on the live poll interval (close games use it as-is).
+    #[serde(default = "default_critical_factor")]
+    pub critical_factor: f64,
+    #[serde(default = "default_normal_factor")]
+    pub normal_factor: f64,
+    #[serde(default = "default_blowout_factor")]
+    pub blowout_factor: f64,
+    #[serde(default = "default_min_interval_ms")]
+    pub min_interval_ms: u64,
+}
+
+impl Default for AdaptivePollConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_adaptive_poll_enabled(),
+            close_margin: default_close_margin(),
+            blowout_margin: default_blowout_margin(),
+            final_window_secs: default_final_window_secs(),
+            close_prob_margin: default_close_prob_margin(),
+            blowout_prob: default_blowout_prob(),
+            critical_factor: default_critical_factor(),
+            normal_factor: default_normal_factor(),
+            blowout_factor: default_blowout_factor(),
+            min_interval_ms: default_min_interval_ms(),
+        }
+    }
+}
+
+fn default_adaptive_poll_enabled() -> bool {
+    true
+}
+fn default_close_margin() -> u32 {
+    6
+}
+fn default_blowout_margin() -> u32 {
+    20
+}
+fn default_final_window_secs() -> u32 {
+    300
+}
+fn default_close_prob_margin() -> f64 {
+    0.15
+}
+fn default_blowout_prob() -> f64 {
+    0.90
+}
+fn default_critical_factor() -> f64 {
+    0.5
+}
+fn default_normal_factor() -> f64 {
+    1.5
+}
+fn default_blowout_factor() -> f64 {
+    4.0
+}
+fn default_min_interval_ms() -> u64 {
+    500
+}
+
+/// Shares a quota-reporting odds source's remaining credits across the
+/// sports polling it; see `feed::budget`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct QuotaBudgetConfig {
+    /// Off falls back to `quota_warning_threshold`.
+    #[serde(default = "default_quota_budget_enabled")]
+    pub enabled: bool,
+    /// How long the remaining credits must last.
+    #[serde(default = "default_slate_hours")]
+    pub slate_hours: f64,
+    /// Credits held back from the budget.
+    #[serde(default = "default_quota_reserve")]
+    pub reserve: u64,
+    /// Extra weight per cent of a sport's mean edge.
+    #[serde(default = "default_budget_edge_weight")]
+    pub edge_weight: f64,
+    /// Longest a budgeted sport waits between polls.
+    #[serde(default = "default_budget_max_interval")]
+    pub max_interval_s: u64,
+}
+
+impl Default for QuotaBudgetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_quota_budget_enabled(),
+            slate_hours: default_slate_hours(),
+            reserve: default_quota_reserve(),
+            edge_weight: default_budget_edge_weight(),
+            max_interval_s: default_budget_max_interval(),
+        }
+    }
+}
+
+fn default_quota_budget_enabled() -> bool {
+    true
+}
+fn default_slate_hours() -> f64 {
+    6.0
+}
+fn default_quota_reserve() -> u64 {
+    50
+}
+fn default_budget_edge_weight() -> f64 {
+    0.25
+}
+fn default_budget_max_interval() -> u64 {
+    900
+}
+
+/// End-of-game sprint mode for close games in their final minutes.
+#[derive(Debug, Deserialize, Clone)]
+pub struct SprintConfig {
+    #[serde(default = "default_sprint_enabled")]
+    pub enabled: bool,
+    /// Largest score margin that still counts as close.
+    #[serde(default = "default_sprint_max_margin")]
+    pub max_margin: u32,
+    /// Seconds left in regulation (or the current OT) that start a sprint.
+    #[serde(default = "default_sprint_final_secs")]
+    pub final_secs: u32,
+    /// Score poll interval while any game is sprinting.
+    #[serde(default = "default_sprint_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// Engine loop period while any game is sprinting, so books are re-evaluated every tick.
+    #[serde(default = "default_sprint_tick_ms")]
+    pub tick_ms: u64,
+    /// Score staleness that blocks trading on a sprinting game (normally 10s).
+    #[serde(default = "default_sprint_stale_threshold_secs")]
+    pub stale_threshold_secs: u64,
+}
+
+impl Default for SprintConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_sprint_enabled(),
+            max_margin: default_sprint_max_margin(),
+            final_secs: default_sprint_final_secs(),
+            poll_interval_ms: default_sprint_poll_interval_ms(),
+            tick_ms: default_sprint_tick_ms(),
+            stale_threshold_secs: default_sprint_stale_threshold_secs(),
+        }
+    }
+}
+
+fn default_sprint_enabled() -> bool {
+    true
+}
+fn default_sprint_max_margin() -> u32 {
+    5
+}
+fn default_sprint_final_secs() -> u32 {
+    120
+}
+fn default_sprint_poll_interval_ms() -> u64 {
+    500
+}
+fn default_sprint_tick_ms() -> u64 {
+    250
+}
+fn default_sprint_stale_threshold_secs() -> u64 {
+    3
+}
+
+/// How long edges survive on the book, and taker-threshold auto-tuning.
+#[derive(Debug, Deserialize, Clone)]
+pub struct EdgePersistenceConfig {
+    /// Raise each sport's taker threshold to the smallest edge size that
+    /// historically outlives execution latency. Never lowers it.
+    #[serde(default)]
+    pub auto_tune: bool,
+    /// Signal-to-exchange latency assumed until live orders have been timed.
+    #[serde(default = "default_edge_assumed_latency_ms")]
+    pub assumed_latency_ms: u64,
+    /// Edges at or above a threshold needed before it can be chosen.
+    #[serde(default = "default_edge_min_samples")]
+    pub min_samples: usize,
+    /// Fraction of edges that must outlive the latency.
+    #[serde(default = "default_edge_survival_target")]
+    pub survival_target: f64,
+}
+
+impl Default for EdgePersistenceConfig {
+    fn default() -> Self {
+        Self {
+            auto_tune: false,
+            assumed_latency_ms: default_edge_assumed_latency_ms(),
+            min_samples: default_edge_min_samples(),
+            survival_target: default_edge_survival_target(),
+        }
+    }
+}
+
+fn default_edge_assumed_latency_ms() -> u64 {
+    300
+}
+fn default_edge_min_samples() -> usize {
+    30
+}
+fn default_edge_survival_target() -> f64 {
+    0.6
+}
+
+/// Escalation of unacknowledged critical alerts in live mode.
+#[derive(Debug, Deserialize, Clone)]
+pub struct EscalationConfig {
+    #[serde(default = "default_escalation_enabled")]
+    pub enabled: bool,
+    /// Webhook that relays escalations to SMS/email. `ESCALATION_WEBHOOK_URL`
+    /// is used when unset, so the URL can stay out of the config file.
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    /// Escalate an alert not acknowledged in the TUI within this many seconds.
+    #[serde(default = "default_escalation_ack_timeout_secs")]
+    pub ack_timeout_secs: u64,
+    /// Raise a critical alert when the Kalshi WS is down this long during live games.
+    #[serde(default = "default_escalation_ws_down_secs")]
+    pub ws_down_secs: u64,
+    /// Email escalations directly over SMTP.
+    #[serde(default)]
+    pub smtp: Option<SmtpConfig>,
+    /// Text escalations as SMS through Twilio.
+    #[serde(default)]
+    pub twilio: Option<TwilioConfig>,
+}
+
+impl Default for EscalationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_escalation_enabled(),
+            webhook_url: None,
+            ack_timeout_secs: default_escalation_ack_timeout_secs(),
+            ws_down_secs: default_escalation_ws_down_secs(),
+            smtp: None,
+            twilio: None,
+        }
+    }
+}
+
+fn default_escalation_enabled() -> bool {
+    true
+}
+fn default_escalation_ack_timeout_secs() -> u64 {
+    300
+}
+fn default_escalation_ws_down_secs() -> u64 {
+    180
+}
+
+/// SMTP server for email escalations. The password is read from
+/// `ESCALATION_SMTP_PASSWORD`, never from the config file.
+#[derive(Debug, Deserialize, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    #[serde(default = "default_smtp_port")]
+    pub port: u16,
+    #[serde(default)]
+    pub security: SmtpSecurity,
+    /// Login for AUTH PLAIN; unset for relays that need none.
+    #[serde(default)]
+    pub username: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+/// How an SMTP connection is encrypted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SmtpSecurity {
+    /// Upgrade a plain connection with STARTTLS (port 587).
+    #[default]
+    Starttls,
+    /// TLS from the first byte (port 465).
+    Tls,
+    /// Unencrypted, for a relay on localhost only.
+    None,
+}
+
+/// Twilio account for SMS escalations. The auth token is read from
+/// `TWILIO_AUTH_TOKEN`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct TwilioConfig {
+    pub account_sid: String,
+    /// Sending number in E.164 form, e.g. "+15551234567".
+    pub from: String,
+    pub to: Vec<String>,
+}
+
+/// Feed capture for later backtesting or bug reproduction (`--record` also enables it).
+#[derive(Debug, Deserialize, Clone)]
+pub struct RecorderConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Parent of the per-session capture directories.
+    #[serde(default = "default_recorder_dir")]
+    pub dir: String,
+}
+
+impl Default for RecorderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: default_recorder_dir(),
+        }
+    }
+}
+
+fn default_recorder_dir() -> String {
+    "captures".to_string()
+}
+
+/// Proxies, extra CA roots and timeouts for every HTTP and WebSocket client.
+#[derive(Debug, Deserialize, Clone)]
+pub struct NetworkConfig {
+    /// PEM file of extra root certificates (e.g. a corporate TLS-inspection CA).
+    #[serde(default)]
+    pub ca_bundle: Option<String>,
+    #[serde(default = "default_connect_timeout_ms")]
+    pub connect_timeout_ms: u64,
+    /// Longest one sport's pipeline tick may run before the engine cycle
+    /// goes on without it, so one hung fetch can't stall every sport.
+    #[serde(default = "default_pipeline_tick_timeout_ms")]
+    pub pipeline_tick_timeout_ms: u64,
+    /// Global `http://`, `https://`, `socks5://` or `socks5h://` proxy URL.
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// Per-feed overrides keyed by odds source name, "kalshi", "polymarket",
+    /// "score-feed" or "escalation". "direct" bypasses the global proxy.
+    #[serde(default)]
+    pub proxies: HashMap<String, String>,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            ca_bundle: None,
+            connect_timeout_ms: default_connect_timeout_ms(),
+            pipeline_tick_timeout_ms: default_pipeline_tick_timeout_ms(),
+            proxy: None,
+            proxies: HashMap::new(),
+        }
+    }
+}
+
+fn default_connect_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_pipeline_tick_timeout_ms() -> u64 {
+    15000
+}
+
+/// Polymarket as a second venue. Sports opt in with `polymarket_tag`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct PolymarketConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Smallest guaranteed profit (cents, after both venues' fees) for a
+    /// cross-venue arbitrage signal.
+    #[serde(default = "default_polymarket_arb_min_profit_cents")]
+    pub arb_min_profit_cents: u32,
+    #[serde(default = "default_polymarket_gamma_url")]
+    pub gamma_url: String,
+    #[serde(default = "default_polymarket_clob_url")]
+    pub clob_url: String,
+    #[serde(default = "default_polymarket_ws_url")]
+    pub ws_url: String,
+    /// 0 = EOA wallet, 1 = Polymarket email/proxy wallet, 2 = browser proxy wallet.
+    #[serde(default)]
+    pub signature_type: u8,
+    /// Taker fee in basis points of notional (most sports markets charge none).
+    #[serde(default)]
+    pub taker_fee_bps: u32,
+}
+
+impl Default for PolymarketConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            arb_min_profit_cents: default_polymarket_arb_min_profit_cents(),
+            gamma_url: default_polymarket_gamma_url(),
+            clob_url: default_polymarket_clob_url(),
+            ws_url: default_polymarket_ws_url(),
+            signature_type: 0,
+            taker_fee_bps: 0,
+        }
+    }
+}
+
+fn default_polymarket_arb_min_profit_cents() -> u32 {
+    1
+}
+fn default_polymarket_gamma_url() -> String {
+    "https://gamma-api.polymarket.com".to_string()
+}
+fn default_polymarket_clob_url() -> String {
+    "https://clob.polymarket.com".to_string()
+}
+fn default_polymarket_ws_url() -> String {
+    "wss://ws-subscriptions-clob.polymarket.com/ws/market".to_string()
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct KalshiConfig {
     pub api_base: String,
     pub ws_url: String,
+    /// Minutes between re-fetches of every series to pick up newly listed
+    /// games. 0 disables the refresh.
+    #[serde(default = "default_index_refresh_mins")]
+    pub index_refresh_mins: u64,
+}
+
+fn default_index_refresh_mins() -> u64 {
+    15
+}
+
+/// `[odds_sources]`: the named odds sources, plus `[odds_sources.weights]`
+/// for combining a source's bookmakers into one consensus line.
+#[derive(Debug, Deserialize, Clone)]
+pub struct OddsSourcesConfig {
+    /// Weight per bookmaker name (case and punctuation ignored, so
+    /// `draftkings` matches "DraftKings"). Unlisted books weigh 1.0 and a
+    /// weight of 0 leaves a book out of the consensus.
+    #[serde(default)]
+    pub weights: HashMap<String, f64>,
+    /// Odds requests in flight at once, across all sources.
+    #[serde(default = "default_max_concurrent_fetches")]
+    pub max_concurrent_fetches: usize,
+    #[serde(flatten)]
+    pub sources: HashMap<String, OddsSourceConfig>,
+}
+
+impl Default for OddsSourcesConfig {
+    fn default() -> Self {
+        Self {
+            weights: HashMap::new(),
+            max_concurrent_fetches: default_max_concurrent_fetches(),
+            sources: HashMap::new(),
+        }
+    }
+}
+
+fn default_max_concurrent_fetches() -> usize {
+    4
+}
+
+impl std::ops::Deref for OddsSourcesConfig {
+    type Target = HashMap<String, OddsSourceConfig>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.sources
+    }
+}
+
+/// Normalized bookmaker name for weight lookups.
+fn bookmaker_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+impl OddsSourcesConfig {
+    /// Consensus weight for a bookmaker, 1.0 unless configured.
+    pub fn bookmaker_weight(&self, name: &str) -> f64 {
+        let key = bookmaker_key(name);
+        self.weights
+            .iter()
+            .find(|(k, _)| bookmaker_key(k) == key)
+            .map_or(1.0, |(_, &w)| w)
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -89,6 +1186,104 @@ pub struct OddsSourceConfig {
     pub request_timeout_ms: u64,
     #[serde(default = "default_max_retries")]
     pub max_retries: u32,
+    /// Consecutive failed polls before a sport using this source fails over
+    /// to its `backup_odds_source`.
+    #[serde(default = "default_failover_threshold")]
+    pub failover_threshold: u32,
+    /// No successful poll for this long also counts as down.
+    #[serde(default = "default_odds_stale_after")]
+    pub stale_after_s: u64,
+    /// Requests per minute this source may be sent, shared by every sport
+    /// polling it.
+    #[serde(default = "default_rate_limit_per_min")]
+    pub rate_limit_per_min: f64,
+    /// Requests that may go out back to back before the rate limit applies.
+    #[serde(default = "default_rate_limit_burst")]
+    pub rate_limit_burst: u32,
+    /// DraftKings event group id per sport key, for leagues missing from (or
+    /// renumbered since) the built-in table.
+    #[serde(default)]
+    pub event_groups: HashMap<String, u64>,
+    /// For `type = "scraped"`: where the book's response keeps its odds.
+    /// Unset means Bovada.
+    #[serde(default)]
+    pub scrape: Option<ScrapeConfig>,
+}
+
+/// `[odds_sources.<name>.scrape]`: a sportsbook's JSON layout, so a new book
+/// is a config entry rather than an adapter.
+///
+/// Selectors are either JSON pointers (`/data/events`) or dotted paths whose
+/// segments may index (`markets[0]`), fan out (`markets[*]`) or filter
+/// (`competitors[home=true]`). `events` is applied to the whole response and
+/// the other selectors to each event; `outcome_name` and `outcome_price` to
+/// each outcome. An array reached by `events` or `outcomes` stands for its
+/// elements.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ScrapeConfig {
+    /// Bookmaker name reported with the odds, as looked up in `weights`.
+    pub bookmaker: String,
+    /// Request URL. `{sport}` is replaced by the sport key and `{path}` by
+    /// its `sport_paths` entry; a sport without one is not supported.
+    pub url: String,
+    #[serde(default)]
+    pub sport_paths: HashMap<String, String>,
+    /// Id of the `<script>` element holding the JSON, for books that embed
+    /// their data in an HTML page (e.g. `__NEXT_DATA__`).
+    #[serde(default)]
+    pub script_id: Option<String>,
+    pub events: String,
+    pub event_id: String,
+    pub home_team: String,
+    pub away_team: String,
+    #[serde(default)]
+    pub commence_time: Option<String>,
+    #[serde(default)]
+    pub time_format: ScrapeTimeFormat,
+    /// Moneyline outcomes, matched to the teams by `outcome_name`...
+    #[serde(default)]
+    pub outcomes: Option<String>,
+    #[serde(default = "default_scrape_outcome_name")]
+    pub outcome_name: String,
+    #[serde(default = "default_scrape_outcome_price")]
+    pub outcome_price: String,
+    /// ...or fixed fields holding each side's price.
+    #[serde(default)]
+    pub home_odds: Option<String>,
+    #[serde(default)]
+    pub away_odds: Option<String>,
+    #[serde(default)]
+    pub draw_odds: Option<String>,
+    #[serde(default)]
+    pub odds_format: ScrapeOddsFormat,
+}
+
+fn default_scrape_outcome_name() -> String {
+    "name".to_string()
+}
+fn default_scrape_outcome_price() -> String {
+    "price".to_string()
+}
+
+/// How a scraped book writes start times.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScrapeTimeFormat {
+    #[default]
+    Rfc3339,
+    EpochMs,
+    EpochS,
+}
+
+/// How a scraped book writes prices.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScrapeOddsFormat {
+    /// "-150", "+130", "EVEN", or plain numbers.
+    #[default]
+    American,
+    /// European decimal odds, e.g. 2.30.
+    Decimal,
 }
 
 fn default_live_poll() -> u64 {
@@ -103,6 +1298,17 @@ fn default_request_timeout() -> u64 {
 fn default_max_retries() -> u32 {
     2
 }
+fn default_odds_stale_after() -> u64 {
+    300
+}
+
+fn default_rate_limit_per_min() -> f64 {
+    60.0
+}
+
+fn default_rate_limit_burst() -> u32 {
+    5
+}
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct SportConfig {
@@ -111,15 +1317,101 @@ pub struct SportConfig {
     pub label: String,
     pub hotkey: String,
     pub fair_value: String,
+    #[serde(default)]
     pub odds_source: String,
+    /// Odds source polled instead of `odds_source` while it is down.
+    #[serde(default)]
+    pub backup_odds_source: Option<String>,
+    /// Gamma tag slug (e.g. "nba") for finding the same games on Polymarket.
+    #[serde(default)]
+    pub polymarket_tag: Option<String>,
     pub score_feed: Option<ScoreFeedConfig>,
     pub win_prob: Option<WinProbConfig>,
     pub strategy: Option<StrategyOverride>,
     pub momentum: Option<MomentumOverride>,
+    #[serde(default)]
+    pub season: Option<SportSeasonConfig>,
+    #[serde(default)]
+    pub period_markets: Option<PeriodMarketsConfig>,
+    /// Three-way in-play model for soccer; replaces `[win_prob]` for score-feed
+    /// fair value.
+    #[serde(default)]
+    pub soccer_model: Option<SoccerModelConfig>,
+    /// Tuning for `fair_value = "order-flow"`; defaults when absent.
+    #[serde(default)]
+    pub order_flow: Option<OrderFlowConfig>,
+    /// Weights for `fair_value = "blended"`; defaults when absent.
+    #[serde(default)]
+    pub blend: Option<BlendConfig>,
+    /// Breaking-news trading halt; off when absent.
+    #[serde(default)]
+    pub news: Option<NewsConfig>,
+    /// Pre-game maker positioning; off when absent.
+    #[serde(default)]
+    pub pre_game: Option<PreGameConfig>,
+}
+
+/// Half/quarter winner markets for a score-feed sport.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct PeriodMarketsConfig {
+    /// Evaluate period markets for trading. When off they are still indexed
+    /// and their books tracked, but only full-game markets are evaluated.
+    #[serde(default)]
+    pub evaluate: bool,
+    /// Kalshi series per segment ("1H", "2H", "Q1".."Q4").
+    #[serde(default)]
+    pub series: HashMap<String, String>,
+}
+
+/// Per-sport override of the built-in season calendar ("MM-DD" dates).
+#[derive(Debug, Deserialize, Clone)]
+pub struct SportSeasonConfig {
+    /// Never auto-disable this sport.
+    #[serde(default)]
+    pub year_round: bool,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    /// Inclusive `["MM-DD", "MM-DD"]` pairs, replacing the built-in breaks.
+    #[serde(default)]
+    pub breaks: Option<Vec<[String; 2]>>,
+}
+
+impl SportConfig {
+    /// Season window for `key`: the built-in calendar with any configured
+    /// overrides applied. `None` means the sport runs year-round.
+    pub fn season_window(&self, key: &str) -> Result<Option<SeasonWindow>> {
+        let default = season::default_season(key);
+        let Some(cfg) = &self.season else {
+            return Ok(default);
+        };
+        if cfg.year_round {
+            return Ok(None);
+        }
+        let date_or_default = |value: &Option<String>, fallback: Option<MonthDay>| match value {
+            Some(v) => season::parse_month_day(v),
+            None => {
+                fallback.with_context(|| format!("sports.{}.season needs both start and end", key))
+            }
+        };
+        let start = date_or_default(&cfg.start, default.as_ref().map(|d| d.start))?;
+        let end = date_or_default(&cfg.end, default.as_ref().map(|d| d.end))?;
+        let breaks = match &cfg.breaks {
+            Some(pairs) => pairs
+                .iter()
+                .map(|[s, e]| Ok((season::parse_month_day(s)?, season::parse_month_day(e)?)))
+                .collect::<Result<Vec<_>>>()?,
+            None => default.map(|d| d.breaks).unwrap_or_default(),
+        };
+        Ok(Some(SeasonWindow { start, end, breaks }))
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct ScoreFeedConfig {
+    /// Scoreboard format: "basketball" (NBA CDN, ESPN fallback), "nfl" or
+    /// "college-football" (ESPN).
+    #[serde(rename = "type", default = "default_score_feed_type")]
+    pub feed_type: String,
     pub primary_url: String,
     #[serde(default)]
     pub fallback_url: Option<String>,
@@ -131,8 +1423,23 @@ pub struct ScoreFeedConfig {
     pub failover_threshold: u32,
     #[serde(default = "default_request_timeout")]
     pub request_timeout_ms: u64,
+    /// Learn the scoreboard's refresh cadence and poll just after each expected refresh.
+    #[serde(default = "default_align_to_cadence")]
+    pub align_to_cadence: bool,
+    /// How long after an expected refresh the aligned poll fires.
+    #[serde(default = "default_cadence_offset_ms")]
+    pub cadence_offset_ms: u64,
 }
 
+fn default_score_feed_type() -> String {
+    "basketball".to_string()
+}
+fn default_align_to_cadence() -> bool {
+    true
+}
+fn default_cadence_offset_ms() -> u64 {
+    150
+}
 fn default_score_live_poll() -> u64 {
     1
 }
@@ -149,6 +1456,9 @@ pub struct StrategyOverride {
     pub maker_edge_threshold: Option<u8>,
     pub min_edge_after_fees: Option<u8>,
     pub max_edge_threshold: Option<u8>,
+    pub taker_time_in_force: Option<TimeInForce>,
+    pub taker_stderr_multiple: Option<f64>,
+    pub maker_stderr_multiple: Option<f64>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -172,6 +1482,10 @@ pub struct DraftKingsFeedConfig {
     pub pre_game_poll_interval_s: u64,
     #[serde(default = "default_dk_timeout")]
     pub request_timeout_ms: u64,
+    #[serde(default = "default_dk_base_url")]
+    pub base_url: String,
+    #[serde(default)]
+    pub event_groups: HashMap<String, u64>,
 }
 
 fn default_dk_live_poll() -> u64 {
@@ -183,6 +1497,9 @@ fn default_dk_pre_game_poll() -> u64 {
 fn default_dk_timeout() -> u64 {
     5000
 }
+fn default_dk_base_url() -> String {
+    crate::feed::draftkings::DK_BASE_URL.to_string()
+}
 
 impl Default for DraftKingsFeedConfig {
     fn default() -> Self {
@@ -190,6 +1507,8 @@ impl Default for DraftKingsFeedConfig {
             live_poll_interval_s: 3,
             pre_game_poll_interval_s: 30,
             request_timeout_ms: 5000,
+            base_url: default_dk_base_url(),
+            event_groups: HashMap::new(),
         }
     }
 }
@@ -214,8 +1533,33 @@ pub struct SimulationConfig {
     pub use_break_even_exit: bool,
     #[serde(default)]
     pub validate_fair_value: bool,
+    /// Alert when a sport's average score-model vs odds FV gap exceeds this.
+    #[serde(default = "default_fv_disagreement_threshold_cents")]
+    pub fv_disagreement_threshold_cents: f64,
+    /// Rolling window for the FV disagreement average.
+    #[serde(default = "default_fv_disagreement_window_secs")]
+    pub fv_disagreement_window_secs: u64,
     #[serde(default)]
     pub realism: SimulationRealismConfig,
+    /// How often a closed market holding positions is checked for its result.
+    #[serde(default = "default_settlement_check_secs")]
+    pub settlement_check_secs: u64,
+    /// Give up waiting for a result after this long and settle at fair value.
+    #[serde(default = "default_settlement_timeout_secs")]
+    pub settlement_timeout_secs: u64,
+}
+
+fn default_fv_disagreement_threshold_cents() -> f64 {
+    8.0
+}
+fn default_fv_disagreement_window_secs() -> u64 {
+    600
+}
+fn default_settlement_check_secs() -> u64 {
+    30
+}
+fn default_settlement_timeout_secs() -> u64 {
+    3600
 }
 
 impl Default for SimulationConfig {
@@ -224,7 +1568,11 @@ impl Default for SimulationConfig {
             latency_ms: 500,
             use_break_even_exit: true,
             validate_fair_value: false,
+            fv_disagreement_threshold_cents: default_fv_disagreement_threshold_cents(),
+            fv_disagreement_window_secs: default_fv_disagreement_window_secs(),
             realism: SimulationRealismConfig::default(),
+            settlement_check_secs: default_settlement_check_secs(),
+            settlement_timeout_secs: default_settlement_timeout_secs(),
         }
     }
 }
@@ -249,6 +1597,9 @@ pub struct SimulationRealismConfig {
     pub max_hold_seconds: u64,
     #[serde(default = "default_timeout_slippage")]
     pub timeout_exit_slippage_cents: u32,
+    /// Fixed RNG seed for reproducible fills (regression runs); entropy when unset.
+    #[serde(default)]
+    pub seed: Option<u64>,
 }
 
 fn default_realism_enabled() -> bool { true }
@@ -273,6 +1624,7 @@ impl Default for SimulationRealismConfig {
             apply_latency: default_apply_latency(),
             max_hold_seconds: default_max_hold_seconds(),
             timeout_exit_slippage_cents: default_timeout_slippage(),
+            seed: None,
         }
     }
 }
@@ -292,6 +1644,64 @@ pub struct WinProbConfig {
     pub ot_k_range: f64,
     #[serde(default)]
     pub regulation_secs: Option<u16>,
+    /// Length of one overtime period; 300 (basketball) when unset.
+    #[serde(default)]
+    pub ot_period_secs: Option<u16>,
+    /// Periods in regulation; 2 up to 2400s of regulation, else 4, when unset.
+    #[serde(default)]
+    pub regulation_periods: Option<u8>,
+    /// Home win probability for a game tied at the end of regulation; 57 when unset.
+    #[serde(default)]
+    pub tied_home_prob: Option<u8>,
+    /// Overtime ends at the first score (hockey): a tie holds at
+    /// `tied_home_prob` and any lead decides the game.
+    #[serde(default)]
+    pub sudden_death: bool,
+    /// Home win probability in a shootout once the OT period runs out. Without
+    /// it, sudden-death OT periods repeat (NHL playoffs).
+    #[serde(default)]
+    pub shootout_home_prob: Option<u8>,
+    /// Sanity bounds on model output; violations are clamped, not traded, and alerted.
+    #[serde(default = "default_sanity_bounds")]
+    pub sanity_bounds: Vec<SanityBound>,
+    /// Standard error of `k_start`/`k_range`/`ot_k_*`, relative to their values.
+    #[serde(default = "default_k_rel_stderr")]
+    pub k_rel_stderr: f64,
+    /// Standard error of `home_advantage`, in points.
+    #[serde(default = "default_home_advantage_stderr")]
+    pub home_advantage_stderr: f64,
+}
+
+fn default_k_rel_stderr() -> f64 {
+    0.1
+}
+
+fn default_home_advantage_stderr() -> f64 {
+    1.0
+}
+
+/// A team leading by at least `min_lead` with at most `max_remaining_secs` left in
+/// the current period of play must be priced at `min_fair` cents or more.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct SanityBound {
+    pub min_lead: u32,
+    pub max_remaining_secs: u16,
+    pub min_fair: u32,
+}
+
+fn default_sanity_bounds() -> Vec<SanityBound> {
+    vec![
+        SanityBound {
+            min_lead: 20,
+            max_remaining_secs: 120,
+            min_fair: 95,
+        },
+        SanityBound {
+            min_lead: 10,
+            max_remaining_secs: 30,
+            min_fair: 90,
+        },
+    ]
 }
 
 impl Default for WinProbConfig {
@@ -303,6 +1713,262 @@ impl Default for WinProbConfig {
             ot_k_start: 0.10,
             ot_k_range: 1.0,
             regulation_secs: Some(2880),
+            ot_period_secs: None,
+            regulation_periods: None,
+            tied_home_prob: None,
+            sudden_death: false,
+            shootout_home_prob: None,
+            sanity_bounds: default_sanity_bounds(),
+            k_rel_stderr: default_k_rel_stderr(),
+            home_advantage_stderr: default_home_advantage_stderr(),
+        }
+    }
+}
+
+/// Kalshi-only fair value from the market's own book (`fair_value = "order-flow"`).
+#[derive(Debug, Deserialize, Clone)]
+pub struct OrderFlowConfig {
+    /// Distinct book samples kept per market for the mean and band.
+    #[serde(default = "default_order_flow_window")]
+    pub window_size: usize,
+    /// Samples needed before a market is evaluated.
+    #[serde(default = "default_order_flow_min_samples")]
+    pub min_samples: usize,
+    /// Reversion band half-width in standard deviations of the mid.
+    #[serde(default = "default_band_stddevs")]
+    pub band_stddevs: f64,
+    /// Floor on the band half-width, in cents.
+    #[serde(default = "default_min_band_cents")]
+    pub min_band_cents: f64,
+    /// Levels within this many cents of each touch count as near-touch depth.
+    #[serde(default = "default_depth_band_cents")]
+    pub depth_band_cents: u32,
+    /// Cents added at full bid-side depth imbalance.
+    #[serde(default = "default_pressure_weight_cents")]
+    pub pressure_weight_cents: f64,
+    /// Cents added at full buy-side flow.
+    #[serde(default = "default_flow_weight_cents")]
+    pub flow_weight_cents: f64,
+}
+
+fn default_order_flow_window() -> usize {
+    30
+}
+
+fn default_order_flow_min_samples() -> usize {
+    10
+}
+
+fn default_band_stddevs() -> f64 {
+    2.0
+}
+
+fn default_min_band_cents() -> f64 {
+    1.0
+}
+
+fn default_depth_band_cents() -> u32 {
+    3
+}
+
+fn default_pressure_weight_cents() -> f64 {
+    3.0
+}
+
+fn default_flow_weight_cents() -> f64 {
+    2.0
+}
+
+impl Default for OrderFlowConfig {
+    fn default() -> Self {
+        Self {
+            window_size: default_order_flow_window(),
+            min_samples: default_order_flow_min_samples(),
+            band_stddevs: default_band_stddevs(),
+            min_band_cents: default_min_band_cents(),
+            depth_band_cents: default_depth_band_cents(),
+            pressure_weight_cents: default_pressure_weight_cents(),
+            flow_weight_cents: default_flow_weight_cents(),
+        }
+    }
+}
+
+/// Score-feed win probability blended with devigged sportsbook odds
+/// (`fair_value = "blended"`). The score model's weight moves from
+/// `start_score_weight` at the start of the game to `end_score_weight` at the
+/// end of regulation: odds dominate early, the score late.
+#[derive(Debug, Deserialize, Clone)]
+pub struct BlendConfig {
+    /// Score model weight at the start of the game; the odds get the rest.
+    #[serde(default = "default_blend_start_score_weight")]
+    pub start_score_weight: f64,
+    /// Score model weight from the end of regulation on, overtime included.
+    #[serde(default = "default_blend_end_score_weight")]
+    pub end_score_weight: f64,
+    /// Shape of the shift over regulation: 1 is linear in elapsed time,
+    /// higher keeps the odds weighted for longer.
+    #[serde(default = "default_blend_curve")]
+    pub curve: f64,
+}
+
+fn default_blend_start_score_weight() -> f64 {
+    0.2
+}
+
+fn default_blend_end_score_weight() -> f64 {
+    0.95
+}
+
+fn default_blend_curve() -> f64 {
+    2.0
+}
+
+impl BlendConfig {
+    /// Score model weight `progress` (0-1) of the way through regulation.
+    pub fn score_weight(&self, progress: f64) -> f64 {
+        let start = self.start_score_weight.clamp(0.0, 1.0);
+        let end = self.end_score_weight.clamp(0.0, 1.0);
+        let shift = progress.clamp(0.0, 1.0).powf(self.curve.max(0.0));
+        start + (end - start) * shift
+    }
+}
+
+impl Default for BlendConfig {
+    fn default() -> Self {
+        Self {
+            start_score_weight: default_blend_start_score_weight(),
+            end_score_weight: default_blend_end_score_weight(),
+            curve: default_blend_curve(),
+        }
+    }
+}
+
+/// Breaking-news trading halt. Items from `url` (an ESPN news API endpoint)
+/// that match `keywords` stop trading on games of the teams they mention for
+/// `halt_minutes` after publication: the fair value models can't price news.
+#[derive(Debug, Deserialize, Clone)]
+pub struct NewsConfig {
+    pub url: String,
+    #[serde(default = "default_news_poll_s")]
+    pub poll_s: u64,
+    #[serde(default = "default_news_halt_minutes")]
+    pub halt_minutes: u64,
+    /// Case-insensitive substrings of the headline or description that make
+    /// an item breaking news. Empty halts on every item.
+    #[serde(default = "default_news_keywords")]
+    pub keywords: Vec<String>,
+    #[serde(default = "default_request_timeout")]
+    pub request_timeout_ms: u64,
+}
+
+fn default_news_poll_s() -> u64 {
+    60
+}
+
+fn default_news_halt_minutes() -> u64 {
+    10
+}
+
+fn default_news_keywords() -> Vec<String> {
+    [
+        "injur",
+        "ruled out",
+        "will not return",
+        "won't return",
+        "questionable",
+        "doubtful",
+        "suspend",
+        "eject",
+        "illness",
+        "concussion",
+        "carted off",
+    ]
+    .iter()
+    .map(|k| k.to_string())
+    .collect()
+}
+
+/// Pre-game positioning for odds-driven sports: between `max_hours_before`
+/// and `min_hours_before` the start, markets whose Kalshi price lags the
+/// devigged consensus by `edge_margin` get small maker bids. Taker entries
+/// stay live-only.
+#[derive(Debug, Deserialize, Clone)]
+pub struct PreGameConfig {
+    #[serde(default = "default_pre_game_max_hours")]
+    pub max_hours_before: f64,
+    #[serde(default = "default_pre_game_min_hours")]
+    pub min_hours_before: f64,
+    /// Edge in cents a maker bid has to clear.
+    #[serde(default = "default_pre_game_edge_margin")]
+    pub edge_margin: u8,
+    /// Most contracts held per market from pre-game entries.
+    #[serde(default = "default_pre_game_max_contracts")]
+    pub max_contracts: u32,
+}
+
+fn default_pre_game_max_hours() -> f64 {
+    12.0
+}
+
+fn default_pre_game_min_hours() -> f64 {
+    1.0
+}
+
+fn default_pre_game_edge_margin() -> u8 {
+    5
+}
+
+fn default_pre_game_max_contracts() -> u32 {
+    5
+}
+
+/// Poisson goal model for soccer's home/draw/away markets.
+#[derive(Debug, Deserialize, Clone)]
+pub struct SoccerModelConfig {
+    /// Expected goals per 90 minutes for the home side at full strength.
+    #[serde(default = "default_home_goals_per_90")]
+    pub home_goals_per_90: f64,
+    #[serde(default = "default_away_goals_per_90")]
+    pub away_goals_per_90: f64,
+    /// Expected added time over the whole match, spread over the remaining play.
+    #[serde(default = "default_added_time_secs")]
+    pub added_time_secs: u16,
+    /// Scoring-rate multiplier per red card against a side.
+    #[serde(default = "default_red_card_own_factor")]
+    pub red_card_own_factor: f64,
+    /// Scoring-rate multiplier per red card against the opponent.
+    #[serde(default = "default_red_card_opponent_factor")]
+    pub red_card_opponent_factor: f64,
+}
+
+fn default_home_goals_per_90() -> f64 {
+    1.55
+}
+
+fn default_away_goals_per_90() -> f64 {
+    1.25
+}
+
+fn default_added_time_secs() -> u16 {
+    480
+}
+
+fn default_red_card_own_factor() -> f64 {
+    0.7
+}
+
+fn default_red_card_opponent_factor() -> f64 {
+    1.3
+}
+
+impl Default for SoccerModelConfig {
+    fn default() -> Self {
+        Self {
+            home_goals_per_90: default_home_goals_per_90(),
+            away_goals_per_90: default_away_goals_per_90(),
+            added_time_secs: default_added_time_secs(),
+            red_card_own_factor: default_red_card_own_factor(),
+            red_card_opponent_factor: default_red_card_opponent_factor(),
         }
     }
 }
@@ -319,6 +1985,10 @@ impl StrategyConfig {
                 min_edge_after_fees: o.min_edge_after_fees.unwrap_or(self.min_edge_after_fees),
                 slippage_buffer_cents: self.slippage_buffer_cents,
                 max_edge_threshold: o.max_edge_threshold.unwrap_or(self.max_edge_threshold),
+                taker_time_in_force: o.taker_time_in_force.unwrap_or(self.taker_time_in_force),
+                walk_taker_depth: self.walk_taker_depth,
+                taker_stderr_multiple: o.taker_stderr_multiple.or(self.taker_stderr_multiple),
+                maker_stderr_multiple: o.maker_stderr_multiple.or(self.maker_stderr_multiple),
             },
         }
     }
@@ -351,6 +2021,58 @@ impl MomentumConfig {
 // ── Runtime config persistence ──────────────────────────────────────────
 
 /// Update a single field in the TOML config file at the given dotted path.
+/// Take profile `name` out of `[profiles]`, or read it from the profiles
+/// directory beside the config file. Defining it in both is an error.
+fn profile_table(base: &mut toml::Table, config_path: &Path, name: &str) -> Result<toml::Table> {
+    let inline = base
+        .get_mut("profiles")
+        .and_then(toml::Value::as_table_mut)
+        .and_then(|profiles| profiles.remove(name));
+    let file = config_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("profiles")
+        .join(format!("{}.toml", name));
+    match (inline, file.exists()) {
+        (Some(_), true) => anyhow::bail!(
+            "profile {:?} is defined in both [profiles] and {}",
+            name,
+            file.display()
+        ),
+        (Some(toml::Value::Table(table)), false) => Ok(table),
+        (Some(_), false) => anyhow::bail!("[profiles.{}] must be a table", name),
+        (None, true) => {
+            let content = std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read profile {}", file.display()))?;
+            toml::from_str(&content)
+                .with_context(|| format!("Failed to parse profile {}", file.display()))
+        }
+        (None, false) => anyhow::bail!(
+            "unknown profile {:?}: no [profiles.{}] and no {}",
+            name,
+            name,
+            file.display()
+        ),
+    }
+}
+
+/// Overlay `overlay` onto `base`: tables merge recursively, anything else
+/// replaces the base value.
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match value {
+            toml::Value::Table(table) if base.get(&key).is_some_and(toml::Value::is_table) => {
+                if let Some(toml::Value::Table(base_table)) = base.get_mut(&key) {
+                    merge_tables(base_table, table);
+                }
+            }
+            value => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
 pub fn persist_field(config_path: &Path, dotted_key: &str, value: &str) -> Result<()> {
     let content = std::fs::read_to_string(config_path)?;
     let mut doc: toml::Value = toml::from_str(&content)?;
@@ -421,35 +2143,53 @@ pub fn remove_field(config_path: &Path, dotted_key: &str) -> Result<()> {
 
 impl Config {
     pub fn load(path: &Path) -> Result<Self> {
+        Self::load_profile(path, None)
+    }
+
+    /// Load `path` with a named profile merged over it. The profile is
+    /// `[profiles.<name>]` in the file itself or `profiles/<name>.toml` next
+    /// to it; its tables override the base config key by key, so a profile
+    /// only lists what it changes.
+    pub fn load_profile(path: &Path, profile: Option<&str>) -> Result<Self> {
         let content = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read config file: {}", path.display()))?;
-        let config: Config =
+        let Some(name) = profile else {
+            return toml::from_str(&content).with_context(|| "Failed to parse config TOML");
+        };
+        let mut base: toml::Table =
             toml::from_str(&content).with_context(|| "Failed to parse config TOML")?;
-        Ok(config)
+        let overlay = profile_table(&mut base, path, name)?;
+        merge_tables(&mut base, overlay);
+        toml::Value::Table(base)
+            .try_into()
+            .with_context(|| format!("Failed to parse config TOML with profile {:?}", name))
     }
 
     /// Load .env file into process environment. Real env vars take precedence.
     pub fn load_env_file() {
-        let path = Path::new(ENV_FILE);
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => return,
+        apply_env_file(false);
+    }
+
+    /// Re-read credentials for a rotation at runtime. Values in .env replace
+    /// the ones loaded at startup, since that file is where a rotated key is
+    /// written. Nothing is prompted for: the TUI owns the terminal.
+    pub fn reload_credentials() -> Result<ReloadedCredentials> {
+        apply_env_file(true);
+        let var = |key: &str| -> Result<String> {
+            std::env::var(key)
+                .ok()
+                .map(|v| sanitize_key(&v))
+                .filter(|v| !v.is_empty())
+                .with_context(|| format!("{} is not set", key))
         };
-        // Strip BOM if present (common on Windows-created files)
-        let content = content.strip_prefix('\u{feff}').unwrap_or(&content);
-        for line in content.lines() {
-            let line = line.trim().trim_matches('\r');
-            if line.is_empty() || line.starts_with('#') {
-                continue;
-            }
-            if let Some((key, value)) = line.split_once('=') {
-                let key = key.trim();
-                let value = value.trim().trim_matches('"').trim_matches('\'');
-                if std::env::var(key).is_err() {
-                    std::env::set_var(key, value);
-                }
-            }
-        }
+        let key_path = expand_home(&var("KALSHI_PRIVATE_KEY_PATH")?);
+        let pem = std::fs::read_to_string(&key_path)
+            .with_context(|| format!("Failed to read private key file: {}", key_path))?;
+        Ok(ReloadedCredentials {
+            kalshi_api_key: var("KALSHI_API_KEY")?,
+            kalshi_private_key_pem: pem.strip_prefix('\u{feff}').unwrap_or(&pem).to_string(),
+            odds_api_key: var("ODDS_API_KEY").ok(),
+        })
     }
 
     /// API keys come from environment variables, or prompted at startup.
@@ -478,12 +2218,7 @@ impl Config {
             }
         };
 
-        let expanded = if path.starts_with('~') {
-            let home = std::env::var("HOME").unwrap_or_default();
-            path.replacen('~', &home, 1)
-        } else {
-            path.clone()
-        };
+        let expanded = expand_home(&path);
 
         let pem = std::fs::read_to_string(&expanded)
             .with_context(|| format!("Failed to read private key file: {}", expanded))?;
@@ -512,6 +2247,58 @@ impl Config {
         Ok(pem)
     }
 
+    /// Polymarket trading credentials: wallet key file (hex), L2 API key,
+    /// secret and passphrase, plus an optional funder (proxy wallet) address.
+    /// Prompted values are saved to .env for future runs.
+    pub fn polymarket_credentials() -> Result<PolymarketCredentials> {
+        let var = |key: &str, label: &str| -> Result<String> {
+            match std::env::var(key) {
+                Ok(v) if !v.is_empty() => Ok(sanitize_key(&v)),
+                _ => {
+                    let v = prompt(label)?;
+                    save_env_var(key, &v);
+                    Ok(v)
+                }
+            }
+        };
+        let key_path = var(
+            "POLYMARKET_PRIVATE_KEY_PATH",
+            "Polymarket wallet private key file path",
+        )?;
+        let expanded = expand_home(&key_path);
+        let private_key = std::fs::read_to_string(&expanded)
+            .with_context(|| format!("Failed to read Polymarket key file: {}", expanded))?;
+        Ok(PolymarketCredentials {
+            private_key: sanitize_key(&private_key),
+            api_key: var("POLYMARKET_API_KEY", "Polymarket API Key")?,
+            api_secret: var("POLYMARKET_API_SECRET", "Polymarket API Secret")?,
+            passphrase: var("POLYMARKET_API_PASSPHRASE", "Polymarket API Passphrase")?,
+            funder: std::env::var("POLYMARKET_FUNDER")
+                .ok()
+                .map(|f| sanitize_key(&f))
+                .filter(|f| !f.is_empty()),
+        })
+    }
+
+    /// Pinnacle API username and password (HTTP basic auth).
+    /// Prompted values are saved to .env for future runs.
+    pub fn pinnacle_credentials() -> Result<(String, String)> {
+        let var = |key: &str, label: &str| -> Result<String> {
+            match std::env::var(key) {
+                Ok(v) if !v.is_empty() => Ok(sanitize_key(&v)),
+                _ => {
+                    let v = prompt(label)?;
+                    save_env_var(key, &v);
+                    Ok(v)
+                }
+            }
+        };
+        Ok((
+            var("PINNACLE_USERNAME", "Pinnacle API Username")?,
+            var("PINNACLE_PASSWORD", "Pinnacle API Password")?,
+        ))
+    }
+
     pub fn odds_api_key() -> Result<String> {
         match std::env::var("ODDS_API_KEY") {
             Ok(key) if !key.is_empty() => Ok(key),
@@ -524,6 +2311,21 @@ impl Config {
     }
 }
 
+/// Credentials re-read by `Config::reload_credentials`.
+pub struct ReloadedCredentials {
+    pub kalshi_api_key: String,
+    pub kalshi_private_key_pem: String,
+    pub odds_api_key: Option<String>,
+}
+
+pub struct PolymarketCredentials {
+    pub private_key: String,
+    pub api_key: String,
+    pub api_secret: String,
+    pub passphrase: String,
+    pub funder: Option<String>,
+}
+
 fn prompt(label: &str) -> Result<String> {
     print!("  {} > ", label);
     io::stdout().flush()?;
@@ -543,6 +2345,41 @@ fn sanitize_key(raw: &str) -> String {
         .to_string()
 }
 
+/// Set each KEY=VALUE in .env in the process environment; existing vars are
+/// kept unless `overwrite`.
+fn apply_env_file(overwrite: bool) {
+    let path = Path::new(ENV_FILE);
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(_) => return,
+    };
+    // Strip BOM if present (common on Windows-created files)
+    let content = content.strip_prefix('\u{feff}').unwrap_or(&content);
+    for line in content.lines() {
+        let line = line.trim().trim_matches('\r');
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim();
+            let value = value.trim().trim_matches('"').trim_matches('\'');
+            if overwrite || std::env::var(key).is_err() {
+                std::env::set_var(key, value);
+            }
+        }
+    }
+}
+
+/// Expand a leading `~` to $HOME.
+fn expand_home(path: &str) -> String {
+    if path.starts_with('~') {
+        let home = std::env::var("HOME").unwrap_or_default();
+        path.replacen('~', &home, 1)
+    } else {
+        path.to_string()
+    }
+}
+
 /// Append a KEY=VALUE line to .env and set it in the current process.
 fn save_env_var(key: &str, value: &str) {
     std::env::set_var(key, value);
@@ -629,6 +2466,7 @@ regulation_secs = 2880
 [sports.basketball.strategy]
 taker_edge_threshold = 3
 maker_edge_threshold = 1
+taker_time_in_force = "fill-or-kill"
 
 [sports.basketball.momentum]
 taker_momentum_threshold = 0
@@ -653,10 +2491,22 @@ odds_source = "the-odds-api"
         assert_eq!(bball.fair_value, "score-feed");
         assert!(bball.score_feed.is_some());
         assert!(bball.win_prob.is_some());
+        assert_eq!(
+            bball.win_prob.as_ref().unwrap().sanity_bounds,
+            default_sanity_bounds()
+        );
         assert_eq!(
             bball.strategy.as_ref().unwrap().taker_edge_threshold,
             Some(3)
         );
+        assert_eq!(
+            bball.strategy.as_ref().unwrap().taker_time_in_force,
+            Some(TimeInForce::FillOrKill)
+        );
+        assert_eq!(
+            config.strategy.taker_time_in_force,
+            TimeInForce::GoodTillCanceled
+        );
         assert_eq!(
             bball.momentum.as_ref().unwrap().taker_momentum_threshold,
             Some(0)
@@ -676,12 +2526,19 @@ odds_source = "the-odds-api"
             min_edge_after_fees: 1,
             slippage_buffer_cents: 1,
             max_edge_threshold: 15,
+            taker_time_in_force: TimeInForce::GoodTillCanceled,
+            walk_taker_depth: true,
+            taker_stderr_multiple: Some(2.0),
+            maker_stderr_multiple: None,
         };
         let ov = StrategyOverride {
             taker_edge_threshold: Some(3),
             maker_edge_threshold: Some(1),
             min_edge_after_fees: None,
             max_edge_threshold: None,
+            taker_time_in_force: Some(TimeInForce::ImmediateOrCancel),
+            taker_stderr_multiple: None,
+            maker_stderr_multiple: Some(1.5),
         };
         let resolved = global.with_override(Some(&ov));
         assert_eq!(resolved.taker_edge_threshold, 3);
@@ -689,6 +2546,9 @@ odds_source = "the-odds-api"
         assert_eq!(resolved.min_edge_after_fees, 1);
         assert_eq!(resolved.slippage_buffer_cents, 1);
         assert_eq!(resolved.max_edge_threshold, 15);
+        assert_eq!(resolved.taker_time_in_force, TimeInForce::ImmediateOrCancel);
+        assert_eq!(resolved.taker_stderr_multiple, Some(2.0));
+        assert_eq!(resolved.maker_stderr_multiple, Some(1.5));
     }
 
     #[test]
@@ -722,8 +2582,15 @@ odds_source = "the-odds-api"
     #[test]
     fn test_config_file_parses() {
         let config = Config::load(std::path::Path::new("config.toml")).unwrap();
-        assert_eq!(config.sports.len(), 8);
+        assert_eq!(config.sports.len(), 9);
         assert!(config.odds_sources.contains_key("the-odds-api"));
+        assert!(
+            !config.odds_sources.contains_key("weights"),
+            "weights is not a source"
+        );
+        assert_eq!(config.odds_sources.bookmaker_weight("Pinnacle"), 3.0);
+        assert_eq!(config.odds_sources.bookmaker_weight("Bovada"), 0.5);
+        assert_eq!(config.odds_sources.bookmaker_weight("FanDuel"), 1.0);
         assert_eq!(config.sports["basketball"].fair_value, "score-feed");
         assert_eq!(config.sports["ice-hockey"].fair_value, "odds-feed");
         assert_eq!(config.sports["college-basketball"].fair_value, "score-feed");
@@ -732,6 +2599,11 @@ odds_source = "the-odds-api"
             "score-feed"
         );
         assert_eq!(config.sports["mma"].fair_value, "odds-feed");
+        let nfl = &config.sports["american-football"];
+        assert_eq!(nfl.score_feed.as_ref().unwrap().feed_type, "nfl");
+        assert_eq!(nfl.win_prob.as_ref().unwrap().ot_period_secs, Some(600));
+        let nba_feed = config.sports["basketball"].score_feed.as_ref().unwrap();
+        assert_eq!(nba_feed.feed_type, "basketball");
     }
 
     #[test]
@@ -825,6 +2697,121 @@ enabled = true
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[test]
+    fn test_season_window_overrides() {
+        let sports: HashMap<String, SportConfig> = toml::from_str(
+            r#"
+[basketball]
+enabled = true
+kalshi_series = "KXNBAGAME"
+label = "NBA"
+hotkey = "1"
+fair_value = "score-feed"
+odds_source = "the-odds-api"
+
+[basketball.season]
+end = "06-30"
+breaks = [["02-10", "02-12"]]
+
+[mma]
+enabled = true
+kalshi_series = "KXUFCFIGHT"
+label = "UFC"
+hotkey = "8"
+fair_value = "odds-feed"
+odds_source = "the-odds-api"
+
+[soccer-epl]
+enabled = true
+kalshi_series = "KXEPLGAME"
+label = "EPL"
+hotkey = "7"
+fair_value = "odds-feed"
+odds_source = "the-odds-api"
+season = { year_round = true }
+"#,
+        )
+        .unwrap();
+
+        let nba = sports["basketball"]
+            .season_window("basketball")
+            .unwrap()
+            .unwrap();
+        let builtin = season::default_season("basketball").unwrap();
+        assert_eq!(nba.start, builtin.start);
+        assert_eq!(nba.end, (6, 30));
+        assert_eq!(nba.breaks, vec![((2, 10), (2, 12))]);
+
+        assert!(sports["mma"].season_window("mma").unwrap().is_none());
+        assert!(sports["soccer-epl"]
+            .season_window("soccer-epl")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_profile_overrides_base() {
+        let path = std::path::Path::new("config.toml");
+        let base = Config::load(path).unwrap();
+        let conservative = Config::load_profile(path, Some("conservative")).unwrap();
+        assert_eq!(conservative.strategy.taker_edge_threshold, 8);
+        assert_eq!(conservative.risk.kelly_fraction, 0.1);
+        // Keys the profile leaves out keep their base values
+        assert_eq!(
+            conservative.strategy.maker_edge_threshold,
+            base.strategy.maker_edge_threshold
+        );
+        assert_eq!(
+            conservative.risk.max_total_exposure_cents,
+            base.risk.max_total_exposure_cents
+        );
+        assert!(Config::load_profile(path, Some("nope")).is_err());
+    }
+
+    #[test]
+    fn test_profile_file_beside_config() {
+        let dir = std::env::temp_dir().join("kalshi_test_profiles");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("profiles")).unwrap();
+        let config_path = dir.join("config.toml");
+        std::fs::copy("config.toml", &config_path).unwrap();
+        std::fs::write(
+            dir.join("profiles/live.toml"),
+            "[risk]\nmax_contracts_per_market = 3\n",
+        )
+        .unwrap();
+        let config = Config::load_profile(&config_path, Some("live")).unwrap();
+        assert_eq!(config.risk.max_contracts_per_market, 3);
+
+        // The same name in [profiles] too is ambiguous
+        std::fs::write(dir.join("profiles/paper.toml"), "").unwrap();
+        assert!(Config::load_profile(&config_path, Some("paper")).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_escalation_channels() {
+        let config: EscalationConfig = toml::from_str(
+            r#"
+[smtp]
+host = "smtp.example.com"
+username = "arb"
+from = "arb@example.com"
+to = ["me@example.com"]
+
+[twilio]
+account_sid = "AC123"
+from = "+15550001111"
+to = ["+15552223333"]
+"#,
+        )
+        .unwrap();
+        let smtp = config.smtp.unwrap();
+        assert_eq!((smtp.port, smtp.security), (587, SmtpSecurity::Starttls));
+        assert_eq!(config.twilio.unwrap().to, vec!["+15552223333"]);
+        assert!(EscalationConfig::default().smtp.is_none());
+    }
+
     #[test]
     fn test_remove_field() {
         let dir = std::env::temp_dir().join("kalshi_test_remove");
@@ -0,0 +1,591 @@
+//! Setup doctor.
+//!
+//! `kalshi-arb doctor` checks every integration the engine depends on (config,
+//! Kalshi auth, REST, WebSocket, clock skew, odds sources, score feeds,
+//! Polymarket) and prints a pass/fail checklist. Nothing is traded and no
+//! credentials are prompted for: missing keys are reported, not requested.
+
+use crate::config::{Config, PolymarketConfig};
+use crate::engine::win_prob::GameSegment;
+use crate::feed::pinnacle::{PinnacleFeed, PINNACLE_BASE_URL};
+use crate::feed::score_feed;
+use crate::feed::the_odds_api::TheOddsApi;
+use crate::kalshi::auth::KalshiAuth;
+use crate::kalshi::rest::KalshiRest;
+use crate::kalshi::ws::KalshiWs;
+use crate::locale::NumberFormat;
+use crate::network::{self, Network};
+use crate::polymarket::auth::PolymarketAuth;
+use anyhow::{Context, Result};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Clock skew above this fails: signed requests carry a millisecond
+/// timestamp that Kalshi rejects when it drifts too far from exchange time.
+pub const MAX_CLOCK_SKEW_SECS: f64 = 5.0;
+
+/// How long to wait for the WebSocket subscription ack.
+const WS_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+const ODDS_SOURCE_TYPES: [&str; 5] = [
+    "the-odds-api",
+    "draftkings",
+    "pinnacle",
+    "scraped",
+    "websocket",
+];
+const FAIR_VALUE_SOURCES: [&str; 4] = ["score-feed", "odds-feed", "order-flow", "blended"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    /// Not applicable, or blocked by an earlier failure.
+    Skip,
+}
+
+impl CheckStatus {
+    fn label(self) -> &'static str {
+        match self {
+            CheckStatus::Pass => "PASS",
+            CheckStatus::Fail => "FAIL",
+            CheckStatus::Skip => "SKIP",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+#[derive(Debug, Default)]
+struct Checklist {
+    checks: Vec<Check>,
+}
+
+impl Checklist {
+    fn push(&mut self, name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) {
+        let check = Check {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        };
+        println!(
+            "  [{}] {:<32} {}",
+            check.status.label(),
+            check.name,
+            check.detail
+        );
+        self.checks.push(check);
+    }
+
+    fn result<T>(
+        &mut self,
+        name: &str,
+        result: Result<T>,
+        detail: impl FnOnce(&T) -> String,
+    ) -> Option<T> {
+        match result {
+            Ok(v) => {
+                let d = detail(&v);
+                self.push(name, CheckStatus::Pass, d);
+                Some(v)
+            }
+            Err(e) => {
+                self.push(name, CheckStatus::Fail, format!("{:#}", e));
+                None
+            }
+        }
+    }
+
+    fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+}
+
+/// Problems in a parsed config that would otherwise surface as a panic or a
+/// silent no-op at startup. Empty means the config is usable.
+pub fn validate_config(config: &Config) -> Vec<String> {
+    let mut problems = Vec::new();
+    for (name, source) in config.odds_sources.iter() {
+        if !ODDS_SOURCE_TYPES.contains(&source.source_type.as_str()) {
+            problems.push(format!(
+                "odds_sources.{}: unknown type \"{}\"",
+                name, source.source_type
+            ));
+        }
+        if source.source_type == "websocket" && source.base_url.is_none() {
+            problems.push(format!(
+                "odds_sources.{}: websocket source needs base_url",
+                name
+            ));
+        }
+    }
+
+    let mut weights: Vec<_> = config.odds_sources.weights.iter().collect();
+    weights.sort_by_key(|(name, _)| name.as_str());
+    for (name, weight) in weights {
+        if !weight.is_finite() || *weight < 0.0 {
+            problems.push(format!(
+                "odds_sources.weights.{}: weight must be 0 or more, got {}",
+                name, weight
+            ));
+        }
+    }
+
+    let mut keys: Vec<&String> = config.sports.keys().collect();
+    keys.sort();
+    let mut hotkeys: HashMap<&str, &str> = HashMap::new();
+    for key in keys {
+        let sport = &config.sports[key];
+        // Order flow prices off the Kalshi book alone
+        if sport.fair_value != "order-flow" && !config.odds_sources.contains_key(&sport.odds_source)
+        {
+            problems.push(format!(
+                "sports.{}: odds_source \"{}\" is not defined",
+                key, sport.odds_source
+            ));
+        }
+        if !FAIR_VALUE_SOURCES.contains(&sport.fair_value.as_str()) {
+            problems.push(format!(
+                "sports.{}: unknown fair_value \"{}\"",
+                key, sport.fair_value
+            ));
+        }
+        if matches!(sport.fair_value.as_str(), "score-feed" | "blended") {
+            if sport.score_feed.is_none() {
+                problems.push(format!(
+                    "sports.{}: {} needs [score_feed]",
+                    key, sport.fair_value
+                ));
+            }
+            if sport.win_prob.is_none() && sport.soccer_model.is_none() {
+                problems.push(format!(
+                    "sports.{}: {} needs [win_prob]",
+                    key, sport.fair_value
+                ));
+            }
+        }
+        if let Some(sf) = &sport.score_feed {
+            if score_feed::scoreboard_parsers(&sf.feed_type).is_none() {
+                problems.push(format!(
+                    "sports.{}.score_feed: unknown type \"{}\"",
+                    key, sf.feed_type
+                ));
+            }
+        }
+        if let Some(other) = hotkeys.insert(&sport.hotkey, key) {
+            problems.push(format!(
+                "sports.{}: hotkey \"{}\" already used by {}",
+                key, sport.hotkey, other
+            ));
+        }
+        if let Err(e) = sport.season_window(key) {
+            problems.push(format!("sports.{}.season: {:#}", key, e));
+        }
+        if let Some(pm) = &sport.period_markets {
+            let mut labels: Vec<&String> = pm.series.keys().collect();
+            labels.sort();
+            for label in labels {
+                if GameSegment::parse(label).is_none() {
+                    problems.push(format!(
+                        "sports.{}.period_markets: unknown segment \"{}\"",
+                        key, label
+                    ));
+                }
+            }
+            if pm.evaluate && !matches!(sport.fair_value.as_str(), "score-feed" | "blended") {
+                problems.push(format!(
+                    "sports.{}.period_markets: evaluate needs score-feed fair value",
+                    key
+                ));
+            }
+        }
+    }
+    problems
+}
+
+/// Local clock minus server clock, in seconds. The server's `Date` header
+/// has one-second resolution, so it is compared with the request midpoint.
+pub fn clock_skew_secs(
+    local_sent: chrono::DateTime<chrono::Utc>,
+    local_received: chrono::DateTime<chrono::Utc>,
+    server: chrono::DateTime<chrono::Utc>,
+) -> f64 {
+    let midpoint = local_sent + (local_received - local_sent) / 2;
+    (midpoint - server).num_milliseconds() as f64 / 1000.0
+}
+
+fn env_var(key: &str) -> Option<String> {
+    std::env::var(key)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn read_key_file(path: &str) -> Result<String> {
+    let expanded = if path.starts_with('~') {
+        let home = std::env::var("HOME").unwrap_or_default();
+        path.replacen('~', &home, 1)
+    } else {
+        path.to_string()
+    };
+    let content = std::fs::read_to_string(&expanded)
+        .with_context(|| format!("failed to read {}", expanded))?;
+    Ok(content
+        .strip_prefix('\u{feff}')
+        .unwrap_or(&content)
+        .to_string())
+}
+
+/// GET `url` and require a 2xx response.
+async fn probe_url(client: &reqwest::Client, url: &str, timeout_ms: u64) -> Result<String> {
+    let started = Instant::now();
+    let resp = client
+        .get(url)
+        .timeout(Duration::from_millis(timeout_ms))
+        .send()
+        .await
+        .context("request failed")?;
+    let status = resp.status();
+    if !status.is_success() {
+        anyhow::bail!("HTTP {}", status);
+    }
+    Ok(format!("{} in {}ms", status, started.elapsed().as_millis()))
+}
+
+/// Run every check and print the checklist. Fails if any check failed.
+pub async fn run(config_path: &Path) -> Result<()> {
+    let mut list = Checklist::default();
+
+    println!("  Config");
+    let config = list.result("config parses", Config::load(config_path), |c| {
+        format!("{} ({} sports)", config_path.display(), c.sports.len())
+    });
+    if let Some(config) = &config {
+        let problems = validate_config(config);
+        if problems.is_empty() {
+            list.push("config consistent", CheckStatus::Pass, "");
+        } else {
+            list.push("config consistent", CheckStatus::Fail, problems.join("; "));
+        }
+    }
+    let network = match &config {
+        Some(config) => list.result(
+            "network settings",
+            Network::from_config(&config.network),
+            |_| match &config.network.proxy {
+                Some(proxy) => format!("proxy {}", proxy),
+                None => "direct".to_string(),
+            },
+        ),
+        None => None,
+    }
+    .unwrap_or_default();
+    let number_format = match &config {
+        Some(config) => list.result("locale", NumberFormat::from_config(&config.locale), |f| {
+            format!("{} ({})", config.locale.name, f.money(123_456_789))
+        }),
+        None => None,
+    }
+    .unwrap_or_default();
+
+    Config::load_env_file();
+
+    println!();
+    println!("  Kalshi");
+    let kalshi_key = env_var("KALSHI_API_KEY");
+    let kalshi_pem_path = env_var("KALSHI_PRIVATE_KEY_PATH");
+    let auth = match (&kalshi_key, &kalshi_pem_path) {
+        (Some(key), Some(path)) => {
+            let auth = read_key_file(path).and_then(|pem| KalshiAuth::new(key.clone(), &pem));
+            let auth = auth.and_then(|a| {
+                a.headers("GET", "/trade-api/v2/portfolio/balance")?;
+                Ok(a)
+            });
+            list.result("auth signing", auth, |_| {
+                "RSA-PSS signature created".to_string()
+            })
+            .map(Arc::new)
+        }
+        _ => {
+            list.push(
+                "auth signing",
+                CheckStatus::Fail,
+                "KALSHI_API_KEY and KALSHI_PRIVATE_KEY_PATH must be set (env or .env)",
+            );
+            None
+        }
+    };
+
+    let rest = match (&config, &auth) {
+        (Some(config), Some(auth)) => list.result(
+            "REST client",
+            KalshiRest::new(
+                auth.clone(),
+                &config.kalshi.api_base,
+                network.client_builder(network::KALSHI),
+            ),
+            |_| config.kalshi.api_base.clone(),
+        ),
+        _ => None,
+    };
+    match &rest {
+        Some(rest) => {
+            let sent = chrono::Utc::now();
+            let status = rest.get_exchange_status().await;
+            let received = chrono::Utc::now();
+            let server_time = match list.result("REST reachable", status, |(s, _)| {
+                format!(
+                    "exchange {}, trading {}",
+                    if s.exchange_active {
+                        "active"
+                    } else {
+                        "inactive"
+                    },
+                    if s.trading_active { "active" } else { "paused" }
+                )
+            }) {
+                Some((_, server_time)) => server_time,
+                None => None,
+            };
+            match server_time {
+                Some(server) => {
+                    let skew = clock_skew_secs(sent, received, server);
+                    let status = if skew.abs() <= MAX_CLOCK_SKEW_SECS {
+                        CheckStatus::Pass
+                    } else {
+                        CheckStatus::Fail
+                    };
+                    list.push(
+                        "clock skew",
+                        status,
+                        format!("{:+.1}s vs exchange (limit {}s)", skew, MAX_CLOCK_SKEW_SECS),
+                    );
+                }
+                None => list.push("clock skew", CheckStatus::Skip, "no server time"),
+            }
+
+            let authed = rest.preflight_auth_check().await;
+            if list
+                .result("REST auth", authed, |_| String::new())
+                .is_some()
+            {
+                list.result("balance", rest.get_balance().await, |b| {
+                    number_format.money(*b)
+                });
+            }
+        }
+        None => {
+            for name in ["REST reachable", "clock skew", "REST auth"] {
+                list.push(name, CheckStatus::Skip, "needs config and credentials");
+            }
+        }
+    }
+    match (&config, &auth) {
+        (Some(config), Some(auth)) => {
+            let ws = KalshiWs::new(auth.clone(), &config.kalshi.ws_url, network.clone());
+            let started = Instant::now();
+            list.result(
+                "WS connect + subscribe",
+                ws.probe(WS_PROBE_TIMEOUT).await,
+                |_| format!("acknowledged in {}ms", started.elapsed().as_millis()),
+            );
+        }
+        _ => list.push(
+            "WS connect + subscribe",
+            CheckStatus::Skip,
+            "needs config and credentials",
+        ),
+    }
+
+    let Some(config) = config else {
+        return finish(&list);
+    };
+
+    println!();
+    println!("  Odds sources");
+    let mut sources: Vec<_> = config.odds_sources.iter().collect();
+    sources.sort_by_key(|(name, _)| name.as_str());
+    for (name, source) in sources {
+        if source.source_type == "pinnacle" {
+            let (Some(username), Some(password)) =
+                (env_var("PINNACLE_USERNAME"), env_var("PINNACLE_PASSWORD"))
+            else {
+                list.push(
+                    name.as_str(),
+                    CheckStatus::Fail,
+                    "PINNACLE_USERNAME / PINNACLE_PASSWORD not set",
+                );
+                continue;
+            };
+            let base_url = source.base_url.as_deref().unwrap_or(PINNACLE_BASE_URL);
+            let feed = PinnacleFeed::new(
+                username,
+                password,
+                base_url,
+                source.request_timeout_ms,
+                network.client_builder(name),
+            );
+            list.result(name, feed.check_auth().await, |_| {
+                "credentials accepted".to_string()
+            });
+            continue;
+        }
+        if source.source_type != "the-odds-api" {
+            list.push(name.as_str(), CheckStatus::Skip, "no key to verify");
+            continue;
+        }
+        let Some(key) = env_var("ODDS_API_KEY") else {
+            list.push(name.as_str(), CheckStatus::Fail, "ODDS_API_KEY is not set");
+            continue;
+        };
+        let base_url = source
+            .base_url
+            .as_deref()
+            .unwrap_or("https://api.the-odds-api.com");
+        let mut api = TheOddsApi::new(key, base_url, "", network.client_builder(name));
+        list.result(name, api.check_quota().await, |q| {
+            format!(
+                "{} used, {} remaining",
+                q.requests_used, q.requests_remaining
+            )
+        });
+    }
+
+    println!();
+    println!("  Score feeds");
+    let client = network
+        .client_builder(network::SCORE_FEED)
+        .build()
+        .context("failed to build HTTP client")?;
+    let mut probed = BTreeSet::new();
+    let mut sports: Vec<_> = config.sports.iter().filter(|(_, s)| s.enabled).collect();
+    sports.sort_by_key(|(key, _)| key.as_str());
+    for (key, sport) in sports {
+        let Some(sf) = &sport.score_feed else {
+            continue;
+        };
+        let urls = [
+            ("primary", Some(&sf.primary_url)),
+            ("fallback", sf.fallback_url.as_ref()),
+        ];
+        for (role, url) in urls {
+            let Some(url) = url else {
+                continue;
+            };
+            if !probed.insert(url.clone()) {
+                continue;
+            }
+            let name = format!("{} {}", key, role);
+            list.result(
+                &name,
+                probe_url(&client, url, sf.request_timeout_ms).await,
+                |d| d.clone(),
+            );
+        }
+    }
+    if probed.is_empty() {
+        list.push(
+            "score feeds",
+            CheckStatus::Skip,
+            "no enabled sport uses one",
+        );
+    }
+
+    println!();
+    println!("  Polymarket");
+    let client = network
+        .client_builder(network::POLYMARKET)
+        .build()
+        .context("failed to build HTTP client")?;
+    check_polymarket(&mut list, &client, &config.polymarket).await;
+
+    finish(&list)
+}
+
+async fn check_polymarket(list: &mut Checklist, client: &reqwest::Client, cfg: &PolymarketConfig) {
+    if !cfg.enabled {
+        list.push("polymarket", CheckStatus::Skip, "disabled in config");
+        return;
+    }
+    let gamma = format!("{}/events?limit=1", cfg.gamma_url.trim_end_matches('/'));
+    list.result(
+        "gamma reachable",
+        probe_url(client, &gamma, 5000).await,
+        |d| d.clone(),
+    );
+
+    let Some(key_path) = env_var("POLYMARKET_PRIVATE_KEY_PATH") else {
+        list.push(
+            "trading credentials",
+            CheckStatus::Skip,
+            "POLYMARKET_PRIVATE_KEY_PATH not set (books only)",
+        );
+        return;
+    };
+    let auth = read_key_file(&key_path).and_then(|pk| {
+        PolymarketAuth::new(
+            &pk,
+            env_var("POLYMARKET_API_KEY").context("POLYMARKET_API_KEY is not set")?,
+            &env_var("POLYMARKET_API_SECRET").context("POLYMARKET_API_SECRET is not set")?,
+            env_var("POLYMARKET_API_PASSPHRASE").context("POLYMARKET_API_PASSPHRASE is not set")?,
+            env_var("POLYMARKET_FUNDER"),
+            cfg.signature_type,
+        )
+    });
+    list.result("trading credentials", auth, |a| {
+        format!("wallet {}", a.address())
+    });
+}
+
+fn finish(list: &Checklist) -> Result<()> {
+    let failed = list.count(CheckStatus::Fail);
+    println!();
+    println!(
+        "  {} passed, {} failed, {} skipped",
+        list.count(CheckStatus::Pass),
+        failed,
+        list.count(CheckStatus::Skip)
+    );
+    if failed > 0 {
+        anyhow::bail!("{} check(s) failed", failed);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_clock_skew_uses_request_midpoint() {
+        let server = chrono::Utc.with_ymd_and_hms(2026, 1, 20, 0, 0, 0).unwrap();
+        let sent = server + chrono::Duration::milliseconds(2_800);
+        let received = sent + chrono::Duration::milliseconds(400);
+        assert!((clock_skew_secs(sent, received, server) - 3.0).abs() < 1e-9);
+        let behind = server - chrono::Duration::seconds(7);
+        assert!(clock_skew_secs(behind, behind, server) < -MAX_CLOCK_SKEW_SECS);
+    }
+
+    #[test]
+    fn test_validate_config_flags_dangling_references() {
+        let mut config: Config =
+            toml::from_str(&std::fs::read_to_string("config.toml").unwrap()).unwrap();
+        assert!(validate_config(&config).is_empty());
+
+        let sport = config.sports.get_mut("basketball").unwrap();
+        sport.odds_source = "missing".to_string();
+        sport.score_feed = None;
+        let problems = validate_config(&config);
+        assert_eq!(problems.len(), 2, "{:?}", problems);
+        assert!(problems[0].contains("odds_source \"missing\""));
+        assert!(problems[1].contains("needs [score_feed]"));
+    }
+}
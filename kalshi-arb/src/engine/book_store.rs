@@ -0,0 +1,201 @@
+//! Sharded per-ticker book store. Tickers hash into a fixed set of shards,
+//! each behind its own `RwLock`, so the WS task writing one book does not
+//! block the engine or the display tick reading others. An epoch counter
+//! advances on every book change; readers compare it to the epoch they last
+//! saw to skip rebuilding views when nothing moved.
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+const SHARDS: usize = 16;
+
+type Shard<B> = RwLock<HashMap<String, B>>;
+
+pub struct BookStore<B> {
+    shards: Box<[Shard<B>]>,
+    hasher: RandomState,
+    epoch: AtomicU64,
+}
+
+impl<B> Default for BookStore<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B> BookStore<B> {
+    pub fn new() -> Self {
+        Self {
+            shards: (0..SHARDS).map(|_| RwLock::new(HashMap::new())).collect(),
+            hasher: RandomState::new(),
+            epoch: AtomicU64::new(0),
+        }
+    }
+
+    fn shard(&self, ticker: &str) -> &Shard<B> {
+        &self.shards[self.hasher.hash_one(ticker) as usize % SHARDS]
+    }
+
+    // A writer that panicked mid-update leaves at worst one stale book,
+    // which the next snapshot replaces, so poisoning is ignored.
+    fn read_shard(shard: &Shard<B>) -> RwLockReadGuard<'_, HashMap<String, B>> {
+        shard.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write_shard(shard: &Shard<B>) -> RwLockWriteGuard<'_, HashMap<String, B>> {
+        shard.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn bump(&self) {
+        self.epoch.fetch_add(1, Ordering::Release);
+    }
+
+    /// Advances whenever a book is inserted, updated or removed.
+    pub fn epoch(&self) -> u64 {
+        self.epoch.load(Ordering::Acquire)
+    }
+
+    /// Read `ticker`'s book, or `None` if there is none.
+    pub fn read<R>(&self, ticker: &str, f: impl FnOnce(&B) -> R) -> Option<R> {
+        Self::read_shard(self.shard(ticker)).get(ticker).map(f)
+    }
+
+    /// Mutate `ticker`'s book in place without advancing the epoch, for
+    /// state riding along with the book such as the liquidity sampler.
+    pub fn with_mut<R>(&self, ticker: &str, f: impl FnOnce(&mut B) -> R) -> Option<R> {
+        Self::write_shard(self.shard(ticker)).get_mut(ticker).map(f)
+    }
+
+    /// Update `ticker`'s book, creating it with `new` first if missing.
+    pub fn upsert<R>(
+        &self,
+        ticker: &str,
+        new: impl FnOnce() -> B,
+        f: impl FnOnce(&mut B) -> R,
+    ) -> R {
+        let mut shard = Self::write_shard(self.shard(ticker));
+        // Check first so updates to an existing book don't allocate a key
+        if !shard.contains_key(ticker) {
+            shard.insert(ticker.to_string(), new());
+        }
+        let result = f(shard.get_mut(ticker).expect("inserted above"));
+        self.bump();
+        result
+    }
+
+    pub fn insert(&self, ticker: String, book: B) {
+        Self::write_shard(self.shard(&ticker)).insert(ticker, book);
+        self.bump();
+    }
+
+    pub fn remove(&self, ticker: &str) -> Option<B> {
+        let removed = Self::write_shard(self.shard(ticker)).remove(ticker);
+        if removed.is_some() {
+            self.bump();
+        }
+        removed
+    }
+
+    /// Keep only the books `keep` accepts. Returns how many were dropped.
+    pub fn retain(&self, mut keep: impl FnMut(&str, &B) -> bool) -> usize {
+        let mut dropped = 0;
+        for shard in self.shards.iter() {
+            let mut shard = Self::write_shard(shard);
+            let before = shard.len();
+            shard.retain(|ticker, book| keep(ticker, book));
+            dropped += before - shard.len();
+        }
+        if dropped > 0 {
+            self.bump();
+        }
+        dropped
+    }
+
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|s| Self::read_shard(s).len()).sum()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|s| Self::read_shard(s).is_empty())
+    }
+
+    /// Visit every book, one shard locked at a time. Books in different
+    /// shards may be seen at slightly different moments.
+    pub fn for_each(&self, mut f: impl FnMut(&str, &B)) {
+        for shard in self.shards.iter() {
+            for (ticker, book) in Self::read_shard(shard).iter() {
+                f(ticker, book);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_epoch_tracks_changes_only() {
+        let store: BookStore<u32> = BookStore::new();
+        assert!(store.is_empty());
+        store.insert("A".to_string(), 1);
+        assert_eq!(store.epoch(), 1);
+
+        assert_eq!(store.read("A", |b| *b), Some(1));
+        assert_eq!(store.with_mut("A", |b| *b += 1), Some(()));
+        assert_eq!(store.read("B", |b| *b), None);
+        assert_eq!(store.epoch(), 1);
+
+        assert_eq!(store.upsert("B", || 10, |b| *b + 1), 11);
+        assert_eq!(store.upsert("A", || 10, |b| *b), 2);
+        assert_eq!(store.epoch(), 3);
+
+        assert_eq!(store.remove("missing"), None);
+        assert_eq!(store.epoch(), 3);
+        assert_eq!(store.remove("B"), Some(10));
+        assert_eq!(store.epoch(), 4);
+    }
+
+    #[test]
+    fn test_retain_and_iterate_across_shards() {
+        let store = BookStore::new();
+        for i in 0..100u32 {
+            store.insert(format!("T-{}", i), i);
+        }
+        assert_eq!(store.len(), 100);
+        assert_eq!(store.retain(|_, &b| b % 2 == 0), 50);
+        assert_eq!(store.retain(|_, _| true), 0);
+
+        let mut sum = 0;
+        store.for_each(|_, &b| sum += b);
+        assert_eq!(sum, (0..100).filter(|b| b % 2 == 0).sum::<u32>());
+        assert_eq!(store.read("T-42", |b| *b), Some(42));
+        assert_eq!(store.read("T-41", |b| *b), None);
+    }
+
+    #[test]
+    fn test_concurrent_writers() {
+        let store = std::sync::Arc::new(BookStore::new());
+        let writers: Vec<_> = (0..4)
+            .map(|_| {
+                let store = store.clone();
+                std::thread::spawn(move || {
+                    for i in 0..250 {
+                        store.upsert(&format!("T-{}", i % 50), || 0u32, |b| *b += 1);
+                    }
+                })
+            })
+            .collect();
+        for w in writers {
+            w.join().unwrap();
+        }
+        let mut total = 0;
+        store.for_each(|_, &b| total += b);
+        assert_eq!(total, 1000);
+        assert_eq!(store.epoch(), 1000);
+    }
+}
@@ -0,0 +1,141 @@
+//! Periodic pruning of per-game state so long sessions stay bounded.
+//!
+//! The market index, order books and per-ticker trackers only ever grow while
+//! the engine runs. A compaction pass drops games that have settled or are
+//! past their retention window, then everything keyed by their tickers.
+
+use super::matcher::{IndexedGame, MarketIndex};
+use chrono::NaiveDate;
+use std::collections::HashSet;
+
+/// Kalshi market statuses after trading has ended.
+const FINISHED_STATUSES: [&str; 4] = ["closed", "settled", "finalized", "determined"];
+
+/// Entries dropped by one compaction pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CompactionReport {
+    pub games: usize,
+    pub books: usize,
+    pub trackers: usize,
+}
+
+impl CompactionReport {
+    pub fn total(&self) -> usize {
+        self.games + self.books + self.trackers
+    }
+}
+
+/// A game whose Kalshi markets have all stopped trading.
+fn is_finished(game: &IndexedGame) -> bool {
+    let mut kalshi_sides = [&game.home, &game.away, &game.draw]
+        .into_iter()
+        .chain(game.periods.iter().flat_map(|p| [&p.home, &p.away]))
+        .flatten()
+        .peekable();
+    kalshi_sides.peek().is_some()
+        && kalshi_sides.all(|sm| FINISHED_STATUSES.contains(&sm.status.as_str()))
+}
+
+/// Drop games that have finished, or whose date is more than `retention_days`
+/// before `today`. Games with a ticker in `held` (open positions, resting
+/// orders) are kept regardless. Returns the number of games removed.
+pub fn compact_index(
+    index: &mut MarketIndex,
+    today: NaiveDate,
+    retention_days: u32,
+    held: &HashSet<String>,
+) -> usize {
+    let before = index.len();
+    let cutoff = today - chrono::Days::new(retention_days as u64);
+    index.retain(|key, game| {
+        game.sides().any(|sm| held.contains(&sm.ticker))
+            || !(key.date < cutoff || is_finished(game))
+    });
+    before - index.len()
+}
+
+/// Every ticker (and Polymarket token id) still in the index.
+pub fn indexed_tickers(index: &MarketIndex) -> HashSet<String> {
+    index
+        .values()
+        .flat_map(|game| game.sides().map(|sm| sm.ticker.clone()))
+        .collect()
+}
+
+/// Resident set size of this process, where the platform exposes it.
+pub fn rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    parse_vm_rss(&status)
+}
+
+/// `VmRSS` from a `/proc/<pid>/status` dump, in bytes.
+fn parse_vm_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find(|l| l.starts_with("VmRSS:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::matcher::{MarketKey, SideMarket};
+
+    fn side(ticker: &str, status: &str) -> Option<SideMarket> {
+        Some(SideMarket {
+            ticker: ticker.to_string(),
+            title: String::new(),
+            yes_bid: 0,
+            yes_ask: 0,
+            no_bid: 0,
+            no_ask: 0,
+            status: status.to_string(),
+            close_time: None,
+        })
+    }
+
+    fn insert(index: &mut MarketIndex, date: NaiveDate, team: &str, status: &str) {
+        let key = MarketKey {
+            sport: "basketball".to_string(),
+            date,
+            teams: [team.to_string(), "ZZZ".to_string()],
+        };
+        index.insert(
+            key,
+            IndexedGame {
+                home: side(&format!("{}-HOME", team), status),
+                away: side(&format!("{}-AWAY", team), status),
+                ..IndexedGame::default()
+            },
+        );
+    }
+
+    #[test]
+    fn test_compact_drops_finished_and_expired_games() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let yesterday = NaiveDate::from_ymd_opt(2026, 3, 9).unwrap();
+        let last_week = NaiveDate::from_ymd_opt(2026, 3, 3).unwrap();
+        let mut index = MarketIndex::new();
+        insert(&mut index, today, "LIVE", "active");
+        insert(&mut index, yesterday, "LATE", "active");
+        insert(&mut index, today, "DONE", "settled");
+        insert(&mut index, last_week, "OLD", "active");
+        insert(&mut index, last_week, "HELD", "active");
+
+        let held = HashSet::from(["HELD-HOME".to_string()]);
+        assert_eq!(compact_index(&mut index, today, 1, &held), 2);
+
+        let tickers = indexed_tickers(&index);
+        assert!(tickers.contains("LIVE-AWAY"));
+        assert!(tickers.contains("LATE-HOME"));
+        assert!(tickers.contains("HELD-AWAY"));
+        assert!(!tickers.contains("DONE-HOME"));
+        assert!(!tickers.contains("OLD-HOME"));
+    }
+
+    #[test]
+    fn test_parse_vm_rss() {
+        let status = "Name:\tkalshi-arb\nVmPeak:\t  300000 kB\nVmRSS:\t  123456 kB\n";
+        assert_eq!(parse_vm_rss(status), Some(123456 * 1024));
+        assert_eq!(parse_vm_rss("Name:\tkalshi-arb\n"), None);
+    }
+}
@@ -0,0 +1,311 @@
+//! Cross-venue arbitrage detection.
+//!
+//! In a two-way game exactly one team wins, so buying YES on one team at one
+//! venue and its complement at the other pays 100c per pair whatever happens.
+//! When the two asks plus both venues' taker fees sum under 100c, the pair
+//! locks in a profit independent of fair value.
+
+use super::fees::calculate_fee;
+use super::matcher::{MarketIndex, SideMarket, Venue};
+
+/// Best ask for buying one side of a market, and the size resting at it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quote {
+    pub price: u32,
+    pub size: i64,
+}
+
+/// One leg of an arbitrage pair, bought as a taker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArbLeg {
+    pub venue: Venue,
+    pub ticker: String,
+    /// "yes" or "no". Polymarket legs are always "yes" on an outcome token.
+    pub side: String,
+    pub price: u32,
+    /// Taker fee for the whole leg (cents).
+    pub fee: u32,
+}
+
+/// A risk-free pair: `yes` pays if `team` wins, `no` pays if it doesn't.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArbSignal {
+    pub team: String,
+    pub yes: ArbLeg,
+    pub no: ArbLeg,
+    pub quantity: u32,
+    /// Guaranteed profit across all pairs after fees (cents).
+    pub profit_cents: i32,
+}
+
+impl ArbSignal {
+    /// Cash outlay for both legs, fees included.
+    pub fn cost_cents(&self) -> u32 {
+        (self.yes.price + self.no.price) * self.quantity + self.yes.fee + self.no.fee
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ArbParams {
+    /// Pairs per signal, before capping by the size at both asks.
+    pub max_quantity: u32,
+    /// Smallest total profit worth signalling (cents).
+    pub min_profit_cents: u32,
+    /// Polymarket taker fee in basis points of notional.
+    pub polymarket_fee_bps: u32,
+}
+
+/// Taker fee for `quantity` contracts at `price` on `venue`.
+pub fn taker_fee(venue: Venue, price: u32, quantity: u32, polymarket_fee_bps: u32) -> u32 {
+    match venue {
+        Venue::Kalshi => calculate_fee(price, quantity, true),
+        Venue::Polymarket => {
+            let notional = price as u64 * quantity as u64;
+            (notional * polymarket_fee_bps as u64).div_ceil(10_000) as u32
+        }
+    }
+}
+
+/// A side that can be bought, with its current best ask.
+#[derive(Debug, Clone)]
+pub struct LegQuote<'a> {
+    pub venue: Venue,
+    pub ticker: &'a str,
+    pub side: &'a str,
+    pub quote: Quote,
+}
+
+/// Price the pair `yes` + `no` at the size both asks support.
+pub fn evaluate_pair(
+    team: &str,
+    yes: &LegQuote,
+    no: &LegQuote,
+    params: &ArbParams,
+) -> Option<ArbSignal> {
+    let size = yes.quote.size.min(no.quote.size).max(0);
+    let quantity = (size as u64).min(params.max_quantity as u64) as u32;
+    if quantity == 0 || yes.quote.price == 0 || no.quote.price == 0 {
+        return None;
+    }
+    let leg = |q: &LegQuote| ArbLeg {
+        venue: q.venue,
+        ticker: q.ticker.to_string(),
+        side: q.side.to_string(),
+        price: q.quote.price,
+        fee: taker_fee(q.venue, q.quote.price, quantity, params.polymarket_fee_bps),
+    };
+    let (yes, no) = (leg(yes), leg(no));
+    let payout = 100 * quantity as i64;
+    let cost = ((yes.price + no.price) * quantity + yes.fee + no.fee) as i64;
+    let profit = payout - cost;
+    if profit <= 0 || profit < params.min_profit_cents as i64 {
+        return None;
+    }
+    Some(ArbSignal {
+        team: team.to_string(),
+        yes,
+        no,
+        quantity,
+        profit_cents: profit as i32,
+    })
+}
+
+fn is_open(m: &SideMarket) -> bool {
+    m.status == "open" || m.status == "active"
+}
+
+fn leg_quote<'a>(
+    quote: &impl Fn(&str, &str) -> Option<Quote>,
+    venue: Venue,
+    market: &'a SideMarket,
+    side: &'a str,
+) -> Option<LegQuote<'a>> {
+    quote(&market.ticker, side).map(|q| LegQuote {
+        venue,
+        ticker: &market.ticker,
+        side,
+        quote: q,
+    })
+}
+
+/// Scan every two-way game listed on both venues. `quote(ticker, side)`
+/// returns the best ask for buying `side`. Results are most profitable first.
+pub fn scan(
+    index: &MarketIndex,
+    quote: impl Fn(&str, &str) -> Option<Quote>,
+    params: &ArbParams,
+) -> Vec<ArbSignal> {
+    let mut signals = Vec::new();
+    for game in index.values() {
+        if game.draw.is_some() {
+            continue;
+        }
+        let teams = [
+            (
+                &game.home_team,
+                &game.home,
+                &game.polymarket_home,
+                &game.polymarket_away,
+            ),
+            (
+                &game.away_team,
+                &game.away,
+                &game.polymarket_away,
+                &game.polymarket_home,
+            ),
+        ];
+        for (team, kalshi, poly_team, poly_opponent) in teams {
+            let (Some(kalshi), Some(poly_team), Some(poly_opponent)) =
+                (kalshi, poly_team, poly_opponent)
+            else {
+                continue;
+            };
+            if !is_open(kalshi) || !is_open(poly_team) || !is_open(poly_opponent) {
+                continue;
+            }
+            let leg = |venue, market, side| leg_quote(&quote, venue, market, side);
+            // Kalshi YES on the team, hedged by the opponent's Polymarket token
+            if let (Some(yes), Some(no)) = (
+                leg(Venue::Kalshi, kalshi, "yes"),
+                leg(Venue::Polymarket, poly_opponent, "yes"),
+            ) {
+                signals.extend(evaluate_pair(team, &yes, &no, params));
+            }
+            // The team's Polymarket token, hedged by Kalshi NO
+            if let (Some(yes), Some(no)) = (
+                leg(Venue::Polymarket, poly_team, "yes"),
+                leg(Venue::Kalshi, kalshi, "no"),
+            ) {
+                signals.extend(evaluate_pair(team, &yes, &no, params));
+            }
+        }
+    }
+    signals.sort_by(|a, b| {
+        b.profit_cents
+            .cmp(&a.profit_cents)
+            .then_with(|| a.yes.ticker.cmp(&b.yes.ticker))
+    });
+    signals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::matcher::IndexedGame;
+    use std::collections::HashMap;
+
+    fn market(ticker: &str) -> SideMarket {
+        SideMarket {
+            ticker: ticker.to_string(),
+            title: String::new(),
+            yes_bid: 0,
+            yes_ask: 0,
+            no_bid: 0,
+            no_ask: 0,
+            status: "open".to_string(),
+            close_time: None,
+        }
+    }
+
+    const PARAMS: ArbParams = ArbParams {
+        max_quantity: 10,
+        min_profit_cents: 1,
+        polymarket_fee_bps: 0,
+    };
+
+    #[test]
+    fn test_pair_profit_is_net_of_both_fees() {
+        let yes = LegQuote {
+            venue: Venue::Kalshi,
+            ticker: "KXNBAGAME-LAL",
+            side: "yes",
+            quote: Quote {
+                price: 45,
+                size: 50,
+            },
+        };
+        let no = LegQuote {
+            venue: Venue::Polymarket,
+            ticker: "222",
+            side: "yes",
+            quote: Quote { price: 50, size: 6 },
+        };
+        let signal = evaluate_pair("LAL", &yes, &no, &PARAMS).unwrap();
+        assert_eq!(signal.quantity, 6, "capped by the thinner ask");
+        // Kalshi fee ceil(7 * 6 * 45 * 55 / 10_000) = 11; 600 - 570 - 11 = 19
+        assert_eq!(signal.yes.fee, 11);
+        assert_eq!(signal.profit_cents, 19);
+        assert_eq!(signal.cost_cents(), 581);
+
+        // A 2% Polymarket fee (6c) still leaves a profit; a 54c ask leaves none
+        let with_fee = ArbParams {
+            polymarket_fee_bps: 200,
+            ..PARAMS
+        };
+        assert_eq!(
+            evaluate_pair("LAL", &yes, &no, &with_fee)
+                .unwrap()
+                .profit_cents,
+            13
+        );
+        let tight = LegQuote {
+            quote: Quote { price: 54, size: 6 },
+            ..no.clone()
+        };
+        assert!(evaluate_pair("LAL", &yes, &tight, &PARAMS).is_none());
+    }
+
+    #[test]
+    fn test_scan_pairs_each_team_across_venues() {
+        let mut index: MarketIndex = HashMap::new();
+        let game = IndexedGame {
+            away: Some(market("K-LAL")),
+            home: Some(market("K-BOS")),
+            draw: None,
+            away_team: "LAL".to_string(),
+            home_team: "BOS".to_string(),
+            polymarket_away: Some(market("P-LAL")),
+            polymarket_home: Some(market("P-BOS")),
+            periods: Vec::new(),
+        };
+        index.insert(
+            crate::engine::matcher::generate_key(
+                "basketball",
+                "LAL",
+                "BOS",
+                chrono::NaiveDate::from_ymd_opt(2026, 1, 20).unwrap(),
+            )
+            .unwrap(),
+            game,
+        );
+
+        let asks: HashMap<(&str, &str), u32> = HashMap::from([
+            (("K-LAL", "yes"), 44),
+            (("K-LAL", "no"), 58),
+            (("K-BOS", "yes"), 54),
+            (("K-BOS", "no"), 48),
+            (("P-LAL", "yes"), 47),
+            (("P-BOS", "yes"), 51),
+        ]);
+        let quote = |t: &str, s: &str| asks.get(&(t, s)).map(|&price| Quote { price, size: 10 });
+        let signals = scan(&index, quote, &PARAMS);
+        // Only K-LAL YES (44) + P-BOS (51) clears 100c after Kalshi's fee
+        assert_eq!(signals.len(), 1, "{:?}", signals);
+        let s = &signals[0];
+        assert_eq!(s.team, "LAL");
+        assert_eq!(
+            (s.yes.venue, s.yes.ticker.as_str()),
+            (Venue::Kalshi, "K-LAL")
+        );
+        assert_eq!(
+            (s.no.venue, s.no.ticker.as_str()),
+            (Venue::Polymarket, "P-BOS")
+        );
+
+        index.values_mut().next().unwrap().draw = Some(market("K-TIE"));
+        assert!(
+            scan(&index, quote, &PARAMS).is_empty(),
+            "three-way games never arb"
+        );
+    }
+}
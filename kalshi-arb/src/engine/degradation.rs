@@ -0,0 +1,210 @@
+//! Feed degradation tiers. The engine keeps trading with whatever feeds are
+//! left, but less aggressively: with a fair-value feed down, entries are
+//! limited to maker orders; with only the Kalshi book left, no new entries
+//! are placed. Exits are allowed in every tier.
+
+use std::time::{Duration, Instant};
+
+/// Trading tier, ordered from healthiest to most degraded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FeedTier {
+    /// Every feed the enabled sports use is up.
+    #[default]
+    Full,
+    /// One fair-value feed is down; the rest still price markets.
+    Degraded,
+    /// Only the Kalshi book is left, or the Kalshi WS itself is down.
+    Minimal,
+}
+
+impl FeedTier {
+    pub fn label(self) -> &'static str {
+        match self {
+            FeedTier::Full => "FULL",
+            FeedTier::Degraded => "DEGRADED",
+            FeedTier::Minimal => "MINIMAL",
+        }
+    }
+
+    /// Whether a new entry may be placed in this tier.
+    pub fn allows_entry(self, is_taker: bool) -> bool {
+        match self {
+            FeedTier::Full => true,
+            FeedTier::Degraded => !is_taker,
+            FeedTier::Minimal => false,
+        }
+    }
+}
+
+/// Health of each feed. `None` for a feed no enabled sport uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeedHealth {
+    pub kalshi_ws: bool,
+    pub scores: Option<bool>,
+    pub odds: Option<bool>,
+}
+
+impl FeedHealth {
+    /// A feed is down once every sport using it has failed `threshold`
+    /// fetches in a row; `None` when no sport uses it.
+    pub fn feed_up(failures: impl IntoIterator<Item = u32>, threshold: u32) -> Option<bool> {
+        let mut used = false;
+        for f in failures {
+            if f < threshold.max(1) {
+                return Some(true);
+            }
+            used = true;
+        }
+        used.then_some(false)
+    }
+
+    pub fn tier(&self) -> FeedTier {
+        if !self.kalshi_ws {
+            return FeedTier::Minimal;
+        }
+        let feeds = [self.scores, self.odds];
+        let used = feeds.iter().flatten().count();
+        let down = feeds.iter().filter(|f| **f == Some(false)).count();
+        match down {
+            0 => FeedTier::Full,
+            d if d < used => FeedTier::Degraded,
+            _ => FeedTier::Minimal,
+        }
+    }
+
+    /// The feeds that are down, for logging a transition.
+    pub fn describe_down(&self) -> String {
+        let mut down = Vec::new();
+        if !self.kalshi_ws {
+            down.push("Kalshi WS");
+        }
+        if self.scores == Some(false) {
+            down.push("score feed");
+        }
+        if self.odds == Some(false) {
+            down.push("odds feed");
+        }
+        if down.is_empty() {
+            "all feeds up".to_string()
+        } else {
+            format!("{} down", down.join(", "))
+        }
+    }
+}
+
+/// Current tier with hysteresis: downgrades apply at once, upgrades only
+/// after the healthier tier has held for the recovery period.
+#[derive(Debug, Clone, Default)]
+pub struct DegradationMonitor {
+    tier: FeedTier,
+    upgrade_since: Option<Instant>,
+}
+
+impl DegradationMonitor {
+    /// Returns the new tier when it changes.
+    pub fn observe(
+        &mut self,
+        health: &FeedHealth,
+        now: Instant,
+        recover: Duration,
+    ) -> Option<FeedTier> {
+        let target = health.tier();
+        if target > self.tier {
+            self.tier = target;
+            self.upgrade_since = None;
+            return Some(target);
+        }
+        if target == self.tier {
+            self.upgrade_since = None;
+            return None;
+        }
+        let since = *self.upgrade_since.get_or_insert(now);
+        if now.duration_since(since) < recover {
+            return None;
+        }
+        self.tier = target;
+        self.upgrade_since = None;
+        Some(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn health(ws: bool, scores: Option<bool>, odds: Option<bool>) -> FeedHealth {
+        FeedHealth {
+            kalshi_ws: ws,
+            scores,
+            odds,
+        }
+    }
+
+    #[test]
+    fn test_tier_from_health() {
+        assert_eq!(health(true, Some(true), Some(true)).tier(), FeedTier::Full);
+        assert_eq!(
+            health(true, Some(false), Some(true)).tier(),
+            FeedTier::Degraded
+        );
+        assert_eq!(
+            health(true, Some(false), Some(false)).tier(),
+            FeedTier::Minimal
+        );
+        assert_eq!(
+            health(false, Some(true), Some(true)).tier(),
+            FeedTier::Minimal
+        );
+        // Odds-only setups have nothing left once odds are down
+        assert_eq!(health(true, None, Some(false)).tier(), FeedTier::Minimal);
+        // Kalshi-only setups need just the book
+        assert_eq!(health(true, None, None).tier(), FeedTier::Full);
+
+        assert_eq!(FeedHealth::feed_up([3, 0], 3), Some(true));
+        assert_eq!(FeedHealth::feed_up([3, 5], 3), Some(false));
+        assert_eq!(FeedHealth::feed_up([], 3), None);
+
+        assert!(FeedTier::Degraded.allows_entry(false));
+        assert!(!FeedTier::Degraded.allows_entry(true));
+        assert!(!FeedTier::Minimal.allows_entry(false));
+    }
+
+    #[test]
+    fn test_downgrades_at_once_and_recovers_after_hold() {
+        let mut m = DegradationMonitor::default();
+        let start = Instant::now();
+        let recover = Duration::from_secs(30);
+        let degraded = health(true, Some(false), Some(true));
+        let full = health(true, Some(true), Some(true));
+
+        assert_eq!(
+            m.observe(&degraded, start, recover),
+            Some(FeedTier::Degraded)
+        );
+        assert_eq!(
+            m.observe(&full, start + Duration::from_secs(1), recover),
+            None
+        );
+        // A relapse restarts the recovery clock
+        assert_eq!(
+            m.observe(&degraded, start + Duration::from_secs(10), recover),
+            None
+        );
+        assert_eq!(
+            m.observe(&full, start + Duration::from_secs(20), recover),
+            None
+        );
+        assert_eq!(
+            m.observe(&full, start + Duration::from_secs(49), recover),
+            None
+        );
+        assert_eq!(
+            m.observe(&full, start + Duration::from_secs(50), recover),
+            Some(FeedTier::Full)
+        );
+        assert_eq!(
+            m.observe(&full, start + Duration::from_secs(51), recover),
+            None
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Slippage-aware taker sizing. A taker buy larger than the size resting at
+//! the best ask sweeps the levels above it, so sizing against the best ask
+//! alone overstates the edge. Walking the ask side prices every contract at
+//! the level it would actually take, and the order is cut to the largest
+//! size whose fill still clears the minimum net profit.
+
+use crate::engine::fees::calculate_fee;
+
+/// A taker buy walked across the ask side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepthFill {
+    pub quantity: u32,
+    /// Sum of the level prices paid, before fees.
+    pub cost_cents: u32,
+    /// Taker fees, charged per level taken.
+    pub entry_fee_cents: u32,
+    /// Highest level reached: the limit price that fills the whole quantity.
+    pub worst_price: u32,
+}
+
+impl DepthFill {
+    /// Net profit of holding to `fair_value`, after the taker entry fee, a
+    /// maker exit fee and `slippage_buffer_cents` per contract. Matches the
+    /// strategy's taker estimate when the fill takes one level.
+    pub fn net_profit(&self, fair_value: u32, slippage_buffer_cents: u8) -> i32 {
+        let exit_fee = calculate_fee(fair_value, self.quantity, false) as i32;
+        (fair_value * self.quantity) as i32
+            - self.cost_cents as i32
+            - self.entry_fee_cents as i32
+            - exit_fee
+            - slippage_buffer_cents as i32 * self.quantity as i32
+    }
+}
+
+/// Take up to `quantity` contracts from `asks` (price, contracts), cheapest
+/// first. Fills less than `quantity` when the book runs out.
+pub fn walk_asks(asks: &[(u32, i64)], quantity: u32) -> DepthFill {
+    let mut fill = DepthFill {
+        quantity: 0,
+        cost_cents: 0,
+        entry_fee_cents: 0,
+        worst_price: 0,
+    };
+    for &(price, size) in asks {
+        if fill.quantity == quantity {
+            break;
+        }
+        let take = (quantity - fill.quantity).min(size.max(0) as u32);
+        if take == 0 {
+            continue;
+        }
+        fill.quantity += take;
+        fill.cost_cents += price * take;
+        fill.entry_fee_cents += calculate_fee(price, take, true);
+        fill.worst_price = price;
+    }
+    fill
+}
+
+/// The largest taker buy of at most `quantity` contracts that the book can
+/// fill and that still nets `min_net_profit` against `fair_value`. Levels
+/// with no edge left after the slippage buffer are never taken, even when
+/// cheaper levels would carry the loss. `None` when not even one contract
+/// clears the bar.
+pub fn size_to_depth(
+    asks: &[(u32, i64)],
+    fair_value: u32,
+    quantity: u32,
+    min_net_profit: i32,
+    slippage_buffer_cents: u8,
+) -> Option<DepthFill> {
+    let edge_levels = asks
+        .iter()
+        .take_while(|&&(price, _)| price + (slippage_buffer_cents as u32) < fair_value)
+        .count();
+    let asks = &asks[..edge_levels];
+    (1..=quantity)
+        .rev()
+        .map(|q| walk_asks(asks, q))
+        .find(|fill| {
+            fill.quantity > 0
+                && fill.net_profit(fair_value, slippage_buffer_cents) >= min_net_profit
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_walk_prices_each_level() {
+        let asks = [(40, 3), (41, 2), (43, 10)];
+        let fill = walk_asks(&asks, 6);
+        assert_eq!(fill.quantity, 6);
+        assert_eq!(fill.cost_cents, 3 * 40 + 2 * 41 + 43);
+        assert_eq!(fill.worst_price, 43);
+        assert_eq!(
+            fill.entry_fee_cents,
+            calculate_fee(40, 3, true) + calculate_fee(41, 2, true) + calculate_fee(43, 1, true)
+        );
+
+        // The book runs out
+        assert_eq!(walk_asks(&asks, 50).quantity, 15);
+        assert_eq!(walk_asks(&[], 5).quantity, 0);
+
+        // One level: the strategy's best-ask estimate
+        let top = walk_asks(&asks, 3);
+        let fees = calculate_fee(40, 3, true) + calculate_fee(50, 3, false);
+        assert_eq!(top.net_profit(50, 0), (50 - 40) * 3 - fees as i32);
+    }
+
+    #[test]
+    fn test_size_shrinks_to_profitable_depth() {
+        // Fair 60: the top two levels pay; the 61c level loses on every
+        // contract, even though the cheaper levels would cover the loss
+        let asks = [(40, 3), (56, 2), (61, 5)];
+        assert!(walk_asks(&asks, 10).net_profit(60, 0) > 1);
+        let fill = size_to_depth(&asks, 60, 10, 1, 0).unwrap();
+        assert_eq!((fill.quantity, fill.worst_price), (5, 56));
+        // The slippage buffer takes the edge out of the 56c level too
+        assert_eq!(size_to_depth(&asks, 60, 10, 1, 4).unwrap().quantity, 3);
+
+        // Thin top of book and nothing behind it worth taking
+        assert!(size_to_depth(&[(45, 1), (49, 5)], 46, 5, 1, 0).is_none());
+        // A smaller size than the book offers is left alone
+        assert_eq!(size_to_depth(&asks, 60, 4, 1, 0).unwrap().quantity, 4);
+    }
+}
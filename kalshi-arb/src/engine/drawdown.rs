@@ -0,0 +1,142 @@
+//! Drawdown tiers. As the session's realized P&L falls from its peak, the
+//! engine steps down through soft limits before the hard one: first sizing
+//! is cut, then taker entries stop, and at the halt limit no new entries are
+//! placed for the rest of the session. Exits are allowed in every tier.
+
+use crate::config::DrawdownConfig;
+
+/// Trading tier, ordered from normal to most restricted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DrawdownTier {
+    #[default]
+    Normal,
+    /// Entries are sized down by `reduced_size_fraction`.
+    Reduced,
+    /// Sized down and limited to maker orders.
+    MakerOnly,
+    /// No new entries.
+    Halted,
+}
+
+impl DrawdownTier {
+    pub fn label(self) -> &'static str {
+        match self {
+            DrawdownTier::Normal => "NORMAL",
+            DrawdownTier::Reduced => "REDUCED",
+            DrawdownTier::MakerOnly => "MAKER ONLY",
+            DrawdownTier::Halted => "HALTED",
+        }
+    }
+
+    /// What the tier changes, for the banner and the log.
+    pub fn describe(self) -> &'static str {
+        match self {
+            DrawdownTier::Normal => "normal trading",
+            DrawdownTier::Reduced => "entry sizing reduced",
+            DrawdownTier::MakerOnly => "entry sizing reduced, taker orders disabled",
+            DrawdownTier::Halted => "new entries halted",
+        }
+    }
+
+    /// Whether a new entry may be placed in this tier.
+    pub fn allows_entry(self, is_taker: bool) -> bool {
+        match self {
+            DrawdownTier::Normal | DrawdownTier::Reduced => true,
+            DrawdownTier::MakerOnly => !is_taker,
+            DrawdownTier::Halted => false,
+        }
+    }
+
+    /// Bankroll entries are sized from in this tier.
+    pub fn sizing_bankroll(self, bankroll_cents: u64, config: &DrawdownConfig) -> u64 {
+        match self {
+            DrawdownTier::Normal => bankroll_cents,
+            _ => (bankroll_cents as f64 * config.reduced_size_fraction.clamp(0.0, 1.0)) as u64,
+        }
+    }
+
+    fn for_drawdown(drawdown_cents: u64, config: &DrawdownConfig) -> Self {
+        let hit = |limit: Option<u64>| limit.is_some_and(|l| drawdown_cents >= l);
+        if hit(config.halt_cents) {
+            DrawdownTier::Halted
+        } else if hit(config.maker_only_cents) {
+            DrawdownTier::MakerOnly
+        } else if hit(config.reduce_size_cents) {
+            DrawdownTier::Reduced
+        } else {
+            DrawdownTier::Normal
+        }
+    }
+}
+
+/// Session P&L peak and the current tier. The soft tiers follow the
+/// drawdown back down as P&L recovers; a halt holds for the session.
+#[derive(Debug, Clone, Default)]
+pub struct DrawdownMonitor {
+    peak_cents: i64,
+    drawdown_cents: u64,
+    tier: DrawdownTier,
+}
+
+impl DrawdownMonitor {
+    /// Returns the new tier when it changes.
+    pub fn observe(&mut self, pnl_cents: i64, config: &DrawdownConfig) -> Option<DrawdownTier> {
+        self.peak_cents = self.peak_cents.max(pnl_cents);
+        self.drawdown_cents = (self.peak_cents - pnl_cents) as u64;
+        if self.tier == DrawdownTier::Halted {
+            return None;
+        }
+        let tier = DrawdownTier::for_drawdown(self.drawdown_cents, config);
+        if tier == self.tier {
+            return None;
+        }
+        self.tier = tier;
+        Some(tier)
+    }
+
+    pub fn drawdown_cents(&self) -> u64 {
+        self.drawdown_cents
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> DrawdownConfig {
+        DrawdownConfig {
+            reduce_size_cents: Some(500),
+            maker_only_cents: Some(1_000),
+            halt_cents: Some(2_000),
+            reduced_size_fraction: 0.5,
+        }
+    }
+
+    #[test]
+    fn test_steps_down_from_peak_and_recovers_until_halt() {
+        let cfg = config();
+        let mut m = DrawdownMonitor::default();
+        assert_eq!(m.observe(800, &cfg), None);
+        // Drawdown is measured from the peak, not from zero
+        assert_eq!(m.observe(300, &cfg), Some(DrawdownTier::Reduced));
+        assert_eq!(m.drawdown_cents(), 500);
+        assert_eq!(m.observe(-200, &cfg), Some(DrawdownTier::MakerOnly));
+        assert_eq!(m.observe(400, &cfg), Some(DrawdownTier::Normal));
+        assert_eq!(m.observe(-1_200, &cfg), Some(DrawdownTier::Halted));
+        assert_eq!(m.observe(800, &cfg), None);
+
+        assert!(DrawdownTier::Reduced.allows_entry(true));
+        assert!(!DrawdownTier::MakerOnly.allows_entry(true));
+        assert!(DrawdownTier::MakerOnly.allows_entry(false));
+        assert!(!DrawdownTier::Halted.allows_entry(false));
+        assert_eq!(DrawdownTier::Normal.sizing_bankroll(10_000, &cfg), 10_000);
+        assert_eq!(DrawdownTier::Reduced.sizing_bankroll(10_000, &cfg), 5_000);
+    }
+
+    #[test]
+    fn test_unset_limits_never_trigger() {
+        let mut m = DrawdownMonitor::default();
+        assert_eq!(m.observe(-1_000_000, &DrawdownConfig::default()), None);
+        assert_eq!(m.drawdown_cents(), 1_000_000);
+    }
+}
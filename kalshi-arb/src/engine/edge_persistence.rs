@@ -0,0 +1,285 @@
+//! Edge persistence: how long a taker edge survives on the Kalshi book.
+//!
+//! An edge opens when an evaluation finds fair value above the best ask on
+//! either side, and closes on the first book update that lifts the ask to
+//! fair value or beyond. Lifetimes are kept per sport with the edge size at
+//! detection, giving a persistence histogram and, optionally, the smallest
+//! taker threshold whose edges usually outlive the engine's own latency.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Instant;
+
+/// Upper bounds (ms) of the histogram buckets; a final bucket holds the rest.
+pub const BUCKET_UPPER_MS: [u64; 7] = [100, 250, 500, 1_000, 2_000, 5_000, 10_000];
+/// Recent lifetimes kept per sport.
+pub const MAX_SAMPLES_PER_SPORT: usize = 2_000;
+/// Highest taker threshold auto-tuning will ask for.
+pub const MAX_TUNED_THRESHOLD: u8 = 30;
+
+/// Weight of the newest order latency in the running average.
+const LATENCY_EWMA_ALPHA: f64 = 0.2;
+
+/// One closed edge: its size at detection and how long the book left it open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdgeSample {
+    pub edge: u32,
+    pub lifetime_ms: u64,
+}
+
+#[derive(Debug, Clone)]
+struct OpenEdge {
+    sport: String,
+    /// Fair value of the side the edge is on (cents).
+    fair: u32,
+    edge: u32,
+    opened_at: Instant,
+}
+
+#[derive(Debug, Default)]
+struct SideEdges {
+    yes: Option<OpenEdge>,
+    no: Option<OpenEdge>,
+}
+
+#[derive(Debug, Default)]
+pub struct EdgePersistence {
+    open: HashMap<String, SideEdges>,
+    samples: HashMap<String, VecDeque<EdgeSample>>,
+    latency_ewma_ms: Option<f64>,
+}
+
+/// Taker edge for buying at `ask` against `fair`, if there is one.
+fn taker_edge(fair: u32, ask: u32) -> Option<u32> {
+    (ask > 0 && fair > ask).then(|| fair - ask)
+}
+
+impl EdgePersistence {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an evaluation of `ticker` at YES fair value `fair`. Opens an
+    /// edge on each side whose ask is below fair value; an edge that vanished
+    /// because fair value moved (not the book) is dropped unmeasured.
+    pub fn observe(
+        &mut self,
+        sport: &str,
+        ticker: &str,
+        fair: u32,
+        yes_ask: u32,
+        no_ask: u32,
+        now: Instant,
+    ) {
+        let sides = self.open.entry(ticker.to_string()).or_default();
+        let no_fair = 100u32.saturating_sub(fair);
+        for (slot, side_fair, ask) in [
+            (&mut sides.yes, fair, yes_ask),
+            (&mut sides.no, no_fair, no_ask),
+        ] {
+            match taker_edge(side_fair, ask) {
+                Some(edge) => match slot {
+                    Some(open) => open.fair = side_fair,
+                    None => {
+                        *slot = Some(OpenEdge {
+                            sport: sport.to_string(),
+                            fair: side_fair,
+                            edge,
+                            opened_at: now,
+                        })
+                    }
+                },
+                None => *slot = None,
+            }
+        }
+        if sides.yes.is_none() && sides.no.is_none() {
+            self.open.remove(ticker);
+        }
+    }
+
+    /// Apply a book update for `ticker`: close every open edge whose ask has
+    /// reached its fair value.
+    pub fn on_book(&mut self, ticker: &str, yes_ask: u32, no_ask: u32, now: Instant) {
+        let Some(sides) = self.open.get_mut(ticker) else {
+            return;
+        };
+        let mut closed = Vec::new();
+        for (slot, ask) in [(&mut sides.yes, yes_ask), (&mut sides.no, no_ask)] {
+            let still_open = slot
+                .as_ref()
+                .is_some_and(|open| taker_edge(open.fair, ask).is_some());
+            if !still_open {
+                closed.extend(slot.take());
+            }
+        }
+        if sides.yes.is_none() && sides.no.is_none() {
+            self.open.remove(ticker);
+        }
+        for open in closed {
+            let samples = self.samples.entry(open.sport).or_default();
+            if samples.len() == MAX_SAMPLES_PER_SPORT {
+                samples.pop_front();
+            }
+            samples.push_back(EdgeSample {
+                edge: open.edge,
+                lifetime_ms: now.duration_since(open.opened_at).as_millis() as u64,
+            });
+        }
+    }
+
+    /// Forget open edges on tickers not in `keep` (e.g. dropped by compaction)
+    /// without recording a lifetime. Returns the number forgotten.
+    pub fn retain_tickers(&mut self, keep: &HashSet<String>) -> usize {
+        let before = self.open.len();
+        self.open.retain(|ticker, _| keep.contains(ticker));
+        before - self.open.len()
+    }
+
+    /// Fold a measured signal-to-exchange order latency into the running average.
+    pub fn record_latency(&mut self, latency_ms: u64) {
+        let ms = latency_ms as f64;
+        self.latency_ewma_ms = Some(match self.latency_ewma_ms {
+            Some(avg) => avg + LATENCY_EWMA_ALPHA * (ms - avg),
+            None => ms,
+        });
+    }
+
+    /// Measured execution latency, or `assumed_ms` before any order was sent.
+    pub fn execution_latency_ms(&self, assumed_ms: u64) -> u64 {
+        self.latency_ewma_ms
+            .map_or(assumed_ms, |avg| avg.round() as u64)
+    }
+
+    pub fn samples(&self, sport: &str) -> impl Iterator<Item = &EdgeSample> {
+        self.samples.get(sport).into_iter().flatten()
+    }
+
+    /// Lifetime counts per `BUCKET_UPPER_MS` bucket, plus one for longer.
+    pub fn histogram(&self, sport: &str) -> [u64; BUCKET_UPPER_MS.len() + 1] {
+        let mut counts = [0u64; BUCKET_UPPER_MS.len() + 1];
+        for s in self.samples(sport) {
+            let bucket = BUCKET_UPPER_MS
+                .iter()
+                .position(|&upper| s.lifetime_ms < upper)
+                .unwrap_or(BUCKET_UPPER_MS.len());
+            counts[bucket] += 1;
+        }
+        counts
+    }
+
+    pub fn median_lifetime_ms(&self, sport: &str) -> Option<u64> {
+        let mut lifetimes: Vec<u64> = self.samples(sport).map(|s| s.lifetime_ms).collect();
+        if lifetimes.is_empty() {
+            return None;
+        }
+        lifetimes.sort_unstable();
+        Some(lifetimes[lifetimes.len() / 2])
+    }
+
+    /// Smallest taker threshold whose edges outlived `latency_ms` at least
+    /// `survival_target` of the time, judged on at least `min_samples` edges.
+    /// `None` when there is too little history or no threshold qualifies.
+    pub fn tuned_threshold(
+        &self,
+        sport: &str,
+        latency_ms: u64,
+        survival_target: f64,
+        min_samples: usize,
+    ) -> Option<u8> {
+        for threshold in 1..=MAX_TUNED_THRESHOLD {
+            let (total, survived) = self
+                .samples(sport)
+                .filter(|s| s.edge >= threshold as u32)
+                .fold((0usize, 0usize), |(total, survived), s| {
+                    (total + 1, survived + (s.lifetime_ms > latency_ms) as usize)
+                });
+            if total < min_samples.max(1) {
+                return None;
+            }
+            if survived as f64 / total as f64 >= survival_target {
+                return Some(threshold);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_book_closes_edge_and_fair_value_moves_drop_it() {
+        let t0 = Instant::now();
+        let mut ep = EdgePersistence::new();
+        // YES fair 60 vs ask 55: 5c edge; NO side has none
+        ep.observe("basketball", "T1", 60, 55, 46, t0);
+        // Re-observing keeps the original open time
+        ep.observe(
+            "basketball",
+            "T1",
+            61,
+            55,
+            46,
+            t0 + Duration::from_millis(100),
+        );
+        ep.on_book("T1", 58, 43, t0 + Duration::from_millis(200));
+        assert_eq!(ep.samples("basketball").count(), 0, "ask still below fair");
+        ep.on_book("T1", 61, 40, t0 + Duration::from_millis(400));
+        assert_eq!(
+            ep.samples("basketball").copied().collect::<Vec<_>>(),
+            vec![EdgeSample {
+                edge: 5,
+                lifetime_ms: 400
+            }]
+        );
+
+        // Fair value falling below the ask is not the book closing the edge
+        ep.observe("basketball", "T2", 60, 55, 46, t0);
+        ep.observe(
+            "basketball",
+            "T2",
+            50,
+            55,
+            46,
+            t0 + Duration::from_millis(50),
+        );
+        ep.on_book("T2", 70, 31, t0 + Duration::from_millis(60));
+        assert_eq!(ep.samples("basketball").count(), 1);
+        assert_eq!(
+            ep.histogram("basketball")[2],
+            1,
+            "400ms lands in [250, 500)"
+        );
+    }
+
+    #[test]
+    fn test_tuned_threshold_picks_edges_that_outlive_latency() {
+        let mut ep = EdgePersistence::new();
+        let t0 = Instant::now();
+        // Small edges (2c) vanish in 50ms; larger ones (6c) last 800ms
+        for (i, (edge, life)) in [(2u32, 50u64), (6, 800)]
+            .iter()
+            .cycle()
+            .take(40)
+            .enumerate()
+        {
+            let ticker = format!("T{}", i);
+            ep.observe("hockey", &ticker, 50 + edge, 50, 50, t0);
+            ep.on_book(&ticker, 99, 1, t0 + Duration::from_millis(*life));
+        }
+        assert_eq!(ep.median_lifetime_ms("hockey"), Some(800));
+        assert_eq!(ep.tuned_threshold("hockey", 300, 0.9, 10), Some(3));
+        assert_eq!(
+            ep.tuned_threshold("hockey", 300, 0.9, 25),
+            None,
+            "too few 6c edges"
+        );
+        assert_eq!(ep.tuned_threshold("hockey", 1_000, 0.9, 10), None);
+
+        assert_eq!(ep.execution_latency_ms(250), 250);
+        ep.record_latency(100);
+        ep.record_latency(200);
+        assert_eq!(ep.execution_latency_ms(250), 120);
+    }
+}
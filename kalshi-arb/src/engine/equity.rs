@@ -0,0 +1,187 @@
+//! Equity curve. Every `interval_secs` the engine samples equity, meaning
+//! balance plus open positions marked to the YES bid. The curve gives the
+//! session's peak, its deepest drawdown and a Sharpe-style ratio of the
+//! per-sample returns. Unlike `drawdown`, which acts on realized P&L, this
+//! also counts open positions and only reports.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::config::EquityConfig;
+
+/// Summary of the curve so far.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EquityStats {
+    pub start_cents: i64,
+    pub current_cents: i64,
+    pub peak_cents: i64,
+    /// Deepest fall from a running peak, in cents and as a fraction of
+    /// that peak.
+    pub max_drawdown_cents: i64,
+    pub max_drawdown_pct: f64,
+    /// Mean over standard deviation of the per-sample returns. `None` until
+    /// there are at least two returns and they vary.
+    pub sharpe: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EquityCurve {
+    samples: VecDeque<i64>,
+    interval: Duration,
+    max_samples: usize,
+    last_sample: Option<Instant>,
+}
+
+impl Default for EquityCurve {
+    fn default() -> Self {
+        Self::new(&EquityConfig::default())
+    }
+}
+
+impl EquityCurve {
+    pub fn new(config: &EquityConfig) -> Self {
+        Self {
+            samples: VecDeque::new(),
+            interval: Duration::from_secs(config.interval_secs),
+            max_samples: config.max_samples.max(2),
+            last_sample: None,
+        }
+    }
+
+    /// Whether a sample is due at `now`.
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.last_sample
+            .is_none_or(|at| now.saturating_duration_since(at) >= self.interval)
+    }
+
+    /// Record `equity_cents`, dropping the oldest sample past the cap.
+    pub fn record(&mut self, equity_cents: i64, now: Instant) {
+        if self.samples.len() >= self.max_samples {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(equity_cents);
+        self.last_sample = Some(now);
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn stats(&self) -> Option<EquityStats> {
+        let start = *self.samples.front()?;
+        let mut peak = start;
+        let mut max_drawdown = 0;
+        let mut max_drawdown_pct = 0.0;
+        for &equity in &self.samples {
+            peak = peak.max(equity);
+            let drawdown = peak - equity;
+            if drawdown > max_drawdown {
+                max_drawdown = drawdown;
+                max_drawdown_pct = if peak > 0 {
+                    drawdown as f64 / peak as f64
+                } else {
+                    0.0
+                };
+            }
+        }
+        Some(EquityStats {
+            start_cents: start,
+            current_cents: *self.samples.back()?,
+            peak_cents: peak,
+            max_drawdown_cents: max_drawdown,
+            max_drawdown_pct,
+            sharpe: self.sharpe(),
+        })
+    }
+
+    fn sharpe(&self) -> Option<f64> {
+        let returns: Vec<f64> = self
+            .samples
+            .iter()
+            .zip(self.samples.iter().skip(1))
+            .filter(|(&prev, _)| prev > 0)
+            .map(|(&prev, &next)| (next - prev) as f64 / prev as f64)
+            .collect();
+        if returns.len() < 2 {
+            return None;
+        }
+        let n = returns.len() as f64;
+        let mean = returns.iter().sum::<f64>() / n;
+        let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        let std_dev = variance.sqrt();
+        (std_dev > 0.0).then(|| mean / std_dev)
+    }
+
+    /// The curve squeezed into at most `width` points, each the mean of its
+    /// bucket of samples.
+    pub fn downsample(&self, width: usize) -> Vec<i64> {
+        if width == 0 || self.samples.is_empty() {
+            return Vec::new();
+        }
+        let bucket = self.samples.len().div_ceil(width);
+        let samples: Vec<i64> = self.samples.iter().copied().collect();
+        samples
+            .chunks(bucket)
+            .map(|c| c.iter().sum::<i64>() / c.len() as i64)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve(samples: &[i64]) -> EquityCurve {
+        let mut curve = EquityCurve::new(&EquityConfig {
+            interval_secs: 60,
+            max_samples: 4,
+        });
+        let start = Instant::now();
+        for (i, &s) in samples.iter().enumerate() {
+            curve.record(s, start + Duration::from_secs(60 * i as u64));
+        }
+        curve
+    }
+
+    #[test]
+    fn test_drawdown_from_running_peak() {
+        let stats = curve(&[10_000, 12_000, 9_000, 11_000]).stats().unwrap();
+        assert_eq!(stats.peak_cents, 12_000);
+        assert_eq!(stats.max_drawdown_cents, 3_000);
+        assert_eq!(stats.max_drawdown_pct, 0.25);
+        assert_eq!(stats.current_cents, 11_000);
+        assert!(stats.sharpe.is_some());
+
+        // Capped at four samples: the oldest is dropped
+        let stats = curve(&[5_000, 10_000, 12_000, 9_000, 11_000])
+            .stats()
+            .unwrap();
+        assert_eq!(stats.start_cents, 10_000);
+        assert!(EquityCurve::default().stats().is_none());
+    }
+
+    #[test]
+    fn test_sharpe_needs_varying_returns() {
+        assert_eq!(
+            curve(&[10_000, 10_000, 10_000]).stats().unwrap().sharpe,
+            None
+        );
+        assert_eq!(curve(&[10_000, 10_100]).stats().unwrap().sharpe, None);
+        let rising = curve(&[10_000, 10_100, 10_300, 10_400]).stats().unwrap();
+        assert!(rising.sharpe.unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_sampling_interval_and_downsample() {
+        let mut c = curve(&[]);
+        let now = Instant::now();
+        assert!(c.is_due(now));
+        c.record(100, now);
+        assert!(!c.is_due(now + Duration::from_secs(59)));
+        assert!(c.is_due(now + Duration::from_secs(60)));
+
+        let c = curve(&[100, 200, 300, 400]);
+        assert_eq!(c.downsample(2), vec![150, 350]);
+        assert_eq!(c.downsample(10), vec![100, 200, 300, 400]);
+    }
+}
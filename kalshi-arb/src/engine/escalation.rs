@@ -0,0 +1,175 @@
+//! Critical alert escalation for unattended live sessions.
+//!
+//! Critical alerts stay open until acknowledged in the TUI. One that is still
+//! unacknowledged after the timeout is handed out once by [`EscalationPolicy::due`]
+//! so it can be pushed to a secondary channel (webhook, email or SMS).
+
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertKind {
+    KillSwitch,
+    /// Realized fills/fees disagree with what the engine expected.
+    ReconciliationMismatch,
+    /// Kalshi WS disconnected for too long while games are live.
+    FeedDown,
+    /// A score feed gave up on its primary URL and switched to the fallback.
+    ScoreFeedFailover,
+    /// The odds API has no requests left in its quota.
+    QuotaExhausted,
+    /// The drawdown halt limit stopped new entries for the session.
+    LossLimit,
+}
+
+impl AlertKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            AlertKind::KillSwitch => "kill switch",
+            AlertKind::ReconciliationMismatch => "reconciliation mismatch",
+            AlertKind::FeedDown => "feed down",
+            AlertKind::ScoreFeedFailover => "score feed failover",
+            AlertKind::QuotaExhausted => "odds quota exhausted",
+            AlertKind::LossLimit => "loss limit",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CriticalAlert {
+    pub kind: AlertKind,
+    pub message: String,
+    pub raised_at: Instant,
+    pub escalated: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EscalationPolicy {
+    alerts: Vec<CriticalAlert>,
+}
+
+impl EscalationPolicy {
+    /// Open a critical alert. Returns false when an alert of the same kind is
+    /// already open, so one outage escalates once.
+    pub fn raise(&mut self, kind: AlertKind, message: String, now: Instant) -> bool {
+        if self.alerts.iter().any(|a| a.kind == kind) {
+            return false;
+        }
+        self.alerts.push(CriticalAlert {
+            kind,
+            message,
+            raised_at: now,
+            escalated: false,
+        });
+        true
+    }
+
+    /// Acknowledge (and close) every open alert, returning how many were open.
+    pub fn acknowledge_all(&mut self) -> usize {
+        let open = self.alerts.len();
+        self.alerts.clear();
+        open
+    }
+
+    pub fn unacknowledged(&self) -> usize {
+        self.alerts.len()
+    }
+
+    /// Alerts left unacknowledged for at least `ack_timeout` that have not been
+    /// escalated yet. Each alert is returned once.
+    pub fn due(&mut self, now: Instant, ack_timeout: Duration) -> Vec<CriticalAlert> {
+        let mut due = Vec::new();
+        for alert in &mut self.alerts {
+            if !alert.escalated && now.duration_since(alert.raised_at) >= ack_timeout {
+                alert.escalated = true;
+                due.push(alert.clone());
+            }
+        }
+        due
+    }
+}
+
+/// Tracks how long the Kalshi WS has been down while games are live.
+#[derive(Debug, Clone, Default)]
+pub struct FeedDownWatch {
+    down_since: Option<Instant>,
+    raised: bool,
+}
+
+impl FeedDownWatch {
+    /// Returns the outage duration the first time it reaches `threshold`.
+    /// The clock resets when the feed reconnects or no games are live.
+    pub fn observe(
+        &mut self,
+        connected: bool,
+        live_games: bool,
+        now: Instant,
+        threshold: Duration,
+    ) -> Option<Duration> {
+        if connected || !live_games {
+            self.down_since = None;
+            self.raised = false;
+            return None;
+        }
+        let down = now.duration_since(*self.down_since.get_or_insert(now));
+        if down >= threshold && !self.raised {
+            self.raised = true;
+            Some(down)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escalates_once_after_timeout_unless_acknowledged() {
+        let mut policy = EscalationPolicy::default();
+        let start = Instant::now();
+        let timeout = Duration::from_secs(300);
+        assert!(policy.raise(AlertKind::FeedDown, "ws down".into(), start));
+        assert!(!policy.raise(AlertKind::FeedDown, "ws down".into(), start));
+        assert!(policy.raise(AlertKind::KillSwitch, "halted".into(), start));
+
+        assert!(policy
+            .due(start + Duration::from_secs(299), timeout)
+            .is_empty());
+        let due = policy.due(start + timeout, timeout);
+        assert_eq!(due.len(), 2);
+        assert!(policy.due(start + timeout * 2, timeout).is_empty());
+
+        assert_eq!(policy.acknowledge_all(), 2);
+        assert_eq!(policy.unacknowledged(), 0);
+        assert!(policy.raise(AlertKind::FeedDown, "ws down again".into(), start));
+        policy.acknowledge_all();
+        assert!(policy.due(start + timeout, timeout).is_empty());
+    }
+
+    #[test]
+    fn test_feed_down_watch_needs_live_games() {
+        let mut watch = FeedDownWatch::default();
+        let start = Instant::now();
+        let threshold = Duration::from_secs(180);
+        assert_eq!(watch.observe(false, true, start, threshold), None);
+        assert_eq!(
+            watch.observe(false, true, start + threshold, threshold),
+            Some(threshold)
+        );
+        assert_eq!(
+            watch.observe(false, true, start + threshold * 2, threshold),
+            None
+        );
+
+        // No live games: the outage clock resets.
+        assert_eq!(
+            watch.observe(false, false, start + threshold * 3, threshold),
+            None
+        );
+        assert_eq!(
+            watch.observe(false, true, start + threshold * 4, threshold),
+            None
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! Wakes the engine when something it prices changes. The WS task reports
+//! book changes and streaming odds sources report pushed updates. A change
+//! to a ticker the engine evaluated last cycle marks that ticker dirty, and
+//! pushed odds mark their whole sport; either wakes the engine, which
+//! re-evaluates just the games trading dirty tickers and the dirty sports
+//! instead of waiting out its timer.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use tokio::sync::Notify;
+
+/// What changed since the last take.
+#[derive(Debug, Default, PartialEq)]
+pub struct Dirty {
+    /// Sports with new feed data, re-evaluated in full.
+    pub sports: HashSet<String>,
+    /// Sport -> its watched tickers whose book changed.
+    pub tickers: HashMap<String, HashSet<String>>,
+}
+
+impl Dirty {
+    pub fn is_empty(&self) -> bool {
+        self.sports.is_empty() && self.tickers.is_empty()
+    }
+
+    /// Whether `sport` has anything to re-evaluate.
+    pub fn touches(&self, sport: &str) -> bool {
+        self.sports.contains(sport) || self.tickers.contains_key(sport)
+    }
+
+    /// Whether every market of `sport` is re-evaluated.
+    pub fn in_full(&self, sport: &str) -> bool {
+        self.sports.contains(sport)
+    }
+
+    /// The tickers `sport` is re-evaluated for, or `None` when it is
+    /// re-evaluated in full or not at all.
+    pub fn tickers_for(&self, sport: &str) -> Option<&HashSet<String>> {
+        if self.in_full(sport) {
+            return None;
+        }
+        self.tickers.get(sport)
+    }
+}
+
+#[derive(Debug, Default)]
+struct BusState {
+    /// Evaluated ticker -> its sport, from the engine's last full cycle.
+    watched: HashMap<String, String>,
+    dirty: Dirty,
+}
+
+#[derive(Debug, Default)]
+pub struct EvalBus {
+    state: Mutex<BusState>,
+    notify: Notify,
+}
+
+impl EvalBus {
+    fn lock(&self) -> MutexGuard<'_, BusState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Replace the watched tickers with `tickers` (ticker -> sport).
+    pub fn watch(&self, tickers: HashMap<String, String>) {
+        self.lock().watched = tickers;
+    }
+
+    /// A ticker's book changed. Wakes the engine if the ticker is watched.
+    pub fn book_changed(&self, ticker: &str) {
+        let mut state = self.lock();
+        let BusState { watched, dirty } = &mut *state;
+        let Some(sport) = watched.get(ticker) else {
+            return;
+        };
+        if dirty.in_full(sport) {
+            return;
+        }
+        let tickers = dirty.tickers.entry(sport.clone()).or_default();
+        if tickers.insert(ticker.to_string()) {
+            self.notify.notify_one();
+        }
+    }
+
+    /// New feed data arrived for `sport`.
+    pub fn feed_changed(&self, sport: &str) {
+        if self.lock().dirty.sports.insert(sport.to_string()) {
+            self.notify.notify_one();
+        }
+    }
+
+    /// Wait for the next change. A change made while nobody waits is kept,
+    /// so the next call returns at once.
+    pub async fn changed(&self) {
+        self.notify.notified().await;
+    }
+
+    /// Take what was marked dirty since the last take.
+    pub fn take(&self) -> Dirty {
+        std::mem::take(&mut self.lock().dirty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_watched_changes_mark_tickers_and_wake() {
+        let bus = EvalBus::default();
+        bus.watch(HashMap::from([
+            ("NBA-A".to_string(), "basketball".to_string()),
+            ("NHL-A".to_string(), "ice-hockey".to_string()),
+        ]));
+
+        // Unwatched tickers neither mark nor wake
+        bus.book_changed("OTHER");
+        assert!(bus.take().is_empty());
+        let idle = tokio::time::timeout(Duration::from_millis(10), bus.changed()).await;
+        assert!(idle.is_err());
+
+        bus.book_changed("NBA-A");
+        bus.book_changed("NBA-A");
+        bus.feed_changed("soccer");
+        // The wake was stored before anyone waited
+        tokio::time::timeout(Duration::from_millis(10), bus.changed())
+            .await
+            .unwrap();
+        let dirty = bus.take();
+        assert_eq!(
+            dirty.tickers_for("basketball"),
+            Some(&HashSet::from(["NBA-A".to_string()]))
+        );
+        assert!(!dirty.in_full("basketball"));
+        assert!(dirty.in_full("soccer") && dirty.touches("soccer"));
+        assert!(!dirty.touches("ice-hockey"));
+        assert!(bus.take().is_empty());
+
+        // A sport already dirty in full takes no tickers
+        bus.feed_changed("ice-hockey");
+        bus.book_changed("NHL-A");
+        let dirty = bus.take();
+        assert!(dirty.in_full("ice-hockey"));
+        assert_eq!(dirty.tickers_for("ice-hockey"), None);
+        assert!(dirty.tickers.is_empty());
+
+        bus.watch(HashMap::new());
+        bus.book_changed("NHL-A");
+        assert!(bus.take().is_empty());
+    }
+}
@@ -0,0 +1,388 @@
+//! Live-mode position exits. Each open position keeps a resting maker sell
+//! at its target (the break-even price, raised to fair value when the model
+//! is above it), re-priced as fair value drifts. The position is sold into
+//! the bid instead when the bid overtakes fair value, the stop-loss or
+//! take-profit is hit, or the hold times out.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::config::{ExitConfig, RiskConfig};
+use crate::engine::positions::Position;
+
+/// Why a position is being sold into the bid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitReason {
+    /// The bid is above fair value: selling now beats holding.
+    EdgeFlipped,
+    StopLoss,
+    TakeProfit,
+    Timeout,
+}
+
+impl ExitReason {
+    pub fn label(self) -> &'static str {
+        match self {
+            ExitReason::EdgeFlipped => "EDGE EXIT",
+            ExitReason::StopLoss => "STOP",
+            ExitReason::TakeProfit => "PROFIT",
+            ExitReason::Timeout => "TIMEOUT",
+        }
+    }
+}
+
+/// The `[risk]` stop-loss or take-profit a position at `entry_price` has hit,
+/// if any. Shared by the sim exit loop and the live exit manager; never
+/// fires without a bid.
+pub fn threshold_exit(entry_price: u32, yes_bid: u32, risk: &RiskConfig) -> Option<ExitReason> {
+    if yes_bid == 0 {
+        return None;
+    }
+    if risk
+        .stop_loss_cents
+        .is_some_and(|stop| yes_bid + stop <= entry_price)
+    {
+        return Some(ExitReason::StopLoss);
+    }
+    if risk
+        .take_profit_cents
+        .is_some_and(|take| yes_bid >= entry_price + take)
+    {
+        return Some(ExitReason::TakeProfit);
+    }
+    None
+}
+
+/// What to do with a position's exit given the current book.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitAction {
+    Hold,
+    /// Rest a sell at `price`; there is none yet.
+    Place {
+        price: u32,
+    },
+    /// Cancel the resting sell and rest a new one at `price`.
+    Replace {
+        price: u32,
+    },
+    /// Cancel any resting sell and sell into the bid.
+    MarketExit {
+        reason: ExitReason,
+    },
+    /// A dry-run resting sell was crossed by the bid and counts as filled.
+    Filled {
+        price: u32,
+    },
+}
+
+/// A resting exit sell. `order_id` is `None` in dry-run.
+#[derive(Debug, Clone)]
+pub struct RestingExit {
+    pub order_id: Option<String>,
+    pub price: u32,
+    pub quantity: u32,
+    pub filled: u32,
+    /// Proceeds of the fills so far, each at its own price.
+    pub filled_value_cents: u32,
+    /// Position size the sell was placed for; a top-up re-places it.
+    pub position_quantity: u32,
+    pub placed_at: Instant,
+}
+
+/// A fill applied to a resting exit sell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExitFill {
+    pub ticker: String,
+    /// Contracts applied, never past what the sell had left.
+    pub count: u32,
+    /// The sell has filled in full and is no longer tracked.
+    pub done: bool,
+}
+
+/// Resting exit sells by ticker.
+#[derive(Debug, Default)]
+pub struct ExitManager {
+    resting: HashMap<String, RestingExit>,
+}
+
+impl ExitManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Price the exit should rest at: the position's break-even target, or
+    /// fair value when that is higher.
+    pub fn target_price(position: &Position, fair_value: Option<u32>) -> u32 {
+        position
+            .sell_target
+            .max(fair_value.unwrap_or(0))
+            .clamp(1, 99)
+    }
+
+    /// Decide the exit for `position`. `fair_value` is `None` when the ticker
+    /// was not evaluated this cycle; the edge check is skipped then. With no
+    /// bid there is nothing to sell into or judge against, so hold.
+    #[allow(clippy::too_many_arguments)]
+    pub fn decide(
+        &self,
+        position: &Position,
+        yes_bid: u32,
+        fair_value: Option<u32>,
+        now: Instant,
+        config: &ExitConfig,
+        risk: &RiskConfig,
+        max_hold: Duration,
+    ) -> ExitAction {
+        if yes_bid == 0 {
+            return ExitAction::Hold;
+        }
+        let resting = self.resting.get(&position.ticker);
+        if let Some(r) = resting {
+            if r.order_id.is_none() && yes_bid >= r.price {
+                return ExitAction::Filled { price: r.price };
+            }
+        }
+
+        if !max_hold.is_zero() && now.duration_since(position.filled_at) > max_hold {
+            return ExitAction::MarketExit {
+                reason: ExitReason::Timeout,
+            };
+        }
+        if let Some(reason) = threshold_exit(position.entry_price, yes_bid, risk) {
+            return ExitAction::MarketExit { reason };
+        }
+        if fair_value.is_some_and(|fair| yes_bid >= fair + config.edge_exit_cents) {
+            return ExitAction::MarketExit {
+                reason: ExitReason::EdgeFlipped,
+            };
+        }
+
+        let target = Self::target_price(position, fair_value);
+        match resting {
+            None => ExitAction::Place { price: target },
+            Some(r) if position.quantity > r.position_quantity => {
+                ExitAction::Replace { price: target }
+            }
+            Some(r)
+                if r.price.abs_diff(target) >= config.reprice_threshold_cents.max(1)
+                    && now.duration_since(r.placed_at)
+                        >= Duration::from_millis(config.min_reprice_interval_ms) =>
+            {
+                ExitAction::Replace { price: target }
+            }
+            Some(_) => ExitAction::Hold,
+        }
+    }
+
+    pub fn get(&self, ticker: &str) -> Option<&RestingExit> {
+        self.resting.get(ticker)
+    }
+
+    /// Record a sell now resting for `ticker`, replacing any previous one.
+    pub fn record_placed(
+        &mut self,
+        ticker: &str,
+        order_id: Option<String>,
+        price: u32,
+        quantity: u32,
+        position_quantity: u32,
+        now: Instant,
+    ) {
+        self.resting.insert(
+            ticker.to_string(),
+            RestingExit {
+                order_id,
+                price,
+                quantity,
+                filled: 0,
+                filled_value_cents: 0,
+                position_quantity,
+                placed_at: now,
+            },
+        );
+    }
+
+    pub fn remove(&mut self, ticker: &str) -> Option<RestingExit> {
+        self.resting.remove(ticker)
+    }
+
+    /// Apply an exchange fill of `count` contracts at `price`, so each fill
+    /// is booked at its own price. `None` for orders that aren't a resting
+    /// exit; a sell that has filled in full is forgotten.
+    pub fn on_fill(&mut self, order_id: &str, count: u32, price: u32) -> Option<ExitFill> {
+        let ticker = self
+            .resting
+            .iter()
+            .find(|(_, r)| r.order_id.as_deref() == Some(order_id))
+            .map(|(t, _)| t.clone())?;
+        let resting = self.resting.get_mut(&ticker)?;
+        let count = count.min(resting.quantity.saturating_sub(resting.filled));
+        resting.filled += count;
+        resting.filled_value_cents += count * price;
+        let done = resting.filled >= resting.quantity;
+        if done {
+            self.resting.remove(&ticker);
+        }
+        Some(ExitFill {
+            ticker,
+            count,
+            done,
+        })
+    }
+
+    /// Forget every resting exit, returning them for cancellation.
+    pub fn drain(&mut self) -> Vec<RestingExit> {
+        self.resting.drain().map(|(_, r)| r).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(entry: u32, target: u32, held_for: Duration) -> Position {
+        Position {
+            ticker: "TEST".to_string(),
+            side: "yes".to_string(),
+            quantity: 2,
+            entry_price: entry,
+            entry_cost_cents: entry * 2 + 2,
+            sell_target: target,
+            filled_at: Instant::now() - held_for,
+            is_taker_entry: true,
+            venue_ticker: None,
+        }
+    }
+
+    fn config() -> ExitConfig {
+        ExitConfig {
+            min_reprice_interval_ms: 0,
+            ..ExitConfig::default()
+        }
+    }
+
+    fn risk(stop_loss_cents: Option<u32>, take_profit_cents: Option<u32>) -> RiskConfig {
+        RiskConfig {
+            stop_loss_cents,
+            take_profit_cents,
+            ..RiskConfig::default()
+        }
+    }
+
+    const HOLD: Duration = Duration::from_secs(300);
+
+    #[test]
+    fn test_places_at_target_and_reprices_on_drift() {
+        let mut m = ExitManager::new();
+        let p = position(50, 53, Duration::from_secs(5));
+        let now = Instant::now();
+        let cfg = config();
+        assert_eq!(
+            m.decide(&p, 49, Some(60), now, &cfg, &risk(None, None), HOLD),
+            ExitAction::Place { price: 60 }
+        );
+        // Fair value below break-even: rest at break-even
+        assert_eq!(
+            m.decide(&p, 49, Some(51), now, &cfg, &risk(None, None), HOLD),
+            ExitAction::Place { price: 53 }
+        );
+
+        m.record_placed("TEST", Some("ord-1".to_string()), 60, 2, 2, now);
+        assert_eq!(
+            m.decide(&p, 49, Some(61), now, &cfg, &risk(None, None), HOLD),
+            ExitAction::Hold
+        );
+        assert_eq!(
+            m.decide(&p, 49, Some(57), now, &cfg, &risk(None, None), HOLD),
+            ExitAction::Replace { price: 57 }
+        );
+        let slow = ExitConfig::default();
+        assert_eq!(
+            m.decide(&p, 49, Some(57), now, &slow, &risk(None, None), HOLD),
+            ExitAction::Hold
+        );
+
+        // A top-up grows the position past what the resting sell covers
+        let p = Position { quantity: 3, ..p };
+        assert_eq!(
+            m.decide(&p, 49, Some(61), now, &slow, &risk(None, None), HOLD),
+            ExitAction::Replace { price: 61 }
+        );
+    }
+
+    #[test]
+    fn test_market_exits() {
+        let m = ExitManager::new();
+        let now = Instant::now();
+        let cfg = config();
+        let p = position(50, 53, Duration::from_secs(5));
+        assert_eq!(
+            m.decide(&p, 52, Some(50), now, &cfg, &risk(None, None), HOLD),
+            ExitAction::MarketExit {
+                reason: ExitReason::EdgeFlipped
+            }
+        );
+        assert_eq!(
+            m.decide(&p, 45, Some(60), now, &cfg, &risk(Some(5), None), HOLD),
+            ExitAction::MarketExit {
+                reason: ExitReason::StopLoss
+            }
+        );
+        assert_eq!(
+            m.decide(&p, 46, Some(60), now, &cfg, &risk(Some(5), None), HOLD),
+            ExitAction::Place { price: 60 }
+        );
+        let stale = position(50, 53, Duration::from_secs(400));
+        assert_eq!(
+            m.decide(&stale, 49, Some(60), now, &cfg, &risk(None, None), HOLD),
+            ExitAction::MarketExit {
+                reason: ExitReason::Timeout
+            }
+        );
+        // No bid: nothing to sell into
+        assert_eq!(
+            m.decide(&stale, 0, None, now, &cfg, &risk(Some(5), None), HOLD),
+            ExitAction::Hold
+        );
+    }
+
+    #[test]
+    fn test_threshold_exits() {
+        let both = risk(Some(5), Some(8));
+        assert_eq!(threshold_exit(50, 45, &both), Some(ExitReason::StopLoss));
+        assert_eq!(threshold_exit(50, 46, &both), None);
+        assert_eq!(threshold_exit(50, 58, &both), Some(ExitReason::TakeProfit));
+        assert_eq!(threshold_exit(50, 57, &both), None);
+        // No bid: an empty book is not a loss
+        assert_eq!(threshold_exit(50, 0, &both), None);
+        assert_eq!(threshold_exit(50, 1, &risk(None, None)), None);
+    }
+
+    #[test]
+    fn test_fills_complete_exit() {
+        let mut m = ExitManager::new();
+        let now = Instant::now();
+        m.record_placed("TEST", Some("ord-1".to_string()), 60, 3, 3, now);
+        assert!(m.on_fill("other", 2, 60).is_none());
+        let fill = |ticker: &str, count, done| ExitFill {
+            ticker: ticker.to_string(),
+            count,
+            done,
+        };
+        assert_eq!(m.on_fill("ord-1", 1, 60), Some(fill("TEST", 1, false)));
+        // Each fill keeps its own price
+        assert_eq!(m.on_fill("ord-1", 1, 62), Some(fill("TEST", 1, false)));
+        assert_eq!(m.get("TEST").unwrap().filled_value_cents, 60 + 62);
+        // An overfill is capped at what the sell had left
+        assert_eq!(m.on_fill("ord-1", 4, 60), Some(fill("TEST", 1, true)));
+        assert!(m.get("TEST").is_none());
+
+        // Dry-run exits fill once the bid reaches them
+        let p = position(50, 53, Duration::from_secs(5));
+        m.record_placed("TEST", None, 55, 2, 2, now);
+        assert_eq!(
+            m.decide(&p, 55, Some(55), now, &config(), &risk(None, None), HOLD),
+            ExitAction::Filled { price: 55 }
+        );
+    }
+}
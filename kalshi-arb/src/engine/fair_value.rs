@@ -0,0 +1,509 @@
+//! Pluggable fair value models.
+//!
+//! A model prices one game from whichever inputs it asks for: live scores,
+//! sportsbook odds, or the Kalshi book. `SportPipeline` polls those feeds,
+//! matches markets and evaluates them; it only asks the model for prices.
+//! A new model (an Elo pre-game prior, say) is an impl of
+//! [`FairValueModel`] plus an arm in [`build`].
+
+use anyhow::{bail, Result};
+
+use crate::config::{BlendConfig, OrderFlowConfig, SoccerModelConfig, WinProbConfig};
+use crate::engine::order_flow::OrderFlowEstimate;
+use crate::engine::strategy;
+use crate::engine::win_prob::{SoccerWinProb, WinProbGrid, WinProbTable, SOCCER_REGULATION_SECS};
+
+/// Feed whose updates drive a model's pipeline tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Driver {
+    Score,
+    Odds,
+    Book,
+}
+
+/// A live game as the score feed reports it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreState {
+    /// Home score minus away score.
+    pub score_diff: i32,
+    pub total_elapsed_secs: u16,
+    pub period: u8,
+    /// (home, away) red cards, soccer only.
+    pub red_cards: (u8, u8),
+}
+
+/// Weighted bookmaker consensus in American odds. `draw` is set only for
+/// three-way games.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OddsQuote {
+    pub home: f64,
+    pub away: f64,
+    pub draw: Option<f64>,
+    /// Standard error of the devigged probabilities across books; `None`
+    /// from a single book.
+    pub stderr: Option<f64>,
+}
+
+/// What the pipeline has for one game this tick.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GameInputs<'a> {
+    pub score: Option<ScoreState>,
+    pub odds: Option<OddsQuote>,
+    /// Book estimate for the single market being priced; its fair value
+    /// comes back as `home`.
+    pub book: Option<&'a OrderFlowEstimate>,
+}
+
+/// Fair values for each side of a game. Cents are what gets traded;
+/// probabilities are kept unrounded for velocity tracking and traces.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FairValues {
+    pub home: u32,
+    pub away: u32,
+    pub draw: Option<u32>,
+    pub home_prob: f64,
+    pub away_prob: f64,
+    pub draw_prob: Option<f64>,
+    /// How a blended model mixed its inputs.
+    pub blend: Option<BlendParts>,
+    /// Standard error of the fair values in cents, when the model knows it.
+    pub stderr: Option<f64>,
+}
+
+/// The two inputs of a blended fair value, for provenance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlendParts {
+    /// Weight of the score model; the odds got the rest.
+    pub score_weight: f64,
+    /// (home, away, draw) probabilities from the score model.
+    pub score: (f64, f64, Option<f64>),
+    /// (home, away, draw) probabilities from devigged odds.
+    pub odds: (f64, f64, Option<f64>),
+}
+
+impl FairValues {
+    pub fn from_cents(home: u32, away: u32, draw: Option<u32>) -> Self {
+        Self {
+            home,
+            away,
+            draw,
+            home_prob: home as f64 / 100.0,
+            away_prob: away as f64 / 100.0,
+            draw_prob: draw.map(|d| d as f64 / 100.0),
+            blend: None,
+            stderr: None,
+        }
+    }
+
+    pub fn from_probs(home: f64, away: f64, draw: Option<f64>) -> Self {
+        Self {
+            home: strategy::fair_value_cents(home),
+            away: strategy::fair_value_cents(away),
+            draw: draw.map(strategy::fair_value_cents),
+            home_prob: home,
+            away_prob: away,
+            draw_prob: draw,
+            blend: None,
+            stderr: None,
+        }
+    }
+
+    pub fn with_stderr(mut self, stderr: Option<f64>) -> Self {
+        self.stderr = stderr;
+        self
+    }
+}
+
+/// Prices a game from the inputs its pipeline gathers.
+pub trait FairValueModel: Send + Sync {
+    /// The `fair_value` config value that selects this model.
+    fn name(&self) -> &str;
+
+    /// Which feed the pipeline polls and iterates to price games.
+    fn driver(&self) -> Driver;
+
+    /// A score-driven model that also needs sportsbook odds every tick.
+    fn wants_odds(&self) -> bool {
+        false
+    }
+
+    /// `None` when the inputs this model needs are missing.
+    fn fair_values(&self, inputs: &GameInputs) -> Option<FairValues>;
+
+    /// Score model behind overtime, period markets, sanity bounds and the
+    /// win-prob view. Required for `Driver::Score`.
+    fn score_model(&self) -> Option<&ScoreWinProb> {
+        None
+    }
+
+    /// Book tracker tuning. Required for `Driver::Book`.
+    fn order_flow(&self) -> Option<&OrderFlowConfig> {
+        None
+    }
+}
+
+/// Configs a model may be built from; the sport's own sections.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ModelConfigs<'a> {
+    pub win_prob: Option<&'a WinProbConfig>,
+    pub soccer_model: Option<&'a SoccerModelConfig>,
+    pub order_flow: Option<&'a OrderFlowConfig>,
+    pub blend: Option<&'a BlendConfig>,
+}
+
+/// Model for a sport's `fair_value`. Anything that is not a built-in model
+/// name is an odds source, priced by devigging its odds.
+pub fn build(fair_value: &str, configs: &ModelConfigs) -> Result<Box<dyn FairValueModel>> {
+    Ok(match fair_value {
+        "score-feed" => Box::new(ScoreWinProb::from_config(configs)?),
+        "blended" => Box::new(Blended {
+            score: ScoreWinProb::from_config(configs)?,
+            config: configs.blend.cloned().unwrap_or_default(),
+        }),
+        "order-flow" => Box::new(OrderFlow {
+            config: configs.order_flow.cloned().unwrap_or_default(),
+        }),
+        _ => Box::new(DeviggedOdds),
+    })
+}
+
+/// Whether `fair_value` names a model rather than an odds source.
+pub fn is_builtin(fair_value: &str) -> bool {
+    matches!(
+        fair_value,
+        "score-feed" | "odds-feed" | "order-flow" | "blended"
+    )
+}
+
+/// Sportsbook odds -> devig -> fair value.
+pub struct DeviggedOdds;
+
+impl FairValueModel for DeviggedOdds {
+    fn name(&self) -> &str {
+        "odds-feed"
+    }
+
+    fn driver(&self) -> Driver {
+        Driver::Odds
+    }
+
+    fn fair_values(&self, inputs: &GameInputs) -> Option<FairValues> {
+        let odds = inputs.odds?;
+        let fv = match odds.draw {
+            Some(draw) => {
+                let (home, away, draw) = strategy::devig_3way(odds.home, odds.away, draw);
+                FairValues::from_probs(home, away, Some(draw))
+            }
+            None => {
+                let (home, away) = strategy::devig(odds.home, odds.away);
+                FairValues::from_probs(home, away, None)
+            }
+        };
+        Some(fv.with_stderr(odds.stderr.map(|se| se * 100.0)))
+    }
+}
+
+/// Live score -> win probability model -> fair value.
+pub struct ScoreWinProb {
+    pub table: WinProbTable,
+    /// Three-way model that prices soccer's home/draw/away markets.
+    pub soccer: Option<SoccerWinProb>,
+    pub regulation_secs: u16,
+}
+
+impl ScoreWinProb {
+    pub fn from_config(configs: &ModelConfigs) -> Result<Self> {
+        // The soccer model prices the game; its table only marks the halves
+        let soccer_halves = WinProbConfig {
+            regulation_secs: Some(SOCCER_REGULATION_SECS),
+            regulation_periods: Some(2),
+            sanity_bounds: Vec::new(),
+            ..WinProbConfig::default()
+        };
+        let wp_config = match (configs.win_prob, configs.soccer_model) {
+            (_, Some(_)) => &soccer_halves,
+            (Some(wp), None) => wp,
+            (None, None) => bail!("no [win_prob] section"),
+        };
+        Ok(Self {
+            table: WinProbTable::from_config(wp_config),
+            soccer: configs.soccer_model.map(SoccerWinProb::from_config),
+            regulation_secs: wp_config.regulation_secs.unwrap_or(2880),
+        })
+    }
+
+    /// Heat grid of whichever model prices the game.
+    pub fn grid(&self, columns: usize) -> WinProbGrid {
+        match &self.soccer {
+            Some(model) => model.grid(columns),
+            None => self.table.grid(columns),
+        }
+    }
+
+    fn price(&self, score: &ScoreState) -> FairValues {
+        if let Some(model) = &self.soccer {
+            let (home, draw, away) =
+                model.fair_values(score.score_diff, score.total_elapsed_secs, score.red_cards);
+            return FairValues::from_cents(home, away, Some(draw));
+        }
+        let ((home, away), stderr) = if score.period > self.table.regulation_periods() {
+            let ot_elapsed = score
+                .total_elapsed_secs
+                .saturating_sub(self.regulation_secs);
+            (
+                self.table.fair_value_overtime(score.score_diff, ot_elapsed),
+                self.table
+                    .fair_value_overtime_stderr(score.score_diff, ot_elapsed),
+            )
+        } else {
+            (
+                self.table
+                    .fair_value(score.score_diff, score.total_elapsed_secs),
+                self.table
+                    .fair_value_stderr(score.score_diff, score.total_elapsed_secs),
+            )
+        };
+        FairValues::from_cents(home, away, None).with_stderr(Some(stderr))
+    }
+}
+
+impl FairValueModel for ScoreWinProb {
+    fn name(&self) -> &str {
+        "score-feed"
+    }
+
+    fn driver(&self) -> Driver {
+        Driver::Score
+    }
+
+    fn fair_values(&self, inputs: &GameInputs) -> Option<FairValues> {
+        inputs.score.as_ref().map(|score| self.price(score))
+    }
+
+    fn score_model(&self) -> Option<&ScoreWinProb> {
+        Some(self)
+    }
+}
+
+/// Score model and devigged odds averaged with a weight that shifts from the
+/// odds to the score as the game runs. Falls back to the score model alone
+/// while the game has no odds.
+pub struct Blended {
+    pub score: ScoreWinProb,
+    pub config: BlendConfig,
+}
+
+impl Blended {
+    /// Score model weight at `elapsed_secs` of game clock.
+    pub fn score_weight(&self, elapsed_secs: u16) -> f64 {
+        let regulation = self.score.regulation_secs.max(1) as f64;
+        self.config.score_weight(elapsed_secs as f64 / regulation)
+    }
+}
+
+impl FairValueModel for Blended {
+    fn name(&self) -> &str {
+        "blended"
+    }
+
+    fn driver(&self) -> Driver {
+        Driver::Score
+    }
+
+    fn wants_odds(&self) -> bool {
+        true
+    }
+
+    fn fair_values(&self, inputs: &GameInputs) -> Option<FairValues> {
+        let score = self.score.fair_values(inputs)?;
+        let Some(odds) = DeviggedOdds.fair_values(inputs) else {
+            return Some(score);
+        };
+        let w = self.score_weight(inputs.score?.total_elapsed_secs);
+        let mix = |s: f64, o: f64| w * s + (1.0 - w) * o;
+        let draw = match (score.draw_prob, odds.draw_prob) {
+            (Some(s), Some(o)) => Some(mix(s, o)),
+            (None, None) => None,
+            // Two-way score model against three-way odds: nothing to blend
+            _ => return Some(score),
+        };
+        // Model error and book disagreement taken as independent
+        let stderr = match (score.stderr, odds.stderr) {
+            (Some(s), Some(o)) => Some(((w * s).powi(2) + ((1.0 - w) * o).powi(2)).sqrt()),
+            _ => None,
+        };
+        let mut blended = FairValues::from_probs(
+            mix(score.home_prob, odds.home_prob),
+            mix(score.away_prob, odds.away_prob),
+            draw,
+        )
+        .with_stderr(stderr);
+        blended.blend = Some(BlendParts {
+            score_weight: w,
+            score: (score.home_prob, score.away_prob, score.draw_prob),
+            odds: (odds.home_prob, odds.away_prob, odds.draw_prob),
+        });
+        Some(blended)
+    }
+
+    fn score_model(&self) -> Option<&ScoreWinProb> {
+        Some(&self.score)
+    }
+}
+
+/// Kalshi's own book (mean reversion, depth, flow) -> fair value. Needs no
+/// external feed.
+pub struct OrderFlow {
+    pub config: OrderFlowConfig,
+}
+
+impl FairValueModel for OrderFlow {
+    fn name(&self) -> &str {
+        "order-flow"
+    }
+
+    fn driver(&self) -> Driver {
+        Driver::Book
+    }
+
+    fn fair_values(&self, inputs: &GameInputs) -> Option<FairValues> {
+        let fair = inputs.book?.fair_cents;
+        Some(FairValues::from_cents(fair, 100 - fair.min(100), None))
+    }
+
+    fn order_flow(&self) -> Option<&OrderFlowConfig> {
+        Some(&self.config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nba(win_prob: &WinProbConfig) -> ModelConfigs<'_> {
+        ModelConfigs {
+            win_prob: Some(win_prob),
+            ..ModelConfigs::default()
+        }
+    }
+
+    fn live(score_diff: i32, total_elapsed_secs: u16) -> GameInputs<'static> {
+        GameInputs {
+            score: Some(ScoreState {
+                score_diff,
+                total_elapsed_secs,
+                period: 3,
+                red_cards: (0, 0),
+            }),
+            ..GameInputs::default()
+        }
+    }
+
+    #[test]
+    fn test_build_selects_model_by_name() {
+        let win_prob = WinProbConfig::default();
+        let configs = nba(&win_prob);
+        for (name, driver) in [
+            ("score-feed", Driver::Score),
+            ("blended", Driver::Score),
+            ("order-flow", Driver::Book),
+            ("draftkings", Driver::Odds),
+        ] {
+            assert_eq!(build(name, &configs).unwrap().driver(), driver, "{}", name);
+        }
+        assert!(build("score-feed", &ModelConfigs::default()).is_err());
+        assert!(is_builtin("blended") && !is_builtin("draftkings"));
+    }
+
+    #[test]
+    fn test_devigged_odds() {
+        let model = DeviggedOdds;
+        assert!(model.fair_values(&GameInputs::default()).is_none());
+        let fv = model
+            .fair_values(&GameInputs {
+                odds: Some(OddsQuote {
+                    home: -150.0,
+                    away: 130.0,
+                    draw: None,
+                    stderr: Some(0.015),
+                }),
+                ..GameInputs::default()
+            })
+            .unwrap();
+        assert_eq!((fv.home, fv.away, fv.draw), (58, 42, None));
+        assert!((fv.stderr.unwrap() - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_score_model_matches_win_prob_table() {
+        let win_prob = WinProbConfig::default();
+        let model = build("score-feed", &nba(&win_prob)).unwrap();
+        let table = WinProbTable::from_config(&win_prob);
+        let fv = model.fair_values(&live(6, 2000)).unwrap();
+        assert_eq!((fv.home, fv.away), table.fair_value(6, 2000));
+        assert_eq!(fv.stderr, Some(table.fair_value_stderr(6, 2000)));
+        assert!(model.score_model().is_some());
+    }
+
+    #[test]
+    fn test_blend_weights_score_and_odds() {
+        let win_prob = WinProbConfig::default();
+        let blend = BlendConfig {
+            start_score_weight: 0.5,
+            end_score_weight: 0.5,
+            curve: 1.0,
+        };
+        let configs = ModelConfigs {
+            blend: Some(&blend),
+            ..nba(&win_prob)
+        };
+        let model = build("blended", &configs).unwrap();
+        let mut inputs = live(10, 2400);
+        let score_only = model.fair_values(&inputs).unwrap();
+        assert!(score_only.blend.is_none());
+        inputs.odds = Some(OddsQuote {
+            home: 100.0,
+            away: 100.0,
+            draw: None,
+            stderr: Some(0.02),
+        });
+        let blended = model.fair_values(&inputs).unwrap();
+        let expected = 0.5 * score_only.home_prob + 0.25;
+        assert!((blended.home_prob - expected).abs() < 1e-9);
+        assert!(blended.home < score_only.home);
+        let parts = blended.blend.unwrap();
+        assert_eq!(parts.score_weight, 0.5);
+        assert_eq!(parts.score.0, score_only.home_prob);
+        assert_eq!(parts.odds.0, 0.5);
+        // Score and odds errors combine as independent
+        let (s, o) = (score_only.stderr.unwrap(), 2.0);
+        let expected_se = (0.25 * s * s + 0.25 * o * o).sqrt();
+        assert!((blended.stderr.unwrap() - expected_se).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_blend_weight_shifts_to_score_over_regulation() {
+        let blended = Blended {
+            score: ScoreWinProb::from_config(&nba(&WinProbConfig::default())).unwrap(),
+            config: BlendConfig {
+                start_score_weight: 0.2,
+                end_score_weight: 1.0,
+                curve: 1.0,
+            },
+        };
+        assert!((blended.score_weight(0) - 0.2).abs() < 1e-9);
+        assert!((blended.score_weight(1440) - 0.6).abs() < 1e-9);
+        assert!((blended.score_weight(2880) - 1.0).abs() < 1e-9);
+        // Overtime stays at the end weight
+        assert!((blended.score_weight(3100) - 1.0).abs() < 1e-9);
+
+        let late = Blended {
+            config: BlendConfig {
+                curve: 2.0,
+                ..blended.config.clone()
+            },
+            ..blended
+        };
+        assert!(late.score_weight(1440) < 0.6);
+    }
+}
@@ -0,0 +1,202 @@
+use super::fees::calculate_fee;
+use std::time::Instant;
+
+/// Fee expected at signal time for a live order whose fills have not been
+/// reconciled yet.
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct ExpectedFee {
+    pub ticker: String,
+    pub quantity: u32,
+    pub price: u32,
+    pub is_taker: bool,
+    pub submitted_at: Instant,
+}
+
+impl ExpectedFee {
+    /// Expected fee for `filled` contracts at the signal price and classification.
+    pub fn expected_cents(&self, filled: u32) -> u32 {
+        calculate_fee(self.price, filled, self.is_taker)
+    }
+}
+
+/// One reconciled trade: what the local fee model predicted vs what was charged.
+#[derive(Debug, Clone)]
+pub struct FeeSample {
+    pub ticker: String,
+    pub expected_cents: u32,
+    pub realized_cents: u32,
+    pub expected_taker: bool,
+    /// Taker/maker classification reported by the exchange, if known.
+    pub realized_taker: Option<bool>,
+}
+
+/// Aggregate expected vs realized fee statistics.
+#[derive(Debug, Clone, Default)]
+pub struct FeeStats {
+    pub samples: u32,
+    pub expected_cents: i64,
+    pub realized_cents: i64,
+    /// Trades where the realized fee exceeded the expected fee.
+    pub overcharged: u32,
+    /// Trades where the exchange classified the fill differently than we did.
+    pub misclassified: u32,
+}
+
+impl FeeStats {
+    /// Realized minus expected fees across all samples.
+    pub fn divergence_cents(&self) -> i64 {
+        self.realized_cents - self.expected_cents
+    }
+
+    /// Divergence as a percentage of expected fees (0.0 when nothing expected).
+    pub fn divergence_pct(&self) -> f64 {
+        if self.expected_cents <= 0 {
+            return 0.0;
+        }
+        self.divergence_cents() as f64 * 100.0 / self.expected_cents as f64
+    }
+}
+
+/// Tracks expected vs realized fees per trade and flags systematic overcharging,
+/// which usually means the local fee model or taker/maker classification is wrong.
+#[derive(Debug, Clone)]
+pub struct FeeTracker {
+    stats: FeeStats,
+    tolerance_pct: f64,
+    min_samples: u32,
+    alerting: bool,
+}
+
+impl Default for FeeTracker {
+    fn default() -> Self {
+        Self::new(10.0, 10)
+    }
+}
+
+impl FeeTracker {
+    pub fn new(tolerance_pct: f64, min_samples: u32) -> Self {
+        Self {
+            stats: FeeStats::default(),
+            tolerance_pct,
+            min_samples,
+            alerting: false,
+        }
+    }
+
+    pub fn stats(&self) -> &FeeStats {
+        &self.stats
+    }
+
+    /// Record a reconciled trade. Returns an alert message the first time realized
+    /// fees systematically exceed expectations; re-arms once back within tolerance.
+    pub fn record(&mut self, sample: &FeeSample) -> Option<String> {
+        self.stats.samples += 1;
+        self.stats.expected_cents += sample.expected_cents as i64;
+        self.stats.realized_cents += sample.realized_cents as i64;
+        if sample.realized_cents > sample.expected_cents {
+            self.stats.overcharged += 1;
+        }
+        if sample
+            .realized_taker
+            .is_some_and(|taker| taker != sample.expected_taker)
+        {
+            self.stats.misclassified += 1;
+        }
+
+        if self.stats.samples < self.min_samples {
+            return None;
+        }
+
+        let limit = self.stats.expected_cents as f64 * (1.0 + self.tolerance_pct / 100.0);
+        let exceeded = self.stats.realized_cents as f64 > limit;
+        if exceeded && !self.alerting {
+            self.alerting = true;
+            return Some(format!(
+                "Realized fees exceed expected by {:+.1}% over {} trades ({}c vs {}c, {} misclassified taker/maker) - check fee model",
+                self.stats.divergence_pct(),
+                self.stats.samples,
+                self.stats.realized_cents,
+                self.stats.expected_cents,
+                self.stats.misclassified,
+            ));
+        }
+        if !exceeded {
+            self.alerting = false;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(expected: u32, realized: u32) -> FeeSample {
+        FeeSample {
+            ticker: "TEST".to_string(),
+            expected_cents: expected,
+            realized_cents: realized,
+            expected_taker: true,
+            realized_taker: Some(true),
+        }
+    }
+
+    #[test]
+    fn test_expected_fee_scales_with_filled_quantity() {
+        let exp = ExpectedFee {
+            ticker: "TEST".to_string(),
+            quantity: 10,
+            price: 50,
+            is_taker: true,
+            submitted_at: Instant::now(),
+        };
+        assert_eq!(exp.expected_cents(10), calculate_fee(50, 10, true));
+        assert_eq!(exp.expected_cents(4), calculate_fee(50, 4, true));
+    }
+
+    #[test]
+    fn test_stats_aggregate_divergence() {
+        let mut tracker = FeeTracker::new(10.0, 100);
+        tracker.record(&sample(10, 12));
+        tracker.record(&sample(10, 10));
+        let stats = tracker.stats();
+        assert_eq!(stats.samples, 2);
+        assert_eq!(stats.divergence_cents(), 2);
+        assert_eq!(stats.overcharged, 1);
+        assert!((stats.divergence_pct() - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_no_alert_before_min_samples() {
+        let mut tracker = FeeTracker::new(10.0, 3);
+        assert!(tracker.record(&sample(10, 20)).is_none());
+        assert!(tracker.record(&sample(10, 20)).is_none());
+        assert!(tracker.record(&sample(10, 20)).is_some());
+    }
+
+    #[test]
+    fn test_alert_fires_once_and_rearms() {
+        let mut tracker = FeeTracker::new(10.0, 1);
+        assert!(tracker.record(&sample(10, 20)).is_some());
+        assert!(tracker.record(&sample(10, 20)).is_none());
+        // Pull the aggregate back within tolerance (realized 40 vs expected 40)
+        assert!(tracker.record(&sample(20, 0)).is_none());
+        assert!(tracker.record(&sample(0, 30)).is_some());
+    }
+
+    #[test]
+    fn test_within_tolerance_does_not_alert() {
+        let mut tracker = FeeTracker::new(10.0, 1);
+        assert!(tracker.record(&sample(100, 105)).is_none());
+    }
+
+    #[test]
+    fn test_misclassification_counted() {
+        let mut tracker = FeeTracker::default();
+        let mut s = sample(2, 8);
+        s.expected_taker = false;
+        tracker.record(&s);
+        assert_eq!(tracker.stats().misclassified, 1);
+    }
+}
@@ -74,7 +74,7 @@ mod tests {
         let be = break_even_sell_price(entry_cost, 1, true).expect("should have break-even");
         // Verify break even is correct
         let exit_fee = calculate_fee(be, 1, true);
-        assert!(be * 1 >= entry_cost + exit_fee);
+        assert!(be >= entry_cost + exit_fee);
     }
 
     #[test]
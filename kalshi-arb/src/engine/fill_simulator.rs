@@ -26,13 +26,24 @@ pub struct FillSimulator {
 
 impl FillSimulator {
     pub fn new(config: SimulationRealismConfig) -> Self {
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Self { config, rng }
+    }
+
+    /// An independent simulator for one concurrent pipeline tick. Its rng
+    /// is seeded from this one, so a seeded run stays reproducible.
+    pub fn fork(&mut self) -> Self {
         Self {
-            config,
-            rng: StdRng::from_entropy(),
+            config: self.config.clone(),
+            rng: StdRng::seed_from_u64(self.rng.gen()),
         }
     }
 
     /// Check if realism simulation is enabled.
+    #[allow(dead_code)]
     pub fn is_enabled(&self) -> bool {
         self.config.enabled
     }
@@ -174,6 +185,7 @@ mod tests {
             apply_latency: true,
             max_hold_seconds: 300,
             timeout_exit_slippage_cents: 2,
+            seed: None,
         }
     }
 
@@ -217,6 +229,24 @@ mod tests {
         assert_eq!(result, FillResult::Filled { price: 48 });
     }
 
+    #[test]
+    fn test_forks_of_seeded_simulator_are_reproducible() {
+        let mut config = test_config();
+        config.seed = Some(7);
+        config.maker_fill_rate = 0.5;
+        let draws = |sim: &mut FillSimulator| -> Vec<FillResult> {
+            (0..20).map(|_| sim.try_maker_entry(50)).collect()
+        };
+
+        let mut a = FillSimulator::new(config.clone());
+        let mut b = FillSimulator::new(config);
+        let (mut a1, mut a2) = (a.fork(), a.fork());
+        let (mut b1, mut b2) = (b.fork(), b.fork());
+        assert_eq!(draws(&mut a1), draws(&mut b1));
+        assert_eq!(draws(&mut a2), draws(&mut b2));
+        assert_ne!(draws(&mut a1), draws(&mut a2));
+    }
+
     #[test]
     fn test_fill_rates_produce_rejections() {
         let mut config = test_config();
@@ -0,0 +1,141 @@
+//! Score-model vs odds-consensus fair value disagreement, per sport.
+//!
+//! With `validate_fair_value` on, every score-feed evaluation that also has an
+//! odds-consensus fair value contributes a sample. A sustained average gap
+//! points at a model or matching problem rather than a fleeting edge.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Samples needed before the average is trusted.
+const MIN_SAMPLES: usize = 10;
+
+/// Alert transition reported by [`DisagreementMonitor::check`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DisagreementAlert {
+    Raised { avg_abs: f64, bias: f64 },
+    Cleared { avg_abs: f64 },
+}
+
+#[derive(Debug, Clone)]
+pub struct DisagreementMonitor {
+    window: Duration,
+    /// (when, score-model FV minus odds FV, in cents)
+    samples: VecDeque<(Instant, f64)>,
+    alerting: bool,
+}
+
+impl DisagreementMonitor {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            samples: VecDeque::new(),
+            alerting: false,
+        }
+    }
+
+    /// Record one game's gap between the score-model and odds fair values.
+    pub fn record(&mut self, at: Instant, model_fv: u32, odds_fv: u32) {
+        self.samples
+            .push_back((at, model_fv as f64 - odds_fv as f64));
+        while self
+            .samples
+            .front()
+            .is_some_and(|&(t, _)| at.duration_since(t) > self.window)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn sample_count(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Mean absolute gap over the window, once enough samples exist.
+    pub fn avg_abs(&self) -> Option<f64> {
+        self.mean(|d| d.abs())
+    }
+
+    /// Mean signed gap: positive when the score model prices home above the books.
+    pub fn bias(&self) -> Option<f64> {
+        self.mean(|d| d)
+    }
+
+    pub fn is_alerting(&self) -> bool {
+        self.alerting
+    }
+
+    /// Compare the average against `threshold_cents`, returning a transition
+    /// when the alert is raised or cleared.
+    pub fn check(&mut self, threshold_cents: f64) -> Option<DisagreementAlert> {
+        let avg_abs = self.avg_abs()?;
+        let over = avg_abs > threshold_cents;
+        if over == self.alerting {
+            return None;
+        }
+        self.alerting = over;
+        Some(if over {
+            DisagreementAlert::Raised {
+                avg_abs,
+                bias: self.bias().unwrap_or(0.0),
+            }
+        } else {
+            DisagreementAlert::Cleared { avg_abs }
+        })
+    }
+
+    fn mean(&self, f: impl Fn(f64) -> f64) -> Option<f64> {
+        if self.samples.len() < MIN_SAMPLES {
+            return None;
+        }
+        let sum: f64 = self.samples.iter().map(|&(_, d)| f(d)).sum();
+        Some(sum / self.samples.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_needs_min_samples() {
+        let mut m = DisagreementMonitor::new(Duration::from_secs(600));
+        let now = Instant::now();
+        for _ in 0..MIN_SAMPLES - 1 {
+            m.record(now, 60, 50);
+        }
+        assert_eq!(m.avg_abs(), None);
+        assert_eq!(m.check(5.0), None);
+        m.record(now, 60, 50);
+        assert_eq!(m.avg_abs(), Some(10.0));
+    }
+
+    #[test]
+    fn test_alert_raised_once_then_cleared() {
+        let mut m = DisagreementMonitor::new(Duration::from_secs(600));
+        let start = Instant::now();
+        for i in 0..10 {
+            m.record(start + Duration::from_secs(i), 40, 52);
+        }
+        assert_eq!(
+            m.check(8.0),
+            Some(DisagreementAlert::Raised {
+                avg_abs: 12.0,
+                bias: -12.0
+            })
+        );
+        assert_eq!(m.check(8.0), None);
+        assert!(m.is_alerting());
+
+        // Old samples age out of the window and agreement returns.
+        let later = start + Duration::from_secs(1000);
+        for i in 0..10 {
+            m.record(later + Duration::from_secs(i), 50, 51);
+        }
+        assert_eq!(m.sample_count(), 10);
+        assert_eq!(
+            m.check(8.0),
+            Some(DisagreementAlert::Cleared { avg_abs: 1.0 })
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! Games kept out of trading by hand. A blacklisted game or market gets no
+//! signals and no new entries; in whitelist-only mode nothing else does
+//! either. Open positions are left to the exit manager either way.
+
+use std::collections::BTreeSet;
+
+use crate::config::GameFilterConfig;
+use crate::engine::risk::game_event;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameFilter {
+    blacklist: BTreeSet<String>,
+    whitelist: BTreeSet<String>,
+    whitelist_only: bool,
+}
+
+impl GameFilter {
+    pub fn new(config: &GameFilterConfig) -> Self {
+        Self {
+            blacklist: config.blacklist.iter().cloned().collect(),
+            whitelist: config.whitelist.iter().cloned().collect(),
+            whitelist_only: config.whitelist_only,
+        }
+    }
+
+    /// The filter as config, for writing back to the config file.
+    pub fn to_config(&self) -> GameFilterConfig {
+        GameFilterConfig {
+            blacklist: self.blacklist.iter().cloned().collect(),
+            whitelist: self.whitelist.iter().cloned().collect(),
+            whitelist_only: self.whitelist_only,
+        }
+    }
+
+    pub fn whitelist_only(&self) -> bool {
+        self.whitelist_only
+    }
+
+    /// `ticker` or its game is blacklisted.
+    pub fn is_blacklisted(&self, ticker: &str) -> bool {
+        listed(&self.blacklist, ticker)
+    }
+
+    /// `ticker` or its game is whitelisted.
+    pub fn is_whitelisted(&self, ticker: &str) -> bool {
+        listed(&self.whitelist, ticker)
+    }
+
+    /// Whether `ticker` may be signalled and entered.
+    pub fn allows(&self, ticker: &str) -> bool {
+        !self.is_blacklisted(ticker) && (!self.whitelist_only || self.is_whitelisted(ticker))
+    }
+
+    /// Blacklist `key` (an event or market ticker), or lift it if already
+    /// listed. Returns whether it is now blacklisted.
+    pub fn toggle_blacklist(&mut self, key: &str) -> bool {
+        toggle(&mut self.blacklist, key)
+    }
+
+    /// Whitelist `key`, or lift it if already listed. Returns whether it is
+    /// now whitelisted.
+    pub fn toggle_whitelist(&mut self, key: &str) -> bool {
+        toggle(&mut self.whitelist, key)
+    }
+}
+
+fn listed(list: &BTreeSet<String>, ticker: &str) -> bool {
+    list.contains(ticker) || list.contains(game_event(ticker))
+}
+
+fn toggle(list: &mut BTreeSet<String>, key: &str) -> bool {
+    !list.remove(key) && list.insert(key.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOME: &str = "KXNBAGAME-26MAR10LALBOS-BOS";
+    const AWAY: &str = "KXNBAGAME-26MAR10LALBOS-LAL";
+    const GAME: &str = "KXNBAGAME-26MAR10LALBOS";
+
+    #[test]
+    fn test_blacklisting_a_game_covers_its_markets() {
+        let mut filter = GameFilter::default();
+        assert!(filter.allows(HOME));
+
+        assert!(filter.toggle_blacklist(GAME));
+        assert!(!filter.allows(HOME));
+        assert!(!filter.allows(AWAY));
+        assert!(filter.allows("KXNBAGAME-26MAR10NYKMIA-NYK"));
+
+        assert!(!filter.toggle_blacklist(GAME));
+        assert!(filter.allows(HOME));
+
+        // A single market leaves the rest of its game tradeable
+        filter.toggle_blacklist(HOME);
+        assert!(!filter.allows(HOME));
+        assert!(filter.allows(AWAY));
+    }
+
+    #[test]
+    fn test_whitelist_only_mode() {
+        let mut filter = GameFilter::new(&GameFilterConfig {
+            whitelist_only: true,
+            ..GameFilterConfig::default()
+        });
+        assert!(!filter.allows(HOME));
+
+        filter.toggle_whitelist(GAME);
+        assert!(filter.allows(HOME));
+        assert!(filter.allows(AWAY));
+
+        // The blacklist still wins
+        filter.toggle_blacklist(AWAY);
+        assert!(!filter.allows(AWAY));
+
+        let config = filter.to_config();
+        assert_eq!(config.whitelist, vec![GAME]);
+        assert_eq!(config.blacklist, vec![AWAY]);
+        assert_eq!(GameFilter::new(&config), filter);
+    }
+}
@@ -0,0 +1,175 @@
+//! Fractional-Kelly auto-tuning from realized edge. Each closed trade's
+//! realized P&L is compared with the net profit predicted when it was
+//! entered. Per sport, over a rolling window of trades, the ratio of the two
+//! scales the Kelly fraction down for sports whose fair-value model has been
+//! overconfident. It never scales sizing above the configured fraction.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::config::KellyTuningConfig;
+
+/// One closed trade: predicted and realized net profit, in cents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ClosedTrade {
+    predicted_cents: i64,
+    realized_cents: i64,
+}
+
+#[derive(Debug, Clone)]
+struct OpenEntry {
+    sport: String,
+    predicted_cents: i64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct KellyTuner {
+    config: KellyTuningConfig,
+    open: HashMap<String, OpenEntry>,
+    closed: HashMap<String, VecDeque<ClosedTrade>>,
+    multipliers: HashMap<String, f64>,
+}
+
+impl KellyTuner {
+    pub fn new(config: KellyTuningConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Record an entry in `ticker` predicted to net `predicted_cents`. A
+    /// top-up adds to the prediction for the position it joins.
+    pub fn record_entry(&mut self, ticker: &str, sport: &str, predicted_cents: i64) {
+        self.open
+            .entry(ticker.to_string())
+            .and_modify(|e| e.predicted_cents += predicted_cents)
+            .or_insert_with(|| OpenEntry {
+                sport: sport.to_string(),
+                predicted_cents,
+            });
+    }
+
+    /// Drop an entry that never became a position (e.g. a cancelled order).
+    pub fn forget(&mut self, ticker: &str) {
+        self.open.remove(ticker);
+    }
+
+    /// Record the position in `ticker` closing for `realized_cents`. Returns
+    /// the sport and its new multiplier when the multiplier changes.
+    /// Positions with no recorded entry (reconciled at startup) are ignored.
+    pub fn record_exit(&mut self, ticker: &str, realized_cents: i64) -> Option<(String, f64)> {
+        let entry = self.open.remove(ticker)?;
+        let window = self.config.window_trades.max(1);
+        let trades = self.closed.entry(entry.sport.clone()).or_default();
+        if trades.len() == window {
+            trades.pop_front();
+        }
+        trades.push_back(ClosedTrade {
+            predicted_cents: entry.predicted_cents,
+            realized_cents,
+        });
+        if !self.config.auto_tune {
+            return None;
+        }
+        let multiplier = self.calibrated(&entry.sport);
+        let previous = self.multipliers.insert(entry.sport.clone(), multiplier);
+        let changed = (previous.unwrap_or(1.0) - multiplier).abs() > f64::EPSILON;
+        changed.then_some((entry.sport, multiplier))
+    }
+
+    /// Kelly fraction scale in force for `sport`; 1.0 until tuned.
+    pub fn multiplier(&self, sport: &str) -> f64 {
+        self.multipliers.get(sport).copied().unwrap_or(1.0)
+    }
+
+    /// Closed trades in `sport`'s window.
+    pub fn trades(&self, sport: &str) -> usize {
+        self.closed.get(sport).map_or(0, VecDeque::len)
+    }
+
+    pub fn config(&self) -> &KellyTuningConfig {
+        &self.config
+    }
+
+    /// Realized over predicted profit across the window, clamped to
+    /// `[min_multiplier, 1]`. 1.0 until `min_trades` have closed or while
+    /// the window predicts no profit to compare against.
+    fn calibrated(&self, sport: &str) -> f64 {
+        let Some(trades) = self.closed.get(sport) else {
+            return 1.0;
+        };
+        if trades.len() < self.config.min_trades {
+            return 1.0;
+        }
+        let predicted: i64 = trades.iter().map(|t| t.predicted_cents).sum();
+        let realized: i64 = trades.iter().map(|t| t.realized_cents).sum();
+        if predicted <= 0 {
+            return 1.0;
+        }
+        (realized as f64 / predicted as f64).clamp(self.config.min_multiplier.clamp(0.0, 1.0), 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> KellyTuningConfig {
+        KellyTuningConfig {
+            auto_tune: true,
+            window_trades: 4,
+            min_trades: 2,
+            min_multiplier: 0.25,
+        }
+    }
+
+    fn close(tuner: &mut KellyTuner, sport: &str, predicted: i64, realized: i64) -> Option<f64> {
+        tuner.record_entry("T", sport, predicted);
+        tuner.record_exit("T", realized).map(|(_, m)| m)
+    }
+
+    #[test]
+    fn test_overconfident_sport_is_scaled_down_within_window() {
+        let mut tuner = KellyTuner::new(config());
+        // Below min_trades: untouched
+        assert_eq!(close(&mut tuner, "basketball", 10, 5), None);
+        assert_eq!(tuner.multiplier("basketball"), 1.0);
+        // 10 of 20 predicted realized
+        assert_eq!(close(&mut tuner, "basketball", 10, 5), Some(0.5));
+        assert_eq!(tuner.multiplier("basketball"), 0.5);
+        // Other sports are tuned separately
+        assert_eq!(tuner.multiplier("ice-hockey"), 1.0);
+
+        // Beating the prediction never sizes above the configured fraction
+        assert_eq!(close(&mut tuner, "basketball", 10, 40), Some(1.0));
+        // Losses floor at min_multiplier
+        for _ in 0..4 {
+            close(&mut tuner, "basketball", 10, -10);
+        }
+        assert_eq!(tuner.trades("basketball"), 4);
+        assert_eq!(tuner.multiplier("basketball"), 0.25);
+    }
+
+    #[test]
+    fn test_top_ups_and_untracked_positions() {
+        let mut tuner = KellyTuner::new(config());
+        tuner.record_entry("T", "basketball", 6);
+        tuner.record_entry("T", "basketball", 4);
+        tuner.record_exit("T", 5);
+        tuner.record_entry("T", "basketball", 10);
+        assert_eq!(tuner.record_exit("T", 5).map(|(_, m)| m), Some(0.5));
+
+        // Reconciled positions and cancelled entries are not counted
+        assert_eq!(tuner.record_exit("RECONCILED", -50), None);
+        tuner.record_entry("CANCELLED", "basketball", 10);
+        tuner.forget("CANCELLED");
+        assert_eq!(tuner.record_exit("CANCELLED", -10), None);
+        assert_eq!(tuner.trades("basketball"), 2);
+
+        // Off: trades are kept but sizing is untouched
+        let mut off = KellyTuner::default();
+        assert_eq!(close(&mut off, "basketball", 10, -10), None);
+        assert_eq!(close(&mut off, "basketball", 10, -10), None);
+        assert_eq!(off.multiplier("basketball"), 1.0);
+    }
+}
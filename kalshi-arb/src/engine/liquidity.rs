@@ -0,0 +1,79 @@
+//! Rolling per-ticker book depth, for sizing positions to what a market can
+//! absorb. A thin market gets a small cap; a deep one is bounded only by the
+//! static risk limits.
+
+use std::collections::VecDeque;
+
+use crate::config::LiquidityCapConfig;
+
+/// Recent near-touch depth for one ticker.
+#[derive(Debug, Clone, Default)]
+pub struct LiquidityTracker {
+    samples: VecDeque<i64>,
+    sum: i64,
+}
+
+impl LiquidityTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one look at the book: contracts resting in the best levels of a
+    /// side, averaged over both sides. Keeps the last `window_size` samples.
+    pub fn push(&mut self, depth: i64, window_size: usize) {
+        while self.samples.len() >= window_size.max(1) {
+            if let Some(old) = self.samples.pop_front() {
+                self.sum -= old;
+            }
+        }
+        self.samples.push_back(depth);
+        self.sum += depth;
+    }
+
+    /// Mean depth over the window, or `None` before any sample.
+    pub fn average(&self) -> Option<f64> {
+        (!self.samples.is_empty()).then(|| self.sum as f64 / self.samples.len() as f64)
+    }
+
+    /// Largest position this market supports, once `min_samples` have been
+    /// seen. Zero means the book is too thin to trade at all.
+    pub fn position_cap(&self, config: &LiquidityCapConfig) -> Option<u32> {
+        if self.samples.len() < config.min_samples.max(1) {
+            return None;
+        }
+        let average = self.average()?;
+        Some((average * config.fraction).floor().max(0.0) as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cap_tracks_rolling_depth() {
+        let config = LiquidityCapConfig {
+            fraction: 0.2,
+            window_size: 4,
+            min_samples: 3,
+            ..LiquidityCapConfig::default()
+        };
+        let mut t = LiquidityTracker::new();
+        t.push(100, config.window_size);
+        t.push(100, config.window_size);
+        assert_eq!(t.position_cap(&config), None);
+        t.push(100, config.window_size);
+        assert_eq!(t.position_cap(&config), Some(20));
+
+        // The window rolls: four thin books replace the deep ones
+        for _ in 0..4 {
+            t.push(12, config.window_size);
+        }
+        assert_eq!(t.average(), Some(12.0));
+        assert_eq!(t.position_cap(&config), Some(2));
+        for _ in 0..4 {
+            t.push(4, config.window_size);
+        }
+        assert_eq!(t.position_cap(&config), Some(0));
+    }
+}
@@ -1,5 +1,6 @@
+use super::win_prob::GameSegment;
 use chrono::NaiveDate;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct MarketKey {
@@ -31,6 +32,64 @@ pub struct IndexedGame {
     pub draw: Option<SideMarket>,
     pub away_team: String,
     pub home_team: String,
+    /// The same game on Polymarket: one outcome token per team, with the
+    /// token id as `ticker` and its book mapped to YES/NO like Kalshi's.
+    pub polymarket_away: Option<SideMarket>,
+    pub polymarket_home: Option<SideMarket>,
+    /// Half/quarter winner markets for the same game, one entry per segment.
+    pub periods: Vec<PeriodMarket>,
+}
+
+/// Winner markets for one segment of a game (e.g. first half).
+#[derive(Debug, Clone)]
+pub struct PeriodMarket {
+    pub segment: GameSegment,
+    pub away: Option<SideMarket>,
+    pub home: Option<SideMarket>,
+}
+
+/// Exchange an order is routed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Venue {
+    #[default]
+    Kalshi,
+    Polymarket,
+}
+
+impl IndexedGame {
+    /// Every listed side across venues, period markets included.
+    pub fn sides(&self) -> impl Iterator<Item = &SideMarket> {
+        [
+            self.home.as_ref(),
+            self.away.as_ref(),
+            self.draw.as_ref(),
+            self.polymarket_home.as_ref(),
+            self.polymarket_away.as_ref(),
+        ]
+        .into_iter()
+        .chain(
+            self.periods
+                .iter()
+                .flat_map(|p| [p.home.as_ref(), p.away.as_ref()]),
+        )
+        .flatten()
+    }
+
+    /// The period entry for `segment`, created empty if not yet indexed.
+    pub fn period_mut(&mut self, segment: GameSegment) -> &mut PeriodMarket {
+        let pos = match self.periods.iter().position(|p| p.segment == segment) {
+            Some(pos) => pos,
+            None => {
+                self.periods.push(PeriodMarket {
+                    segment,
+                    away: None,
+                    home: None,
+                });
+                self.periods.len() - 1
+            }
+        };
+        &mut self.periods[pos]
+    }
 }
 
 /// Result of looking up a market — includes whether we had to invert.
@@ -64,75 +123,75 @@ fn team_code(sport: &str, name: &str) -> Option<&'static str> {
 
 fn nba_team_code(name: &str) -> Option<&'static str> {
     match name {
-        "ATLANTA HAWKS" | "ATLANTA" => Some("ATL"),
-        "BOSTON CELTICS" | "BOSTON" => Some("BOS"),
-        "BROOKLYN NETS" | "BROOKLYN" => Some("BKN"),
-        "CHARLOTTE HORNETS" | "CHARLOTTE" => Some("CHA"),
-        "CHICAGO BULLS" | "CHICAGO" => Some("CHI"),
-        "CLEVELAND CAVALIERS" | "CLEVELAND" => Some("CLE"),
-        "DALLAS MAVERICKS" | "DALLAS" => Some("DAL"),
-        "DENVER NUGGETS" | "DENVER" => Some("DEN"),
-        "DETROIT PISTONS" | "DETROIT" => Some("DET"),
-        "GOLDEN STATE WARRIORS" | "GOLDEN STATE" => Some("GSW"),
-        "HOUSTON ROCKETS" | "HOUSTON" => Some("HOU"),
-        "INDIANA PACERS" | "INDIANA" => Some("IND"),
-        "LOS ANGELES CLIPPERS" | "LOS ANGELES C" | "LA CLIPPERS" => Some("LAC"),
-        "LOS ANGELES LAKERS" | "LOS ANGELES L" | "LA LAKERS" => Some("LAL"),
-        "MEMPHIS GRIZZLIES" | "MEMPHIS" => Some("MEM"),
-        "MIAMI HEAT" | "MIAMI" => Some("MIA"),
-        "MILWAUKEE BUCKS" | "MILWAUKEE" => Some("MIL"),
-        "MINNESOTA TIMBERWOLVES" | "MINNESOTA" => Some("MIN"),
-        "NEW ORLEANS PELICANS" | "NEW ORLEANS" => Some("NOP"),
-        "NEW YORK KNICKS" | "NEW YORK" => Some("NYK"),
-        "OKLAHOMA CITY THUNDER" | "OKLAHOMA CITY" => Some("OKC"),
-        "ORLANDO MAGIC" | "ORLANDO" => Some("ORL"),
-        "PHILADELPHIA 76ERS" | "PHILADELPHIA SIXERS" | "PHILADELPHIA" => Some("PHI"),
-        "PHOENIX SUNS" | "PHOENIX" => Some("PHX"),
-        "PORTLAND TRAIL BLAZERS" | "PORTLAND" => Some("POR"),
-        "SACRAMENTO KINGS" | "SACRAMENTO" => Some("SAC"),
-        "SAN ANTONIO SPURS" | "SAN ANTONIO" => Some("SAS"),
-        "TORONTO RAPTORS" | "TORONTO" => Some("TOR"),
-        "UTAH JAZZ" | "UTAH" => Some("UTA"),
-        "WASHINGTON WIZARDS" | "WASHINGTON" => Some("WAS"),
+        "ATLANTA HAWKS" | "ATLANTA" | "HAWKS" => Some("ATL"),
+        "BOSTON CELTICS" | "BOSTON" | "CELTICS" => Some("BOS"),
+        "BROOKLYN NETS" | "BROOKLYN" | "NETS" => Some("BKN"),
+        "CHARLOTTE HORNETS" | "CHARLOTTE" | "HORNETS" => Some("CHA"),
+        "CHICAGO BULLS" | "CHICAGO" | "BULLS" => Some("CHI"),
+        "CLEVELAND CAVALIERS" | "CLEVELAND" | "CAVALIERS" => Some("CLE"),
+        "DALLAS MAVERICKS" | "DALLAS" | "MAVERICKS" => Some("DAL"),
+        "DENVER NUGGETS" | "DENVER" | "NUGGETS" => Some("DEN"),
+        "DETROIT PISTONS" | "DETROIT" | "PISTONS" => Some("DET"),
+        "GOLDEN STATE WARRIORS" | "GOLDEN STATE" | "WARRIORS" => Some("GSW"),
+        "HOUSTON ROCKETS" | "HOUSTON" | "ROCKETS" => Some("HOU"),
+        "INDIANA PACERS" | "INDIANA" | "PACERS" => Some("IND"),
+        "LOS ANGELES CLIPPERS" | "LOS ANGELES C" | "LA CLIPPERS" | "CLIPPERS" => Some("LAC"),
+        "LOS ANGELES LAKERS" | "LOS ANGELES L" | "LA LAKERS" | "LAKERS" => Some("LAL"),
+        "MEMPHIS GRIZZLIES" | "MEMPHIS" | "GRIZZLIES" => Some("MEM"),
+        "MIAMI HEAT" | "MIAMI" | "HEAT" => Some("MIA"),
+        "MILWAUKEE BUCKS" | "MILWAUKEE" | "BUCKS" => Some("MIL"),
+        "MINNESOTA TIMBERWOLVES" | "MINNESOTA" | "TIMBERWOLVES" => Some("MIN"),
+        "NEW ORLEANS PELICANS" | "NEW ORLEANS" | "PELICANS" => Some("NOP"),
+        "NEW YORK KNICKS" | "NEW YORK" | "KNICKS" => Some("NYK"),
+        "OKLAHOMA CITY THUNDER" | "OKLAHOMA CITY" | "THUNDER" => Some("OKC"),
+        "ORLANDO MAGIC" | "ORLANDO" | "MAGIC" => Some("ORL"),
+        "PHILADELPHIA 76ERS" | "PHILADELPHIA SIXERS" | "PHILADELPHIA" | "76ERS" => Some("PHI"),
+        "PHOENIX SUNS" | "PHOENIX" | "SUNS" => Some("PHX"),
+        "PORTLAND TRAIL BLAZERS" | "PORTLAND" | "TRAIL BLAZERS" => Some("POR"),
+        "SACRAMENTO KINGS" | "SACRAMENTO" | "KINGS" => Some("SAC"),
+        "SAN ANTONIO SPURS" | "SAN ANTONIO" | "SPURS" => Some("SAS"),
+        "TORONTO RAPTORS" | "TORONTO" | "RAPTORS" => Some("TOR"),
+        "UTAH JAZZ" | "UTAH" | "JAZZ" => Some("UTA"),
+        "WASHINGTON WIZARDS" | "WASHINGTON" | "WIZARDS" => Some("WAS"),
         _ => None,
     }
 }
 
 fn nhl_team_code(name: &str) -> Option<&'static str> {
     match name {
-        "ANAHEIM DUCKS" | "ANAHEIM" => Some("ANA"),
-        "ARIZONA COYOTES" | "ARIZONA" => Some("ARI"),
-        "BOSTON BRUINS" => Some("BOS"),
-        "BUFFALO SABRES" | "BUFFALO" => Some("BUF"),
-        "CALGARY FLAMES" | "CALGARY" => Some("CGY"),
-        "CAROLINA HURRICANES" | "CAROLINA" => Some("CAR"),
-        "CHICAGO BLACKHAWKS" => Some("CHI"),
-        "COLORADO AVALANCHE" | "COLORADO" => Some("COL"),
-        "COLUMBUS BLUE JACKETS" | "COLUMBUS" => Some("CBJ"),
-        "DALLAS STARS" => Some("DAL"),
-        "DETROIT RED WINGS" => Some("DET"),
-        "EDMONTON OILERS" | "EDMONTON" => Some("EDM"),
-        "FLORIDA PANTHERS" | "FLORIDA" => Some("FLA"),
-        "LOS ANGELES KINGS" | "LOS ANGELES" | "LA KINGS" => Some("LA"),
-        "MINNESOTA WILD" => Some("MIN"),
-        "MONTREAL CANADIENS" | "MONTREAL" => Some("MTL"),
-        "NASHVILLE PREDATORS" | "NASHVILLE" => Some("NSH"),
-        "NEW JERSEY DEVILS" | "NEW JERSEY" => Some("NJ"),
-        "NEW YORK ISLANDERS" | "NEW YORK I" | "NY ISLANDERS" => Some("NYI"),
-        "NEW YORK RANGERS" | "NEW YORK R" | "NY RANGERS" => Some("NYR"),
-        "OTTAWA SENATORS" | "OTTAWA" => Some("OTT"),
-        "PHILADELPHIA FLYERS" => Some("PHI"),
-        "PITTSBURGH PENGUINS" | "PITTSBURGH" => Some("PIT"),
-        "SAN JOSE SHARKS" | "SAN JOSE" => Some("SJ"),
-        "SEATTLE KRAKEN" | "SEATTLE" => Some("SEA"),
-        "ST LOUIS BLUES" | "ST. LOUIS BLUES" | "ST LOUIS" | "ST. LOUIS" => Some("STL"),
-        "TAMPA BAY LIGHTNING" | "TAMPA BAY" => Some("TB"),
-        "TORONTO MAPLE LEAFS" => Some("TOR"),
-        "UTAH HOCKEY CLUB" => Some("UTA"),
-        "VANCOUVER CANUCKS" | "VANCOUVER" => Some("VAN"),
-        "VEGAS GOLDEN KNIGHTS" | "VEGAS" => Some("VGK"),
-        "WASHINGTON CAPITALS" | "WASHINGTON" => Some("WSH"),
-        "WINNIPEG JETS" | "WINNIPEG" => Some("WPG"),
+        "ANAHEIM DUCKS" | "ANAHEIM" | "DUCKS" => Some("ANA"),
+        "ARIZONA COYOTES" | "ARIZONA" | "COYOTES" => Some("ARI"),
+        "BOSTON BRUINS" | "BRUINS" => Some("BOS"),
+        "BUFFALO SABRES" | "BUFFALO" | "SABRES" => Some("BUF"),
+        "CALGARY FLAMES" | "CALGARY" | "FLAMES" => Some("CGY"),
+        "CAROLINA HURRICANES" | "CAROLINA" | "HURRICANES" => Some("CAR"),
+        "CHICAGO BLACKHAWKS" | "BLACKHAWKS" => Some("CHI"),
+        "COLORADO AVALANCHE" | "COLORADO" | "AVALANCHE" => Some("COL"),
+        "COLUMBUS BLUE JACKETS" | "COLUMBUS" | "BLUE JACKETS" => Some("CBJ"),
+        "DALLAS STARS" | "STARS" => Some("DAL"),
+        "DETROIT RED WINGS" | "RED WINGS" => Some("DET"),
+        "EDMONTON OILERS" | "EDMONTON" | "OILERS" => Some("EDM"),
+        "FLORIDA PANTHERS" | "FLORIDA" | "PANTHERS" => Some("FLA"),
+        "LOS ANGELES KINGS" | "LOS ANGELES" | "LA KINGS" | "KINGS" => Some("LA"),
+        "MINNESOTA WILD" | "WILD" => Some("MIN"),
+        "MONTREAL CANADIENS" | "MONTREAL" | "CANADIENS" => Some("MTL"),
+        "NASHVILLE PREDATORS" | "NASHVILLE" | "PREDATORS" => Some("NSH"),
+        "NEW JERSEY DEVILS" | "NEW JERSEY" | "DEVILS" => Some("NJ"),
+        "NEW YORK ISLANDERS" | "NEW YORK I" | "NY ISLANDERS" | "ISLANDERS" => Some("NYI"),
+        "NEW YORK RANGERS" | "NEW YORK R" | "NY RANGERS" | "RANGERS" => Some("NYR"),
+        "OTTAWA SENATORS" | "OTTAWA" | "SENATORS" => Some("OTT"),
+        "PHILADELPHIA FLYERS" | "FLYERS" => Some("PHI"),
+        "PITTSBURGH PENGUINS" | "PITTSBURGH" | "PENGUINS" => Some("PIT"),
+        "SAN JOSE SHARKS" | "SAN JOSE" | "SHARKS" => Some("SJ"),
+        "SEATTLE KRAKEN" | "SEATTLE" | "KRAKEN" => Some("SEA"),
+        "ST LOUIS BLUES" | "ST. LOUIS BLUES" | "ST LOUIS" | "ST. LOUIS" | "BLUES" => Some("STL"),
+        "TAMPA BAY LIGHTNING" | "TAMPA BAY" | "LIGHTNING" => Some("TB"),
+        "TORONTO MAPLE LEAFS" | "MAPLE LEAFS" => Some("TOR"),
+        "UTAH HOCKEY CLUB" | "UTAH MAMMOTH" | "MAMMOTH" => Some("UTA"),
+        "VANCOUVER CANUCKS" | "VANCOUVER" | "CANUCKS" => Some("VAN"),
+        "VEGAS GOLDEN KNIGHTS" | "VEGAS" | "GOLDEN KNIGHTS" => Some("VGK"),
+        "WASHINGTON CAPITALS" | "WASHINGTON" | "CAPITALS" => Some("WSH"),
+        "WINNIPEG JETS" | "WINNIPEG" | "JETS" => Some("WPG"),
         _ => None,
     }
 }
@@ -502,16 +561,159 @@ pub fn generate_key(sport: &str, team1: &str, team2: &str, date: NaiveDate) -> O
     let mut teams = [n1, n2];
     teams.sort();
     Some(MarketKey {
-        sport: sport
-            .to_uppercase()
-            .chars()
-            .filter(|c| c.is_ascii_alphabetic())
-            .collect(),
+        sport: key_sport(sport),
         date,
         teams,
     })
 }
 
+fn key_sport(sport: &str) -> String {
+    sport
+        .to_uppercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .collect()
+}
+
+/// Jaro-Winkler similarity of two strings, from 0.0 (nothing in common) to
+/// 1.0 (identical). Shared leading characters count extra, which suits team
+/// names that differ mostly in their suffix ("MIAMI" vs "MIAMIFL").
+pub fn jaro_winkler(a: &str, b: &str) -> f64 {
+    let a = a.as_bytes();
+    let b = b.as_bytes();
+    if a.is_empty() || b.is_empty() {
+        return if a == b { 1.0 } else { 0.0 };
+    }
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0usize;
+    for (i, &ca) in a.iter().enumerate() {
+        let lo = i.saturating_sub(window);
+        let hi = (i + window + 1).min(b.len());
+        for j in lo..hi {
+            if !b_matched[j] && b[j] == ca {
+                a_matched[i] = true;
+                b_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+    if matches == 0 {
+        return 0.0;
+    }
+    let a_seq = a.iter().zip(&a_matched).filter(|(_, &m)| m).map(|(c, _)| c);
+    let b_seq = b.iter().zip(&b_matched).filter(|(_, &m)| m).map(|(c, _)| c);
+    let transpositions = a_seq.zip(b_seq).filter(|(x, y)| x != y).count() / 2;
+    let m = matches as f64;
+    let jaro = (m / a.len() as f64 + m / b.len() as f64 + (m - transpositions as f64) / m) / 3.0;
+    let prefix = a.iter().zip(b).take(4).take_while(|(x, y)| x == y).count();
+    jaro + prefix as f64 * 0.1 * (1.0 - jaro)
+}
+
+/// The indexed game a feed matchup most resembles by team name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    pub key: MarketKey,
+    /// Indexed team names, oriented to the feed's home and away.
+    pub home_team: String,
+    pub away_team: String,
+    /// The weaker of the two teams' similarities.
+    pub score: f64,
+    /// How far the match leads its closest rival: the next-best game, or
+    /// another team playing that day that either feed team resembles.
+    pub margin: f64,
+}
+
+/// Least lead over the runner-up for a fuzzy match to bind. "Miami" scores
+/// the same against "Miami FL" and "Miami OH", so on a day both play it
+/// binds to neither.
+pub const FUZZY_MIN_MARGIN: f64 = 0.05;
+
+impl FuzzyMatch {
+    /// Whether the match scores at least `min_score` and is clear of its
+    /// rivals by [`FUZZY_MIN_MARGIN`].
+    pub fn accepted(&self, min_score: f64) -> bool {
+        self.score >= min_score && self.margin >= FUZZY_MIN_MARGIN
+    }
+}
+
+/// Closest indexed game of the same sport and date to a matchup whose key
+/// isn't in the index, scored by Jaro-Winkler over normalized team names.
+/// Both teams must resemble the game's teams, so one team's name alone never
+/// matches. The caller decides with [`FuzzyMatch::accepted`] whether the
+/// match is good and unambiguous enough.
+pub fn fuzzy_match(
+    index: &MarketIndex,
+    sport: &str,
+    home_team: &str,
+    away_team: &str,
+    date: NaiveDate,
+) -> Option<FuzzyMatch> {
+    let home = normalize_team(sport, home_team);
+    let away = normalize_team(sport, away_team);
+    if home.is_empty() || away.is_empty() {
+        return None;
+    }
+    let sport_key = key_sport(sport);
+    let mut candidates: Vec<FuzzyMatch> = index
+        .iter()
+        .filter(|(key, _)| key.date == date && key.sport == sport_key)
+        .map(|(key, game)| {
+            let game_home = normalize_team(sport, &game.home_team);
+            let game_away = normalize_team(sport, &game.away_team);
+            let same = jaro_winkler(&home, &game_home).min(jaro_winkler(&away, &game_away));
+            let swapped = jaro_winkler(&home, &game_away).min(jaro_winkler(&away, &game_home));
+            let (home_team, away_team) = if swapped > same {
+                (&game.away_team, &game.home_team)
+            } else {
+                (&game.home_team, &game.away_team)
+            };
+            FuzzyMatch {
+                key: key.clone(),
+                home_team: home_team.clone(),
+                away_team: away_team.clone(),
+                score: same.max(swapped),
+                margin: 0.0,
+            }
+        })
+        .collect();
+    // Best first; ties go to the alphabetically first game, whatever the
+    // map order
+    candidates.sort_by(|x, y| {
+        y.score
+            .total_cmp(&x.score)
+            .then_with(|| x.key.teams.cmp(&y.key.teams))
+    });
+    let mut candidates = candidates.into_iter();
+    let mut best = candidates.next()?;
+    let runner_up = candidates.next().map_or(0.0, |m| m.score);
+
+    // Each feed team must also resemble its matched team more than any
+    // other team playing that day
+    let best_home = normalize_team(sport, &best.home_team);
+    let best_away = normalize_team(sport, &best.away_team);
+    let rivals: Vec<String> = index
+        .iter()
+        .filter(|(key, _)| key.date == date && key.sport == sport_key)
+        .flat_map(|(_, game)| [&game.home_team, &game.away_team])
+        .map(|team| normalize_team(sport, team))
+        .filter(|team| *team != best_home && *team != best_away)
+        .collect();
+    let lead = |feed: &str, matched: &str| {
+        let rival = rivals
+            .iter()
+            .map(|team| jaro_winkler(feed, team))
+            .fold(0.0, f64::max);
+        jaro_winkler(feed, matched) - rival
+    };
+    best.margin = (best.score - runner_up)
+        .min(lead(&home, &best_home))
+        .min(lead(&away, &best_away));
+    Some(best)
+}
+
 /// Parse date from Kalshi event ticker.
 /// Format: "KXNBAGAME-26JAN19LACWAS" -> 2026-01-19
 pub fn parse_date_from_ticker(ticker: &str) -> Option<NaiveDate> {
@@ -566,6 +768,37 @@ pub fn parse_kalshi_title(title: &str) -> Option<(String, String)> {
     Some((away, home))
 }
 
+/// Parse a period winner market title, e.g. "Los Angeles L at Boston:
+/// First Half Winner?" or "Los Angeles L at Boston 1st Half Winner?", by
+/// dropping the segment wording before the usual game-title parse.
+pub fn parse_period_title(title: &str) -> Option<(String, String)> {
+    let game = match title.split_once(':') {
+        Some((game, _)) => game.to_string(),
+        None => {
+            let lower = title.to_lowercase();
+            let cut = [
+                " first half",
+                " second half",
+                " 1st half",
+                " 2nd half",
+                " first quarter",
+                " second quarter",
+                " third quarter",
+                " fourth quarter",
+                " 1st quarter",
+                " 2nd quarter",
+                " 3rd quarter",
+                " 4th quarter",
+            ]
+            .iter()
+            .filter_map(|w| lower.find(w))
+            .min()?;
+            title[..cut].to_string()
+        }
+    };
+    parse_kalshi_title(game.trim())
+}
+
 /// Parse UFC/MMA title to extract fighter names from the event portion.
 /// Title format: "Will X win the Fighter1 vs Fighter2 professional MMA fight scheduled for ..."
 /// Returns (fighter1, fighter2) from the event portion.
@@ -662,10 +895,126 @@ pub fn find_match(
 
 pub type MarketIndex = HashMap<MarketKey, IndexedGame>;
 
+/// Markets closing within this many seconds get a REST status re-check
+/// before an order is submitted against them.
+pub const CLOSE_RECHECK_WINDOW_SECS: i64 = 60;
+
+/// Pre-submit verdict on whether a market is still tradeable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitCheck {
+    Open,
+    Closed,
+    /// Unknown status or close time within the re-check window: confirm via REST.
+    Borderline,
+}
+
+/// Find the indexed side market for `ticker`.
+pub fn find_side_market<'a>(index: &'a MarketIndex, ticker: &str) -> Option<&'a SideMarket> {
+    index
+        .values()
+        .find_map(|game| game.sides().find(|sm| sm.ticker == ticker))
+}
+
+/// Keys of the games with a market, on either venue, on any of `tickers`.
+pub fn games_trading(index: &MarketIndex, tickers: &HashSet<String>) -> HashSet<MarketKey> {
+    index
+        .iter()
+        .filter(|(_, game)| game.sides().any(|sm| tickers.contains(&sm.ticker)))
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+/// Polymarket token that pays out on the same outcome as buying `side` of the
+/// Kalshi market `ticker`. Two-way games only: with a draw, NO on one team is
+/// not a win for the other.
+pub fn polymarket_equivalent<'a>(
+    index: &'a MarketIndex,
+    ticker: &str,
+    side: &str,
+) -> Option<&'a SideMarket> {
+    let game = index.values().find(|g| {
+        g.home.as_ref().is_some_and(|m| m.ticker == ticker)
+            || g.away.as_ref().is_some_and(|m| m.ticker == ticker)
+    })?;
+    if game.draw.is_some() {
+        return None;
+    }
+    let on_home = game.home.as_ref().is_some_and(|m| m.ticker == ticker);
+    if on_home == (side == "yes") {
+        game.polymarket_home.as_ref()
+    } else {
+        game.polymarket_away.as_ref()
+    }
+}
+
+/// Mark `ticker` closed in the index so later cycles stop signalling on it.
+pub fn mark_closed(index: &mut MarketIndex, ticker: &str) {
+    for game in index.values_mut() {
+        let periods = game
+            .periods
+            .iter_mut()
+            .flat_map(|p| [&mut p.home, &mut p.away]);
+        for sm in [
+            &mut game.home,
+            &mut game.away,
+            &mut game.draw,
+            &mut game.polymarket_home,
+            &mut game.polymarket_away,
+        ]
+        .into_iter()
+        .chain(periods)
+        .flatten()
+        {
+            if sm.ticker == ticker {
+                sm.status = "closed".to_string();
+            }
+        }
+    }
+}
+
+/// Classify a market from its `status` and RFC 3339 `close_time` at `now`.
+/// A close time within `window_secs` is borderline; pass 0 to force a verdict.
+pub fn submit_check(
+    status: &str,
+    close_time: Option<&str>,
+    now: chrono::DateTime<chrono::Utc>,
+    window_secs: i64,
+) -> SubmitCheck {
+    if status != "open" && status != "active" {
+        return SubmitCheck::Closed;
+    }
+    let Some(close) = close_time.and_then(|ct| chrono::DateTime::parse_from_rfc3339(ct).ok())
+    else {
+        return SubmitCheck::Open;
+    };
+    let remaining = (close.with_timezone(&chrono::Utc) - now).num_seconds();
+    if remaining <= 0 {
+        SubmitCheck::Closed
+    } else if remaining <= window_secs {
+        SubmitCheck::Borderline
+    } else {
+        SubmitCheck::Open
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_period_title() {
+        let expected = Some(("Los Angeles L".to_string(), "Boston".to_string()));
+        assert_eq!(
+            parse_period_title("Los Angeles L at Boston: First Half Winner?"),
+            expected
+        );
+        assert_eq!(
+            parse_period_title("Los Angeles L at Boston 3rd Quarter Winner?"),
+            expected
+        );
+        assert_eq!(parse_period_title("Los Angeles L at Boston Winner?"), None);
+    }
+
     #[test]
     fn test_normalize_team() {
         // With lookup tables, NBA teams return ticker codes
@@ -794,6 +1143,55 @@ mod tests {
         assert_eq!(sm.close_time.as_deref(), Some("2026-01-20T04:00:00Z"));
     }
 
+    #[test]
+    fn test_submit_check_flags_closed_and_borderline_markets() {
+        let now = chrono::DateTime::parse_from_rfc3339("2026-01-20T03:59:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let close = Some("2026-01-20T04:00:00Z");
+        assert_eq!(submit_check("open", close, now, 30), SubmitCheck::Open);
+        assert_eq!(
+            submit_check("open", close, now, 60),
+            SubmitCheck::Borderline
+        );
+        assert_eq!(submit_check("active", close, now, 0), SubmitCheck::Open);
+        assert_eq!(submit_check("closed", close, now, 60), SubmitCheck::Closed);
+        assert_eq!(
+            submit_check("open", close, now + chrono::Duration::seconds(60), 0),
+            SubmitCheck::Closed
+        );
+        assert_eq!(submit_check("open", None, now, 60), SubmitCheck::Open);
+
+        let mut index = MarketIndex::new();
+        let key = generate_key(
+            "basketball",
+            "LA Clippers",
+            "Washington Wizards",
+            NaiveDate::from_ymd_opt(2026, 1, 19).unwrap(),
+        )
+        .unwrap();
+        index.insert(
+            key,
+            IndexedGame {
+                home: Some(SideMarket {
+                    ticker: "KXNBAGAME-26JAN19LACWAS-WAS".to_string(),
+                    title: "Test".to_string(),
+                    yes_bid: 50,
+                    yes_ask: 55,
+                    no_bid: 45,
+                    no_ask: 50,
+                    status: "open".to_string(),
+                    close_time: close.map(str::to_string),
+                }),
+                ..Default::default()
+            },
+        );
+        mark_closed(&mut index, "KXNBAGAME-26JAN19LACWAS-WAS");
+        let sm = find_side_market(&index, "KXNBAGAME-26JAN19LACWAS-WAS").unwrap();
+        assert_eq!(sm.status, "closed");
+        assert!(find_side_market(&index, "KXNBAGAME-26JAN19LACWAS-LAC").is_none());
+    }
+
     #[test]
     fn test_team_code_nba_full_names() {
         assert_eq!(team_code("basketball", "Los Angeles Lakers"), Some("LAL"));
@@ -915,6 +1313,87 @@ mod tests {
         assert_eq!(normalize_team(s, "Hawaii Rainbow Warriors"), "HAWAII");
     }
 
+    #[test]
+    fn test_polymarket_equivalent_backs_same_team() {
+        let d = NaiveDate::from_ymd_opt(2026, 1, 19).unwrap();
+        // Polymarket lists NBA teams by nickname
+        let key = generate_key("basketball", "Lakers", "Celtics", d).unwrap();
+        assert_eq!(
+            key,
+            generate_key("basketball", "Los Angeles L", "Boston", d).unwrap()
+        );
+
+        let side = |ticker: &str| SideMarket {
+            ticker: ticker.to_string(),
+            title: "Test".to_string(),
+            yes_bid: 0,
+            yes_ask: 0,
+            no_bid: 0,
+            no_ask: 0,
+            status: "open".to_string(),
+            close_time: None,
+        };
+        let mut index = MarketIndex::new();
+        index.insert(
+            key,
+            IndexedGame {
+                home: Some(side("KXNBAGAME-26JAN19LALBOS-BOS")),
+                away: Some(side("KXNBAGAME-26JAN19LALBOS-LAL")),
+                polymarket_home: Some(side("222")),
+                polymarket_away: Some(side("111")),
+                ..Default::default()
+            },
+        );
+        let eq = |ticker: &str, side: &str| {
+            polymarket_equivalent(&index, ticker, side).map(|m| m.ticker.as_str())
+        };
+        assert_eq!(eq("KXNBAGAME-26JAN19LALBOS-BOS", "yes"), Some("222"));
+        assert_eq!(eq("KXNBAGAME-26JAN19LALBOS-BOS", "no"), Some("111"));
+        assert_eq!(eq("KXNBAGAME-26JAN19LALBOS-LAL", "yes"), Some("111"));
+        assert_eq!(eq("KXNBAGAME-OTHER", "yes"), None);
+        assert!(find_side_market(&index, "111").is_some());
+    }
+
+    #[test]
+    fn test_games_trading_finds_games_by_any_market() {
+        let d = NaiveDate::from_ymd_opt(2026, 1, 19).unwrap();
+        let side = |ticker: &str| SideMarket {
+            ticker: ticker.to_string(),
+            title: "Test".to_string(),
+            yes_bid: 0,
+            yes_ask: 0,
+            no_bid: 0,
+            no_ask: 0,
+            status: "open".to_string(),
+            close_time: None,
+        };
+        let lal_bos = generate_key("basketball", "Lakers", "Celtics", d).unwrap();
+        let mia_nyk = generate_key("basketball", "Heat", "Knicks", d).unwrap();
+        let mut index = MarketIndex::new();
+        index.insert(
+            lal_bos.clone(),
+            IndexedGame {
+                home: Some(side("KXNBAGAME-26JAN19LALBOS-BOS")),
+                polymarket_away: Some(side("111")),
+                ..Default::default()
+            },
+        );
+        index.insert(
+            mia_nyk,
+            IndexedGame {
+                home: Some(side("KXNBAGAME-26JAN19MIANYK-NYK")),
+                ..Default::default()
+            },
+        );
+
+        let tickers = |t: &[&str]| t.iter().map(|s| s.to_string()).collect::<HashSet<_>>();
+        let games = games_trading(&index, &tickers(&["111"]));
+        assert_eq!(games, HashSet::from([lal_bos]));
+        let both = tickers(&["KXNBAGAME-26JAN19LALBOS-BOS", "KXNBAGAME-26JAN19MIANYK-NYK"]);
+        assert_eq!(games_trading(&index, &both).len(), 2);
+        assert!(games_trading(&index, &tickers(&["OTHER"])).is_empty());
+    }
+
     #[test]
     fn test_college_cross_source_matching() {
         let s = "college-basketball";
@@ -933,6 +1412,73 @@ mod tests {
         assert_eq!(k_poly, k_kalshi);
     }
 
+    #[test]
+    fn test_jaro_winkler() {
+        assert_eq!(jaro_winkler("DUKE", "DUKE"), 1.0);
+        assert_eq!(jaro_winkler("", "DUKE"), 0.0);
+        assert_eq!(jaro_winkler("ABC", "XYZ"), 0.0);
+        // Textbook values
+        assert!((jaro_winkler("MARTHA", "MARHTA") - 0.961).abs() < 0.001);
+        assert!((jaro_winkler("DIXON", "DICKSONX") - 0.813).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_fuzzy_match_same_day_games_only() {
+        let s = "college-basketball";
+        let d = NaiveDate::from_ymd_opt(2026, 2, 7).unwrap();
+        let mut index = MarketIndex::new();
+        for (away, home, date) in [
+            ("Miami FL", "Louisville", d),
+            ("Iowa St", "Kansas", d),
+            ("Miami OH", "Louisville", d.succ_opt().unwrap()),
+            ("Miami OH", "Ball St", d),
+        ] {
+            index.insert(
+                generate_key(s, away, home, date).unwrap(),
+                IndexedGame {
+                    away_team: away.to_string(),
+                    home_team: home.to_string(),
+                    ..Default::default()
+                },
+            );
+        }
+
+        // Miami FL and Miami OH both play: "Miami" is too close to both
+        let m = fuzzy_match(&index, s, "Miami", "Louisville", d).unwrap();
+        assert!(m.score > 0.9, "{}", m.score);
+        assert!(m.margin < FUZZY_MIN_MARGIN, "{}", m.margin);
+        assert!(!m.accepted(0.9));
+
+        // The day after, Miami OH is the only Miami; feed home/away are the
+        // reverse of Kalshi's and the names follow the feed
+        let next_day = d.succ_opt().unwrap();
+        let m = fuzzy_match(&index, s, "Miami", "Louisville", next_day).unwrap();
+        assert_eq!(
+            m.key,
+            generate_key(s, "Miami OH", "Louisville", next_day).unwrap()
+        );
+        assert_eq!(
+            (m.home_team.as_str(), m.away_team.as_str()),
+            ("Miami OH", "Louisville")
+        );
+        assert!(m.accepted(0.9), "{} {}", m.score, m.margin);
+
+        // One matching team is not enough
+        let m = fuzzy_match(&index, s, "Kansas", "Gonzaga", d).unwrap();
+        assert!(m.score < 0.7, "{}", m.score);
+        assert!(!m.accepted(0.9));
+
+        let other_day = d.pred_opt().unwrap();
+        assert_eq!(
+            fuzzy_match(&index, s, "Miami", "Louisville", other_day),
+            None
+        );
+        assert_eq!(
+            fuzzy_match(&index, "basketball", "Miami", "Louisville", d),
+            None
+        );
+    }
+
     #[test]
     fn test_team_code_fallback_unknown() {
         assert_eq!(team_code("basketball", "Nonexistent Team"), None);
@@ -1,14 +1,35 @@
+pub mod book_store;
+pub mod compaction;
+pub mod cross_arb;
+pub mod degradation;
+pub mod depth_walk;
+pub mod drawdown;
+pub mod edge_persistence;
+pub mod equity;
+pub mod escalation;
+pub mod eval_bus;
+pub mod exit_manager;
+pub mod fair_value;
+pub mod fee_tracker;
 pub mod fees;
 pub mod fill_simulator;
+pub mod fv_disagreement;
+pub mod game_filter;
 pub mod kelly;
+pub mod kelly_tuner;
+pub mod liquidity;
 pub mod matcher;
 pub mod momentum;
+pub mod order_flow;
 pub mod pending_orders;
 pub mod positions;
 pub mod risk;
+pub mod season;
 pub mod strategy;
 pub mod win_prob;
 
+pub use escalation::{AlertKind, EscalationPolicy, FeedDownWatch};
+pub use fee_tracker::{ExpectedFee, FeeSample, FeeTracker};
 pub use fill_simulator::{FillResult, FillSimulator};
 pub use pending_orders::{OrderSide, PendingOrderRegistry};
 pub use positions::PositionTracker;
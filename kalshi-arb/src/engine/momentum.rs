@@ -13,6 +13,8 @@ pub struct OddsSnapshot {
 pub struct VelocityTracker {
     snapshots: VecDeque<OddsSnapshot>,
     window_size: usize,
+    /// Set when the fair-value source changes; the next push re-levels history.
+    rebase_pending: bool,
 }
 
 impl VelocityTracker {
@@ -20,13 +22,32 @@ impl VelocityTracker {
         Self {
             snapshots: VecDeque::with_capacity(window_size),
             window_size,
+            rebase_pending: false,
         }
     }
 
+    /// Carry history across a fair-value source switch.
+    ///
+    /// Different sources disagree on level (devigged odds vs. a win-prob model),
+    /// so the first sample from the new source would read as a jump. On the next
+    /// push, existing snapshots are shifted by the gap between the old and new
+    /// levels, keeping the recent trend without a spurious velocity spike.
+    pub fn rebase_on_next_push(&mut self) {
+        self.rebase_pending = !self.snapshots.is_empty();
+    }
+
     /// Push a new odds snapshot. If implied_prob is identical to the previous
     /// snapshot, it's a stale cache hit -- skip it (don't store).
     /// Returns true if the snapshot was stored (i.e., it was a genuine update).
     pub fn push(&mut self, implied_prob: f64, timestamp: Instant) -> bool {
+        if std::mem::take(&mut self.rebase_pending) {
+            if let Some(last) = self.snapshots.back().map(|s| s.implied_prob) {
+                let offset = implied_prob - last;
+                for snap in self.snapshots.iter_mut() {
+                    snap.implied_prob += offset;
+                }
+            }
+        }
         // Skip stale duplicates
         if let Some(last) = self.snapshots.back() {
             if (last.implied_prob - implied_prob).abs() < 1e-9 {
@@ -246,6 +267,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_velocity_rebase_removes_source_jump() {
+        let mut tracker = VelocityTracker::new(5);
+        let t0 = Instant::now();
+        tracker.push(0.60, t0);
+        tracker.push(0.61, t0 + Duration::from_secs(60));
+        let before = tracker.score();
+        tracker.rebase_on_next_push();
+        // New source reads 8pp lower; without rebasing this is a big spike.
+        // The level gap is absorbed, so the new sample is a stale duplicate.
+        assert!(!tracker.push(0.53, t0 + Duration::from_secs(70)));
+        assert!((tracker.score() - before).abs() < 1e-9);
+        // Subsequent moves are measured from the re-levelled history.
+        tracker.push(0.54, t0 + Duration::from_secs(120));
+        let score = tracker.score();
+        assert!(
+            score > 0.0 && score < 20.0,
+            "score should be low: {}",
+            score
+        );
+    }
+
+    #[test]
+    fn test_velocity_rebase_on_empty_is_noop() {
+        let mut tracker = VelocityTracker::new(5);
+        tracker.rebase_on_next_push();
+        assert!(tracker.push(0.50, Instant::now()));
+        assert_eq!(tracker.score(), 0.0);
+    }
+
     // --- BookPressureTracker tests ---
 
     #[test]
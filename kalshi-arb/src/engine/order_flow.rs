@@ -0,0 +1,169 @@
+//! Kalshi-only fair value from a market's own order flow.
+//!
+//! Without a score or odds feed the only information is the book itself. The
+//! estimate anchors on the recent mean of the mid price and only departs from
+//! the current mid when the price has stretched outside a volatility band
+//! (mean reversion), then leans with resting depth near the touch (book
+//! pressure) and with which side of the touch is being consumed (flow).
+
+use std::collections::VecDeque;
+
+use crate::config::OrderFlowConfig;
+
+/// Smoothing of the per-sample flow reading.
+const FLOW_EMA_ALPHA: f64 = 0.3;
+
+/// One look at the book.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct BookSample {
+    mid: f64,
+    bid_depth: i64,
+    ask_depth: i64,
+}
+
+/// Fair value and the inputs that produced it, for traces.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderFlowEstimate {
+    pub fair_cents: u32,
+    pub mid: f64,
+    pub mean: f64,
+    /// Half-width of the reversion band around `mean`, in cents.
+    pub band: f64,
+    /// Near-touch depth imbalance, -1 (all asks) to 1 (all bids).
+    pub imbalance: f64,
+    /// Smoothed flow, -1 (selling) to 1 (buying); see `OrderFlowTracker::push`.
+    pub flow: f64,
+    pub samples: usize,
+}
+
+/// Rolling order-flow state for one ticker.
+#[derive(Debug)]
+pub struct OrderFlowTracker {
+    samples: VecDeque<BookSample>,
+    window_size: usize,
+    flow: f64,
+}
+
+impl OrderFlowTracker {
+    pub fn new(window_size: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(window_size),
+            window_size: window_size.max(2),
+            flow: 0.0,
+        }
+    }
+
+    /// Record the YES touch and the contracts resting near it. An unchanged
+    /// book is skipped, so an idle market doesn't shrink its own band.
+    ///
+    /// Flow is the change in bid depth minus the change in ask depth, over
+    /// total depth: asks being taken (or bids added) read as buying.
+    pub fn push(&mut self, yes_bid: u32, yes_ask: u32, bid_depth: i64, ask_depth: i64) {
+        let sample = BookSample {
+            mid: (yes_bid + yes_ask) as f64 / 2.0,
+            bid_depth,
+            ask_depth,
+        };
+        if let Some(last) = self.samples.back() {
+            if *last == sample {
+                return;
+            }
+            let total = (bid_depth + ask_depth).max(1) as f64;
+            let reading = (((bid_depth - last.bid_depth) - (ask_depth - last.ask_depth)) as f64
+                / total)
+                .clamp(-1.0, 1.0);
+            self.flow += FLOW_EMA_ALPHA * (reading - self.flow);
+        }
+        if self.samples.len() >= self.window_size {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Fair value once `min_samples` distinct books have been seen.
+    pub fn estimate(&self, config: &OrderFlowConfig) -> Option<OrderFlowEstimate> {
+        let last = self.samples.back()?;
+        if self.samples.len() < config.min_samples.max(2) {
+            return None;
+        }
+        let n = self.samples.len() as f64;
+        let mean = self.samples.iter().map(|s| s.mid).sum::<f64>() / n;
+        let variance = self
+            .samples
+            .iter()
+            .map(|s| (s.mid - mean).powi(2))
+            .sum::<f64>()
+            / n;
+        let band = (config.band_stddevs * variance.sqrt()).max(config.min_band_cents);
+
+        // Inside the band the mid is fair; outside it, expect a move back
+        let anchor = if (last.mid - mean).abs() > band {
+            mean
+        } else {
+            last.mid
+        };
+        let total = (last.bid_depth + last.ask_depth).max(1) as f64;
+        let imbalance = (last.bid_depth - last.ask_depth) as f64 / total;
+        let fair = anchor
+            + config.pressure_weight_cents * imbalance
+            + config.flow_weight_cents * self.flow;
+
+        Some(OrderFlowEstimate {
+            fair_cents: fair.round().clamp(1.0, 99.0) as u32,
+            mid: last.mid,
+            mean,
+            band,
+            imbalance,
+            flow: self.flow,
+            samples: self.samples.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> OrderFlowConfig {
+        OrderFlowConfig {
+            min_samples: 5,
+            ..OrderFlowConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_reverts_to_mean_outside_band_and_leans_with_depth() {
+        let mut t = OrderFlowTracker::new(20);
+        for i in 0..8 {
+            t.push(49 + i % 2, 51 + i % 2, 100, 100);
+        }
+        assert_eq!(t.estimate(&OrderFlowConfig::default()), None);
+        let quiet = t.estimate(&config()).unwrap();
+        assert_eq!(quiet.fair_cents, 51);
+        assert!(quiet.band >= 1.0);
+
+        // Spike far above the band: fair stays near the mean, not the new mid
+        t.push(60, 62, 100, 100);
+        let spike = t.estimate(&config()).unwrap();
+        assert_eq!(spike.mid, 61.0);
+        assert!(spike.fair_cents < 54, "{:?}", spike);
+
+        // Identical books are not resampled
+        t.push(60, 62, 100, 100);
+        assert_eq!(t.estimate(&config()).unwrap().samples, spike.samples);
+
+        // Asks pulled, bids stacked: the estimate leans up
+        let mut t = OrderFlowTracker::new(20);
+        for i in 0..6 {
+            t.push(
+                49 + i % 2,
+                51 + i % 2,
+                100 + i as i64 * 40,
+                100 - i as i64 * 15,
+            );
+        }
+        let bid_heavy = t.estimate(&config()).unwrap();
+        assert!(bid_heavy.imbalance > 0.5 && bid_heavy.flow > 0.0);
+        assert!(bid_heavy.fair_cents > bid_heavy.mid.round() as u32);
+    }
+}
@@ -65,6 +65,7 @@ impl PendingOrderRegistry {
     }
 
     /// Register with a known order ID (after submission succeeds).
+    #[allow(dead_code)]
     pub fn register_with_id(
         &mut self,
         ticker: String,
@@ -94,16 +95,19 @@ impl PendingOrderRegistry {
     }
 
     /// Get a pending order by ticker and side.
+    #[allow(dead_code)]
     pub fn get(&self, ticker: &str, side: OrderSide) -> Option<&PendingOrder> {
         self.orders.get(&(ticker.to_string(), side))
     }
 
     /// Get the order ID for a ticker and side (for cancellation).
+    #[allow(dead_code)]
     pub fn get_order_id(&self, ticker: &str, side: OrderSide) -> Option<String> {
         self.orders.get(&(ticker.to_string(), side)).and_then(|o| o.order_id.clone())
     }
 
     /// Get all pending order IDs (for bulk cancellation on kill-switch).
+    #[allow(dead_code)]
     pub fn all_order_ids(&self) -> Vec<String> {
         self.orders
             .values()
@@ -111,7 +115,13 @@ impl PendingOrderRegistry {
             .collect()
     }
 
+    /// Tickers with an order in flight on either side.
+    pub fn tickers(&self) -> impl Iterator<Item = &str> {
+        self.orders.keys().map(|(ticker, _)| ticker.as_str())
+    }
+
     /// Set the order ID after submission succeeds.
+    #[allow(dead_code)]
     pub fn set_order_id(&mut self, ticker: &str, side: OrderSide, order_id: String) {
         let key = (ticker.to_string(), side);
         if let Some(order) = self.orders.get_mut(&key) {
@@ -161,6 +171,7 @@ impl PendingOrderRegistry {
         self.orders.drain().map(|(_, order)| order).collect()
     }
 
+    #[allow(dead_code)]
     pub fn count(&self) -> usize {
         self.orders.len()
     }
@@ -5,12 +5,27 @@ use std::time::Instant;
 #[allow(dead_code)]
 pub struct Position {
     pub ticker: String,
+    pub side: String, // "yes" or "no"
     pub quantity: u32,
     pub entry_price: u32,
     pub entry_cost_cents: u32, // includes fees
     pub sell_target: u32,      // break-even exit price
     pub filled_at: Instant,    // for timeout tracking
     pub is_taker_entry: bool,  // for fee calculation
+    /// Polymarket token the position is held in, when its entry was routed
+    /// there; the position itself is keyed by the Kalshi outcome it backs.
+    pub venue_ticker: Option<String>,
+}
+
+impl Position {
+    /// Ticker and side the position's orders go to: its Polymarket token,
+    /// which is a YES on the outcome, or the Kalshi ticker and side.
+    pub fn order_leg(&self) -> (&str, &str) {
+        match self.venue_ticker.as_deref() {
+            Some(token) => (token, "yes"),
+            None => (&self.ticker, &self.side),
+        }
+    }
 }
 
 pub struct PositionTracker {
@@ -30,13 +45,27 @@ impl PositionTracker {
         }
     }
 
-    pub fn has_position(&self, ticker: &str) -> bool {
-        self.positions.contains_key(ticker)
+    /// Gate 1: whether an entry of `quantity` contracts on `side` may be
+    /// placed. It may top up a position held on the same side to
+    /// `max_contracts`; the other side of a held ticker is blocked.
+    pub fn can_enter(&self, ticker: &str, side: &str, quantity: u32, max_contracts: u32) -> bool {
+        let held = match self.positions.get(ticker) {
+            Some(p) if p.side != side => return false,
+            Some(p) => p.quantity,
+            None => 0,
+        };
+        held + quantity <= max_contracts
     }
 
+    /// Record a filled entry. An entry on the side already held in a ticker
+    /// tops the position up: quantities and costs add, the entry price
+    /// becomes the average, the higher sell target is kept and the hold
+    /// clock is not reset. An entry on the other side is never merged.
+    #[allow(clippy::too_many_arguments)]
     pub fn record_entry(
         &mut self,
         ticker: String,
+        side: &str,
         quantity: u32,
         entry_price: u32,
         entry_cost_cents: u32,
@@ -44,25 +73,79 @@ impl PositionTracker {
         filled_at: Instant,
         is_taker_entry: bool,
     ) {
+        if let Some(p) = self.positions.get_mut(&ticker) {
+            if p.side != side {
+                tracing::warn!(
+                    ticker = %ticker,
+                    held = %p.side,
+                    side,
+                    "entry on the other side of a held position not recorded"
+                );
+                return;
+            }
+            let cost = p.quantity * p.entry_price + quantity * entry_price;
+            p.quantity += quantity;
+            p.entry_price = cost / p.quantity.max(1);
+            p.entry_cost_cents += entry_cost_cents;
+            p.sell_target = p.sell_target.max(sell_target);
+            return;
+        }
         self.positions.insert(
             ticker.clone(),
             Position {
                 ticker,
+                side: side.to_string(),
                 quantity,
                 entry_price,
                 entry_cost_cents,
                 sell_target,
                 filled_at,
                 is_taker_entry,
+                venue_ticker: None,
             },
         );
     }
 
+    /// Mark the position on `ticker` as held in Polymarket token
+    /// `venue_ticker`.
+    pub fn set_venue_ticker(&mut self, ticker: &str, venue_ticker: String) {
+        if let Some(p) = self.positions.get_mut(ticker) {
+            p.venue_ticker = Some(venue_ticker);
+        }
+    }
+
+    /// Restart a position's hold clock from its actual exchange fill time.
+    pub fn mark_filled(&mut self, ticker: &str, filled_at: Instant) {
+        if let Some(p) = self.positions.get_mut(ticker) {
+            p.filled_at = filled_at;
+        }
+    }
+
     #[allow(dead_code)]
     pub fn record_exit(&mut self, ticker: &str) -> Option<Position> {
         self.positions.remove(ticker)
     }
 
+    /// Close `quantity` contracts of a position, returning them with their
+    /// share of its entry cost. The position is removed once none are left.
+    pub fn record_partial_exit(&mut self, ticker: &str, quantity: u32) -> Option<Position> {
+        let p = self.positions.get_mut(ticker)?;
+        if quantity == 0 {
+            return None;
+        }
+        if quantity >= p.quantity {
+            return self.positions.remove(ticker);
+        }
+        let cost = (p.entry_cost_cents as u64 * quantity as u64 / p.quantity as u64) as u32;
+        p.quantity -= quantity;
+        p.entry_cost_cents -= cost;
+        Some(Position {
+            quantity,
+            entry_cost_cents: cost,
+            ..p.clone()
+        })
+    }
+
     #[allow(dead_code)]
     pub fn get(&self, ticker: &str) -> Option<&Position> {
         self.positions.get(ticker)
@@ -87,15 +170,15 @@ mod tests {
     fn test_new_tracker_is_empty() {
         let tracker = PositionTracker::new();
         assert_eq!(tracker.count(), 0);
-        assert!(!tracker.has_position("TEST-TICKER"));
+        assert!(tracker.get("TEST-TICKER").is_none());
     }
 
     #[test]
     fn test_record_and_retrieve_position() {
         let mut tracker = PositionTracker::new();
-        tracker.record_entry("TEST-TICKER".to_string(), 10, 50, 520, 55, Instant::now(), true);
+        tracker.record_entry("TEST-TICKER".to_string(), "yes", 10, 50, 520, 55, Instant::now(), true);
 
-        assert!(tracker.has_position("TEST-TICKER"));
+        assert!(tracker.get("TEST-TICKER").is_some());
         assert_eq!(tracker.count(), 1);
 
         let pos = tracker.get("TEST-TICKER").unwrap();
@@ -110,16 +193,49 @@ mod tests {
     #[test]
     fn test_exit_removes_position() {
         let mut tracker = PositionTracker::new();
-        tracker.record_entry("TEST-TICKER".to_string(), 10, 50, 520, 55, Instant::now(), false);
+        tracker.record_entry("TEST-TICKER".to_string(), "yes", 10, 50, 520, 55, Instant::now(), false);
 
         let exited = tracker.record_exit("TEST-TICKER");
         assert!(exited.is_some());
         assert_eq!(exited.unwrap().quantity, 10);
 
-        assert!(!tracker.has_position("TEST-TICKER"));
+        assert!(tracker.get("TEST-TICKER").is_none());
         assert_eq!(tracker.count(), 0);
     }
 
+    #[test]
+    fn test_partial_exit_takes_its_share_of_cost() {
+        let mut tracker = PositionTracker::new();
+        tracker.record_entry("TEST-TICKER".to_string(), "no", 10, 50, 520, 55, Instant::now(), false);
+
+        let closed = tracker.record_partial_exit("TEST-TICKER", 4).unwrap();
+        assert_eq!((closed.quantity, closed.entry_cost_cents), (4, 208));
+        assert_eq!(closed.side, "no");
+        let pos = tracker.get("TEST-TICKER").unwrap();
+        assert_eq!((pos.quantity, pos.entry_cost_cents), (6, 312));
+
+        assert!(tracker.record_partial_exit("TEST-TICKER", 0).is_none());
+        let rest = tracker.record_partial_exit("TEST-TICKER", 9).unwrap();
+        assert_eq!((rest.quantity, rest.entry_cost_cents), (6, 312));
+        assert!(tracker.get("TEST-TICKER").is_none());
+    }
+
+    #[test]
+    fn test_venue_position_orders_its_token() {
+        let mut tracker = PositionTracker::new();
+        tracker.record_entry("TEST-TICKER".to_string(), "no", 10, 40, 400, 45, Instant::now(), true);
+        let pos = tracker.get("TEST-TICKER").unwrap();
+        assert_eq!(pos.order_leg(), ("TEST-TICKER", "no"));
+
+        tracker.set_venue_ticker("TEST-TICKER", "111".to_string());
+        tracker.record_entry("TEST-TICKER".to_string(), "no", 5, 40, 200, 45, Instant::now(), true);
+        let pos = tracker.get("TEST-TICKER").unwrap();
+        assert_eq!(pos.order_leg(), ("111", "yes"));
+        assert_eq!(pos.quantity, 15);
+        let closed = tracker.record_partial_exit("TEST-TICKER", 5).unwrap();
+        assert!(closed.venue_ticker.is_some());
+    }
+
     #[test]
     fn test_exit_nonexistent_returns_none() {
         let mut tracker = PositionTracker::new();
@@ -130,14 +246,59 @@ mod tests {
     #[test]
     fn test_multiple_positions() {
         let mut tracker = PositionTracker::new();
-        tracker.record_entry("TICKER-1".to_string(), 5, 40, 210, 45, Instant::now(), false);
-        tracker.record_entry("TICKER-2".to_string(), 8, 60, 490, 65, Instant::now(), true);
+        tracker.record_entry("TICKER-1".to_string(), "yes", 5, 40, 210, 45, Instant::now(), false);
+        tracker.record_entry("TICKER-2".to_string(), "yes", 8, 60, 490, 65, Instant::now(), true);
 
         assert_eq!(tracker.count(), 2);
-        assert!(tracker.has_position("TICKER-1"));
-        assert!(tracker.has_position("TICKER-2"));
+        assert!(tracker.get("TICKER-1").is_some());
+        assert!(tracker.get("TICKER-2").is_some());
 
         let all = tracker.all_positions();
         assert_eq!(all.len(), 2);
     }
+
+    #[test]
+    fn test_entry_in_held_ticker_tops_up() {
+        let mut tracker = PositionTracker::new();
+        let first = Instant::now();
+        tracker.record_entry("TEST-TICKER".to_string(), "yes", 4, 50, 204, 55, first, true);
+        let later = first + std::time::Duration::from_secs(10);
+        tracker.record_entry("TEST-TICKER".to_string(), "yes", 2, 56, 114, 59, later, false);
+
+        assert_eq!(tracker.count(), 1);
+        let pos = tracker.get("TEST-TICKER").unwrap();
+        assert_eq!(pos.quantity, 6);
+        assert_eq!(pos.entry_price, 52);
+        assert_eq!(pos.entry_cost_cents, 318);
+        assert_eq!(pos.sell_target, 59);
+        assert_eq!(pos.filled_at, first);
+    }
+
+    #[test]
+    fn test_other_side_of_held_ticker_is_never_merged() {
+        let mut tracker = PositionTracker::new();
+        let now = Instant::now();
+        tracker.record_entry("TEST-TICKER".to_string(), "yes", 4, 50, 204, 55, now, true);
+        assert!(tracker.can_enter("TEST-TICKER", "yes", 6, 10));
+        assert!(!tracker.can_enter("TEST-TICKER", "yes", 7, 10));
+        assert!(!tracker.can_enter("TEST-TICKER", "no", 1, 10));
+        assert!(tracker.can_enter("OTHER", "no", 10, 10));
+
+        tracker.record_entry("TEST-TICKER".to_string(), "no", 2, 48, 98, 52, now, true);
+        let pos = tracker.get("TEST-TICKER").unwrap();
+        assert_eq!((pos.side.as_str(), pos.quantity, pos.entry_price), ("yes", 4, 50));
+        assert_eq!(pos.entry_cost_cents, 204);
+    }
+
+    #[test]
+    fn test_mark_filled_resets_hold_clock() {
+        let mut tracker = PositionTracker::new();
+        let submitted = Instant::now();
+        tracker.record_entry("TEST-TICKER".to_string(), "yes", 10, 50, 520, 55, submitted, false);
+
+        let filled = submitted + std::time::Duration::from_secs(30);
+        tracker.mark_filled("TEST-TICKER", filled);
+        tracker.mark_filled("NONEXISTENT", filled);
+        assert_eq!(tracker.get("TEST-TICKER").unwrap().filled_at, filled);
+    }
 }
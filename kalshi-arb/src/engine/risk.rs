@@ -3,7 +3,7 @@ use std::collections::HashMap;
 
 pub struct RiskManager {
     config: RiskConfig,
-    positions: HashMap<String, u32>, // ticker -> contract count
+    positions: HashMap<String, (String, u32)>, // ticker -> (side, contract count)
 }
 
 impl RiskManager {
@@ -14,12 +14,33 @@ impl RiskManager {
         }
     }
 
-    /// Check if we can open a new position.
-    pub fn can_trade(&self, ticker: &str, quantity: u32, cost_cents: u32) -> bool {
-        let current = self.positions.get(ticker).copied().unwrap_or(0);
-        if current + quantity > self.config.max_contracts_per_market {
+    /// Check if we can open a new position on `side` ("yes"/"no").
+    /// `position_cap` is a per-ticker limit (e.g. from market liquidity)
+    /// applied below `max_contracts_per_market`.
+    pub fn can_trade(
+        &self,
+        ticker: &str,
+        side: &str,
+        quantity: u32,
+        cost_cents: u32,
+        position_cap: Option<u32>,
+    ) -> bool {
+        let current = self.position_count(ticker);
+        let market_cap = position_cap.map_or(self.config.max_contracts_per_market, |cap| {
+            cap.min(self.config.max_contracts_per_market)
+        });
+        if current + quantity > market_cap {
             return false;
         }
+        if let Some(entity) = exposure_entity(ticker, side) {
+            let entity_cap = self
+                .config
+                .max_contracts_per_entity
+                .unwrap_or(self.config.max_contracts_per_market);
+            if self.entity_count(&entity) + quantity > entity_cap {
+                return false;
+            }
+        }
         if self.positions.len() as u32 >= self.config.max_concurrent_markets
             && !self.positions.contains_key(ticker)
         {
@@ -28,7 +49,7 @@ impl RiskManager {
         let total_exposure: u64 = self
             .positions
             .values()
-            .map(|&q| q as u64 * 100)
+            .map(|&(_, q)| q as u64 * 100)
             .sum::<u64>()
             + cost_cents as u64;
         if total_exposure > self.config.max_total_exposure_cents {
@@ -37,13 +58,16 @@ impl RiskManager {
         true
     }
 
-    pub fn record_buy(&mut self, ticker: &str, quantity: u32) {
-        *self.positions.entry(ticker.to_string()).or_insert(0) += quantity;
+    pub fn record_buy(&mut self, ticker: &str, side: &str, quantity: u32) {
+        self.positions
+            .entry(ticker.to_string())
+            .or_insert_with(|| (side.to_string(), 0))
+            .1 += quantity;
     }
 
     #[allow(dead_code)]
     pub fn record_sell(&mut self, ticker: &str, quantity: u32) {
-        if let Some(pos) = self.positions.get_mut(ticker) {
+        if let Some((_, pos)) = self.positions.get_mut(ticker) {
             *pos = pos.saturating_sub(quantity);
             if *pos == 0 {
                 self.positions.remove(ticker);
@@ -51,13 +75,394 @@ impl RiskManager {
         }
     }
 
-    #[allow(dead_code)]
     pub fn position_count(&self, ticker: &str) -> u32 {
-        self.positions.get(ticker).copied().unwrap_or(0)
+        self.positions.get(ticker).map_or(0, |&(_, q)| q)
     }
 
     #[allow(dead_code)]
     pub fn total_markets(&self) -> usize {
         self.positions.len()
     }
+
+    /// Contracts held across every market on `entity` (see [`exposure_entity`]).
+    pub fn entity_count(&self, entity: &str) -> u32 {
+        self.positions
+            .iter()
+            .filter(|(t, (side, _))| exposure_entity(t, side).as_deref() == Some(entity))
+            .map(|(_, &(_, q))| q)
+            .sum()
+    }
+}
+
+/// Which exposure cap an entry would breach.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExposureCap {
+    Market,
+    Game,
+    Sport,
+    Total,
+}
+
+impl ExposureCap {
+    pub fn label(self) -> &'static str {
+        match self {
+            ExposureCap::Market => "per-market",
+            ExposureCap::Game => "per-game",
+            ExposureCap::Sport => "per-sport",
+            ExposureCap::Total => "total",
+        }
+    }
+}
+
+/// What is held in one market.
+#[derive(Debug, Clone, Default)]
+struct Holding {
+    sport: String,
+    side: String,
+    cost_cents: u64,
+    contracts: u32,
+}
+
+/// Cost and contracts held in each market, tagged with its sport. Checked
+/// against the `[risk]` exposure caps before an entry is emitted, and read
+/// by sizing so held inventory counts toward the Kelly target.
+#[derive(Debug, Clone, Default)]
+pub struct Exposure {
+    markets: HashMap<String, Holding>,
+}
+
+impl Exposure {
+    pub fn add(&mut self, ticker: &str, sport: &str, side: &str, cost_cents: u64, contracts: u32) {
+        let entry = self
+            .markets
+            .entry(ticker.to_string())
+            .or_insert_with(|| Holding {
+                sport: sport.to_string(),
+                side: side.to_string(),
+                ..Holding::default()
+            });
+        entry.cost_cents += cost_cents;
+        entry.contracts += contracts;
+    }
+
+    pub fn market_cents(&self, ticker: &str) -> u64 {
+        self.markets.get(ticker).map_or(0, |h| h.cost_cents)
+    }
+
+    /// Side ("yes"/"no") and contracts held in `ticker`.
+    pub fn held(&self, ticker: &str) -> Option<(&str, u32)> {
+        self.markets
+            .get(ticker)
+            .map(|h| (h.side.as_str(), h.contracts))
+    }
+
+    /// Cost held across every side market of `ticker`'s game.
+    pub fn game_cents(&self, ticker: &str) -> u64 {
+        let game = game_event(ticker);
+        self.markets
+            .iter()
+            .filter(|(t, _)| game_event(t) == game)
+            .map(|(_, h)| h.cost_cents)
+            .sum()
+    }
+
+    pub fn sport_cents(&self, sport: &str) -> u64 {
+        self.markets
+            .values()
+            .filter(|h| h.sport == sport)
+            .map(|h| h.cost_cents)
+            .sum()
+    }
+
+    pub fn total_cents(&self) -> u64 {
+        self.markets.values().map(|h| h.cost_cents).sum()
+    }
+
+    /// The first cap that adding `cost_cents` on `ticker` would breach.
+    pub fn check(
+        &self,
+        ticker: &str,
+        sport: &str,
+        cost_cents: u64,
+        config: &RiskConfig,
+    ) -> Option<ExposureCap> {
+        let over = |held: u64, cap: Option<u64>| cap.is_some_and(|cap| held + cost_cents > cap);
+        if over(
+            self.market_cents(ticker),
+            config.max_exposure_per_market_cents,
+        ) {
+            Some(ExposureCap::Market)
+        } else if over(self.game_cents(ticker), config.max_exposure_per_game_cents) {
+            Some(ExposureCap::Game)
+        } else if over(self.sport_cents(sport), config.max_exposure_per_sport_cents) {
+            Some(ExposureCap::Sport)
+        } else if over(self.total_cents(), Some(config.max_total_exposure_cents)) {
+            Some(ExposureCap::Total)
+        } else {
+            None
+        }
+    }
+}
+
+/// Kalshi event a side market belongs to: the ticker without its outcome
+/// suffix. The home, away and draw markets of one `IndexedGame` share it,
+/// e.g. `KXEPLGAME-26FEB01ARSCHE-ARS` and `-TIE` both map to
+/// `KXEPLGAME-26FEB01ARSCHE`.
+pub fn game_event(ticker: &str) -> &str {
+    ticker.rsplit_once('-').map_or(ticker, |(event, _)| event)
+}
+
+/// Balance available to sizing after holding back `reserve_cents`.
+pub fn deployable_cents(balance_cents: i64, reserve_cents: u64) -> u64 {
+    (balance_cents.max(0) as u64).saturating_sub(reserve_cents)
+}
+
+/// Market-type suffixes on a series ticker, stripped to get the league.
+const MARKET_TYPE_SUFFIXES: &[&str] = &["GAME", "SPREAD", "SERIES", "FIGHT", "WINNER"];
+
+/// Market types with one market per side of a matchup, where NO on one
+/// side backs the other.
+const HEAD_TO_HEAD_SUFFIXES: &[&str] = &["GAME", "SERIES", "FIGHT"];
+
+/// Team entity a position on `side` of a ticker is correlated with, e.g.
+/// YES on `KXNBAGAME-26FEB01LALBOS-LAL`, `KXNBASPREAD-26FEB01LALBOS-LAL4`
+/// and `KXNBASERIES-26LALBOS-LAL` all map to `KXNBA:LAL`, and so does NO on
+/// the opponent's `KXNBAGAME-26FEB01LALBOS-BOS`. NO on a team's spread bets
+/// against the team and maps to `KXNBA:LAL:no`, so it neither adds to the
+/// YES cap nor is blocked by it. Returns `None` for outcomes with no team
+/// (totals, ties) or unrecognized tickers.
+pub fn exposure_entity(ticker: &str, side: &str) -> Option<String> {
+    let mut parts = ticker.split('-');
+    let series = parts.next()?;
+    let event = parts.next()?;
+    let outcome = parts.next()?;
+    let team: String = outcome
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect();
+    if team.is_empty() || team == "TIE" {
+        return None;
+    }
+    let league = MARKET_TYPE_SUFFIXES
+        .iter()
+        .find_map(|suffix| series.strip_suffix(suffix))
+        .unwrap_or(series);
+    if side != "no" {
+        return Some(format!("{}:{}", league, team));
+    }
+    // In a game with a draw this also counts the draw against the
+    // opponent, erring towards the tighter cap
+    let head_to_head = HEAD_TO_HEAD_SUFFIXES.iter().any(|s| series.ends_with(s));
+    match opponent(event, &team).filter(|_| head_to_head) {
+        Some(opponent) => Some(format!("{}:{}", league, opponent)),
+        None => Some(format!("{}:{}:no", league, team)),
+    }
+}
+
+/// The other team in an event ticker's matchup (`26FEB01LALBOS` or
+/// `26LALBOS`), given one of its teams.
+fn opponent<'a>(event: &'a str, team: &str) -> Option<&'a str> {
+    let rest = event.trim_start_matches(|c: char| c.is_ascii_digit());
+    // A dated event: three-letter month, then the day
+    let dated = rest
+        .get(3..)
+        .filter(|r| r.starts_with(|c: char| c.is_ascii_digit()));
+    let matchup = match dated {
+        Some(r) => r.trim_start_matches(|c: char| c.is_ascii_digit()),
+        None => rest,
+    };
+    let other = matchup
+        .strip_prefix(team)
+        .or_else(|| matchup.strip_suffix(team))?;
+    (!other.is_empty() && other.chars().all(|c| c.is_ascii_alphabetic())).then_some(other)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(per_market: u32, per_entity: Option<u32>) -> RiskConfig {
+        RiskConfig {
+            max_contracts_per_market: per_market,
+            max_contracts_per_entity: per_entity,
+            max_total_exposure_cents: 100_000,
+            max_concurrent_markets: 10,
+            ..RiskConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_exposure_entity_groups_market_types() {
+        let win = exposure_entity("KXNBAGAME-26FEB01LALBOS-LAL", "yes");
+        assert_eq!(win.as_deref(), Some("KXNBA:LAL"));
+        assert_eq!(
+            exposure_entity("KXNBASPREAD-26FEB01LALBOS-LAL4", "yes"),
+            win
+        );
+        assert_eq!(exposure_entity("KXNBASERIES-26LALBOS-LAL", "yes"), win);
+        assert_eq!(
+            exposure_entity("KXNHLGAME-26FEB01LALBOS-LAL", "yes").as_deref(),
+            Some("KXNHL:LAL")
+        );
+        assert_eq!(exposure_entity("KXNBATOTAL-26FEB01LALBOS-225", "yes"), None);
+        assert_eq!(exposure_entity("KXEPLGAME-26FEB01ARSCHE-TIE", "yes"), None);
+        assert_eq!(exposure_entity("TEST-1", "yes"), None);
+    }
+
+    #[test]
+    fn test_combined_cap_across_team_markets() {
+        let mut rm = RiskManager::new(config(5, Some(6)));
+        rm.record_buy("KXNBAGAME-26FEB01LALBOS-LAL", "yes", 4);
+        assert_eq!(rm.entity_count("KXNBA:LAL"), 4);
+        // Per-market room on the spread, but the team cap is 6.
+        assert!(rm.can_trade("KXNBASPREAD-26FEB01LALBOS-LAL4", "yes", 2, 100, None));
+        assert!(!rm.can_trade("KXNBASPREAD-26FEB01LALBOS-LAL4", "yes", 3, 100, None));
+        // The opponent is a separate entity.
+        assert!(rm.can_trade("KXNBAGAME-26FEB01LALBOS-BOS", "yes", 5, 100, None));
+    }
+
+    #[test]
+    fn test_no_side_is_separate_entity() {
+        assert_eq!(
+            exposure_entity("KXNBASPREAD-26FEB01LALBOS-LAL4", "no").as_deref(),
+            Some("KXNBA:LAL:no")
+        );
+        let mut rm = RiskManager::new(config(5, Some(6)));
+        rm.record_buy("KXNBAGAME-26FEB01LALBOS-LAL", "yes", 6);
+        assert!(!rm.can_trade("KXNBASPREAD-26FEB01LALBOS-LAL4", "yes", 1, 100, None));
+        // A NO hedge on the spread doesn't count as more LAL exposure
+        assert!(rm.can_trade("KXNBASPREAD-26FEB01LALBOS-LAL4", "no", 5, 100, None));
+        rm.record_buy("KXNBASPREAD-26FEB01LALBOS-LAL4", "no", 5);
+        assert_eq!(rm.entity_count("KXNBA:LAL"), 6);
+        assert_eq!(rm.entity_count("KXNBA:LAL:no"), 5);
+    }
+
+    #[test]
+    fn test_no_on_opponent_counts_as_team() {
+        assert_eq!(
+            exposure_entity("KXNBAGAME-26FEB01LALBOS-BOS", "no").as_deref(),
+            Some("KXNBA:LAL")
+        );
+        assert_eq!(
+            exposure_entity("KXNBASERIES-26LALBOS-LAL", "no").as_deref(),
+            Some("KXNBA:BOS")
+        );
+        let mut rm = RiskManager::new(config(5, Some(6)));
+        rm.record_buy("KXNBAGAME-26FEB01LALBOS-LAL", "yes", 4);
+        // NO on BOS is another bet on LAL, under the same team cap
+        assert!(!rm.can_trade("KXNBAGAME-26FEB01LALBOS-BOS", "no", 3, 100, None));
+        assert!(rm.can_trade("KXNBAGAME-26FEB01LALBOS-BOS", "no", 2, 100, None));
+        rm.record_buy("KXNBAGAME-26FEB01LALBOS-BOS", "no", 2);
+        assert_eq!(rm.entity_count("KXNBA:LAL"), 6);
+        assert_eq!(rm.entity_count("KXNBA:BOS:no"), 0);
+    }
+
+    #[test]
+    fn test_exposure_caps_per_market_sport_and_total() {
+        let cfg = RiskConfig {
+            max_exposure_per_market_cents: Some(500),
+            max_exposure_per_sport_cents: Some(800),
+            max_total_exposure_cents: 1_000,
+            ..config(10, None)
+        };
+        let mut exposure = Exposure::default();
+        exposure.add("KXNBAGAME-26FEB01LALBOS-LAL", "basketball", "yes", 400, 8);
+        exposure.add("KXNBAGAME-26FEB01DENNYK-NYK", "basketball", "no", 300, 1);
+        exposure.add("KXNHLGAME-26FEB01TORBOS-TOR", "ice-hockey", "yes", 200, 1);
+        assert_eq!(exposure.sport_cents("basketball"), 700);
+        assert_eq!(exposure.total_cents(), 900);
+        assert_eq!(
+            exposure.held("KXNBAGAME-26FEB01LALBOS-LAL"),
+            Some(("yes", 8))
+        );
+        assert_eq!(
+            exposure.held("KXNBAGAME-26FEB01DENNYK-NYK"),
+            Some(("no", 1))
+        );
+
+        let check = |ticker, sport, cost| exposure.check(ticker, sport, cost, &cfg);
+        assert_eq!(
+            check("KXNBAGAME-26FEB01LALBOS-LAL", "basketball", 101),
+            Some(ExposureCap::Market)
+        );
+        assert_eq!(
+            check("KXNBAGAME-26FEB01PHXMIA-MIA", "basketball", 101),
+            Some(ExposureCap::Sport)
+        );
+        assert_eq!(
+            check("KXNHLGAME-26FEB01NYRPIT-PIT", "ice-hockey", 101),
+            Some(ExposureCap::Total)
+        );
+        assert_eq!(
+            check("KXNHLGAME-26FEB01NYRPIT-PIT", "ice-hockey", 100),
+            None
+        );
+        // Without per-market or per-sport caps only the total applies
+        let total_only = config(10, None);
+        assert_eq!(
+            exposure.check(
+                "KXNBAGAME-26FEB01LALBOS-LAL",
+                "basketball",
+                100,
+                &total_only
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_game_cap_spans_side_markets() {
+        let cfg = RiskConfig {
+            max_exposure_per_game_cents: Some(600),
+            ..config(10, None)
+        };
+        let mut exposure = Exposure::default();
+        exposure.add("KXEPLGAME-26FEB01ARSCHE-ARS", "soccer-epl", "yes", 400, 1);
+        assert_eq!(
+            game_event("KXEPLGAME-26FEB01ARSCHE-TIE"),
+            "KXEPLGAME-26FEB01ARSCHE"
+        );
+        assert_eq!(exposure.game_cents("KXEPLGAME-26FEB01ARSCHE-CHE"), 400);
+
+        // The draw and the opponent share the game's budget
+        for side in ["TIE", "CHE"] {
+            let ticker = format!("KXEPLGAME-26FEB01ARSCHE-{}", side);
+            assert_eq!(
+                exposure.check(&ticker, "soccer-epl", 201, &cfg),
+                Some(ExposureCap::Game)
+            );
+            assert_eq!(exposure.check(&ticker, "soccer-epl", 200, &cfg), None);
+        }
+        // Another game has its own
+        assert_eq!(
+            exposure.check("KXEPLGAME-26FEB01LIVMCI-LIV", "soccer-epl", 600, &cfg),
+            None
+        );
+    }
+
+    #[test]
+    fn test_deployable_holds_back_reserve() {
+        assert_eq!(deployable_cents(10_000, 2_500), 7_500);
+        assert_eq!(deployable_cents(2_000, 2_500), 0);
+        assert_eq!(deployable_cents(-500, 0), 0);
+    }
+
+    #[test]
+    fn test_entity_cap_defaults_to_market_cap() {
+        let mut rm = RiskManager::new(config(5, None));
+        rm.record_buy("KXNBAGAME-26FEB01LALBOS-LAL", "yes", 5);
+        assert!(!rm.can_trade("KXNBASERIES-26LALBOS-LAL", "yes", 1, 100, None));
+        rm.record_sell("KXNBAGAME-26FEB01LALBOS-LAL", 5);
+        assert!(rm.can_trade("KXNBASERIES-26LALBOS-LAL", "yes", 1, 100, None));
+    }
+
+    #[test]
+    fn test_position_cap_counts_held_contracts() {
+        let mut rm = RiskManager::new(config(10, None));
+        rm.record_buy("KXNBAGAME-26FEB01LALBOS-LAL", "yes", 2);
+        assert!(rm.can_trade("KXNBAGAME-26FEB01LALBOS-LAL", "yes", 1, 100, Some(3)));
+        assert!(!rm.can_trade("KXNBAGAME-26FEB01LALBOS-LAL", "yes", 2, 100, Some(3)));
+        // A cap above the static limit doesn't raise it
+        assert!(!rm.can_trade("KXNBAGAME-26FEB01LALBOS-BOS", "yes", 11, 100, Some(50)));
+    }
 }
@@ -0,0 +1,140 @@
+//! Season calendar: per-league regular/post-season windows and mid-season
+//! breaks, used to skip polling sports with no games on the schedule.
+//!
+//! Dates are recurring month/day pairs, so a window whose start is later in
+//! the year than its end (e.g. NBA Oct–Jun) wraps across New Year.
+
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate};
+
+/// A recurring (month, day) date, independent of year.
+pub type MonthDay = (u32, u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeasonStatus {
+    InSeason,
+    /// Within the season but inside a scheduled break (e.g. All-Star).
+    Break,
+    OffSeason,
+}
+
+impl SeasonStatus {
+    /// Whether the sport is expected to have games on this date.
+    pub fn is_active(self) -> bool {
+        self == SeasonStatus::InSeason
+    }
+}
+
+/// Start/end of a season plus breaks inside it, all inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeasonWindow {
+    pub start: MonthDay,
+    pub end: MonthDay,
+    pub breaks: Vec<(MonthDay, MonthDay)>,
+}
+
+impl SeasonWindow {
+    pub fn new(start: MonthDay, end: MonthDay) -> Self {
+        Self {
+            start,
+            end,
+            breaks: Vec::new(),
+        }
+    }
+
+    pub fn with_break(mut self, start: MonthDay, end: MonthDay) -> Self {
+        self.breaks.push((start, end));
+        self
+    }
+
+    pub fn status(&self, date: NaiveDate) -> SeasonStatus {
+        let md = (date.month(), date.day());
+        if !in_range(md, self.start, self.end) {
+            SeasonStatus::OffSeason
+        } else if self.breaks.iter().any(|&(s, e)| in_range(md, s, e)) {
+            SeasonStatus::Break
+        } else {
+            SeasonStatus::InSeason
+        }
+    }
+}
+
+/// Inclusive range check that wraps across New Year when `start > end`.
+fn in_range(md: MonthDay, start: MonthDay, end: MonthDay) -> bool {
+    if start <= end {
+        start <= md && md <= end
+    } else {
+        md >= start || md <= end
+    }
+}
+
+/// Parse a "MM-DD" date.
+pub fn parse_month_day(s: &str) -> Result<MonthDay> {
+    let (m, d) = s
+        .trim()
+        .split_once('-')
+        .with_context(|| format!("expected MM-DD, got {:?}", s))?;
+    let month: u32 = m.parse().with_context(|| format!("bad month in {:?}", s))?;
+    let day: u32 = d.parse().with_context(|| format!("bad day in {:?}", s))?;
+    // 2024 is a leap year, so 02-29 is accepted.
+    NaiveDate::from_ymd_opt(2024, month, day).with_context(|| format!("invalid date {:?}", s))?;
+    Ok((month, day))
+}
+
+/// Built-in calendar for a sport key. `None` means the sport runs year-round.
+/// Windows are deliberately a little wider than the real schedule so
+/// preseason/finals edges are never cut off.
+pub fn default_season(sport: &str) -> Option<SeasonWindow> {
+    let window = match sport {
+        "basketball" => SeasonWindow::new((10, 1), (6, 25)).with_break((2, 13), (2, 18)),
+        "college-basketball" | "college-basketball-womens" => SeasonWindow::new((11, 1), (4, 10)),
+        "american-football" => SeasonWindow::new((8, 1), (2, 15)),
+        "college-football" => SeasonWindow::new((8, 20), (1, 25)),
+        "baseball" => SeasonWindow::new((2, 20), (11, 5)).with_break((7, 13), (7, 16)),
+        "ice-hockey" => SeasonWindow::new((9, 20), (6, 25)),
+        "soccer-epl" => SeasonWindow::new((8, 10), (5, 31)),
+        _ => return None,
+    };
+    Some(window)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_wrapping_window() {
+        let nba = default_season("basketball").unwrap();
+        assert_eq!(nba.status(date(2026, 12, 25)), SeasonStatus::InSeason);
+        assert_eq!(nba.status(date(2027, 3, 1)), SeasonStatus::InSeason);
+        assert_eq!(nba.status(date(2027, 2, 15)), SeasonStatus::Break);
+        assert_eq!(nba.status(date(2026, 8, 1)), SeasonStatus::OffSeason);
+        assert!(!SeasonStatus::Break.is_active());
+    }
+
+    #[test]
+    fn test_non_wrapping_window() {
+        let mlb = default_season("baseball").unwrap();
+        assert_eq!(mlb.status(date(2026, 6, 1)), SeasonStatus::InSeason);
+        assert_eq!(mlb.status(date(2026, 7, 14)), SeasonStatus::Break);
+        assert_eq!(mlb.status(date(2026, 12, 1)), SeasonStatus::OffSeason);
+        assert_eq!(mlb.status(date(2026, 11, 5)), SeasonStatus::InSeason);
+    }
+
+    #[test]
+    fn test_year_round_sport_has_no_calendar() {
+        assert!(default_season("mma").is_none());
+    }
+
+    #[test]
+    fn test_parse_month_day() {
+        assert_eq!(parse_month_day("10-01").unwrap(), (10, 1));
+        assert_eq!(parse_month_day("2-29").unwrap(), (2, 29));
+        assert!(parse_month_day("13-01").is_err());
+        assert!(parse_month_day("0615").is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use super::fees::calculate_fee;
+use serde::Serialize;
 
 /// Result of strategy evaluation for a single market.
 #[derive(Debug, Clone)]
@@ -9,6 +10,85 @@ pub struct StrategySignal {
     pub edge: i32,
     pub net_profit_estimate: i32,
     pub quantity: u32,
+    /// Why the signal is a skip (`None` for buys).
+    pub skip_reason: Option<SkipReason>,
+}
+
+/// Why a market was not traded, from strategy evaluation through risk gates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// Fair-value input is too old to trade on.
+    Stale,
+    /// Fair value violated the win-probability sanity bounds.
+    FvOutOfBounds,
+    /// Edge was present but momentum was below the maker threshold.
+    MomentumGated,
+    /// Edge (after slippage and fees) below the configured thresholds.
+    BelowThreshold,
+    /// No ask to buy against.
+    NoLiquidity,
+    /// Edge above `max_edge_threshold`, likely a bad match or stale book.
+    MaxEdge,
+    /// Blocked by risk limits or available balance.
+    RiskCap,
+    /// Market closed between signal generation and order submission.
+    ClosedBeforeSubmit,
+    /// No exit price can recover the entry cost plus fees.
+    BreakEvenInfeasible,
+    /// Entry not allowed in the current feed degradation tier.
+    FeedDegraded,
+    /// Contracts already held meet the Kelly target.
+    InventoryHeld,
+    /// Entry not allowed in the current drawdown tier.
+    Drawdown,
+    /// Ask-side depth too thin for a taker fill to keep its edge.
+    ThinBook,
+    /// Breaking news about either team; fair value can't price it yet.
+    NewsHalt,
+    /// Game or market blacklisted, or not whitelisted in whitelist-only mode.
+    Blocked,
+}
+
+impl SkipReason {
+    /// Short label for the markets table action column (at most 8 chars).
+    pub fn label(self) -> &'static str {
+        match self {
+            SkipReason::Stale => "STALE",
+            SkipReason::FvOutOfBounds => "FV_BOUND",
+            SkipReason::MomentumGated => "MOMENTUM",
+            SkipReason::BelowThreshold => "NO_EDGE",
+            SkipReason::NoLiquidity => "NO_LIQ",
+            SkipReason::MaxEdge => "MAX_EDGE",
+            SkipReason::RiskCap => "RISK_CAP",
+            SkipReason::ClosedBeforeSubmit => "CLOSED",
+            SkipReason::BreakEvenInfeasible => "NO_EXIT",
+            SkipReason::FeedDegraded => "DEGRADED",
+            SkipReason::InventoryHeld => "HELD",
+            SkipReason::Drawdown => "DRAWDOWN",
+            SkipReason::ThinBook => "THIN",
+            SkipReason::NewsHalt => "NEWS",
+            SkipReason::Blocked => "BLOCKED",
+        }
+    }
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// Skip signal for a market that is not worth quoting at all.
+fn skip(edge: i32, reason: SkipReason) -> StrategySignal {
+    StrategySignal {
+        action: TradeAction::Skip,
+        price: 0,
+        edge,
+        net_profit_estimate: 0,
+        quantity: 0,
+        skip_reason: Some(reason),
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -23,7 +103,7 @@ pub enum TradeAction {
 /// `fair_value`: vig-free probability * 100 (cents)
 /// `best_bid`: best bid on Kalshi orderbook (cents)
 /// `best_ask`: best ask on Kalshi orderbook (cents)
-#[allow(clippy::too_many_arguments)]
+#[allow(clippy::too_many_arguments, dead_code)]
 pub fn evaluate(
     fair_value: u32,
     best_bid: u32,
@@ -35,26 +115,17 @@ pub fn evaluate(
     kelly_fraction: f64,
     max_contracts: u32,
 ) -> StrategySignal {
-    if best_ask == 0 || fair_value == 0 {
-        return StrategySignal {
-            action: TradeAction::Skip,
-            price: 0,
-            edge: 0,
-            net_profit_estimate: 0,
-            quantity: 0,
-        };
+    if best_ask == 0 {
+        return skip(0, SkipReason::NoLiquidity);
+    }
+    if fair_value == 0 {
+        return skip(0, SkipReason::BelowThreshold);
     }
 
     let edge = fair_value as i32 - best_ask as i32;
 
     if edge < maker_threshold as i32 {
-        return StrategySignal {
-            action: TradeAction::Skip,
-            price: 0,
-            edge,
-            net_profit_estimate: 0,
-            quantity: 0,
-        };
+        return skip(edge, SkipReason::BelowThreshold);
     }
 
     // Kelly-size for taker path
@@ -88,6 +159,7 @@ pub fn evaluate(
             edge,
             net_profit_estimate: taker_profit,
             quantity: taker_qty,
+            skip_reason: None,
         }
     } else if edge >= maker_threshold as i32 && maker_profit >= min_edge_after_fees as i32 {
         StrategySignal {
@@ -98,20 +170,16 @@ pub fn evaluate(
             edge,
             net_profit_estimate: maker_profit,
             quantity: maker_qty,
+            skip_reason: None,
         }
     } else {
-        StrategySignal {
-            action: TradeAction::Skip,
-            price: 0,
-            edge,
-            net_profit_estimate: 0,
-            quantity: 0,
-        }
+        skip(edge, SkipReason::BelowThreshold)
     }
 }
 
 /// Evaluate with slippage buffer applied to edge calculation.
 /// slippage_buffer_cents is subtracted from the raw edge before threshold comparison.
+/// held_contracts already in the ticker count toward the Kelly-sized position.
 #[allow(clippy::too_many_arguments)]
 pub fn evaluate_with_slippage(
     fair_value: u32,
@@ -123,35 +191,30 @@ pub fn evaluate_with_slippage(
     bankroll_cents: u64,
     kelly_fraction: f64,
     max_contracts: u32,
+    held_contracts: u32,
     slippage_buffer_cents: u8,
 ) -> StrategySignal {
-    if best_ask == 0 || fair_value == 0 {
-        return StrategySignal {
-            action: TradeAction::Skip,
-            price: 0,
-            edge: 0,
-            net_profit_estimate: 0,
-            quantity: 0,
-        };
+    if best_ask == 0 {
+        return skip(0, SkipReason::NoLiquidity);
+    }
+    if fair_value == 0 {
+        return skip(0, SkipReason::BelowThreshold);
     }
 
     let raw_edge = fair_value as i32 - best_ask as i32;
     let effective_edge = raw_edge - slippage_buffer_cents as i32;
 
     if effective_edge < maker_threshold as i32 {
-        return StrategySignal {
-            action: TradeAction::Skip,
-            price: 0,
-            edge: raw_edge,  // Report raw edge for display
-            net_profit_estimate: 0,
-            quantity: 0,
-        };
+        // Report raw edge for display
+        return skip(raw_edge, SkipReason::BelowThreshold);
     }
 
     // Kelly-size for taker path (using actual price, not buffered)
+    // Kelly gives the position to hold; contracts already held count toward
+    // it, so repeated signals only top up to the target
     let taker_qty = {
         let raw = super::kelly::kelly_size(fair_value, best_ask, bankroll_cents, kelly_fraction);
-        raw.min(max_contracts)
+        raw.min(max_contracts).saturating_sub(held_contracts)
     };
     let entry_fee_taker = calculate_fee(best_ask, taker_qty, true) as i32;
     let exit_fee_maker_t = calculate_fee(fair_value, taker_qty, false) as i32;
@@ -165,7 +228,7 @@ pub fn evaluate_with_slippage(
     let maker_qty = {
         let raw =
             super::kelly::kelly_size(fair_value, maker_buy_price, bankroll_cents, kelly_fraction);
-        raw.min(max_contracts)
+        raw.min(max_contracts).saturating_sub(held_contracts)
     };
     let entry_fee_maker = calculate_fee(maker_buy_price, maker_qty, false) as i32;
     let exit_fee_maker_m = calculate_fee(fair_value, maker_qty, false) as i32;
@@ -173,15 +236,26 @@ pub fn evaluate_with_slippage(
         - entry_fee_maker
         - exit_fee_maker_m; // Maker has less slippage risk
 
-    if effective_edge >= taker_threshold as i32 && taker_profit >= min_edge_after_fees as i32 {
+    if taker_qty == 0 && maker_qty == 0 {
+        return skip(raw_edge, SkipReason::InventoryHeld);
+    }
+
+    if effective_edge >= taker_threshold as i32
+        && taker_qty > 0
+        && taker_profit >= min_edge_after_fees as i32
+    {
         StrategySignal {
             action: TradeAction::TakerBuy,
             price: best_ask,
             edge: raw_edge,
             net_profit_estimate: taker_profit,
             quantity: taker_qty,
+            skip_reason: None,
         }
-    } else if effective_edge >= maker_threshold as i32 && maker_profit >= min_edge_after_fees as i32 {
+    } else if effective_edge >= maker_threshold as i32
+        && maker_qty > 0
+        && maker_profit >= min_edge_after_fees as i32
+    {
         StrategySignal {
             action: TradeAction::MakerBuy {
                 bid_price: maker_buy_price,
@@ -190,15 +264,10 @@ pub fn evaluate_with_slippage(
             edge: raw_edge,
             net_profit_estimate: maker_profit,
             quantity: maker_qty,
+            skip_reason: None,
         }
     } else {
-        StrategySignal {
-            action: TradeAction::Skip,
-            price: 0,
-            edge: raw_edge,
-            net_profit_estimate: 0,
-            quantity: 0,
-        }
+        skip(raw_edge, SkipReason::BelowThreshold)
     }
 }
 
@@ -210,6 +279,9 @@ pub struct DualSideSignal {
 }
 
 /// Evaluate both YES and NO sides, return the better opportunity.
+/// `held` is the side and contracts already held in the ticker: they count
+/// toward that side's Kelly-sized position, and the other side of a held
+/// ticker is never entered.
 #[allow(clippy::too_many_arguments)]
 pub fn evaluate_best_side(
     fair_value: u32,
@@ -223,8 +295,15 @@ pub fn evaluate_best_side(
     bankroll_cents: u64,
     kelly_fraction: f64,
     max_contracts: u32,
+    held: Option<(&str, u32)>,
     slippage_buffer_cents: u8,
 ) -> DualSideSignal {
+    let held_on = |side: &str| match held {
+        Some((held_side, contracts)) if held_side == side => contracts,
+        Some(_) => u32::MAX,
+        None => 0,
+    };
+
     // Evaluate YES side
     let yes_signal = evaluate_with_slippage(
         fair_value,
@@ -236,6 +315,7 @@ pub fn evaluate_best_side(
         bankroll_cents,
         kelly_fraction,
         max_contracts,
+        held_on("yes"),
         slippage_buffer_cents,
     );
 
@@ -251,6 +331,7 @@ pub fn evaluate_best_side(
         bankroll_cents,
         kelly_fraction,
         max_contracts,
+        held_on("no"),
         slippage_buffer_cents,
     );
 
@@ -301,6 +382,7 @@ pub fn momentum_gate(
                 StrategySignal {
                     action: TradeAction::Skip,
                     quantity: 0,
+                    skip_reason: Some(SkipReason::MomentumGated),
                     ..signal
                 }
             } else if momentum_score < taker_momentum_threshold as f64 {
@@ -321,6 +403,7 @@ pub fn momentum_gate(
                 StrategySignal {
                     action: TradeAction::Skip,
                     quantity: 0,
+                    skip_reason: Some(SkipReason::MomentumGated),
                     ..signal
                 }
             } else {
@@ -371,6 +454,39 @@ pub fn devig_3way(home_odds: f64, away_odds: f64, draw_odds: f64) -> (f64, f64,
     )
 }
 
+/// Standard error of a weighted consensus probability, from how far the
+/// books' own devigged probabilities spread around it. Uses the effective
+/// number of books, (sum w)^2 / sum w^2, so one heavily weighted book counts
+/// as one. `None` with fewer than two books.
+pub fn devig_stderr(probs: &[(f64, f64)]) -> Option<f64> {
+    if probs.len() < 2 {
+        return None;
+    }
+    let total: f64 = probs.iter().map(|&(_, w)| w).sum();
+    let total_sq: f64 = probs.iter().map(|&(_, w)| w * w).sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let mean = probs.iter().map(|&(p, w)| p * w).sum::<f64>() / total;
+    let variance = probs
+        .iter()
+        .map(|&(p, w)| w * (p - mean).powi(2))
+        .sum::<f64>()
+        / total;
+    let n_eff = total * total / total_sq;
+    Some((variance / n_eff).sqrt())
+}
+
+/// Edge threshold for a fair value with standard error `stderr` cents: the
+/// smallest whole-cent edge above `multiple` standard errors. Falls back to
+/// `fixed` when either is unknown.
+pub fn stderr_edge_threshold(fixed: u8, multiple: Option<f64>, stderr: Option<f64>) -> u8 {
+    match (multiple, stderr) {
+        (Some(k), Some(se)) if se.is_finite() => ((k * se).max(0.0).floor() + 1.0).min(99.0) as u8,
+        _ => fixed,
+    }
+}
+
 /// Compute fair value in cents from devigged probability.
 pub fn fair_value_cents(probability: f64) -> u32 {
     (probability * 100.0).round().clamp(1.0, 99.0) as u32
@@ -396,6 +512,29 @@ mod tests {
         assert!(home > 0.5); // favorite
     }
 
+    #[test]
+    fn test_devig_stderr() {
+        assert_eq!(devig_stderr(&[(0.6, 1.0)]), None);
+        assert_eq!(devig_stderr(&[(0.6, 1.0), (0.6, 1.0)]), Some(0.0));
+        // Two equal books 4 points apart: sd 0.02 over sqrt(2)
+        let se = devig_stderr(&[(0.58, 1.0), (0.62, 1.0)]).unwrap();
+        assert!((se - 0.02 / 2f64.sqrt()).abs() < 1e-9);
+        // A dominant book leaves fewer effective books and a smaller spread
+        let skewed = devig_stderr(&[(0.58, 9.0), (0.62, 1.0)]).unwrap();
+        assert!(skewed < se);
+    }
+
+    #[test]
+    fn test_stderr_edge_threshold() {
+        assert_eq!(stderr_edge_threshold(5, None, Some(2.0)), 5);
+        assert_eq!(stderr_edge_threshold(5, Some(2.0), None), 5);
+        // edge must exceed k * stderr
+        assert_eq!(stderr_edge_threshold(5, Some(2.0), Some(1.5)), 4);
+        assert_eq!(stderr_edge_threshold(5, Some(2.0), Some(1.4)), 3);
+        assert_eq!(stderr_edge_threshold(5, Some(2.0), Some(0.0)), 1);
+        assert_eq!(stderr_edge_threshold(5, Some(2.0), Some(80.0)), 99);
+    }
+
     #[test]
     fn test_fair_value_cents() {
         assert_eq!(fair_value_cents(0.60), 60);
@@ -449,6 +588,7 @@ mod tests {
         assert_eq!(signal.action, TradeAction::TakerBuy);
         let gated = momentum_gate(signal, 30.0, 40, 75);
         assert_eq!(gated.action, TradeAction::Skip);
+        assert_eq!(gated.skip_reason, Some(SkipReason::MomentumGated));
     }
 
     #[test]
@@ -476,6 +616,16 @@ mod tests {
         assert_eq!(signal.action, TradeAction::Skip);
         let gated = momentum_gate(signal, 90.0, 40, 75);
         assert_eq!(gated.action, TradeAction::Skip);
+        assert_eq!(gated.skip_reason, Some(SkipReason::BelowThreshold));
+    }
+
+    #[test]
+    fn test_skip_reason_no_liquidity() {
+        let signal = evaluate_with_slippage(65, 0, 0, 5, 2, 1, 100_000, 0.25, 100, 0, 0);
+        assert_eq!(signal.action, TradeAction::Skip);
+        assert_eq!(signal.skip_reason, Some(SkipReason::NoLiquidity));
+        let buy = evaluate_with_slippage(65, 58, 60, 5, 2, 1, 100_000, 0.25, 100, 0, 0);
+        assert_eq!(buy.skip_reason, None);
     }
 
     #[test]
@@ -500,7 +650,7 @@ mod tests {
     fn test_evaluate_with_slippage_buffer() {
         // Edge of 5 with 2-cent slippage buffer -> effective edge of 3
         // Should downgrade from taker (threshold 5) to maker (threshold 2)
-        let signal = evaluate_with_slippage(65, 58, 60, 5, 2, 1, 100_000, 0.25, 100, 2);
+        let signal = evaluate_with_slippage(65, 58, 60, 5, 2, 1, 100_000, 0.25, 100, 0, 2);
         assert!(matches!(signal.action, TradeAction::MakerBuy { .. }));
     }
 
@@ -508,24 +658,38 @@ mod tests {
     fn test_slippage_buffer_can_cause_skip() {
         // Edge of 3 with 2-cent slippage buffer -> effective edge of 1
         // Below maker threshold (2) -> SKIP
-        let signal = evaluate_with_slippage(63, 58, 60, 5, 2, 1, 100_000, 0.25, 100, 2);
+        let signal = evaluate_with_slippage(63, 58, 60, 5, 2, 1, 100_000, 0.25, 100, 0, 2);
         assert_eq!(signal.action, TradeAction::Skip);
     }
 
     #[test]
     fn test_slippage_zero_same_as_evaluate() {
         // With 0 slippage buffer, should behave same as regular evaluate
-        let signal_with = evaluate_with_slippage(65, 58, 60, 5, 2, 1, 100_000, 0.25, 100, 0);
+        let signal_with = evaluate_with_slippage(65, 58, 60, 5, 2, 1, 100_000, 0.25, 100, 0, 0);
         let signal_without = evaluate(65, 58, 60, 5, 2, 1, 100_000, 0.25, 100);
         assert_eq!(signal_with.action, signal_without.action);
     }
 
+    #[test]
+    fn test_held_inventory_tops_up_to_kelly_target() {
+        let full = evaluate_with_slippage(65, 58, 60, 5, 2, 1, 100_000, 0.25, 100, 0, 0);
+        assert_eq!(full.action, TradeAction::TakerBuy);
+        let top_up = evaluate_with_slippage(65, 58, 60, 5, 2, 1, 100_000, 0.25, 100, 10, 0);
+        assert_eq!(top_up.action, TradeAction::TakerBuy);
+        assert_eq!(top_up.quantity, full.quantity - 10);
+
+        // At the per-market cap nothing more is bought
+        let capped = evaluate_with_slippage(65, 58, 60, 5, 2, 1, 100_000, 0.25, 20, 20, 0);
+        assert_eq!(capped.action, TradeAction::Skip);
+        assert_eq!(capped.skip_reason, Some(SkipReason::InventoryHeld));
+    }
+
     #[test]
     fn test_dual_side_prefers_profitable_no() {
         // YES edge -12, NO edge +10 → should return NO side
         // fair_value=55, yes_ask=67 → YES edge = 55-67 = -12
         // no_fair_value=45, no_ask=35 → NO edge = 45-35 = +10
-        let dual = evaluate_best_side(55, 65, 67, 33, 35, 5, 2, 1, 100_000, 0.25, 100, 0);
+        let dual = evaluate_best_side(55, 65, 67, 33, 35, 5, 2, 1, 100_000, 0.25, 100, None, 0);
         assert_eq!(dual.side, "no");
         assert!(dual.signal.action != TradeAction::Skip);
     }
@@ -535,7 +699,7 @@ mod tests {
         // YES edge +5, NO edge +3 → should return YES side
         // fair_value=65, yes_ask=60 → YES edge = 65-60 = +5
         // no_fair_value=35, no_ask=40 → NO edge = 35-40 = -5
-        let dual = evaluate_best_side(65, 58, 60, 38, 40, 5, 2, 1, 100_000, 0.25, 100, 0);
+        let dual = evaluate_best_side(65, 58, 60, 38, 40, 5, 2, 1, 100_000, 0.25, 100, None, 0);
         assert_eq!(dual.side, "yes");
     }
 
@@ -544,7 +708,7 @@ mod tests {
         // Both edges negative → should return YES side Skip
         // fair_value=50, yes_ask=52 → YES edge = -2
         // no_fair_value=50, no_ask=52 → NO edge = -2
-        let dual = evaluate_best_side(50, 48, 52, 48, 52, 5, 2, 1, 100_000, 0.25, 100, 0);
+        let dual = evaluate_best_side(50, 48, 52, 48, 52, 5, 2, 1, 100_000, 0.25, 100, None, 0);
         assert_eq!(dual.side, "yes");
         assert_eq!(dual.signal.action, TradeAction::Skip);
     }
@@ -554,7 +718,7 @@ mod tests {
         // YES has poor edge, NO has good edge
         // fair_value=30, yes_ask=40 → YES edge = 30-40 = -10 (Skip)
         // no_fair_value=70, no_ask=60 → NO edge = 70-60 = +10 (Taker)
-        let dual = evaluate_best_side(30, 38, 40, 58, 60, 5, 2, 1, 100_000, 0.25, 100, 0);
+        let dual = evaluate_best_side(30, 38, 40, 58, 60, 5, 2, 1, 100_000, 0.25, 100, None, 0);
         assert_eq!(dual.side, "no");
         assert_eq!(dual.signal.action, TradeAction::TakerBuy);
     }
@@ -564,8 +728,25 @@ mod tests {
         // YES has good edge, NO has poor edge
         // fair_value=70, yes_ask=60 → YES edge = 70-60 = +10 (Taker)
         // no_fair_value=30, no_ask=40 → NO edge = 30-40 = -10 (Skip)
-        let dual = evaluate_best_side(70, 58, 60, 38, 40, 5, 2, 1, 100_000, 0.25, 100, 0);
+        let dual = evaluate_best_side(70, 58, 60, 38, 40, 5, 2, 1, 100_000, 0.25, 100, None, 0);
         assert_eq!(dual.side, "yes");
         assert_eq!(dual.signal.action, TradeAction::TakerBuy);
     }
+
+    #[test]
+    fn test_dual_side_held_inventory_is_per_side() {
+        // Only NO is tradeable (see above)
+        let eval =
+            |held| evaluate_best_side(30, 38, 40, 58, 60, 5, 2, 1, 100_000, 0.25, 100, held, 0);
+        let full = eval(None).signal.quantity;
+
+        // Held NO contracts count toward the NO target
+        let top_up = eval(Some(("no", 3)));
+        assert_eq!(top_up.side, "no");
+        assert_eq!(top_up.signal.quantity, full - 3);
+
+        // Holding YES never opens NO in the same ticker
+        let other = eval(Some(("yes", 3)));
+        assert_eq!(other.signal.action, TradeAction::Skip);
+    }
 }
@@ -11,6 +11,62 @@
 //!
 //! Supports both NBA (2880s regulation, 96 buckets) and college basketball
 //! (2400s regulation, 80 buckets) via the `regulation_secs` parameter.
+//! Hockey uses the same curve over goals, with sudden-death overtime and an
+//! optional shootout in place of timed OT.
+//!
+//! Soccer has a third outcome, so it uses `SoccerWinProb` instead: each side's
+//! remaining goals are Poisson, scaled by time left and red cards.
+
+use crate::config::{SanityBound, SoccerModelConfig};
+
+/// A slice of regulation that Kalshi lists its own winner market for: a half
+/// ("1H", "2H") or a quarter ("Q1".."Q4").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GameSegment {
+    /// 0-based position within regulation.
+    pub index: u8,
+    /// Equal segments regulation is divided into (2 halves or 4 quarters).
+    pub count: u8,
+}
+
+impl GameSegment {
+    pub fn parse(label: &str) -> Option<Self> {
+        let label = label.trim().to_uppercase();
+        let (index, count) = match label.as_str() {
+            "1H" => (0, 2),
+            "2H" => (1, 2),
+            "Q1" => (0, 4),
+            "Q2" => (1, 4),
+            "Q3" => (2, 4),
+            "Q4" => (3, 4),
+            _ => return None,
+        };
+        Some(Self { index, count })
+    }
+
+    pub fn label(&self) -> String {
+        match self.count {
+            2 => format!("{}H", self.index + 1),
+            _ => format!("Q{}", self.index + 1),
+        }
+    }
+
+    /// Game-clock seconds elapsed at the start and end of the segment.
+    pub fn bounds(&self, regulation_secs: u16) -> (u16, u16) {
+        let len = regulation_secs / self.count as u16;
+        (len * self.index as u16, len * (self.index as u16 + 1))
+    }
+}
+
+/// Periods in regulation implied by its length, for sports that do not
+/// configure it: two halves up to 2400s, otherwise four quarters.
+fn default_regulation_periods(regulation_secs: u16) -> u8 {
+    if regulation_secs <= 2400 {
+        2
+    } else {
+        4
+    }
+}
 
 /// Parameterized win-probability model.
 #[derive(Debug, Clone)]
@@ -21,6 +77,30 @@ pub struct WinProbTable {
     ot_k_start: f64,
     ot_k_range: f64,
     regulation_secs: u16,
+    /// Length of one overtime period (300s in basketball).
+    ot_period_secs: u16,
+    regulation_periods: u8,
+    /// Home win probability for a game tied at the end of regulation.
+    tied_home_prob: u8,
+    /// The first OT score wins (hockey), instead of timed overtime.
+    sudden_death: bool,
+    /// Home win probability in a shootout after the OT period, if one follows.
+    shootout_home_prob: Option<u8>,
+    sanity_bounds: Vec<SanityBound>,
+    /// Standard error of every k, relative to its value.
+    k_rel_stderr: f64,
+    /// Standard error of `home_advantage`, in points.
+    home_advantage_stderr: f64,
+}
+
+/// Model output that broke a configured sanity bound.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundViolation {
+    /// Model home fair value in cents.
+    pub raw_home_fair: u32,
+    /// Home fair value clamped into the bound.
+    pub clamped_home_fair: u32,
+    pub bound: SanityBound,
 }
 
 impl WinProbTable {
@@ -39,19 +119,82 @@ impl WinProbTable {
             ot_k_start,
             ot_k_range,
             regulation_secs,
+            ot_period_secs: 300,
+            regulation_periods: default_regulation_periods(regulation_secs),
+            tied_home_prob: 57,
+            sudden_death: false,
+            shootout_home_prob: None,
+            sanity_bounds: Vec::new(),
+            k_rel_stderr: 0.0,
+            home_advantage_stderr: 0.0,
         }
     }
 
+    pub fn with_ot_period_secs(mut self, secs: u16) -> Self {
+        self.ot_period_secs = secs;
+        self
+    }
+
+    pub fn with_regulation_periods(mut self, periods: u8) -> Self {
+        self.regulation_periods = periods;
+        self
+    }
+
+    pub fn with_tied_home_prob(mut self, prob: u8) -> Self {
+        self.tied_home_prob = prob;
+        self
+    }
+
+    /// Sudden-death overtime, followed by a shootout at `shootout_home_prob`
+    /// when one is given.
+    pub fn with_sudden_death(mut self, shootout_home_prob: Option<u8>) -> Self {
+        self.sudden_death = true;
+        self.shootout_home_prob = shootout_home_prob;
+        self
+    }
+
+    /// Periods in regulation; later periods are overtime.
+    pub fn regulation_periods(&self) -> u8 {
+        self.regulation_periods
+    }
+
+    pub fn with_sanity_bounds(mut self, bounds: Vec<SanityBound>) -> Self {
+        self.sanity_bounds = bounds;
+        self
+    }
+
+    /// Parameter uncertainty behind `fair_value_stderr`.
+    pub fn with_stderr(mut self, k_rel_stderr: f64, home_advantage_stderr: f64) -> Self {
+        self.k_rel_stderr = k_rel_stderr;
+        self.home_advantage_stderr = home_advantage_stderr;
+        self
+    }
+
     /// Convenience constructor from config.
     pub fn from_config(config: &crate::config::WinProbConfig) -> Self {
-        Self::new(
+        let regulation_secs = config.regulation_secs.unwrap_or(2880);
+        let table = Self::new(
             config.home_advantage,
             config.k_start,
             config.k_range,
             config.ot_k_start,
             config.ot_k_range,
-            config.regulation_secs.unwrap_or(2880),
+            regulation_secs,
+        )
+        .with_ot_period_secs(config.ot_period_secs.unwrap_or(300))
+        .with_regulation_periods(
+            config
+                .regulation_periods
+                .unwrap_or_else(|| default_regulation_periods(regulation_secs)),
         )
+        .with_tied_home_prob(config.tied_home_prob.unwrap_or(57))
+        .with_sanity_bounds(config.sanity_bounds.clone())
+        .with_stderr(config.k_rel_stderr, config.home_advantage_stderr);
+        if config.sudden_death {
+            table.with_sudden_death(config.shootout_home_prob)
+        } else {
+            table
+        }
     }
 
     /// Regulation lookup.
@@ -74,7 +217,7 @@ impl WinProbTable {
             } else if clamped_diff < 0 {
                 0
             } else {
-                57 // tied -> OT, slight home edge
+                self.tied_home_prob // tied -> OT, slight home edge
             };
         }
 
@@ -88,27 +231,39 @@ impl WinProbTable {
     /// Overtime lookup.
     ///
     /// * `score_diff` -- home score minus away score (clamped to -40..=40).
-    /// * `time_bucket` -- 0 = OT start, 10 = end of OT period (each bucket
-    ///   = 30 seconds).
+    /// * `time_bucket` -- 0 = OT start, end of the OT period varies by sport
+    ///   (10 for basketball's 5 minutes; each bucket = 30 seconds).
     ///
     /// Returns a probability 0-100 (u8).
     pub fn lookup_overtime(&self, score_diff: i32, time_bucket: u16) -> u8 {
         let clamped_diff = score_diff.clamp(-40, 40);
-        let bucket = (time_bucket as f64).min(10.0);
+        let ot_buckets = (self.ot_period_secs as f64 / 30.0).max(1.0);
+        let bucket = (time_bucket as f64).min(ot_buckets);
+
+        if self.sudden_death {
+            return match clamped_diff.signum() {
+                1 => 100,
+                -1 => 0,
+                _ => match self.shootout_home_prob {
+                    Some(prob) if bucket >= ot_buckets => prob,
+                    _ => self.tied_home_prob,
+                },
+            };
+        }
 
-        if bucket >= 10.0 {
+        if bucket >= ot_buckets {
             return if clamped_diff > 0 {
                 100
             } else if clamped_diff < 0 {
                 0
             } else {
-                57
+                self.tied_home_prob
             };
         }
 
         let adjusted_diff = clamped_diff as f64 + self.home_advantage;
-        // OT k ramps from ot_k_start to ot_k_start+ot_k_range over 5 minutes (10 buckets).
-        let k = self.ot_k_start + (bucket / 10.0).powi(3) * self.ot_k_range;
+        // OT k ramps from ot_k_start to ot_k_start+ot_k_range over the OT period.
+        let k = self.ot_k_start + (bucket / ot_buckets).powi(3) * self.ot_k_range;
         let prob = 1.0 / (1.0 + (-k * adjusted_diff).exp());
         (prob * 100.0).round().clamp(0.0, 100.0) as u8
     }
@@ -128,6 +283,278 @@ impl WinProbTable {
         let home = self.lookup_overtime(score_diff, time_bucket) as u32;
         (home, 100 - home)
     }
+
+    /// Standard error in cents of the regulation home fair value, from the
+    /// uncertainty in k and home advantage (delta method). Zero once the game
+    /// is decided by the clock.
+    pub fn fair_value_stderr(&self, score_diff: i32, total_elapsed_seconds: u16) -> f64 {
+        let regulation_buckets = self.regulation_secs as f64 / 30.0;
+        let bucket = (total_elapsed_seconds / 30) as f64;
+        if bucket >= regulation_buckets {
+            return 0.0;
+        }
+        let k = self.k_start + (bucket / regulation_buckets).powi(3) * self.k_range;
+        self.logistic_stderr(k, score_diff)
+    }
+
+    /// Same for the current overtime period. Sudden death has no curve, so
+    /// no model error.
+    pub fn fair_value_overtime_stderr(&self, score_diff: i32, ot_elapsed_seconds: u16) -> f64 {
+        let ot_buckets = (self.ot_period_secs as f64 / 30.0).max(1.0);
+        let bucket = (ot_elapsed_seconds / 30) as f64;
+        if self.sudden_death || bucket >= ot_buckets {
+            return 0.0;
+        }
+        let k = self.ot_k_start + (bucket / ot_buckets).powi(3) * self.ot_k_range;
+        self.logistic_stderr(k, score_diff)
+    }
+
+    /// dp/dk = p(1-p)x and dp/dh = p(1-p)k, for p = 1 / (1 + exp(-kx)) with
+    /// x = diff + h.
+    fn logistic_stderr(&self, k: f64, score_diff: i32) -> f64 {
+        let x = score_diff.clamp(-40, 40) as f64 + self.home_advantage;
+        let p = 1.0 / (1.0 + (-k * x).exp());
+        let k_term = x * k * self.k_rel_stderr;
+        let h_term = k * self.home_advantage_stderr;
+        p * (1.0 - p) * (k_term * k_term + h_term * h_term).sqrt() * 100.0
+    }
+
+    /// (home_fair, away_fair) in cents for winning one segment of regulation,
+    /// given the score margin within the segment so far.
+    ///
+    /// The segment is priced as the end of a game with the same time left,
+    /// with home advantage scaled to the segment's share of regulation. A
+    /// segment tied at its end pays neither side, so a decided tie is (0, 0).
+    pub fn segment_fair_value(
+        &self,
+        segment_diff: i32,
+        segment_secs: u16,
+        segment_elapsed: u16,
+    ) -> (u32, u32) {
+        if segment_elapsed >= segment_secs {
+            return match segment_diff.signum() {
+                1 => (100, 0),
+                -1 => (0, 100),
+                _ => (0, 0),
+            };
+        }
+        let remaining = (segment_secs - segment_elapsed) as f64;
+        let regulation = self.regulation_secs as f64;
+        let equivalent_elapsed = (regulation - remaining).max(0.0);
+        let k = self.k_start + (equivalent_elapsed / regulation).powi(3) * self.k_range;
+        let adjusted_diff = segment_diff.clamp(-40, 40) as f64
+            + self.home_advantage * segment_secs as f64 / regulation;
+        let prob = 1.0 / (1.0 + (-k * adjusted_diff).exp());
+        let home = (prob * 100.0).round().clamp(0.0, 100.0) as u32;
+        (home, 100 - home)
+    }
+
+    /// Home fair values over score diff x regulation time remaining, for
+    /// eyeballing the parameters. Rows span the largest sanity-bound lead
+    /// (20 without bounds); cells a bound would clamp are flagged.
+    pub fn grid(&self, columns: usize) -> WinProbGrid {
+        let max_lead = self
+            .sanity_bounds
+            .iter()
+            .map(|b| b.min_lead as i32)
+            .max()
+            .unwrap_or(20);
+        WinProbGrid::build(
+            max_lead,
+            self.regulation_secs,
+            columns,
+            |diff, remaining| {
+                let (home, _) = self.fair_value(diff, self.regulation_secs - remaining);
+                (home, self.check_bounds(diff, remaining, home).is_some())
+            },
+        )
+    }
+
+    /// Check a home fair value against the sanity bounds. `remaining_secs` is the
+    /// time left in the current period of play (regulation or the current OT).
+    /// Returns the tightest violated bound with the clamped fair value, or `None`.
+    pub fn check_bounds(
+        &self,
+        score_diff: i32,
+        remaining_secs: u16,
+        home_fair: u32,
+    ) -> Option<BoundViolation> {
+        let lead = score_diff.unsigned_abs();
+        // Express the fair value from the leader's point of view.
+        let leader_fair = if score_diff >= 0 {
+            home_fair
+        } else {
+            100u32.saturating_sub(home_fair)
+        };
+        let bound = self
+            .sanity_bounds
+            .iter()
+            .filter(|b| lead >= b.min_lead && remaining_secs <= b.max_remaining_secs)
+            .filter(|b| leader_fair < b.min_fair)
+            .max_by_key(|b| b.min_fair)?;
+        let clamped_leader = bound.min_fair.min(99);
+        let clamped_home_fair = if score_diff >= 0 {
+            clamped_leader
+        } else {
+            100 - clamped_leader
+        };
+        Some(BoundViolation {
+            raw_home_fair: home_fair,
+            clamped_home_fair,
+            bound: bound.clone(),
+        })
+    }
+}
+
+/// Two 45-minute halves.
+pub const SOCCER_REGULATION_SECS: u16 = 5400;
+
+/// Play still left once the clock passes 90 minutes, until the final whistle.
+const SOCCER_MIN_REMAINING_SECS: f64 = 60.0;
+
+/// Goals per side beyond which the Poisson tail is ignored.
+const MAX_REMAINING_GOALS: i32 = 15;
+
+/// Three-way (home/draw/away) in-play model for soccer.
+#[derive(Debug, Clone)]
+pub struct SoccerWinProb {
+    home_goals_per_90: f64,
+    away_goals_per_90: f64,
+    added_time_secs: u16,
+    red_card_own_factor: f64,
+    red_card_opponent_factor: f64,
+}
+
+impl SoccerWinProb {
+    pub fn from_config(config: &SoccerModelConfig) -> Self {
+        Self {
+            home_goals_per_90: config.home_goals_per_90,
+            away_goals_per_90: config.away_goals_per_90,
+            added_time_secs: config.added_time_secs,
+            red_card_own_factor: config.red_card_own_factor,
+            red_card_opponent_factor: config.red_card_opponent_factor,
+        }
+    }
+
+    /// (home, draw, away) probabilities given the goal difference, match
+    /// seconds elapsed (capped at 5400) and red cards shown to each side.
+    pub fn probabilities(
+        &self,
+        score_diff: i32,
+        elapsed_secs: u16,
+        (home_reds, away_reds): (u8, u8),
+    ) -> (f64, f64, f64) {
+        let regulation = SOCCER_REGULATION_SECS as f64;
+        let left = regulation - (elapsed_secs as f64).min(regulation);
+        let stretch = 1.0 + self.added_time_secs as f64 / regulation;
+        let remaining = (left * stretch).max(SOCCER_MIN_REMAINING_SECS) / regulation;
+
+        let home_rate = self.home_goals_per_90
+            * self.red_card_own_factor.powi(home_reds as i32)
+            * self.red_card_opponent_factor.powi(away_reds as i32);
+        let away_rate = self.away_goals_per_90
+            * self.red_card_own_factor.powi(away_reds as i32)
+            * self.red_card_opponent_factor.powi(home_reds as i32);
+        let home_goals = poisson(home_rate * remaining);
+        let away_goals = poisson(away_rate * remaining);
+
+        let (mut home, mut draw, mut away) = (0.0, 0.0, 0.0);
+        for (h, ph) in home_goals.iter().enumerate() {
+            for (a, pa) in away_goals.iter().enumerate() {
+                let p = ph * pa;
+                match (score_diff + h as i32 - a as i32).signum() {
+                    1 => home += p,
+                    -1 => away += p,
+                    _ => draw += p,
+                }
+            }
+        }
+        let total = home + draw + away;
+        (home / total, draw / total, away / total)
+    }
+
+    /// (home, draw, away) fair values in cents, summing to 100.
+    pub fn fair_values(
+        &self,
+        score_diff: i32,
+        elapsed_secs: u16,
+        red_cards: (u8, u8),
+    ) -> (u32, u32, u32) {
+        let (home, _, away) = self.probabilities(score_diff, elapsed_secs, red_cards);
+        let home = (home * 100.0).round() as u32;
+        let away = ((away * 100.0).round() as u32).min(100 - home);
+        (home, 100 - home - away, away)
+    }
+
+    /// Home win fair values over goal diff x time remaining, at full strength.
+    pub fn grid(&self, columns: usize) -> WinProbGrid {
+        WinProbGrid::build(3, SOCCER_REGULATION_SECS, columns, |diff, remaining| {
+            let (home, _, _) = self.fair_values(diff, SOCCER_REGULATION_SECS - remaining, (0, 0));
+            (home, false)
+        })
+    }
+}
+
+/// Home fair values over a score diff x time remaining grid, shown by the
+/// TUI's win-prob view.
+#[derive(Debug, Clone, Default)]
+pub struct WinProbGrid {
+    /// Sport label, set by the pipeline.
+    pub sport: String,
+    /// Home score minus away score per row, home leading first.
+    pub score_diffs: Vec<i32>,
+    /// Regulation seconds remaining per column, start of the game first.
+    pub remaining_secs: Vec<u16>,
+    /// (home fair cents, clamped by a sanity bound) per row and column.
+    pub cells: Vec<Vec<(u32, bool)>>,
+}
+
+impl WinProbGrid {
+    /// Rows from `max_lead` down to `-max_lead` in about ten steps. Columns
+    /// split regulation evenly, then add the last two minutes and 30 seconds
+    /// that the default sanity bounds watch.
+    fn build(
+        max_lead: i32,
+        regulation_secs: u16,
+        columns: usize,
+        fair: impl Fn(i32, u16) -> (u32, bool),
+    ) -> Self {
+        let step = (max_lead / 5).max(1);
+        let score_diffs: Vec<i32> = (-max_lead..=max_lead)
+            .rev()
+            .filter(|d| d % step == 0)
+            .collect();
+        let columns = columns.max(1);
+        let mut remaining_secs: Vec<u16> = (0..columns)
+            .map(|i| (regulation_secs as usize * (columns - i) / columns) as u16)
+            .collect();
+        for tail in [120, 30] {
+            if remaining_secs.last().is_some_and(|&last| tail < last) {
+                remaining_secs.push(tail);
+            }
+        }
+        let cells = score_diffs
+            .iter()
+            .map(|&diff| remaining_secs.iter().map(|&r| fair(diff, r)).collect())
+            .collect();
+        Self {
+            sport: String::new(),
+            score_diffs,
+            remaining_secs,
+            cells,
+        }
+    }
+}
+
+/// P(k goals) for k in 0..=MAX_REMAINING_GOALS with mean `lambda`.
+fn poisson(lambda: f64) -> Vec<f64> {
+    let mut p = (-lambda).exp();
+    let mut probs = Vec::with_capacity(MAX_REMAINING_GOALS as usize + 1);
+    for k in 0..=MAX_REMAINING_GOALS {
+        probs.push(p);
+        p *= lambda / (k + 1) as f64;
+    }
+    probs
 }
 
 #[cfg(test)]
@@ -141,7 +568,7 @@ mod tests {
     #[test]
     fn test_lookup_tied_game_start() {
         let prob = default_table().lookup(0, 0);
-        assert!(prob >= 52 && prob <= 57, "got {prob}");
+        assert!((52..=57).contains(&prob), "got {prob}");
     }
 
     #[test]
@@ -198,7 +625,7 @@ mod tests {
     #[test]
     fn test_lookup_overtime() {
         let prob = default_table().lookup_overtime(0, 0);
-        assert!(prob >= 50 && prob <= 60, "got {prob}");
+        assert!((50..=60).contains(&prob), "got {prob}");
     }
 
     #[test]
@@ -207,31 +634,40 @@ mod tests {
         assert!(prob >= 90, "got {prob}");
     }
 
+    #[test]
+    fn test_lookup_overtime_longer_period() {
+        // NFL overtime runs 10 minutes: 5 minutes in is not yet decided
+        let table = default_table().with_ot_period_secs(600);
+        assert!(table.lookup_overtime(3, 10) < 100);
+        assert_eq!(table.lookup_overtime(3, 20), 100);
+        assert_eq!(default_table().lookup_overtime(3, 10), 100);
+    }
+
     // ---- Mid-game calibration (NBA data-driven) ----
 
     #[test]
     fn test_lookup_home_up_5_halftime() {
         let prob = default_table().lookup(5, 48);
-        assert!(prob >= 64 && prob <= 72, "got {prob}");
+        assert!((64..=72).contains(&prob), "got {prob}");
     }
 
     #[test]
     fn test_lookup_home_up_10_halftime() {
         let prob = default_table().lookup(10, 48);
-        assert!(prob >= 74 && prob <= 82, "got {prob}");
+        assert!((74..=82).contains(&prob), "got {prob}");
     }
 
     #[test]
     fn test_lookup_home_up_5_end_q3() {
         let prob = default_table().lookup(5, 72);
-        assert!(prob >= 75 && prob <= 83, "got {prob}");
+        assert!((75..=83).contains(&prob), "got {prob}");
     }
 
     #[test]
     fn test_overtime_bucket4_up3() {
         // 3 min into OT with a 3-point lead should be ~73-82 %, not >90 %.
         let prob = default_table().lookup_overtime(3, 4);
-        assert!(prob >= 68 && prob <= 78, "got {prob}");
+        assert!((68..=78).contains(&prob), "got {prob}");
     }
 
     // ---- Fair value bridge functions ----
@@ -247,14 +683,14 @@ mod tests {
     fn test_fair_value_from_score_overtime() {
         let (home, away) = default_table().fair_value_overtime(0, 120);
         // At OT bucket 4 (120s/30), tied game with home-court advantage => ~62%
-        assert!(home >= 55 && home <= 63, "got {home}");
+        assert!((55..=63).contains(&home), "got {home}");
         assert_eq!(home + away, 100);
     }
 
     #[test]
     fn test_fair_value_pregame() {
         let (home, away) = default_table().fair_value(0, 0);
-        assert!(home >= 52 && home <= 57);
+        assert!((52..=57).contains(&home));
         assert_eq!(home + away, 100);
     }
 
@@ -271,7 +707,7 @@ mod tests {
     fn test_college_table_from_defaults() {
         let table = WinProbTable::new(3.5, 0.065, 0.25, 0.10, 1.0, 2400);
         let prob = table.lookup(0, 0);
-        assert!(prob >= 56 && prob <= 62, "got {prob}");
+        assert!((56..=62).contains(&prob), "got {prob}");
         let prob = table.lookup(0, 80);
         assert_eq!(prob, 57);
     }
@@ -280,7 +716,7 @@ mod tests {
     fn test_nba_unchanged_with_regulation_secs() {
         let nba = WinProbTable::new(2.5, 0.065, 0.25, 0.10, 1.0, 2880);
         let prob = nba.lookup(0, 0);
-        assert!(prob >= 52 && prob <= 57, "got {prob}");
+        assert!((52..=57).contains(&prob), "got {prob}");
         let prob = nba.lookup(10, 92);
         assert!(prob >= 95, "got {prob}");
         let prob = nba.lookup(5, 96);
@@ -293,7 +729,7 @@ mod tests {
     fn test_college_home_up_5_halftime() {
         let table = WinProbTable::new(3.5, 0.065, 0.25, 0.10, 1.0, 2400);
         let prob = table.lookup(5, 40); // bucket 40 = halftime
-        assert!(prob >= 67 && prob <= 72, "got {prob}");
+        assert!((67..=72).contains(&prob), "got {prob}");
     }
 
     #[test]
@@ -307,14 +743,177 @@ mod tests {
     fn test_college_pregame_home_advantage() {
         let table = WinProbTable::new(3.5, 0.065, 0.25, 0.10, 1.0, 2400);
         let prob = table.lookup(0, 0);
-        assert!(prob >= 55 && prob <= 58, "got {prob}");
+        assert!((55..=58).contains(&prob), "got {prob}");
     }
 
     #[test]
     fn test_college_fair_value_bridge() {
         let table = WinProbTable::new(3.5, 0.065, 0.25, 0.10, 1.0, 2400);
         let (home, away) = table.fair_value(8, 1800);
-        assert!(home >= 86 && home <= 90, "got {home}");
+        assert!((86..=90).contains(&home), "got {home}");
         assert_eq!(home + away, 100);
     }
+
+    fn bounds() -> Vec<SanityBound> {
+        vec![
+            SanityBound {
+                min_lead: 20,
+                max_remaining_secs: 120,
+                min_fair: 95,
+            },
+            SanityBound {
+                min_lead: 10,
+                max_remaining_secs: 30,
+                min_fair: 90,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_bounds_pass_for_model_output() {
+        let table = default_table().with_sanity_bounds(bounds());
+        let (home, _) = table.fair_value(20, 2880 - 120);
+        assert!(table.check_bounds(20, 120, home).is_none());
+        let (home, _) = table.fair_value(-10, 2880 - 30);
+        assert!(table.check_bounds(-10, 30, home).is_none());
+    }
+
+    #[test]
+    fn test_bounds_clamp_leader_fair_value() {
+        let table = default_table().with_sanity_bounds(bounds());
+        let v = table.check_bounds(22, 90, 60).expect("violation");
+        assert_eq!(v.raw_home_fair, 60);
+        assert_eq!(v.clamped_home_fair, 95);
+        assert_eq!(v.bound.min_lead, 20);
+
+        // Away team leading: home fair must be <= 100 - min_fair
+        let v = table.check_bounds(-12, 20, 30).expect("violation");
+        assert_eq!(v.clamped_home_fair, 10);
+    }
+
+    #[test]
+    fn test_bounds_ignore_early_game_and_small_leads() {
+        let table = default_table().with_sanity_bounds(bounds());
+        assert!(table.check_bounds(22, 600, 60).is_none());
+        assert!(table.check_bounds(5, 10, 60).is_none());
+        assert!(default_table().check_bounds(30, 0, 50).is_none());
+    }
+
+    #[test]
+    fn test_hockey_sudden_death_and_shootout() {
+        let table = WinProbTable::new(0.27, 0.59, 1.69, 0.59, 1.0, 3600)
+            .with_regulation_periods(3)
+            .with_tied_home_prob(52)
+            .with_sudden_death(Some(50));
+        assert_eq!(table.regulation_periods(), 3);
+        assert_eq!(default_table().regulation_periods(), 4);
+
+        // One goal late in the third is most of a win
+        let (home, _) = table.fair_value(1, 3600 - 120);
+        assert!(home >= 85, "got {home}");
+        assert_eq!(table.fair_value(0, 3600), (52, 48));
+
+        // The first OT goal ends the game; a tie after OT goes to a shootout
+        assert_eq!(table.fair_value_overtime(1, 30), (100, 0));
+        assert_eq!(table.fair_value_overtime(-1, 30), (0, 100));
+        assert_eq!(table.fair_value_overtime(0, 120), (52, 48));
+        assert_eq!(table.fair_value_overtime(0, 300), (50, 50));
+    }
+
+    #[test]
+    fn test_segment_bounds_and_labels() {
+        let q3 = GameSegment::parse("q3").unwrap();
+        assert_eq!(q3.bounds(2880), (1440, 2160));
+        assert_eq!(q3.label(), "Q3");
+        assert_eq!(GameSegment::parse("2H").unwrap().bounds(2400), (1200, 2400));
+        assert!(GameSegment::parse("3H").is_none());
+    }
+
+    #[test]
+    fn test_segment_fair_value_tightens_as_segment_ends() {
+        let table = default_table();
+        // Half tied at its start: home edge only
+        let (start, _) = table.segment_fair_value(0, 1440, 0);
+        assert!((50..=55).contains(&start), "got {start}");
+        // Same 6-point lead is worth more with less of the half left
+        let (early, _) = table.segment_fair_value(6, 1440, 300);
+        let (late, _) = table.segment_fair_value(6, 1440, 1380);
+        assert!(late > early && late >= 90, "early {early}, late {late}");
+        // A 6-point half lead is not a 6-point game lead with a half to play
+        let (game, _) = table.fair_value(6, 1440);
+        assert!(late > game);
+        // Decided segments
+        assert_eq!(table.segment_fair_value(-3, 720, 720), (0, 100));
+        assert_eq!(table.segment_fair_value(0, 720, 720), (0, 0));
+    }
+
+    #[test]
+    fn test_fair_value_stderr_peaks_in_close_late_games() {
+        assert_eq!(default_table().fair_value_stderr(0, 1440), 0.0);
+        let table = default_table().with_stderr(0.1, 1.0);
+        let start = table.fair_value_stderr(0, 0);
+        assert!((1.0..2.5).contains(&start), "got {start}");
+        // A close game late hinges on home advantage; a blowout does not
+        let close = table.fair_value_stderr(0, 2820);
+        let blowout = table.fair_value_stderr(20, 2820);
+        assert!(
+            close > start && blowout < 0.5,
+            "close {close}, blowout {blowout}"
+        );
+        assert_eq!(table.fair_value_stderr(3, 2880), 0.0);
+        assert!(table.fair_value_overtime_stderr(0, 60) > 0.0);
+        let hockey = table.with_sudden_death(None);
+        assert_eq!(hockey.fair_value_overtime_stderr(0, 60), 0.0);
+    }
+
+    #[test]
+    fn test_grid_spans_bounds_and_flags_clamped_cells() {
+        let table = default_table().with_sanity_bounds(bounds());
+        let grid = table.grid(12);
+        assert_eq!(grid.score_diffs.first(), Some(&20));
+        assert_eq!(grid.score_diffs.last(), Some(&-20));
+        assert_eq!(grid.score_diffs.len(), 11);
+        assert_eq!(grid.remaining_secs[0], 2880);
+        assert_eq!(grid.remaining_secs[11..], [240, 120, 30]);
+
+        // Home value rises with the lead in every column
+        for col in 0..grid.remaining_secs.len() {
+            let column: Vec<u32> = grid.cells.iter().map(|row| row[col].0).collect();
+            assert!(column.windows(2).all(|w| w[0] >= w[1]), "{column:?}");
+        }
+        assert!(grid.cells.iter().flatten().all(|&(_, clamped)| !clamped));
+
+        // A flat model trips the late-game bounds
+        let flat = WinProbTable::new(0.0, 0.001, 0.0, 0.1, 1.0, 2880).with_sanity_bounds(bounds());
+        let grid = flat.grid(12);
+        assert_eq!(grid.cells[0].last(), Some(&(50, true)));
+        assert!(!grid.cells[0][0].1);
+    }
+
+    #[test]
+    fn test_soccer_three_way_fair_values() {
+        let model = SoccerWinProb::from_config(&SoccerModelConfig::default());
+        // Kickoff: home favoured, draw a real outcome
+        let (home, draw, away) = model.fair_values(0, 0, (0, 0));
+        assert_eq!(home + draw + away, 100);
+        assert!(
+            home > away && (20..=32).contains(&draw),
+            "{home}/{draw}/{away}"
+        );
+
+        // A one-goal lead grows in value as the clock runs down
+        let (early, _, _) = model.fair_values(1, 900, (0, 0));
+        let (late, draw_late, _) = model.fair_values(1, 5100, (0, 0));
+        assert!(late > early && late >= 85, "early {early}, late {late}");
+        assert!(draw_late > 0);
+
+        // Stoppage time is never treated as decided
+        let (home, draw, away) = model.fair_values(0, 5400, (0, 0));
+        assert!(draw < 100 && home > 0 && away > 0);
+
+        // A home red card moves value toward the away side
+        let (home_reds, _, away_reds) = model.fair_values(0, 2700, (1, 0));
+        let (home_even, _, away_even) = model.fair_values(0, 2700, (0, 0));
+        assert!(home_reds < home_even && away_reds > away_even);
+    }
 }
@@ -0,0 +1,237 @@
+//! The engine loop's evaluation stage: what each sport's pipeline tick
+//! produced, carried across cycles that re-tick only some sports or games,
+//! and the wait for the next cycle.
+
+use crate::engine::eval_bus::{Dirty, EvalBus};
+use crate::tui::state::{FilterStats, MarketRow};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Live, pre-game and closed game counts from a tick, and its earliest
+/// upcoming start.
+pub type FilterCounts = (usize, usize, usize, Option<DateTime<Utc>>);
+
+/// Market rows and filter counts from each sport's last tick.
+#[derive(Default)]
+pub struct Evaluated {
+    rows: HashMap<String, MarketRow>,
+    /// Evaluated ticker -> sport, watched by the eval bus
+    sports: HashMap<String, String>,
+    /// Counts from each sport's last tick, so a cycle that ticks only
+    /// some sports still reports totals for all of them
+    filters: HashMap<String, FilterCounts>,
+}
+
+impl Evaluated {
+    pub fn rows(&self) -> &HashMap<String, MarketRow> {
+        &self.rows
+    }
+
+    /// Evaluated ticker -> sport.
+    pub fn sports(&self) -> &HashMap<String, String> {
+        &self.sports
+    }
+
+    /// Drop the rows and counts of every sport about to be replaced; sports
+    /// `carried_over` keep theirs.
+    pub fn carry_over(&mut self, carried_over: impl Fn(&String) -> bool) {
+        let sports = &self.sports;
+        self.rows
+            .retain(|ticker, _| sports.get(ticker).is_some_and(&carried_over));
+        self.sports.retain(|_, sport| carried_over(sport));
+        self.filters.retain(|sport, _| carried_over(sport));
+    }
+
+    /// Take in a sport's tick. `filters` is `None` when the tick covered only
+    /// some of its games, so the sport's last counts stand.
+    pub fn absorb(
+        &mut self,
+        sport: &str,
+        rows: HashMap<String, MarketRow>,
+        filters: Option<FilterCounts>,
+    ) {
+        if let Some(filters) = filters {
+            self.filters.insert(sport.to_string(), filters);
+        }
+        self.sports.extend(
+            rows.keys()
+                .map(|ticker| (ticker.clone(), sport.to_string())),
+        );
+        self.rows.extend(rows);
+    }
+
+    /// Counts summed over `sports`, with the earliest upcoming start.
+    pub fn totals<'a>(
+        &self,
+        sports: impl IntoIterator<Item = &'a str>,
+    ) -> (FilterStats, Option<DateTime<Utc>>) {
+        let mut stats = FilterStats::default();
+        let mut earliest_commence: Option<DateTime<Utc>> = None;
+        for sport in sports {
+            if let Some(&(live, pre_game, closed, commence)) = self.filters.get(sport) {
+                stats.live += live;
+                stats.pre_game += pre_game;
+                stats.closed += closed;
+                if let Some(ec) = commence {
+                    earliest_commence = Some(earliest_commence.map_or(ec, |e| e.min(ec)));
+                }
+            }
+        }
+        (stats, earliest_commence)
+    }
+
+    /// Rows for display: sprinting games first, then by momentum
+    /// descending, then edge.
+    pub fn sorted_rows(&self) -> Vec<MarketRow> {
+        let mut rows: Vec<MarketRow> = self.rows.values().cloned().collect();
+        rows.sort_by(|a, b| {
+            b.sprint
+                .cmp(&a.sprint)
+                .then_with(|| {
+                    b.momentum_score
+                        .partial_cmp(&a.momentum_score)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .then_with(|| b.edge.cmp(&a.edge))
+        });
+        rows
+    }
+}
+
+/// Sleep until `wake_at`, or wake as soon as a watched book or streamed odds
+/// change, no sooner than `not_before`. Returns what changed, or `None` when
+/// the timer ran out; a timed cycle ticks every sport, so the change marks
+/// are cleared then.
+pub async fn wait_for_change(
+    eval_bus: &EvalBus,
+    wake_at: Instant,
+    not_before: Instant,
+) -> Option<Dirty> {
+    let timer = tokio::time::sleep_until(tokio::time::Instant::from_std(wake_at));
+    tokio::pin!(timer);
+    let dirty = loop {
+        tokio::select! {
+            _ = &mut timer => break None,
+            _ = eval_bus.changed() => {
+                tokio::time::sleep_until(tokio::time::Instant::from_std(not_before)).await;
+                let dirty = eval_bus.take();
+                if !dirty.is_empty() {
+                    break Some(dirty);
+                }
+            }
+        }
+    };
+    if dirty.is_none() {
+        eval_bus.take();
+    }
+    dirty
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn row(ticker: &str, sprint: bool, momentum_score: f64, edge: i32) -> MarketRow {
+        MarketRow {
+            ticker: ticker.to_string(),
+            fair_value: 50,
+            bid: 48,
+            ask: 52,
+            edge,
+            action: "SKIP".to_string(),
+            skip_reason: None,
+            latency_ms: None,
+            momentum_score,
+            staleness_secs: None,
+            odds_api_fair_value: None,
+            fair_value_source: String::new(),
+            phase: String::new(),
+            sprint,
+            smoothed_bid: 0.0,
+            smoothed_ask: 0.0,
+        }
+    }
+
+    fn rows(tickers: &[&str]) -> HashMap<String, MarketRow> {
+        tickers
+            .iter()
+            .map(|t| (t.to_string(), row(t, false, 0.0, 0)))
+            .collect()
+    }
+
+    #[test]
+    fn test_focused_cycle_keeps_other_sports_rows_and_counts() {
+        let mut evaluated = Evaluated::default();
+        evaluated.absorb("nba", rows(&["NBA-A", "NBA-B"]), Some((2, 0, 0, None)));
+        evaluated.absorb("nhl", rows(&["NHL-A", "NHL-B"]), Some((0, 1, 1, None)));
+
+        // NBA is re-ticked in full and NHL for one game: NBA's rows are
+        // replaced, NHL keeps its other rows and its last full counts
+        evaluated.carry_over(|sport| sport == "nhl");
+        assert_eq!(evaluated.rows().len(), 2);
+        evaluated.absorb("nba", rows(&["NBA-A"]), Some((1, 0, 0, None)));
+        evaluated.absorb("nhl", rows(&["NHL-A"]), None);
+        let (stats, _) = evaluated.totals(["nba", "nhl"]);
+        assert_eq!((stats.live, stats.pre_game, stats.closed), (1, 1, 1));
+        assert!(!evaluated.rows().contains_key("NBA-B"));
+        assert_eq!(evaluated.sports()["NHL-B"], "nhl");
+    }
+
+    #[test]
+    fn test_totals_sum_sports_and_take_earliest_start() {
+        let early = DateTime::from_timestamp(1_000, 0);
+        let late = DateTime::from_timestamp(2_000, 0);
+        let mut evaluated = Evaluated::default();
+        evaluated.absorb("nba", HashMap::new(), Some((1, 2, 0, late)));
+        evaluated.absorb("nhl", HashMap::new(), Some((3, 0, 1, early)));
+        evaluated.absorb("mlb", HashMap::new(), Some((5, 5, 5, None)));
+
+        let (stats, earliest) = evaluated.totals(["nba", "nhl"]);
+        assert_eq!((stats.live, stats.pre_game, stats.closed), (4, 2, 1));
+        assert_eq!(earliest, early);
+    }
+
+    #[test]
+    fn test_sorted_rows_sprint_then_momentum_then_edge() {
+        let mut evaluated = Evaluated::default();
+        let all = [
+            row("EDGE", false, 10.0, 5),
+            row("FLAT", false, 10.0, 1),
+            row("HOT", false, 90.0, 0),
+            row("SPRINT", true, 0.0, 0),
+        ];
+        evaluated.absorb(
+            "nba",
+            all.into_iter().map(|r| (r.ticker.clone(), r)).collect(),
+            None,
+        );
+        let order: Vec<_> = evaluated
+            .sorted_rows()
+            .into_iter()
+            .map(|r| r.ticker)
+            .collect();
+        assert_eq!(order, ["SPRINT", "HOT", "EDGE", "FLAT"]);
+    }
+
+    #[tokio::test]
+    async fn test_wait_returns_change_or_times_out() {
+        let bus = EvalBus::default();
+        let soon = || Instant::now() + Duration::from_millis(20);
+        assert!(wait_for_change(&bus, soon(), Instant::now())
+            .await
+            .is_none());
+
+        bus.feed_changed("nba");
+        let dirty = wait_for_change(
+            &bus,
+            Instant::now() + Duration::from_secs(5),
+            Instant::now(),
+        )
+        .await
+        .unwrap();
+        assert!(dirty.in_full("nba"));
+        assert!(bus.take().is_empty());
+    }
+}
@@ -0,0 +1,154 @@
+//! Executor-level duplicate order guard. A logic bug can emit the same
+//! intent on consecutive cycles; the guard rejects an order when one for the
+//! same ticker, side and action, priced within a band of it, was submitted
+//! inside the window and is still working (not yet filled or cancelled).
+
+use std::time::{Duration, Instant};
+
+/// A live order the guard is watching.
+#[derive(Debug, Clone)]
+pub struct WorkingOrder {
+    pub order_id: String,
+    pub ticker: String,
+    /// "yes" or "no".
+    pub side: String,
+    pub is_buy: bool,
+    pub price: u32,
+    pub quantity: u32,
+    pub filled: u32,
+    pub submitted_at: Instant,
+}
+
+#[derive(Debug, Default)]
+pub struct DuplicateGuard {
+    /// Zero disables the guard.
+    window: Duration,
+    price_band_cents: u32,
+    working: Vec<WorkingOrder>,
+}
+
+impl DuplicateGuard {
+    pub fn new(window: Duration, price_band_cents: u32) -> Self {
+        Self {
+            window,
+            price_band_cents,
+            working: Vec::new(),
+        }
+    }
+
+    /// The working order an order for `ticker`/`side` at `price` would
+    /// duplicate, if any. Forgets orders older than the window.
+    pub fn find_duplicate(
+        &mut self,
+        ticker: &str,
+        side: &str,
+        is_buy: bool,
+        price: u32,
+        now: Instant,
+    ) -> Option<&WorkingOrder> {
+        let window = self.window;
+        self.working
+            .retain(|o| now.duration_since(o.submitted_at) < window);
+        self.working.iter().find(|o| {
+            o.ticker == ticker
+                && o.side == side
+                && o.is_buy == is_buy
+                && o.price.abs_diff(price) <= self.price_band_cents
+        })
+    }
+
+    /// Watch a submitted order. No-op while the guard is disabled.
+    pub fn record(&mut self, order: WorkingOrder) {
+        if !self.window.is_zero() {
+            self.working.push(order);
+        }
+    }
+
+    /// Apply a fill; a fully filled order stops working.
+    pub fn on_fill(&mut self, order_id: &str, count: u32) {
+        if let Some(pos) = self.working.iter().position(|o| o.order_id == order_id) {
+            let order = &mut self.working[pos];
+            order.filled += count;
+            if order.filled >= order.quantity {
+                self.working.swap_remove(pos);
+            }
+        }
+    }
+
+    /// A cancelled order stops working.
+    pub fn release(&mut self, order_id: &str) {
+        self.working.retain(|o| o.order_id != order_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(id: &str, price: u32, at: Instant) -> WorkingOrder {
+        WorkingOrder {
+            order_id: id.to_string(),
+            ticker: "KXNBAGAME-26MAR10LALBOS-BOS".to_string(),
+            side: "yes".to_string(),
+            is_buy: true,
+            price,
+            quantity: 2,
+            filled: 0,
+            submitted_at: at,
+        }
+    }
+
+    #[test]
+    fn test_rejects_similar_working_order_within_window() {
+        let start = Instant::now();
+        let mut guard = DuplicateGuard::new(Duration::from_secs(5), 1);
+        let ticker = "KXNBAGAME-26MAR10LALBOS-BOS";
+        guard.record(order("ord-1", 50, start));
+
+        let now = start + Duration::from_secs(1);
+        assert_eq!(
+            guard
+                .find_duplicate(ticker, "yes", true, 51, now)
+                .map(|o| o.order_id.as_str()),
+            Some("ord-1")
+        );
+        // Outside the band, other side, other action, other ticker
+        assert!(guard.find_duplicate(ticker, "yes", true, 52, now).is_none());
+        assert!(guard.find_duplicate(ticker, "no", true, 50, now).is_none());
+        assert!(guard
+            .find_duplicate(ticker, "yes", false, 50, now)
+            .is_none());
+        assert!(guard
+            .find_duplicate("OTHER", "yes", true, 50, now)
+            .is_none());
+        // Past the window
+        assert!(guard
+            .find_duplicate(ticker, "yes", true, 50, start + Duration::from_secs(5))
+            .is_none());
+    }
+
+    #[test]
+    fn test_filled_or_cancelled_orders_stop_working() {
+        let start = Instant::now();
+        let mut guard = DuplicateGuard::new(Duration::from_secs(5), 1);
+        let ticker = "KXNBAGAME-26MAR10LALBOS-BOS";
+        guard.record(order("ord-1", 50, start));
+        guard.record(order("ord-2", 50, start));
+
+        guard.on_fill("ord-1", 1);
+        guard.release("ord-2");
+        assert!(guard
+            .find_duplicate(ticker, "yes", true, 50, start)
+            .is_some());
+        guard.on_fill("ord-1", 1);
+        assert!(guard
+            .find_duplicate(ticker, "yes", true, 50, start)
+            .is_none());
+
+        let mut disabled = DuplicateGuard::default();
+        disabled.record(order("ord-3", 50, start));
+        assert!(disabled
+            .find_duplicate(ticker, "yes", true, 50, start)
+            .is_none());
+    }
+}
@@ -1,19 +1,143 @@
+use super::dedup::{DuplicateGuard, WorkingOrder};
 use crate::kalshi::rest::KalshiRest;
-use crate::kalshi::types::CreateOrderRequest;
+use crate::kalshi::types::{CreateOrderRequest, TimeInForce};
+use crate::polymarket::rest::PolymarketRest;
 use anyhow::{Context, Result};
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Pre-built buy requests for one ticker, so a sprint entry only patches
+/// count, type, price and time in force.
+#[derive(Debug, Clone)]
+struct OrderTemplates {
+    yes: CreateOrderRequest,
+    no: CreateOrderRequest,
+}
+
+/// An order the exchange accepted.
+#[derive(Debug, Clone)]
+pub struct PlacedOrder {
+    pub order_id: String,
+    /// Contracts filled on arrival; `None` when the venue does not say.
+    pub filled: Option<u32>,
+}
 
 pub struct OrderExecutor {
     rest: Arc<KalshiRest>,
     dry_run: bool,
+    templates: HashMap<String, OrderTemplates>,
+    polymarket: Option<Arc<PolymarketRest>>,
+    /// Polymarket token ids routed to `polymarket`, with their neg-risk flag.
+    polymarket_tokens: HashMap<String, bool>,
+    duplicates: Mutex<DuplicateGuard>,
 }
 
 impl OrderExecutor {
     pub fn new(rest: Arc<KalshiRest>, dry_run: bool) -> Self {
-        Self { rest, dry_run }
+        Self {
+            rest,
+            dry_run,
+            templates: HashMap::new(),
+            polymarket: None,
+            polymarket_tokens: HashMap::new(),
+            duplicates: Mutex::new(DuplicateGuard::default()),
+        }
+    }
+
+    /// Reject orders that duplicate a working one: same ticker, side and
+    /// action, within `price_band_cents`, submitted less than `window` ago.
+    pub fn with_duplicate_guard(mut self, window: Duration, price_band_cents: u32) -> Self {
+        self.duplicates = Mutex::new(DuplicateGuard::new(window, price_band_cents));
+        self
+    }
+
+    /// Report an exchange fill, so a fully filled order stops counting as
+    /// working.
+    pub fn on_fill(&self, order_id: &str, count: u32) {
+        if let Ok(mut guard) = self.duplicates.lock() {
+            guard.on_fill(order_id, count);
+        }
+    }
+
+    /// Watch a submitted live order for duplicates.
+    fn record_working(
+        &self,
+        order_id: &str,
+        ticker: &str,
+        side: &str,
+        is_buy: bool,
+        price: u32,
+        quantity: u32,
+    ) {
+        if let Ok(mut guard) = self.duplicates.lock() {
+            guard.record(WorkingOrder {
+                order_id: order_id.to_string(),
+                ticker: ticker.to_string(),
+                side: side.to_string(),
+                is_buy,
+                price,
+                quantity,
+                filled: 0,
+                submitted_at: Instant::now(),
+            });
+        }
+    }
+
+    /// Route orders for `tokens` (token id -> neg-risk) to Polymarket.
+    pub fn with_polymarket(
+        mut self,
+        rest: Arc<PolymarketRest>,
+        tokens: HashMap<String, bool>,
+    ) -> Self {
+        self.polymarket = Some(rest);
+        self.polymarket_tokens = tokens;
+        self
+    }
+
+    /// Pre-build buy order templates for `ticker`. Returns false if already warm.
+    pub fn prewarm(&mut self, ticker: &str) -> bool {
+        if self.templates.contains_key(ticker) {
+            return false;
+        }
+        let gtc = TimeInForce::GoodTillCanceled;
+        let template = |side: &str| build_order(None, ticker, 1, 1, true, false, side, gtc);
+        self.templates.insert(
+            ticker.to_string(),
+            OrderTemplates {
+                yes: template("yes"),
+                no: template("no"),
+            },
+        );
+        true
+    }
+
+    /// Drop templates for tickers not in `keep`.
+    pub fn release_templates(&mut self, keep: &std::collections::HashSet<String>) {
+        self.templates.retain(|ticker, _| keep.contains(ticker));
+    }
+
+    /// Touch the API in the background so the pooled HTTPS connection is
+    /// open before a sprint order needs it.
+    pub fn warm_connection(&self) {
+        if self.dry_run {
+            return;
+        }
+        let rest = self.rest.clone();
+        tokio::spawn(async move {
+            if let Err(e) = rest.get_balance().await {
+                tracing::warn!(error = %e, "connection pre-warm failed");
+            }
+        });
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
     }
 
-    /// Submit order with validation
+    /// Submit order with validation. `time_in_force` applies to Kalshi
+    /// orders; Polymarket takers are always fill-or-kill.
+    #[allow(clippy::too_many_arguments)]
     pub async fn submit_order(
         &self,
         ticker: &str,
@@ -22,7 +146,8 @@ impl OrderExecutor {
         is_buy: bool,
         is_taker: bool,
         side: &str, // "yes" or "no"
-    ) -> Result<Option<String>> {
+        time_in_force: TimeInForce,
+    ) -> Result<Option<PlacedOrder>> {
         // Validation
         if quantity == 0 {
             anyhow::bail!("quantity must be > 0");
@@ -33,6 +158,17 @@ impl OrderExecutor {
         if side != "yes" && side != "no" {
             anyhow::bail!("side must be 'yes' or 'no', got '{}'", side);
         }
+        if let Ok(mut guard) = self.duplicates.lock() {
+            if let Some(dup) = guard.find_duplicate(ticker, side, is_buy, price, Instant::now()) {
+                anyhow::bail!(
+                    "duplicate of working order {} ({} @ {}c, {}ms ago)",
+                    dup.order_id,
+                    ticker,
+                    dup.price,
+                    dup.submitted_at.elapsed().as_millis()
+                );
+            }
+        }
 
         if self.dry_run {
             tracing::info!(
@@ -42,27 +178,55 @@ impl OrderExecutor {
                 action = if is_buy { "BUY" } else { "SELL" },
                 side = %side,
                 order_type = if is_taker { "TAKER" } else { "MAKER" },
+                time_in_force = time_in_force.label(),
                 "DRY RUN: would submit order"
             );
             return Ok(None); // No order ID in dry run
         }
 
-        // Build order request with dynamic side and price field
-        let order_type = if is_taker { "market" } else { "limit" };
-        let order = CreateOrderRequest {
-            ticker: ticker.to_string(),
-            action: if is_buy {
-                "buy".to_string()
+        if let (Some(poly), Some(&neg_risk)) =
+            (&self.polymarket, self.polymarket_tokens.get(ticker))
+        {
+            // A Polymarket token is a YES on its outcome
+            if side != "yes" {
+                anyhow::bail!("Polymarket orders must buy or sell the outcome token");
+            }
+            let response = poly
+                .create_order(ticker, quantity, price, is_buy, is_taker, neg_risk)
+                .await
+                .context("Polymarket order submission failed")?;
+            tracing::info!(
+                token = %ticker,
+                order_id = %response.order_id,
+                status = %response.status,
+                "Polymarket order submitted"
+            );
+            self.record_working(&response.order_id, ticker, side, is_buy, price, quantity);
+            // An accepted fill-or-kill taker matched in full
+            let matched = is_taker && response.status == "matched";
+            return Ok(Some(PlacedOrder {
+                order_id: response.order_id,
+                filled: matched.then_some(quantity),
+            }));
+        }
+
+        let template = self.templates.get(ticker).filter(|_| is_buy).map(|t| {
+            if side == "yes" {
+                &t.yes
             } else {
-                "sell".to_string()
-            },
-            side: side.to_string(),
-            count: quantity,
-            order_type: order_type.to_string(),
-            yes_price: if side == "yes" { Some(price) } else { None },
-            no_price: if side == "no" { Some(price) } else { None },
-            client_order_id: None,
-        };
+                &t.no
+            }
+        });
+        let order = build_order(
+            template,
+            ticker,
+            quantity,
+            price,
+            is_buy,
+            is_taker,
+            side,
+            time_in_force,
+        );
 
         // Submit to Kalshi API
         let response = self
@@ -76,10 +240,25 @@ impl OrderExecutor {
             side = %side,
             order_id = %response.order.order_id,
             status = %response.order.status,
+            filled = ?response.order.fill_count,
             "order submitted"
         );
+        // Nothing is left working once an immediate order returns
+        if !time_in_force.is_immediate() {
+            self.record_working(
+                &response.order.order_id,
+                ticker,
+                side,
+                is_buy,
+                price,
+                quantity,
+            );
+        }
 
-        Ok(Some(response.order.order_id))
+        Ok(Some(PlacedOrder {
+            order_id: response.order.order_id,
+            filled: response.order.fill_count,
+        }))
     }
 
     /// Cancel an order by ID.
@@ -93,16 +272,64 @@ impl OrderExecutor {
             return Ok(());
         }
 
-        self.rest
-            .cancel_order(order_id)
-            .await
-            .context(format!("failed to cancel order {}", order_id))?;
+        // Polymarket order ids are 0x-prefixed hashes; Kalshi's are UUIDs
+        match &self.polymarket {
+            Some(poly) if order_id.starts_with("0x") => poly.cancel_order(order_id).await,
+            _ => self.rest.cancel_order(order_id).await,
+        }
+        .context(format!("failed to cancel order {}", order_id))?;
+        if let Ok(mut guard) = self.duplicates.lock() {
+            guard.release(order_id);
+        }
 
         tracing::info!(order_id = %order_id, "order cancelled");
         Ok(())
     }
 }
 
+/// Build an order request with dynamic side and price field, patching a
+/// pre-built template for the same ticker and side when one is given.
+#[allow(clippy::too_many_arguments)]
+fn build_order(
+    template: Option<&CreateOrderRequest>,
+    ticker: &str,
+    quantity: u32,
+    price: u32,
+    is_buy: bool,
+    is_taker: bool,
+    side: &str,
+    time_in_force: TimeInForce,
+) -> CreateOrderRequest {
+    let order_type = if is_taker { "market" } else { "limit" };
+    let yes_price = if side == "yes" { Some(price) } else { None };
+    let no_price = if side == "no" { Some(price) } else { None };
+    let time_in_force = time_in_force.api_value().map(str::to_string);
+    if let Some(template) = template {
+        let mut order = template.clone();
+        order.count = quantity;
+        order.order_type = order_type.to_string();
+        order.yes_price = yes_price;
+        order.no_price = no_price;
+        order.time_in_force = time_in_force;
+        return order;
+    }
+    CreateOrderRequest {
+        ticker: ticker.to_string(),
+        action: if is_buy {
+            "buy".to_string()
+        } else {
+            "sell".to_string()
+        },
+        side: side.to_string(),
+        count: quantity,
+        order_type: order_type.to_string(),
+        yes_price,
+        no_price,
+        client_order_id: None,
+        time_in_force,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -111,7 +338,38 @@ mod tests {
     fn test_executor_has_cancel_method() {
         // Compile-time verification that cancel_order exists with correct signature
         fn _assert_cancel_exists(executor: &OrderExecutor) {
-            let _ = executor.cancel_order("test-id");
+            drop(executor.cancel_order("test-id"));
         }
     }
+
+    #[test]
+    fn test_template_order_matches_fresh_build() {
+        let gtc = TimeInForce::GoodTillCanceled;
+        let ioc = TimeInForce::ImmediateOrCancel;
+        let template = build_order(None, "T", 1, 1, true, false, "no", gtc);
+        let patched = build_order(Some(&template), "T", 7, 42, true, true, "no", ioc);
+        let fresh = build_order(None, "T", 7, 42, true, true, "no", ioc);
+        assert_eq!(
+            serde_json::to_value(&patched).unwrap(),
+            serde_json::to_value(&fresh).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_time_in_force_in_request() {
+        let body = |tif| {
+            serde_json::to_value(build_order(None, "T", 5, 40, true, true, "yes", tif)).unwrap()
+        };
+        assert!(body(TimeInForce::GoodTillCanceled)
+            .get("time_in_force")
+            .is_none());
+        assert_eq!(
+            body(TimeInForce::ImmediateOrCancel)["time_in_force"],
+            "immediate_or_cancel"
+        );
+        assert_eq!(
+            body(TimeInForce::FillOrKill)["time_in_force"],
+            "fill_or_kill"
+        );
+    }
 }
@@ -1,2 +1,7 @@
+pub mod dedup;
 pub mod executor;
-pub use executor::OrderExecutor;
+pub mod pegged;
+pub mod preflight;
+pub use executor::{OrderExecutor, PlacedOrder};
+pub use pegged::{PegAction, PegMode, RestingEntry};
+pub use preflight::PreflightReport;
@@ -0,0 +1,364 @@
+use crate::engine::fees::calculate_fee;
+use std::time::{Duration, Instant};
+
+/// Where a pegged maker order sits relative to the best bid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PegMode {
+    /// Join the best bid.
+    Touch,
+    /// Improve the best bid by one cent (never crossing the ask).
+    Improve,
+}
+
+impl PegMode {
+    /// Parse from config; anything other than "touch" means improve.
+    pub fn from_config(s: &str) -> Self {
+        if s.eq_ignore_ascii_case("touch") {
+            PegMode::Touch
+        } else {
+            PegMode::Improve
+        }
+    }
+}
+
+/// What to do with a pegged order given the current book.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PegAction {
+    Hold,
+    Repeg { price: u32 },
+    Cancel { reason: &'static str },
+}
+
+/// A live buy order resting on the book. The position is booked fill by
+/// fill as the exchange reports them, on any order id the entry has rested
+/// under. A pegged entry is kept at the touch via cancel/replace and only
+/// its unfilled remainder is re-pegged; any other rests at its price until
+/// it fills or times out.
+#[derive(Debug, Clone)]
+pub struct RestingEntry {
+    /// The order currently resting.
+    pub order_id: String,
+    /// Every order id the entry has rested under, `order_id` last. A fill
+    /// on a replaced order can still arrive after the re-peg.
+    pub order_ids: Vec<String>,
+    pub ticker: String,
+    pub side: String,
+    pub quantity: u32,
+    pub filled: u32,
+    /// Sum of fill prices times counts.
+    pub filled_cost_cents: u32,
+    pub price: u32,
+    pub sell_target: u32,
+    pub is_taker: bool,
+    /// Re-pegged to the touch in this mode; `None` rests at `price`.
+    pub peg: Option<PegMode>,
+    pub fair_value: u32,
+    pub min_net_profit: i32,
+    pub placed_at: Instant,
+    pub last_peg: Instant,
+    pub repegs: u32,
+}
+
+impl RestingEntry {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        order_id: String,
+        ticker: String,
+        side: String,
+        quantity: u32,
+        price: u32,
+        sell_target: u32,
+        is_taker: bool,
+        now: Instant,
+    ) -> Self {
+        Self {
+            order_ids: vec![order_id.clone()],
+            order_id,
+            ticker,
+            side,
+            quantity,
+            filled: 0,
+            filled_cost_cents: 0,
+            price,
+            sell_target,
+            is_taker,
+            peg: None,
+            fair_value: 0,
+            min_net_profit: 0,
+            placed_at: now,
+            last_peg: now,
+            repegs: 0,
+        }
+    }
+
+    /// Keep the entry at the touch while it still nets `min_net_profit`
+    /// against `fair_value`.
+    pub fn pegged(mut self, mode: PegMode, fair_value: u32, min_net_profit: i32) -> Self {
+        self.peg = Some(mode);
+        self.fair_value = fair_value;
+        self.min_net_profit = min_net_profit;
+        self
+    }
+
+    /// Whether `order_id` is one this entry has rested under.
+    pub fn owns(&self, order_id: &str) -> bool {
+        self.order_ids.iter().any(|id| id == order_id)
+    }
+
+    /// Price the order should rest at for the given touch.
+    pub fn target_price(&self, best_bid: u32, best_ask: u32) -> u32 {
+        let target = match self.peg {
+            Some(PegMode::Improve) => best_bid + 1,
+            Some(PegMode::Touch) | None => best_bid,
+        };
+        if best_ask > 0 && target >= best_ask {
+            best_bid
+        } else {
+            target.min(99)
+        }
+    }
+
+    /// Contracts still unfilled.
+    pub fn remaining(&self) -> u32 {
+        self.quantity.saturating_sub(self.filled)
+    }
+
+    /// Apply an exchange fill of `count` contracts at `price`. Returns the
+    /// contracts it adds, capped at what was still unfilled.
+    pub fn on_fill(&mut self, count: u32, price: u32) -> u32 {
+        let count = count.min(self.remaining());
+        self.filled += count;
+        self.filled_cost_cents += count * price;
+        count
+    }
+
+    /// Net profit (after maker entry and maker exit fees) of resting the
+    /// unfilled remainder at `price`.
+    pub fn net_profit_at(&self, price: u32) -> i32 {
+        let remaining = self.remaining();
+        let entry_fee = calculate_fee(price, remaining, false) as i32;
+        let exit_fee = calculate_fee(self.fair_value, remaining, false) as i32;
+        (self.fair_value as i32 - price as i32) * remaining as i32 - entry_fee - exit_fee
+    }
+
+    /// Decide whether to hold, re-peg, or cancel.
+    ///
+    /// `best_bid` is the touch without our own order, so it is what others
+    /// bid. The entry re-pegs up when someone bids above its target and down
+    /// when the bids under it fall away, so it never overpays for the queue.
+    /// A re-peg only happens when the net edge at the new price still clears
+    /// `min_net_profit` and the rate limiter allows it. After a partial fill
+    /// the edge is judged on the remainder alone, so a remainder too small to
+    /// be worth chasing is cancelled. An entry that isn't pegged, or that no
+    /// one else bids against, holds.
+    pub fn evaluate(
+        &self,
+        best_bid: u32,
+        best_ask: u32,
+        now: Instant,
+        min_interval: Duration,
+        max_repegs: u32,
+    ) -> PegAction {
+        if self.peg.is_none() || self.remaining() == 0 || best_bid == 0 {
+            return PegAction::Hold;
+        }
+        let target = self.target_price(best_bid, best_ask);
+        if target == self.price {
+            return PegAction::Hold;
+        }
+        if self.net_profit_at(target) < self.min_net_profit {
+            return PegAction::Cancel {
+                reason: "net edge gone at new touch",
+            };
+        }
+        if self.repegs >= max_repegs {
+            return PegAction::Cancel {
+                reason: "re-peg limit reached",
+            };
+        }
+        if now.duration_since(self.last_peg) < min_interval {
+            return PegAction::Hold;
+        }
+        PegAction::Repeg { price: target }
+    }
+
+    /// Record a completed cancel/replace.
+    pub fn record_repeg(&mut self, order_id: String, price: u32, now: Instant) {
+        if !self.owns(&order_id) {
+            self.order_ids.push(order_id.clone());
+        }
+        self.order_id = order_id;
+        self.price = price;
+        self.last_peg = now;
+        self.repegs += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(mode: PegMode, price: u32, fair: u32) -> RestingEntry {
+        RestingEntry::new(
+            "ord-1".to_string(),
+            "TEST".to_string(),
+            "yes".to_string(),
+            1,
+            price,
+            fair,
+            false,
+            Instant::now() - Duration::from_secs(10),
+        )
+        .pegged(mode, fair, 1)
+    }
+
+    #[test]
+    fn test_mode_from_config() {
+        assert_eq!(PegMode::from_config("touch"), PegMode::Touch);
+        assert_eq!(PegMode::from_config("improve"), PegMode::Improve);
+        assert_eq!(PegMode::from_config("anything"), PegMode::Improve);
+    }
+
+    #[test]
+    fn test_target_price_never_crosses_ask() {
+        let o = order(PegMode::Improve, 50, 70);
+        assert_eq!(o.target_price(52, 60), 53);
+        assert_eq!(o.target_price(52, 53), 52);
+        let t = order(PegMode::Touch, 50, 70);
+        assert_eq!(t.target_price(52, 60), 52);
+    }
+
+    #[test]
+    fn test_hold_while_at_touch() {
+        let o = order(PegMode::Improve, 50, 70);
+        let now = Instant::now();
+        assert_eq!(o.evaluate(49, 60, now, Duration::ZERO, 10), PegAction::Hold);
+        assert_eq!(o.evaluate(0, 60, now, Duration::ZERO, 10), PegAction::Hold);
+        let t = order(PegMode::Touch, 50, 70);
+        assert_eq!(t.evaluate(50, 60, now, Duration::ZERO, 10), PegAction::Hold);
+    }
+
+    #[test]
+    fn test_repeg_down_when_bid_falls() {
+        let now = Instant::now();
+        // The bids we improved on fell from 49 to 46: follow them down
+        let o = order(PegMode::Improve, 50, 70);
+        assert_eq!(
+            o.evaluate(46, 60, now, Duration::ZERO, 10),
+            PegAction::Repeg { price: 47 }
+        );
+        let t = order(PegMode::Touch, 50, 70);
+        assert_eq!(
+            t.evaluate(46, 60, now, Duration::ZERO, 10),
+            PegAction::Repeg { price: 46 }
+        );
+    }
+
+    #[test]
+    fn test_repeg_when_outbid() {
+        let o = order(PegMode::Improve, 50, 70);
+        let now = Instant::now();
+        assert_eq!(
+            o.evaluate(52, 60, now, Duration::ZERO, 10),
+            PegAction::Repeg { price: 53 }
+        );
+    }
+
+    #[test]
+    fn test_rate_limiter_holds() {
+        let mut o = order(PegMode::Touch, 50, 70);
+        let now = Instant::now();
+        o.record_repeg("ord-2".to_string(), 51, now);
+        assert_eq!(
+            o.evaluate(53, 60, now, Duration::from_secs(1), 10),
+            PegAction::Hold
+        );
+        assert_eq!(
+            o.evaluate(53, 60, now + Duration::from_secs(2), Duration::from_secs(1), 10),
+            PegAction::Repeg { price: 53 }
+        );
+        assert_eq!(o.order_id, "ord-2");
+        assert_eq!(o.repegs, 1);
+    }
+
+    #[test]
+    fn test_cancel_when_edge_gone() {
+        // Fair 55: resting at 54 nets 1c minus fees -> below min_net_profit
+        let o = order(PegMode::Touch, 50, 55);
+        assert!(matches!(
+            o.evaluate(54, 56, Instant::now(), Duration::ZERO, 10),
+            PegAction::Cancel { .. }
+        ));
+    }
+
+    #[test]
+    fn test_partial_fills_chase_or_cancel_remainder() {
+        let mut o = order(PegMode::Touch, 50, 60);
+        o.quantity = 10;
+        assert_eq!(o.on_fill(4, 50), 4);
+        o.record_repeg("ord-2".to_string(), 52, Instant::now());
+        assert_eq!(o.on_fill(2, 52), 2);
+        assert_eq!((o.filled, o.remaining()), (6, 4));
+        assert_eq!(o.filled_cost_cents, 304);
+
+        // 4 left at 55 against fair 60 still clears the bar: chase
+        let now = Instant::now() + Duration::from_secs(1);
+        assert_eq!(
+            o.evaluate(54, 60, now, Duration::ZERO, 10),
+            PegAction::Repeg { price: 54 }
+        );
+        // A 30c bar that all 10 at 6c edge would clear is too much for 4
+        o.min_net_profit = 30;
+        assert!(o.net_profit_at(54) < 30);
+        assert!(matches!(
+            o.evaluate(54, 60, now, Duration::ZERO, 10),
+            PegAction::Cancel { .. }
+        ));
+
+        // Overfills are capped and a filled order needs no maintenance
+        assert_eq!(o.on_fill(9, 52), 4);
+        assert_eq!(o.filled, 10);
+        assert_eq!(o.evaluate(58, 60, now, Duration::ZERO, 10), PegAction::Hold);
+    }
+
+    #[test]
+    fn test_fills_match_every_order_id_the_entry_rested_under() {
+        let mut o = order(PegMode::Touch, 50, 70);
+        o.record_repeg("ord-2".to_string(), 51, Instant::now());
+        o.record_repeg("ord-3".to_string(), 52, Instant::now());
+        assert_eq!(o.order_id, "ord-3");
+        // A fill on the order replaced two re-pegs ago still belongs here
+        assert!(o.owns("ord-1") && o.owns("ord-2") && o.owns("ord-3"));
+        assert!(!o.owns("ord-4"));
+    }
+
+    #[test]
+    fn test_unpegged_entry_rests_at_its_price() {
+        let mut o = RestingEntry::new(
+            "ord-1".to_string(),
+            "TEST".to_string(),
+            "yes".to_string(),
+            5,
+            50,
+            60,
+            true,
+            Instant::now(),
+        );
+        assert_eq!(
+            o.evaluate(55, 60, Instant::now(), Duration::ZERO, 10),
+            PegAction::Hold
+        );
+        assert_eq!(o.on_fill(2, 50), 2);
+        assert_eq!(o.remaining(), 3);
+    }
+
+    #[test]
+    fn test_cancel_after_max_repegs() {
+        let mut o = order(PegMode::Touch, 50, 80);
+        o.repegs = 3;
+        assert!(matches!(
+            o.evaluate(52, 60, Instant::now(), Duration::ZERO, 3),
+            PegAction::Cancel { .. }
+        ));
+    }
+}
@@ -0,0 +1,186 @@
+use super::{OrderExecutor, PlacedOrder};
+use crate::kalshi::types::TimeInForce;
+use std::time::Instant;
+
+/// Resting price for the preflight order: a 1-lot YES bid at 1c.
+pub const PREFLIGHT_PRICE: u32 = 1;
+/// Only tickers whose best YES bid is at least this high are used, so the 1c
+/// preflight bid rests far below the market and cannot fill.
+pub const MIN_PREFLIGHT_BID: u32 = 10;
+
+/// One stage of the live-mode order-path preflight.
+#[derive(Debug, Clone)]
+pub struct PreflightStep {
+    pub name: &'static str,
+    pub ok: bool,
+    pub latency_ms: Option<u64>,
+    pub detail: String,
+}
+
+/// Result of placing and cancelling a far-from-market order before trading.
+#[derive(Debug, Clone)]
+pub struct PreflightReport {
+    pub ticker: String,
+    pub price: u32,
+    pub ran_at: chrono::DateTime<chrono::Local>,
+    pub steps: Vec<PreflightStep>,
+}
+
+impl PreflightReport {
+    pub fn passed(&self) -> bool {
+        !self.steps.is_empty() && self.steps.iter().all(|s| s.ok)
+    }
+
+    /// Latency of a named step, if it ran.
+    pub fn latency_ms(&self, name: &str) -> Option<u64> {
+        self.steps
+            .iter()
+            .find(|s| s.name == name)
+            .and_then(|s| s.latency_ms)
+    }
+
+    /// First failed step, if any.
+    pub fn failure(&self) -> Option<&PreflightStep> {
+        self.steps.iter().find(|s| !s.ok)
+    }
+}
+
+/// Pick the most liquid ticker whose best YES bid is far enough above 1c.
+/// Candidates are `(ticker, best_yes_bid, resting_depth)`.
+pub fn pick_preflight_ticker<'a>(
+    candidates: impl IntoIterator<Item = (&'a str, u32, i64)>,
+) -> Option<&'a str> {
+    candidates
+        .into_iter()
+        .filter(|&(_, bid, _)| bid >= MIN_PREFLIGHT_BID)
+        .max_by_key(|&(ticker, _, depth)| (depth, std::cmp::Reverse(ticker)))
+        .map(|(ticker, _, _)| ticker)
+}
+
+/// Place a 1-lot maker bid at 1c on `ticker` and cancel it immediately,
+/// verifying auth, trading permissions, and both order paths.
+pub async fn run_preflight(executor: &OrderExecutor, ticker: &str) -> PreflightReport {
+    let mut steps = Vec::new();
+
+    let started = Instant::now();
+    let submit = executor
+        .submit_order(
+            ticker,
+            1,
+            PREFLIGHT_PRICE,
+            true,
+            false,
+            "yes",
+            TimeInForce::GoodTillCanceled,
+        )
+        .await;
+    let submit_ms = started.elapsed().as_millis() as u64;
+
+    let order_id = match submit {
+        Ok(Some(PlacedOrder { order_id, .. })) => {
+            steps.push(PreflightStep {
+                name: "submit",
+                ok: true,
+                latency_ms: Some(submit_ms),
+                detail: order_id.clone(),
+            });
+            Some(order_id)
+        }
+        Ok(None) => {
+            steps.push(PreflightStep {
+                name: "submit",
+                ok: false,
+                latency_ms: Some(submit_ms),
+                detail: "no order id returned".to_string(),
+            });
+            None
+        }
+        Err(e) => {
+            steps.push(PreflightStep {
+                name: "submit",
+                ok: false,
+                latency_ms: Some(submit_ms),
+                detail: format!("{:#}", e),
+            });
+            None
+        }
+    };
+
+    if let Some(order_id) = order_id {
+        let started = Instant::now();
+        let cancel = executor.cancel_order(&order_id).await;
+        let cancel_ms = started.elapsed().as_millis() as u64;
+        steps.push(match cancel {
+            Ok(()) => PreflightStep {
+                name: "cancel",
+                ok: true,
+                latency_ms: Some(cancel_ms),
+                detail: String::new(),
+            },
+            Err(e) => PreflightStep {
+                name: "cancel",
+                ok: false,
+                latency_ms: Some(cancel_ms),
+                detail: format!("order {} may still be resting: {:#}", order_id, e),
+            },
+        });
+    }
+
+    PreflightReport {
+        ticker: ticker.to_string(),
+        price: PREFLIGHT_PRICE,
+        ran_at: chrono::Local::now(),
+        steps,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(name: &'static str, ok: bool) -> PreflightStep {
+        PreflightStep {
+            name,
+            ok,
+            latency_ms: Some(100),
+            detail: String::new(),
+        }
+    }
+
+    fn report(steps: Vec<PreflightStep>) -> PreflightReport {
+        PreflightReport {
+            ticker: "TEST".to_string(),
+            price: PREFLIGHT_PRICE,
+            ran_at: chrono::Local::now(),
+            steps,
+        }
+    }
+
+    #[test]
+    fn test_pick_most_liquid_ticker_far_from_price() {
+        let candidates = vec![("A", 50, 100), ("B", 45, 900), ("C", 3, 5000)];
+        assert_eq!(pick_preflight_ticker(candidates), Some("B"));
+    }
+
+    #[test]
+    fn test_pick_none_when_all_bids_near_one_cent() {
+        let candidates = vec![("A", 2, 100), ("B", 9, 900)];
+        assert_eq!(pick_preflight_ticker(candidates), None);
+    }
+
+    #[test]
+    fn test_report_passes_only_when_all_steps_ok() {
+        assert!(report(vec![step("submit", true), step("cancel", true)]).passed());
+        let failed = report(vec![step("submit", true), step("cancel", false)]);
+        assert!(!failed.passed());
+        assert_eq!(failed.failure().map(|s| s.name), Some("cancel"));
+        assert!(!report(Vec::new()).passed());
+    }
+
+    #[test]
+    fn test_step_latency_lookup() {
+        let r = report(vec![step("submit", true)]);
+        assert_eq!(r.latency_ms("submit"), Some(100));
+        assert_eq!(r.latency_ms("cancel"), None);
+    }
+}
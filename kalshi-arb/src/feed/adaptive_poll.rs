@@ -0,0 +1,144 @@
+//! Adaptive live poll intervals.
+//!
+//! A close game in its final minutes can swing on every possession, while a
+//! blowout barely moves. Each live game is classified by closeness and time
+//! remaining, and the most volatile game sets the sport's live poll interval
+//! as a multiple of the configured `live_poll_s`.
+
+use crate::config::{AdaptivePollConfig, SprintConfig};
+use std::time::Duration;
+
+/// How fast a game's fair value can move, least to most volatile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Volatility {
+    Blowout,
+    Normal,
+    Close,
+    /// Close game inside the final window.
+    Critical,
+}
+
+impl Volatility {
+    pub fn label(self) -> &'static str {
+        match self {
+            Volatility::Blowout => "blowout",
+            Volatility::Normal => "normal",
+            Volatility::Close => "close",
+            Volatility::Critical => "critical",
+        }
+    }
+}
+
+/// Classify a live game from its score margin and regulation time remaining
+/// (zero in overtime).
+pub fn score_volatility(cfg: &AdaptivePollConfig, margin: u32, remaining_secs: u32) -> Volatility {
+    if margin >= cfg.blowout_margin {
+        Volatility::Blowout
+    } else if margin <= cfg.close_margin {
+        if remaining_secs <= cfg.final_window_secs {
+            Volatility::Critical
+        } else {
+            Volatility::Close
+        }
+    } else {
+        Volatility::Normal
+    }
+}
+
+/// Classify a live game from the devigged home win probability, for feeds
+/// that report no score or clock.
+pub fn odds_volatility(cfg: &AdaptivePollConfig, home_prob: f64) -> Volatility {
+    let favorite = home_prob.max(1.0 - home_prob);
+    if favorite >= cfg.blowout_prob {
+        Volatility::Blowout
+    } else if favorite <= 0.5 + cfg.close_prob_margin {
+        Volatility::Close
+    } else {
+        Volatility::Normal
+    }
+}
+
+/// Live poll interval for `base` scaled by `volatility`. Never drops below
+/// `min_interval_ms` unless the base interval itself is shorter.
+pub fn live_interval(cfg: &AdaptivePollConfig, base: Duration, volatility: Volatility) -> Duration {
+    if !cfg.enabled {
+        return base;
+    }
+    let factor = match volatility {
+        Volatility::Blowout => cfg.blowout_factor,
+        Volatility::Normal => cfg.normal_factor,
+        Volatility::Close => 1.0,
+        Volatility::Critical => cfg.critical_factor,
+    };
+    let floor = Duration::from_millis(cfg.min_interval_ms).min(base);
+    base.mul_f64(factor.max(0.0)).max(floor)
+}
+
+/// Whether a live game is in its end-of-game sprint: close, with little time
+/// left in regulation or the current overtime.
+pub fn in_sprint(cfg: &SprintConfig, margin: u32, remaining_secs: u32) -> bool {
+    cfg.enabled && margin <= cfg.max_margin && remaining_secs <= cfg.final_secs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_volatility_tiers() {
+        let cfg = AdaptivePollConfig::default();
+        assert_eq!(score_volatility(&cfg, 2, 120), Volatility::Critical);
+        assert_eq!(score_volatility(&cfg, 2, 1200), Volatility::Close);
+        assert_eq!(score_volatility(&cfg, 12, 120), Volatility::Normal);
+        assert_eq!(score_volatility(&cfg, 25, 120), Volatility::Blowout);
+        // Overtime counts as the final window.
+        assert_eq!(score_volatility(&cfg, 0, 0), Volatility::Critical);
+    }
+
+    #[test]
+    fn test_odds_volatility_tiers() {
+        let cfg = AdaptivePollConfig::default();
+        assert_eq!(odds_volatility(&cfg, 0.55), Volatility::Close);
+        assert_eq!(odds_volatility(&cfg, 0.25), Volatility::Normal);
+        assert_eq!(odds_volatility(&cfg, 0.04), Volatility::Blowout);
+    }
+
+    #[test]
+    fn test_live_interval_scaling() {
+        let cfg = AdaptivePollConfig::default();
+        let base = Duration::from_secs(20);
+        assert_eq!(live_interval(&cfg, base, Volatility::Close), base);
+        assert_eq!(
+            live_interval(&cfg, base, Volatility::Critical),
+            Duration::from_secs(10)
+        );
+        assert_eq!(
+            live_interval(&cfg, base, Volatility::Blowout),
+            Duration::from_secs(80)
+        );
+        // Floors at min_interval_ms, but never above the base interval.
+        let one = Duration::from_secs(1);
+        assert_eq!(
+            live_interval(&cfg, one, Volatility::Critical),
+            Duration::from_millis(cfg.min_interval_ms)
+        );
+        let disabled = AdaptivePollConfig {
+            enabled: false,
+            ..AdaptivePollConfig::default()
+        };
+        assert_eq!(live_interval(&disabled, base, Volatility::Blowout), base);
+    }
+
+    #[test]
+    fn test_in_sprint() {
+        let cfg = SprintConfig::default();
+        assert!(in_sprint(&cfg, 3, 90));
+        assert!(!in_sprint(&cfg, 3, 300));
+        assert!(!in_sprint(&cfg, 9, 90));
+        let disabled = SprintConfig {
+            enabled: false,
+            ..SprintConfig::default()
+        };
+        assert!(!in_sprint(&disabled, 0, 10));
+    }
+}
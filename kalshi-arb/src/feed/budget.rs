@@ -0,0 +1,218 @@
+//! Odds API quota budgeting across sports.
+//!
+//! A quota-reporting source's remaining credits, less a reserve, are spread
+//! over `slate_hours`. Each sport polling the source asks for the rate its
+//! cadence wants; when the credits can't cover every ask they are shared by
+//! weight: live games, scaled up by the edge the sport has been showing.
+//! Sports whose ask is below their share get their full rate and the rest is
+//! split among the others (water-filling). The budget only ever slows polls.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::config::QuotaBudgetConfig;
+
+/// What one sport wants from a source this cycle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PollDemand {
+    /// Games in progress. Sports with none still weigh as one game.
+    pub live_games: usize,
+    /// Mean positive edge of the sport's markets last cycle, in cents.
+    pub edge_cents: f64,
+}
+
+#[derive(Debug, Clone)]
+struct SportDemand {
+    demand: PollDemand,
+    /// Interval the sport's cadence asks for.
+    wanted: Duration,
+    /// Credits one request for this sport costs.
+    credits: u64,
+    at: Instant,
+}
+
+/// Per-source budget. Demand older than `slate_hours` is forgotten, so a
+/// sport that was switched off stops holding a share.
+#[derive(Debug, Clone)]
+pub struct QuotaBudget {
+    config: QuotaBudgetConfig,
+    sports: HashMap<String, SportDemand>,
+}
+
+impl QuotaBudget {
+    pub fn new(config: QuotaBudgetConfig) -> Self {
+        Self {
+            config,
+            sports: HashMap::new(),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Record what `sport` wants, keeping its last known request cost.
+    pub fn set_demand(&mut self, sport: &str, demand: PollDemand, wanted: Duration, now: Instant) {
+        let entry = self
+            .sports
+            .entry(sport.to_string())
+            .or_insert_with(|| SportDemand {
+                demand,
+                wanted,
+                credits: 1,
+                at: now,
+            });
+        entry.demand = demand;
+        entry.wanted = wanted;
+        entry.at = now;
+        let forget = Duration::from_secs_f64(self.config.slate_hours.max(0.0) * 3600.0);
+        self.sports
+            .retain(|_, d| now.saturating_duration_since(d.at) <= forget);
+    }
+
+    /// Record the credits a request for `sport` cost.
+    pub fn record_cost(&mut self, sport: &str, credits: u64) {
+        if let Some(d) = self.sports.get_mut(sport) {
+            d.credits = credits.max(1);
+        }
+    }
+
+    /// Budgeted interval for `sport` with `remaining` credits left: never
+    /// shorter than what it asked for, never longer than `max_interval_s`.
+    pub fn interval(&self, sport: &str, remaining: u64) -> Option<Duration> {
+        let own = self.sports.get(sport)?;
+        let per_hour = remaining.saturating_sub(self.config.reserve) as f64
+            / self.config.slate_hours.max(1.0 / 60.0);
+
+        let mut names: Vec<&String> = self.sports.keys().collect();
+        names.sort();
+        let asks: Vec<(f64, f64)> = names
+            .iter()
+            .map(|name| {
+                let d = &self.sports[*name];
+                (self.weight(&d.demand), hourly_credits(d.credits, d.wanted))
+            })
+            .collect();
+        let rates = water_fill(per_hour, &asks);
+        let idx = names.iter().position(|n| n.as_str() == sport)?;
+
+        let max = Duration::from_secs(self.config.max_interval_s).max(own.wanted);
+        let rate = rates[idx];
+        if rate <= 0.0 {
+            return Some(max);
+        }
+        let secs = own.credits as f64 * 3600.0 / rate;
+        Some(Duration::from_secs_f64(secs).clamp(own.wanted, max))
+    }
+
+    fn weight(&self, demand: &PollDemand) -> f64 {
+        demand.live_games.max(1) as f64
+            * (1.0 + self.config.edge_weight * demand.edge_cents.max(0.0))
+    }
+}
+
+/// Credits per hour a sport burns polling every `interval`.
+fn hourly_credits(credits: u64, interval: Duration) -> f64 {
+    credits as f64 * 3600.0 / interval.as_secs_f64().max(0.001)
+}
+
+/// Split `budget` by weight, capping each share at its ask and handing what
+/// capped shares leave over to the rest. `asks` are (weight, ask) pairs.
+fn water_fill(budget: f64, asks: &[(f64, f64)]) -> Vec<f64> {
+    let mut rates = vec![0.0; asks.len()];
+    let mut open: Vec<usize> = (0..asks.len()).collect();
+    let mut left = budget.max(0.0);
+    while !open.is_empty() && left > 0.0 {
+        let total: f64 = open.iter().map(|&i| asks[i].0).sum();
+        if total <= 0.0 {
+            break;
+        }
+        let capped: Vec<usize> = open
+            .iter()
+            .copied()
+            .filter(|&i| left * asks[i].0 / total >= asks[i].1)
+            .collect();
+        if capped.is_empty() {
+            for &i in &open {
+                rates[i] = left * asks[i].0 / total;
+            }
+            break;
+        }
+        for &i in &capped {
+            rates[i] = asks[i].1;
+            left -= asks[i].1;
+        }
+        open.retain(|i| !capped.contains(i));
+    }
+    rates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> QuotaBudgetConfig {
+        QuotaBudgetConfig {
+            enabled: true,
+            slate_hours: 1.0,
+            reserve: 0,
+            edge_weight: 0.5,
+            max_interval_s: 900,
+        }
+    }
+
+    fn demand(live_games: usize, edge_cents: f64) -> PollDemand {
+        PollDemand {
+            live_games,
+            edge_cents,
+        }
+    }
+
+    #[test]
+    fn test_water_fill_hands_capped_surplus_to_the_rest() {
+        let rates = water_fill(100.0, &[(1.0, 10.0), (1.0, 200.0), (2.0, 200.0)]);
+        assert_eq!(rates[0], 10.0);
+        assert_eq!(rates[1], 30.0);
+        assert_eq!(rates[2], 60.0);
+        assert_eq!(water_fill(0.0, &[(1.0, 10.0)]), vec![0.0]);
+    }
+
+    #[test]
+    fn test_plenty_of_quota_leaves_intervals_alone() {
+        let now = Instant::now();
+        let mut budget = QuotaBudget::new(config());
+        budget.set_demand("nba", demand(5, 3.0), Duration::from_secs(20), now);
+        assert_eq!(
+            budget.interval("nba", 10_000),
+            Some(Duration::from_secs(20))
+        );
+        assert_eq!(budget.interval("nhl", 10_000), None);
+    }
+
+    #[test]
+    fn test_scarce_quota_goes_to_live_games_and_edge() {
+        let now = Instant::now();
+        let mut budget = QuotaBudget::new(config());
+        let live = Duration::from_secs(10);
+        // 180 credits for the hour; each sport wants 360
+        budget.set_demand("nba", demand(3, 0.0), live, now);
+        budget.set_demand("nhl", demand(1, 0.0), live, now);
+        let nba = budget.interval("nba", 180).unwrap();
+        let nhl = budget.interval("nhl", 180).unwrap();
+        // 135 and 45 credits an hour
+        assert_eq!(nba.as_secs(), 26);
+        assert_eq!(nhl.as_secs(), 80);
+
+        // Edge on the NHL side evens the split out: weights 3 and 1 × (1 + 0.5 × 4)
+        budget.set_demand("nhl", demand(1, 4.0), live, now);
+        assert_eq!(budget.interval("nba", 180), budget.interval("nhl", 180));
+
+        // Nothing left over the reserve: poll at the ceiling
+        let mut budget = QuotaBudget::new(QuotaBudgetConfig {
+            reserve: 200,
+            ..config()
+        });
+        budget.set_demand("nba", demand(3, 0.0), live, now);
+        assert_eq!(budget.interval("nba", 180), Some(Duration::from_secs(900)));
+    }
+}
@@ -0,0 +1,154 @@
+//! Update-cadence detection for polled feeds.
+//!
+//! Scoreboard CDNs regenerate their JSON on a roughly fixed cadence. Polling on
+//! an unrelated fixed interval means a fresh update can sit unseen for up to a
+//! full interval. `CadenceTracker` records when polled content actually changed,
+//! estimates the refresh period and phase, and predicts the next refresh so the
+//! poller can fire just after it.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Minimum inter-change gaps before a cadence is trusted.
+const MIN_GAPS: usize = 4;
+/// Change timestamps kept for estimation.
+const MAX_CHANGES: usize = 16;
+/// Periods shorter than this are indistinguishable from our own poll jitter.
+const MIN_PERIOD: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Default)]
+pub struct CadenceTracker {
+    changes: VecDeque<Instant>,
+}
+
+impl CadenceTracker {
+    /// Record that polled content changed at `at`.
+    pub fn record_change(&mut self, at: Instant) {
+        if self.changes.back().is_some_and(|&last| at <= last) {
+            return;
+        }
+        if self.changes.len() >= MAX_CHANGES {
+            self.changes.pop_front();
+        }
+        self.changes.push_back(at);
+    }
+
+    /// Estimated refresh period.
+    ///
+    /// The median gap between observed changes gives a rough period (robust to a
+    /// missed refresh), which is then refined by spreading the whole observed span
+    /// over the nearest whole number of periods to average out poll-lag jitter.
+    pub fn period(&self) -> Option<Duration> {
+        if self.changes.len() < MIN_GAPS + 1 {
+            return None;
+        }
+        let mut gaps: Vec<Duration> = self
+            .changes
+            .iter()
+            .zip(self.changes.iter().skip(1))
+            .map(|(a, b)| *b - *a)
+            .collect();
+        gaps.sort_unstable();
+        let median = gaps[gaps.len() / 2];
+        if median < MIN_PERIOD {
+            return None;
+        }
+        let span = *self.changes.back()? - *self.changes.front()?;
+        let periods = (span.as_secs_f64() / median.as_secs_f64()).round().max(1.0);
+        let period = span.div_f64(periods);
+        (period >= MIN_PERIOD).then_some(period)
+    }
+
+    /// Predicted first refresh strictly after `after`.
+    ///
+    /// Changes are only observed when we poll, so every timestamp lags the true
+    /// refresh by somewhere between zero and one poll interval. The phase is
+    /// therefore taken from the earliest-observed change relative to the cadence
+    /// grid, which is the closest estimate of when refreshes really happen.
+    pub fn next_refresh_after(&self, after: Instant) -> Option<Instant> {
+        let period_ns = self.period()?.as_nanos() as i128;
+        let origin = *self.changes.front()?;
+        let ns = |t: Instant| t.saturating_duration_since(origin).as_nanos() as i128;
+        let anchor = ns(*self.changes.back()?);
+
+        // Offset of each observation from the anchor on the cadence grid,
+        // wrapped into (-period/2, period/2].
+        let phase = self
+            .changes
+            .iter()
+            .map(|&c| {
+                let r = (ns(c) - anchor).rem_euclid(period_ns);
+                if r > period_ns / 2 {
+                    r - period_ns
+                } else {
+                    r
+                }
+            })
+            .min()
+            .unwrap_or(0);
+
+        let base = anchor + phase;
+        let k = (ns(after) - base).div_euclid(period_ns) + 1;
+        let target = base + k * period_ns;
+        Some(origin + Duration::from_nanos(target.max(0) as u64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker_with(start: Instant, offsets_ms: &[u64]) -> CadenceTracker {
+        let mut t = CadenceTracker::default();
+        for &ms in offsets_ms {
+            t.record_change(start + Duration::from_millis(ms));
+        }
+        t
+    }
+
+    #[test]
+    fn test_no_cadence_until_enough_changes() {
+        let start = Instant::now();
+        let t = tracker_with(start, &[0, 5000, 10000, 15000]);
+        assert!(t.period().is_none());
+        assert!(t.next_refresh_after(start).is_none());
+    }
+
+    #[test]
+    fn test_period_is_median_gap() {
+        let start = Instant::now();
+        // One gap doubled by a missed refresh should not skew the estimate.
+        let t = tracker_with(start, &[0, 5000, 10000, 20000, 25000, 30000]);
+        assert_eq!(t.period(), Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_next_refresh_uses_earliest_observed_phase() {
+        let start = Instant::now();
+        // True refreshes every 5s at t=0,5,10,...; observed with 0-900ms poll lag.
+        let t = tracker_with(start, &[100, 5900, 10400, 15000, 20700, 25300]);
+        let period = t.period().unwrap();
+        assert!(period >= Duration::from_millis(4900) && period <= Duration::from_millis(5100));
+        let next = t.next_refresh_after(start + Duration::from_millis(25300)).unwrap();
+        let next_ms = next.duration_since(start).as_millis() as i64;
+        // Expect the refresh near t=30s (not 30.7s, the latest observed lag)
+        assert!((29_500..=30_600).contains(&next_ms), "got {next_ms}");
+    }
+
+    #[test]
+    fn test_next_refresh_is_strictly_after() {
+        let start = Instant::now();
+        let t = tracker_with(start, &[0, 2000, 4000, 6000, 8000]);
+        let next = t.next_refresh_after(start + Duration::from_millis(8000)).unwrap();
+        assert_eq!(next, start + Duration::from_millis(10_000));
+        let later = t.next_refresh_after(start + Duration::from_millis(13_500)).unwrap();
+        assert_eq!(later, start + Duration::from_millis(14_000));
+    }
+
+    #[test]
+    fn test_tiny_periods_ignored() {
+        let start = Instant::now();
+        let t = tracker_with(start, &[0, 100, 200, 300, 400, 500]);
+        assert!(t.period().is_none());
+    }
+}
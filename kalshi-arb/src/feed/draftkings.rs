@@ -3,42 +3,310 @@ use super::OddsFeed;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use reqwest::Client;
+use std::collections::{HashMap, HashSet};
 use std::time::{Duration, Instant};
 
-const DK_BASE_URL: &str = "https://sportsbook-nash.draftkings.com/sites/US-SB/api/v5/eventgroups";
+pub const DK_BASE_URL: &str =
+    "https://sportsbook-nash.draftkings.com/sites/US-SB/api/v5/eventgroups";
+
+/// Where an event group keeps its full-game moneylines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct MoneylinePath {
+    category_id: u64,
+    subcategory_id: u64,
+}
+
+/// Outcome of one conditional GET.
+enum Fetched {
+    Body(DkResponse),
+    NotModified,
+    NotFound,
+}
 
 pub struct DraftKingsFeed {
     client: Client,
+    base_url: String,
     poll_interval: Duration,
     configured_poll_interval: Duration,
     #[allow(dead_code)]
     pre_game_poll_interval: Duration,
+    /// Event group ids from config, over the built-in table.
+    event_groups: HashMap<String, u64>,
+    /// Moneyline location per event group, discovered on first use.
+    paths: HashMap<u64, MoneylinePath>,
     last_fetch: Option<Instant>,
-    last_etag: Option<String>,
+    /// ETag and parsed updates of the last response per URL, replayed on 304.
+    etags: HashMap<String, String>,
+    last_updates: HashMap<String, Vec<OddsUpdate>>,
 }
 
-/// Map internal sport key to DraftKings (event_group_id, category_id, subcategory_id).
-/// Basketball only for now.
-fn dk_event_group(sport: &str) -> Option<(u64, u64, u64)> {
+/// Map internal sport key to its DraftKings event group id.
+fn dk_event_group(sport: &str) -> Option<u64> {
     match sport {
-        "basketball" => Some((42648, 487, 4518)),
+        "basketball" => Some(42648),
+        "college-basketball" => Some(92483),
+        "college-basketball-womens" => Some(36647),
+        "ice-hockey" => Some(42133),
+        "american-football" => Some(88808),
+        "college-football" => Some(87637),
+        "baseball" => Some(84240),
+        "soccer-epl" => Some(40253),
+        "mma" => Some(9034),
         _ => None,
     }
 }
 
 /// Parse DraftKings American odds string to f64.
-/// Handles "+150", "-180", "EVEN" (= +100).
+/// Handles "+150", "-180", "EVEN" (= +100), and the Unicode minus sign
+/// DraftKings sometimes sends.
 fn parse_american_odds(s: &str) -> Option<f64> {
-    let s = s.trim();
+    let s = s.trim().replace('\u{2212}', "-");
     if s.eq_ignore_ascii_case("EVEN") {
         return Some(100.0);
     }
     s.parse::<f64>().ok()
 }
 
+/// City for a DraftKings team-name prefix ("GS", "KC", "NY", ...).
+fn dk_city(sport: &str, abbr: &str) -> Option<&'static str> {
+    Some(match abbr {
+        "ANA" => "Anaheim",
+        "ARI" | "ARZ" => "Arizona",
+        "ATL" => "Atlanta",
+        "BAL" => "Baltimore",
+        "BKN" | "BRK" => "Brooklyn",
+        "BOS" => "Boston",
+        "BUF" => "Buffalo",
+        "CAR" => "Carolina",
+        "CBJ" | "CLB" => "Columbus",
+        "CGY" | "CAL" => "Calgary",
+        "CHA" => "Charlotte",
+        "CHI" => "Chicago",
+        "CIN" => "Cincinnati",
+        "CLE" => "Cleveland",
+        "COL" => "Colorado",
+        "DAL" => "Dallas",
+        "DEN" => "Denver",
+        "DET" => "Detroit",
+        "EDM" => "Edmonton",
+        "FLA" => "Florida",
+        "GB" => "Green Bay",
+        "GS" => "Golden State",
+        "HOU" => "Houston",
+        "IND" if sport == "american-football" => "Indianapolis",
+        "IND" => "Indiana",
+        "JAX" | "JAC" => "Jacksonville",
+        "KC" => "Kansas City",
+        "LA" => "Los Angeles",
+        "LV" => "Las Vegas",
+        "MEM" => "Memphis",
+        "MIA" => "Miami",
+        "MIL" => "Milwaukee",
+        "MIN" => "Minnesota",
+        "MTL" | "MON" => "Montreal",
+        "NE" => "New England",
+        "NJ" => "New Jersey",
+        "NO" => "New Orleans",
+        "NSH" | "NAS" => "Nashville",
+        "NY" => "New York",
+        "OAK" => "Oakland",
+        "OKC" => "Oklahoma City",
+        "ORL" => "Orlando",
+        "OTT" => "Ottawa",
+        "PHI" => "Philadelphia",
+        "PHO" | "PHX" => "Phoenix",
+        "PIT" => "Pittsburgh",
+        "POR" => "Portland",
+        "SA" => "San Antonio",
+        "SAC" => "Sacramento",
+        "SD" => "San Diego",
+        "SEA" => "Seattle",
+        "SF" => "San Francisco",
+        "SJ" => "San Jose",
+        "STL" => "St. Louis",
+        "TB" => "Tampa Bay",
+        "TEN" => "Tennessee",
+        "TEX" => "Texas",
+        "TOR" => "Toronto",
+        "UTA" | "UTAH" => "Utah",
+        "VAN" => "Vancouver",
+        "VGK" | "VEG" => "Vegas",
+        "WAS" | "WSH" => "Washington",
+        "WPG" | "WIN" => "Winnipeg",
+        _ => return None,
+    })
+}
+
+/// Rewrite a DraftKings team name into the form the other feeds use, so the
+/// matcher normalizes both alike. Pro teams come as "<city abbreviation>
+/// <nickname>" ("GS Warriors", "KC Chiefs") and get their city back; EPL
+/// sides come shortened ("Man City", "Nottm Forest").
+pub fn dk_team_name(sport: &str, name: &str) -> String {
+    let name = name.trim();
+    match sport {
+        "basketball" | "ice-hockey" | "american-football" | "baseball" => name
+            .split_once(' ')
+            .and_then(|(abbr, nickname)| {
+                Some(format!("{} {}", dk_city(sport, abbr)?, nickname.trim()))
+            })
+            .unwrap_or_else(|| name.to_string()),
+        s if s.starts_with("soccer") => match name {
+            "Man City" => "Manchester City",
+            "Man Utd" | "Man United" => "Manchester United",
+            "Nottm Forest" | "Nott'm Forest" => "Nottingham Forest",
+            "Spurs" => "Tottenham Hotspur",
+            "Leeds" => "Leeds United",
+            "Brighton & Hove Albion" => "Brighton and Hove Albion",
+            other => other,
+        }
+        .to_string(),
+        _ => name.to_string(),
+    }
+}
+
+/// Find the full-game moneyline subcategory in an event group's category
+/// list: "Game Lines" > "Game" for team sports, "Fight Lines" > "Moneyline"
+/// for MMA. A subcategory named for the moneyline beats a generic "Game".
+fn find_moneyline_path(group: &DkEventGroup) -> Option<MoneylinePath> {
+    let score = |name: &str| {
+        let name = name.to_lowercase();
+        if name.contains("moneyline") {
+            3
+        } else if matches!(name.as_str(), "game" | "match" | "fight lines") {
+            2
+        } else {
+            0
+        }
+    };
+    group
+        .offer_categories
+        .iter()
+        .filter(|c| {
+            let name = c.name.to_lowercase();
+            name.contains("lines") || name.contains("moneyline")
+        })
+        .flat_map(|c| {
+            c.offer_subcategory_descriptors
+                .iter()
+                .map(move |d| (score(&d.name), c.offer_category_id, d.subcategory_id))
+        })
+        .filter(|(score, _, _)| *score > 0)
+        // max_by_key keeps the last of equals; reverse so the first wins
+        .rev()
+        .max_by_key(|(score, _, _)| *score)
+        .map(|(_, category_id, subcategory_id)| MoneylinePath {
+            category_id,
+            subcategory_id,
+        })
+}
+
+/// Whether an offer is a full-game moneyline: labelled as one, or (in
+/// responses without labels) priced with no handicap or total.
+fn is_moneyline(offer: &DkOffer) -> bool {
+    let label = offer.label.to_lowercase();
+    let moneyline_label = label.is_empty()
+        || label.contains("moneyline")
+        || matches!(
+            label.as_str(),
+            "match result" | "full time result" | "fight winner"
+        );
+    moneyline_label && offer.outcomes.iter().all(|o| o.line.is_none())
+}
+
+/// Home and away team of an event as DraftKings names them. US events are
+/// "Away @ Home" with `teamName1` the away side; soccer and MMA are
+/// "Home vs Away" (MMA has no home side, the first fighter stands in).
+fn event_teams(event: &DkEvent) -> Option<(String, String)> {
+    let away_first = event.name.contains(" @ ");
+    let (first, second) = match (&event.team_name1, &event.team_name2) {
+        (Some(a), Some(b)) if !a.is_empty() && !b.is_empty() => (a.clone(), b.clone()),
+        _ => {
+            let (a, b) = event
+                .name
+                .split_once(" @ ")
+                .or_else(|| event.name.split_once(" vs "))
+                .or_else(|| event.name.split_once(" v "))?;
+            (a.trim().to_string(), b.trim().to_string())
+        }
+    };
+    Some(if away_first {
+        (second, first)
+    } else {
+        (first, second)
+    })
+}
+
+/// Full-game moneylines in an event group response, pre-game and in play.
+/// Suspended offers (common in play, around scores) are left out, as are
+/// finished events. Team names are normalized with `dk_team_name`.
+fn parse_moneylines(sport: &str, group: &DkEventGroup, now: &str) -> Vec<OddsUpdate> {
+    let events: HashMap<u64, &DkEvent> = group.events.iter().map(|e| (e.event_id, e)).collect();
+    let offers = group.offer_categories.iter().flat_map(|c| {
+        c.offers.iter().chain(
+            c.offer_subcategory_descriptors
+                .iter()
+                .filter_map(|d| d.offer_subcategory.as_ref())
+                .flat_map(|s| &s.offers),
+        )
+    });
+
+    let mut seen = HashSet::new();
+    let mut updates = Vec::new();
+    for offer in offers.flatten() {
+        if offer.is_suspended || !offer.is_open || !is_moneyline(offer) {
+            continue;
+        }
+        let Some(event) = events.get(&offer.event_id) else {
+            continue;
+        };
+        if event
+            .event_status
+            .as_ref()
+            .is_some_and(|s| s.state == "FINISHED")
+        {
+            continue;
+        }
+        let Some((home, away)) = event_teams(event) else {
+            continue;
+        };
+        let price = |pred: &dyn Fn(&str) -> bool| {
+            offer
+                .outcomes
+                .iter()
+                .find(|o| pred(o.label.trim()))
+                .and_then(|o| parse_american_odds(&o.odds_american))
+        };
+        let home_odds = price(&|label| label.eq_ignore_ascii_case(&home));
+        let away_odds = price(&|label| label.eq_ignore_ascii_case(&away));
+        let draw_odds =
+            price(&|label| label.eq_ignore_ascii_case("draw") || label.eq_ignore_ascii_case("tie"));
+        let (Some(h), Some(a)) = (home_odds, away_odds) else {
+            continue;
+        };
+        if !seen.insert(offer.event_id) {
+            continue;
+        }
+        updates.push(OddsUpdate {
+            event_id: offer.event_id.to_string(),
+            sport: sport.to_string(),
+            home_team: dk_team_name(sport, &home),
+            away_team: dk_team_name(sport, &away),
+            commence_time: event.start_date.clone(),
+            bookmakers: vec![BookmakerOdds {
+                name: "DraftKings".to_string(),
+                home_odds: h,
+                away_odds: a,
+                draw_odds,
+                last_update: now.to_string(),
+            }],
+        });
+    }
+    updates
+}
+
 impl DraftKingsFeed {
-    pub fn new(config: &crate::config::DraftKingsFeedConfig) -> Self {
-        let client = Client::builder()
+    pub fn new(config: &crate::config::DraftKingsFeedConfig, http: reqwest::ClientBuilder) -> Self {
+        let client = http
             .timeout(Duration::from_millis(config.request_timeout_ms))
             .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36")
             .build()
@@ -47,30 +315,28 @@ impl DraftKingsFeed {
         let poll_interval = Duration::from_secs(config.live_poll_interval_s);
         Self {
             client,
+            base_url: config.base_url.trim_end_matches('/').to_string(),
             poll_interval,
             configured_poll_interval: poll_interval,
             pre_game_poll_interval: Duration::from_secs(config.pre_game_poll_interval_s),
+            event_groups: config.event_groups.clone(),
+            paths: HashMap::new(),
             last_fetch: None,
-            last_etag: None,
+            etags: HashMap::new(),
+            last_updates: HashMap::new(),
         }
     }
 
     /// Build the URL for fetching moneyline odds for a sport.
-    fn build_url(group_id: u64, category_id: u64, subcategory_id: u64) -> String {
+    fn build_url(&self, group_id: u64, path: MoneylinePath) -> String {
         format!(
             "{}/{}/categories/{}/subcategories/{}",
-            DK_BASE_URL, group_id, category_id, subcategory_id
+            self.base_url, group_id, path.category_id, path.subcategory_id
         )
     }
-}
 
-#[async_trait]
-impl OddsFeed for DraftKingsFeed {
-    async fn fetch_odds(&mut self, sport: &str) -> Result<Vec<OddsUpdate>> {
-        let (group_id, category_id, subcategory_id) = dk_event_group(sport)
-            .with_context(|| format!("DraftKings does not support sport: {}", sport))?;
-
-        // Rate-limit
+    /// Conditional GET of `url`, rate-limited to the poll interval.
+    async fn get(&mut self, url: &str) -> Result<Fetched> {
         if let Some(last) = self.last_fetch {
             let elapsed = last.elapsed();
             if elapsed < self.poll_interval {
@@ -78,10 +344,8 @@ impl OddsFeed for DraftKingsFeed {
             }
         }
 
-        let url = Self::build_url(group_id, category_id, subcategory_id);
-
-        let mut req = self.client.get(&url);
-        if let Some(ref etag) = self.last_etag {
+        let mut req = self.client.get(url);
+        if let Some(etag) = self.etags.get(url) {
             req = req.header("If-None-Match", etag.as_str());
         }
 
@@ -90,7 +354,10 @@ impl OddsFeed for DraftKingsFeed {
 
         // Handle 304 Not Modified (unchanged since last ETag)
         if resp.status() == reqwest::StatusCode::NOT_MODIFIED {
-            return Ok(Vec::new());
+            return Ok(Fetched::NotModified);
+        }
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(Fetched::NotFound);
         }
 
         // Handle rate limiting
@@ -111,85 +378,94 @@ impl OddsFeed for DraftKingsFeed {
         self.poll_interval = self.configured_poll_interval;
 
         // Store ETag for next conditional GET
-        if let Some(etag) = resp.headers().get("etag") {
-            self.last_etag = etag.to_str().ok().map(|s| s.to_string());
+        match resp.headers().get("etag").and_then(|e| e.to_str().ok()) {
+            Some(etag) => {
+                self.etags.insert(url.to_string(), etag.to_string());
+            }
+            None => {
+                self.etags.remove(url);
+            }
         }
 
-        let dk_resp: DkResponse = resp
+        let body = resp
             .json()
             .await
             .context("failed to parse DraftKings response")?;
+        Ok(Fetched::Body(body))
+    }
 
-        let Some(event_group) = dk_resp.event_group else {
-            return Ok(Vec::new());
+    /// Find where `group_id` keeps its moneylines from the group's category list.
+    async fn discover(&mut self, group_id: u64) -> Result<MoneylinePath> {
+        let url = format!("{}/{}", self.base_url, group_id);
+        // Always a full response: the path is not cached yet
+        self.etags.remove(&url);
+        let Fetched::Body(resp) = self.get(&url).await? else {
+            anyhow::bail!("DraftKings event group {} not found", group_id);
         };
+        let group = resp
+            .event_group
+            .with_context(|| format!("DraftKings event group {} is empty", group_id))?;
+        let path = find_moneyline_path(&group).with_context(|| {
+            format!(
+                "no moneyline category in DraftKings event group {}",
+                group_id
+            )
+        })?;
+        tracing::info!(
+            group_id,
+            category_id = path.category_id,
+            subcategory_id = path.subcategory_id,
+            "DraftKings moneyline category discovered"
+        );
+        Ok(path)
+    }
+}
 
-        // Build event_id -> DkEvent lookup
-        let event_map: std::collections::HashMap<u64, &DkEvent> =
-            event_group.events.iter().map(|e| (e.event_id, e)).collect();
-
-        let mut updates: Vec<OddsUpdate> = Vec::new();
+#[async_trait]
+impl OddsFeed for DraftKingsFeed {
+    async fn fetch_odds(&mut self, sport: &str) -> Result<Vec<OddsUpdate>> {
+        let group_id = self
+            .event_groups
+            .get(sport)
+            .copied()
+            .or_else(|| dk_event_group(sport))
+            .with_context(|| format!("DraftKings does not support sport: {}", sport))?;
 
-        // Flatten all offers across categories
-        for category in &event_group.offer_categories {
-            for offer_list in &category.offers {
-                for offer in offer_list {
-                    if offer.is_suspended || offer.outcomes.len() < 2 {
-                        continue;
-                    }
+        let path = match self.paths.get(&group_id) {
+            Some(path) => *path,
+            None => {
+                let path = self.discover(group_id).await?;
+                self.paths.insert(group_id, path);
+                path
+            }
+        };
 
-                    let Some(event) = event_map.get(&offer.event_id) else {
-                        continue;
-                    };
-
-                    // Determine home/away from event team names or outcome labels
-                    let (home_team, away_team) = match (&event.team_name1, &event.team_name2) {
-                        (Some(t1), Some(t2)) => (t1.clone(), t2.clone()),
-                        _ => {
-                            // Fall back to outcome labels
-                            if offer.outcomes.len() >= 2 {
-                                (
-                                    offer.outcomes[0].label.clone(),
-                                    offer.outcomes[1].label.clone(),
-                                )
-                            } else {
-                                continue;
-                            }
-                        }
-                    };
-
-                    let home_odds = offer
-                        .outcomes
-                        .iter()
-                        .find(|o| o.label == home_team)
-                        .and_then(|o| parse_american_odds(&o.odds_american));
-                    let away_odds = offer
-                        .outcomes
-                        .iter()
-                        .find(|o| o.label == away_team)
-                        .and_then(|o| parse_american_odds(&o.odds_american));
-
-                    if let (Some(h), Some(a)) = (home_odds, away_odds) {
-                        updates.push(OddsUpdate {
-                            event_id: offer.event_id.to_string(),
-                            sport: sport.to_string(),
-                            home_team: home_team.clone(),
-                            away_team: away_team.clone(),
-                            commence_time: event.start_date.clone(),
-                            bookmakers: vec![BookmakerOdds {
-                                name: "DraftKings".to_string(),
-                                home_odds: h,
-                                away_odds: a,
-                                draw_odds: None,
-                                last_update: chrono::Utc::now().to_rfc3339(),
-                            }],
-                        });
-                    }
+        let url = self.build_url(group_id, path);
+        let now = chrono::Utc::now().to_rfc3339();
+        match self.get(&url).await? {
+            Fetched::Body(resp) => {
+                let updates = resp
+                    .event_group
+                    .map(|group| parse_moneylines(sport, &group, &now))
+                    .unwrap_or_default();
+                self.last_updates.insert(url, updates.clone());
+                Ok(updates)
+            }
+            // Unchanged: the previous lines are still current
+            Fetched::NotModified => {
+                let mut updates = self.last_updates.get(&url).cloned().unwrap_or_default();
+                for bm in updates.iter_mut().flat_map(|u| &mut u.bookmakers) {
+                    bm.last_update = now.clone();
                 }
+                Ok(updates)
+            }
+            // Categories get renumbered; rediscover on the next poll
+            Fetched::NotFound => {
+                self.paths.remove(&group_id);
+                self.last_updates.remove(&url);
+                anyhow::bail!("DraftKings moneyline category moved for {}", sport)
             }
         }
-
-        Ok(updates)
     }
 
     fn last_quota(&self) -> Option<ApiQuota> {
@@ -201,14 +477,134 @@ impl OddsFeed for DraftKingsFeed {
 mod tests {
     use super::*;
 
+    const NBA_GROUP: &str = r#"{
+        "eventGroup": {
+            "eventGroupId": 42648,
+            "events": [
+                {"eventId": 1, "name": "GS Warriors @ BOS Celtics", "startDate": "2026-03-10T23:30:00Z",
+                 "teamName1": "GS Warriors", "teamName2": "BOS Celtics", "eventStatus": {"state": "STARTED"}},
+                {"eventId": 2, "name": "LA Lakers @ NY Knicks", "startDate": "2026-03-11T00:00:00Z",
+                 "teamName1": "LA Lakers", "teamName2": "NY Knicks", "eventStatus": {"state": "NOT_STARTED"}},
+                {"eventId": 3, "name": "MIA Heat @ CHI Bulls", "startDate": "2026-03-10T20:00:00Z",
+                 "teamName1": "MIA Heat", "teamName2": "CHI Bulls", "eventStatus": {"state": "FINISHED"}}
+            ],
+            "offerCategories": [
+                {"offerCategoryId": 583, "name": "Player Props"},
+                {"offerCategoryId": 487, "name": "Game Lines", "offerSubcategoryDescriptors": [
+                    {"subcategoryId": 4511, "name": "Game"},
+                    {"subcategoryId": 4518, "name": "Moneyline", "offerSubcategory": {"offers": [
+                        [
+                            {"eventId": 1, "label": "Point Spread", "outcomes": [
+                                {"label": "GS Warriors", "oddsAmerican": "-110", "line": 4.5},
+                                {"label": "BOS Celtics", "oddsAmerican": "-110", "line": -4.5}]},
+                            {"eventId": 1, "label": "Moneyline", "outcomes": [
+                                {"label": "GS Warriors", "oddsAmerican": "+165"},
+                                {"label": "BOS Celtics", "oddsAmerican": "\u2212200"}]}
+                        ],
+                        [
+                            {"eventId": 2, "label": "Moneyline", "isSuspended": true, "outcomes": [
+                                {"label": "LA Lakers", "oddsAmerican": "+120"},
+                                {"label": "NY Knicks", "oddsAmerican": "-140"}]}
+                        ],
+                        [
+                            {"eventId": 3, "label": "Moneyline", "outcomes": [
+                                {"label": "MIA Heat", "oddsAmerican": "+5000"},
+                                {"label": "CHI Bulls", "oddsAmerican": "-20000"}]}
+                        ]
+                    ]}}
+                ]}
+            ]
+        }
+    }"#;
+
+    fn group(json: &str) -> DkEventGroup {
+        serde_json::from_str::<DkResponse>(json)
+            .unwrap()
+            .event_group
+            .unwrap()
+    }
+
+    #[test]
+    fn test_dk_event_group() {
+        assert_eq!(dk_event_group("basketball"), Some(42648));
+        assert_eq!(dk_event_group("college-basketball"), Some(92483));
+        assert_eq!(dk_event_group("mma"), Some(9034));
+        assert_eq!(dk_event_group("cricket"), None);
+    }
+
+    #[test]
+    fn test_find_moneyline_path_prefers_moneyline_subcategory() {
+        assert_eq!(
+            find_moneyline_path(&group(NBA_GROUP)),
+            Some(MoneylinePath {
+                category_id: 487,
+                subcategory_id: 4518
+            })
+        );
+        let props_only = r#"{"eventGroup": {"offerCategories": [
+            {"offerCategoryId": 583, "name": "Player Props", "offerSubcategoryDescriptors": [
+                {"subcategoryId": 1, "name": "Points"}]}]}}"#;
+        assert_eq!(find_moneyline_path(&group(props_only)), None);
+    }
+
+    #[test]
+    fn test_parse_moneylines_in_play() {
+        let updates = parse_moneylines("basketball", &group(NBA_GROUP), "now");
+        // The suspended offer and the finished game are left out
+        assert_eq!(updates.len(), 1);
+        let u = &updates[0];
+        assert_eq!(u.event_id, "1");
+        // "Away @ Home": teamName1 is the visitor
+        assert_eq!(u.home_team, "Boston Celtics");
+        assert_eq!(u.away_team, "Golden State Warriors");
+        let bm = &u.bookmakers[0];
+        assert_eq!(bm.name, "DraftKings");
+        assert!((bm.home_odds - -200.0).abs() < f64::EPSILON);
+        assert!((bm.away_odds - 165.0).abs() < f64::EPSILON);
+        assert_eq!(bm.draw_odds, None);
+    }
+
     #[test]
-    fn test_dk_event_group_basketball() {
-        assert_eq!(dk_event_group("basketball"), Some((42648, 487, 4518)));
+    fn test_parse_moneylines_soccer_three_way() {
+        let json = r#"{"eventGroup": {
+            "events": [{"eventId": 9, "name": "Man City vs Nottm Forest", "startDate": "2026-03-14T15:00:00Z",
+                        "teamName1": "Man City", "teamName2": "Nottm Forest"}],
+            "offerCategories": [{"offerCategoryId": 490, "name": "Game Lines", "offers": [[
+                {"eventId": 9, "label": "Full Time Result", "outcomes": [
+                    {"label": "Man City", "oddsAmerican": "-250"},
+                    {"label": "Draw", "oddsAmerican": "+375"},
+                    {"label": "Nottm Forest", "oddsAmerican": "+600"}]}]]}]
+        }}"#;
+        let updates = parse_moneylines("soccer-epl", &group(json), "now");
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].home_team, "Manchester City");
+        assert_eq!(updates[0].away_team, "Nottingham Forest");
+        assert_eq!(updates[0].bookmakers[0].draw_odds, Some(375.0));
     }
 
     #[test]
-    fn test_dk_event_group_unknown() {
-        assert_eq!(dk_event_group("baseball"), None);
+    fn test_dk_team_name() {
+        assert_eq!(
+            dk_team_name("basketball", "GS Warriors"),
+            "Golden State Warriors"
+        );
+        assert_eq!(dk_team_name("basketball", "IND Pacers"), "Indiana Pacers");
+        assert_eq!(
+            dk_team_name("american-football", "IND Colts"),
+            "Indianapolis Colts"
+        );
+        assert_eq!(
+            dk_team_name("ice-hockey", "VGK Golden Knights"),
+            "Vegas Golden Knights"
+        );
+        // Already a full name, or no known prefix
+        assert_eq!(
+            dk_team_name("basketball", "Boston Celtics"),
+            "Boston Celtics"
+        );
+        assert_eq!(dk_team_name("college-basketball", "NC State"), "NC State");
+        assert_eq!(dk_team_name("soccer-epl", "Spurs"), "Tottenham Hotspur");
+        assert_eq!(dk_team_name("soccer-epl", "Arsenal"), "Arsenal");
     }
 
     #[test]
@@ -238,7 +634,7 @@ mod tests {
     #[ignore]
     async fn dk_live_fetch() {
         let config = crate::config::DraftKingsFeedConfig::default();
-        let mut feed = DraftKingsFeed::new(&config);
+        let mut feed = DraftKingsFeed::new(&config, Client::builder());
         match feed.fetch_odds("basketball").await {
             Ok(updates) => {
                 println!("Got {} NBA events from DraftKings", updates.len());
@@ -0,0 +1,290 @@
+//! Odds source health and failover to a backup source.
+//!
+//! Each sport tracks how its odds sources have been answering: consecutive
+//! failed polls and the time since the last good one. When the primary
+//! source goes down, `OddsFailover` polls the sport's backup source instead
+//! and, like `ScorePoller`, probes the primary every `threshold` polls so it
+//! switches back once the primary recovers.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How a source is doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceStatus {
+    Healthy,
+    /// Failing, but not yet for `threshold` polls in a row.
+    Degraded,
+    /// `threshold` failed polls in a row, or nothing good for `stale_after`.
+    Down,
+}
+
+/// Poll outcomes of one source.
+#[derive(Debug, Clone, Default)]
+pub struct SourceHealth {
+    pub consecutive_errors: u32,
+    pub last_success: Option<Instant>,
+    pub last_error: Option<String>,
+}
+
+impl SourceHealth {
+    pub fn record(&mut self, result: Result<(), String>, now: Instant) {
+        match result {
+            Ok(()) => {
+                self.consecutive_errors = 0;
+                self.last_success = Some(now);
+                self.last_error = None;
+            }
+            Err(e) => {
+                self.consecutive_errors += 1;
+                self.last_error = Some(e);
+            }
+        }
+    }
+
+    pub fn status(&self, now: Instant, threshold: u32, stale_after: Duration) -> SourceStatus {
+        // A source that never answered is judged by its failures alone
+        let stale = self
+            .last_success
+            .is_some_and(|t| now.duration_since(t) >= stale_after);
+        if self.consecutive_errors >= threshold.max(1) || (stale && self.consecutive_errors > 0) {
+            SourceStatus::Down
+        } else if self.consecutive_errors > 0 || stale {
+            SourceStatus::Degraded
+        } else {
+            SourceStatus::Healthy
+        }
+    }
+
+    /// Short label for the diagnostic view: "OK 12s", "ERR 1/3", "DOWN".
+    pub fn label(&self, now: Instant, threshold: u32, stale_after: Duration) -> String {
+        match self.status(now, threshold, stale_after) {
+            SourceStatus::Down => "DOWN".to_string(),
+            SourceStatus::Degraded if self.consecutive_errors > 0 => {
+                format!("ERR {}/{}", self.consecutive_errors, threshold)
+            }
+            _ => match self.last_success {
+                Some(t) => format!("OK {}s", now.duration_since(t).as_secs()),
+                None => "-".to_string(),
+            },
+        }
+    }
+}
+
+/// A sport's odds source health as shown in the diagnostic view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthSummary {
+    pub status: SourceStatus,
+    pub label: String,
+    /// Polling the backup source.
+    pub on_backup: bool,
+}
+
+/// A failover or its reversal, for the event log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailoverEvent {
+    FailedOver {
+        from: String,
+        to: String,
+        error: String,
+    },
+    Restored {
+        to: String,
+    },
+}
+
+/// Primary/backup switching for one sport's odds polling.
+#[derive(Debug, Clone, Default)]
+pub struct OddsFailover {
+    backup: Option<String>,
+    on_backup: bool,
+    /// Backup polls since the primary was last probed.
+    backup_polls: u32,
+    health: HashMap<String, SourceHealth>,
+}
+
+impl OddsFailover {
+    pub fn new(backup: Option<String>) -> Self {
+        Self {
+            backup,
+            ..Self::default()
+        }
+    }
+
+    pub fn on_backup(&self) -> bool {
+        self.on_backup && self.backup.is_some()
+    }
+
+    /// The source serving this sport: the backup while failed over.
+    pub fn active<'a>(&'a self, primary: &'a str) -> &'a str {
+        match &self.backup {
+            Some(backup) if self.on_backup => backup,
+            _ => primary,
+        }
+    }
+
+    /// The source to poll next. While failed over this is the backup, except
+    /// every `threshold`-th poll, which probes the primary.
+    pub fn next_source(&mut self, primary: &str, threshold: u32) -> String {
+        if !self.on_backup() {
+            return primary.to_string();
+        }
+        if self.backup_polls >= threshold {
+            self.backup_polls = 0;
+            return primary.to_string();
+        }
+        self.backup_polls += 1;
+        self.active(primary).to_string()
+    }
+
+    /// Record a poll of `source`. Returns the switch it caused, if any.
+    pub fn record(
+        &mut self,
+        source: &str,
+        primary: &str,
+        result: Result<(), String>,
+        now: Instant,
+        threshold: u32,
+        stale_after: Duration,
+    ) -> Option<FailoverEvent> {
+        let error = result.as_ref().err().cloned();
+        let health = self.health.entry(source.to_string()).or_default();
+        health.record(result, now);
+        if source != primary {
+            return None;
+        }
+        match error {
+            None if self.on_backup => {
+                self.on_backup = false;
+                Some(FailoverEvent::Restored {
+                    to: primary.to_string(),
+                })
+            }
+            Some(error)
+                if !self.on_backup
+                    && health.status(now, threshold, stale_after) == SourceStatus::Down =>
+            {
+                let backup = self.backup.as_ref().filter(|b| *b != primary)?;
+                self.on_backup = true;
+                self.backup_polls = 0;
+                Some(FailoverEvent::FailedOver {
+                    from: primary.to_string(),
+                    to: backup.clone(),
+                    error,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Health of the source serving the sport.
+    pub fn summary(
+        &self,
+        primary: &str,
+        now: Instant,
+        threshold: u32,
+        stale_after: Duration,
+    ) -> HealthSummary {
+        let health = self
+            .health
+            .get(self.active(primary))
+            .cloned()
+            .unwrap_or_default();
+        HealthSummary {
+            status: health.status(now, threshold, stale_after),
+            label: health.label(now, threshold, stale_after),
+            on_backup: self.on_backup(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STALE: Duration = Duration::from_secs(300);
+
+    #[test]
+    fn test_source_health_status_and_label() {
+        let t0 = Instant::now();
+        let mut health = SourceHealth::default();
+        assert_eq!(health.status(t0, 3, STALE), SourceStatus::Healthy);
+        assert_eq!(health.label(t0, 3, STALE), "-");
+
+        health.record(Ok(()), t0);
+        let t1 = t0 + Duration::from_secs(12);
+        assert_eq!(health.label(t1, 3, STALE), "OK 12s");
+
+        health.record(Err("timeout".into()), t1);
+        assert_eq!(health.status(t1, 3, STALE), SourceStatus::Degraded);
+        assert_eq!(health.label(t1, 3, STALE), "ERR 1/3");
+
+        // One more failure after a long silence is enough
+        let late = t0 + STALE;
+        health.record(Err("timeout".into()), late);
+        assert_eq!(health.status(late, 3, STALE), SourceStatus::Down);
+        assert_eq!(health.label(late, 3, STALE), "DOWN");
+    }
+
+    #[test]
+    fn test_fails_over_after_threshold_and_restores_on_probe() {
+        let now = Instant::now();
+        let mut failover = OddsFailover::new(Some("draftkings".to_string()));
+        let primary = "the-odds-api";
+
+        for i in 1..=3 {
+            assert_eq!(failover.next_source(primary, 3), primary);
+            let event = failover.record(primary, primary, Err("HTTP 500".into()), now, 3, STALE);
+            assert_eq!(event.is_some(), i == 3);
+        }
+        assert!(failover.on_backup());
+        assert_eq!(failover.active(primary), "draftkings");
+
+        // Backup polls, then a probe of the primary
+        for _ in 0..3 {
+            let source = failover.next_source(primary, 3);
+            assert_eq!(source, "draftkings");
+            assert_eq!(
+                failover.record(&source, primary, Ok(()), now, 3, STALE),
+                None
+            );
+        }
+        let summary = failover.summary(primary, now, 3, STALE);
+        assert!(summary.on_backup);
+        assert_eq!(summary.status, SourceStatus::Healthy);
+
+        assert_eq!(failover.next_source(primary, 3), primary);
+        // A failed probe stays on the backup
+        assert_eq!(
+            failover.record(primary, primary, Err("HTTP 500".into()), now, 3, STALE),
+            None
+        );
+        for _ in 0..3 {
+            failover.next_source(primary, 3);
+        }
+        assert_eq!(failover.next_source(primary, 3), primary);
+        assert_eq!(
+            failover.record(primary, primary, Ok(()), now, 3, STALE),
+            Some(FailoverEvent::Restored {
+                to: primary.to_string()
+            })
+        );
+        assert!(!failover.on_backup());
+        assert_eq!(failover.next_source(primary, 3), primary);
+    }
+
+    #[test]
+    fn test_no_backup_never_fails_over() {
+        let now = Instant::now();
+        let mut failover = OddsFailover::new(None);
+        for _ in 0..5 {
+            assert_eq!(
+                failover.record("pinnacle", "pinnacle", Err("down".into()), now, 3, STALE),
+                None
+            );
+        }
+        assert!(!failover.on_backup());
+        let summary = failover.summary("pinnacle", now, 3, STALE);
+        assert_eq!(summary.status, SourceStatus::Down);
+    }
+}
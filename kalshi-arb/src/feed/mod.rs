@@ -1,15 +1,45 @@
+pub mod adaptive_poll;
+pub mod budget;
+pub mod cadence;
 pub mod draftkings;
+pub mod health;
+pub mod news;
+pub mod pinnacle;
 pub mod score_feed;
 pub mod scraped;
+pub mod supervisor;
 pub mod the_odds_api;
 pub mod types;
+pub mod usage;
+pub mod ws_odds;
 
 use anyhow::Result;
 use async_trait::async_trait;
+use tokio::sync::mpsc;
 use types::{ApiQuota, OddsUpdate};
 
 #[async_trait]
 pub trait OddsFeed: Send + Sync {
     async fn fetch_odds(&mut self, sport: &str) -> Result<Vec<OddsUpdate>>;
     fn last_quota(&self) -> Option<ApiQuota>;
+    /// Number of response items skipped because they failed to deserialize.
+    fn schema_drift_count(&self) -> u64 {
+        0
+    }
+}
+
+/// Events from a push-based odds source.
+#[derive(Debug, Clone)]
+pub enum OddsStreamEvent {
+    Update(OddsUpdate),
+    Connected,
+    Disconnected(String),
+}
+
+/// An odds source that pushes updates instead of being polled.
+#[async_trait]
+pub trait StreamingOddsFeed: Send + Sync {
+    /// Stream odds for `sports` into `tx`, reconnecting on failure, until
+    /// the receiver is dropped.
+    async fn run(&self, sports: Vec<String>, tx: mpsc::Sender<OddsStreamEvent>);
 }
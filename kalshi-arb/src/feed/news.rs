@@ -0,0 +1,313 @@
+//! Breaking news as a trading halt.
+//!
+//! Fair value models price scores and odds, not news: a star ruled out at
+//! halftime moves the real price before either feed does. `NewsPoller` reads
+//! a sport's news feed (ESPN's news API) and `NewsHalts` holds each breaking
+//! item for a while, during which games of the teams it mentions are not
+//! traded.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+
+/// One news item.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewsItem {
+    pub id: String,
+    pub headline: String,
+    pub description: String,
+    pub published: Option<DateTime<Utc>>,
+    /// Teams the feed tagged the item with.
+    pub teams: Vec<String>,
+}
+
+impl NewsItem {
+    /// Whether the headline or description contains any of `keywords`
+    /// (case-insensitive). An empty list matches everything.
+    pub fn is_breaking(&self, keywords: &[String]) -> bool {
+        let text = format!("{} {}", self.headline, self.description).to_lowercase();
+        keywords.is_empty() || keywords.iter().any(|k| text.contains(&k.to_lowercase()))
+    }
+
+    /// Whether the item is about `team` (a full name like "Boston Celtics"):
+    /// tagged with it, or naming it or its nickname in the headline.
+    pub fn mentions(&self, team: &str) -> bool {
+        let team = team.to_lowercase();
+        if team.is_empty() {
+            return false;
+        }
+        let tagged = self.teams.iter().any(|t| {
+            let t = t.to_lowercase();
+            t.contains(&team) || team.contains(&t)
+        });
+        let headline = self.headline.to_lowercase();
+        let nickname = team.rsplit(' ').next().filter(|n| n.len() >= 4);
+        tagged || headline.contains(&team) || nickname.is_some_and(|n| headline.contains(n))
+    }
+}
+
+// ── ESPN News API Deserialization ────────────────────────────────────
+
+#[derive(Deserialize)]
+struct EspnNews {
+    #[serde(default)]
+    articles: Vec<EspnArticle>,
+}
+
+#[derive(Deserialize)]
+struct EspnArticle {
+    #[serde(default)]
+    id: Option<serde_json::Value>,
+    #[serde(default)]
+    headline: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    published: Option<String>,
+    #[serde(default)]
+    categories: Vec<EspnCategory>,
+}
+
+#[derive(Deserialize)]
+struct EspnCategory {
+    #[serde(rename = "type", default)]
+    category_type: String,
+    #[serde(default)]
+    description: String,
+}
+
+/// Parse an ESPN news response (`.../sports/<sport>/<league>/news`).
+pub fn parse_espn_news(json: &str) -> anyhow::Result<Vec<NewsItem>> {
+    let news: EspnNews = serde_json::from_str(json)?;
+    Ok(news
+        .articles
+        .into_iter()
+        .map(|a| NewsItem {
+            id: match a.id {
+                Some(serde_json::Value::String(id)) => id,
+                Some(id) => id.to_string(),
+                None => a.headline.clone(),
+            },
+            published: a
+                .published
+                .as_deref()
+                .and_then(|p| DateTime::parse_from_rfc3339(p).ok())
+                .map(|p| p.with_timezone(&Utc)),
+            teams: a
+                .categories
+                .into_iter()
+                .filter(|c| c.category_type == "team" && !c.description.is_empty())
+                .map(|c| c.description)
+                .collect(),
+            headline: a.headline,
+            description: a.description,
+        })
+        .collect())
+}
+
+// ── NewsPoller ───────────────────────────────────────────────────────
+
+pub struct NewsPoller {
+    client: Client,
+    url: String,
+    timeout: Duration,
+    /// Item ids already returned, so each item halts trading once.
+    seen: HashSet<String>,
+}
+
+impl NewsPoller {
+    pub fn new(url: &str, timeout_ms: u64, http: reqwest::ClientBuilder) -> Self {
+        Self {
+            client: http.build().expect("failed to build reqwest client"),
+            url: url.to_string(),
+            timeout: Duration::from_millis(timeout_ms),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Items not returned by an earlier fetch.
+    pub async fn fetch(&mut self) -> anyhow::Result<Vec<NewsItem>> {
+        let text = self
+            .client
+            .get(&self.url)
+            .timeout(self.timeout)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        let items = parse_espn_news(&text)?;
+        Ok(items
+            .into_iter()
+            .filter(|item| self.seen.insert(item.id.clone()))
+            .collect())
+    }
+}
+
+// ── NewsHalts ────────────────────────────────────────────────────────
+
+/// A breaking item and when its halt ends.
+#[derive(Debug, Clone)]
+pub struct NewsHalt {
+    pub item: NewsItem,
+    pub until: DateTime<Utc>,
+}
+
+/// Active news halts for one sport.
+#[derive(Debug, Clone, Default)]
+pub struct NewsHalts {
+    halts: Vec<NewsHalt>,
+}
+
+impl NewsHalts {
+    /// Halt the teams `item` mentions for `duration` from its publish time
+    /// (or `now`, if it has none or claims to be from the future). Returns
+    /// the halt when the item is breaking and its halt has not already run out.
+    pub fn record(
+        &mut self,
+        item: NewsItem,
+        keywords: &[String],
+        duration: chrono::Duration,
+        now: DateTime<Utc>,
+    ) -> Option<&NewsHalt> {
+        if !item.is_breaking(keywords) {
+            return None;
+        }
+        let start = item.published.map_or(now, |p| p.min(now));
+        let until = start + duration;
+        if until <= now {
+            return None;
+        }
+        self.halts.push(NewsHalt { item, until });
+        self.halts.last()
+    }
+
+    /// The latest-ending halt covering a game between `home` and `away`.
+    pub fn halt_for(&self, home: &str, away: &str, now: DateTime<Utc>) -> Option<&NewsHalt> {
+        self.halts
+            .iter()
+            .filter(|h| h.until > now && (h.item.mentions(home) || h.item.mentions(away)))
+            .max_by_key(|h| h.until)
+    }
+
+    /// Drop halts that have run out.
+    pub fn prune(&mut self, now: DateTime<Utc>) {
+        self.halts.retain(|h| h.until > now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ESPN_NEWS: &str = r#"{
+        "header": "NBA News",
+        "articles": [
+            {
+                "id": 41234567,
+                "type": "HeadlineNews",
+                "headline": "Celtics' Tatum ruled out for rest of game with ankle injury",
+                "description": "Jayson Tatum left in the second quarter.",
+                "published": "2026-03-10T23:40:00Z",
+                "categories": [
+                    {"type": "league", "description": "NBA"},
+                    {"type": "team", "description": "Boston Celtics", "teamId": 2},
+                    {"type": "athlete", "description": "Jayson Tatum"}
+                ]
+            },
+            {
+                "id": "41234500",
+                "type": "Preview",
+                "headline": "Lakers visit Boston in a marquee matchup",
+                "published": "2026-03-10T20:00:00Z",
+                "categories": [{"type": "team", "description": "Los Angeles Lakers"}]
+            }
+        ]
+    }"#;
+
+    fn keywords() -> Vec<String> {
+        vec!["injur".to_string(), "ruled out".to_string()]
+    }
+
+    fn at(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_espn_news() {
+        let items = parse_espn_news(ESPN_NEWS).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].id, "41234567");
+        assert_eq!(items[0].teams, vec!["Boston Celtics"]);
+        assert_eq!(items[0].published, Some(at("2026-03-10T23:40:00Z")));
+        assert_eq!(items[1].id, "41234500");
+        assert!(items[0].is_breaking(&keywords()));
+        assert!(!items[1].is_breaking(&keywords()));
+        assert!(items[1].is_breaking(&[]));
+    }
+
+    #[test]
+    fn test_mentions_by_tag_name_or_nickname() {
+        let items = parse_espn_news(ESPN_NEWS).unwrap();
+        assert!(items[0].mentions("Boston Celtics"));
+        assert!(items[0].mentions("BOSTON CELTICS"));
+        assert!(!items[0].mentions("Los Angeles Lakers"));
+        // Untagged, but the headline names the team
+        let untagged = NewsItem {
+            teams: Vec::new(),
+            ..items[0].clone()
+        };
+        assert!(untagged.mentions("Boston Celtics"));
+        assert!(!untagged.mentions(""));
+    }
+
+    #[test]
+    fn test_halts_cover_either_team_until_they_run_out() {
+        let items = parse_espn_news(ESPN_NEWS).unwrap();
+        let mut halts = NewsHalts::default();
+        let minutes = chrono::Duration::minutes(10);
+        let now = at("2026-03-10T23:45:00Z");
+
+        // A preview is not breaking news
+        assert!(halts
+            .record(items[1].clone(), &keywords(), minutes, now)
+            .is_none());
+        let halt = halts
+            .record(items[0].clone(), &keywords(), minutes, now)
+            .unwrap();
+        // Ten minutes from publication, not from when it was polled
+        assert_eq!(halt.until, at("2026-03-10T23:50:00Z"));
+
+        assert!(halts
+            .halt_for("Boston Celtics", "Los Angeles Lakers", now)
+            .is_some());
+        assert!(halts
+            .halt_for("Los Angeles Lakers", "Boston Celtics", now)
+            .is_some());
+        assert!(halts
+            .halt_for("New York Knicks", "Miami Heat", now)
+            .is_none());
+
+        let later = at("2026-03-10T23:50:00Z");
+        assert!(halts
+            .halt_for("Boston Celtics", "Los Angeles Lakers", later)
+            .is_none());
+        halts.prune(later);
+        assert!(halts.halts.is_empty());
+
+        // An item whose halt already ran out before it was seen is ignored
+        assert!(halts
+            .record(
+                items[0].clone(),
+                &keywords(),
+                minutes,
+                at("2026-03-11T01:00:00Z")
+            )
+            .is_none());
+    }
+}
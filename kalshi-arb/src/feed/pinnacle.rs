@@ -0,0 +1,333 @@
+use super::types::*;
+use super::OddsFeed;
+use crate::engine::strategy::american_to_probability;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::time::Duration;
+
+pub const PINNACLE_BASE_URL: &str = "https://api.pinnacle.com";
+
+/// Full-game period number in Pinnacle's odds.
+const FULL_GAME_PERIOD: i32 = 0;
+
+/// Fixtures and full-game moneylines for one league, kept current by
+/// Pinnacle's `since` deltas so each poll only transfers what changed.
+#[derive(Default)]
+struct LeagueCache {
+    fixtures_since: Option<i64>,
+    odds_since: Option<i64>,
+    fixtures: HashMap<u64, PinnacleFixture>,
+    moneylines: HashMap<u64, PinnacleMoneyline>,
+}
+
+pub struct PinnacleFeed {
+    client: Client,
+    base_url: String,
+    username: String,
+    password: String,
+    leagues: HashMap<String, LeagueCache>,
+}
+
+/// Map internal sport key to Pinnacle (sport_id, league_id).
+fn pinnacle_league(sport: &str) -> Option<(u32, u32)> {
+    match sport {
+        "basketball" => Some((4, 487)),
+        "college-basketball" => Some((4, 493)),
+        "ice-hockey" => Some((19, 1456)),
+        "american-football" => Some((15, 889)),
+        "baseball" => Some((3, 246)),
+        "soccer-epl" => Some((29, 1980)),
+        "mma" => Some((22, 1624)),
+        _ => None,
+    }
+}
+
+/// Strip the margin from implied probabilities with the power method: find
+/// `k` such that the `pᵢᵏ` sum to 1. Unlike proportional scaling this takes
+/// more of the margin from longshots, which is where a sharp book's
+/// favourite-longshot bias puts it.
+pub fn devig_power(implied: &[f64]) -> Vec<f64> {
+    let total = |k: f64| implied.iter().map(|p| p.powf(k)).sum::<f64>();
+    if implied.is_empty() || implied.iter().any(|&p| p <= 0.0 || p >= 1.0) {
+        let sum: f64 = implied.iter().sum();
+        return implied.iter().map(|p| p / sum).collect();
+    }
+    // Σ pᵢᵏ falls as k rises; bracket the root and bisect
+    let (mut lo, mut hi) = (0.01_f64, 10.0_f64);
+    for _ in 0..100 {
+        let mid = (lo + hi) / 2.0;
+        if total(mid) > 1.0 {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    let k = (lo + hi) / 2.0;
+    implied.iter().map(|p| p.powf(k)).collect()
+}
+
+/// American odds paying exactly `probability` (no margin).
+pub fn probability_to_american(probability: f64) -> f64 {
+    let p = probability.clamp(0.001, 0.999);
+    if p >= 0.5 {
+        -100.0 * p / (1.0 - p)
+    } else {
+        100.0 * (1.0 - p) / p
+    }
+}
+
+/// Replace a Pinnacle moneyline with its fair (no-vig) equivalent, so the
+/// engine's own de-vig downstream leaves Pinnacle's probabilities unchanged.
+pub fn fair_moneyline(line: &PinnacleMoneyline) -> PinnacleMoneyline {
+    let mut implied = vec![
+        american_to_probability(line.home),
+        american_to_probability(line.away),
+    ];
+    implied.extend(line.draw.map(american_to_probability));
+    let fair = devig_power(&implied);
+    PinnacleMoneyline {
+        home: probability_to_american(fair[0]),
+        away: probability_to_american(fair[1]),
+        draw: fair.get(2).copied().map(probability_to_american),
+    }
+}
+
+impl LeagueCache {
+    fn apply_fixtures(&mut self, resp: PinnacleFixturesResponse) {
+        self.fixtures_since = Some(resp.last);
+        for fixture in resp.league.into_iter().flat_map(|l| l.events) {
+            if fixture.resulting_unit == "Regular" {
+                self.fixtures.insert(fixture.id, fixture);
+            }
+        }
+    }
+
+    fn apply_odds(&mut self, resp: PinnacleOddsResponse) {
+        self.odds_since = Some(resp.last);
+        for event in resp.leagues.into_iter().flat_map(|l| l.events) {
+            let Some(period) = event
+                .periods
+                .into_iter()
+                .find(|p| p.number == FULL_GAME_PERIOD)
+            else {
+                continue;
+            };
+            match period.moneyline {
+                Some(line) if period.status == 1 => {
+                    self.moneylines.insert(event.id, line);
+                }
+                _ => {
+                    self.moneylines.remove(&event.id);
+                }
+            }
+        }
+    }
+
+    fn updates(&self, sport: &str) -> Vec<OddsUpdate> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut updates: Vec<OddsUpdate> = self
+            .moneylines
+            .iter()
+            .filter_map(|(id, line)| {
+                let fixture = self.fixtures.get(id)?;
+                let fair = fair_moneyline(line);
+                Some(OddsUpdate {
+                    event_id: id.to_string(),
+                    sport: sport.to_string(),
+                    home_team: fixture.home.clone(),
+                    away_team: fixture.away.clone(),
+                    commence_time: fixture.starts.clone(),
+                    bookmakers: vec![BookmakerOdds {
+                        name: "Pinnacle".to_string(),
+                        home_odds: fair.home,
+                        away_odds: fair.away,
+                        draw_odds: fair.draw,
+                        last_update: now.clone(),
+                    }],
+                })
+            })
+            .collect();
+        updates.sort_by(|a, b| a.event_id.cmp(&b.event_id));
+        updates
+    }
+}
+
+impl PinnacleFeed {
+    pub fn new(
+        username: String,
+        password: String,
+        base_url: &str,
+        request_timeout_ms: u64,
+        http: reqwest::ClientBuilder,
+    ) -> Self {
+        let client = http
+            .timeout(Duration::from_millis(request_timeout_ms))
+            .build()
+            .expect("failed to build reqwest client");
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            username,
+            password,
+            leagues: HashMap::new(),
+        }
+    }
+
+    /// GET an authenticated endpoint. Pinnacle answers a `since` poll with
+    /// nothing changed by an empty body, returned here as `None`.
+    async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<Option<T>> {
+        let url = format!("{}{}", self.base_url, path);
+        let resp = self
+            .client
+            .get(&url)
+            .basic_auth(&self.username, Some(&self.password))
+            .query(query)
+            .send()
+            .await
+            .context("Pinnacle request failed")?;
+
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        if !status.is_success() {
+            anyhow::bail!("Pinnacle API error {} ({}): {}", path, status, body);
+        }
+        if body.trim().is_empty() {
+            return Ok(None);
+        }
+        let parsed = serde_json::from_str(&body)
+            .with_context(|| format!("failed to parse Pinnacle {} response", path))?;
+        Ok(Some(parsed))
+    }
+
+    /// Verify the credentials against the account balance endpoint.
+    pub async fn check_auth(&self) -> Result<()> {
+        self.get::<serde_json::Value>("/v1/client/balance", &[])
+            .await
+            .map(|_| ())
+    }
+}
+
+#[async_trait]
+impl OddsFeed for PinnacleFeed {
+    async fn fetch_odds(&mut self, sport: &str) -> Result<Vec<OddsUpdate>> {
+        let (sport_id, league_id) = pinnacle_league(sport)
+            .with_context(|| format!("Pinnacle does not support sport: {}", sport))?;
+        // Taken out for the poll: if it fails the cache is dropped, so the
+        // next poll starts from a full snapshot rather than a delta against
+        // state that may have missed changes.
+        let mut cache = self.leagues.remove(sport).unwrap_or_default();
+        let mut query = vec![
+            ("sportId", sport_id.to_string()),
+            ("leagueIds", league_id.to_string()),
+        ];
+
+        let mut fixtures_query = query.clone();
+        fixtures_query.extend(cache.fixtures_since.map(|s| ("since", s.to_string())));
+        if let Some(resp) = self.get("/v1/fixtures", &fixtures_query).await? {
+            cache.apply_fixtures(resp);
+        }
+
+        query.push(("oddsFormat", "American".to_string()));
+        query.extend(cache.odds_since.map(|s| ("since", s.to_string())));
+        if let Some(resp) = self.get("/v1/odds", &query).await? {
+            cache.apply_odds(resp);
+        }
+
+        let updates = cache.updates(sport);
+        self.leagues.insert(sport.to_string(), cache);
+        Ok(updates)
+    }
+
+    fn last_quota(&self) -> Option<ApiQuota> {
+        None // Pinnacle rate-limits per endpoint instead of metering a quota
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_power_devig_sums_to_one_and_shades_longshots() {
+        // -200 / +170: implied 0.6667 + 0.3704 = 1.037
+        let line = PinnacleMoneyline {
+            home: -200.0,
+            away: 170.0,
+            draw: None,
+        };
+        let fair = fair_moneyline(&line);
+        let (home, away) = (
+            american_to_probability(fair.home),
+            american_to_probability(fair.away),
+        );
+        assert!((home + away - 1.0).abs() < 1e-6);
+        // Proportional scaling would give the favourite 0.6429
+        assert!(home > 0.6429 && home < 0.6667, "home {}", home);
+
+        let draw_line = PinnacleMoneyline {
+            home: 150.0,
+            away: 190.0,
+            draw: Some(230.0),
+        };
+        let fair = fair_moneyline(&draw_line);
+        let total: f64 = [fair.home, fair.away, fair.draw.unwrap()]
+            .into_iter()
+            .map(american_to_probability)
+            .sum();
+        assert!((total - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_deltas_update_and_remove_moneylines() {
+        let mut cache = LeagueCache::default();
+        cache.apply_fixtures(
+            serde_json::from_value(serde_json::json!({
+                "sportId": 4,
+                "last": 100,
+                "league": [{"id": 487, "events": [
+                    {"id": 1, "starts": "2026-01-20T00:10:00Z", "home": "Boston Celtics",
+                     "away": "Los Angeles Lakers", "resultingUnit": "Regular"},
+                    {"id": 2, "starts": "2026-01-20T00:10:00Z", "home": "Boston Celtics",
+                     "away": "Los Angeles Lakers", "resultingUnit": "Corners"}
+                ]}]
+            }))
+            .unwrap(),
+        );
+        let odds = |last: i64, status: i32, home: f64| -> PinnacleOddsResponse {
+            serde_json::from_value(serde_json::json!({
+                "sportId": 4,
+                "last": last,
+                "leagues": [{"id": 487, "events": [{"id": 1, "periods": [
+                    {"number": 1, "status": 1, "moneyline": {"home": -300, "away": 250}},
+                    {"number": 0, "status": status, "moneyline": {"home": home, "away": 110}}
+                ]}]}]
+            }))
+            .unwrap()
+        };
+
+        cache.apply_odds(odds(200, 1, -130.0));
+        assert_eq!(
+            (cache.fixtures_since, cache.odds_since),
+            (Some(100), Some(200))
+        );
+        let updates = cache.updates("basketball");
+        assert_eq!(updates.len(), 1, "non-regular fixtures are ignored");
+        assert_eq!(updates[0].home_team, "Boston Celtics");
+        assert_eq!(updates[0].bookmakers[0].name, "Pinnacle");
+        assert_eq!(cache.moneylines[&1].home, -130.0, "full game, not period 1");
+
+        cache.apply_odds(odds(300, 2, -140.0));
+        assert!(
+            cache.updates("basketball").is_empty(),
+            "offline period drops the line"
+        );
+        assert_eq!(cache.odds_since, Some(300));
+    }
+}
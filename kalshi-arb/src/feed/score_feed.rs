@@ -1,15 +1,17 @@
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use super::cadence::CadenceTracker;
 use std::collections::HashMap;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ScoreSource {
     Nba,
     Espn,
+    Nhl,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ScoreUpdate {
     pub game_id: String,
     pub home_team: String,
@@ -20,11 +22,16 @@ pub struct ScoreUpdate {
     pub clock_seconds: u16,
     pub total_elapsed_seconds: u16,
     pub game_status: GameStatus,
+    /// Red cards shown to each side (soccer); zero elsewhere.
+    #[serde(default)]
+    pub home_red_cards: u8,
+    #[serde(default)]
+    pub away_red_cards: u8,
     #[allow(dead_code)]
     pub source: ScoreSource,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GameStatus {
     PreGame,
     Live,
@@ -36,6 +43,76 @@ pub enum GameStatus {
     Finished,
 }
 
+/// Coarse game phase used to tag evaluations and trades for phase-based analysis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamePhase {
+    PreGame,
+    /// Regulation period: a half, hockey period or quarter, by `periods` in
+    /// regulation (2, 3 or 4).
+    Period {
+        number: u8,
+        periods: u8,
+    },
+    Halftime,
+    /// Final two minutes of regulation.
+    FinalTwoMin,
+    Overtime,
+    /// In play, but the source does not report period/clock (odds-only feeds).
+    Live,
+    Final,
+}
+
+impl GamePhase {
+    /// Display/filter order for phase labels.
+    pub const LABELS: [&'static str; 15] = [
+        "PRE", "P1", "Q1", "Q2", "H1", "P2", "Q3", "Q4", "H2", "P3", "HALF", "F2M", "OT", "LIVE",
+        "FINAL",
+    ];
+
+    /// Derive the phase from a score update in a sport with `regulation_periods`
+    /// periods of regulation (see `WinProbTable::regulation_periods`).
+    pub fn from_update(update: &ScoreUpdate, regulation_periods: u8) -> Self {
+        match update.game_status {
+            GameStatus::PreGame => return GamePhase::PreGame,
+            GameStatus::Halftime => return GamePhase::Halftime,
+            GameStatus::Finished => return GamePhase::Final,
+            GameStatus::Live => {}
+        }
+        if update.period == 0 {
+            GamePhase::PreGame
+        } else if update.period > regulation_periods {
+            GamePhase::Overtime
+        } else if regulation_periods.is_multiple_of(2)
+            && update.period == regulation_periods / 2
+            && update.clock_seconds == 0
+        {
+            GamePhase::Halftime
+        } else if update.period == regulation_periods && update.clock_seconds <= 120 {
+            GamePhase::FinalTwoMin
+        } else {
+            GamePhase::Period {
+                number: update.period,
+                periods: regulation_periods,
+            }
+        }
+    }
+
+    /// Short label shown in the TUI and stored in the journal.
+    pub fn label(&self) -> String {
+        match self {
+            GamePhase::PreGame => "PRE".to_string(),
+            GamePhase::Period { number, periods: 2 } => format!("H{}", number),
+            GamePhase::Period { number, periods: 3 } => format!("P{}", number),
+            GamePhase::Period { number, .. } => format!("Q{}", number),
+            GamePhase::Halftime => "HALF".to_string(),
+            GamePhase::FinalTwoMin => "F2M".to_string(),
+            GamePhase::Overtime => "OT".to_string(),
+            GamePhase::Live => "LIVE".to_string(),
+            GamePhase::Final => "FINAL".to_string(),
+        }
+    }
+}
+
 impl ScoreUpdate {
     /// Compute total elapsed seconds from period and clock.
     /// NBA: 4 periods x 12 min (720s each). OT periods are 5 min (300s each).
@@ -68,6 +145,52 @@ impl ScoreUpdate {
             2400 + ot_period * 300 + 300u16.saturating_sub(clock_seconds)
         }
     }
+
+    /// Compute total elapsed seconds for football.
+    /// Football: 4 quarters x 15 min (900s each). OT periods are `ot_period_secs`
+    /// long: 600 for the NFL regular season, 0 for college, whose overtime is
+    /// played without a game clock.
+    pub fn compute_elapsed_football(period: u8, clock_seconds: u16, ot_period_secs: u16) -> u16 {
+        if period == 0 {
+            return 0;
+        }
+        if period <= 4 {
+            let completed = (period - 1) as u16;
+            completed * 900 + 900u16.saturating_sub(clock_seconds)
+        } else {
+            // Overtime: regulation (3600s) + completed OT periods
+            let ot_period = (period - 5) as u16;
+            3600 + ot_period * ot_period_secs + ot_period_secs.saturating_sub(clock_seconds)
+        }
+    }
+
+    /// Compute total elapsed seconds for hockey.
+    /// Hockey: 3 periods x 20 min (1200s each). OT periods are `ot_period_secs`
+    /// long: 300 in the NHL regular season, whose shootout follows as the next
+    /// period, and 1200 in the playoffs.
+    pub fn compute_elapsed_hockey(period: u8, clock_seconds: u16, ot_period_secs: u16) -> u16 {
+        if period == 0 {
+            return 0;
+        }
+        if period <= 3 {
+            let completed = (period - 1) as u16;
+            completed * 1200 + 1200u16.saturating_sub(clock_seconds)
+        } else {
+            // Overtime: regulation (3600s) + completed OT periods
+            let ot_period = (period - 4) as u16;
+            3600 + ot_period * ot_period_secs + ot_period_secs.saturating_sub(clock_seconds)
+        }
+    }
+
+    /// Compute total elapsed seconds for soccer from the running match clock.
+    /// Soccer: 2 halves x 45 min; stoppage time holds at the end of its half.
+    pub fn compute_elapsed_soccer(period: u8, match_clock_secs: u16) -> u16 {
+        match period {
+            0 => 0,
+            1 => match_clock_secs.min(2700),
+            _ => match_clock_secs.clamp(2700, 5400),
+        }
+    }
 }
 
 // ── NBA API Deserialization ──────────────────────────────────────────
@@ -139,12 +262,87 @@ pub fn parse_nba_scoreboard(json: &str) -> anyhow::Result<Vec<ScoreUpdate>> {
             clock_seconds: clock_secs,
             total_elapsed_seconds: elapsed,
             game_status: status,
+            home_red_cards: 0,
+            away_red_cards: 0,
             source: ScoreSource::Nba,
         });
     }
     Ok(updates)
 }
 
+// ── NHL API Deserialization ──────────────────────────────────────────
+
+#[derive(Deserialize)]
+struct NhlScoreboard {
+    games: Vec<NhlGame>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NhlGame {
+    id: u64,
+    game_state: String,
+    home_team: NhlTeam,
+    away_team: NhlTeam,
+    #[serde(default)]
+    period: u8,
+    #[serde(default)]
+    clock: Option<NhlClock>,
+}
+
+#[derive(Deserialize)]
+struct NhlTeam {
+    name: NhlName,
+    /// Absent before the game starts.
+    #[serde(default)]
+    score: u16,
+}
+
+#[derive(Deserialize)]
+struct NhlName {
+    default: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NhlClock {
+    seconds_remaining: u16,
+}
+
+fn nhl_game_status(state: &str) -> GameStatus {
+    match state {
+        "LIVE" | "CRIT" => GameStatus::Live,
+        "FINAL" | "OFF" => GameStatus::Finished,
+        _ => GameStatus::PreGame,
+    }
+}
+
+/// NHL web API scoreboard (`api-web.nhle.com/v1/score/now`). Teams are
+/// reported by nickname ("Bruins"), which the NHL matcher table resolves.
+pub fn parse_nhl_scoreboard(json: &str) -> anyhow::Result<Vec<ScoreUpdate>> {
+    let scoreboard: NhlScoreboard = serde_json::from_str(json)?;
+    let mut updates = Vec::new();
+    for game in scoreboard.games {
+        let clock_secs = game.clock.map_or(0, |c| c.seconds_remaining);
+        let elapsed = ScoreUpdate::compute_elapsed_hockey(game.period, clock_secs, 300);
+        updates.push(ScoreUpdate {
+            game_id: game.id.to_string(),
+            home_team: game.home_team.name.default,
+            away_team: game.away_team.name.default,
+            home_score: game.home_team.score,
+            away_score: game.away_team.score,
+            period: game.period,
+            clock_seconds: clock_secs,
+            total_elapsed_seconds: elapsed,
+            game_status: nhl_game_status(&game.game_state),
+            home_red_cards: 0,
+            away_red_cards: 0,
+            source: ScoreSource::Nhl,
+        });
+    }
+    Ok(updates)
+}
+
 // ── ESPN API Deserialization ─────────────────────────────────────────
 
 #[derive(Deserialize)]
@@ -162,6 +360,22 @@ struct EspnEvent {
 struct EspnCompetition {
     competitors: Vec<EspnCompetitor>,
     status: EspnStatus,
+    /// Match events (goals, cards); soccer only.
+    #[serde(default)]
+    details: Vec<EspnDetail>,
+}
+
+#[derive(Deserialize)]
+struct EspnDetail {
+    #[serde(default, rename = "redCard")]
+    red_card: bool,
+    #[serde(default)]
+    team: Option<EspnTeamRef>,
+}
+
+#[derive(Deserialize)]
+struct EspnTeamRef {
+    id: String,
 }
 
 #[derive(Deserialize)]
@@ -174,6 +388,8 @@ struct EspnCompetitor {
 
 #[derive(Deserialize)]
 struct EspnTeam {
+    #[serde(default)]
+    id: String,
     #[serde(rename = "displayName")]
     display_name: String,
 }
@@ -185,11 +401,19 @@ struct EspnStatus {
     period: u8,
     #[serde(rename = "displayClock")]
     display_clock: String,
+    /// Match clock in seconds; counts up in soccer.
+    #[serde(default)]
+    clock: f64,
 }
 
 #[derive(Deserialize)]
 struct EspnStatusType {
     id: String,
+    /// "pre", "in" or "post".
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    completed: bool,
 }
 
 fn parse_espn_clock(clock: &str) -> Option<u16> {
@@ -201,6 +425,38 @@ fn parse_espn_clock(clock: &str) -> Option<u16> {
 }
 
 pub fn parse_espn_scoreboard(json: &str) -> anyhow::Result<Vec<ScoreUpdate>> {
+    parse_espn_events(json, &["2"], ScoreUpdate::compute_elapsed)
+}
+
+/// ESPN NFL scoreboard. End of quarter ("22") and halftime ("23") are
+/// reported as their own status ids and count as live.
+pub fn parse_espn_nfl_scoreboard(json: &str) -> anyhow::Result<Vec<ScoreUpdate>> {
+    parse_espn_events(json, &["2", "22", "23"], |period, clock| {
+        ScoreUpdate::compute_elapsed_football(period, clock, 600)
+    })
+}
+
+/// ESPN college football scoreboard; like the NFL, but overtime is untimed.
+pub fn parse_espn_college_football_scoreboard(json: &str) -> anyhow::Result<Vec<ScoreUpdate>> {
+    parse_espn_events(json, &["2", "22", "23"], |period, clock| {
+        ScoreUpdate::compute_elapsed_football(period, clock, 0)
+    })
+}
+
+/// ESPN NHL scoreboard. End of period ("22") counts as live.
+pub fn parse_espn_nhl_scoreboard(json: &str) -> anyhow::Result<Vec<ScoreUpdate>> {
+    parse_espn_events(json, &["2", "22"], |period, clock| {
+        ScoreUpdate::compute_elapsed_hockey(period, clock, 300)
+    })
+}
+
+/// Parse an ESPN scoreboard. `live_status_ids` are the status type ids that
+/// mean the game is in play; `elapsed` converts period and clock to seconds.
+fn parse_espn_events(
+    json: &str,
+    live_status_ids: &[&str],
+    elapsed: fn(u8, u16) -> u16,
+) -> anyhow::Result<Vec<ScoreUpdate>> {
     let scoreboard: EspnScoreboard = serde_json::from_str(json)?;
     let mut updates = Vec::new();
     for event in scoreboard.events {
@@ -214,12 +470,12 @@ pub fn parse_espn_scoreboard(json: &str) -> anyhow::Result<Vec<ScoreUpdate>> {
         };
         let status = match comp.status.status_type.id.as_str() {
             "1" => GameStatus::PreGame,
-            "2" => GameStatus::Live,
             "3" => GameStatus::Finished,
+            id if live_status_ids.contains(&id) => GameStatus::Live,
             _ => GameStatus::PreGame,
         };
         let clock_secs = parse_espn_clock(&comp.status.display_clock).unwrap_or(0);
-        let elapsed = ScoreUpdate::compute_elapsed(comp.status.period, clock_secs);
+        let elapsed = elapsed(comp.status.period, clock_secs);
         updates.push(ScoreUpdate {
             game_id: event.id,
             home_team: home.team.display_name.clone(),
@@ -230,18 +486,91 @@ pub fn parse_espn_scoreboard(json: &str) -> anyhow::Result<Vec<ScoreUpdate>> {
             clock_seconds: clock_secs,
             total_elapsed_seconds: elapsed,
             game_status: status,
+            home_red_cards: 0,
+            away_red_cards: 0,
+            source: ScoreSource::Espn,
+        });
+    }
+    Ok(updates)
+}
+
+/// ESPN soccer scoreboard. Soccer's many in-play status ids (halves, stoppage,
+/// halftime) are read from the status state instead; a postponed match is
+/// "post" without being completed. Red cards come from the match details.
+pub fn parse_espn_soccer_scoreboard(json: &str) -> anyhow::Result<Vec<ScoreUpdate>> {
+    let scoreboard: EspnScoreboard = serde_json::from_str(json)?;
+    let mut updates = Vec::new();
+    for event in scoreboard.events {
+        let Some(comp) = event.competitions.first() else {
+            continue;
+        };
+        let home = comp.competitors.iter().find(|c| c.home_away == "home");
+        let away = comp.competitors.iter().find(|c| c.home_away == "away");
+        let (Some(home), Some(away)) = (home, away) else {
+            continue;
+        };
+        let status_type = &comp.status.status_type;
+        let status = match status_type.state.as_str() {
+            "in" => GameStatus::Live,
+            "post" if status_type.completed => GameStatus::Finished,
+            _ => GameStatus::PreGame,
+        };
+        let red_cards = |team_id: &str| {
+            comp.details
+                .iter()
+                .filter(|d| d.red_card && d.team.as_ref().is_some_and(|t| t.id == team_id))
+                .count() as u8
+        };
+        let period = comp.status.period;
+        let elapsed = ScoreUpdate::compute_elapsed_soccer(period, comp.status.clock as u16);
+        let half_end = if period <= 1 { 2700 } else { 5400 };
+        updates.push(ScoreUpdate {
+            game_id: event.id,
+            home_team: home.team.display_name.clone(),
+            away_team: away.team.display_name.clone(),
+            home_score: home.score.parse().unwrap_or(0),
+            away_score: away.score.parse().unwrap_or(0),
+            period,
+            clock_seconds: half_end - elapsed.min(half_end),
+            total_elapsed_seconds: elapsed,
+            game_status: status,
+            home_red_cards: red_cards(&home.team.id),
+            away_red_cards: red_cards(&away.team.id),
             source: ScoreSource::Espn,
         });
     }
     Ok(updates)
 }
 
+/// Parses one scoreboard response.
+pub type ScoreboardParser = fn(&str) -> anyhow::Result<Vec<ScoreUpdate>>;
+
+/// (primary, fallback) scoreboard parsers for a `[score_feed] type`.
+/// Basketball reads the NBA CDN and hockey the NHL web API, each with ESPN
+/// as the fallback; football and soccer read ESPN for both.
+pub fn scoreboard_parsers(feed_type: &str) -> Option<(ScoreboardParser, ScoreboardParser)> {
+    match feed_type {
+        "basketball" => Some((parse_nba_scoreboard, parse_espn_scoreboard)),
+        "nfl" => Some((parse_espn_nfl_scoreboard, parse_espn_nfl_scoreboard)),
+        "nhl" => Some((parse_nhl_scoreboard, parse_espn_nhl_scoreboard)),
+        "soccer" => Some((parse_espn_soccer_scoreboard, parse_espn_soccer_scoreboard)),
+        "college-football" => Some((
+            parse_espn_college_football_scoreboard,
+            parse_espn_college_football_scoreboard,
+        )),
+        _ => None,
+    }
+}
+
 // ── ScorePoller — HTTP Fetching With Failover ──────────────────────
 
 pub struct ScorePoller {
     client: Client,
     nba_url: String,
     espn_url: String,
+    /// Parsers for `nba_url` (primary) and `espn_url` (fallback).
+    primary_parser: ScoreboardParser,
+    fallback_parser: ScoreboardParser,
     timeout: Duration,
     failover_threshold: u32,
     nba_consecutive_failures: u32,
@@ -252,14 +581,24 @@ pub struct ScorePoller {
     last_etag: HashMap<String, String>,
     /// Cached response text per URL, returned on 304 Not Modified
     cached_response: HashMap<String, Vec<ScoreUpdate>>,
+    /// Observed content-refresh cadence per URL
+    cadence: HashMap<String, CadenceTracker>,
 }
 
 impl ScorePoller {
-    pub fn new(nba_url: &str, espn_url: &str, timeout_ms: u64, failover_threshold: u32) -> Self {
+    pub fn new(
+        nba_url: &str,
+        espn_url: &str,
+        timeout_ms: u64,
+        failover_threshold: u32,
+        http: reqwest::ClientBuilder,
+    ) -> Self {
         Self {
-            client: Client::new(),
+            client: http.build().expect("failed to build reqwest client"),
             nba_url: nba_url.to_string(),
             espn_url: espn_url.to_string(),
+            primary_parser: parse_nba_scoreboard,
+            fallback_parser: parse_espn_scoreboard,
             timeout: Duration::from_millis(timeout_ms),
             failover_threshold,
             nba_consecutive_failures: 0,
@@ -267,13 +606,42 @@ impl ScorePoller {
             espn_primary_polls: 0,
             last_etag: HashMap::new(),
             cached_response: HashMap::new(),
+            cadence: HashMap::new(),
         }
     }
 
+    /// Read a different scoreboard format, e.g. from `scoreboard_parsers`.
+    pub fn with_parsers(mut self, primary: ScoreboardParser, fallback: ScoreboardParser) -> Self {
+        self.primary_parser = primary;
+        self.fallback_parser = fallback;
+        self
+    }
+
     pub fn primary_url(&self) -> &str {
         &self.nba_url
     }
 
+    pub fn fallback_url(&self) -> &str {
+        &self.espn_url
+    }
+
+    /// Whether fetches go to the fallback URL first after too many primary
+    /// failures. Never true without a separate fallback URL.
+    pub fn on_fallback(&self) -> bool {
+        self.espn_is_primary && self.espn_url != self.nba_url
+    }
+
+    /// Predicted next content refresh of the active scoreboard after `after`,
+    /// once enough refreshes have been observed to estimate its cadence.
+    pub fn next_expected_refresh(&self, after: Instant) -> Option<Instant> {
+        let url = if self.espn_is_primary {
+            &self.espn_url
+        } else {
+            &self.nba_url
+        };
+        self.cadence.get(url)?.next_refresh_after(after)
+    }
+
     pub async fn fetch(&mut self) -> anyhow::Result<Vec<ScoreUpdate>> {
         // When ESPN is primary, periodically probe NBA API for recovery.
         // Every `failover_threshold` polls, try NBA first instead of ESPN.
@@ -282,7 +650,7 @@ impl ScorePoller {
             if self.espn_primary_polls >= self.failover_threshold {
                 self.espn_primary_polls = 0;
                 let url = self.nba_url.clone();
-                if let Ok(updates) = self.fetch_and_parse(&url, parse_nba_scoreboard).await {
+                if let Ok(updates) = self.fetch_and_parse(&url, self.primary_parser).await {
                     tracing::info!("NBA API recovered, swapping back to primary");
                     self.espn_is_primary = false;
                     self.nba_consecutive_failures = 0;
@@ -296,15 +664,15 @@ impl ScorePoller {
             (
                 self.espn_url.clone(),
                 self.nba_url.clone(),
-                parse_espn_scoreboard as fn(&str) -> _,
-                parse_nba_scoreboard as fn(&str) -> _,
+                self.fallback_parser,
+                self.primary_parser,
             )
         } else {
             (
                 self.nba_url.clone(),
                 self.espn_url.clone(),
-                parse_nba_scoreboard as fn(&str) -> _,
-                parse_espn_scoreboard as fn(&str) -> _,
+                self.primary_parser,
+                self.fallback_parser,
             )
         };
 
@@ -338,7 +706,7 @@ impl ScorePoller {
     async fn fetch_and_parse(
         &mut self,
         url: &str,
-        parser: fn(&str) -> anyhow::Result<Vec<ScoreUpdate>>,
+        parser: ScoreboardParser,
     ) -> anyhow::Result<Vec<ScoreUpdate>> {
         let mut req = self.client.get(url).timeout(self.timeout);
         if let Some(etag) = self.last_etag.get(url) {
@@ -361,6 +729,16 @@ impl ScorePoller {
 
         let text = resp.text().await?;
         let updates = parser(&text)?;
+        if self
+            .cached_response
+            .get(url)
+            .is_some_and(|cached| *cached != updates)
+        {
+            self.cadence
+                .entry(url.to_string())
+                .or_default()
+                .record_change(Instant::now());
+        }
         self.cached_response
             .insert(url.to_string(), updates.clone());
         Ok(updates)
@@ -439,6 +817,67 @@ impl CollegeScorePoller {
 mod tests {
     use super::*;
 
+    fn live(period: u8, clock_seconds: u16) -> ScoreUpdate {
+        ScoreUpdate {
+            game_id: "g1".to_string(),
+            home_team: "Home".to_string(),
+            away_team: "Away".to_string(),
+            home_score: 10,
+            away_score: 8,
+            period,
+            clock_seconds,
+            total_elapsed_seconds: 0,
+            game_status: GameStatus::Live,
+            home_red_cards: 0,
+            away_red_cards: 0,
+            source: ScoreSource::Espn,
+        }
+    }
+
+    #[test]
+    fn test_phase_quarters() {
+        assert_eq!(GamePhase::from_update(&live(1, 600), 4).label(), "Q1");
+        assert_eq!(GamePhase::from_update(&live(3, 30), 4).label(), "Q3");
+        assert_eq!(GamePhase::from_update(&live(4, 500), 4).label(), "Q4");
+    }
+
+    #[test]
+    fn test_phase_halves_for_college() {
+        assert_eq!(GamePhase::from_update(&live(1, 900), 2).label(), "H1");
+        assert_eq!(GamePhase::from_update(&live(2, 900), 2).label(), "H2");
+    }
+
+    #[test]
+    fn test_phase_halftime_final_two_min_and_ot() {
+        assert_eq!(GamePhase::from_update(&live(2, 0), 4), GamePhase::Halftime);
+        assert_eq!(GamePhase::from_update(&live(1, 0), 2), GamePhase::Halftime);
+        assert_eq!(
+            GamePhase::from_update(&live(4, 120), 4),
+            GamePhase::FinalTwoMin
+        );
+        assert_eq!(
+            GamePhase::from_update(&live(2, 45), 2),
+            GamePhase::FinalTwoMin
+        );
+        assert_eq!(
+            GamePhase::from_update(&live(5, 200), 4),
+            GamePhase::Overtime
+        );
+        assert_eq!(
+            GamePhase::from_update(&live(3, 200), 2),
+            GamePhase::Overtime
+        );
+    }
+
+    #[test]
+    fn test_phase_from_status() {
+        let mut u = live(0, 0);
+        u.game_status = GameStatus::PreGame;
+        assert_eq!(GamePhase::from_update(&u, 4).label(), "PRE");
+        u.game_status = GameStatus::Finished;
+        assert_eq!(GamePhase::from_update(&u, 4).label(), "FINAL");
+    }
+
     #[test]
     fn test_elapsed_period_zero() {
         assert_eq!(ScoreUpdate::compute_elapsed(0, 0), 0);
@@ -597,18 +1036,36 @@ mod tests {
 
     #[test]
     fn test_failover_threshold_tracking() {
-        let mut poller = ScorePoller::new("http://fake-nba", "http://fake-espn", 1000, 3);
+        let mut poller = ScorePoller::new(
+            "http://fake-nba",
+            "http://fake-espn",
+            1000,
+            3,
+            Client::builder(),
+        );
         assert!(!poller.espn_is_primary);
         poller.nba_consecutive_failures = 2;
         assert!(!poller.espn_is_primary);
         poller.nba_consecutive_failures = 3;
         assert!(poller.nba_consecutive_failures >= poller.failover_threshold);
+
+        // Without a separate fallback URL there is nothing to fail over to
+        let mut single = ScorePoller::new("http://fake", "http://fake", 1000, 3, Client::builder());
+        single.espn_is_primary = true;
+        assert!(!single.on_fallback());
     }
 
     #[test]
     fn test_recovery_probe_counter() {
-        let mut poller = ScorePoller::new("http://fake-nba", "http://fake-espn", 1000, 3);
+        let mut poller = ScorePoller::new(
+            "http://fake-nba",
+            "http://fake-espn",
+            1000,
+            3,
+            Client::builder(),
+        );
         poller.espn_is_primary = true;
+        assert!(poller.on_fallback());
         poller.espn_primary_polls = 0;
         // Counter increments each poll cycle; resets at failover_threshold
         poller.espn_primary_polls += 1;
@@ -661,4 +1118,132 @@ mod tests {
         // Clock exceeding half length should saturate to 0 elapsed-in-period
         assert_eq!(ScoreUpdate::compute_elapsed_college(1, 1500), 0);
     }
+
+    #[test]
+    fn test_football_elapsed_quarters_and_overtime() {
+        assert_eq!(ScoreUpdate::compute_elapsed_football(1, 900, 600), 0);
+        assert_eq!(ScoreUpdate::compute_elapsed_football(2, 0, 600), 1800);
+        assert_eq!(ScoreUpdate::compute_elapsed_football(4, 120, 600), 3480);
+        // NFL overtime: 10-minute period after 3600s of regulation
+        assert_eq!(ScoreUpdate::compute_elapsed_football(5, 300, 600), 3900);
+        // College overtime has no clock; elapsed stays at end of regulation
+        assert_eq!(ScoreUpdate::compute_elapsed_football(6, 0, 0), 3600);
+    }
+
+    #[test]
+    fn test_parse_espn_football_scoreboard() {
+        let json = |status_id: &str, period: u8, clock: &str| {
+            format!(
+                r#"{{"events": [{{"id": "401671800", "competitions": [{{
+                    "competitors": [
+                        {{"homeAway": "home", "team": {{"displayName": "Kansas City Chiefs"}}, "score": "17"}},
+                        {{"homeAway": "away", "team": {{"displayName": "Buffalo Bills"}}, "score": "10"}}
+                    ],
+                    "status": {{"type": {{"id": "{status_id}"}}, "period": {period}, "displayClock": "{clock}"}}
+                }}]}}]}}"#
+            )
+        };
+
+        let u = &parse_espn_nfl_scoreboard(&json("2", 3, "7:30")).unwrap()[0];
+        assert_eq!(u.home_team, "Kansas City Chiefs");
+        assert_eq!((u.home_score, u.away_score), (17, 10));
+        assert_eq!(u.total_elapsed_seconds, 2250);
+        assert_eq!(u.game_status, GameStatus::Live);
+
+        // Halftime is its own ESPN status but still a live game
+        let u = &parse_espn_nfl_scoreboard(&json("23", 2, "0:00")).unwrap()[0];
+        assert_eq!(u.game_status, GameStatus::Live);
+        assert_eq!(u.total_elapsed_seconds, 1800);
+        assert_eq!(GamePhase::from_update(u, 4), GamePhase::Halftime);
+
+        let u = &parse_espn_college_football_scoreboard(&json("2", 6, "0:00")).unwrap()[0];
+        assert_eq!(u.total_elapsed_seconds, 3600);
+
+        let (primary, _) = scoreboard_parsers("nfl").unwrap();
+        assert_eq!(
+            primary(&json("3", 4, "0:00")).unwrap()[0].game_status,
+            GameStatus::Finished
+        );
+        assert!(scoreboard_parsers("cricket").is_none());
+    }
+
+    #[test]
+    fn test_parse_nhl_scoreboard() {
+        let json = r#"{"games": [
+            {"id": 2025020345, "gameState": "LIVE", "period": 4,
+             "clock": {"timeRemaining": "03:20", "secondsRemaining": 200},
+             "homeTeam": {"name": {"default": "Bruins"}, "score": 2},
+             "awayTeam": {"name": {"default": "Maple Leafs"}, "score": 2}},
+            {"id": 2025020346, "gameState": "FUT",
+             "homeTeam": {"name": {"default": "Kraken"}},
+             "awayTeam": {"name": {"default": "Oilers"}}}
+        ]}"#;
+        let updates = parse_nhl_scoreboard(json).unwrap();
+        let u = &updates[0];
+        assert_eq!(u.game_id, "2025020345");
+        assert_eq!(
+            (u.home_team.as_str(), u.away_team.as_str()),
+            ("Bruins", "Maple Leafs")
+        );
+        // 5-minute sudden-death OT after 3600s of regulation
+        assert_eq!(u.total_elapsed_seconds, 3700);
+        assert_eq!(u.game_status, GameStatus::Live);
+        assert_eq!(GamePhase::from_update(u, 3), GamePhase::Overtime);
+        assert_eq!(updates[1].game_status, GameStatus::PreGame);
+        assert_eq!((updates[1].home_score, updates[1].period), (0, 0));
+
+        assert_eq!(ScoreUpdate::compute_elapsed_hockey(2, 600, 300), 1800);
+        assert_eq!(ScoreUpdate::compute_elapsed_hockey(3, 0, 300), 3600);
+        // Playoff OT periods run the full 20 minutes
+        assert_eq!(ScoreUpdate::compute_elapsed_hockey(5, 1200, 1200), 4800);
+
+        let mut u = u.clone();
+        u.period = 2;
+        u.clock_seconds = 0;
+        // Three periods have no halftime
+        assert_eq!(GamePhase::from_update(&u, 3).label(), "P2");
+    }
+
+    #[test]
+    fn test_parse_espn_soccer_scoreboard() {
+        let json = |state: &str, completed: bool, period: u8, clock: f64| {
+            format!(
+                r#"{{"events": [{{"id": "704512", "competitions": [{{
+                    "competitors": [
+                        {{"homeAway": "home", "team": {{"id": "359", "displayName": "Arsenal"}}, "score": "1"}},
+                        {{"homeAway": "away", "team": {{"id": "364", "displayName": "Liverpool"}}, "score": "1"}}
+                    ],
+                    "status": {{"type": {{"id": "2", "state": "{state}", "completed": {completed}}},
+                                "period": {period}, "displayClock": "67'", "clock": {clock}}},
+                    "details": [
+                        {{"type": {{"text": "Goal"}}, "redCard": false, "team": {{"id": "359"}}}},
+                        {{"type": {{"text": "Red Card"}}, "redCard": true, "team": {{"id": "364"}}}}
+                    ]
+                }}]}}]}}"#
+            )
+        };
+
+        let u = &parse_espn_soccer_scoreboard(&json("in", false, 2, 4020.0)).unwrap()[0];
+        assert_eq!(
+            (u.home_team.as_str(), u.away_team.as_str()),
+            ("Arsenal", "Liverpool")
+        );
+        assert_eq!(u.game_status, GameStatus::Live);
+        assert_eq!(u.total_elapsed_seconds, 4020);
+        assert_eq!(u.clock_seconds, 1380);
+        assert_eq!((u.home_red_cards, u.away_red_cards), (0, 1));
+
+        // First-half stoppage time holds at 45 minutes
+        let u = &parse_espn_soccer_scoreboard(&json("in", false, 1, 2820.0)).unwrap()[0];
+        assert_eq!(u.total_elapsed_seconds, 2700);
+        assert_eq!(GamePhase::from_update(u, 2), GamePhase::Halftime);
+
+        let (primary, _) = scoreboard_parsers("soccer").unwrap();
+        let u = &primary(&json("post", true, 2, 5700.0)).unwrap()[0];
+        assert_eq!(u.game_status, GameStatus::Finished);
+        assert_eq!(u.total_elapsed_seconds, 5400);
+        // Postponed matches are "post" but never completed
+        let u = &primary(&json("post", false, 0, 0.0)).unwrap()[0];
+        assert_eq!(u.game_status, GameStatus::PreGame);
+    }
 }
@@ -1,14 +1,18 @@
-//! Bovada sportsbook odds feed via their public JSON API.
+//! Scraped sportsbook odds, driven by a per-book `ScrapeConfig`.
 //!
-//! Endpoint: /services/sports/event/coupon/events/A/description/{sport}/{league}
-//! Returns structured JSON with events, competitors, and moneyline odds.
+//! A book is described by its URL and selectors into its JSON (or the JSON
+//! embedded in its HTML page) rather than by an adapter. Bovada's public
+//! coupon API is the built-in default:
+//! /services/sports/event/coupon/events/A/description/{sport}/{league}
 
 use super::types::*;
 use super::OddsFeed;
+use crate::config::{ScrapeConfig, ScrapeOddsFormat, ScrapeTimeFormat};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use reqwest::Client;
-use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
 use std::time::Duration;
 
 const BOVADA_BASE: &str = "https://www.bovada.lv/services/sports/event/coupon/events/A/description";
@@ -25,69 +29,129 @@ fn bovada_sport_path(sport: &str) -> Option<&'static str> {
     }
 }
 
-// ── Bovada JSON response types ────────────────────────────────────────
-
-#[derive(Debug, Deserialize)]
-pub struct BovadaResponse {
-    #[serde(default)]
-    pub events: Vec<BovadaEvent>,
+/// Bovada's coupon API as a `ScrapeConfig`. A `base_url` starting with
+/// "http" is fetched as-is for every sport.
+pub fn bovada_config(base_url: &str) -> ScrapeConfig {
+    let sports = [
+        "college-basketball",
+        "college-basketball-womens",
+        "basketball",
+        "ice-hockey",
+        "baseball",
+        "mma",
+    ];
+    ScrapeConfig {
+        bookmaker: "Bovada".to_string(),
+        url: if base_url.starts_with("http") {
+            base_url.to_string()
+        } else {
+            format!("{}/{{path}}", BOVADA_BASE)
+        },
+        sport_paths: sports
+            .iter()
+            .filter_map(|s| Some((s.to_string(), bovada_sport_path(s)?.to_string())))
+            .collect(),
+        script_id: None,
+        // An array of path sections, each with its events
+        events: "[*].events[*]".to_string(),
+        event_id: "id".to_string(),
+        home_team: "competitors[home=true].name".to_string(),
+        away_team: "competitors[home=false].name".to_string(),
+        commence_time: Some("startTime".to_string()),
+        time_format: ScrapeTimeFormat::EpochMs,
+        // "2W-12" is the 2-way moneyline
+        outcomes: Some("displayGroups[*].markets[key=2W-12].outcomes[*]".to_string()),
+        outcome_name: "description".to_string(),
+        outcome_price: "price.american".to_string(),
+        home_odds: None,
+        away_odds: None,
+        draw_odds: None,
+        odds_format: ScrapeOddsFormat::American,
+    }
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct BovadaEvent {
-    pub id: String,
-    #[serde(default)]
-    pub description: String,
-    #[serde(default)]
-    pub start_time: i64,
-    #[serde(default)]
-    pub live: bool,
-    #[serde(default)]
-    pub competitors: Vec<BovadaCompetitor>,
-    #[serde(default)]
-    pub display_groups: Vec<BovadaDisplayGroup>,
-}
+// ── Selectors ─────────────────────────────────────────────────────────
 
-#[derive(Debug, Deserialize)]
-pub struct BovadaCompetitor {
-    #[serde(default)]
-    pub name: String,
-    #[serde(default)]
-    pub home: bool,
+/// Every value `selector` reaches from `value`: a JSON pointer, or a dotted
+/// path of `field`, `field[N]`, `field[*]` and `field[key=value]` segments.
+fn select<'a>(value: &'a Value, selector: &str) -> Vec<&'a Value> {
+    let selector = selector.trim();
+    if selector.starts_with('/') {
+        return value.pointer(selector).into_iter().collect();
+    }
+    let mut current = vec![value];
+    for segment in split_segments(selector) {
+        let (field, brackets) = segment
+            .split_once('[')
+            .map_or((segment, ""), |(f, rest)| (f, rest));
+        if !field.is_empty() {
+            current = current.into_iter().filter_map(|v| v.get(field)).collect();
+        }
+        for index in brackets.split('[').filter(|b| !b.is_empty()) {
+            let index = index.trim_end_matches(']');
+            current = current
+                .into_iter()
+                .flat_map(|v| v.as_array().into_iter().flatten().enumerate())
+                .filter(|(i, v)| match index.split_once('=') {
+                    Some((key, want)) => v.get(key).and_then(text).as_deref() == Some(want),
+                    None => index == "*" || index.parse() == Ok(*i),
+                })
+                .map(|(_, v)| v)
+                .collect();
+        }
+    }
+    current
 }
 
-#[derive(Debug, Deserialize)]
-pub struct BovadaDisplayGroup {
-    #[serde(default)]
-    pub markets: Vec<BovadaMarket>,
+/// Path segments, splitting on dots outside brackets (filter values may
+/// contain dots).
+fn split_segments(selector: &str) -> Vec<&str> {
+    let mut segments = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in selector.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            '.' if depth == 0 => {
+                segments.push(&selector[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    segments.push(&selector[start..]);
+    segments.retain(|s| !s.is_empty());
+    segments
 }
 
-#[derive(Debug, Deserialize)]
-pub struct BovadaMarket {
-    #[serde(default)]
-    pub key: String,
-    #[serde(default)]
-    pub outcomes: Vec<BovadaOutcome>,
+/// `select`, with any arrays reached replaced by their elements.
+fn select_items<'a>(value: &'a Value, selector: &str) -> Vec<&'a Value> {
+    select(value, selector)
+        .into_iter()
+        .flat_map(|v| match v {
+            Value::Array(items) => items.iter().collect(),
+            other => vec![other],
+        })
+        .collect()
 }
 
-#[derive(Debug, Deserialize)]
-pub struct BovadaOutcome {
-    #[serde(default)]
-    pub description: String,
-    #[serde(default)]
-    pub price: BovadaPrice,
+fn select_one<'a>(value: &'a Value, selector: &str) -> Option<&'a Value> {
+    select(value, selector).into_iter().next()
 }
 
-#[derive(Debug, Deserialize, Default)]
-pub struct BovadaPrice {
-    #[serde(default)]
-    pub american: String,
+/// A scalar as text: strings trimmed, numbers and booleans printed.
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
 }
 
 // ── Parsing ───────────────────────────────────────────────────────────
 
-/// Parse American odds string from Bovada: "-150", "+130", "EVEN".
+/// Parse American odds string: "-150", "+130", "EVEN".
 fn parse_american_odds(s: &str) -> Option<f64> {
     let s = s.trim();
     if s.eq_ignore_ascii_case("EVEN") {
@@ -96,71 +160,130 @@ fn parse_american_odds(s: &str) -> Option<f64> {
     s.parse::<f64>().ok()
 }
 
-/// Parse Bovada JSON response into `Vec<OddsUpdate>`.
-/// Public for unit testing with fixtures.
-pub fn parse_bovada_response(json: &str, sport: &str) -> Result<Vec<OddsUpdate>> {
-    // Bovada returns an array of path-sections; each has events.
-    let sections: Vec<BovadaResponse> =
-        serde_json::from_str(json).context("failed to parse Bovada JSON")?;
+/// A price in `format`, as American odds.
+fn parse_price(value: &Value, format: ScrapeOddsFormat) -> Option<f64> {
+    let raw = text(value)?;
+    match format {
+        ScrapeOddsFormat::American => parse_american_odds(&raw),
+        ScrapeOddsFormat::Decimal => {
+            let decimal: f64 = raw.parse().ok()?;
+            if decimal >= 2.0 {
+                Some((decimal - 1.0) * 100.0)
+            } else if decimal > 1.0 {
+                Some(-100.0 / (decimal - 1.0))
+            } else {
+                None
+            }
+        }
+    }
+    .filter(|odds| odds.is_finite())
+}
+
+/// A start time in `format`, as RFC 3339 UTC.
+fn parse_time(value: &Value, format: ScrapeTimeFormat) -> Option<String> {
+    let raw = text(value)?;
+    let time = match format {
+        ScrapeTimeFormat::Rfc3339 => chrono::DateTime::parse_from_rfc3339(&raw)
+            .ok()?
+            .with_timezone(&chrono::Utc),
+        ScrapeTimeFormat::EpochMs => chrono::DateTime::from_timestamp_millis(raw.parse().ok()?)?,
+        ScrapeTimeFormat::EpochS => chrono::DateTime::from_timestamp(raw.parse().ok()?, 0)?,
+    };
+    Some(time.to_rfc3339())
+}
+
+/// The JSON inside `<script id="{id}">` of an HTML page.
+fn embedded_json<'a>(html: &'a str, id: &str) -> Option<&'a str> {
+    let at = [format!("id=\"{}\"", id), format!("id='{}'", id)]
+        .iter()
+        .find_map(|attr| html.find(attr.as_str()))?;
+    let start = at + html[at..].find('>')? + 1;
+    let end = start + html[start..].find("</script>")?;
+    Some(html[start..end].trim())
+}
+
+/// Parse a book's response into `Vec<OddsUpdate>` as `config` describes.
+/// Events missing an id, a team or either side's price are skipped.
+pub fn parse_scraped_response(
+    body: &str,
+    config: &ScrapeConfig,
+    sport: &str,
+) -> Result<Vec<OddsUpdate>> {
+    let json = match &config.script_id {
+        Some(id) => embedded_json(body, id)
+            .with_context(|| format!("{} page has no script #{}", config.bookmaker, id))?,
+        None => body,
+    };
+    let root: Value = serde_json::from_str(json)
+        .with_context(|| format!("failed to parse {} JSON", config.bookmaker))?;
 
     let now = chrono::Utc::now().to_rfc3339();
     let mut updates = Vec::new();
 
-    for section in &sections {
-        for event in &section.events {
-            let home = event.competitors.iter().find(|c| c.home);
-            let away = event.competitors.iter().find(|c| !c.home);
-            let (Some(home), Some(away)) = (home, away) else {
-                continue;
-            };
-
-            // Find moneyline market (key "2W-12" = 2-way moneyline)
-            let moneyline = event
-                .display_groups
-                .iter()
-                .flat_map(|dg| &dg.markets)
-                .find(|m| m.key == "2W-12");
-
-            let Some(ml) = moneyline else { continue };
-            if ml.outcomes.len() < 2 {
-                continue;
+    for event in select_items(&root, &config.events) {
+        let field = |selector: &str| select_one(event, selector).and_then(text);
+        let (Some(id), Some(home), Some(away)) = (
+            field(&config.event_id),
+            field(&config.home_team),
+            field(&config.away_team),
+        ) else {
+            continue;
+        };
+
+        let price = |selector: &Option<String>| {
+            let selector = selector.as_deref()?;
+            select_one(event, selector).and_then(|v| parse_price(v, config.odds_format))
+        };
+        let mut home_odds = price(&config.home_odds);
+        let mut away_odds = price(&config.away_odds);
+        let mut draw_odds = price(&config.draw_odds);
+        if let Some(outcomes) = &config.outcomes {
+            for outcome in select_items(event, outcomes) {
+                let (Some(name), Some(odds)) = (
+                    select_one(outcome, &config.outcome_name).and_then(text),
+                    select_one(outcome, &config.outcome_price)
+                        .and_then(|v| parse_price(v, config.odds_format)),
+                ) else {
+                    continue;
+                };
+                let slot = if name.eq_ignore_ascii_case(&home) {
+                    &mut home_odds
+                } else if name.eq_ignore_ascii_case(&away) {
+                    &mut away_odds
+                } else if name.eq_ignore_ascii_case("draw") || name.eq_ignore_ascii_case("tie") {
+                    &mut draw_odds
+                } else {
+                    continue;
+                };
+                slot.get_or_insert(odds);
             }
-
-            let home_odds = ml
-                .outcomes
-                .iter()
-                .find(|o| o.description == home.name)
-                .and_then(|o| parse_american_odds(&o.price.american));
-            let away_odds = ml
-                .outcomes
-                .iter()
-                .find(|o| o.description == away.name)
-                .and_then(|o| parse_american_odds(&o.price.american));
-
-            let (Some(h), Some(a)) = (home_odds, away_odds) else {
-                continue;
-            };
-
-            // Convert epoch millis to RFC3339
-            let commence = chrono::DateTime::from_timestamp_millis(event.start_time)
-                .map(|dt| dt.to_rfc3339())
-                .unwrap_or_default();
-
-            updates.push(OddsUpdate {
-                event_id: event.id.clone(),
-                sport: sport.to_string(),
-                home_team: home.name.clone(),
-                away_team: away.name.clone(),
-                commence_time: commence,
-                bookmakers: vec![BookmakerOdds {
-                    name: "Bovada".to_string(),
-                    home_odds: h,
-                    away_odds: a,
-                    draw_odds: None,
-                    last_update: now.clone(),
-                }],
-            });
         }
+
+        let (Some(h), Some(a)) = (home_odds, away_odds) else {
+            continue;
+        };
+
+        let commence = config
+            .commence_time
+            .as_deref()
+            .and_then(|selector| select_one(event, selector))
+            .and_then(|v| parse_time(v, config.time_format))
+            .unwrap_or_default();
+
+        updates.push(OddsUpdate {
+            event_id: id,
+            sport: sport.to_string(),
+            home_team: home,
+            away_team: away,
+            commence_time: commence,
+            bookmakers: vec![BookmakerOdds {
+                name: config.bookmaker.clone(),
+                home_odds: h,
+                away_odds: a,
+                draw_odds,
+                last_update: now.clone(),
+            }],
+        });
     }
 
     Ok(updates)
@@ -170,14 +293,31 @@ pub fn parse_bovada_response(json: &str, sport: &str) -> Result<Vec<OddsUpdate>>
 
 pub struct ScrapedOddsFeed {
     client: Client,
-    base_url: String,
+    config: ScrapeConfig,
     max_retries: u32,
-    cached: Vec<OddsUpdate>,
+    /// Last non-empty poll per sport.
+    cached: HashMap<String, Vec<OddsUpdate>>,
 }
 
 impl ScrapedOddsFeed {
-    pub fn new(base_url: &str, timeout_ms: u64, max_retries: u32) -> Self {
-        let client = Client::builder()
+    /// Bovada, from its coupon API (or `base_url`, if a full URL).
+    pub fn new(
+        base_url: &str,
+        timeout_ms: u64,
+        max_retries: u32,
+        http: reqwest::ClientBuilder,
+    ) -> Self {
+        Self::with_config(bovada_config(base_url), timeout_ms, max_retries, http)
+    }
+
+    /// Any book described by `config`.
+    pub fn with_config(
+        config: ScrapeConfig,
+        timeout_ms: u64,
+        max_retries: u32,
+        http: reqwest::ClientBuilder,
+    ) -> Self {
+        let client = http
             .timeout(Duration::from_millis(timeout_ms))
             .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36")
             .build()
@@ -185,18 +325,19 @@ impl ScrapedOddsFeed {
 
         Self {
             client,
-            base_url: base_url.to_string(),
+            config,
             max_retries,
-            cached: Vec::new(),
+            cached: HashMap::new(),
         }
     }
 
     fn build_url(&self, sport: &str) -> Option<String> {
-        if self.base_url.starts_with("http") {
-            // Full URL override from config
-            Some(self.base_url.clone())
+        let url = self.config.url.replace("{sport}", sport);
+        if url.contains("{path}") {
+            let path = self.config.sport_paths.get(sport)?;
+            Some(url.replace("{path}", path))
         } else {
-            bovada_sport_path(sport).map(|path| format!("{}/{}", BOVADA_BASE, path))
+            Some(url)
         }
     }
 }
@@ -204,9 +345,11 @@ impl ScrapedOddsFeed {
 #[async_trait]
 impl OddsFeed for ScrapedOddsFeed {
     async fn fetch_odds(&mut self, sport: &str) -> Result<Vec<OddsUpdate>> {
+        let book = self.config.bookmaker.clone();
         let url = self
             .build_url(sport)
-            .with_context(|| format!("Bovada does not support sport: {}", sport))?;
+            .with_context(|| format!("{} does not support sport: {}", book, sport))?;
+        let cached = self.cached.get(sport).filter(|c| !c.is_empty()).cloned();
 
         let mut last_err = None;
         for attempt in 0..=self.max_retries {
@@ -219,38 +362,41 @@ impl OddsFeed for ScrapedOddsFeed {
                     if !resp.status().is_success() {
                         let status = resp.status();
                         let body = resp.text().await.unwrap_or_default();
-                        last_err = Some(anyhow::anyhow!("Bovada HTTP {} : {}", status, body));
+                        last_err = Some(anyhow::anyhow!("{} HTTP {} : {}", book, status, body));
                         continue;
                     }
-                    let text = resp.text().await.context("Bovada response read failed")?;
-                    match parse_bovada_response(&text, sport) {
-                        Ok(updates) if updates.is_empty() && !self.cached.is_empty() => {
-                            tracing::warn!("Bovada returned 0 events, using cache");
-                            return Ok(self.cached.clone());
+                    let text = resp
+                        .text()
+                        .await
+                        .with_context(|| format!("{} response read failed", book))?;
+                    match parse_scraped_response(&text, &self.config, sport) {
+                        Ok(updates) if updates.is_empty() && cached.is_some() => {
+                            tracing::warn!(book = %book, "scraped book returned 0 events, using cache");
+                            return Ok(cached.unwrap_or_default());
                         }
                         Ok(updates) => {
-                            self.cached = updates.clone();
+                            self.cached.insert(sport.to_string(), updates.clone());
                             return Ok(updates);
                         }
                         Err(e) => {
-                            tracing::warn!(attempt, error = %e, "Bovada parse failed");
+                            tracing::warn!(book = %book, attempt, error = %e, "scraped book parse failed");
                             last_err = Some(e);
                         }
                     }
                 }
                 Err(e) => {
-                    tracing::warn!(attempt, error = %e, "Bovada request failed");
+                    tracing::warn!(book = %book, attempt, error = %e, "scraped book request failed");
                     last_err = Some(e.into());
                 }
             }
         }
 
         // All retries exhausted — return cache if available
-        if !self.cached.is_empty() {
-            tracing::warn!("Bovada fetch exhausted retries, returning cached data");
-            Ok(self.cached.clone())
+        if let Some(cached) = cached {
+            tracing::warn!(book = %book, "scraped book fetch exhausted retries, returning cached data");
+            Ok(cached)
         } else {
-            Err(last_err.unwrap_or_else(|| anyhow::anyhow!("Bovada fetch failed")))
+            Err(last_err.unwrap_or_else(|| anyhow::anyhow!("{} fetch failed", book)))
         }
     }
 
@@ -294,6 +440,10 @@ mod tests {
         assert_eq!(bovada_sport_path("curling"), None);
     }
 
+    fn parse_bovada(json: &str) -> Vec<OddsUpdate> {
+        parse_scraped_response(json, &bovada_config(""), "college-basketball").unwrap()
+    }
+
     fn fixture_json() -> &'static str {
         r#"[{
             "path": [],
@@ -354,13 +504,13 @@ mod tests {
 
     #[test]
     fn test_parse_bovada_response_basic() {
-        let updates = parse_bovada_response(fixture_json(), "college-basketball").unwrap();
+        let updates = parse_bovada(fixture_json());
         assert_eq!(updates.len(), 2);
     }
 
     #[test]
     fn test_parse_bovada_response_teams() {
-        let updates = parse_bovada_response(fixture_json(), "college-basketball").unwrap();
+        let updates = parse_bovada(fixture_json());
         let boise = &updates[0];
         assert_eq!(boise.home_team, "Boise State");
         assert_eq!(boise.away_team, "UC Riverside");
@@ -368,7 +518,7 @@ mod tests {
 
     #[test]
     fn test_parse_bovada_response_odds() {
-        let updates = parse_bovada_response(fixture_json(), "college-basketball").unwrap();
+        let updates = parse_bovada(fixture_json());
         let boise = &updates[0];
         let bm = &boise.bookmakers[0];
         assert_eq!(bm.name, "Bovada");
@@ -379,19 +529,19 @@ mod tests {
 
     #[test]
     fn test_parse_bovada_response_commence_time() {
-        let updates = parse_bovada_response(fixture_json(), "college-basketball").unwrap();
+        let updates = parse_bovada(fixture_json());
         assert!(updates[0].commence_time.contains("2025-01-31"));
     }
 
     #[test]
     fn test_parse_bovada_response_sport() {
-        let updates = parse_bovada_response(fixture_json(), "college-basketball").unwrap();
+        let updates = parse_bovada(fixture_json());
         assert_eq!(updates[0].sport, "college-basketball");
     }
 
     #[test]
     fn test_parse_bovada_response_event_id() {
-        let updates = parse_bovada_response(fixture_json(), "college-basketball").unwrap();
+        let updates = parse_bovada(fixture_json());
         assert_eq!(updates[0].event_id, "21494924");
         assert_eq!(updates[1].event_id, "21494925");
     }
@@ -399,7 +549,7 @@ mod tests {
     #[test]
     fn test_parse_bovada_empty_events() {
         let json = r#"[{"path": [], "events": []}]"#;
-        let updates = parse_bovada_response(json, "college-basketball").unwrap();
+        let updates = parse_bovada(json);
         assert!(updates.is_empty());
     }
 
@@ -424,7 +574,7 @@ mod tests {
                 }]
             }]
         }]"#;
-        let updates = parse_bovada_response(json, "college-basketball").unwrap();
+        let updates = parse_bovada(json);
         assert!(updates.is_empty());
     }
 
@@ -452,11 +602,99 @@ mod tests {
                 }]
             }]
         }]"#;
-        let updates = parse_bovada_response(json, "college-basketball").unwrap();
+        let updates = parse_bovada(json);
         assert_eq!(updates.len(), 1);
         assert!((updates[0].bookmakers[0].home_odds - 100.0).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_select_paths_filters_and_pointers() {
+        let json: Value = serde_json::from_str(
+            r#"{"data": {"games": [
+                {"id": 1, "teams": [{"side": "home", "name": "A"}, {"side": "away", "name": "B"}]},
+                {"id": 2, "teams": [{"side": "home", "name": "C"}, {"side": "away", "name": "D"}]}
+            ]}}"#,
+        )
+        .unwrap();
+        assert_eq!(select(&json, "data.games[*]").len(), 2);
+        assert_eq!(
+            select(&json, "data.games[*].teams[side=away].name"),
+            vec!["B", "D"]
+        );
+        // Indexes count within each array, not across them
+        assert_eq!(select(&json, "data.games[*].teams[0].name"), vec!["A", "C"]);
+        assert_eq!(select(&json, "/data/games/1/id"), vec![2]);
+        assert!(select(&json, "data.players[*]").is_empty());
+        assert!(select(&json, "/data/missing").is_empty());
+    }
+
+    #[test]
+    fn test_parse_configured_book_from_embedded_json() {
+        let config: ScrapeConfig = toml::from_str(
+            r#"
+            bookmaker = "ExampleBook"
+            url = "https://example.com/{sport}"
+            script_id = "__NEXT_DATA__"
+            events = "/props/pageProps/events"
+            event_id = "eventId"
+            home_team = "participants[home=true].name"
+            away_team = "participants[home=false].name"
+            commence_time = "kickoff"
+            outcomes = "markets[type=1X2].selections[*]"
+            outcome_name = "label"
+            outcome_price = "odds"
+            odds_format = "decimal"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.time_format, ScrapeTimeFormat::Rfc3339);
+        let html = r#"<html><body><div id="root"></div>
+            <script id="__NEXT_DATA__" type="application/json">
+            {"props": {"pageProps": {"events": [
+                {"eventId": "e1", "kickoff": "2026-03-14T15:00:00+01:00",
+                 "participants": [{"name": "Arsenal", "home": true}, {"name": "Chelsea", "home": false}],
+                 "markets": [{"type": "1X2", "selections": [
+                    {"label": "Arsenal", "odds": "1.80"},
+                    {"label": "Draw", "odds": 3.6},
+                    {"label": "Chelsea", "odds": 4.5}]}]},
+                {"eventId": "e2", "participants": [{"name": "Spurs", "home": true}]}
+            ]}}}
+            </script></body></html>"#;
+
+        let updates = parse_scraped_response(html, &config, "soccer-epl").unwrap();
+        assert_eq!(updates.len(), 1);
+        let u = &updates[0];
+        assert_eq!(u.event_id, "e1");
+        assert_eq!(
+            (u.home_team.as_str(), u.away_team.as_str()),
+            ("Arsenal", "Chelsea")
+        );
+        assert_eq!(u.commence_time, "2026-03-14T14:00:00+00:00");
+        let bm = &u.bookmakers[0];
+        assert_eq!(bm.name, "ExampleBook");
+        assert!((bm.home_odds - -125.0).abs() < 1e-9);
+        assert!((bm.away_odds - 350.0).abs() < 1e-9);
+        assert!((bm.draw_odds.unwrap() - 260.0).abs() < 1e-9);
+
+        assert!(parse_scraped_response("<html></html>", &config, "soccer-epl").is_err());
+    }
+
+    #[test]
+    fn test_build_url_from_template() {
+        let feed = ScrapedOddsFeed::new("", 1000, 0, Client::builder());
+        assert_eq!(
+            feed.build_url("basketball").as_deref(),
+            Some("https://www.bovada.lv/services/sports/event/coupon/events/A/description/basketball/nba")
+        );
+        assert_eq!(feed.build_url("curling"), None);
+        // A full URL is used for every sport
+        let feed = ScrapedOddsFeed::new("http://localhost/odds", 1000, 0, Client::builder());
+        assert_eq!(
+            feed.build_url("curling").as_deref(),
+            Some("http://localhost/odds")
+        );
+    }
+
     /// Integration test: hits real Bovada API.
     /// Run with: cargo test bovada_live --ignored -- --nocapture
     #[tokio::test]
@@ -466,6 +704,7 @@ mod tests {
             "https://www.bovada.lv/services/sports/event/coupon/events/A/description/basketball/college-basketball",
             10000,
             2,
+            Client::builder(),
         );
         match feed.fetch_odds("college-basketball").await {
             Ok(updates) => {
@@ -0,0 +1,490 @@
+//! One owner for every odds feed.
+//!
+//! Pipelines used to hold the feeds mutably and work out their own poll
+//! intervals. `FeedSupervisor` owns the feeds instead and decides both when
+//! a sport's poll is due and whether a source may be sent a request at all:
+//! each source has a token bucket shared by every sport polling it, and a
+//! cap bounds the fetches in flight across all sources. A source serves one
+//! fetch at a time, since a feed keeps per-source state (ETags, caches).
+//! Sources that report a quota also get a `QuotaBudget`, which stretches
+//! intervals so the credits last the slate. Streaming sources push into a
+//! `StreamedOdds` instead; fetching one takes whatever changed since the
+//! last fetch, so pipelines read polled and pushed odds the same way.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use tokio::sync::Semaphore;
+
+use super::adaptive_poll::{self, Volatility};
+use super::budget::{PollDemand, QuotaBudget};
+use super::types::{ApiQuota, OddsUpdate};
+use super::ws_odds::StreamedOdds;
+use super::OddsFeed;
+use crate::config::{AdaptivePollConfig, OddsSourcesConfig, QuotaBudgetConfig};
+
+/// How urgently a sport wants odds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cadence {
+    PreGame,
+    /// Live, scaled by the most volatile game.
+    Live(Volatility),
+}
+
+/// One successful fetch.
+#[derive(Debug, Clone)]
+pub struct Fetched {
+    pub updates: Vec<OddsUpdate>,
+    pub quota: Option<ApiQuota>,
+    /// The feed's running schema drift count after this fetch.
+    pub schema_drift: u64,
+}
+
+/// Token bucket: `capacity` requests back to back, refilled at `per_sec`.
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    per_sec: f64,
+    last: Option<Instant>,
+}
+
+impl TokenBucket {
+    fn new(per_min: f64, burst: u32) -> Self {
+        let capacity = f64::from(burst.max(1));
+        Self {
+            capacity,
+            tokens: capacity,
+            per_sec: per_min.max(0.0) / 60.0,
+            last: None,
+        }
+    }
+
+    fn try_take(&mut self, now: Instant) -> bool {
+        if let Some(last) = self.last {
+            let refill = now.saturating_duration_since(last).as_secs_f64() * self.per_sec;
+            self.tokens = (self.tokens + refill).min(self.capacity);
+        }
+        self.last = Some(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+struct Source {
+    feed: tokio::sync::Mutex<Box<dyn OddsFeed>>,
+    /// `None` when unthrottled (replays).
+    bucket: Mutex<Option<TokenBucket>>,
+    live_poll: Duration,
+    pre_game_poll: Duration,
+    quota_warning: u64,
+    last_quota: Mutex<Option<ApiQuota>>,
+    budget: Mutex<QuotaBudget>,
+}
+
+pub struct FeedSupervisor {
+    sources: HashMap<String, Source>,
+    streams: HashMap<String, Arc<StreamedOdds>>,
+    in_flight: Semaphore,
+    adaptive_poll: AdaptivePollConfig,
+}
+
+impl FeedSupervisor {
+    pub fn new(
+        feeds: HashMap<String, Box<dyn OddsFeed>>,
+        configs: &OddsSourcesConfig,
+        adaptive_poll: AdaptivePollConfig,
+        quota_budget: QuotaBudgetConfig,
+    ) -> Self {
+        let sources = feeds
+            .into_iter()
+            .map(|(name, feed)| {
+                let config = configs.get(&name);
+                let source = Source {
+                    feed: tokio::sync::Mutex::new(feed),
+                    bucket: Mutex::new(
+                        config.map(|c| TokenBucket::new(c.rate_limit_per_min, c.rate_limit_burst)),
+                    ),
+                    live_poll: Duration::from_secs(config.map_or(20, |c| c.live_poll_s)),
+                    pre_game_poll: Duration::from_secs(config.map_or(120, |c| c.pre_game_poll_s)),
+                    quota_warning: config
+                        .and_then(|c| c.quota_warning_threshold)
+                        .unwrap_or(100),
+                    last_quota: Mutex::new(None),
+                    budget: Mutex::new(QuotaBudget::new(quota_budget.clone())),
+                };
+                (name, source)
+            })
+            .collect();
+        Self {
+            sources,
+            streams: HashMap::new(),
+            in_flight: Semaphore::new(configs.max_concurrent_fetches.max(1)),
+            adaptive_poll,
+        }
+    }
+
+    /// Drop the rate limits, for replaying recorded feeds.
+    pub fn unthrottled(self) -> Self {
+        for source in self.sources.values() {
+            *lock(&source.bucket) = None;
+        }
+        self
+    }
+
+    /// Serve a streaming source from `streamed`, which its stream task fills.
+    pub fn add_stream(&mut self, source: &str, streamed: Arc<StreamedOdds>) {
+        self.streams.insert(source.to_string(), streamed);
+    }
+
+    pub fn contains(&self, source: &str) -> bool {
+        self.sources.contains_key(source) || self.streams.contains_key(source)
+    }
+
+    pub fn is_streaming(&self, source: &str) -> bool {
+        self.streams.contains_key(source)
+    }
+
+    /// Source names, sorted.
+    pub fn sources(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .sources
+            .keys()
+            .chain(self.streams.keys())
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// How often a sport at `cadence` should poll `source`, before the quota
+    /// budget. With the budget off, live polling drops to the pre-game
+    /// interval while the source's quota is under `quota_warning_threshold`.
+    pub fn interval(&self, source: &str, cadence: Cadence) -> Duration {
+        let Some(s) = self.sources.get(source) else {
+            return Duration::from_secs(120);
+        };
+        let quota_low = !lock(&s.budget).enabled()
+            && lock(&s.last_quota)
+                .as_ref()
+                .is_some_and(|q| q.requests_remaining < s.quota_warning);
+        match cadence {
+            Cadence::Live(volatility) if !quota_low => {
+                adaptive_poll::live_interval(&self.adaptive_poll, s.live_poll, volatility)
+            }
+            _ => s.pre_game_poll,
+        }
+    }
+
+    /// Whether `sport`, last polled at `last`, is due to poll `source` again.
+    /// Also records the sport's demand for the source's quota budget.
+    pub fn is_due(
+        &self,
+        source: &str,
+        sport: &str,
+        cadence: Cadence,
+        demand: PollDemand,
+        last: Option<Instant>,
+        now: Instant,
+    ) -> bool {
+        let interval = self.budgeted_interval(source, sport, cadence, demand, now);
+        last.is_none_or(|t| now.saturating_duration_since(t) >= interval)
+    }
+
+    fn budgeted_interval(
+        &self,
+        source: &str,
+        sport: &str,
+        cadence: Cadence,
+        demand: PollDemand,
+        now: Instant,
+    ) -> Duration {
+        // Pushed odds are taken as soon as they change
+        if self.is_streaming(source) {
+            return Duration::ZERO;
+        }
+        let wanted = self.interval(source, cadence);
+        let Some(s) = self.sources.get(source) else {
+            return wanted;
+        };
+        let mut budget = lock(&s.budget);
+        if !budget.enabled() {
+            return wanted;
+        }
+        budget.set_demand(sport, demand, wanted, now);
+        let remaining = lock(&s.last_quota).as_ref().map(|q| q.requests_remaining);
+        remaining
+            .and_then(|r| budget.interval(sport, r))
+            .unwrap_or(wanted)
+    }
+
+    /// The shortest pre-game interval of any source, which bounds how long
+    /// the engine may sleep waiting for games to start.
+    pub fn shortest_pre_game_interval(&self) -> Option<Duration> {
+        self.sources.values().map(|s| s.pre_game_poll).min()
+    }
+
+    /// Fetch `sport` from `source`. `Ok(None)` means the source's rate limit
+    /// held the request back and nothing was sent, or for a streaming
+    /// source that nothing changed.
+    pub async fn fetch(&self, source: &str, sport: &str, now: Instant) -> Result<Option<Fetched>> {
+        if let Some(streamed) = self.streams.get(source) {
+            return Ok(streamed.take_changed(sport)?.map(|updates| Fetched {
+                updates,
+                quota: None,
+                schema_drift: 0,
+            }));
+        }
+        let s = self
+            .sources
+            .get(source)
+            .with_context(|| format!("unknown odds source: {}", source))?;
+        if let Some(bucket) = lock(&s.bucket).as_mut() {
+            if !bucket.try_take(now) {
+                tracing::debug!(source, sport, "odds fetch held back by rate limit");
+                return Ok(None);
+            }
+        }
+
+        let _permit = self
+            .in_flight
+            .acquire()
+            .await
+            .context("feed supervisor closed")?;
+        let mut feed = s.feed.lock().await;
+        let result = feed.fetch_odds(sport).await;
+        let quota = feed.last_quota();
+        let schema_drift = feed.schema_drift_count();
+        drop(feed);
+
+        if let Some(q) = &quota {
+            lock(&s.budget).record_cost(sport, q.requests_last);
+            *lock(&s.last_quota) = Some(q.clone());
+        }
+        Ok(Some(Fetched {
+            updates: result?,
+            quota,
+            schema_drift,
+        }))
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::OddsSourceConfig;
+    use async_trait::async_trait;
+
+    struct CountingFeed {
+        calls: u64,
+        remaining: u64,
+    }
+
+    #[async_trait]
+    impl OddsFeed for CountingFeed {
+        async fn fetch_odds(&mut self, _sport: &str) -> Result<Vec<OddsUpdate>> {
+            self.calls += 1;
+            self.remaining = self.remaining.saturating_sub(60);
+            Ok(Vec::new())
+        }
+
+        fn last_quota(&self) -> Option<ApiQuota> {
+            Some(ApiQuota {
+                requests_used: self.calls,
+                requests_remaining: self.remaining,
+                requests_last: 1,
+            })
+        }
+    }
+
+    const DEMAND: PollDemand = PollDemand {
+        live_games: 1,
+        edge_cents: 0.0,
+    };
+
+    fn source_config(per_min: f64, burst: u32) -> OddsSourceConfig {
+        toml::from_str(&format!(
+            "type = \"the-odds-api\"\nlive_poll_s = 10\npre_game_poll_s = 60\n\
+             quota_warning_threshold = 100\nrate_limit_per_min = {}\nrate_limit_burst = {}",
+            per_min, burst
+        ))
+        .unwrap()
+    }
+
+    fn supervisor(per_min: f64, burst: u32, remaining: u64) -> FeedSupervisor {
+        let budget = QuotaBudgetConfig {
+            enabled: false,
+            ..QuotaBudgetConfig::default()
+        };
+        supervisor_with_budget(per_min, burst, remaining, budget)
+    }
+
+    fn supervisor_with_budget(
+        per_min: f64,
+        burst: u32,
+        remaining: u64,
+        budget: QuotaBudgetConfig,
+    ) -> FeedSupervisor {
+        let mut configs = OddsSourcesConfig::default();
+        configs
+            .sources
+            .insert("odds".to_string(), source_config(per_min, burst));
+        let mut feeds: HashMap<String, Box<dyn OddsFeed>> = HashMap::new();
+        feeds.insert(
+            "odds".to_string(),
+            Box::new(CountingFeed {
+                calls: 0,
+                remaining,
+            }),
+        );
+        let adaptive: AdaptivePollConfig = toml::from_str("enabled = false").unwrap();
+        FeedSupervisor::new(feeds, &configs, adaptive, budget)
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_is_shared_across_sports() {
+        let feeds = supervisor(6.0, 2, 10_000);
+        let t0 = Instant::now();
+        assert!(feeds
+            .fetch("odds", "basketball", t0)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(feeds
+            .fetch("odds", "ice-hockey", t0)
+            .await
+            .unwrap()
+            .is_some());
+        // Burst spent: held back until a token refills (10s at 6/min)
+        assert!(feeds
+            .fetch("odds", "basketball", t0)
+            .await
+            .unwrap()
+            .is_none());
+        let later = t0 + Duration::from_secs(10);
+        assert!(feeds
+            .fetch("odds", "basketball", later)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(feeds.fetch("missing", "basketball", later).await.is_err());
+
+        // Replays are never held back
+        let feeds = supervisor(6.0, 1, 10_000).unthrottled();
+        for _ in 0..5 {
+            assert!(feeds
+                .fetch("odds", "basketball", t0)
+                .await
+                .unwrap()
+                .is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_intervals_fall_back_to_pre_game_when_quota_low() {
+        let feeds = supervisor(60.0, 5, 200);
+        let live = Cadence::Live(Volatility::Close);
+        assert_eq!(feeds.interval("odds", live), Duration::from_secs(10));
+        assert_eq!(
+            feeds.interval("odds", Cadence::PreGame),
+            Duration::from_secs(60)
+        );
+        let t0 = Instant::now();
+        let due = |last, now| feeds.is_due("odds", "basketball", live, DEMAND, last, now);
+        assert!(due(None, t0));
+        assert!(!due(Some(t0), t0 + Duration::from_secs(9)));
+        assert!(due(Some(t0), t0 + Duration::from_secs(10)));
+
+        // 200 - 2 * 60 = 80 remaining, under the warning threshold of 100
+        feeds.fetch("odds", "basketball", t0).await.unwrap();
+        assert_eq!(feeds.interval("odds", live), Duration::from_secs(10));
+        let fetched = feeds
+            .fetch("odds", "basketball", t0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.quota.unwrap().requests_remaining, 80);
+        assert_eq!(feeds.interval("odds", live), Duration::from_secs(60));
+        assert_eq!(
+            feeds.shortest_pre_game_interval(),
+            Some(Duration::from_secs(60))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_budget_stretches_intervals_to_last_the_slate() {
+        let budget = QuotaBudgetConfig {
+            slate_hours: 1.0,
+            reserve: 0,
+            ..QuotaBudgetConfig::default()
+        };
+        // 360 - 60 = 300 credits left for the hour after one fetch
+        let feeds = supervisor_with_budget(60.0, 5, 360, budget);
+        let live = Cadence::Live(Volatility::Close);
+        let t0 = Instant::now();
+        assert!(feeds.is_due("odds", "basketball", live, DEMAND, None, t0));
+        feeds.fetch("odds", "basketball", t0).await.unwrap();
+
+        // One sport alone asks for 360 an hour: 3600 / 300 = 12s
+        let due = |sport, secs| {
+            let now = t0 + Duration::from_secs(secs);
+            feeds.is_due("odds", sport, live, DEMAND, Some(t0), now)
+        };
+        assert!(!due("basketball", 11));
+        assert!(due("basketball", 12));
+        // A second live sport halves each share
+        assert!(!due("ice-hockey", 23));
+        assert!(!due("basketball", 23));
+        assert!(due("basketball", 24));
+    }
+
+    #[tokio::test]
+    async fn test_streaming_source_serves_changes_only() {
+        let mut feeds = supervisor(6.0, 1, 10_000);
+        let streamed = Arc::new(StreamedOdds::default());
+        feeds.add_stream("push", streamed.clone());
+        assert!(feeds.contains("push"));
+        assert_eq!(feeds.sources(), vec!["odds", "push"]);
+
+        let t0 = Instant::now();
+        let live = Cadence::Live(Volatility::Close);
+        assert!(feeds.is_due("push", "basketball", live, DEMAND, Some(t0), t0));
+        assert!(feeds
+            .fetch("push", "basketball", t0)
+            .await
+            .unwrap()
+            .is_none());
+
+        streamed.apply(crate::feed::OddsStreamEvent::Update(OddsUpdate {
+            event_id: "e1".to_string(),
+            sport: "basketball".to_string(),
+            home_team: "Boston Celtics".to_string(),
+            away_team: "New York Knicks".to_string(),
+            commence_time: "2026-01-10T00:00:00Z".to_string(),
+            bookmakers: Vec::new(),
+        }));
+        let fetched = feeds
+            .fetch("push", "basketball", t0)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.updates.len(), 1);
+        assert!(fetched.quota.is_none());
+        // Streams are not rate limited: the bucket of one is untouched
+        assert!(feeds
+            .fetch("odds", "basketball", t0)
+            .await
+            .unwrap()
+            .is_some());
+    }
+}
@@ -2,14 +2,21 @@ use super::types::*;
 use super::OddsFeed;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
 use reqwest::Client;
+use std::sync::{Arc, RwLock};
+
+/// One Odds API key shared by every source that uses it, so rotating the
+/// key reaches all of them at once.
+pub type SharedApiKey = Arc<RwLock<String>>;
 
 pub struct TheOddsApi {
     client: Client,
-    api_key: String,
+    api_key: SharedApiKey,
     base_url: String,
     bookmakers: String,
     last_quota: Option<ApiQuota>,
+    schema_drift_count: u64,
 }
 
 /// Map our internal sport key to the-odds-api.com sport key.
@@ -17,6 +24,7 @@ fn api_sport_key(sport: &str) -> &str {
     match sport {
         "basketball" => "basketball_nba",
         "american-football" => "americanfootball_nfl",
+        "college-football" => "americanfootball_ncaaf",
         "baseball" => "baseball_mlb",
         "ice-hockey" => "icehockey_nhl",
         "college-basketball" => "basketball_ncaab",
@@ -27,6 +35,33 @@ fn api_sport_key(sport: &str) -> &str {
     }
 }
 
+/// Parse the events array one element at a time so a single malformed event
+/// doesn't discard the whole batch. Returns the parsed events and the number
+/// of events that failed to deserialize.
+fn parse_events(body: serde_json::Value) -> Result<(Vec<TheOddsApiEvent>, u64)> {
+    let serde_json::Value::Array(items) = body else {
+        anyhow::bail!("the-odds-api response is not an array");
+    };
+
+    let mut events = Vec::with_capacity(items.len());
+    let mut drift = 0u64;
+    for item in items {
+        let id = item
+            .get("id")
+            .and_then(|v| v.as_str())
+            .unwrap_or("?")
+            .to_string();
+        match serde_json::from_value::<TheOddsApiEvent>(item) {
+            Ok(event) => events.push(event),
+            Err(e) => {
+                drift += 1;
+                tracing::warn!(event_id = %id, error = %e, "the-odds-api schema drift: skipping event");
+            }
+        }
+    }
+    Ok((events, drift))
+}
+
 /// Parse a quota header that may be an integer or float (e.g. "14527.0").
 fn parse_quota_header(headers: &reqwest::header::HeaderMap, name: &str) -> u64 {
     headers
@@ -38,21 +73,40 @@ fn parse_quota_header(headers: &reqwest::header::HeaderMap, name: &str) -> u64 {
 }
 
 impl TheOddsApi {
-    pub fn new(api_key: String, base_url: &str, bookmakers: &str) -> Self {
+    pub fn new(
+        api_key: String,
+        base_url: &str,
+        bookmakers: &str,
+        http: reqwest::ClientBuilder,
+    ) -> Self {
+        Self::with_shared_key(Arc::new(RwLock::new(api_key)), base_url, bookmakers, http)
+    }
+
+    pub fn with_shared_key(
+        api_key: SharedApiKey,
+        base_url: &str,
+        bookmakers: &str,
+        http: reqwest::ClientBuilder,
+    ) -> Self {
         Self {
-            client: Client::new(),
+            client: http.build().expect("failed to build reqwest client"),
             api_key,
             base_url: base_url.trim_end_matches('/').to_string(),
             bookmakers: bookmakers.to_string(),
             last_quota: None,
+            schema_drift_count: 0,
         }
     }
 
+    fn api_key(&self) -> String {
+        self.api_key.read().unwrap().clone()
+    }
+
     /// Call the free `/v4/sports` endpoint to check quota without consuming usage credits.
     /// Returns an error if the key is invalid or quota is exhausted.
     #[allow(dead_code)]
     pub async fn check_quota(&mut self) -> Result<ApiQuota> {
-        let url = format!("{}/v4/sports?apiKey={}", self.base_url, self.api_key,);
+        let url = format!("{}/v4/sports?apiKey={}", self.base_url, self.api_key());
 
         let resp = self
             .client
@@ -64,6 +118,7 @@ impl TheOddsApi {
         let status = resp.status();
         let used = parse_quota_header(resp.headers(), "x-requests-used");
         let remaining = parse_quota_header(resp.headers(), "x-requests-remaining");
+        let last = parse_quota_header(resp.headers(), "x-requests-last");
 
         if !status.is_success() {
             let body = resp.text().await.unwrap_or_default();
@@ -73,6 +128,7 @@ impl TheOddsApi {
         let quota = ApiQuota {
             requests_used: used,
             requests_remaining: remaining,
+            requests_last: last,
         };
         self.last_quota = Some(quota.clone());
 
@@ -82,21 +138,12 @@ impl TheOddsApi {
 
         Ok(quota)
     }
-}
-
-#[async_trait]
-impl OddsFeed for TheOddsApi {
-    async fn fetch_odds(&mut self, sport: &str) -> Result<Vec<OddsUpdate>> {
-        let api_sport = api_sport_key(sport);
-
-        let url = format!(
-            "{}/v4/sports/{}/odds?apiKey={}&regions=us&markets=h2h&oddsFormat=american&bookmakers={}",
-            self.base_url, api_sport, self.api_key, self.bookmakers,
-        );
 
+    /// GET an odds endpoint, recording the quota headers.
+    async fn get_odds_json(&mut self, url: &str, api_sport: &str) -> Result<serde_json::Value> {
         let resp = self
             .client
-            .get(&url)
+            .get(url)
             .send()
             .await
             .context("the-odds-api request failed")?;
@@ -104,9 +151,11 @@ impl OddsFeed for TheOddsApi {
         // Extract quota from response headers
         let used = parse_quota_header(resp.headers(), "x-requests-used");
         let remaining = parse_quota_header(resp.headers(), "x-requests-remaining");
+        let last = parse_quota_header(resp.headers(), "x-requests-last");
         self.last_quota = Some(ApiQuota {
             requests_used: used,
             requests_remaining: remaining,
+            requests_last: last,
         });
 
         let status = resp.status();
@@ -115,65 +164,196 @@ impl OddsFeed for TheOddsApi {
             anyhow::bail!("the-odds-api {} ({}): {}", api_sport, status, body);
         }
 
-        let events: Vec<TheOddsApiEvent> = resp
-            .json()
+        resp.json()
             .await
-            .context("failed to parse the-odds-api response")?;
-
-        let mut updates: Vec<OddsUpdate> = Vec::new();
-
-        for event in events {
-            let mut bookmaker_odds: Vec<BookmakerOdds> = Vec::new();
-
-            for bm in &event.bookmakers {
-                // Find h2h (moneyline) market
-                let h2h = bm.markets.iter().find(|m| m.key == "h2h");
-
-                if let Some(market) = h2h {
-                    let home_price = market
-                        .outcomes
-                        .iter()
-                        .find(|o| o.name == event.home_team)
-                        .map(|o| o.price);
-                    let away_price = market
-                        .outcomes
-                        .iter()
-                        .find(|o| o.name == event.away_team)
-                        .map(|o| o.price);
-                    let draw_price = market
-                        .outcomes
-                        .iter()
-                        .find(|o| o.name == "Draw")
-                        .map(|o| o.price);
-
-                    if let (Some(h), Some(a)) = (home_price, away_price) {
-                        bookmaker_odds.push(BookmakerOdds {
-                            name: bm.title.clone(),
-                            home_odds: h,
-                            away_odds: a,
-                            draw_odds: draw_price,
-                            last_update: bm.last_update.clone(),
-                        });
-                    }
-                }
+            .context("failed to parse the-odds-api response")
+    }
+
+    /// `parse_events`, counting skipped events as schema drift.
+    fn parse_tracked(&mut self, body: serde_json::Value) -> Result<Vec<TheOddsApiEvent>> {
+        match parse_events(body) {
+            Ok((events, drift)) => {
+                self.schema_drift_count += drift;
+                Ok(events)
+            }
+            Err(e) => {
+                self.schema_drift_count += 1;
+                Err(e)
             }
+        }
+    }
+
+    /// Historical odds snapshot at or before `at`, for building backtest
+    /// captures. Returns the snapshot's own timestamp with the updates.
+    /// Costs 10 credits per call (one region, one market).
+    pub async fn fetch_historical_odds(
+        &mut self,
+        sport: &str,
+        at: DateTime<Utc>,
+    ) -> Result<(DateTime<Utc>, Vec<OddsUpdate>)> {
+        let api_sport = api_sport_key(sport);
+        let url = format!(
+            "{}/v4/historical/sports/{}/odds?apiKey={}&regions=us&markets=h2h&oddsFormat=american&bookmakers={}&date={}",
+            self.base_url,
+            api_sport,
+            self.api_key(),
+            self.bookmakers,
+            at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        );
+        let mut body = self.get_odds_json(&url, api_sport).await?;
+        let timestamp = body
+            .get("timestamp")
+            .and_then(|t| t.as_str())
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Utc))
+            .context("historical odds response has no snapshot timestamp")?;
+        let events = self.parse_tracked(body["data"].take())?;
+        Ok((timestamp, to_updates(events, sport)))
+    }
+}
 
-            if !bookmaker_odds.is_empty() {
-                updates.push(OddsUpdate {
-                    event_id: event.id,
-                    sport: sport.to_string(),
-                    home_team: event.home_team,
-                    away_team: event.away_team,
-                    commence_time: event.commence_time,
-                    bookmakers: bookmaker_odds,
-                });
+/// Moneyline updates for every event with at least one complete h2h market.
+fn to_updates(events: Vec<TheOddsApiEvent>, sport: &str) -> Vec<OddsUpdate> {
+    let mut updates: Vec<OddsUpdate> = Vec::new();
+
+    for event in events {
+        let mut bookmaker_odds: Vec<BookmakerOdds> = Vec::new();
+
+        for bm in &event.bookmakers {
+            // Find h2h (moneyline) market
+            let h2h = bm.markets.iter().find(|m| m.key == "h2h");
+
+            if let Some(market) = h2h {
+                let home_price = market
+                    .outcomes
+                    .iter()
+                    .find(|o| o.name == event.home_team)
+                    .map(|o| o.price);
+                let away_price = market
+                    .outcomes
+                    .iter()
+                    .find(|o| o.name == event.away_team)
+                    .map(|o| o.price);
+                let draw_price = market
+                    .outcomes
+                    .iter()
+                    .find(|o| o.name == "Draw")
+                    .map(|o| o.price);
+
+                if let (Some(h), Some(a)) = (home_price, away_price) {
+                    bookmaker_odds.push(BookmakerOdds {
+                        name: bm.title.clone(),
+                        home_odds: h,
+                        away_odds: a,
+                        draw_odds: draw_price,
+                        last_update: bm.last_update.clone(),
+                    });
+                }
             }
         }
 
-        Ok(updates)
+        if !bookmaker_odds.is_empty() {
+            updates.push(OddsUpdate {
+                event_id: event.id,
+                sport: sport.to_string(),
+                home_team: event.home_team,
+                away_team: event.away_team,
+                commence_time: event.commence_time,
+                bookmakers: bookmaker_odds,
+            });
+        }
+    }
+
+    updates
+}
+
+#[async_trait]
+impl OddsFeed for TheOddsApi {
+    async fn fetch_odds(&mut self, sport: &str) -> Result<Vec<OddsUpdate>> {
+        let api_sport = api_sport_key(sport);
+
+        let url = format!(
+            "{}/v4/sports/{}/odds?apiKey={}&regions=us&markets=h2h&oddsFormat=american&bookmakers={}",
+            self.base_url,
+            api_sport,
+            self.api_key(),
+            self.bookmakers,
+        );
+
+        let body = self.get_odds_json(&url, api_sport).await?;
+        let events = self.parse_tracked(body)?;
+        Ok(to_updates(events, sport))
     }
 
     fn last_quota(&self) -> Option<ApiQuota> {
         self.last_quota.clone()
     }
+
+    fn schema_drift_count(&self) -> u64 {
+        self.schema_drift_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event_json(id: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "sport_key": "basketball_nba",
+            "home_team": "Boston Celtics",
+            "away_team": "New York Knicks",
+            "commence_time": "2026-01-31T00:00:00Z",
+            "bookmakers": [{
+                "key": "draftkings",
+                "title": "DraftKings",
+                "last_update": "2026-01-31T00:00:00Z",
+                "markets": [{
+                    "key": "h2h",
+                    "outcomes": [
+                        {"name": "Boston Celtics", "price": -150.0},
+                        {"name": "New York Knicks", "price": 130.0}
+                    ]
+                }]
+            }]
+        })
+    }
+
+    #[test]
+    fn test_parse_events_ignores_unknown_fields() {
+        let mut ev = event_json("a");
+        ev["new_field"] = serde_json::json!({"nested": true});
+        ev["bookmakers"][0]["link"] = serde_json::json!("https://example.com");
+        let (events, drift) = parse_events(serde_json::json!([ev])).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(drift, 0);
+    }
+
+    #[test]
+    fn test_parse_events_isolates_malformed_event() {
+        let mut bad = event_json("bad");
+        bad.as_object_mut().unwrap().remove("home_team");
+        let body = serde_json::json!([event_json("a"), bad, event_json("b")]);
+        let (events, drift) = parse_events(body).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(drift, 1);
+        assert_eq!(events[1].id, "b");
+    }
+
+    #[test]
+    fn test_parse_events_tolerates_missing_optional_bookmaker_fields() {
+        let mut ev = event_json("a");
+        let bm = ev["bookmakers"][0].as_object_mut().unwrap();
+        bm.remove("title");
+        bm.remove("last_update");
+        let (events, drift) = parse_events(serde_json::json!([ev])).unwrap();
+        assert_eq!(drift, 0);
+        assert_eq!(events[0].bookmakers[0].title, "");
+    }
+
+    #[test]
+    fn test_parse_events_rejects_non_array() {
+        assert!(parse_events(serde_json::json!({"message": "error"})).is_err());
+    }
 }
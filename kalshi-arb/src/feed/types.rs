@@ -1,8 +1,29 @@
-use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Deserialize a list one element at a time, dropping elements that fail so
+/// one malformed item doesn't discard the rest of the response.
+pub(crate) fn skip_malformed<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let items = Vec::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(items
+        .into_iter()
+        .filter_map(|item| match serde_json::from_value(item) {
+            Ok(parsed) => Some(parsed),
+            Err(e) => {
+                tracing::warn!(error = %e, "schema drift: skipping item");
+                None
+            }
+        })
+        .collect())
+}
 
 /// Normalized internal types used by the engine (provider-agnostic).
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct OddsUpdate {
     pub event_id: String,
@@ -13,7 +34,7 @@ pub struct OddsUpdate {
     pub bookmakers: Vec<BookmakerOdds>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct BookmakerOdds {
     pub name: String,
@@ -40,14 +61,18 @@ pub struct TheOddsApiEvent {
 #[allow(dead_code)]
 pub struct TheOddsApiBookmaker {
     pub key: String,
+    #[serde(default)]
     pub title: String,
+    #[serde(default)]
     pub last_update: String,
+    #[serde(default)]
     pub markets: Vec<TheOddsApiMarket>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct TheOddsApiMarket {
     pub key: String,
+    #[serde(default)]
     pub outcomes: Vec<TheOddsApiOutcome>,
 }
 
@@ -62,6 +87,8 @@ pub struct TheOddsApiOutcome {
 pub struct ApiQuota {
     pub requests_used: u64,
     pub requests_remaining: u64,
+    /// Credits charged for the request that returned this quota.
+    pub requests_last: u64,
 }
 
 /// DraftKings sportsbook API response types.
@@ -75,7 +102,7 @@ pub struct DkResponse {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DkEventGroup {
-    #[serde(default)]
+    #[serde(default, deserialize_with = "skip_malformed")]
     pub events: Vec<DkEvent>,
     #[serde(default)]
     pub offer_categories: Vec<DkOfferCategory>,
@@ -83,9 +110,9 @@ pub struct DkEventGroup {
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-#[allow(dead_code)]
 pub struct DkEvent {
     pub event_id: u64,
+    /// "Away @ Home" for US sports, "Home vs Away" for soccer and MMA.
     #[serde(default)]
     pub name: String,
     #[serde(default)]
@@ -94,14 +121,47 @@ pub struct DkEvent {
     pub team_name1: Option<String>,
     #[serde(default)]
     pub team_name2: Option<String>,
+    #[serde(default)]
+    pub event_status: Option<DkEventStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DkEventStatus {
+    /// "NOT_STARTED", "STARTED" (in play) or "FINISHED".
+    #[serde(default)]
+    pub state: String,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-#[allow(dead_code)]
 pub struct DkOfferCategory {
     #[serde(default)]
     pub offer_category_id: u64,
+    #[serde(default)]
+    pub name: String,
+    /// Offers listed directly under the category (older responses).
+    #[serde(default)]
+    pub offers: Vec<Vec<DkOffer>>,
+    #[serde(default)]
+    pub offer_subcategory_descriptors: Vec<DkSubcategoryDescriptor>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DkSubcategoryDescriptor {
+    #[serde(default)]
+    pub subcategory_id: u64,
+    #[serde(default)]
+    pub name: String,
+    /// Present only on the subcategory that was requested.
+    #[serde(default)]
+    pub offer_subcategory: Option<DkOfferSubcategory>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DkOfferSubcategory {
     #[serde(default)]
     pub offers: Vec<Vec<DkOffer>>,
 }
@@ -111,10 +171,19 @@ pub struct DkOfferCategory {
 pub struct DkOffer {
     #[serde(default)]
     pub event_id: u64,
+    /// "Moneyline", "Spread", "Total", ...
+    #[serde(default)]
+    pub label: String,
     #[serde(default)]
     pub outcomes: Vec<DkOutcome>,
     #[serde(default)]
     pub is_suspended: bool,
+    #[serde(default = "default_dk_offer_open")]
+    pub is_open: bool,
+}
+
+fn default_dk_offer_open() -> bool {
+    true
 }
 
 #[derive(Debug, Deserialize)]
@@ -124,4 +193,81 @@ pub struct DkOutcome {
     pub label: String,
     #[serde(default)]
     pub odds_american: String,
+    /// Handicap or total; set on spread and total outcomes only.
+    #[serde(default)]
+    pub line: Option<f64>,
+}
+
+/// Pinnacle odds API (v1) response types.
+
+#[derive(Debug, Deserialize)]
+pub struct PinnacleFixturesResponse {
+    pub last: i64,
+    #[serde(default)]
+    pub league: Vec<PinnacleFixtureLeague>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PinnacleFixtureLeague {
+    #[serde(default, deserialize_with = "skip_malformed")]
+    pub events: Vec<PinnacleFixture>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinnacleFixture {
+    pub id: u64,
+    pub starts: String,
+    pub home: String,
+    pub away: String,
+    /// "Regular" for the match itself; corners, bookings etc. are separate events.
+    #[serde(default = "default_resulting_unit")]
+    pub resulting_unit: String,
+}
+
+fn default_resulting_unit() -> String {
+    "Regular".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PinnacleOddsResponse {
+    pub last: i64,
+    #[serde(default)]
+    pub leagues: Vec<PinnacleOddsLeague>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PinnacleOddsLeague {
+    #[serde(default, deserialize_with = "skip_malformed")]
+    pub events: Vec<PinnacleOddsEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PinnacleOddsEvent {
+    pub id: u64,
+    #[serde(default)]
+    pub periods: Vec<PinnaclePeriod>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PinnaclePeriod {
+    /// 0 is the full game; halves, quarters etc. follow.
+    pub number: i32,
+    /// 1 = online, 2 = offline.
+    #[serde(default = "default_period_status")]
+    pub status: i32,
+    #[serde(default)]
+    pub moneyline: Option<PinnacleMoneyline>,
+}
+
+fn default_period_status() -> i32 {
+    1
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct PinnacleMoneyline {
+    pub home: f64, // American odds
+    pub away: f64, // American odds
+    #[serde(default)]
+    pub draw: Option<f64>,
 }
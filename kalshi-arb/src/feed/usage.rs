@@ -0,0 +1,135 @@
+//! Odds API credit attribution by sport and purpose, so the polling that
+//! burns the most quota can be found and turned off.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Window for the per-row burn rate.
+const RATE_WINDOW: Duration = Duration::from_secs(3600);
+
+/// Why an odds request was made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UsagePurpose {
+    /// Odds-feed fair value while a game is live.
+    LiveEval,
+    /// Odds-feed polling with nothing live, and the score-feed sports'
+    /// background poll for commence times and diagnostic rows.
+    PreGameScan,
+    /// The score-feed background poll sped up by `validate_fair_value`.
+    Validation,
+    /// One-shot fetch when the diagnostic view opens with the engine idle.
+    Diagnostic,
+}
+
+impl UsagePurpose {
+    pub fn label(self) -> &'static str {
+        match self {
+            UsagePurpose::LiveEval => "live eval",
+            UsagePurpose::PreGameScan => "pre-game scan",
+            UsagePurpose::Validation => "validation",
+            UsagePurpose::Diagnostic => "diagnostic",
+        }
+    }
+}
+
+/// Requests and credits for one (sport, purpose) since startup.
+#[derive(Debug, Clone, Default)]
+struct UsageStat {
+    requests: u64,
+    credits: u64,
+    /// (when, credits) within the last `RATE_WINDOW`.
+    recent: VecDeque<(Instant, u64)>,
+}
+
+/// One line of the breakdown table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageRow {
+    pub sport: String,
+    pub purpose: UsagePurpose,
+    pub requests: u64,
+    pub credits: u64,
+    pub credits_last_hour: u64,
+}
+
+/// Running attribution of every quota-reporting odds request.
+#[derive(Debug, Clone, Default)]
+pub struct ApiUsage {
+    stats: BTreeMap<(String, UsagePurpose), UsageStat>,
+}
+
+impl ApiUsage {
+    pub fn record(&mut self, sport: &str, purpose: UsagePurpose, credits: u64, now: Instant) {
+        let stat = self.stats.entry((sport.to_string(), purpose)).or_default();
+        stat.requests += 1;
+        stat.credits += credits;
+        stat.recent.push_back((now, credits));
+        for stat in self.stats.values_mut() {
+            while stat
+                .recent
+                .front()
+                .is_some_and(|&(t, _)| now.duration_since(t) > RATE_WINDOW)
+            {
+                stat.recent.pop_front();
+            }
+        }
+    }
+
+    pub fn total_credits(&self) -> u64 {
+        self.stats.values().map(|s| s.credits).sum()
+    }
+
+    /// Rows by credits spent, most expensive first.
+    pub fn breakdown(&self, now: Instant) -> Vec<UsageRow> {
+        let mut rows: Vec<UsageRow> = self
+            .stats
+            .iter()
+            .map(|((sport, purpose), stat)| UsageRow {
+                sport: sport.clone(),
+                purpose: *purpose,
+                requests: stat.requests,
+                credits: stat.credits,
+                credits_last_hour: stat
+                    .recent
+                    .iter()
+                    .filter(|&&(t, _)| now.duration_since(t) <= RATE_WINDOW)
+                    .map(|&(_, c)| c)
+                    .sum(),
+            })
+            .collect();
+        rows.sort_by_key(|r| std::cmp::Reverse(r.credits));
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breakdown_attributes_credits_by_sport_and_purpose() {
+        let start = Instant::now();
+        let mut usage = ApiUsage::default();
+        usage.record("basketball", UsagePurpose::LiveEval, 1, start);
+        usage.record("basketball", UsagePurpose::LiveEval, 1, start);
+        usage.record("ice-hockey", UsagePurpose::Validation, 3, start);
+        let later = start + Duration::from_secs(3700);
+        usage.record("basketball", UsagePurpose::Diagnostic, 1, later);
+
+        assert_eq!(usage.total_credits(), 6);
+        let rows = usage.breakdown(later);
+        assert_eq!(rows[0].sport, "ice-hockey");
+        assert_eq!((rows[0].requests, rows[0].credits), (1, 3));
+        // Older than an hour: counted in totals, not in the burn rate
+        assert_eq!(rows[0].credits_last_hour, 0);
+        let live = rows
+            .iter()
+            .find(|r| r.purpose == UsagePurpose::LiveEval)
+            .unwrap();
+        assert_eq!((live.requests, live.credits), (2, 2));
+        let diag = rows
+            .iter()
+            .find(|r| r.purpose == UsagePurpose::Diagnostic)
+            .unwrap();
+        assert_eq!(diag.credits_last_hour, 1);
+    }
+}
@@ -0,0 +1,211 @@
+//! Push-based odds over a WebSocket.
+//!
+//! The provider is sent `{"action": "subscribe", "sports": [...]}` on
+//! connect and then pushes text frames holding one `OddsUpdate` or an array
+//! of them, in the engine's own normalized shape. Anything else (heartbeats,
+//! acks) is ignored. `StreamedOdds` keeps the latest update per event so a
+//! pipeline can take a sport's whole board whenever it changed.
+
+use super::types::OddsUpdate;
+use super::{OddsStreamEvent, StreamingOddsFeed};
+use crate::network::Network;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+
+pub struct WsOddsFeed {
+    name: String,
+    ws_url: String,
+    network: Network,
+}
+
+impl WsOddsFeed {
+    /// `name` is the source's `[network.proxies]` key.
+    pub fn new(name: &str, ws_url: &str, network: Network) -> Self {
+        Self {
+            name: name.to_string(),
+            ws_url: ws_url.to_string(),
+            network,
+        }
+    }
+
+    async fn connect_and_listen(
+        &self,
+        sports: &[String],
+        tx: &mpsc::Sender<OddsStreamEvent>,
+    ) -> Result<()> {
+        let ws_stream = self
+            .network
+            .connect_ws(&self.name, self.ws_url.as_str())
+            .await
+            .context("odds WS connection failed")?;
+        let (mut write, mut read) = ws_stream.split();
+        let _ = tx.send(OddsStreamEvent::Connected).await;
+
+        let sub = serde_json::json!({
+            "action": "subscribe",
+            "sports": sports,
+        });
+        write
+            .send(Message::Text(sub.to_string()))
+            .await
+            .context("odds WS subscribe failed")?;
+        tracing::debug!(source = %self.name, count = sports.len(), "subscribed to odds stream");
+
+        while let Some(msg) = read.next().await {
+            match msg.context("odds WS read error")? {
+                Message::Text(text) => {
+                    for update in parse_frame(&text) {
+                        if tx.send(OddsStreamEvent::Update(update)).await.is_err() {
+                            return Ok(());
+                        }
+                    }
+                }
+                Message::Ping(data) => {
+                    write.send(Message::Pong(data)).await?;
+                }
+                Message::Close(_) => {
+                    tracing::debug!(source = %self.name, "odds WS received close frame");
+                    break;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StreamingOddsFeed for WsOddsFeed {
+    async fn run(&self, sports: Vec<String>, tx: mpsc::Sender<OddsStreamEvent>) {
+        while !tx.is_closed() {
+            match self.connect_and_listen(&sports, &tx).await {
+                Ok(()) => tracing::warn!(source = %self.name, "odds WS closed, reconnecting..."),
+                Err(e) => {
+                    tracing::error!(source = %self.name, "odds WS error: {:#}, reconnecting in 2s...", e);
+                    let _ = tx
+                        .send(OddsStreamEvent::Disconnected(format!("{:#}", e)))
+                        .await;
+                }
+            }
+            tokio::time::sleep(Duration::from_secs(2)).await;
+        }
+    }
+}
+
+/// Odds updates in one text frame: a single update or an array of them.
+fn parse_frame(text: &str) -> Vec<OddsUpdate> {
+    match serde_json::from_str::<serde_json::Value>(text) {
+        Ok(serde_json::Value::Array(items)) => items
+            .into_iter()
+            .filter_map(|v| serde_json::from_value(v).ok())
+            .collect(),
+        Ok(v) => serde_json::from_value(v).into_iter().collect(),
+        Err(e) => {
+            tracing::warn!("odds WS message parse error: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct SportBoard {
+    events: HashMap<String, OddsUpdate>,
+    changed: bool,
+}
+
+#[derive(Debug, Default)]
+struct StreamState {
+    sports: HashMap<String, SportBoard>,
+    /// Why the stream is down, cleared when it reconnects.
+    error: Option<String>,
+}
+
+/// Latest streamed odds per sport, shared between the stream's task and
+/// the feed supervisor.
+#[derive(Debug, Default)]
+pub struct StreamedOdds {
+    state: Mutex<StreamState>,
+}
+
+impl StreamedOdds {
+    pub fn apply(&self, event: OddsStreamEvent) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match event {
+            OddsStreamEvent::Update(update) => {
+                let board = state.sports.entry(update.sport.clone()).or_default();
+                board.events.insert(update.event_id.clone(), update);
+                board.changed = true;
+            }
+            OddsStreamEvent::Connected => state.error = None,
+            OddsStreamEvent::Disconnected(reason) => state.error = Some(reason),
+        }
+    }
+
+    /// The sport's board if anything changed since the last take, sorted by
+    /// event id. Errors while the stream is down.
+    pub fn take_changed(&self, sport: &str) -> Result<Option<Vec<OddsUpdate>>> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(error) = &state.error {
+            anyhow::bail!("odds stream down: {}", error);
+        }
+        let Some(board) = state.sports.get_mut(sport).filter(|b| b.changed) else {
+            return Ok(None);
+        };
+        board.changed = false;
+        let mut updates: Vec<OddsUpdate> = board.events.values().cloned().collect();
+        updates.sort_by(|a, b| a.event_id.cmp(&b.event_id));
+        Ok(Some(updates))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(event_id: &str, home_odds: f64) -> String {
+        format!(
+            r#"{{"event_id":"{}","sport":"basketball","home_team":"Boston Celtics",
+            "away_team":"New York Knicks","commence_time":"2026-01-10T00:00:00Z",
+            "bookmakers":[{{"name":"StreamBook","home_odds":{},"away_odds":120.0,
+            "draw_odds":null,"last_update":"2026-01-10T00:30:00Z"}}]}}"#,
+            event_id, home_odds
+        )
+    }
+
+    #[test]
+    fn test_parse_frame_single_array_and_noise() {
+        assert_eq!(parse_frame(&frame("e1", -140.0)).len(), 1);
+        let batch = format!("[{},{}]", frame("e1", -140.0), frame("e2", 105.0));
+        assert_eq!(parse_frame(&batch).len(), 2);
+        assert!(parse_frame(r#"{"type":"heartbeat"}"#).is_empty());
+        assert!(parse_frame("not json").is_empty());
+    }
+
+    #[test]
+    fn test_streamed_odds_keeps_latest_per_event() {
+        let streamed = StreamedOdds::default();
+        assert!(streamed.take_changed("basketball").unwrap().is_none());
+
+        for text in [frame("e1", -140.0), frame("e2", 105.0), frame("e1", -155.0)] {
+            for update in parse_frame(&text) {
+                streamed.apply(OddsStreamEvent::Update(update));
+            }
+        }
+        let board = streamed.take_changed("basketball").unwrap().unwrap();
+        assert_eq!(board.len(), 2);
+        assert_eq!(board[0].bookmakers[0].home_odds, -155.0);
+        // Nothing new since the last take
+        assert!(streamed.take_changed("basketball").unwrap().is_none());
+
+        streamed.apply(OddsStreamEvent::Disconnected("reset".into()));
+        assert!(streamed.take_changed("basketball").is_err());
+        streamed.apply(OddsStreamEvent::Connected);
+        assert!(streamed.take_changed("basketball").unwrap().is_none());
+    }
+}
@@ -0,0 +1,410 @@
+//! Headless daemon mode: run the engine without the TUI and stream what it
+//! does as JSON lines, to stdout or to clients of a unix socket.
+//!
+//! Each line is one event object tagged by `type`: `log`, `trade` and
+//! `signal` as they happen, and a `snapshot` of balances and positions on a
+//! fixed interval. Ctrl-C stops the engine.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{broadcast, mpsc, watch};
+
+use crate::tui::state::{AppState, LogEntry, TradeRow};
+use crate::tui::TuiCommand;
+
+/// How often a `snapshot` event is emitted.
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(5);
+/// Lines buffered per socket client before a slow one starts missing them.
+const SOCKET_BUFFER: usize = 1024;
+
+/// Where events go.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Sink {
+    Stdout,
+    Socket(PathBuf),
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event<'a> {
+    Log {
+        time: &'a str,
+        level: &'a str,
+        message: &'a str,
+    },
+    Trade {
+        id: u64,
+        time: &'a str,
+        action: &'a str,
+        ticker: &'a str,
+        price: u32,
+        quantity: u32,
+        order_type: &'a str,
+        pnl: Option<i32>,
+        source: &'a str,
+        phase: &'a str,
+    },
+    /// A market row turned into a trade signal.
+    Signal {
+        ticker: &'a str,
+        action: &'a str,
+        fair_value: u32,
+        bid: u32,
+        ask: u32,
+        edge: i32,
+        phase: &'a str,
+    },
+    Snapshot {
+        ts: chrono::DateTime<chrono::Utc>,
+        sim: bool,
+        paused: bool,
+        balance_cents: i64,
+        exposure_cents: i64,
+        realized_pnl_cents: i64,
+        total_trades: u32,
+        winning_trades: u32,
+        kalshi_ws_connected: bool,
+        feed_tier: &'static str,
+        drawdown_tier: &'static str,
+        markets: usize,
+        positions: Vec<SnapshotPosition<'a>>,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct SnapshotPosition<'a> {
+    pub ticker: &'a str,
+    pub quantity: u32,
+    pub entry_price: u32,
+    pub sell_price: u32,
+}
+
+/// What has already been streamed, so each state change only emits what is
+/// new: trades past the last id, logs past the last entry seen, and markets
+/// that were not signalling before.
+#[derive(Debug, Default)]
+pub struct EventCursor {
+    last_trade_id: u64,
+    last_log: Option<(String, String)>,
+    signalling: HashSet<String>,
+}
+
+impl EventCursor {
+    pub fn advance<'a>(&mut self, state: &'a AppState) -> Vec<Event<'a>> {
+        let mut events = Vec::new();
+
+        // The log ring drops old entries, so resume after the last one seen
+        let start = self
+            .last_log
+            .as_ref()
+            .and_then(|(time, message)| {
+                state
+                    .logs
+                    .iter()
+                    .rposition(|l| &l.time == time && &l.message == message)
+            })
+            .map_or(0, |i| i + 1);
+        events.extend(state.logs.iter().skip(start).map(log_event));
+        if let Some(last) = state.logs.back() {
+            self.last_log = Some((last.time.clone(), last.message.clone()));
+        }
+
+        events.extend(
+            state
+                .trades
+                .iter()
+                .filter(|t| t.id > self.last_trade_id)
+                .map(trade_event),
+        );
+        self.last_trade_id = state.trades.back().map_or(self.last_trade_id, |t| t.id);
+
+        let mut signalling = HashSet::new();
+        for m in state.markets.iter().filter(|m| m.skip_reason.is_none()) {
+            if !self.signalling.contains(&m.ticker) {
+                events.push(Event::Signal {
+                    ticker: &m.ticker,
+                    action: &m.action,
+                    fair_value: m.fair_value,
+                    bid: m.bid,
+                    ask: m.ask,
+                    edge: m.edge,
+                    phase: &m.phase,
+                });
+            }
+            signalling.insert(m.ticker.clone());
+        }
+        self.signalling = signalling;
+        events
+    }
+}
+
+fn log_event(l: &LogEntry) -> Event<'_> {
+    Event::Log {
+        time: &l.time,
+        level: &l.level,
+        message: &l.message,
+    }
+}
+
+fn trade_event(t: &TradeRow) -> Event<'_> {
+    Event::Trade {
+        id: t.id,
+        time: &t.time,
+        action: &t.action,
+        ticker: &t.ticker,
+        price: t.price,
+        quantity: t.quantity,
+        order_type: &t.order_type,
+        pnl: t.pnl,
+        source: &t.source,
+        phase: &t.phase,
+    }
+}
+
+/// Open positions: simulated ones under `--simulate`, else the live ones.
+pub fn positions(state: &AppState) -> Vec<SnapshotPosition<'_>> {
+    if state.sim_mode {
+        state
+            .sim_positions
+            .iter()
+            .map(|p| SnapshotPosition {
+                ticker: &p.ticker,
+                quantity: p.quantity,
+                entry_price: p.entry_price,
+                sell_price: p.sell_price,
+            })
+            .collect()
+    } else {
+        state
+            .positions
+            .iter()
+            .map(|p| SnapshotPosition {
+                ticker: &p.ticker,
+                quantity: p.quantity,
+                entry_price: p.entry_price,
+                sell_price: p.sell_price,
+            })
+            .collect()
+    }
+}
+
+/// The virtual balance under `--simulate`, else the exchange balance.
+pub fn balance_cents(state: &AppState) -> i64 {
+    if state.sim_mode {
+        state.sim_balance_cents
+    } else {
+        state.balance_cents
+    }
+}
+
+pub fn snapshot(state: &AppState) -> Event<'_> {
+    Event::Snapshot {
+        ts: chrono::Utc::now(),
+        sim: state.sim_mode,
+        paused: state.is_paused,
+        balance_cents: balance_cents(state),
+        exposure_cents: state.total_exposure_cents,
+        realized_pnl_cents: state.realized_pnl_cents,
+        total_trades: state.total_trades,
+        winning_trades: state.winning_trades,
+        kalshi_ws_connected: state.kalshi_ws_connected,
+        feed_tier: state.feed_tier.label(),
+        drawdown_tier: state.drawdown_tier.label(),
+        markets: state.markets.len(),
+        positions: positions(state),
+    }
+}
+
+enum Output {
+    Stdout(tokio::io::Stdout),
+    Socket(broadcast::Sender<String>),
+}
+
+impl Output {
+    async fn open(sink: &Sink) -> Result<Self> {
+        match sink {
+            Sink::Stdout => Ok(Self::Stdout(tokio::io::stdout())),
+            Sink::Socket(path) => Ok(Self::Socket(serve_socket(path)?)),
+        }
+    }
+
+    async fn emit(&mut self, event: &Event<'_>) -> Result<()> {
+        let mut line = serde_json::to_string(event)?;
+        line.push('\n');
+        match self {
+            Self::Stdout(out) => {
+                out.write_all(line.as_bytes()).await?;
+                out.flush().await?;
+            }
+            // No clients connected is not an error
+            Self::Socket(tx) => {
+                let _ = tx.send(line);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Listen on `path` and copy every event line to each connected client. A
+/// stale socket file from an earlier run is replaced.
+#[cfg(unix)]
+fn serve_socket(path: &std::path::Path) -> Result<broadcast::Sender<String>> {
+    if path.exists() {
+        std::fs::remove_file(path)
+            .with_context(|| format!("failed to remove stale socket {}", path.display()))?;
+    }
+    let listener = tokio::net::UnixListener::bind(path)
+        .with_context(|| format!("failed to bind {}", path.display()))?;
+    let (tx, _) = broadcast::channel::<String>(SOCKET_BUFFER);
+    let clients = tx.clone();
+    tokio::spawn(async move {
+        loop {
+            let mut stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    tracing::warn!(error = %e, "headless socket accept failed");
+                    continue;
+                }
+            };
+            let mut rx = clients.subscribe();
+            tokio::spawn(async move {
+                loop {
+                    let line = match rx.recv().await {
+                        Ok(line) => line,
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            tracing::warn!(skipped = n, "headless client too slow, events dropped");
+                            continue;
+                        }
+                        Err(broadcast::error::RecvError::Closed) => return,
+                    };
+                    // Client went away
+                    if stream.write_all(line.as_bytes()).await.is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+    });
+    Ok(tx)
+}
+
+#[cfg(not(unix))]
+fn serve_socket(_path: &std::path::Path) -> Result<broadcast::Sender<String>> {
+    anyhow::bail!("--headless=PATH needs unix sockets; use --headless for stdout")
+}
+
+/// Run in place of the TUI: stream events until Ctrl-C, then stop the engine.
+pub async fn run(
+    mut state_rx: watch::Receiver<AppState>,
+    cmd_tx: mpsc::Sender<TuiCommand>,
+    sink: Sink,
+) -> Result<()> {
+    let mut output = Output::open(&sink).await?;
+    let mut cursor = EventCursor::default();
+    let mut snapshot_tick = tokio::time::interval(SNAPSHOT_INTERVAL);
+    loop {
+        tokio::select! {
+            changed = state_rx.changed() => {
+                if changed.is_err() {
+                    return Ok(());
+                }
+                let state = state_rx.borrow_and_update().clone();
+                for event in cursor.advance(&state) {
+                    output.emit(&event).await?;
+                }
+            }
+            _ = snapshot_tick.tick() => {
+                let state = state_rx.borrow().clone();
+                output.emit(&snapshot(&state)).await?;
+            }
+            _ = tokio::signal::ctrl_c() => {
+                let _ = cmd_tx.send(TuiCommand::Quit).await;
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(ticker: &str) -> TradeRow {
+        TradeRow {
+            id: 0,
+            time: "12:00:00".to_string(),
+            action: "BUY".to_string(),
+            ticker: ticker.to_string(),
+            price: 45,
+            quantity: 2,
+            order_type: "SIM".to_string(),
+            pnl: None,
+            slippage: None,
+            source: "espn".to_string(),
+            fair_value_basis: String::new(),
+            phase: "Q3".to_string(),
+            note: String::new(),
+        }
+    }
+
+    fn json(events: &[Event]) -> Vec<serde_json::Value> {
+        events
+            .iter()
+            .map(|e| serde_json::to_value(e).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_cursor_emits_only_new_events() {
+        let mut state = AppState::new();
+        let mut cursor = EventCursor::default();
+        state.push_log("INFO", "started".to_string());
+        state.push_trade(trade("A"));
+        let events = json(&cursor.advance(&state));
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["type"], "log");
+        assert_eq!(events[0]["message"], "started");
+        assert_eq!(events[1]["type"], "trade");
+        assert_eq!(events[1]["id"], 1);
+
+        assert!(cursor.advance(&state).is_empty());
+
+        state.push_trade(trade("B"));
+        let events = json(&cursor.advance(&state));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["ticker"], "B");
+    }
+
+    #[test]
+    fn test_cursor_resumes_logs_after_ring_drops() {
+        let mut state = AppState::new();
+        let mut cursor = EventCursor::default();
+        state.push_log("INFO", "first".to_string());
+        cursor.advance(&state);
+        // 250 more entries push "first" out of the 200-entry ring
+        for i in 0..250 {
+            state.push_log("INFO", format!("line {}", i));
+        }
+        let events = json(&cursor.advance(&state));
+        assert_eq!(events.len(), 200);
+        state.push_log("INFO", "last".to_string());
+        let events = json(&cursor.advance(&state));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["message"], "last");
+    }
+
+    #[test]
+    fn test_snapshot_reports_sim_balance() {
+        let mut state = AppState::new();
+        state.sim_mode = true;
+        let event = serde_json::to_value(snapshot(&state)).unwrap();
+        assert_eq!(event["type"], "snapshot");
+        assert_eq!(event["balance_cents"], 100_000);
+        assert_eq!(event["positions"], serde_json::json!([]));
+    }
+}
@@ -0,0 +1,555 @@
+//! Bulk historical capture downloader.
+//!
+//! `kalshi-arb download --from YYYY-MM-DD [--to YYYY-MM-DD] [--sports a,b]
+//! [--out DIR] [--odds-every MINUTES]` assembles a backtest dataset instead of
+//! recording one live. For each sport and game day it fetches the settled
+//! game markets of the sport's Kalshi series, their one-minute candles as
+//! top-of-book snapshots, The Odds API historical snapshots across the day's
+//! games, the final scoreboard, and each market's settlement. Everything is
+//! written in the `--record` capture format, so the directory goes straight
+//! to `--backtest`.
+//!
+//! Candles carry no depth, so each book holds one level per side at a nominal
+//! size. Historical odds cost 10 credits per snapshot; `--odds-every` trades
+//! resolution for quota.
+
+use crate::backtest::{RecordedData, RecordedEvent};
+use crate::config::{Config, ScoreFeedConfig};
+use crate::feed::score_feed::{self, ScoreboardParser};
+use crate::feed::the_odds_api::TheOddsApi;
+use crate::journal::BookSnapshot;
+use crate::kalshi::rest::KalshiRest;
+use crate::kalshi::types::{Candlestick, Market};
+use crate::network::{self, Network};
+use crate::recorder;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, TimeZone, Utc};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Minutes between odds snapshots when `--odds-every` is not given.
+pub const DEFAULT_ODDS_EVERY_MINS: u32 = 10;
+/// Candle period in minutes.
+const CANDLE_MINUTES: u32 = 1;
+/// Contracts shown at each candle-derived book level.
+const CANDLE_DEPTH: i64 = 100;
+/// How far before the start a game's window opens.
+const PRE_GAME_MINS: i64 = 60;
+/// Cap on a game's window when its market closed long after the start.
+const MAX_GAME_HOURS: i64 = 8;
+/// Settled markets closing this long into the day after `--to` still belong
+/// to late games on `--to`.
+const LATE_CLOSE_HOURS: i64 = 12;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DownloadArgs {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// Sport keys; every enabled sport when empty.
+    pub sports: Vec<String>,
+    pub out: Option<PathBuf>,
+    pub odds_every_mins: u32,
+}
+
+fn eastern() -> FixedOffset {
+    FixedOffset::west_opt(5 * 3600).unwrap()
+}
+
+/// Midnight Eastern at the start of `day`.
+fn day_start(day: NaiveDate) -> DateTime<Utc> {
+    eastern()
+        .from_local_datetime(&day.and_hms_opt(0, 0, 0).unwrap())
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+fn parse_time(s: Option<&str>) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s?)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Eastern calendar day a market's game started on.
+fn game_day(market: &Market) -> Option<NaiveDate> {
+    parse_time(market.event_start_time.as_deref()).map(|t| t.with_timezone(&eastern()).date_naive())
+}
+
+/// Span of a market's history worth replaying: from an hour before the start
+/// to the close, capped at `MAX_GAME_HOURS`.
+fn trading_window(market: &Market) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let start = parse_time(market.event_start_time.as_deref())?;
+    let cap = start + Duration::hours(MAX_GAME_HOURS);
+    let end = parse_time(market.close_time.as_deref()).map_or(cap, |close| close.min(cap));
+    Some((start - Duration::minutes(PRE_GAME_MINS), end))
+}
+
+/// A settled market as it looked while it traded, so the replayed pipeline
+/// treats it as open.
+fn as_traded(market: &Market) -> Market {
+    Market {
+        status: "active".to_string(),
+        result: None,
+        settlement_value_dollars: None,
+        ..market.clone()
+    }
+}
+
+/// One-level book from a candle's closing bid and ask, `None` when neither
+/// side was quoted.
+fn candle_book(candle: &Candlestick) -> Option<BookSnapshot> {
+    let bid = candle.yes_bid.close_cents();
+    let ask = candle.yes_ask.close_cents();
+    let book = BookSnapshot {
+        yes: (1..=99)
+            .contains(&bid)
+            .then_some((bid, CANDLE_DEPTH))
+            .into_iter()
+            .collect(),
+        no: (1..=99)
+            .contains(&ask)
+            .then_some((100 - ask, CANDLE_DEPTH))
+            .into_iter()
+            .collect(),
+    };
+    (!book.yes.is_empty() || !book.no.is_empty()).then_some(book)
+}
+
+/// Odds snapshot times from `start` to `end` inclusive, `every` apart.
+fn snapshot_times(start: DateTime<Utc>, end: DateTime<Utc>, every: Duration) -> Vec<DateTime<Utc>> {
+    let mut times = Vec::new();
+    let mut at = start;
+    while at <= end {
+        times.push(at);
+        at += every;
+    }
+    times
+}
+
+/// The ESPN scoreboard among a score feed's URLs for `day`, with the parser
+/// that reads it. Only ESPN serves past dates.
+fn espn_scoreboard(feed: &ScoreFeedConfig, day: NaiveDate) -> Option<(String, ScoreboardParser)> {
+    let (primary, fallback) = score_feed::scoreboard_parsers(&feed.feed_type)?;
+    let (url, parser) = if feed.primary_url.contains("espn.com") {
+        (feed.primary_url.as_str(), primary)
+    } else {
+        (
+            feed.fallback_url
+                .as_deref()
+                .filter(|u| u.contains("espn.com"))?,
+            fallback,
+        )
+    };
+    let sep = if url.contains('?') { '&' } else { '?' };
+    Some((
+        format!("{}{}dates={}", url, sep, day.format("%Y%m%d")),
+        parser,
+    ))
+}
+
+#[derive(Debug, Default)]
+pub struct DownloadSummary {
+    pub markets: usize,
+    pub books: usize,
+    pub odds_snapshots: usize,
+    pub scoreboards: usize,
+    pub settlements: usize,
+    /// Requests that failed and were left out of the capture.
+    pub failures: usize,
+}
+
+/// Default output directory: `<recorder dir>/history-<from>-<to>`.
+pub fn default_out_dir(config: &Config, args: &DownloadArgs) -> PathBuf {
+    Path::new(&config.recorder.dir).join(format!(
+        "history-{}-{}",
+        args.from.format("%Y%m%d"),
+        args.to.format("%Y%m%d")
+    ))
+}
+
+/// Download every selected sport's history in `args` into `out`.
+pub async fn run(
+    config: &Config,
+    network: &Network,
+    rest: &KalshiRest,
+    odds_api_key: Option<String>,
+    args: &DownloadArgs,
+    out: &Path,
+) -> Result<DownloadSummary> {
+    let mut sports: Vec<String> = if args.sports.is_empty() {
+        config
+            .sports
+            .iter()
+            .filter(|(_, s)| s.enabled)
+            .map(|(k, _)| k.clone())
+            .collect()
+    } else {
+        args.sports.clone()
+    };
+    sports.sort();
+    for sport in &sports {
+        anyhow::ensure!(
+            config.sports.contains_key(sport),
+            "unknown sport {:?} (not in config.toml)",
+            sport
+        );
+    }
+    if out.join("markets.jsonl").exists() {
+        anyhow::bail!("{} already holds a capture", out.display());
+    }
+
+    let odds_source = config
+        .odds_sources
+        .iter()
+        .find(|(_, s)| s.source_type == "the-odds-api");
+    let mut odds_api = odds_api_key.map(|key| {
+        let source = odds_source.map(|(_, s)| s);
+        TheOddsApi::new(
+            key,
+            source
+                .and_then(|s| s.base_url.as_deref())
+                .unwrap_or("https://api.the-odds-api.com"),
+            source
+                .and_then(|s| s.bookmakers.as_deref())
+                .unwrap_or("draftkings,fanduel,betmgm,caesars"),
+            network.client_builder(odds_source.map_or("the-odds-api", |(name, _)| name)),
+        )
+    });
+    let scores_client = network
+        .client_builder(network::SCORE_FEED)
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .context("failed to build score feed client")?;
+    let odds_every = Duration::minutes(args.odds_every_mins as i64);
+
+    let mut summary = DownloadSummary::default();
+    let mut events: Vec<RecordedEvent> = Vec::new();
+    let mut push = |at: DateTime<Utc>, data: RecordedData| events.push(RecordedEvent { at, data });
+
+    for sport in &sports {
+        let sport_config = &config.sports[sport];
+        let series = &sport_config.kalshi_series;
+        let min_close = day_start(args.from);
+        let max_close = day_start(args.to + Duration::days(1)) + Duration::hours(LATE_CLOSE_HOURS);
+        let markets = match rest
+            .get_settled_markets_between(series, min_close.timestamp(), max_close.timestamp())
+            .await
+        {
+            Ok(markets) => markets,
+            Err(e) => {
+                println!("  {}: FAILED ({:#})", sport, e);
+                tracing::warn!(sport = %sport, error = %e, "history market fetch failed");
+                summary.failures += 1;
+                continue;
+            }
+        };
+
+        let mut days: BTreeMap<NaiveDate, Vec<Market>> = BTreeMap::new();
+        for market in markets {
+            if let Some(day) = game_day(&market).filter(|d| (args.from..=args.to).contains(d)) {
+                days.entry(day).or_default().push(market);
+            }
+        }
+        let uses_odds_api = config
+            .odds_sources
+            .get(&sport_config.odds_source)
+            .is_some_and(|s| s.source_type == "the-odds-api");
+        if uses_odds_api && odds_api.is_none() {
+            println!("  {}: odds skipped (ODDS_API_KEY not set)", sport);
+        }
+
+        for (day, markets) in &days {
+            let windows: Vec<(&Market, DateTime<Utc>, DateTime<Utc>)> = markets
+                .iter()
+                .filter_map(|m| trading_window(m).map(|(start, end)| (m, start, end)))
+                .collect();
+            let (Some(day_open), Some(day_close)) = (
+                windows.iter().map(|w| w.1).min(),
+                windows.iter().map(|w| w.2).max(),
+            ) else {
+                continue;
+            };
+
+            push(
+                day_open,
+                RecordedData::Markets {
+                    sport: sport.clone(),
+                    markets: markets.iter().map(as_traded).collect(),
+                    segment: None,
+                },
+            );
+            summary.markets += markets.len();
+
+            for (market, start, end) in &windows {
+                let candles = match rest
+                    .get_candlesticks(
+                        series,
+                        &market.ticker,
+                        start.timestamp(),
+                        end.timestamp(),
+                        CANDLE_MINUTES,
+                    )
+                    .await
+                {
+                    Ok(candles) => candles,
+                    Err(e) => {
+                        tracing::warn!(ticker = %market.ticker, error = %e, "candle fetch failed");
+                        summary.failures += 1;
+                        continue;
+                    }
+                };
+                for candle in &candles {
+                    let (Some(book), Some(at)) = (
+                        candle_book(candle),
+                        DateTime::from_timestamp(candle.end_period_ts, 0),
+                    ) else {
+                        continue;
+                    };
+                    push(
+                        at,
+                        RecordedData::Book {
+                            ticker: market.ticker.clone(),
+                            book,
+                            update: "CANDLE".to_string(),
+                        },
+                    );
+                    summary.books += 1;
+                }
+            }
+
+            if let Some(api) = odds_api.as_mut().filter(|_| uses_odds_api) {
+                let mut last = None;
+                for at in snapshot_times(day_open, day_close, odds_every) {
+                    match api.fetch_historical_odds(sport, at).await {
+                        // Sparse history returns the same snapshot again
+                        Ok((snapshot_at, _)) if last == Some(snapshot_at) => {}
+                        Ok((snapshot_at, updates)) => {
+                            last = Some(snapshot_at);
+                            push(
+                                snapshot_at,
+                                RecordedData::Odds {
+                                    sport: sport.clone(),
+                                    updates,
+                                },
+                            );
+                            summary.odds_snapshots += 1;
+                        }
+                        Err(e) => {
+                            tracing::warn!(sport = %sport, %at, error = %e, "historical odds fetch failed");
+                            summary.failures += 1;
+                        }
+                    }
+                }
+            }
+
+            if let Some((url, parser)) = sport_config
+                .score_feed
+                .as_ref()
+                .and_then(|feed| espn_scoreboard(feed, *day))
+            {
+                let scores = async {
+                    let body = scores_client.get(&url).send().await?.text().await?;
+                    parser(&body)
+                };
+                match scores.await {
+                    Ok(updates) => {
+                        push(
+                            day_close,
+                            RecordedData::Scores {
+                                sport: sport.clone(),
+                                updates,
+                            },
+                        );
+                        summary.scoreboards += 1;
+                    }
+                    Err(e) => {
+                        tracing::warn!(sport = %sport, %day, error = %e, "scoreboard fetch failed");
+                        summary.failures += 1;
+                    }
+                }
+            }
+
+            for market in markets {
+                let Some(result) = market
+                    .result
+                    .as_deref()
+                    .filter(|r| *r == "yes" || *r == "no")
+                else {
+                    continue;
+                };
+                let at = parse_time(market.close_time.as_deref()).unwrap_or(day_close);
+                push(
+                    at,
+                    RecordedData::Settlement {
+                        ticker: market.ticker.clone(),
+                        result: result.to_string(),
+                    },
+                );
+                summary.settlements += 1;
+            }
+        }
+
+        println!(
+            "  {}: {} game day(s), {} markets",
+            sport,
+            days.len(),
+            days.values().map(Vec::len).sum::<usize>()
+        );
+    }
+
+    events.sort_by_key(|e| e.at);
+    recorder::write_capture(out, &events)?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kalshi::types::CandlePrices;
+
+    fn market(start: &str, close: &str) -> Market {
+        Market {
+            ticker: "KXNBAGAME-26MAR10LALBOS-BOS".to_string(),
+            event_ticker: "KXNBAGAME-26MAR10LALBOS".to_string(),
+            title: "Los Angeles L at Boston Winner?".to_string(),
+            status: "finalized".to_string(),
+            yes_bid_dollars: None,
+            yes_ask_dollars: None,
+            no_bid_dollars: None,
+            no_ask_dollars: None,
+            volume: 1000,
+            open_interest: 0,
+            close_time: Some(close.to_string()),
+            expected_expiration_time: None,
+            event_start_time: Some(start.to_string()),
+            result: Some("yes".to_string()),
+            last_price_dollars: Some("0.9900".to_string()),
+            settlement_value_dollars: Some("1.0000".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_market_day_and_window() {
+        // 7:30pm Eastern tip-off, closed after the game
+        let m = market("2026-03-11T00:30:00Z", "2026-03-11T03:10:00Z");
+        assert_eq!(game_day(&m), NaiveDate::from_ymd_opt(2026, 3, 10));
+        let (start, end) = trading_window(&m).unwrap();
+        assert_eq!(start.to_rfc3339(), "2026-03-10T23:30:00+00:00");
+        assert_eq!(end.to_rfc3339(), "2026-03-11T03:10:00+00:00");
+
+        // A close long after the game is capped
+        let late = market("2026-03-11T00:30:00Z", "2026-03-25T00:00:00Z");
+        assert_eq!(
+            trading_window(&late).unwrap().1.to_rfc3339(),
+            "2026-03-11T08:30:00+00:00"
+        );
+
+        let traded = as_traded(&m);
+        assert_eq!(traded.status, "active");
+        assert!(traded.result.is_none() && traded.settlement_value_dollars.is_none());
+    }
+
+    #[test]
+    fn test_candle_book() {
+        let candle = |bid: CandlePrices, ask: CandlePrices| Candlestick {
+            end_period_ts: 0,
+            yes_bid: bid,
+            yes_ask: ask,
+            volume: 0,
+        };
+        let cents = |c| CandlePrices {
+            close: Some(c),
+            close_dollars: None,
+        };
+        let dollars = |d: &str| CandlePrices {
+            close: None,
+            close_dollars: Some(d.to_string()),
+        };
+
+        let book = candle_book(&candle(cents(52), dollars("0.5500"))).unwrap();
+        assert_eq!(book.yes, vec![(52, CANDLE_DEPTH)]);
+        assert_eq!(book.no, vec![(45, CANDLE_DEPTH)]);
+        // One-sided: an empty ask leaves only the bid
+        let bid_only = candle_book(&candle(cents(52), cents(0))).unwrap();
+        assert!(bid_only.no.is_empty());
+        assert!(candle_book(&candle(cents(0), CandlePrices::default())).is_none());
+    }
+
+    #[test]
+    fn test_snapshot_times_and_scoreboard_url() {
+        let start = parse_time(Some("2026-03-10T23:30:00Z")).unwrap();
+        let times = snapshot_times(start, start + Duration::minutes(25), Duration::minutes(10));
+        assert_eq!(times.len(), 3);
+        assert_eq!(times[2], start + Duration::minutes(20));
+
+        let day = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+        let feed = ScoreFeedConfig {
+            feed_type: "basketball".to_string(),
+            primary_url:
+                "https://cdn.nba.com/static/json/liveData/scoreboard/todaysScoreboard_00.json"
+                    .to_string(),
+            fallback_url: Some(
+                "https://site.api.espn.com/apis/site/v2/sports/basketball/nba/scoreboard"
+                    .to_string(),
+            ),
+            live_poll_s: 1,
+            pre_game_poll_s: 60,
+            failover_threshold: 3,
+            request_timeout_ms: 5000,
+            align_to_cadence: false,
+            cadence_offset_ms: 0,
+        };
+        let (url, _) = espn_scoreboard(&feed, day).unwrap();
+        assert_eq!(
+            url,
+            "https://site.api.espn.com/apis/site/v2/sports/basketball/nba/scoreboard?dates=20260310"
+        );
+
+        let college = ScoreFeedConfig {
+            feed_type: "college-football".to_string(),
+            primary_url: "https://site.api.espn.com/apis/site/v2/sports/football/college-football/scoreboard?groups=80".to_string(),
+            fallback_url: None,
+            ..feed.clone()
+        };
+        assert!(espn_scoreboard(&college, day)
+            .unwrap()
+            .0
+            .ends_with("?groups=80&dates=20260310"));
+        let no_espn = ScoreFeedConfig {
+            fallback_url: None,
+            ..feed
+        };
+        assert!(espn_scoreboard(&no_espn, day).is_none());
+    }
+
+    #[test]
+    fn test_written_capture_loads_for_backtest() {
+        let dir = std::env::temp_dir().join(format!("history-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let m = market("2026-03-11T00:30:00Z", "2026-03-11T03:10:00Z");
+        let at = parse_time(Some("2026-03-11T00:00:00Z")).unwrap();
+        let events = vec![
+            RecordedEvent {
+                at,
+                data: RecordedData::Markets {
+                    sport: "basketball".to_string(),
+                    markets: vec![as_traded(&m)],
+                    segment: None,
+                },
+            },
+            RecordedEvent {
+                at: at + Duration::hours(3),
+                data: RecordedData::Settlement {
+                    ticker: m.ticker.clone(),
+                    result: "yes".to_string(),
+                },
+            },
+        ];
+        recorder::write_capture(&dir, &events).unwrap();
+
+        let loaded = crate::backtest::load_events(&dir).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert!(matches!(loaded[0].data, RecordedData::Markets { .. }));
+        assert!(dir.join("settlements.jsonl").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
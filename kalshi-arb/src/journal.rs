@@ -0,0 +1,583 @@
+//! Persistent SQLite trade journal for post-trade review.
+//!
+//! Every journal entry (orders, rejects, notes, settlements, equity samples)
+//! is stored as JSON in `entries`, including the full `SignalTrace`. Each row
+//! pushed to the trades panel is also stored in `trades`, so today's trades
+//! can be shown again after a restart.
+
+use crate::engine::strategy::SkipReason;
+use crate::pipeline::SignalTrace;
+use crate::tui::state::TradeRow;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Trades restored into the trades panel on startup (the panel's capacity).
+const RESTORED_TRADES: usize = 100;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS entries (
+        id INTEGER PRIMARY KEY,
+        ts TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        ticker TEXT,
+        sim INTEGER,
+        body TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS entries_kind_ticker ON entries (kind, ticker);
+    CREATE TABLE IF NOT EXISTS trades (
+        id INTEGER PRIMARY KEY,
+        ts TEXT NOT NULL,
+        local_date TEXT NOT NULL,
+        time TEXT NOT NULL,
+        action TEXT NOT NULL,
+        ticker TEXT NOT NULL,
+        price INTEGER NOT NULL,
+        quantity INTEGER NOT NULL,
+        order_type TEXT NOT NULL,
+        pnl INTEGER,
+        slippage INTEGER,
+        source TEXT NOT NULL,
+        fair_value_basis TEXT NOT NULL,
+        phase TEXT NOT NULL,
+        note TEXT NOT NULL,
+        sim INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS trades_local_date ON trades (local_date);
+";
+
+/// Full depth of one ticker's orderbook at a point in time.
+/// Each level is (price_cents, quantity), best price first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BookSnapshot {
+    pub yes: Vec<(u32, i64)>,
+    pub no: Vec<(u32, i64)>,
+}
+
+/// One journal line.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)]
+pub enum JournalEntry {
+    /// A signal converted into an order (live submit or sim entry).
+    Order {
+        ts: DateTime<Utc>,
+        ticker: String,
+        side: String,
+        action: String,
+        quantity: u32,
+        price: u32,
+        is_taker: bool,
+        sim: bool,
+        source: String,
+        trace: SignalTrace,
+        book: Option<BookSnapshot>,
+    },
+    /// A signal with tradeable edge that was rejected before becoming an order.
+    Reject {
+        ts: DateTime<Utc>,
+        ticker: String,
+        side: String,
+        reason: SkipReason,
+        sim: bool,
+        trace: SignalTrace,
+    },
+    /// A review note or tag attached to a trade from the trades view.
+    Note {
+        ts: DateTime<Utc>,
+        trade_id: u64,
+        /// Local time of the trade as shown in the trades view.
+        trade_time: String,
+        ticker: String,
+        action: String,
+        price: u32,
+        quantity: u32,
+        note: String,
+    },
+    /// Final outcome of a settled market, loaded as ground truth for
+    /// win-prob calibration and backtest validation.
+    Settlement {
+        /// When the outcome was loaded.
+        ts: DateTime<Utc>,
+        series: String,
+        ticker: String,
+        event_ticker: String,
+        title: String,
+        /// "yes" or "no".
+        result: String,
+        /// Payout per YES contract in cents (100 or 0).
+        settlement_value: u32,
+        /// Last traded price before close, in cents.
+        last_price: u32,
+        close_time: Option<String>,
+    },
+    /// A sample of the equity curve: balance plus open positions marked to
+    /// the YES bid.
+    Equity {
+        ts: DateTime<Utc>,
+        sim: bool,
+        equity_cents: i64,
+        balance_cents: i64,
+        positions_cents: i64,
+    },
+}
+
+/// A `trades` row with when it was stored and in which mode.
+#[derive(Debug, Clone)]
+pub struct StoredTrade {
+    pub ts: DateTime<Utc>,
+    pub sim: bool,
+    pub trade: TradeRow,
+}
+
+/// Cheaply cloneable journal handle. A disabled journal drops all entries.
+#[derive(Clone, Default)]
+pub struct Journal {
+    conn: Option<Arc<Mutex<Connection>>>,
+}
+
+impl Journal {
+    /// Open (or create) the journal database.
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        let conn = Connection::open(path)
+            .with_context(|| format!("failed to open journal {}", path.display()))?;
+        conn.pragma_update(None, "journal_mode", "WAL")
+            .context("failed to enable WAL")?;
+        conn.pragma_update(None, "synchronous", "NORMAL")
+            .context("failed to set synchronous mode")?;
+        conn.execute_batch(SCHEMA)
+            .context("failed to create journal schema")?;
+        Ok(Self {
+            conn: Some(Arc::new(Mutex::new(conn))),
+        })
+    }
+
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Record an entry. Failures are logged, never propagated to the trading path.
+    pub fn record(&self, entry: &JournalEntry) {
+        if let Err(e) = self.try_record(entry) {
+            tracing::warn!(error = %e, "failed to write journal entry");
+        }
+    }
+
+    fn try_record(&self, entry: &JournalEntry) -> Result<()> {
+        let Some(conn) = &self.conn else {
+            return Ok(());
+        };
+        let body = serde_json::to_value(entry).context("failed to serialize journal entry")?;
+        let Ok(conn) = conn.lock() else {
+            return Ok(());
+        };
+        conn.execute(
+            "INSERT INTO entries (ts, kind, ticker, sim, body) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                body["ts"].as_str(),
+                body["kind"].as_str(),
+                body["ticker"].as_str(),
+                body["sim"].as_bool(),
+                body.to_string(),
+            ],
+        )?;
+        // Keep today's stored trade in sync with its review note
+        if let JournalEntry::Note {
+            trade_time,
+            ticker,
+            action,
+            note,
+            ..
+        } = entry
+        {
+            conn.execute(
+                "UPDATE trades SET note = ?1
+                 WHERE local_date = ?2 AND time = ?3 AND ticker = ?4 AND action = ?5",
+                params![note, local_today(), trade_time, ticker, action],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Store a row shown in the trades panel. Failures are logged.
+    pub fn record_trade(&self, trade: &TradeRow, sim: bool) {
+        let Some(conn) = &self.conn else {
+            return;
+        };
+        let Ok(conn) = conn.lock() else {
+            return;
+        };
+        let result = conn.execute(
+            "INSERT INTO trades (ts, local_date, time, action, ticker, price, quantity,
+                order_type, pnl, slippage, source, fair_value_basis, phase, note, sim)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+            params![
+                Utc::now().to_rfc3339(),
+                local_today(),
+                trade.time,
+                trade.action,
+                trade.ticker,
+                trade.price,
+                trade.quantity,
+                trade.order_type,
+                trade.pnl,
+                trade.slippage,
+                trade.source,
+                trade.fair_value_basis,
+                trade.phase,
+                trade.note,
+                sim,
+            ],
+        );
+        if let Err(e) = result {
+            tracing::warn!(error = %e, "failed to write journal trade");
+        }
+    }
+
+    /// The most recent trades stored today (local date), oldest first.
+    pub fn todays_trades(&self) -> Result<Vec<TradeRow>> {
+        let Some(conn) = &self.conn else {
+            return Ok(Vec::new());
+        };
+        let Ok(conn) = conn.lock() else {
+            return Ok(Vec::new());
+        };
+        let mut stmt = conn.prepare(
+            "SELECT time, action, ticker, price, quantity, order_type, pnl, slippage,
+                source, fair_value_basis, phase, note
+             FROM trades WHERE local_date = ?1 ORDER BY id DESC LIMIT ?2",
+        )?;
+        let mut trades = stmt
+            .query_map(params![local_today(), RESTORED_TRADES], trade_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        trades.reverse();
+        Ok(trades)
+    }
+
+    /// Today's trades (local date) with a realized P&L in the given mode,
+    /// oldest first.
+    pub fn todays_closed_trades(&self, sim: bool) -> Result<Vec<TradeRow>> {
+        let Some(conn) = &self.conn else {
+            return Ok(Vec::new());
+        };
+        let Ok(conn) = conn.lock() else {
+            return Ok(Vec::new());
+        };
+        let mut stmt = conn.prepare(
+            "SELECT time, action, ticker, price, quantity, order_type, pnl, slippage,
+                source, fair_value_basis, phase, note
+             FROM trades WHERE local_date = ?1 AND sim = ?2 AND pnl IS NOT NULL
+             ORDER BY id",
+        )?;
+        let trades = stmt
+            .query_map(params![local_today(), sim], trade_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(trades)
+    }
+
+    /// Every stored trade, oldest first, for session replay.
+    pub fn stored_trades(&self) -> Result<Vec<StoredTrade>> {
+        let Some(conn) = &self.conn else {
+            return Ok(Vec::new());
+        };
+        let Ok(conn) = conn.lock() else {
+            return Ok(Vec::new());
+        };
+        let mut stmt = conn.prepare(
+            "SELECT time, action, ticker, price, quantity, order_type, pnl, slippage,
+                source, fair_value_basis, phase, note, ts, sim
+             FROM trades ORDER BY id",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(12)?,
+                    row.get::<_, bool>(13)?,
+                    trade_row(row)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        rows.into_iter()
+            .map(|(ts, sim, trade)| {
+                let ts = DateTime::parse_from_rfc3339(&ts)
+                    .with_context(|| format!("bad trade timestamp {:?}", ts))?;
+                Ok(StoredTrade {
+                    ts: ts.with_timezone(&Utc),
+                    sim,
+                    trade,
+                })
+            })
+            .collect()
+    }
+
+    /// Tickers with at least one entry of `kind` (e.g. "settlement").
+    pub fn recorded_tickers(&self, kind: &str) -> Result<HashSet<String>> {
+        let Some(conn) = &self.conn else {
+            return Ok(HashSet::new());
+        };
+        let Ok(conn) = conn.lock() else {
+            return Ok(HashSet::new());
+        };
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT ticker FROM entries WHERE kind = ?1 AND ticker IS NOT NULL",
+        )?;
+        let tickers = stmt
+            .query_map(params![kind], |row| row.get(0))?
+            .collect::<rusqlite::Result<HashSet<String>>>()?;
+        Ok(tickers)
+    }
+
+    /// Stored entries of `kind` as JSON, oldest first, for after-the-fact analysis.
+    pub fn entries(&self, kind: &str) -> Result<Vec<serde_json::Value>> {
+        let Some(conn) = &self.conn else {
+            return Ok(Vec::new());
+        };
+        let Ok(conn) = conn.lock() else {
+            return Ok(Vec::new());
+        };
+        let mut stmt = conn.prepare("SELECT body FROM entries WHERE kind = ?1 ORDER BY id")?;
+        let bodies = stmt
+            .query_map(params![kind], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        bodies
+            .iter()
+            .map(|b| serde_json::from_str(b).context("corrupt journal entry"))
+            .collect()
+    }
+
+    /// Every stored entry as JSON, oldest first, for session replay.
+    pub fn all_entries(&self) -> Result<Vec<serde_json::Value>> {
+        let Some(conn) = &self.conn else {
+            return Ok(Vec::new());
+        };
+        let Ok(conn) = conn.lock() else {
+            return Ok(Vec::new());
+        };
+        let mut stmt = conn.prepare("SELECT body FROM entries ORDER BY id")?;
+        let bodies = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        bodies
+            .iter()
+            .map(|b| serde_json::from_str(b).context("corrupt journal entry"))
+            .collect()
+    }
+}
+
+/// A `trades` row selected as time, action, ticker, price, quantity,
+/// order_type, pnl, slippage, source, fair_value_basis, phase, note.
+fn trade_row(row: &rusqlite::Row) -> rusqlite::Result<TradeRow> {
+    Ok(TradeRow {
+        id: 0,
+        time: row.get(0)?,
+        action: row.get(1)?,
+        ticker: row.get(2)?,
+        price: row.get(3)?,
+        quantity: row.get(4)?,
+        order_type: row.get(5)?,
+        pnl: row.get(6)?,
+        slippage: row.get(7)?,
+        source: row.get(8)?,
+        fair_value_basis: row.get(9)?,
+        phase: row.get(10)?,
+        note: row.get(11)?,
+    })
+}
+
+fn local_today() -> String {
+    chrono::Local::now().date_naive().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::{FairValueInputs, FairValueMethod};
+    use std::time::Instant;
+
+    fn trace() -> SignalTrace {
+        SignalTrace {
+            sport: "basketball".to_string(),
+            ticker: "TEST-T".to_string(),
+            timestamp: Instant::now(),
+            fair_value_method: FairValueMethod::ScoreFeed {
+                source: "espn".to_string(),
+            },
+            fair_value_cents: 60,
+            fair_value_stderr: None,
+            inputs: FairValueInputs::Score {
+                home_score: 50,
+                away_score: 45,
+                elapsed_secs: 1200,
+                period: "3".to_string(),
+                phase: "Q3".to_string(),
+                win_prob: 0.6,
+            },
+            best_bid: 52,
+            best_ask: 54,
+            edge: 6,
+            action: "TAKER BUY".to_string(),
+            net_profit_estimate: 4,
+            quantity: 1,
+            momentum_score: 80.0,
+            momentum_gated: false,
+            skip_reason: None,
+        }
+    }
+
+    #[test]
+    fn test_order_entry_stored_with_book() {
+        let dir = std::env::temp_dir().join(format!("journal-test-{}", std::process::id()));
+        let path = dir.join("journal.db");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let journal = Journal::open(&path).unwrap();
+        let entry = JournalEntry::Order {
+            ts: Utc::now(),
+            ticker: "TEST-T".to_string(),
+            side: "yes".to_string(),
+            action: "buy".to_string(),
+            quantity: 1,
+            price: 54,
+            is_taker: true,
+            sim: false,
+            source: "score-feed".to_string(),
+            trace: trace(),
+            book: Some(BookSnapshot {
+                yes: vec![(52, 10), (51, 5)],
+                no: vec![(46, 7)],
+            }),
+        };
+        journal.record(&entry);
+        journal.record(&entry);
+
+        let orders = journal.entries("order").unwrap();
+        assert_eq!(orders.len(), 2);
+        let v = &orders[0];
+        assert_eq!(v["kind"], "order");
+        assert_eq!(v["book"]["yes"][0], serde_json::json!([52, 10]));
+        assert_eq!(v["trace"]["fair_value_cents"], 60);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_todays_trades_survive_reopen_with_notes() {
+        let dir = std::env::temp_dir().join(format!("journal-trades-{}", std::process::id()));
+        let path = dir.join("journal.db");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let journal = Journal::open(&path).unwrap();
+        let trade = TradeRow {
+            id: 3,
+            time: "20:15:03".to_string(),
+            action: "BUY".to_string(),
+            ticker: "TEST-T".to_string(),
+            price: 54,
+            quantity: 2,
+            order_type: "TAKER".to_string(),
+            pnl: None,
+            slippage: Some(1),
+            source: "espn".to_string(),
+            fair_value_basis: "score".to_string(),
+            phase: "Q3".to_string(),
+            note: String::new(),
+        };
+        journal.record_trade(&trade, true);
+        journal.record(&JournalEntry::Note {
+            ts: Utc::now(),
+            trade_id: 3,
+            trade_time: "20:15:03".to_string(),
+            ticker: "TEST-T".to_string(),
+            action: "BUY".to_string(),
+            price: 54,
+            quantity: 2,
+            note: "model lag".to_string(),
+        });
+        drop(journal);
+
+        let reopened = Journal::open(&path).unwrap();
+        let trades = reopened.todays_trades().unwrap();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].ticker, "TEST-T");
+        assert_eq!(trades[0].slippage, Some(1));
+        assert_eq!(trades[0].note, "model lag");
+        assert_eq!(trades[0].id, 0);
+
+        // Only exits count as closed, and only in their own mode
+        reopened.record_trade(
+            &TradeRow {
+                action: "SELL".to_string(),
+                pnl: Some(12),
+                ..trade.clone()
+            },
+            true,
+        );
+        assert_eq!(reopened.todays_closed_trades(true).unwrap().len(), 1);
+        assert!(reopened.todays_closed_trades(false).unwrap().is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_reject_entry_serializes_reason() {
+        let mut t = trace();
+        t.skip_reason = Some(SkipReason::BreakEvenInfeasible);
+        let entry = JournalEntry::Reject {
+            ts: Utc::now(),
+            ticker: "TEST-T".to_string(),
+            side: "yes".to_string(),
+            reason: SkipReason::BreakEvenInfeasible,
+            sim: true,
+            trace: t,
+        };
+        let v = serde_json::to_value(&entry).unwrap();
+        assert_eq!(v["kind"], "reject");
+        assert_eq!(v["reason"], "break_even_infeasible");
+        assert_eq!(v["trace"]["skip_reason"], "break_even_infeasible");
+    }
+
+    #[test]
+    fn test_note_entry_serializes_with_kind() {
+        let entry = JournalEntry::Note {
+            ts: Utc::now(),
+            trade_id: 7,
+            trade_time: "20:15:03".to_string(),
+            ticker: "TEST-T".to_string(),
+            action: "BUY".to_string(),
+            price: 54,
+            quantity: 1,
+            note: "model lag".to_string(),
+        };
+        let v = serde_json::to_value(&entry).unwrap();
+        assert_eq!(v["kind"], "note");
+        assert_eq!(v["trade_id"], 7);
+        assert_eq!(v["note"], "model lag");
+    }
+
+    #[test]
+    fn test_disabled_journal_is_noop() {
+        let journal = Journal::disabled();
+        journal.record(&JournalEntry::Order {
+            ts: Utc::now(),
+            ticker: "X".to_string(),
+            side: "yes".to_string(),
+            action: "buy".to_string(),
+            quantity: 1,
+            price: 50,
+            is_taker: false,
+            sim: true,
+            source: String::new(),
+            trace: trace(),
+            book: None,
+        });
+        assert!(journal.todays_trades().unwrap().is_empty());
+    }
+}
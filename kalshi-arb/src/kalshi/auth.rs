@@ -2,88 +2,45 @@ use anyhow::{Context, Result};
 use base64::Engine as _;
 use ring::rand::SystemRandom;
 use ring::signature::{RsaKeyPair, RSA_PSS_SHA256};
+use std::sync::RwLock;
 use std::time::{SystemTime, UNIX_EPOCH};
 
-pub struct KalshiAuth {
+/// API key and the RSA key pair it signs with; replaced together on rotation.
+struct Credentials {
     api_key: String,
     key_pair: RsaKeyPair,
+}
+
+pub struct KalshiAuth {
+    credentials: RwLock<Credentials>,
     rng: SystemRandom,
 }
 
 impl KalshiAuth {
     pub fn new(api_key: String, private_key_pem: &str) -> Result<Self> {
-        // Validate API key looks reasonable
-        let api_key = api_key.trim().to_string();
-        if api_key.is_empty() {
-            anyhow::bail!("API key is empty");
-        }
-        if api_key.len() < 10 {
-            anyhow::bail!(
-                "API key too short ({} chars) — check for truncation",
-                api_key.len()
-            );
-        }
-        if api_key.bytes().any(|b| !(0x20..=0x7e).contains(&b)) {
-            anyhow::bail!(
-                "API key contains non-printable characters — check for BOM, \\r, or copy-paste artifacts \
-                 (first 20 bytes: {:?})",
-                &api_key.as_bytes()[..api_key.len().min(20)]
-            );
-        }
-
-        // Validate PEM has expected structure
-        let pem_trimmed = private_key_pem.trim();
-        if !pem_trimmed.contains("BEGIN") || !pem_trimmed.contains("END") {
-            anyhow::bail!(
-                "Private key does not look like PEM (missing BEGIN/END markers). \
-                 File starts with: {:?}",
-                &pem_trimmed[..pem_trimmed.len().min(60)]
-            );
-        }
-
-        let der = pem_to_der(private_key_pem)?;
-
-        if der.len() < 100 {
-            anyhow::bail!(
-                "Decoded private key is suspiciously small ({} bytes) — \
-                 file may be truncated or corrupted",
-                der.len()
-            );
-        }
-
-        // Try PKCS#8 first, then fall back to PKCS#1 with wrapping
-        let key_pair = match RsaKeyPair::from_pkcs8(&der) {
-            Ok(kp) => kp,
-            Err(pkcs8_err) => {
-                // Key is likely PKCS#1 (BEGIN RSA PRIVATE KEY).
-                // Wrap the raw PKCS#1 DER in a PKCS#8 envelope.
-                let pkcs8 = wrap_pkcs1_in_pkcs8(&der);
-                RsaKeyPair::from_pkcs8(&pkcs8).map_err(|pkcs1_err| {
-                    anyhow::anyhow!(
-                        "Failed to parse RSA key:\n  PKCS#8: {}\n  PKCS#1: {}\n  \
-                         DER size: {} bytes. Check that the key file is not corrupted.",
-                        pkcs8_err,
-                        pkcs1_err,
-                        der.len()
-                    )
-                })?
-            }
-        };
-
-        let key_bits = key_pair.public().modulus_len() * 8;
+        let credentials = load_credentials(api_key, private_key_pem)?;
+        let key_bits = credentials.key_pair.public().modulus_len() * 8;
         println!(
             "  RSA key loaded: {} bits, API key: {}...",
             key_bits,
-            &api_key[..api_key.len().min(8)]
+            &credentials.api_key[..credentials.api_key.len().min(8)]
         );
 
         Ok(Self {
-            api_key,
-            key_pair,
+            credentials: RwLock::new(credentials),
             rng: SystemRandom::new(),
         })
     }
 
+    /// Swap in a new API key and private key. Every client sharing this auth
+    /// signs with them from the next request on; on error the current
+    /// credentials stay in place.
+    pub fn rotate(&self, api_key: String, private_key_pem: &str) -> Result<()> {
+        let credentials = load_credentials(api_key, private_key_pem)?;
+        *self.credentials.write().unwrap() = credentials;
+        Ok(())
+    }
+
     pub fn timestamp_ms() -> u64 {
         SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -92,14 +49,20 @@ impl KalshiAuth {
     }
 
     /// Sign a request and return (timestamp, signature) for headers.
-    pub fn sign(&self, method: &str, path: &str) -> Result<(String, String)> {
+    fn sign(
+        &self,
+        credentials: &Credentials,
+        method: &str,
+        path: &str,
+    ) -> Result<(String, String)> {
         let timestamp = Self::timestamp_ms().to_string();
         // Strip query params before signing
         let path_clean = path.split('?').next().unwrap_or(path);
         let message = format!("{}{}{}", timestamp, method, path_clean);
 
-        let mut signature = vec![0u8; self.key_pair.public().modulus_len()];
-        self.key_pair
+        let key_pair = &credentials.key_pair;
+        let mut signature = vec![0u8; key_pair.public().modulus_len()];
+        key_pair
             .sign(
                 &RSA_PSS_SHA256,
                 &self.rng,
@@ -113,21 +76,86 @@ impl KalshiAuth {
     }
 
     #[allow(dead_code)]
-    pub fn api_key(&self) -> &str {
-        &self.api_key
+    pub fn api_key(&self) -> String {
+        self.credentials.read().unwrap().api_key.clone()
     }
 
     /// Build auth headers for a request.
     pub fn headers(&self, method: &str, path: &str) -> Result<Vec<(String, String)>> {
-        let (timestamp, signature) = self.sign(method, path)?;
+        // One read so the key and signature always come from the same pair
+        let credentials = self.credentials.read().unwrap();
+        let (timestamp, signature) = self.sign(&credentials, method, path)?;
         Ok(vec![
-            ("KALSHI-ACCESS-KEY".to_string(), self.api_key.clone()),
+            ("KALSHI-ACCESS-KEY".to_string(), credentials.api_key.clone()),
             ("KALSHI-ACCESS-TIMESTAMP".to_string(), timestamp),
             ("KALSHI-ACCESS-SIGNATURE".to_string(), signature),
         ])
     }
 }
 
+/// Validate an API key and parse its PEM private key.
+fn load_credentials(api_key: String, private_key_pem: &str) -> Result<Credentials> {
+    // Validate API key looks reasonable
+    let api_key = api_key.trim().to_string();
+    if api_key.is_empty() {
+        anyhow::bail!("API key is empty");
+    }
+    if api_key.len() < 10 {
+        anyhow::bail!(
+            "API key too short ({} chars) — check for truncation",
+            api_key.len()
+        );
+    }
+    if api_key.bytes().any(|b| !(0x20..=0x7e).contains(&b)) {
+        anyhow::bail!(
+            "API key contains non-printable characters — check for BOM, \\r, or copy-paste artifacts \
+             (first 20 bytes: {:?})",
+            &api_key.as_bytes()[..api_key.len().min(20)]
+        );
+    }
+
+    // Validate PEM has expected structure
+    let pem_trimmed = private_key_pem.trim();
+    if !pem_trimmed.contains("BEGIN") || !pem_trimmed.contains("END") {
+        anyhow::bail!(
+            "Private key does not look like PEM (missing BEGIN/END markers). \
+             File starts with: {:?}",
+            &pem_trimmed[..pem_trimmed.len().min(60)]
+        );
+    }
+
+    let der = pem_to_der(private_key_pem)?;
+
+    if der.len() < 100 {
+        anyhow::bail!(
+            "Decoded private key is suspiciously small ({} bytes) — \
+             file may be truncated or corrupted",
+            der.len()
+        );
+    }
+
+    // Try PKCS#8 first, then fall back to PKCS#1 with wrapping
+    let key_pair = match RsaKeyPair::from_pkcs8(&der) {
+        Ok(kp) => kp,
+        Err(pkcs8_err) => {
+            // Key is likely PKCS#1 (BEGIN RSA PRIVATE KEY).
+            // Wrap the raw PKCS#1 DER in a PKCS#8 envelope.
+            let pkcs8 = wrap_pkcs1_in_pkcs8(&der);
+            RsaKeyPair::from_pkcs8(&pkcs8).map_err(|pkcs1_err| {
+                anyhow::anyhow!(
+                    "Failed to parse RSA key:\n  PKCS#8: {}\n  PKCS#1: {}\n  \
+                     DER size: {} bytes. Check that the key file is not corrupted.",
+                    pkcs8_err,
+                    pkcs1_err,
+                    der.len()
+                )
+            })?
+        }
+    };
+
+    Ok(Credentials { api_key, key_pair })
+}
+
 /// Convert PEM-encoded private key to DER bytes.
 /// Handles both PKCS#1 (BEGIN RSA PRIVATE KEY) and PKCS#8 (BEGIN PRIVATE KEY).
 fn pem_to_der(pem: &str) -> Result<Vec<u8>> {
@@ -2,3 +2,4 @@ pub mod auth;
 pub mod rest;
 pub mod types;
 pub mod ws;
+pub mod ws_queue;
@@ -3,16 +3,26 @@ use super::types::*;
 use anyhow::{Context, Result};
 use reqwest::Client;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// Minimum spacing between market list reads, shared by concurrent callers so
+/// parallel series fetches stay under Kalshi's read rate limit.
+const MARKET_READ_INTERVAL: Duration = Duration::from_millis(100);
 
 pub struct KalshiRest {
     client: Client,
     auth: Arc<KalshiAuth>,
     base_url: String,
+    next_market_read: tokio::sync::Mutex<tokio::time::Instant>,
 }
 
 impl KalshiRest {
-    pub fn new(auth: Arc<KalshiAuth>, base_url: &str) -> Result<Self> {
-        let client = Client::builder()
+    pub fn new(
+        auth: Arc<KalshiAuth>,
+        base_url: &str,
+        http: reqwest::ClientBuilder,
+    ) -> Result<Self> {
+        let client = http
             .pool_max_idle_per_host(4)
             .build()
             .context("failed to build HTTP client")?;
@@ -20,22 +30,125 @@ impl KalshiRest {
             client,
             auth,
             base_url: base_url.trim_end_matches('/').to_string(),
+            next_market_read: tokio::sync::Mutex::new(tokio::time::Instant::now()),
         })
     }
 
+    /// Wait for this caller's slot under `MARKET_READ_INTERVAL`.
+    async fn throttle_market_read(&self) {
+        let at = {
+            let mut next = self.next_market_read.lock().await;
+            let at = (*next).max(tokio::time::Instant::now());
+            *next = at + MARKET_READ_INTERVAL;
+            at
+        };
+        tokio::time::sleep_until(at).await;
+    }
+
     /// Fetch all markets for a given series ticker. Paginates automatically.
     pub async fn get_markets_by_series(&self, series_ticker: &str) -> Result<Vec<Market>> {
+        self.get_markets(&format!("series_ticker={}&status=open", series_ticker))
+            .await
+    }
+
+    /// Fetch settled markets for a series that closed at or after `min_close_ts`
+    /// (unix seconds). Paginates automatically.
+    pub async fn get_settled_markets(
+        &self,
+        series_ticker: &str,
+        min_close_ts: i64,
+    ) -> Result<Vec<Market>> {
+        self.get_markets(&format!(
+            "series_ticker={}&status=settled&min_close_ts={}",
+            series_ticker, min_close_ts
+        ))
+        .await
+    }
+
+    /// Fetch settled markets for a series that closed between `min_close_ts`
+    /// and `max_close_ts` (unix seconds). Paginates automatically.
+    pub async fn get_settled_markets_between(
+        &self,
+        series_ticker: &str,
+        min_close_ts: i64,
+        max_close_ts: i64,
+    ) -> Result<Vec<Market>> {
+        self.get_markets(&format!(
+            "series_ticker={}&status=settled&min_close_ts={}&max_close_ts={}",
+            series_ticker, min_close_ts, max_close_ts
+        ))
+        .await
+    }
+
+    /// Price history of one market between `start_ts` and `end_ts` (unix
+    /// seconds), one candle per `period_minutes` (1, 60 or 1440).
+    pub async fn get_candlesticks(
+        &self,
+        series_ticker: &str,
+        ticker: &str,
+        start_ts: i64,
+        end_ts: i64,
+        period_minutes: u32,
+    ) -> Result<Vec<Candlestick>> {
+        let url = format!(
+            "{}/trade-api/v2/series/{}/markets/{}/candlesticks?start_ts={}&end_ts={}&period_interval={}",
+            self.base_url, series_ticker, ticker, start_ts, end_ts, period_minutes
+        );
+        self.throttle_market_read().await;
+
+        let resp = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("GET candlesticks failed")?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("GET candlesticks {} failed ({}): {}", ticker, status, body);
+        }
+
+        let parsed: CandlesticksResponse = resp
+            .json()
+            .await
+            .context("failed to parse candlesticks response")?;
+        Ok(parsed.candlesticks)
+    }
+
+    /// Fetch a single market, for a fresh status / close time check.
+    pub async fn get_market(&self, ticker: &str) -> Result<Market> {
+        let url = format!("{}/trade-api/v2/markets/{}", self.base_url, ticker);
+        self.throttle_market_read().await;
+
+        let resp = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("GET market failed")?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("GET market {} failed ({}): {}", ticker, status, body);
+        }
+
+        let parsed: MarketResponse = resp
+            .json()
+            .await
+            .context("failed to parse market response")?;
+        Ok(parsed.market)
+    }
+
+    async fn get_markets(&self, query: &str) -> Result<Vec<Market>> {
         let mut all_markets = Vec::new();
         let mut cursor: Option<String> = None;
 
         loop {
-            let mut url = format!(
-                "{}/trade-api/v2/markets?series_ticker={}&limit=200&status=open",
-                self.base_url, series_ticker
-            );
+            let mut url = format!("{}/trade-api/v2/markets?{}&limit=200", self.base_url, query);
             if let Some(ref c) = cursor {
                 url.push_str(&format!("&cursor={}", c));
             }
+            self.throttle_market_read().await;
 
             let resp = self
                 .client
@@ -86,6 +199,36 @@ impl KalshiRest {
         resp.json().await.context("failed to parse order response")
     }
 
+    /// Public exchange status, plus the server clock from the `Date` header
+    /// (second resolution) for measuring local clock skew.
+    pub async fn get_exchange_status(
+        &self,
+    ) -> Result<(ExchangeStatus, Option<chrono::DateTime<chrono::Utc>>)> {
+        let url = format!("{}/trade-api/v2/exchange/status", self.base_url);
+        let resp = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("exchange status request failed")?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("exchange status failed ({}): {}", status, body);
+        }
+        let server_time = resp
+            .headers()
+            .get(reqwest::header::DATE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| chrono::DateTime::parse_from_rfc2822(v).ok())
+            .map(|t| t.with_timezone(&chrono::Utc));
+        let parsed: ExchangeStatus = resp
+            .json()
+            .await
+            .context("failed to parse exchange status")?;
+        Ok((parsed, server_time))
+    }
+
     /// Get account balance.
     pub async fn get_balance(&self) -> Result<i64> {
         let path = "/trade-api/v2/portfolio/balance";
@@ -102,6 +245,14 @@ impl KalshiRest {
         Ok(resp.market_positions)
     }
 
+    /// Get fills for a single order.
+    pub async fn get_fills_for_order(&self, order_id: &str) -> Result<Vec<Fill>> {
+        let path = "/trade-api/v2/portfolio/fills";
+        let url = format!("{}{}?order_id={}", self.base_url, path, order_id);
+        let resp: FillsResponse = self.get_authed(&url, path).await?;
+        Ok(resp.fills)
+    }
+
     /// Pre-flight check: verify API key + signature auth works before starting WS.
     /// Calls the balance endpoint and checks for 401.
     pub async fn preflight_auth_check(&self) -> Result<()> {
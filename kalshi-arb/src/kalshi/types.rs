@@ -15,6 +15,46 @@ pub struct CreateOrderRequest {
     pub no_price: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub client_order_id: Option<String>,
+    /// Omitted for good-till-canceled, the exchange default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_in_force: Option<String>,
+}
+
+/// How long an order may rest on the book.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimeInForce {
+    /// Rest until filled or cancelled.
+    #[default]
+    GoodTillCanceled,
+    /// Fill what is available now and cancel the rest.
+    ImmediateOrCancel,
+    /// Fill the whole quantity now or none of it.
+    FillOrKill,
+}
+
+impl TimeInForce {
+    /// `time_in_force` value for an order request; `None` for the default.
+    pub fn api_value(self) -> Option<&'static str> {
+        match self {
+            TimeInForce::GoodTillCanceled => None,
+            TimeInForce::ImmediateOrCancel => Some("immediate_or_cancel"),
+            TimeInForce::FillOrKill => Some("fill_or_kill"),
+        }
+    }
+
+    /// Whatever does not fill on arrival is cancelled by the exchange.
+    pub fn is_immediate(self) -> bool {
+        self != TimeInForce::GoodTillCanceled
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TimeInForce::GoodTillCanceled => "GTC",
+            TimeInForce::ImmediateOrCancel => "IOC",
+            TimeInForce::FillOrKill => "FOK",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -33,8 +73,9 @@ pub struct Order {
     pub status: String,
     pub yes_price: u32,
     pub no_price: u32,
+    /// Contracts filled so far; `None` when the response leaves it out.
     #[serde(default)]
-    pub fill_count: u32,
+    pub fill_count: Option<u32>,
     #[serde(default)]
     pub remaining_count: u32,
 }
@@ -46,6 +87,11 @@ pub struct MarketsResponse {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+pub struct MarketResponse {
+    pub market: Market,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub struct Market {
     pub ticker: String,
@@ -67,6 +113,62 @@ pub struct Market {
     pub close_time: Option<String>,
     pub expected_expiration_time: Option<String>,
     pub event_start_time: Option<String>,
+    /// Settlement outcome ("yes"/"no"); empty until the market settles.
+    #[serde(default)]
+    pub result: Option<String>,
+    #[serde(default)]
+    pub last_price_dollars: Option<String>,
+    #[serde(default)]
+    pub settlement_value_dollars: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CandlesticksResponse {
+    #[serde(default)]
+    pub candlesticks: Vec<Candlestick>,
+}
+
+/// One period of a market's price history.
+#[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
+pub struct Candlestick {
+    /// Unix seconds at the end of the period.
+    pub end_period_ts: i64,
+    #[serde(default)]
+    pub yes_bid: CandlePrices,
+    #[serde(default)]
+    pub yes_ask: CandlePrices,
+    #[serde(default)]
+    pub volume: u64,
+}
+
+/// OHLC of one side of a candle. Only the close is kept; older responses
+/// carry whole cents, newer ones dollar strings.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CandlePrices {
+    #[serde(default)]
+    pub close: Option<u32>,
+    #[serde(default)]
+    pub close_dollars: Option<String>,
+}
+
+impl CandlePrices {
+    /// Closing price in cents, 0 when the side had no quote.
+    pub fn close_cents(&self) -> u32 {
+        match &self.close_dollars {
+            Some(d) if !d.is_empty() => dollars_to_cents(Some(d)),
+            _ => self.close.unwrap_or(0),
+        }
+    }
+}
+
+/// Public exchange status (`GET /exchange/status`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExchangeStatus {
+    #[serde(default)]
+    pub exchange_active: bool,
+    #[serde(default)]
+    pub trading_active: bool,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -91,6 +193,79 @@ pub struct MarketPosition {
     pub position: i64,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
+pub struct FillsResponse {
+    pub fills: Vec<Fill>,
+    pub cursor: Option<String>,
+}
+
+/// A single execution against one of our orders.
+#[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
+pub struct Fill {
+    #[serde(default)]
+    pub trade_id: String,
+    #[serde(default)]
+    pub order_id: String,
+    pub ticker: String,
+    #[serde(default)]
+    pub side: String,
+    #[serde(default)]
+    pub action: String,
+    pub count: u32,
+    #[serde(default)]
+    pub yes_price: u32,
+    #[serde(default)]
+    pub no_price: u32,
+    #[serde(default)]
+    pub is_taker: bool,
+    /// Fee charged for this fill as a dollar string ("0.0200").
+    #[serde(default)]
+    pub fee_cost: Option<String>,
+}
+
+/// WebSocket fill message from the authenticated `fill` channel: one
+/// execution against one of our orders.
+#[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
+pub struct WsFill {
+    #[serde(default)]
+    pub trade_id: String,
+    #[serde(default)]
+    pub order_id: String,
+    pub market_ticker: String,
+    #[serde(default)]
+    pub side: String,
+    #[serde(default)]
+    pub action: String,
+    pub count: u32,
+    #[serde(default)]
+    pub yes_price: u32,
+    #[serde(default)]
+    pub yes_price_dollars: Option<String>,
+    #[serde(default)]
+    pub is_taker: bool,
+    /// Unix seconds.
+    #[serde(default)]
+    pub ts: Option<i64>,
+}
+
+impl WsFill {
+    /// Fill price in cents for the contract side that traded.
+    pub fn price_cents(&self) -> u32 {
+        let yes = match self.yes_price_dollars.as_deref() {
+            Some(d) if !d.is_empty() => dollars_to_cents(Some(d)),
+            _ => self.yes_price,
+        };
+        if self.side == "no" {
+            100u32.saturating_sub(yes)
+        } else {
+            yes
+        }
+    }
+}
+
 /// WebSocket orderbook snapshot message.
 /// Kalshi deprecated cent-based `yes`/`no` fields (Jan 2026).
 /// We accept both formats: dollar strings (current) and legacy cent arrays.
@@ -103,11 +278,37 @@ pub struct OrderbookSnapshot {
     pub yes: Vec<[i64; 2]>,
     #[serde(default)]
     pub no: Vec<[i64; 2]>,
-    /// Dollar-based depth: each entry is [price_str, quantity]
-    #[serde(default)]
-    pub yes_dollars: Vec<(String, i64)>,
-    #[serde(default)]
-    pub no_dollars: Vec<(String, i64)>,
+    /// Dollar-based depth: each entry is [price_str, quantity], the price
+    /// parsed to cents on arrival. Malformed levels are dropped, not fatal.
+    #[serde(default, deserialize_with = "dollar_levels")]
+    pub yes_dollars: Vec<(DollarCents, i64)>,
+    #[serde(default, deserialize_with = "dollar_levels")]
+    pub no_dollars: Vec<(DollarCents, i64)>,
+}
+
+/// Depth levels with any unparseable entry skipped and logged, so one bad
+/// price costs that level rather than the whole snapshot.
+fn dollar_levels<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<(DollarCents, i64)>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Level {
+        Valid(DollarCents, i64),
+        Invalid(serde_json::Value),
+    }
+
+    let levels = Vec::<Level>::deserialize(deserializer)?;
+    Ok(levels
+        .into_iter()
+        .filter_map(|level| match level {
+            Level::Valid(price, qty) => Some((price, qty)),
+            Level::Invalid(raw) => {
+                tracing::warn!("skipping malformed orderbook level: {}", raw);
+                None
+            }
+        })
+        .collect())
 }
 
 /// WebSocket orderbook delta message
@@ -120,7 +321,83 @@ pub struct OrderbookDelta {
     pub delta: i64,
     pub side: String, // "yes" or "no"
     #[serde(default)]
-    pub price_dollars: Option<String>,
+    pub price_dollars: Option<DollarCents>,
+}
+
+impl OrderbookDelta {
+    /// Level price in cents, dollars preferred.
+    pub fn price_cents(&self) -> u32 {
+        self.price_dollars.map_or(self.price, |p| p.0)
+    }
+}
+
+/// A Kalshi dollar price ("0.5600") held as cents (56). Parsed once while
+/// deserializing, so applying book updates never touches strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DollarCents(pub u32);
+
+impl<'de> Deserialize<'de> for DollarCents {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl serde::de::Visitor<'_> for Visitor {
+            type Value = DollarCents;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a dollar price such as \"0.5600\"")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<DollarCents, E> {
+                parse_dollar_cents(v)
+                    .map(DollarCents)
+                    .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Str(v), &self))
+            }
+
+            fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<DollarCents, E> {
+                if (0.0..=f64::from(u32::MAX) / 100.0).contains(&v) {
+                    Ok(DollarCents((v * 100.0).round() as u32))
+                } else {
+                    Err(E::invalid_value(serde::de::Unexpected::Float(v), &self))
+                }
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<DollarCents, E> {
+                v.checked_mul(100)
+                    .and_then(|c| u32::try_from(c).ok())
+                    .map(DollarCents)
+                    .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Unsigned(v), &self))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+/// Integer parse of a dollar string to cents, rounding half a cent up like
+/// `dollars_to_cents`.
+fn parse_dollar_cents(s: &str) -> Option<u32> {
+    let (whole, frac) = s.split_once('.').unwrap_or((s, ""));
+    if whole.is_empty() && frac.is_empty() {
+        return None;
+    }
+    if !whole
+        .bytes()
+        .chain(frac.bytes())
+        .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let mut cents: u32 = 0;
+    for b in whole.bytes() {
+        cents = cents.checked_mul(10)?.checked_add(u32::from(b - b'0'))?;
+    }
+    let mut frac = frac.bytes().map(|b| u32::from(b - b'0'));
+    let tenths = frac.next().unwrap_or(0);
+    let hundredths = frac.next().unwrap_or(0);
+    let round_up = frac.next().is_some_and(|d| d >= 5);
+    cents
+        .checked_mul(100)?
+        .checked_add(tenths * 10 + hundredths + u32::from(round_up))
 }
 
 /// Wrapper for WS messages
@@ -129,6 +406,9 @@ pub struct OrderbookDelta {
 pub struct WsMessage {
     #[serde(rename = "type")]
     pub msg_type: String,
+    /// Echo of the command id, on `subscribed`/`error` replies.
+    #[serde(default)]
+    pub id: u64,
     #[serde(default)]
     pub sid: u64,
     #[serde(default)]
@@ -148,3 +428,96 @@ pub fn dollars_to_cents(dollars: Option<&str>) -> u32 {
         .map(|d| (d * 100.0).round() as u32)
         .unwrap_or(0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ws_fill_parses_and_prices_side() {
+        let fill: WsFill = serde_json::from_value(serde_json::json!({
+            "trade_id": "t1",
+            "order_id": "o1",
+            "market_ticker": "KXNBAGAME-26OCT17LALBOS-BOS",
+            "side": "no",
+            "action": "buy",
+            "count": 3,
+            "yes_price": 40,
+            "yes_price_dollars": "0.4100",
+            "is_taker": true,
+            "ts": 1760750000
+        }))
+        .unwrap();
+        assert_eq!(fill.count, 3);
+        // NO side pays the complement of the YES price, dollars preferred.
+        assert_eq!(fill.price_cents(), 59);
+    }
+
+    #[test]
+    fn test_order_fill_count_missing_is_unknown_not_zero() {
+        let order = |fill_count: Option<u32>| {
+            let mut v = serde_json::json!({
+                "order_id": "o1",
+                "ticker": "KXNBAGAME-26OCT17LALBOS-BOS",
+                "side": "yes",
+                "action": "buy",
+                "status": "canceled",
+                "yes_price": 40,
+                "no_price": 60
+            });
+            if let Some(n) = fill_count {
+                v["fill_count"] = n.into();
+            }
+            serde_json::from_value::<Order>(v).unwrap()
+        };
+        assert_eq!(order(None).fill_count, None);
+        assert_eq!(order(Some(0)).fill_count, Some(0));
+        assert_eq!(order(Some(3)).fill_count, Some(3));
+    }
+
+    #[test]
+    fn test_book_messages_parse_dollar_prices_to_cents() {
+        let snap: OrderbookSnapshot = serde_json::from_value(serde_json::json!({
+            "market_ticker": "T",
+            "yes_dollars": [["0.5500", 10], ["0.0700", 3]],
+            "no_dollars": [["1.0000", 2], ["0.455", 1]],
+        }))
+        .unwrap();
+        assert_eq!(
+            snap.yes_dollars,
+            [(DollarCents(55), 10), (DollarCents(7), 3)]
+        );
+        assert_eq!(
+            snap.no_dollars,
+            [(DollarCents(100), 2), (DollarCents(46), 1)]
+        );
+
+        let delta: OrderbookDelta = serde_json::from_str(
+            r#"{"market_ticker":"T","price":40,"delta":-5,"side":"yes","price_dollars":"0.41"}"#,
+        )
+        .unwrap();
+        assert_eq!(delta.price_cents(), 41);
+        let legacy: OrderbookDelta =
+            serde_json::from_str(r#"{"market_ticker":"T","price":40,"delta":1,"side":"no"}"#)
+                .unwrap();
+        assert_eq!(legacy.price_cents(), 40);
+
+        let snap: OrderbookSnapshot = serde_json::from_value(serde_json::json!({
+            "market_ticker": "T",
+            "yes_dollars": [["0.5500", 10], ["0.5O", 4], ["0.5400", 2]],
+            "no_dollars": [["0.4000", 1], "junk"],
+        }))
+        .unwrap();
+        assert_eq!(
+            snap.yes_dollars,
+            [(DollarCents(55), 10), (DollarCents(54), 2)]
+        );
+        assert_eq!(snap.no_dollars, [(DollarCents(40), 1)]);
+
+        for bad in ["", ".", "-0.50", "0.5O", "1e2"] {
+            assert_eq!(parse_dollar_cents(bad), None, "{bad:?}");
+        }
+        assert_eq!(parse_dollar_cents("12"), Some(1200));
+        assert_eq!(parse_dollar_cents(".5"), Some(50));
+    }
+}
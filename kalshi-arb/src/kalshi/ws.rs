@@ -1,8 +1,13 @@
 use super::auth::KalshiAuth;
-use super::types::{OrderbookDelta, OrderbookSnapshot, WsMessage};
+use super::types::{OrderbookDelta, OrderbookSnapshot, WsFill, WsMessage};
+use super::ws_queue::WsEventQueue;
+use crate::network::{self, Network, WsStream};
 use anyhow::{Context, Result};
 use futures_util::{SinkExt, StreamExt};
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 use tokio_tungstenite::tungstenite::Message;
@@ -12,75 +17,263 @@ use tokio_tungstenite::tungstenite::Message;
 pub enum KalshiWsEvent {
     Snapshot(OrderbookSnapshot),
     Delta(OrderbookDelta),
+    /// Execution against one of our orders (authenticated `fill` channel).
+    Fill(WsFill),
+    /// A delta was lost on one orderbook subscription. Its books are stale
+    /// until the fresh snapshots from the resubscribe arrive.
+    SequenceGap {
+        tickers: Vec<String>,
+        expected: u64,
+        got: u64,
+    },
     Connected,
     Disconnected(String),
 }
 
+/// First reconnect delay; doubles per consecutive failure up to the cap.
+const RECONNECT_BASE: Duration = Duration::from_secs(1);
+const RECONNECT_MAX: Duration = Duration::from_secs(60);
+/// A connection that lasted this long resets the backoff.
+const STABLE_CONNECTION: Duration = Duration::from_secs(30);
+/// Auth failures in a row before the credentials hint is surfaced.
+const AUTH_FAILURE_HINT_AFTER: u32 = 3;
+
+/// Reconnect delays with exponential backoff and jitter, so a blip on the
+/// exchange side doesn't get hammered by instant retries.
+struct Backoff {
+    failures: u32,
+}
+
+impl Backoff {
+    fn new() -> Self {
+        Self { failures: 0 }
+    }
+
+    /// Delay before the next attempt: a random point in the upper half of
+    /// `base * 2^failures`, capped at `RECONNECT_MAX`.
+    fn next_delay(&mut self, rng: &mut impl Rng) -> Duration {
+        let ceiling = RECONNECT_BASE
+            .saturating_mul(1 << self.failures.min(16))
+            .min(RECONNECT_MAX);
+        self.failures += 1;
+        ceiling.mul_f64(rng.gen_range(0.5..=1.0))
+    }
+
+    fn reset(&mut self) {
+        self.failures = 0;
+    }
+}
+
+/// Command id of the `fill` channel subscription; orderbook subscriptions
+/// number upward from the next id.
+const FILL_SUB_ID: u64 = 1;
+
+/// Orderbook subscriptions on one connection, for sequence validation.
+///
+/// Kalshi numbers messages per subscription (`sid`), not per ticker: each
+/// snapshot and delta on a sid carries the next `seq`. A skipped number means
+/// a lost delta, so every book on that sid is suspect.
+struct OrderbookSubs {
+    next_id: u64,
+    /// Subscribe command id -> tickers, until the server acks with a sid.
+    pending: HashMap<u64, Vec<String>>,
+    sids: HashMap<u64, SidState>,
+    /// Unsubscribed after a gap; their in-flight messages are dropped.
+    dropped: HashSet<u64>,
+}
+
+#[derive(Default)]
+struct SidState {
+    tickers: Vec<String>,
+    last_seq: Option<u64>,
+}
+
+#[derive(Debug, PartialEq)]
+enum SeqCheck {
+    InOrder,
+    Gap {
+        sid: u64,
+        expected: u64,
+        got: u64,
+        tickers: Vec<String>,
+    },
+    /// From a subscription we already dropped.
+    Stale,
+}
+
+impl OrderbookSubs {
+    fn new() -> Self {
+        Self {
+            next_id: FILL_SUB_ID + 1,
+            pending: HashMap::new(),
+            sids: HashMap::new(),
+            dropped: HashSet::new(),
+        }
+    }
+
+    fn next_command_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id - 1
+    }
+
+    /// Command id for a subscribe covering `tickers`.
+    fn register(&mut self, tickers: &[String]) -> u64 {
+        let id = self.next_command_id();
+        self.pending.insert(id, tickers.to_vec());
+        id
+    }
+
+    /// Take `tickers` off their subscriptions. Returns, per sid, the tickers
+    /// to delete from it on the server.
+    fn remove_tickers(&mut self, tickers: &HashSet<&str>) -> Vec<(u64, Vec<String>)> {
+        for pending in self.pending.values_mut() {
+            pending.retain(|t| !tickers.contains(t.as_str()));
+        }
+        let mut removed: Vec<(u64, Vec<String>)> = self
+            .sids
+            .iter_mut()
+            .filter_map(|(&sid, state)| {
+                let (gone, kept) = state
+                    .tickers
+                    .drain(..)
+                    .partition(|t| tickers.contains(t.as_str()));
+                state.tickers = kept;
+                let gone: Vec<String> = gone;
+                (!gone.is_empty()).then_some((sid, gone))
+            })
+            .collect();
+        removed.sort();
+        removed
+    }
+
+    /// The server acked command `id` as subscription `sid`.
+    fn on_subscribed(&mut self, id: u64, sid: u64) {
+        if let Some(tickers) = self.pending.remove(&id) {
+            self.sids.entry(sid).or_default().tickers.extend(tickers);
+        }
+    }
+
+    /// Check `seq` of an orderbook message for `ticker` on `sid`. A gap drops
+    /// the subscription and returns the tickers that need fresh snapshots.
+    fn observe(&mut self, sid: u64, seq: u64, ticker: &str) -> SeqCheck {
+        if self.dropped.contains(&sid) {
+            return SeqCheck::Stale;
+        }
+        let state = self.sids.entry(sid).or_default();
+        if !state.tickers.iter().any(|t| t == ticker) {
+            state.tickers.push(ticker.to_string());
+        }
+        if seq == 0 {
+            return SeqCheck::InOrder;
+        }
+        match state.last_seq {
+            Some(last) if seq != last + 1 => {
+                let state = self.sids.remove(&sid).unwrap_or_default();
+                self.dropped.insert(sid);
+                SeqCheck::Gap {
+                    sid,
+                    expected: last + 1,
+                    got: seq,
+                    tickers: state.tickers,
+                }
+            }
+            _ => {
+                state.last_seq = Some(seq);
+                SeqCheck::InOrder
+            }
+        }
+    }
+}
+
+/// Engine -> WS changes to the orderbook subscription list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WsCommand {
+    /// Start streaming books for these tickers; already-subscribed ones are ignored.
+    Subscribe(Vec<String>),
+    /// Stop streaming these tickers, e.g. after their games settle.
+    Unsubscribe(Vec<String>),
+}
+
 pub struct KalshiWs {
     auth: Arc<KalshiAuth>,
     ws_url: String,
+    network: Network,
 }
 
 impl KalshiWs {
-    pub fn new(auth: Arc<KalshiAuth>, ws_url: &str) -> Self {
+    pub fn new(auth: Arc<KalshiAuth>, ws_url: &str, network: Network) -> Self {
         Self {
             auth,
             ws_url: ws_url.to_string(),
+            network,
         }
     }
 
-    /// Connect and run the WebSocket loop. Sends events on `tx`.
-    /// `tickers` are subscribed immediately after connect.
-    pub async fn run(&self, tickers: Vec<String>, tx: mpsc::Sender<KalshiWsEvent>) -> Result<()> {
+    /// Connect and run the WebSocket loop forever. Queues events on `events`.
+    /// Subscription changes arriving on `commands` are applied to the live
+    /// connection, and the resulting ticker list is re-subscribed after every
+    /// reconnect, so markets can be added while other series are still being
+    /// indexed and dropped once they settle. Every dropped connection emits
+    /// `Disconnected` and is retried with backoff; `Connected` follows once a
+    /// new one is up.
+    pub async fn run(
+        &self,
+        mut commands: mpsc::UnboundedReceiver<WsCommand>,
+        events: Arc<WsEventQueue>,
+    ) {
+        let mut tickers: Vec<String> = Vec::new();
+        let mut backoff = Backoff::new();
         let mut consecutive_auth_failures = 0u32;
         loop {
-            match self.connect_and_listen(&tickers, &tx).await {
+            let started = Instant::now();
+            let reason = match self
+                .connect_and_listen(&mut tickers, &mut commands, &events)
+                .await
+            {
                 Ok(()) => {
                     consecutive_auth_failures = 0;
-                    tracing::warn!("kalshi WS closed cleanly, reconnecting...");
+                    "connection closed by server".to_string()
                 }
                 Err(e) => {
                     let err_str = format!("{:#}", e);
-                    let is_auth = err_str.contains("401") || err_str.contains("Unauthorized");
-                    if is_auth {
+                    if err_str.contains("401") || err_str.contains("Unauthorized") {
                         consecutive_auth_failures += 1;
-                        tracing::error!(
-                            "kalshi WS auth failure #{}: {:#}",
-                            consecutive_auth_failures,
-                            e
-                        );
-                        if consecutive_auth_failures >= 3 {
-                            tracing::error!(
-                                "3 consecutive WS auth failures — stopping reconnects. \
-                                 Check API key/private key pair and system clock."
-                            );
-                            let _ = tx
-                                .send(KalshiWsEvent::Disconnected(
-                                    "Authentication failed repeatedly (401). Check credentials."
-                                        .to_string(),
-                                ))
-                                .await;
-                            return Err(e);
-                        }
                     } else {
                         consecutive_auth_failures = 0;
-                        tracing::error!("kalshi WS error: {:#}, reconnecting in 2s...", e);
                     }
-                    let _ = tx
-                        .send(KalshiWsEvent::Disconnected(format!("{:#}", e)))
-                        .await;
+                    if consecutive_auth_failures >= AUTH_FAILURE_HINT_AFTER {
+                        format!(
+                            "Authentication failed {} times in a row (401). Check API key/private \
+                             key pair and system clock.",
+                            consecutive_auth_failures
+                        )
+                    } else {
+                        err_str
+                    }
                 }
+            };
+            if started.elapsed() >= STABLE_CONNECTION {
+                backoff.reset();
             }
-            let delay = if consecutive_auth_failures > 0 { 5 } else { 2 };
-            tokio::time::sleep(std::time::Duration::from_secs(delay)).await;
+            let delay = backoff.next_delay(&mut rand::thread_rng());
+            tracing::warn!(
+                "kalshi WS down: {}, reconnecting in {:.1}s",
+                reason,
+                delay.as_secs_f64()
+            );
+            events
+                .push(KalshiWsEvent::Disconnected(format!(
+                    "{}; retrying in {:.0}s",
+                    reason,
+                    delay.as_secs_f64().ceil()
+                )))
+                .await;
+            tokio::time::sleep(delay).await;
         }
     }
 
-    async fn connect_and_listen(
-        &self,
-        tickers: &[String],
-        tx: &mpsc::Sender<KalshiWsEvent>,
-    ) -> Result<()> {
+    /// Open an authenticated connection to the Kalshi WebSocket.
+    async fn connect(&self) -> Result<WsStream> {
         let path = "/trade-api/ws/v2";
         let auth_headers = self.auth.headers("GET", path)?;
 
@@ -100,8 +293,8 @@ impl KalshiWs {
             );
         }
 
-        let (ws_stream, _) = match tokio_tungstenite::connect_async(request).await {
-            Ok(pair) => pair,
+        let ws_stream = match self.network.connect_ws(network::KALSHI, request).await {
+            Ok(ws) => ws,
             Err(e) => {
                 let err_str = format!("{:#}", e);
                 if err_str.contains("401") || err_str.contains("Unauthorized") {
@@ -118,66 +311,202 @@ impl KalshiWs {
                         KalshiAuth::timestamp_ms()
                     );
                 }
-                return Err(anyhow::anyhow!(e).context("WS connection failed"));
+                return Err(e.context("WS connection failed"));
             }
         };
 
+        Ok(ws_stream)
+    }
+
+    async fn connect_and_listen(
+        &self,
+        tickers: &mut Vec<String>,
+        commands: &mut mpsc::UnboundedReceiver<WsCommand>,
+        events: &WsEventQueue,
+    ) -> Result<()> {
+        let ws_stream = self.connect().await?;
         let (mut write, mut read) = ws_stream.split();
         tracing::debug!("kalshi WS connected");
-        let _ = tx.send(KalshiWsEvent::Connected).await;
+        events.push(KalshiWsEvent::Connected).await;
+
+        let mut subs = OrderbookSubs::new();
+        subscribe_orderbook(&mut write, &mut subs, tickers).await?;
 
-        // Subscribe to orderbook_delta for all tickers (batch in groups of 50)
-        for chunk in tickers.chunks(50) {
+        // Our own fills across all markets, so positions follow real executions
+        let sub = serde_json::json!({
+            "id": FILL_SUB_ID,
+            "cmd": "subscribe",
+            "params": {
+                "channels": ["fill"],
+            }
+        });
+        write
+            .send(Message::Text(sub.to_string()))
+            .await
+            .context("WS fill subscribe failed")?;
+
+        // Read loop, applying subscription changes as they arrive
+        let mut commands_open = true;
+        loop {
+            tokio::select! {
+                msg = read.next() => {
+                    let Some(msg) = msg else {
+                        break;
+                    };
+                    match msg.context("WS read error")? {
+                        Message::Text(text) => {
+                            match self.handle_message(&text, events, &mut subs).await {
+                                Ok(Some((sid, resync))) => {
+                                    let unsub = serde_json::json!({
+                                        "id": subs.next_command_id(),
+                                        "cmd": "unsubscribe",
+                                        "params": { "sids": [sid] }
+                                    });
+                                    write
+                                        .send(Message::Text(unsub.to_string()))
+                                        .await
+                                        .context("WS unsubscribe failed")?;
+                                    subscribe_orderbook(&mut write, &mut subs, &resync).await?;
+                                }
+                                Ok(None) => {}
+                                Err(e) => tracing::warn!("WS message parse error: {:#}", e),
+                            }
+                        }
+                        Message::Ping(data) => {
+                            write.send(Message::Pong(data)).await?;
+                        }
+                        Message::Close(_) => {
+                            tracing::debug!("kalshi WS received close frame");
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                command = commands.recv(), if commands_open => {
+                    match command {
+                        Some(WsCommand::Subscribe(new_tickers)) => {
+                            let mut new_tickers: Vec<String> = new_tickers
+                                .into_iter()
+                                .filter(|t| !tickers.contains(t))
+                                .collect();
+                            new_tickers.dedup();
+                            subscribe_orderbook(&mut write, &mut subs, &new_tickers).await?;
+                            tickers.extend(new_tickers);
+                        }
+                        Some(WsCommand::Unsubscribe(gone)) => {
+                            let gone: HashSet<&str> = gone.iter().map(String::as_str).collect();
+                            tickers.retain(|t| !gone.contains(t.as_str()));
+                            for (sid, sid_tickers) in subs.remove_tickers(&gone) {
+                                let update = serde_json::json!({
+                                    "id": subs.next_command_id(),
+                                    "cmd": "update_subscription",
+                                    "params": {
+                                        "sids": [sid],
+                                        "market_tickers": sid_tickers,
+                                        "action": "delete_markets",
+                                    }
+                                });
+                                write
+                                    .send(Message::Text(update.to_string()))
+                                    .await
+                                    .context("WS unsubscribe failed")?;
+                            }
+                            tracing::debug!(count = gone.len(), "unsubscribed tickers");
+                        }
+                        None => commands_open = false,
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Connect, subscribe to the `fill` channel and wait for the subscription
+    /// to be acknowledged. Used by `doctor`; needs no market tickers.
+    pub async fn probe(&self, timeout: std::time::Duration) -> Result<()> {
+        let probe = async {
+            let ws_stream = self.connect().await?;
+            let (mut write, mut read) = ws_stream.split();
             let sub = serde_json::json!({
-                "id": 1,
+                "id": FILL_SUB_ID,
                 "cmd": "subscribe",
                 "params": {
-                    "channels": ["orderbook_delta"],
-                    "market_tickers": chunk,
+                    "channels": ["fill"],
                 }
             });
             write
                 .send(Message::Text(sub.to_string()))
                 .await
                 .context("WS subscribe failed")?;
-        }
-
-        tracing::debug!(count = tickers.len(), "subscribed to tickers");
-
-        // Read loop
-        while let Some(msg) = read.next().await {
-            let msg = msg.context("WS read error")?;
-            match msg {
-                Message::Text(text) => {
-                    if let Err(e) = self.handle_message(&text, tx).await {
-                        tracing::warn!("WS message parse error: {:#}", e);
-                    }
-                }
-                Message::Ping(data) => {
-                    write.send(Message::Pong(data)).await?;
+            while let Some(msg) = read.next().await {
+                let Message::Text(text) = msg.context("WS read error")? else {
+                    continue;
+                };
+                let ws_msg: WsMessage =
+                    serde_json::from_str(&text).context("failed to parse WS message")?;
+                match ws_msg.msg_type.as_str() {
+                    "subscribed" => return Ok(()),
+                    "error" => anyhow::bail!("subscribe rejected: {}", ws_msg.msg),
+                    _ => {}
                 }
-                Message::Close(_) => {
-                    tracing::debug!("kalshi WS received close frame");
-                    break;
-                }
-                _ => {}
             }
-        }
-
-        Ok(())
+            anyhow::bail!("connection closed before the subscription was acknowledged")
+        };
+        tokio::time::timeout(timeout, probe)
+            .await
+            .context("timed out waiting for the subscription ack")?
     }
 
-    async fn handle_message(&self, text: &str, tx: &mpsc::Sender<KalshiWsEvent>) -> Result<()> {
+    /// Forward one message as an event. Returns the sid to drop and the
+    /// tickers to resubscribe when it reveals a sequence gap.
+    async fn handle_message(
+        &self,
+        text: &str,
+        events: &WsEventQueue,
+        subs: &mut OrderbookSubs,
+    ) -> Result<Option<(u64, Vec<String>)>> {
         let ws_msg: WsMessage = serde_json::from_str(text).context("failed to parse WS message")?;
 
         match ws_msg.msg_type.as_str() {
+            "subscribed" => {
+                if let Some(sid) = ws_msg.msg.get("sid").and_then(|v| v.as_u64()) {
+                    subs.on_subscribed(ws_msg.id, sid);
+                }
+            }
             "orderbook_snapshot" => {
                 let snapshot: OrderbookSnapshot = serde_json::from_value(ws_msg.msg)?;
-                let _ = tx.send(KalshiWsEvent::Snapshot(snapshot)).await;
+                match subs.observe(ws_msg.sid, ws_msg.seq, &snapshot.market_ticker) {
+                    SeqCheck::InOrder => {
+                        events.push(KalshiWsEvent::Snapshot(snapshot)).await;
+                    }
+                    SeqCheck::Gap {
+                        sid,
+                        expected,
+                        got,
+                        tickers,
+                    } => return Ok(Some(report_gap(events, sid, expected, got, tickers).await)),
+                    SeqCheck::Stale => {}
+                }
             }
             "orderbook_delta" => {
                 let delta: OrderbookDelta = serde_json::from_value(ws_msg.msg)?;
-                let _ = tx.send(KalshiWsEvent::Delta(delta)).await;
+                match subs.observe(ws_msg.sid, ws_msg.seq, &delta.market_ticker) {
+                    SeqCheck::InOrder => {
+                        events.push(KalshiWsEvent::Delta(delta)).await;
+                    }
+                    SeqCheck::Gap {
+                        sid,
+                        expected,
+                        got,
+                        tickers,
+                    } => return Ok(Some(report_gap(events, sid, expected, got, tickers).await)),
+                    SeqCheck::Stale => {}
+                }
+            }
+            "fill" => {
+                let fill: WsFill = serde_json::from_value(ws_msg.msg)?;
+                events.push(KalshiWsEvent::Fill(fill)).await;
             }
             "error" => {
                 tracing::warn!("kalshi WS error: {:?}", ws_msg.msg);
@@ -186,6 +515,136 @@ impl KalshiWs {
                 tracing::trace!(msg_type = ws_msg.msg_type, "unhandled WS message type");
             }
         }
-        Ok(())
+        Ok(None)
+    }
+}
+
+/// Log a sequence gap and pass it on, returning what to resubscribe.
+async fn report_gap(
+    events: &WsEventQueue,
+    sid: u64,
+    expected: u64,
+    got: u64,
+    tickers: Vec<String>,
+) -> (u64, Vec<String>) {
+    tracing::warn!(
+        sid,
+        expected,
+        got,
+        markets = tickers.len(),
+        "kalshi WS sequence gap, resubscribing"
+    );
+    events
+        .push(KalshiWsEvent::SequenceGap {
+            tickers: tickers.clone(),
+            expected,
+            got,
+        })
+        .await;
+    (sid, tickers)
+}
+
+/// Subscribe to orderbook_delta for `tickers` (batched in groups of 50).
+async fn subscribe_orderbook<S>(
+    write: &mut S,
+    subs: &mut OrderbookSubs,
+    tickers: &[String],
+) -> Result<()>
+where
+    S: SinkExt<Message> + Unpin,
+    S::Error: std::error::Error + Send + Sync + 'static,
+{
+    for chunk in tickers.chunks(50) {
+        let sub = serde_json::json!({
+            "id": subs.register(chunk),
+            "cmd": "subscribe",
+            "params": {
+                "channels": ["orderbook_delta"],
+                "market_tickers": chunk,
+            }
+        });
+        write
+            .send(Message::Text(sub.to_string()))
+            .await
+            .context("WS subscribe failed")?;
+    }
+    if !tickers.is_empty() {
+        tracing::debug!(count = tickers.len(), "subscribed to tickers");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_backoff_doubles_with_jitter_up_to_cap() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut backoff = Backoff::new();
+        for ceiling in [1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 60.0, 60.0] {
+            let delay = backoff.next_delay(&mut rng).as_secs_f64();
+            assert!(
+                (ceiling / 2.0..=ceiling).contains(&delay),
+                "{delay} not in upper half of {ceiling}"
+            );
+        }
+        backoff.reset();
+        assert!(backoff.next_delay(&mut rng) <= RECONNECT_BASE);
+    }
+
+    #[test]
+    fn test_remove_tickers_groups_by_subscription() {
+        let mut subs = OrderbookSubs::new();
+        let first = subs.register(&["T-A".to_string(), "T-B".to_string()]);
+        let second = subs.register(&["T-C".to_string()]);
+        let unacked = subs.register(&["T-D".to_string(), "T-E".to_string()]);
+        subs.on_subscribed(first, 1);
+        subs.on_subscribed(second, 2);
+
+        let gone: HashSet<&str> = ["T-B", "T-C", "T-D"].into_iter().collect();
+        assert_eq!(
+            subs.remove_tickers(&gone),
+            vec![(1, vec!["T-B".to_string()]), (2, vec!["T-C".to_string()])]
+        );
+        assert_eq!(subs.sids[&1].tickers, ["T-A"]);
+        assert!(subs.sids[&2].tickers.is_empty());
+        // Not acked yet: the sid it gets won't carry the removed ticker
+        subs.on_subscribed(unacked, 3);
+        assert_eq!(subs.sids[&3].tickers, ["T-E"]);
+    }
+
+    #[test]
+    fn test_sequence_gap_drops_subscription() {
+        let mut subs = OrderbookSubs::new();
+        let tickers = vec!["T-A".to_string(), "T-B".to_string()];
+        let id = subs.register(&tickers);
+        assert!(id > FILL_SUB_ID);
+        subs.on_subscribed(id, 7);
+
+        assert_eq!(subs.observe(7, 1, "T-A"), SeqCheck::InOrder);
+        assert_eq!(subs.observe(7, 2, "T-B"), SeqCheck::InOrder);
+        // Unsequenced messages never count as gaps
+        assert_eq!(subs.observe(7, 0, "T-A"), SeqCheck::InOrder);
+        assert_eq!(
+            subs.observe(7, 4, "T-A"),
+            SeqCheck::Gap {
+                sid: 7,
+                expected: 3,
+                got: 4,
+                tickers,
+            }
+        );
+        // In-flight messages from the dropped sid are ignored
+        assert_eq!(subs.observe(7, 5, "T-B"), SeqCheck::Stale);
+
+        // Other subscriptions are unaffected; unacked sids learn their tickers
+        assert_eq!(subs.observe(8, 10, "T-C"), SeqCheck::InOrder);
+        assert!(matches!(
+            subs.observe(8, 12, "T-C"),
+            SeqCheck::Gap { tickers, .. } if tickers == ["T-C"]
+        ));
     }
 }
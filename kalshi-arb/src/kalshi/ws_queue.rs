@@ -0,0 +1,320 @@
+//! Bounded queue between the Kalshi WS reader and the event consumer.
+//!
+//! A plain channel blocks the socket reader as soon as it fills, which is
+//! exactly when bursts (game endings, big score swings) arrive. Once the
+//! consumer lags, this queue shrinks the backlog instead:
+//! - a delta for a price level that already has a queued delta is merged
+//!   into it, since book deltas add up;
+//! - a snapshot drops the queued deltas and snapshots for its ticker,
+//!   which it replaces;
+//! - a connection status event drops older queued status events.
+//!
+//! Fills and sequence gaps are never dropped. Only when the queue is full
+//! of events it can neither merge nor drop does the reader wait for room.
+
+use super::types::OrderbookDelta;
+use super::ws::KalshiWsEvent;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use tokio::sync::Notify;
+
+/// Same size as the channel this replaced.
+pub const CAPACITY: usize = 512;
+/// Queue depth at which the consumer counts as lagging.
+const LAGGING_AT: usize = CAPACITY / 4;
+
+/// Queue health, shown in the diagnostic view.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct QueueStats {
+    pub depth: usize,
+    pub peak_depth: usize,
+    /// Deltas merged into a queued delta for the same price level.
+    pub coalesced: u64,
+    /// Queued events dropped because a newer event replaced them.
+    pub dropped: u64,
+    /// Times the reader had to wait for room.
+    pub stalls: u64,
+}
+
+/// Ticker, side and price in cents of a delta's level.
+type LevelKey = (String, String, u32);
+
+fn level_key(delta: &OrderbookDelta) -> LevelKey {
+    (
+        delta.market_ticker.clone(),
+        delta.side.clone(),
+        delta.price_cents(),
+    )
+}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    /// Queued events; `None` marks one dropped in place.
+    events: VecDeque<Option<KalshiWsEvent>>,
+    /// Sequence number of `events[0]`.
+    head: u64,
+    /// Events still queued, not counting dropped ones.
+    len: usize,
+    /// Queued deltas open for merging, by level. Only filled while lagging.
+    levels: HashMap<LevelKey, u64>,
+    stats: QueueStats,
+}
+
+impl QueueState {
+    fn slot(&mut self, seq: u64) -> Option<&mut Option<KalshiWsEvent>> {
+        let index = seq.checked_sub(self.head)? as usize;
+        self.events.get_mut(index)
+    }
+
+    /// Drop queued events matching `stale`.
+    fn drop_where(&mut self, stale: impl Fn(&KalshiWsEvent) -> bool) {
+        let mut dropped = 0;
+        for slot in self.events.iter_mut() {
+            if slot.as_ref().is_some_and(&stale) {
+                *slot = None;
+                dropped += 1;
+            }
+        }
+        self.len -= dropped;
+        self.stats.dropped += dropped as u64;
+    }
+
+    /// Fold `event` into the queue without adding to it, if it can be.
+    /// Returns the event back otherwise.
+    fn absorb(&mut self, event: KalshiWsEvent) -> Option<KalshiWsEvent> {
+        let lagging = self.len >= LAGGING_AT;
+        match &event {
+            KalshiWsEvent::Delta(delta) if lagging => {
+                let key = level_key(delta);
+                if let Some(seq) = self.levels.get(&key).copied() {
+                    if let Some(Some(KalshiWsEvent::Delta(queued))) = self.slot(seq) {
+                        queued.delta += delta.delta;
+                        self.stats.coalesced += 1;
+                        return None;
+                    }
+                }
+                self.levels
+                    .insert(key, self.head + self.events.len() as u64);
+            }
+            KalshiWsEvent::Snapshot(snapshot) => {
+                let ticker = snapshot.market_ticker.as_str();
+                // Deltas after a snapshot must not merge into ones before it
+                self.levels.retain(|(t, ..), _| t != ticker);
+                if lagging {
+                    self.drop_where(|queued| match queued {
+                        KalshiWsEvent::Snapshot(s) => s.market_ticker == ticker,
+                        KalshiWsEvent::Delta(d) => d.market_ticker == ticker,
+                        _ => false,
+                    });
+                }
+            }
+            KalshiWsEvent::Connected | KalshiWsEvent::Disconnected(_) if lagging => {
+                self.drop_where(|queued| {
+                    matches!(
+                        queued,
+                        KalshiWsEvent::Connected | KalshiWsEvent::Disconnected(_)
+                    )
+                });
+            }
+            _ => {}
+        }
+        Some(event)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct WsEventQueue {
+    state: Mutex<QueueState>,
+    /// Signalled when an event is queued.
+    ready: Notify,
+    /// Signalled when an event is taken.
+    room: Notify,
+}
+
+impl WsEventQueue {
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Queue `event`, merging or dropping as described above, and wait for
+    /// room only if the queue is still full.
+    pub async fn push(&self, mut event: KalshiWsEvent) {
+        let mut stalled = false;
+        loop {
+            {
+                let mut state = self.lock();
+                match state.absorb(event) {
+                    None => return,
+                    Some(rest) if state.len < CAPACITY => {
+                        state.events.push_back(Some(rest));
+                        state.len += 1;
+                        state.stats.peak_depth = state.stats.peak_depth.max(state.len);
+                        drop(state);
+                        self.ready.notify_one();
+                        return;
+                    }
+                    Some(rest) => {
+                        if !stalled {
+                            stalled = true;
+                            state.stats.stalls += 1;
+                            tracing::warn!(
+                                depth = state.len,
+                                "kalshi WS queue full, reader waiting"
+                            );
+                        }
+                        event = rest;
+                    }
+                }
+            }
+            self.room.notified().await;
+        }
+    }
+
+    /// Take the oldest queued event, waiting for one if the queue is empty.
+    pub async fn recv(&self) -> KalshiWsEvent {
+        loop {
+            {
+                let mut state = self.lock();
+                while let Some(slot) = state.events.pop_front() {
+                    state.head += 1;
+                    let Some(event) = slot else {
+                        continue;
+                    };
+                    state.len -= 1;
+                    // Entries for taken deltas just stop matching; forget
+                    // them all once the lag is over
+                    if state.len < LAGGING_AT && !state.levels.is_empty() {
+                        state.levels.clear();
+                    }
+                    drop(state);
+                    self.room.notify_one();
+                    return event;
+                }
+            }
+            self.ready.notified().await;
+        }
+    }
+
+    pub fn stats(&self) -> QueueStats {
+        let state = self.lock();
+        QueueStats {
+            depth: state.len,
+            ..state.stats
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kalshi::types::OrderbookSnapshot;
+
+    fn delta(ticker: &str, price: u32, qty: i64) -> KalshiWsEvent {
+        KalshiWsEvent::Delta(OrderbookDelta {
+            market_ticker: ticker.to_string(),
+            price,
+            delta: qty,
+            side: "yes".to_string(),
+            price_dollars: None,
+        })
+    }
+
+    fn snapshot(ticker: &str) -> KalshiWsEvent {
+        KalshiWsEvent::Snapshot(OrderbookSnapshot {
+            market_ticker: ticker.to_string(),
+            yes: Vec::new(),
+            no: Vec::new(),
+            yes_dollars: Vec::new(),
+            no_dollars: Vec::new(),
+        })
+    }
+
+    /// Fill the queue to the lagging depth with deltas for other tickers.
+    async fn lag(queue: &WsEventQueue) {
+        for i in 0..LAGGING_AT {
+            queue.push(delta(&format!("FILL-{}", i), 50, 1)).await;
+        }
+    }
+
+    async fn drain(queue: &WsEventQueue) -> Vec<KalshiWsEvent> {
+        let mut events = Vec::new();
+        while queue.stats().depth > 0 {
+            events.push(queue.recv().await);
+        }
+        events
+    }
+
+    fn qty(event: &KalshiWsEvent) -> Option<(&str, u32, i64)> {
+        match event {
+            KalshiWsEvent::Delta(d) => Some((d.market_ticker.as_str(), d.price, d.delta)),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deltas_coalesce_only_while_lagging() {
+        let queue = WsEventQueue::default();
+        queue.push(delta("T", 40, 5)).await;
+        queue.push(delta("T", 40, -2)).await;
+        assert_eq!(queue.stats().depth, 2);
+        drain(&queue).await;
+
+        lag(&queue).await;
+        queue.push(delta("T", 40, 5)).await;
+        queue.push(delta("T", 41, 1)).await;
+        queue.push(delta("T", 40, -2)).await;
+        // A snapshot closes the earlier deltas to merging
+        queue.push(snapshot("T")).await;
+        queue.push(delta("T", 40, 7)).await;
+
+        let stats = queue.stats();
+        assert_eq!(stats.coalesced, 1);
+        assert_eq!(stats.dropped, 2);
+        let events = drain(&queue).await;
+        let tail: Vec<_> = events[LAGGING_AT..].iter().map(qty).collect();
+        assert_eq!(tail, vec![None, Some(("T", 40, 7))]);
+        assert!(
+            matches!(&events[LAGGING_AT], KalshiWsEvent::Snapshot(s) if s.market_ticker == "T")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_merged_delta_keeps_queue_order() {
+        let queue = WsEventQueue::default();
+        lag(&queue).await;
+        queue.push(delta("T", 40, 5)).await;
+        queue.push(delta("U", 40, 1)).await;
+        queue.push(delta("T", 40, 3)).await;
+        queue
+            .push(KalshiWsEvent::Disconnected("reset".to_string()))
+            .await;
+        queue.push(KalshiWsEvent::Connected).await;
+
+        let stats = queue.stats();
+        assert_eq!((stats.coalesced, stats.dropped), (1, 1));
+        let events = drain(&queue).await;
+        let tail: Vec<_> = events[LAGGING_AT..].iter().map(qty).collect();
+        assert_eq!(tail, vec![Some(("T", 40, 8)), Some(("U", 40, 1)), None]);
+        assert!(matches!(events.last(), Some(KalshiWsEvent::Connected)));
+    }
+
+    #[tokio::test]
+    async fn test_full_queue_waits_for_room() {
+        let queue = std::sync::Arc::new(WsEventQueue::default());
+        for i in 0..CAPACITY {
+            queue.push(delta(&format!("T-{}", i), 50, 1)).await;
+        }
+        let pusher = {
+            let queue = queue.clone();
+            tokio::spawn(async move { queue.push(delta("LAST", 50, 1)).await })
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        assert!(!pusher.is_finished());
+        assert_eq!(queue.stats().stalls, 1);
+
+        assert_eq!(qty(&queue.recv().await), Some(("T-0", 50, 1)));
+        pusher.await.unwrap();
+        let stats = queue.stats();
+        assert_eq!((stats.depth, stats.peak_depth), (CAPACITY, CAPACITY));
+    }
+}
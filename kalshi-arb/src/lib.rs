@@ -6,5 +6,8 @@ pub mod engine;
 pub mod execution;
 pub mod feed;
 pub mod kalshi;
+pub mod locale;
+pub mod network;
+pub mod polymarket;
 // Note: pipeline and tui modules excluded — they have cross-references to types
 // that will be refactored. Re-add once main.rs is cleaned up.
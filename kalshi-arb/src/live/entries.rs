@@ -0,0 +1,699 @@
+//! Live entries: signalled entries through the submit gates to the exchange,
+//! and resting entries kept pegged to the touch until they fill, lose their
+//! edge or time out.
+
+use super::LiveSession;
+use crate::config::RiskConfig;
+use crate::engine::edge_persistence::EdgePersistence;
+use crate::engine::fees::calculate_fee;
+use crate::engine::matcher::{self, MarketIndex};
+use crate::engine::strategy::SkipReason;
+use crate::engine::{ExpectedFee, OrderSide};
+use crate::execution::{OrderExecutor, PegAction, RestingEntry};
+use crate::journal::{Journal, JournalEntry};
+use crate::kalshi::{rest::KalshiRest, types::TimeInForce};
+use crate::pipeline::{self, OrderIntent};
+use crate::tui::state::{AppState, TradeRow};
+use crate::LiveBook;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::{watch, RwLock};
+
+impl LiveSession {
+    /// Maintain resting entries: re-peg pegged ones to the touch, and cancel
+    /// when the edge is gone, the re-peg budget is spent, or the entry times
+    /// out.
+    pub async fn maintain_resting_entries(
+        &mut self,
+        exec: &OrderExecutor,
+        rest: &KalshiRest,
+        live_book: &LiveBook,
+        state_tx: &watch::Sender<AppState>,
+    ) {
+        let now = Instant::now();
+        let entry_timeout = Duration::from_secs(self.execution.order_timeout_secs);
+        let min_interval = Duration::from_millis(self.execution.min_repeg_interval_ms);
+        let tickers: Vec<String> = self.resting_entries.keys().cloned().collect();
+        for ticker in tickers {
+            let Some(entry) = self.resting_entries.get(&ticker).cloned() else {
+                continue;
+            };
+            // The touch without our own order, so it can fall as well as rise
+            let (best_bid, best_ask) = live_book
+                .read(&ticker, |d| {
+                    let bid = d.best_bid_excluding(&entry.side, entry.price, entry.remaining());
+                    let ask = d.best_ask(&entry.side).map_or(0, |q| q.price);
+                    (bid, ask)
+                })
+                .unwrap_or((0, 0));
+
+            let action = if now.duration_since(entry.placed_at) > entry_timeout {
+                PegAction::Cancel {
+                    reason: "entry timed out",
+                }
+            } else {
+                entry.evaluate(
+                    best_bid,
+                    best_ask,
+                    now,
+                    min_interval,
+                    self.execution.max_repegs,
+                )
+            };
+
+            let target = match action {
+                PegAction::Hold => continue,
+                PegAction::Repeg { price } => Ok(price),
+                PegAction::Cancel { reason } => {
+                    tracing::warn!(ticker = %ticker, order_id = %entry.order_id, reason, "cancelling resting entry");
+                    Err(reason.to_string())
+                }
+            };
+
+            // Cancel first; a failed cancel means the order already filled
+            // (or is gone), so stop tracking it and book what filled
+            if let Err(e) = exec.cancel_order(&entry.order_id).await {
+                tracing::warn!(ticker = %ticker, error = %e, "resting entry cancel failed, assuming filled");
+                self.resting_entries.remove(&ticker);
+                self.close_resting_entry(entry, rest).await;
+                continue;
+            }
+
+            let replaced = match target {
+                Ok(price) => exec
+                    .submit_order(
+                        &ticker,
+                        entry.remaining(),
+                        price,
+                        true,
+                        false,
+                        &entry.side,
+                        TimeInForce::GoodTillCanceled,
+                    )
+                    .await
+                    .map(|placed| {
+                        let id = placed.map(|p| p.order_id);
+                        (id.unwrap_or_else(|| entry.order_id.clone()), price)
+                    })
+                    .map_err(|e| format!("re-peg submit failed: {}", e)),
+                Err(reason) => Err(reason),
+            };
+
+            match replaced {
+                Ok((new_id, price)) => {
+                    if let Some(mut exp) = self.expected_fees.remove(&entry.order_id) {
+                        exp.price = price;
+                        exp.quantity = entry.remaining();
+                        self.expected_fees.insert(new_id.clone(), exp);
+                    }
+                    if let Some(e) = self.resting_entries.get_mut(&ticker) {
+                        e.record_repeg(new_id, price, now);
+                    }
+                    state_tx.send_modify(|s| {
+                        s.push_log(
+                            "ORDER",
+                            format!("RE-PEG {} {}c -> {}c", entry.ticker, entry.price, price),
+                        );
+                    });
+                }
+                Err(e) => {
+                    // Order is no longer resting: keep what filled and
+                    // release the risk held for the remainder
+                    self.resting_entries.remove(&ticker);
+                    self.expected_fees.remove(&entry.order_id);
+                    let entry = self.close_resting_entry(entry, rest).await;
+                    state_tx.send_modify(|s| {
+                        let msg = if entry.filled > 0 {
+                            format!(
+                                "ENTRY CANCELLED {}: {} (kept {}/{} filled)",
+                                ticker, e, entry.filled, entry.quantity
+                            )
+                        } else {
+                            s.kelly_tuner.forget(&ticker);
+                            format!("ENTRY CANCELLED {}: {}", ticker, e)
+                        };
+                        s.push_log("ORDER", msg);
+                    });
+                }
+            }
+        }
+    }
+
+    /// Submit signalled entries that pass the live gates: one position per
+    /// market on one venue, no entry already resting or pending, the risk
+    /// limits, the available balance and the market still open. Each is
+    /// routed to the cheaper venue for the same outcome first.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn submit_entries(
+        &mut self,
+        intents: &[OrderIntent],
+        exec: &OrderExecutor,
+        rest: &KalshiRest,
+        market_index: &RwLock<MarketIndex>,
+        live_book: &LiveBook,
+        risk_config: &RiskConfig,
+        min_edge_after_fees: u8,
+        available_balance_cents: &mut u64,
+        edge_persistence: &Mutex<EdgePersistence>,
+        cycle_start: Instant,
+        journal: &Journal,
+        state_tx: &watch::Sender<AppState>,
+    ) {
+        for intent in intents {
+            // Take the cheaper venue for the same outcome
+            let routed = {
+                let index = market_index.read().await;
+                crate::route_to_better_venue(intent, &index, live_book)
+            };
+            if let Some(ref r) = routed {
+                tracing::info!(
+                    ticker = %intent.ticker,
+                    token = %r.ticker,
+                    kalshi_price = intent.price,
+                    polymarket_price = r.price,
+                    "routing entry to Polymarket"
+                );
+                state_tx.send_modify(|s| {
+                    s.push_log(
+                        "ORDER",
+                        format!(
+                            "ROUTE {} -> Polymarket @ {}c (Kalshi {}c)",
+                            intent.ticker, r.price, intent.price
+                        ),
+                    );
+                });
+            }
+            // Positions and risk are keyed by the Kalshi outcome
+            // whichever venue fills it, so both venues share one cap
+            let canonical = intent;
+            let intent = routed.as_ref().unwrap_or(intent);
+
+            // Gate 1: PositionTracker - sizing already counted held
+            // contracts, so only top up the held side to the
+            // per-market cap; never enter the other side
+            if !self.position_tracker.can_enter(
+                &canonical.ticker,
+                &canonical.side,
+                intent.quantity,
+                risk_config.max_contracts_per_market,
+            ) {
+                let held = self.position_tracker.get(&canonical.ticker);
+                tracing::warn!(
+                    ticker = %canonical.ticker,
+                    side = %canonical.side,
+                    held_side = held.map(|p| p.side.as_str()),
+                    held = held.map_or(0, |p| p.quantity),
+                    quantity = intent.quantity,
+                    "BLOCKED: already holding position"
+                );
+                continue;
+            }
+            // A position is held on one venue, so it exits and
+            // settles as a whole
+            let venue = routed.as_ref().map(|r| r.ticker.as_str());
+            if let Some(held) = self.position_tracker.get(&canonical.ticker) {
+                if held.venue_ticker.as_deref() != venue {
+                    tracing::warn!(
+                        ticker = %canonical.ticker,
+                        held_on = held.order_leg().0,
+                        "BLOCKED: position held on the other venue"
+                    );
+                    continue;
+                }
+            }
+            // An entry still resting on the ticker must finish
+            // first; a second would replace it in `resting_entries`
+            // and orphan the resting order
+            if self.resting_entries.contains_key(&canonical.ticker) {
+                tracing::warn!(
+                    ticker = %intent.ticker,
+                    "BLOCKED: entry still resting"
+                );
+                continue;
+            }
+
+            // Gate 2: PendingOrderRegistry - skip if already pending
+            if self
+                .pending_orders
+                .is_pending(&intent.ticker, OrderSide::Entry)
+            {
+                tracing::warn!(
+                    ticker = %intent.ticker,
+                    "BLOCKED: order already pending"
+                );
+                continue;
+            }
+
+            // Gate 3: RiskManager - skip if risk limits exceeded
+            let position_cap = risk_config.liquidity_cap.as_ref().and_then(|cfg| {
+                live_book
+                    .read(&intent.ticker, |b| b.liquidity.position_cap(cfg))
+                    .flatten()
+            });
+            if !self.risk_manager.can_trade(
+                &canonical.ticker,
+                &canonical.side,
+                intent.quantity,
+                intent.entry_cost_cents,
+                position_cap,
+            ) {
+                tracing::warn!(
+                    ticker = %intent.ticker,
+                    quantity = intent.quantity,
+                    cost = intent.entry_cost_cents,
+                    "BLOCKED: risk limits exceeded"
+                );
+                record_reject(intent, SkipReason::RiskCap, state_tx, journal);
+                continue;
+            }
+
+            // Gate 4: Available balance
+            if (intent.entry_cost_cents as u64) > *available_balance_cents {
+                tracing::warn!(
+                    ticker = %intent.ticker,
+                    cost = intent.entry_cost_cents,
+                    available = *available_balance_cents,
+                    "BLOCKED: insufficient balance"
+                );
+                record_reject(intent, SkipReason::RiskCap, state_tx, journal);
+                continue;
+            }
+
+            // Gate 5: Market still open - it may have closed since the signal
+            let mut close_check = {
+                let index = market_index.read().await;
+                matcher::find_side_market(&index, &intent.ticker).map_or(
+                    matcher::SubmitCheck::Borderline,
+                    |sm| {
+                        matcher::submit_check(
+                            &sm.status,
+                            sm.close_time.as_deref(),
+                            chrono::Utc::now(),
+                            matcher::CLOSE_RECHECK_WINDOW_SECS,
+                        )
+                    },
+                )
+            };
+            if close_check == matcher::SubmitCheck::Borderline
+                && intent.venue == matcher::Venue::Kalshi
+            {
+                close_check = match rest.get_market(&intent.ticker).await {
+                    Ok(m) => matcher::submit_check(
+                        &m.status,
+                        m.close_time.as_deref(),
+                        chrono::Utc::now(),
+                        0,
+                    ),
+                    Err(e) => {
+                        tracing::warn!(
+                            ticker = %intent.ticker,
+                            error = %e,
+                            "market status re-check failed, submitting anyway"
+                        );
+                        matcher::SubmitCheck::Open
+                    }
+                };
+            }
+            if close_check == matcher::SubmitCheck::Closed {
+                tracing::warn!(
+                    ticker = %intent.ticker,
+                    "BLOCKED: market closed before submit"
+                );
+                matcher::mark_closed(&mut *market_index.write().await, &intent.ticker);
+                record_closed_before_submit(intent, state_tx, journal);
+                continue;
+            }
+
+            // Register pending order
+            if !self.pending_orders.try_register(
+                intent.ticker.clone(),
+                intent.quantity,
+                intent.price,
+                intent.is_taker,
+                OrderSide::Entry,
+            ) {
+                continue; // Race condition: another intent registered first
+            }
+
+            // Deduct from available balance (pessimistic)
+            *available_balance_cents -= intent.entry_cost_cents as u64;
+
+            // Log prominently
+            tracing::error!(
+                ticker = %intent.ticker,
+                quantity = intent.quantity,
+                price = intent.price,
+                edge = intent.edge,
+                net = intent.net_profit_estimate,
+                fair_value = intent.fair_value,
+                source = %intent.source,
+                sell_target = intent.sell_target,
+                is_taker = intent.is_taker,
+                "SUBMITTING ORDER"
+            );
+            state_tx.send_modify(|s| {
+                s.push_log(
+                    "ORDER",
+                    format!(
+                        "SUBMIT {}x {} @ {}c (edge {}c, FV {}c, {})",
+                        intent.quantity,
+                        intent.ticker,
+                        intent.price,
+                        intent.edge,
+                        intent.fair_value,
+                        if intent.is_taker { "TAKER" } else { "MAKER" },
+                    ),
+                );
+            });
+
+            // Submit order
+            let result = exec
+                .submit_order(
+                    &intent.ticker,
+                    intent.quantity,
+                    intent.price,
+                    intent.is_buy,
+                    intent.is_taker,
+                    &intent.side,
+                    intent.time_in_force,
+                )
+                .await;
+            let placed = match result {
+                Ok(placed) => placed,
+                Err(e) => {
+                    // Release pending order
+                    self.pending_orders
+                        .complete(&intent.ticker, OrderSide::Entry);
+                    // Restore available balance
+                    *available_balance_cents += intent.entry_cost_cents as u64;
+                    // Closed in the gap after the pre-submit check: not an error
+                    if format!("{:#}", e).contains("market_closed") {
+                        tracing::warn!(
+                            ticker = %intent.ticker,
+                            "order rejected: market closed before submit"
+                        );
+                        matcher::mark_closed(&mut *market_index.write().await, &intent.ticker);
+                        record_closed_before_submit(intent, state_tx, journal);
+                        continue;
+                    }
+                    tracing::error!(
+                        ticker = %intent.ticker,
+                        error = %e,
+                        "order submission failed"
+                    );
+                    state_tx.send_modify(|s| {
+                        s.push_log("ERROR", format!("ORDER FAILED {}: {}", intent.ticker, e));
+                    });
+                    continue;
+                }
+            };
+
+            // An immediate taker holds only what filled on
+            // arrival. A Kalshi response without a fill count
+            // is looked up over REST; failing that, the order
+            // is left to the fill channel like a resting entry.
+            let immediate = placed
+                .as_ref()
+                .filter(|_| intent.time_in_force.is_immediate());
+            let mut fills_unknown = false;
+            let filled = match immediate {
+                Some(p) if p.filled.is_none() && intent.venue == matcher::Venue::Kalshi => {
+                    let n = super::fills::immediate_fill_count(rest, &p.order_id).await;
+                    fills_unknown = n.is_none();
+                    n
+                }
+                Some(p) => p.filled,
+                None => None,
+            }
+            .filter(|&n| n < intent.quantity);
+            let part;
+            let intent = match filled {
+                Some(n) => {
+                    part = filled_part(intent, n);
+                    *available_balance_cents += intent
+                        .entry_cost_cents
+                        .saturating_sub(part.entry_cost_cents)
+                        as u64;
+                    &part
+                }
+                None => intent,
+            };
+            let order_id = placed.map(|p| p.order_id);
+            if intent.quantity == 0 {
+                self.pending_orders
+                    .complete(&intent.ticker, OrderSide::Entry);
+                tracing::warn!(
+                    ticker = %intent.ticker,
+                    order_id = ?order_id,
+                    time_in_force = intent.time_in_force.label(),
+                    "taker entry not filled, nothing rests"
+                );
+                state_tx.send_modify(|s| {
+                    s.push_log(
+                        "ORDER",
+                        format!(
+                            "{} UNFILLED {} @ {}c",
+                            intent.time_in_force.label(),
+                            intent.ticker,
+                            intent.price
+                        ),
+                    );
+                });
+                continue;
+            }
+            // Signal-to-exchange latency, for edge persistence tuning
+            if intent.is_taker && intent.venue == matcher::Venue::Kalshi {
+                if let Ok(mut ep) = edge_persistence.lock() {
+                    ep.record_latency(cycle_start.elapsed().as_millis() as u64);
+                }
+            }
+            journal.record(&JournalEntry::Order {
+                ts: chrono::Utc::now(),
+                ticker: intent.ticker.clone(),
+                side: intent.side.clone(),
+                action: "buy".to_string(),
+                quantity: intent.quantity,
+                price: intent.price,
+                is_taker: intent.is_taker,
+                sim: false,
+                source: intent.source.clone(),
+                trace: intent.trace.clone(),
+                book: intent.book.clone(),
+            });
+            // A Kalshi order left resting books its position
+            // fill by fill; maker entries may be kept pegged
+            // to the touch
+            let resting = order_id.as_ref().filter(|_| {
+                (!intent.time_in_force.is_immediate() || fills_unknown)
+                    && intent.venue == matcher::Venue::Kalshi
+            });
+            if let Some(order_id) = resting {
+                let entry = RestingEntry::new(
+                    order_id.clone(),
+                    intent.ticker.clone(),
+                    intent.side.clone(),
+                    intent.quantity,
+                    intent.price,
+                    intent.sell_target,
+                    intent.is_taker,
+                    Instant::now(),
+                );
+                let entry = if !intent.is_taker && self.execution.peg_maker_orders {
+                    entry.pegged(self.peg_mode, intent.fair_value, min_edge_after_fees as i32)
+                } else {
+                    entry
+                };
+                self.resting_entries.insert(intent.ticker.clone(), entry);
+            }
+            let resting = resting.is_some();
+            // Live orders are only accepted here; the fill is
+            // confirmed when fees are reconciled. Immediate
+            // orders have already filled.
+            let status = if order_id.is_some() && !intent.time_in_force.is_immediate() {
+                "ACCEPTED"
+            } else {
+                "FILLED"
+            };
+            // Track expected fee until fills are reconciled
+            if let Some(order_id) = order_id {
+                self.expected_fees.insert(
+                    order_id,
+                    ExpectedFee {
+                        ticker: intent.ticker.clone(),
+                        quantity: intent.quantity,
+                        price: intent.price,
+                        is_taker: intent.is_taker,
+                        submitted_at: Instant::now(),
+                    },
+                );
+            }
+            // Update RiskManager: a resting entry holds risk
+            // for its whole quantity until it is done
+            self.risk_manager
+                .record_buy(&canonical.ticker, &canonical.side, intent.quantity);
+            // Update PositionTracker; resting entries are
+            // booked as their fills arrive
+            if !resting {
+                self.position_tracker.record_entry(
+                    canonical.ticker.clone(),
+                    &canonical.side,
+                    intent.quantity,
+                    intent.price,
+                    intent.entry_cost_cents,
+                    intent.sell_target,
+                    Instant::now(),
+                    intent.is_taker,
+                );
+                if intent.venue == matcher::Venue::Polymarket {
+                    self.position_tracker
+                        .set_venue_ticker(&canonical.ticker, intent.ticker.clone());
+                }
+            }
+            // Complete pending order
+            self.pending_orders
+                .complete(&intent.ticker, OrderSide::Entry);
+            // Push trade to TUI
+            state_tx.send_modify(|s| {
+                s.kelly_tuner.record_entry(
+                    &intent.ticker,
+                    &intent.trace.sport,
+                    intent.net_profit_estimate as i64,
+                );
+                s.push_trade(TradeRow {
+                    id: 0,
+                    time: chrono::Local::now().format("%H:%M:%S").to_string(),
+                    action: "BUY".to_string(),
+                    ticker: intent.ticker.clone(),
+                    price: intent.price,
+                    quantity: intent.quantity,
+                    order_type: if intent.is_taker { "TAKER" } else { "MAKER" }.to_string(),
+                    pnl: None,
+                    slippage: None,
+                    source: intent.source.clone(),
+                    fair_value_basis: pipeline::format_fair_value_basis(&intent.trace),
+                    phase: pipeline::trace_phase(&intent.trace),
+                    note: String::new(),
+                });
+                s.push_log(
+                    "ORDER",
+                    format!(
+                        "{} {}x {} @ {}c",
+                        status, intent.quantity, intent.ticker, intent.price
+                    ),
+                );
+            });
+        }
+    }
+}
+
+/// The part of an immediate-or-cancel or fill-or-kill entry that filled on
+/// arrival; the exchange has already cancelled the rest. Quantity, cost and
+/// predicted profit scale down to `filled`.
+fn filled_part(intent: &OrderIntent, filled: u32) -> OrderIntent {
+    let mut part = intent.clone();
+    part.quantity = filled;
+    part.entry_cost_cents =
+        filled * intent.price + calculate_fee(intent.price, filled, intent.is_taker);
+    part.net_profit_estimate =
+        intent.net_profit_estimate * filled as i32 / intent.quantity.max(1) as i32;
+    part
+}
+
+fn record_reject(
+    intent: &OrderIntent,
+    reason: SkipReason,
+    state_tx: &watch::Sender<AppState>,
+    journal: &Journal,
+) {
+    let mut trace = intent.trace.clone();
+    trace.action = reason.label().to_string();
+    trace.skip_reason = Some(reason);
+    journal.record(&JournalEntry::Reject {
+        ts: chrono::Utc::now(),
+        ticker: intent.ticker.clone(),
+        side: intent.side.clone(),
+        reason,
+        sim: false,
+        trace,
+    });
+    state_tx.send_modify(|s| {
+        if let Some(row) = s.markets.iter_mut().find(|m| m.ticker == intent.ticker) {
+            row.action = reason.label().to_string();
+            row.skip_reason = Some(reason);
+        }
+    });
+}
+
+/// Journal an entry whose market closed between signal and submission.
+fn record_closed_before_submit(
+    intent: &OrderIntent,
+    state_tx: &watch::Sender<AppState>,
+    journal: &Journal,
+) {
+    record_reject(intent, SkipReason::ClosedBeforeSubmit, state_tx, journal);
+    state_tx.send_modify(|s| {
+        s.push_log("CLOSED", format!("{} closed before submit", intent.ticker));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intent(price: u32) -> OrderIntent {
+        OrderIntent {
+            ticker: "T".to_string(),
+            quantity: 2,
+            price,
+            is_buy: true,
+            is_taker: true,
+            edge: 5,
+            net_profit_estimate: 3,
+            fair_value: price + 5,
+            source: "espn".to_string(),
+            trace: pipeline::SignalTrace {
+                sport: "basketball".to_string(),
+                ticker: "T".to_string(),
+                timestamp: Instant::now(),
+                fair_value_method: pipeline::FairValueMethod::ScoreFeed {
+                    source: "espn".to_string(),
+                },
+                fair_value_cents: price + 5,
+                fair_value_stderr: None,
+                inputs: pipeline::FairValueInputs::Score {
+                    home_score: 50,
+                    away_score: 45,
+                    elapsed_secs: 1200,
+                    period: "3".to_string(),
+                    phase: "Q3".to_string(),
+                    win_prob: 0.6,
+                },
+                best_bid: price - 2,
+                best_ask: price,
+                edge: 5,
+                action: "TAKER BUY".to_string(),
+                net_profit_estimate: 3,
+                quantity: 2,
+                momentum_score: 80.0,
+                momentum_gated: false,
+                skip_reason: None,
+            },
+            entry_cost_cents: price * 2 + 4,
+            sell_target: price + 4,
+            side: "yes".to_string(),
+            time_in_force: TimeInForce::ImmediateOrCancel,
+            book: None,
+            venue: matcher::Venue::Kalshi,
+        }
+    }
+
+    #[test]
+    fn test_filled_part_of_immediate_entry() {
+        let full = intent(40);
+        let part = filled_part(&full, 1);
+        assert_eq!(part.quantity, 1);
+        assert_eq!(part.entry_cost_cents, 40 + calculate_fee(40, 1, true));
+        assert_eq!(part.net_profit_estimate, 1);
+        assert_eq!(filled_part(&full, 0).entry_cost_cents, 0);
+    }
+}
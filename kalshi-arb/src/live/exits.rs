@@ -0,0 +1,465 @@
+//! Live exits: a resting sell at each position's target, re-priced as fair
+//! value drifts, and sales into the bid on an edge flip, stop-loss or
+//! timeout. Also books the closes those sells and settlements produce.
+
+use super::LiveSession;
+use crate::config::{ExitConfig, RiskConfig};
+use crate::engine::exit_manager::{ExitAction, RestingExit};
+use crate::engine::fees::calculate_fee;
+use crate::engine::{ExpectedFee, OrderSide};
+use crate::execution::{OrderExecutor, PlacedOrder};
+use crate::kalshi::{rest::KalshiRest, types::TimeInForce};
+use crate::tui::state::{AppState, MarketRow, TradeRow};
+use crate::LiveBook;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+impl LiveSession {
+    /// Manage the exit of every position whose entry is done filling, each
+    /// sold on the venue it is held on against that side's bid and the fair
+    /// value in `rows`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn manage_exits(
+        &mut self,
+        exec: &OrderExecutor,
+        rest: &KalshiRest,
+        live_book: &LiveBook,
+        rows: &HashMap<String, MarketRow>,
+        exit_config: &ExitConfig,
+        risk_config: &RiskConfig,
+        max_hold: Duration,
+        state_tx: &watch::Sender<AppState>,
+    ) {
+        let now = Instant::now();
+        let positions: Vec<_> = self
+            .position_tracker
+            .all_positions()
+            .iter()
+            .map(|p| (*p).clone())
+            .collect();
+
+        for position in positions {
+            // Exit once the entry is done filling
+            if self.resting_entries.contains_key(&position.ticker)
+                || self
+                    .pending_orders
+                    .is_pending(&position.ticker, OrderSide::Exit)
+            {
+                continue;
+            }
+
+            // Sell the side held, on the venue it is held on,
+            // against that side's bid and fair value
+            let (leg, leg_side) = position.order_leg();
+            let bid = live_book.read(leg, |d| d.best_bid(leg_side)).unwrap_or(0);
+            let fair_value = rows.get(&position.ticker).map(|r| {
+                if position.side == "no" {
+                    100u32.saturating_sub(r.fair_value)
+                } else {
+                    r.fair_value
+                }
+            });
+
+            let action = self.exit_manager.decide(
+                &position,
+                bid,
+                fair_value,
+                now,
+                exit_config,
+                risk_config,
+                max_hold,
+            );
+            let (price, reason) = match action {
+                ExitAction::Hold => continue,
+                ExitAction::Filled { price } => {
+                    self.exit_manager.remove(&position.ticker);
+                    self.book_exit(
+                        &position.ticker,
+                        position.quantity,
+                        price,
+                        false,
+                        "SELL",
+                        state_tx,
+                    );
+                    continue;
+                }
+                ExitAction::Place { price } | ExitAction::Replace { price } => (price, None),
+                ExitAction::MarketExit { reason } => (bid, Some(reason)),
+            };
+            let is_taker = reason.is_some() || price <= bid;
+
+            // Pull the resting sell first; a failed cancel most likely
+            // means it filled, and its fill books the exit. Fills are
+            // booked as they arrive, so what is still held is what
+            // the new sell covers.
+            let previous = self.exit_manager.get(&position.ticker).cloned();
+            if let Some(ref resting) = previous {
+                if let Some(ref order_id) = resting.order_id {
+                    if let Err(e) = exec.cancel_order(order_id).await {
+                        tracing::warn!(ticker = %position.ticker, error = %e, "resting exit cancel failed, awaiting fill");
+                        continue;
+                    }
+                    self.expected_fees.remove(order_id);
+                }
+                self.exit_manager.remove(&position.ticker);
+                self.close_resting_exit(&position.ticker, resting, rest, state_tx)
+                    .await;
+            }
+            let Some(position) = self.position_tracker.get(&position.ticker).cloned() else {
+                continue;
+            };
+            let quantity = position.quantity;
+
+            if !self.pending_orders.try_register(
+                position.ticker.clone(),
+                quantity,
+                price,
+                is_taker,
+                OrderSide::Exit,
+            ) {
+                continue;
+            }
+
+            tracing::info!(
+                ticker = %position.ticker,
+                quantity,
+                price,
+                sell_target = position.sell_target,
+                fair_value = ?fair_value,
+                reason = reason.map(|r| r.label()),
+                "submitting exit order"
+            );
+
+            // A sale into the bid is immediate-or-cancel, so it never
+            // rests once the bid moves away
+            let time_in_force = match reason {
+                Some(_) => TimeInForce::ImmediateOrCancel,
+                None => TimeInForce::GoodTillCanceled,
+            };
+            let (leg, leg_side) = position.order_leg();
+            let result = exec
+                .submit_order(
+                    leg,
+                    quantity,
+                    price,
+                    false,
+                    is_taker,
+                    leg_side,
+                    time_in_force,
+                )
+                .await;
+            self.pending_orders
+                .complete(&position.ticker, OrderSide::Exit);
+            match result {
+                Ok(placed) => {
+                    let order_id = placed.as_ref().map(|p| p.order_id.clone());
+                    if let Some(ref order_id) = order_id {
+                        self.expected_fees.insert(
+                            order_id.clone(),
+                            ExpectedFee {
+                                ticker: position.ticker.clone(),
+                                quantity,
+                                price,
+                                is_taker,
+                                submitted_at: now,
+                            },
+                        );
+                    }
+                    match reason {
+                        // Sales into the bid book only what the exchange
+                        // reports filled; dry-run fills in full
+                        Some(reason) => {
+                            let filled = immediate_sell_fill(placed.as_ref(), quantity, rest).await;
+                            match filled {
+                                Some(n) if n > 0 => {
+                                    self.book_exit(
+                                        &position.ticker,
+                                        n,
+                                        price,
+                                        true,
+                                        reason.label(),
+                                        state_tx,
+                                    );
+                                }
+                                Some(_) => {
+                                    state_tx.send_modify(|s| {
+                                        s.push_log(
+                                            "ORDER",
+                                            format!(
+                                                "{} UNFILLED {} @ {}c",
+                                                reason.label(),
+                                                position.ticker,
+                                                price
+                                            ),
+                                        );
+                                    });
+                                }
+                                None => {
+                                    tracing::warn!(ticker = %position.ticker, "exit fill count unknown, position left as held");
+                                }
+                            }
+                        }
+                        None => {
+                            self.exit_manager.record_placed(
+                                &position.ticker,
+                                order_id,
+                                price,
+                                quantity,
+                                position.quantity,
+                                now,
+                            );
+                            let msg = match previous {
+                                Some(p) => {
+                                    format!("EXIT {} {}c -> {}c", position.ticker, p.price, price)
+                                }
+                                None => format!(
+                                    "EXIT {}x {} resting @ {}c",
+                                    quantity, position.ticker, price
+                                ),
+                            };
+                            state_tx.send_modify(|s| s.push_log("ORDER", msg));
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::error!(
+                        ticker = %position.ticker,
+                        error = %e,
+                        "exit order submission failed"
+                    );
+                    state_tx.send_modify(|s| {
+                        s.push_log("ERROR", format!("EXIT FAILED {}: {}", position.ticker, e));
+                    });
+                }
+            }
+        }
+    }
+
+    /// Book a position on a settled market at the result, `value` being
+    /// the YES payout: a NO position settles at its complement and no fee is
+    /// charged. A resting exit on the market is dropped with it.
+    pub fn settle(&mut self, ticker: &str, value: u32, state_tx: &watch::Sender<AppState>) {
+        let Some(position) = self.position_tracker.get(ticker).cloned() else {
+            return;
+        };
+        let price = if position.side == "no" {
+            100u32.saturating_sub(value)
+        } else {
+            value
+        };
+        self.exit_manager.remove(ticker);
+        self.book_close(
+            ticker,
+            position.quantity,
+            price,
+            0,
+            "SETTLE",
+            "RESULT",
+            state_tx,
+        );
+    }
+
+    /// Book `quantity` contracts of a position sold at `exit_price`:
+    /// release them and their risk, and push the trade with its realized P&L.
+    pub(super) fn book_exit(
+        &mut self,
+        ticker: &str,
+        quantity: u32,
+        exit_price: u32,
+        is_taker: bool,
+        action: &str,
+        state_tx: &watch::Sender<AppState>,
+    ) {
+        let exit_fee = calculate_fee(exit_price, quantity, is_taker);
+        let order_type = if is_taker { "TAKER" } else { "MAKER" };
+        self.book_close(
+            ticker, quantity, exit_price, exit_fee, action, order_type, state_tx,
+        );
+    }
+
+    /// Release `quantity` contracts of a position and their risk, and push
+    /// the trade with its realized P&L after `exit_fee`. The contracts carry
+    /// their share of the position's entry cost.
+    #[allow(clippy::too_many_arguments)]
+    fn book_close(
+        &mut self,
+        ticker: &str,
+        quantity: u32,
+        exit_price: u32,
+        exit_fee: u32,
+        action: &str,
+        order_type: &str,
+        state_tx: &watch::Sender<AppState>,
+    ) {
+        let Some(position) = self.position_tracker.record_partial_exit(ticker, quantity) else {
+            return;
+        };
+        let exit_revenue = (position.quantity * exit_price) as i64;
+        let pnl = (exit_revenue - exit_fee as i64) - position.entry_cost_cents as i64;
+
+        self.risk_manager
+            .record_sell(&position.ticker, position.quantity);
+
+        state_tx.send_modify(|s| {
+            s.realized_pnl_cents += pnl;
+            s.record_realized_edge(&position.ticker, pnl);
+            s.total_trades += 1;
+            if pnl > 0 {
+                s.winning_trades += 1;
+            }
+            s.push_trade(TradeRow {
+                id: 0,
+                time: chrono::Local::now().format("%H:%M:%S").to_string(),
+                action: action.to_string(),
+                ticker: position.ticker.clone(),
+                price: exit_price,
+                quantity: position.quantity,
+                order_type: order_type.to_string(),
+                pnl: Some(pnl as i32),
+                slippage: None,
+                source: String::new(),
+                fair_value_basis: String::new(),
+                phase: String::new(),
+                note: String::new(),
+            });
+            s.push_log(
+                "ORDER",
+                format!(
+                    "{} {}x {} @ {}c, P&L: {:+}c",
+                    action, position.quantity, position.ticker, exit_price, pnl
+                ),
+            );
+        });
+    }
+
+    /// Book the fills the WS fill channel missed on an exit sell that is off
+    /// the book, looked up over REST, at their average price.
+    pub(super) async fn close_resting_exit(
+        &mut self,
+        ticker: &str,
+        exit: &RestingExit,
+        rest: &KalshiRest,
+        state_tx: &watch::Sender<AppState>,
+    ) {
+        let Some(ref order_id) = exit.order_id else {
+            return;
+        };
+        let Some(side) = self.position_tracker.get(ticker).map(|p| p.side.clone()) else {
+            return;
+        };
+        let fills = match rest.get_fills_for_order(order_id).await {
+            Ok(fills) => fills,
+            Err(e) => {
+                tracing::warn!(
+                    order_id = %order_id,
+                    error = %e,
+                    "fill lookup failed, keeping WS fills"
+                );
+                return;
+            }
+        };
+        let (mut filled, mut value) = (0u32, 0u32);
+        for f in fills.iter().filter(|f| f.action == "sell") {
+            let price = if side == "no" {
+                f.no_price
+            } else {
+                f.yes_price
+            };
+            filled += f.count;
+            value += f.count * price;
+        }
+        let missed = filled.saturating_sub(exit.filled);
+        let missed_value = value.saturating_sub(exit.filled_value_cents);
+        if let Some(price) = missed_value.checked_div(missed) {
+            tracing::warn!(ticker, missed, price, "booking exit fills the WS missed");
+            self.book_exit(ticker, missed, price, false, "SELL", state_tx);
+        }
+    }
+}
+
+/// Contracts an immediate-or-cancel sell filled: the count the exchange
+/// reported, else looked up over REST; `None` when neither says. A dry-run
+/// sell places nothing and counts as filled in full.
+pub(super) async fn immediate_sell_fill(
+    placed: Option<&PlacedOrder>,
+    quantity: u32,
+    rest: &KalshiRest,
+) -> Option<u32> {
+    let Some(p) = placed else {
+        return Some(quantity);
+    };
+    match p.filled {
+        Some(n) => Some(n.min(quantity)),
+        None => super::fills::immediate_fill_count(rest, &p.order_id)
+            .await
+            .map(|n| n.min(quantity)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ExecutionConfig;
+
+    fn session() -> LiveSession {
+        LiveSession::new(&RiskConfig::default(), &ExecutionConfig::default())
+    }
+
+    #[test]
+    fn test_settlement_releases_position_at_result() {
+        let mut live = session();
+        live.position_tracker.record_entry(
+            "T".to_string(),
+            "no",
+            2,
+            30,
+            62,
+            40,
+            Instant::now(),
+            true,
+        );
+        let (state_tx, state_rx) = watch::channel(AppState::new());
+
+        // A NO position settles at the complement of the YES result, fee-free
+        live.settle("T", 0, &state_tx);
+        assert!(live.position_tracker.get("T").is_none());
+        let s = state_rx.borrow();
+        assert_eq!(s.realized_pnl_cents, 2 * 100 - 62);
+        assert_eq!(s.winning_trades, 1);
+
+        // Nothing left to settle on a second result
+        drop(s);
+        live.settle("T", 0, &state_tx);
+        assert_eq!(state_rx.borrow().total_trades, 1);
+    }
+
+    #[test]
+    fn test_exit_fills_book_at_their_own_price() {
+        let mut live = session();
+        live.position_tracker.record_entry(
+            "T".to_string(),
+            "yes",
+            3,
+            50,
+            153,
+            55,
+            Instant::now(),
+            true,
+        );
+        live.exit_manager
+            .record_placed("T", Some("ord-1".to_string()), 60, 3, 3, Instant::now());
+        let (state_tx, state_rx) = watch::channel(AppState::new());
+
+        for (count, price) in [(1, 60), (2, 62)] {
+            let f = live.exit_manager.on_fill("ord-1", count, price).unwrap();
+            live.book_exit(&f.ticker, f.count, price, false, "SELL", &state_tx);
+        }
+        assert!(live.position_tracker.get("T").is_none());
+        assert!(live.exit_manager.get("T").is_none());
+        // The first fill is not repriced to the last fill's price
+        let fees = calculate_fee(60, 1, false) + calculate_fee(62, 2, false);
+        let pnl = (60 + 2 * 62 - fees) as i64 - 153;
+        assert_eq!(state_rx.borrow().realized_pnl_cents, pnl);
+    }
+}
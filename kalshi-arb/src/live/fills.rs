@@ -0,0 +1,261 @@
+//! Booking our own exchange fills as the WS reports them, and the REST
+//! fallback for fills it missed.
+
+use super::LiveSession;
+use crate::engine::fees::calculate_fee;
+use crate::engine::PositionTracker;
+use crate::execution::RestingEntry;
+use crate::kalshi::{rest::KalshiRest, types::WsFill};
+use crate::tui::state::AppState;
+use std::time::Instant;
+use tokio::sync::watch;
+
+impl LiveSession {
+    /// Apply a WS fill for one of our orders. Entry fills are confirmed as
+    /// they happen and restart the position's hold clock from the real fill
+    /// time. Resting entries and exit sells book each fill at its own price
+    /// as it arrives.
+    pub fn apply_fill(&mut self, fill: &WsFill, state_tx: &watch::Sender<AppState>) {
+        let price = fill.price_cents();
+        tracing::info!(
+            ticker = %fill.market_ticker,
+            order_id = %fill.order_id,
+            action = %fill.action,
+            count = fill.count,
+            price,
+            "ws fill"
+        );
+        let msg = match self.expected_fees.get(&fill.order_id) {
+            Some(expected) => {
+                let filled = self.ws_filled.entry(fill.order_id.clone()).or_insert(0);
+                if *filled == 0 && fill.action == "buy" {
+                    self.position_tracker
+                        .mark_filled(&expected.ticker, Instant::now());
+                }
+                *filled += fill.count;
+                format!(
+                    "FILLED {}/{} {} @ {}c",
+                    filled, expected.quantity, expected.ticker, price
+                )
+            }
+            None => format!(
+                "FILL {} {}x {} @ {}c",
+                fill.action.to_uppercase(),
+                fill.count,
+                fill.market_ticker,
+                price
+            ),
+        };
+        state_tx.send_modify(|s| s.push_log("ORDER", msg));
+
+        // Resting entries book each fill on whichever of the entry's order
+        // ids it filled
+        let resting = self
+            .resting_entries
+            .get_mut(&fill.market_ticker)
+            .filter(|e| fill.action == "buy" && e.owns(&fill.order_id));
+        if let Some(entry) = resting {
+            book_entry_fill(entry, fill.count, price, &mut self.position_tracker);
+            if entry.remaining() == 0 {
+                self.resting_entries.remove(&fill.market_ticker);
+            }
+        }
+        if fill.action == "sell" {
+            if let Some(f) = self.exit_manager.on_fill(&fill.order_id, fill.count, price) {
+                self.book_exit(&f.ticker, f.count, price, fill.is_taker, "SELL", state_tx);
+            }
+        }
+    }
+
+    /// Stop tracking a resting entry that is off the book. Fills the WS fill
+    /// channel missed are looked up over REST, across every order id the entry
+    /// rested under, and booked; then the risk held for the unfilled remainder
+    /// is released. Returns the entry with its final fill count.
+    pub async fn close_resting_entry(
+        &mut self,
+        mut entry: RestingEntry,
+        rest: &KalshiRest,
+    ) -> RestingEntry {
+        let mut reported = Some((0u32, 0u32));
+        for order_id in &entry.order_ids {
+            match rest.get_fills_for_order(order_id).await {
+                Ok(fills) => {
+                    for f in fills.iter().filter(|f| f.action != "sell") {
+                        let price = if entry.side == "no" {
+                            f.no_price
+                        } else {
+                            f.yes_price
+                        };
+                        reported = reported.map(|(n, cost)| (n + f.count, cost + f.count * price));
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        order_id = %order_id,
+                        error = %e,
+                        "fill lookup failed, keeping WS fills"
+                    );
+                    reported = None;
+                    break;
+                }
+            }
+        }
+        if let Some((filled, cost)) = reported {
+            let missed = filled.saturating_sub(entry.filled);
+            let missed_cost = cost.saturating_sub(entry.filled_cost_cents);
+            if let Some(price) = missed_cost.checked_div(missed) {
+                tracing::warn!(
+                    ticker = %entry.ticker,
+                    missed,
+                    price,
+                    "booking entry fills the WS missed"
+                );
+                book_entry_fill(&mut entry, missed, price, &mut self.position_tracker);
+            }
+        }
+        self.risk_manager
+            .record_sell(&entry.ticker, entry.remaining());
+        entry
+    }
+}
+
+/// Book a fill on a resting entry: the position grows by the contracts the
+/// fill adds, at its price plus fee.
+fn book_entry_fill(
+    entry: &mut RestingEntry,
+    count: u32,
+    price: u32,
+    position_tracker: &mut PositionTracker,
+) {
+    let count = entry.on_fill(count, price);
+    if count == 0 {
+        return;
+    }
+    position_tracker.record_entry(
+        entry.ticker.clone(),
+        &entry.side,
+        count,
+        price,
+        count * price + calculate_fee(price, count, entry.is_taker),
+        entry.sell_target,
+        Instant::now(),
+        entry.is_taker,
+    );
+}
+
+/// Contracts an immediate order filled, summed from its fills over REST;
+/// `None` when the lookup fails.
+pub(super) async fn immediate_fill_count(rest: &KalshiRest, order_id: &str) -> Option<u32> {
+    match rest.get_fills_for_order(order_id).await {
+        Ok(fills) => Some(fills.iter().map(|f| f.count).sum()),
+        Err(e) => {
+            tracing::warn!(
+                order_id = %order_id,
+                error = %e,
+                "fill lookup failed, waiting on the fill channel"
+            );
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ExecutionConfig, RiskConfig};
+    use crate::engine::ExpectedFee;
+
+    fn session() -> LiveSession {
+        LiveSession::new(&RiskConfig::default(), &ExecutionConfig::default())
+    }
+
+    fn fill(order_id: &str, action: &str, count: u32, yes_price: u32) -> WsFill {
+        WsFill {
+            trade_id: String::new(),
+            order_id: order_id.to_string(),
+            market_ticker: "T".to_string(),
+            side: "yes".to_string(),
+            action: action.to_string(),
+            count,
+            yes_price,
+            yes_price_dollars: None,
+            is_taker: false,
+            ts: None,
+        }
+    }
+
+    #[test]
+    fn test_resting_entry_books_each_fill() {
+        let mut entry = RestingEntry::new(
+            "ord-1".to_string(),
+            "T".to_string(),
+            "yes".to_string(),
+            10,
+            50,
+            60,
+            false,
+            Instant::now(),
+        );
+        let mut pt = PositionTracker::new();
+        book_entry_fill(&mut entry, 4, 50, &mut pt);
+        assert_eq!(pt.get("T").unwrap().quantity, 4);
+
+        // The rest fills at the re-pegged price; an overfill is capped
+        entry.record_repeg("ord-2".to_string(), 52, Instant::now());
+        book_entry_fill(&mut entry, 6, 52, &mut pt);
+        book_entry_fill(&mut entry, 3, 52, &mut pt);
+        let pos = pt.get("T").unwrap();
+        assert_eq!(pos.quantity, 10);
+        assert_eq!(
+            pos.entry_cost_cents,
+            4 * 50 + calculate_fee(50, 4, false) + 6 * 52 + calculate_fee(52, 6, false)
+        );
+        assert_eq!(entry.remaining(), 0);
+    }
+
+    #[test]
+    fn test_ws_fills_book_resting_entry_then_exit() {
+        let mut live = session();
+        let (state_tx, state_rx) = watch::channel(AppState::new());
+        let entry = RestingEntry::new(
+            "ord-1".to_string(),
+            "T".to_string(),
+            "yes".to_string(),
+            3,
+            50,
+            60,
+            false,
+            Instant::now(),
+        );
+        live.resting_entries.insert("T".to_string(), entry);
+        live.expected_fees.insert(
+            "ord-1".to_string(),
+            ExpectedFee {
+                ticker: "T".to_string(),
+                quantity: 3,
+                price: 50,
+                is_taker: false,
+                submitted_at: Instant::now(),
+            },
+        );
+
+        // A fill on someone else's order id leaves the entry alone
+        live.apply_fill(&fill("other", "buy", 3, 50), &state_tx);
+        assert!(live.position_tracker.get("T").is_none());
+
+        live.apply_fill(&fill("ord-1", "buy", 1, 50), &state_tx);
+        live.apply_fill(&fill("ord-1", "buy", 2, 50), &state_tx);
+        assert_eq!(live.position_tracker.get("T").unwrap().quantity, 3);
+        assert!(live.resting_entries.is_empty());
+        assert_eq!(live.ws_filled["ord-1"], 3);
+
+        // The resting exit's fill closes the position at its price
+        live.exit_manager
+            .record_placed("T", Some("ord-2".to_string()), 60, 3, 3, Instant::now());
+        live.apply_fill(&fill("ord-2", "sell", 3, 60), &state_tx);
+        assert!(live.position_tracker.get("T").is_none());
+        let cost = 3 * 50 + calculate_fee(50, 3, false);
+        let pnl = (3 * 60 - calculate_fee(60, 3, false) - cost) as i64;
+        assert_eq!(state_rx.borrow().realized_pnl_cents, pnl);
+    }
+}
@@ -0,0 +1,294 @@
+//! Manual orders from the TUI in live mode.
+
+use super::exits::immediate_sell_fill;
+use super::LiveSession;
+use crate::engine::fees::{break_even_sell_price, calculate_fee};
+use crate::engine::{ExpectedFee, OrderSide};
+use crate::execution::{OrderExecutor, RestingEntry};
+use crate::kalshi::{rest::KalshiRest, types::TimeInForce};
+use crate::tui::state::{AppState, TradeRow};
+use crate::tui::ManualOrder;
+use std::time::Instant;
+use tokio::sync::watch;
+
+impl LiveSession {
+    /// Submit a manual order. Buys pass the risk and pending-order gates and,
+    /// like signalled entries, rest until filled or timed out. Sells close the
+    /// whole held position on its own side: into the bid they go
+    /// immediate-or-cancel and book what filled, above it they rest as the
+    /// position's exit and the exit manager reprices them as usual. `bid` and
+    /// `ask` are from `manual_order_touch`.
+    pub async fn submit_manual_order(
+        &mut self,
+        order: &ManualOrder,
+        bid: u32,
+        ask: u32,
+        exec: &OrderExecutor,
+        rest: &KalshiRest,
+        state_tx: &watch::Sender<AppState>,
+    ) {
+        let log = |msg: String| {
+            tracing::warn!("{}", msg);
+            state_tx.send_modify(|s| s.push_log("ORDER", msg));
+        };
+        let verb = if order.is_buy { "BUY" } else { "SELL" };
+        let position = self.position_tracker.get(&order.ticker).cloned();
+        // A sell closes the held position, so it sells that position's side
+        let (is_taker, order_side, side, mut quantity) = if order.is_buy {
+            let is_taker = ask > 0 && order.price >= ask;
+            (
+                is_taker,
+                OrderSide::Entry,
+                order.side.as_str(),
+                order.quantity,
+            )
+        } else {
+            let side = position.as_ref().map_or("yes", |p| p.side.as_str());
+            (bid > 0 && order.price <= bid, OrderSide::Exit, side, 0)
+        };
+        // ...on the venue it is held on
+        let (order_ticker, side) = match position.as_ref() {
+            Some(p) if !order.is_buy => p.order_leg(),
+            _ => (order.ticker.as_str(), side),
+        };
+        let cost =
+            order.price * order.quantity + calculate_fee(order.price, order.quantity, is_taker);
+        if order.is_buy {
+            if position.as_ref().is_some_and(|p| p.side != order.side) {
+                log(format!(
+                    "MANUAL BUY {} {} blocked: holding the other side",
+                    order.ticker,
+                    order.side.to_uppercase()
+                ));
+                return;
+            }
+            if position.as_ref().is_some_and(|p| p.venue_ticker.is_some()) {
+                log(format!(
+                    "MANUAL BUY {} blocked: position held on Polymarket",
+                    order.ticker
+                ));
+                return;
+            }
+            if self.resting_entries.contains_key(&order.ticker) {
+                log(format!(
+                    "MANUAL BUY {} blocked: entry still resting",
+                    order.ticker
+                ));
+                return;
+            }
+            if !self
+                .risk_manager
+                .can_trade(&order.ticker, &order.side, order.quantity, cost, None)
+            {
+                log(format!(
+                    "MANUAL BUY {} blocked: risk limits exceeded",
+                    order.ticker
+                ));
+                return;
+            }
+        } else {
+            if position.is_none() {
+                log(format!("MANUAL SELL {}: no position held", order.ticker));
+                return;
+            };
+            // Pull the engine's resting exit first; a failed cancel most likely
+            // means it filled, and its fill books the exit
+            if let Some(resting) = self.exit_manager.get(&order.ticker).cloned() {
+                if let Some(ref order_id) = resting.order_id {
+                    if let Err(e) = exec.cancel_order(order_id).await {
+                        log(format!(
+                            "MANUAL SELL {}: resting exit cancel failed: {}",
+                            order.ticker, e
+                        ));
+                        return;
+                    }
+                    self.expected_fees.remove(order_id);
+                }
+                self.exit_manager.remove(&order.ticker);
+                self.close_resting_exit(&order.ticker, &resting, rest, state_tx)
+                    .await;
+            }
+            // Its fills are booked as they arrive; sell what is still held
+            let Some(held) = self.position_tracker.get(&order.ticker) else {
+                log(format!(
+                    "MANUAL SELL {}: position already closed",
+                    order.ticker
+                ));
+                return;
+            };
+            quantity = held.quantity;
+        }
+
+        if !self.pending_orders.try_register(
+            order.ticker.clone(),
+            quantity,
+            order.price,
+            is_taker,
+            order_side,
+        ) {
+            log(format!(
+                "MANUAL {} {} blocked: order already pending",
+                verb, order.ticker
+            ));
+            return;
+        }
+        tracing::info!(
+            ticker = %order.ticker,
+            side,
+            is_buy = order.is_buy,
+            quantity,
+            price = order.price,
+            is_taker,
+            "submitting manual order"
+        );
+        // A sell into the bid is immediate-or-cancel, so it never rests once
+        // the bid moves away
+        let time_in_force = if is_taker && !order.is_buy {
+            TimeInForce::ImmediateOrCancel
+        } else {
+            TimeInForce::GoodTillCanceled
+        };
+        let result = exec
+            .submit_order(
+                order_ticker,
+                quantity,
+                order.price,
+                order.is_buy,
+                is_taker,
+                side,
+                time_in_force,
+            )
+            .await;
+        self.pending_orders.complete(&order.ticker, order_side);
+        let placed = match result {
+            Ok(placed) => placed,
+            Err(e) => {
+                tracing::error!(ticker = %order.ticker, error = %e, "manual order submission failed");
+                state_tx.send_modify(|s| {
+                    s.push_log(
+                        "ERROR",
+                        format!("MANUAL {} FAILED {}: {}", verb, order.ticker, e),
+                    );
+                });
+                return;
+            }
+        };
+        let order_id = placed.as_ref().map(|p| p.order_id.clone());
+        if let Some(ref order_id) = order_id {
+            self.expected_fees.insert(
+                order_id.clone(),
+                ExpectedFee {
+                    ticker: order.ticker.clone(),
+                    quantity,
+                    price: order.price,
+                    is_taker,
+                    submitted_at: Instant::now(),
+                },
+            );
+        }
+
+        if let Some(position) = position.filter(|_| !order.is_buy) {
+            if is_taker {
+                // Only what the exchange reports filled is booked
+                match immediate_sell_fill(placed.as_ref(), quantity, rest).await {
+                    Some(0) => log(format!(
+                        "MANUAL SELL {} @ {}c not filled",
+                        order.ticker, order.price
+                    )),
+                    Some(filled) => {
+                        self.book_exit(
+                            &position.ticker,
+                            filled,
+                            order.price,
+                            true,
+                            "MANUAL SELL",
+                            state_tx,
+                        );
+                    }
+                    None => log(format!(
+                        "MANUAL SELL {}: fill count unknown, position left as held",
+                        order.ticker
+                    )),
+                }
+            } else {
+                self.exit_manager.record_placed(
+                    &order.ticker,
+                    order_id,
+                    order.price,
+                    quantity,
+                    position.quantity,
+                    Instant::now(),
+                );
+                state_tx.send_modify(|s| {
+                    s.push_log(
+                        "ORDER",
+                        format!(
+                            "MANUAL EXIT {}x {} resting @ {}c",
+                            quantity, order.ticker, order.price
+                        ),
+                    );
+                });
+            }
+            return;
+        }
+
+        self.risk_manager
+            .record_buy(&order.ticker, &order.side, quantity);
+        let sell_target = break_even_sell_price(cost, quantity, false).unwrap_or(99);
+        // A resting buy books its position as fills arrive
+        if let Some(order_id) = order_id {
+            self.resting_entries.insert(
+                order.ticker.clone(),
+                RestingEntry::new(
+                    order_id,
+                    order.ticker.clone(),
+                    order.side.clone(),
+                    quantity,
+                    order.price,
+                    sell_target,
+                    is_taker,
+                    Instant::now(),
+                ),
+            );
+        } else {
+            self.position_tracker.record_entry(
+                order.ticker.clone(),
+                &order.side,
+                quantity,
+                order.price,
+                cost,
+                sell_target,
+                Instant::now(),
+                is_taker,
+            );
+        }
+        state_tx.send_modify(|s| {
+            s.push_trade(TradeRow {
+                id: 0,
+                time: chrono::Local::now().format("%H:%M:%S").to_string(),
+                action: "BUY".to_string(),
+                ticker: order.ticker.clone(),
+                price: order.price,
+                quantity,
+                order_type: if is_taker { "TAKER" } else { "MAKER" }.to_string(),
+                pnl: None,
+                slippage: None,
+                source: "manual".to_string(),
+                fair_value_basis: String::new(),
+                phase: String::new(),
+                note: String::new(),
+            });
+            s.push_log(
+                "ORDER",
+                format!(
+                    "MANUAL BUY {}x {} {} @ {}c ({})",
+                    quantity,
+                    order.ticker,
+                    order.side.to_uppercase(),
+                    order.price,
+                    if is_taker { "TAKER" } else { "MAKER" }
+                ),
+            );
+        });
+    }
+}
@@ -0,0 +1,152 @@
+//! Live-mode order state and the engine-loop stages that act on it.
+//!
+//! `main` holds one `LiveSession` in live mode and none in simulation. Each
+//! stage is a method run once per cycle: `fills` books what the WS reports,
+//! `entries` submits signalled entries and keeps resting ones pegged, `exits`
+//! manages the sells and `manual` carries out orders from the TUI. The
+//! exchange is reached through the executor and REST client passed in, so
+//! the bookkeeping can be tested without one.
+
+mod entries;
+mod exits;
+mod fills;
+mod manual;
+
+use crate::config::{ExecutionConfig, RiskConfig};
+use crate::engine::exit_manager::ExitManager;
+use crate::engine::risk::RiskManager;
+use crate::engine::{ExpectedFee, PendingOrderRegistry, PositionTracker};
+use crate::execution::{OrderExecutor, PegMode, RestingEntry};
+use crate::kalshi::{rest::KalshiRest, types::MarketPosition};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// What a live session holds, has resting and is waiting on fills for.
+pub struct LiveSession {
+    pub risk_manager: RiskManager,
+    pub position_tracker: PositionTracker,
+    pub pending_orders: PendingOrderRegistry,
+    /// Expected fees of submitted orders, keyed by order ID, awaiting fills
+    pub expected_fees: HashMap<String, ExpectedFee>,
+    /// Contracts filled per order ID as reported by the WS fill channel
+    pub ws_filled: HashMap<String, u32>,
+    /// Entries still resting on the book, keyed by ticker; pegged maker
+    /// entries are kept at the touch via cancel/replace
+    pub resting_entries: HashMap<String, RestingEntry>,
+    /// Resting exit sells, keyed by ticker
+    pub exit_manager: ExitManager,
+    execution: ExecutionConfig,
+    peg_mode: PegMode,
+}
+
+impl LiveSession {
+    pub fn new(risk_config: &RiskConfig, execution: &ExecutionConfig) -> Self {
+        Self {
+            risk_manager: RiskManager::new(risk_config.clone()),
+            position_tracker: PositionTracker::new(),
+            pending_orders: PendingOrderRegistry::new(),
+            expected_fees: HashMap::new(),
+            ws_filled: HashMap::new(),
+            resting_entries: HashMap::new(),
+            exit_manager: ExitManager::new(),
+            execution: execution.clone(),
+            peg_mode: PegMode::from_config(&execution.peg_mode),
+        }
+    }
+
+    /// Take on the positions found on the exchange at startup.
+    pub fn reconcile(&mut self, positions: &[MarketPosition]) {
+        for pos in positions {
+            tracing::info!(
+                ticker = %pos.ticker,
+                position = pos.position,
+                "existing position"
+            );
+            if pos.position > 0 {
+                self.risk_manager
+                    .record_buy(&pos.ticker, "yes", pos.position as u32);
+                // Conservative defaults for reconciled positions:
+                // sell_target=99 means manual exit only (bid will never reach 99)
+                self.position_tracker.record_entry(
+                    pos.ticker.clone(),
+                    "yes",
+                    pos.position as u32,
+                    0,  // unknown entry price
+                    0,  // unknown entry cost
+                    99, // conservative sell target (manual exit only)
+                    Instant::now(),
+                    false,
+                );
+            }
+        }
+    }
+
+    /// Tickers with anything held, resting or in flight, which compaction
+    /// must keep.
+    pub fn held_tickers(&self) -> impl Iterator<Item = String> + '_ {
+        let positions = self
+            .position_tracker
+            .all_positions()
+            .into_iter()
+            .flat_map(|p| std::iter::once(p.ticker.clone()).chain(p.venue_ticker.clone()));
+        self.resting_entries
+            .keys()
+            .cloned()
+            .chain(positions)
+            .chain(self.pending_orders.tickers().map(str::to_string))
+    }
+
+    /// Kill switch: cancel every pending order, resting entry and resting
+    /// exit, keeping what already filled.
+    pub async fn cancel_all(&mut self, exec: &OrderExecutor, rest: &KalshiRest) {
+        let orders = self.pending_orders.drain();
+        if !orders.is_empty() {
+            tracing::error!(count = orders.len(), "cancelling pending orders");
+            for order in &orders {
+                if let Some(ref order_id) = order.order_id {
+                    if let Err(e) = exec.cancel_order(order_id).await {
+                        tracing::error!(order_id = %order_id, error = %e, "failed to cancel order");
+                    } else {
+                        tracing::info!(order_id = %order_id, "order cancelled");
+                    }
+                }
+            }
+        }
+
+        // Cancel resting entries, keeping what filled
+        let entries: Vec<_> = self.resting_entries.drain().map(|(_, e)| e).collect();
+        for entry in entries {
+            if let Err(e) = exec.cancel_order(&entry.order_id).await {
+                tracing::error!(order_id = %entry.order_id, error = %e, "failed to cancel resting entry");
+            }
+            self.close_resting_entry(entry, rest).await;
+        }
+        for exit in self.exit_manager.drain() {
+            if let Some(order_id) = exit.order_id {
+                if let Err(e) = exec.cancel_order(&order_id).await {
+                    tracing::error!(order_id = %order_id, error = %e, "failed to cancel resting exit");
+                }
+            }
+        }
+    }
+
+    /// Drop pending orders older than the order timeout, cancelling any
+    /// that reached the exchange.
+    pub async fn expire_pending(&mut self, exec: &OrderExecutor) {
+        let timeout = Duration::from_secs(self.execution.order_timeout_secs);
+        for order in self.pending_orders.expire_older_than(timeout) {
+            tracing::warn!(
+                ticker = %order.ticker,
+                age_secs = order.submitted_at.elapsed().as_secs(),
+                order_id = ?order.order_id,
+                "expired stale pending order"
+            );
+            // Attempt to cancel if we have an order ID
+            if let Some(ref order_id) = order.order_id {
+                if let Err(e) = exec.cancel_order(order_id).await {
+                    tracing::error!(order_id = %order_id, error = %e, "failed to cancel expired order");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Locale-aware number and currency formatting. Dollar amounts shown in the
+//! TUI header, the backtest report, doctor and the logs all go through
+//! `NumberFormat`, so separators and the currency symbol follow `[locale]`.
+
+use anyhow::Result;
+
+use crate::config::LocaleConfig;
+
+/// Separators and currency placement for one locale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub thousands: String,
+    pub decimal: String,
+    pub currency: String,
+    /// "1.234,56 $" rather than "$1,234.56".
+    pub currency_after: bool,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::preset("en-US").expect("en-US preset")
+    }
+}
+
+impl NumberFormat {
+    /// Built-in formats by locale name.
+    pub fn preset(name: &str) -> Option<Self> {
+        let (thousands, decimal, currency_after) = match name {
+            "en-US" | "en-GB" | "en-CA" | "en-AU" | "ja-JP" => (",", ".", false),
+            "de-DE" | "es-ES" | "it-IT" | "nl-NL" | "pt-BR" => (".", ",", true),
+            // Narrow no-break space, as CLDR uses for French grouping
+            "fr-FR" | "fr-CA" => ("\u{202f}", ",", true),
+            "de-CH" => ("'", ".", false),
+            _ => return None,
+        };
+        Some(Self {
+            thousands: thousands.to_string(),
+            decimal: decimal.to_string(),
+            currency: "$".to_string(),
+            currency_after,
+        })
+    }
+
+    /// The `[locale]` preset with any per-field overrides applied.
+    pub fn from_config(config: &LocaleConfig) -> Result<Self> {
+        let mut format = Self::preset(&config.name)
+            .ok_or_else(|| anyhow::anyhow!("unknown [locale] name '{}'", config.name))?;
+        if let Some(s) = &config.thousands_separator {
+            format.thousands = s.clone();
+        }
+        if let Some(s) = &config.decimal_separator {
+            format.decimal = s.clone();
+        }
+        if let Some(s) = &config.currency_symbol {
+            format.currency = s.clone();
+        }
+        if let Some(after) = config.currency_after {
+            format.currency_after = after;
+        }
+        Ok(format)
+    }
+
+    /// Whole number with thousands separators.
+    pub fn integer(&self, n: i64) -> String {
+        let sign = if n < 0 { "-" } else { "" };
+        format!("{}{}", sign, self.group(n.unsigned_abs()))
+    }
+
+    /// Cents as a currency amount, e.g. "$1,234.56" or "-$0.50".
+    pub fn money(&self, cents: i64) -> String {
+        let sign = if cents < 0 { "-" } else { "" };
+        format!("{}{}", sign, self.unsigned_money(cents.unsigned_abs()))
+    }
+
+    /// Like `money`, with a "+" on gains.
+    pub fn signed_money(&self, cents: i64) -> String {
+        let sign = if cents < 0 { "-" } else { "+" };
+        format!("{}{}", sign, self.unsigned_money(cents.unsigned_abs()))
+    }
+
+    fn unsigned_money(&self, cents: u64) -> String {
+        let amount = format!(
+            "{}{}{:02}",
+            self.group(cents / 100),
+            self.decimal,
+            cents % 100
+        );
+        if self.currency_after {
+            format!("{} {}", amount, self.currency)
+        } else {
+            format!("{}{}", self.currency, amount)
+        }
+    }
+
+    fn group(&self, n: u64) -> String {
+        let digits = n.to_string();
+        let mut out = String::with_capacity(digits.len() + digits.len() / 3 * self.thousands.len());
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                out.push_str(&self.thousands);
+            }
+            out.push(c);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_money_by_locale() {
+        let us = NumberFormat::default();
+        assert_eq!(us.money(123_456_789), "$1,234,567.89");
+        assert_eq!(us.money(-50), "-$0.50");
+        assert_eq!(us.money(100_000), "$1,000.00");
+        assert_eq!(us.signed_money(1_205), "+$12.05");
+        assert_eq!(us.signed_money(0), "+$0.00");
+        assert_eq!(us.integer(-1_234_567), "-1,234,567");
+        assert_eq!(us.integer(999), "999");
+
+        let de = NumberFormat::preset("de-DE").unwrap();
+        assert_eq!(de.money(123_456_789), "1.234.567,89 $");
+        assert_eq!(de.signed_money(-1_205), "-12,05 $");
+        let fr = NumberFormat::preset("fr-FR").unwrap();
+        assert_eq!(fr.money(123_456), "1\u{202f}234,56 $");
+        assert!(NumberFormat::preset("xx-XX").is_none());
+    }
+
+    #[test]
+    fn test_config_overrides_preset() {
+        let config = LocaleConfig {
+            name: "de-DE".to_string(),
+            thousands_separator: Some(" ".to_string()),
+            decimal_separator: None,
+            currency_symbol: Some("USD".to_string()),
+            currency_after: None,
+        };
+        let format = NumberFormat::from_config(&config).unwrap();
+        assert_eq!(format.money(123_456), "1 234,56 USD");
+
+        let unknown = LocaleConfig {
+            name: "xx-XX".to_string(),
+            ..LocaleConfig::default()
+        };
+        assert!(NumberFormat::from_config(&unknown).is_err());
+    }
+}
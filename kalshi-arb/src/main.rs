@@ -1,27 +1,62 @@
+mod backtest;
+mod book_replay;
+mod calibrate;
+mod cli;
 mod config;
+mod doctor;
 mod engine;
+mod eval_loop;
 mod execution;
 mod feed;
+mod headless;
+mod history;
+mod journal;
 mod kalshi;
+mod live;
+mod locale;
+mod network;
+mod notify;
 mod pipeline;
+mod polymarket;
+mod portfolio;
+mod profiling;
+mod recorder;
+mod session_replay;
+mod settlement_loop;
+mod settlements;
 mod tui;
+mod web;
 
 use anyhow::{Context, Result};
+use clap::Parser;
 use config::Config;
+use engine::exit_manager::{threshold_exit, ExitReason};
 use engine::fees::calculate_fee;
 use engine::matcher;
 use engine::momentum::MomentumScorer;
-use engine::OrderSide;
+use engine::season::SeasonStatus;
+use engine::strategy::SkipReason;
+use engine::win_prob::GameSegment;
 use feed::{
-    draftkings::DraftKingsFeed, scraped::ScrapedOddsFeed, the_odds_api::TheOddsApi, OddsFeed,
+    draftkings::DraftKingsFeed,
+    pinnacle::PinnacleFeed,
+    scraped::ScrapedOddsFeed,
+    the_odds_api::{SharedApiKey, TheOddsApi},
+    ws_odds::{StreamedOdds, WsOddsFeed},
+    OddsFeed, OddsStreamEvent, StreamingOddsFeed,
 };
+use futures_util::StreamExt;
 use kalshi::{auth::KalshiAuth, rest::KalshiRest, ws::KalshiWs};
-use std::collections::{HashMap, VecDeque};
+use network::Network;
+use polymarket::{auth::PolymarketAuth, rest::PolymarketRest, ws::PolymarketWs};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, watch};
 use tui::state::{AppState, MarketRow};
+use tracing::Instrument;
+use tracing_subscriber::prelude::*;
 
 /// Retry an async operation with exponential backoff.
 async fn retry_with_backoff<T, E, F, Fut>(
@@ -67,19 +102,96 @@ where
     }
 }
 
+/// Price slots per book side: one per cent from 0 to 100.
+const BOOK_SLOTS: usize = 101;
+
+/// One side of a depth book: contracts per cent in a fixed slot array, with
+/// the best (highest) level and the total kept current as levels change, so
+/// reading the top of book never scans.
+#[derive(Debug, Clone)]
+struct BookSide {
+    qty: [i64; BOOK_SLOTS],
+    best: Option<u32>,
+    total: i64,
+}
+
+impl BookSide {
+    fn new() -> Self {
+        Self {
+            qty: [0; BOOK_SLOTS],
+            best: None,
+            total: 0,
+        }
+    }
+
+    fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Set the contracts resting at `price`; zero or less removes the level.
+    /// Prices past 100 cents are not valid quotes and are dropped.
+    fn set(&mut self, price: u32, qty: i64) {
+        let Some(slot) = self.qty.get_mut(price as usize) else {
+            return;
+        };
+        let qty = qty.max(0);
+        self.total += qty - *slot;
+        *slot = qty;
+        if qty > 0 {
+            if self.best.is_none_or(|best| price > best) {
+                self.best = Some(price);
+            }
+        } else if self.best == Some(price) {
+            // Only emptying the best level needs a scan, down to the next one
+            self.best = (0..price).rev().find(|&p| self.qty[p as usize] > 0);
+        }
+    }
+
+    fn add(&mut self, price: u32, delta: i64) {
+        self.set(price, self.get(price) + delta);
+    }
+
+    fn get(&self, price: u32) -> i64 {
+        self.qty.get(price as usize).copied().unwrap_or(0)
+    }
+
+    /// Best price and the contracts resting at it.
+    fn best(&self) -> Option<(u32, i64)> {
+        self.best.map(|p| (p, self.qty[p as usize]))
+    }
+
+    fn best_price(&self) -> u32 {
+        self.best.unwrap_or(0)
+    }
+
+    /// Non-empty levels as (price, contracts), best first.
+    fn levels(&self) -> impl Iterator<Item = (u32, i64)> + '_ {
+        let top = self.best.map_or(0, |p| p as usize + 1);
+        self.qty[..top]
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|&(_, &q)| q > 0)
+            .map(|(p, &q)| (p as u32, q))
+    }
+}
+
 /// Per-ticker orderbook depth: price_cents -> quantity for each side.
 /// Supports snapshot replacement and incremental delta application.
 #[derive(Debug, Clone)]
 pub(crate) struct DepthBook {
-    yes: HashMap<u32, i64>,
-    no: HashMap<u32, i64>,
+    yes: BookSide,
+    no: BookSide,
+    /// Rolling depth for the per-ticker liquidity cap, sampled on evaluation.
+    liquidity: engine::liquidity::LiquidityTracker,
 }
 
 impl DepthBook {
     fn new() -> Self {
         Self {
-            yes: HashMap::new(),
-            no: HashMap::new(),
+            yes: BookSide::new(),
+            no: BookSide::new(),
+            liquidity: engine::liquidity::LiquidityTracker::new(),
         }
     }
 
@@ -90,36 +202,34 @@ impl DepthBook {
         self.no.clear();
 
         if !snap.yes_dollars.is_empty() || !snap.no_dollars.is_empty() {
-            for (price_str, qty) in &snap.yes_dollars {
-                if let Ok(d) = price_str.parse::<f64>() {
-                    let cents = (d * 100.0).round() as u32;
-                    if *qty > 0 {
-                        self.yes.insert(cents, *qty);
-                    }
-                }
+            for &(price, qty) in &snap.yes_dollars {
+                self.yes.set(price.0, qty);
             }
-            for (price_str, qty) in &snap.no_dollars {
-                if let Ok(d) = price_str.parse::<f64>() {
-                    let cents = (d * 100.0).round() as u32;
-                    if *qty > 0 {
-                        self.no.insert(cents, *qty);
-                    }
-                }
+            for &(price, qty) in &snap.no_dollars {
+                self.no.set(price.0, qty);
             }
         } else {
             for level in &snap.yes {
-                if level[1] > 0 {
-                    self.yes.insert(level[0] as u32, level[1]);
-                }
+                self.yes.set(level[0] as u32, level[1]);
             }
             for level in &snap.no {
-                if level[1] > 0 {
-                    self.no.insert(level[0] as u32, level[1]);
-                }
+                self.no.set(level[0] as u32, level[1]);
             }
         }
     }
 
+    /// Rebuild a book from recorded levels.
+    fn from_snapshot(snap: &journal::BookSnapshot) -> Self {
+        let mut book = Self::new();
+        for &(price, qty) in &snap.yes {
+            book.yes.set(price, qty);
+        }
+        for &(price, qty) in &snap.no {
+            book.no.set(price, qty);
+        }
+        book
+    }
+
     /// Apply an incremental delta at one price level.
     fn apply_delta(&mut self, side: &str, price_cents: u32, delta: i64) {
         let book = if side == "yes" {
@@ -127,34 +237,99 @@ impl DepthBook {
         } else {
             &mut self.no
         };
-        let qty = book.entry(price_cents).or_insert(0);
-        *qty += delta;
-        if *qty <= 0 {
-            book.remove(&price_cents);
-        }
-    }
-
-    /// Apply a delta using dollar-string price (e.g. "0.5500").
-    fn apply_delta_dollars(&mut self, side: &str, price_dollars: &str, delta: i64) {
-        if let Ok(d) = price_dollars.parse::<f64>() {
-            let cents = (d * 100.0).round() as u32;
-            self.apply_delta(side, cents, delta);
-        }
+        book.add(price_cents, delta);
     }
 
     /// Derive best bid/ask from current depth.
     /// Returns (yes_bid, yes_ask, no_bid, no_ask).
     fn best_bid_ask(&self) -> (u32, u32, u32, u32) {
-        let yes_bid = self.yes.keys().copied().max().unwrap_or(0);
-        let no_bid = self.no.keys().copied().max().unwrap_or(0);
+        let yes_bid = self.yes.best_price();
+        let no_bid = self.no.best_price();
         let yes_ask = if no_bid > 0 { 100 - no_bid } else { 0 };
         let no_ask = if yes_bid > 0 { 100 - yes_bid } else { 0 };
         (yes_bid, yes_ask, no_bid, no_ask)
     }
+
+    /// Best bid for `side` as others quote it, with `own` contracts of ours
+    /// resting at `own_price` taken out; 0 when no one else bids.
+    fn best_bid_excluding(&self, side: &str, own_price: u32, own: u32) -> u32 {
+        let book = if side == "yes" { &self.yes } else { &self.no };
+        book.levels()
+            .find(|&(price, qty)| price != own_price || qty > own as i64)
+            .map_or(0, |(price, _)| price)
+    }
+
+    /// Best bid for selling `side` ("yes"/"no"), 0 when there is none.
+    fn best_bid(&self, side: &str) -> u32 {
+        if side == "yes" {
+            self.yes.best_price()
+        } else {
+            self.no.best_price()
+        }
+    }
+
+    /// Best ask for buying `side` ("yes"/"no") and the contracts resting at it.
+    fn best_ask(&self, side: &str) -> Option<engine::cross_arb::Quote> {
+        let opposite = if side == "yes" { &self.no } else { &self.yes };
+        let (bid, size) = opposite.best()?;
+        Some(engine::cross_arb::Quote {
+            price: 100 - bid,
+            size,
+        })
+    }
+
+    /// Ask levels for buying `side` ("yes"/"no"), cheapest first, as
+    /// (price, contracts). Asks are the opposite side's bids at 100 - bid.
+    fn ask_levels(&self, side: &str) -> Vec<(u32, i64)> {
+        let opposite = if side == "yes" { &self.no } else { &self.yes };
+        opposite.levels().map(|(p, q)| (100 - p, q)).collect()
+    }
+
+    /// Contracts resting within `band_cents` of the YES bid and of the YES
+    /// ask (NO bids), as (bid_depth, ask_depth).
+    fn depth_near_touch(&self, band_cents: u32) -> (i64, i64) {
+        let near = |side: &BookSide| {
+            let best = side.best_price();
+            side.levels()
+                .take_while(|&(p, _)| p + band_cents >= best)
+                .map(|(_, q)| q)
+                .sum()
+        };
+        (near(&self.yes), near(&self.no))
+    }
+
+    /// Contracts in the best `levels` price levels of each side, averaged
+    /// over the two sides.
+    fn top_levels_depth(&self, levels: usize) -> i64 {
+        let top = |side: &BookSide| side.levels().take(levels).map(|(_, q)| q).sum::<i64>();
+        (top(&self.yes) + top(&self.no)) / 2
+    }
+
+    /// Sample the book into the rolling liquidity estimate and return the
+    /// resulting position cap, if one applies yet.
+    fn sample_liquidity(&mut self, config: &config::LiquidityCapConfig) -> Option<u32> {
+        let depth = self.top_levels_depth(config.levels);
+        self.liquidity.push(depth, config.window_size);
+        self.liquidity.position_cap(config)
+    }
+
+    /// Total resting contracts across both sides.
+    fn total_depth(&self) -> i64 {
+        self.yes.total + self.no.total
+    }
+
+    /// Copy all levels of both sides, best price first.
+    fn snapshot(&self) -> journal::BookSnapshot {
+        journal::BookSnapshot {
+            yes: self.yes.levels().collect(),
+            no: self.no.levels().collect(),
+        }
+    }
 }
 
-/// Live orderbook: ticker -> full depth book
-pub(crate) type LiveBook = Arc<Mutex<HashMap<String, DepthBook>>>;
+/// Live orderbook: ticker -> full depth book, sharded so the WS task, engine
+/// and display tick rarely contend
+pub(crate) type LiveBook = Arc<engine::book_store::BookStore<DepthBook>>;
 
 /// Extract last name from a full name (for MMA fighter matching).
 /// "Alex Volkanovski" -> "Volkanovski", "Benoit Saint-Denis" -> "Saint-Denis"
@@ -164,6 +339,225 @@ pub fn last_name(full_name: &str) -> &str {
         .map_or(full_name, |(_, last)| last)
 }
 
+/// Attach Polymarket moneyline markets to games already indexed from Kalshi.
+/// Returns the matched outcome token ids with their neg-risk flag.
+fn index_polymarket_events(
+    market_index: &mut matcher::MarketIndex,
+    sport: &str,
+    events: &[polymarket::types::GammaEvent],
+) -> Vec<(String, bool)> {
+    let mut tokens = Vec::new();
+    for event in events {
+        for m in event.markets.iter().filter(|m| m.is_moneyline(&event.slug)) {
+            let Some(date) = polymarket_game_date(&event.slug, m.game_start_time.as_deref()) else {
+                continue;
+            };
+            let Some(key) = matcher::generate_key(sport, &m.outcomes[0], &m.outcomes[1], date)
+            else {
+                continue;
+            };
+            let Some(game) = market_index.get_mut(&key) else {
+                continue;
+            };
+            let home = matcher::normalize_team(sport, &game.home_team);
+            let side = |i: usize| matcher::SideMarket {
+                ticker: m.clob_token_ids[i].clone(),
+                title: format!("{} ({})", m.question, m.outcomes[i]),
+                yes_bid: 0,
+                yes_ask: 0,
+                no_bid: 0,
+                no_ask: 0,
+                status: m.status().to_string(),
+                close_time: m.end_date.clone(),
+            };
+            let home_idx = if matcher::normalize_team(sport, &m.outcomes[0]) == home {
+                0
+            } else {
+                1
+            };
+            game.polymarket_home = Some(side(home_idx));
+            game.polymarket_away = Some(side(1 - home_idx));
+            tokens.extend(m.clob_token_ids.iter().map(|t| (t.clone(), m.neg_risk)));
+        }
+    }
+    tokens
+}
+
+/// Local game date: the `YYYY-MM-DD` suffix of the event slug, else the start
+/// time in US Eastern (Kalshi dates games the same way).
+fn polymarket_game_date(slug: &str, game_start_time: Option<&str>) -> Option<chrono::NaiveDate> {
+    if let Some(date) = slug
+        .len()
+        .checked_sub(10)
+        .and_then(|i| slug.get(i..))
+        .and_then(|tail| chrono::NaiveDate::parse_from_str(tail, "%Y-%m-%d").ok())
+    {
+        return Some(date);
+    }
+    // Gamma start times look like "2026-01-20 00:30:00+00"
+    let start = game_start_time?.replacen(' ', "T", 1);
+    let start = if start.ends_with("+00") {
+        format!("{}:00", start)
+    } else {
+        start
+    };
+    let eastern = chrono::FixedOffset::west_opt(5 * 3600)?;
+    chrono::DateTime::parse_from_rfc3339(&start)
+        .ok()
+        .map(|dt| dt.with_timezone(&eastern).date_naive())
+}
+
+/// Series fetched in parallel at startup; requests are paced by `KalshiRest`.
+const MARKET_INDEX_CONCURRENCY: usize = 4;
+
+/// Add one series' markets to the index. Returns the tickers indexed.
+fn index_series_markets(
+    market_index: &mut matcher::MarketIndex,
+    sport: &str,
+    markets: &[kalshi::types::Market],
+    segment: Option<GameSegment>,
+) -> Vec<String> {
+    let mut tickers = Vec::new();
+    for m in markets {
+        let parsed = if segment.is_some() {
+            matcher::parse_period_title(&m.title)
+        } else {
+            matcher::parse_kalshi_title(&m.title).or_else(|| matcher::parse_ufc_title(&m.title))
+        };
+        if let Some((away, home)) = parsed {
+            let date = matcher::parse_date_from_ticker(&m.event_ticker).or_else(|| {
+                m.event_start_time
+                    .as_deref()
+                    .or(m.expected_expiration_time.as_deref())
+                    .or(m.close_time.as_deref())
+                    .and_then(|ts| {
+                        chrono::DateTime::parse_from_rfc3339(ts)
+                            .ok()
+                            .map(|dt| dt.date_naive())
+                    })
+            });
+
+            if let Some(date) = date {
+                if let Some(key) = matcher::generate_key(sport, &away, &home, date) {
+                    let game = market_index
+                        .entry(key)
+                        .or_insert_with(|| matcher::IndexedGame {
+                            away_team: away.clone(),
+                            home_team: home.clone(),
+                            ..Default::default()
+                        });
+
+                    let side_market = matcher::SideMarket {
+                        ticker: m.ticker.clone(),
+                        title: m.title.clone(),
+                        yes_bid: kalshi::types::dollars_to_cents(m.yes_bid_dollars.as_deref()),
+                        yes_ask: kalshi::types::dollars_to_cents(m.yes_ask_dollars.as_deref()),
+                        no_bid: kalshi::types::dollars_to_cents(m.no_bid_dollars.as_deref()),
+                        no_ask: kalshi::types::dollars_to_cents(m.no_ask_dollars.as_deref()),
+                        status: m.status.clone(),
+                        close_time: m.close_time.clone(),
+                    };
+
+                    let winner_code = m.ticker.split('-').next_back().unwrap_or("");
+                    if let Some(segment) = segment {
+                        let period = game.period_mut(segment);
+                        match matcher::is_away_market(&m.ticker, &away, &home) {
+                            Some(true) => period.away = Some(side_market),
+                            Some(false) => period.home = Some(side_market),
+                            None => continue,
+                        }
+                    } else if winner_code.eq_ignore_ascii_case("TIE") {
+                        game.draw = Some(side_market);
+                    } else {
+                        match matcher::is_away_market(&m.ticker, &away, &home) {
+                            Some(true) => game.away = Some(side_market),
+                            Some(false) => game.home = Some(side_market),
+                            None => {
+                                if game.away.is_none() {
+                                    game.away = Some(side_market);
+                                } else {
+                                    game.home = Some(side_market);
+                                }
+                            }
+                        }
+                    }
+
+                    tickers.push(m.ticker.clone());
+                }
+            }
+        }
+    }
+    tickers
+}
+
+/// Market index shared by the engine and the background refresh task.
+type SharedIndex = Arc<tokio::sync::RwLock<matcher::MarketIndex>>;
+
+/// Re-fetch every series each `interval` and merge newly listed games into
+/// the shared index, subscribing the WS to tickers it hasn't seen. Games
+/// already indexed have their markets refreshed in place.
+async fn refresh_market_index(
+    rest: Arc<KalshiRest>,
+    market_index: SharedIndex,
+    sport_series: Vec<(String, String, Option<GameSegment>)>,
+    ws_command_tx: mpsc::UnboundedSender<kalshi::ws::WsCommand>,
+    feed_recorder: recorder::FeedRecorder,
+    state_tx: watch::Sender<AppState>,
+    interval: Duration,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // The first tick fires immediately; startup has just indexed everything
+    ticker.tick().await;
+    loop {
+        ticker.tick().await;
+        let mut new_games = 0;
+        let mut new_tickers = Vec::new();
+        for (sport, series, segment) in &sport_series {
+            let markets = match rest.get_markets_by_series(series).await {
+                Ok(markets) => markets,
+                Err(e) => {
+                    tracing::warn!(sport = sport.as_str(), series = series.as_str(), error = %e, "index refresh fetch failed");
+                    continue;
+                }
+            };
+            {
+                let mut index = market_index.write().await;
+                let known = engine::compaction::indexed_tickers(&index);
+                let games_before = index.len();
+                let tickers = index_series_markets(&mut index, sport, &markets, *segment);
+                new_games += index.len().saturating_sub(games_before);
+                new_tickers.extend(tickers.into_iter().filter(|t| !known.contains(t)));
+            }
+            if feed_recorder.is_enabled() {
+                feed_recorder.record(backtest::RecordedData::Markets {
+                    sport: sport.clone(),
+                    markets,
+                    segment: segment.map(|s| s.label()),
+                });
+            }
+        }
+        tracing::debug!(
+            games = new_games,
+            markets = new_tickers.len(),
+            "market index refreshed"
+        );
+        if !new_tickers.is_empty() {
+            state_tx.send_modify(|s| {
+                s.push_log(
+                    "INFO",
+                    format!(
+                        "Index refresh: {} new games, {} new markets",
+                        new_games,
+                        new_tickers.len()
+                    ),
+                );
+            });
+            let _ = ws_command_tx.send(kalshi::ws::WsCommand::Subscribe(new_tickers));
+        }
+    }
+}
+
 /// Toggle a sport pipeline's enabled state and persist to config.
 fn handle_toggle_sport(
     sport_pipelines: &mut [pipeline::SportPipeline],
@@ -177,11 +571,573 @@ fn handle_toggle_sport(
     }
 }
 
+/// Blacklist or whitelist `key` (an event or market ticker), or lift it, and
+/// save the filter to the config file so it survives a restart.
+fn handle_toggle_game_filter(
+    key: &str,
+    whitelist: bool,
+    config_path: &Path,
+    state_tx: &watch::Sender<AppState>,
+) {
+    let mut filter = config::GameFilterConfig::default();
+    state_tx.send_modify(|s| {
+        let (listed, list) = if whitelist {
+            (s.game_filter.toggle_whitelist(key), "whitelist")
+        } else {
+            (s.game_filter.toggle_blacklist(key), "blacklist")
+        };
+        let msg = if listed {
+            format!("{} added to {}", key, list)
+        } else {
+            format!("{} removed from {}", key, list)
+        };
+        tracing::info!(key, list, listed, "game filter changed");
+        s.push_log("INFO", msg);
+        filter = s.game_filter.to_config();
+    });
+    if let Err(e) = persist_game_filter(config_path, &filter) {
+        tracing::warn!(error = %e, "failed to save game filter");
+        state_tx.send_modify(|s| {
+            s.push_log("WARN", format!("Game filter not saved: {:#}", e));
+        });
+    }
+}
+
+/// Acknowledge open critical alerts so they are not escalated.
+fn handle_acknowledge_alerts(state_tx: &watch::Sender<AppState>) {
+    state_tx.send_modify(|s| {
+        let open = s.escalation.acknowledge_all();
+        if open > 0 {
+            tracing::info!(count = open, "critical alerts acknowledged");
+            s.push_log("WARN", format!("{} critical alert(s) acknowledged", open));
+        }
+    });
+}
+
+/// Re-read Kalshi and Odds API credentials and swap them into the shared
+/// auth and key. REST and WS clients, the live books and position tracking
+/// carry on untouched; the new Kalshi key is verified before reporting success.
+async fn handle_reload_credentials(
+    auth: &KalshiAuth,
+    rest: &KalshiRest,
+    odds_api_key: &SharedApiKey,
+    state_tx: &watch::Sender<AppState>,
+) {
+    let rotated = Config::reload_credentials().and_then(|creds| {
+        auth.rotate(creds.kalshi_api_key, &creds.kalshi_private_key_pem)?;
+        let mut rotated = vec!["Kalshi"];
+        if let Some(key) = creds.odds_api_key {
+            *odds_api_key.write().unwrap() = key;
+            rotated.push("Odds API");
+        }
+        Ok(rotated)
+    });
+    let rotated = match rotated {
+        Ok(rotated) => rotated,
+        Err(e) => {
+            tracing::warn!(error = %e, "credential reload failed");
+            state_tx.send_modify(|s| {
+                s.push_log("WARN", format!("Credential reload failed: {:#}", e));
+            });
+            return;
+        }
+    };
+    match rest.preflight_auth_check().await {
+        Ok(()) => {
+            tracing::info!(rotated = ?rotated, "credentials reloaded");
+            state_tx.send_modify(|s| {
+                s.push_log(
+                    "INFO",
+                    format!("Credentials reloaded: {}", rotated.join(", ")),
+                );
+            });
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "reloaded Kalshi credentials rejected");
+            state_tx.send_modify(|s| {
+                s.push_log(
+                    "ERROR",
+                    format!("Reloaded Kalshi credentials rejected: {:#}", e),
+                );
+            });
+        }
+    }
+}
+
+/// Attach a review note to a trade in the TUI and record it in the journal.
+fn handle_annotate_trade(
+    trade_id: u64,
+    note: String,
+    state_tx: &watch::Sender<AppState>,
+    journal: &journal::Journal,
+) {
+    let mut annotated = None;
+    state_tx.send_modify(|s| {
+        if let Some(t) = s.trades.iter_mut().find(|t| t.id == trade_id) {
+            t.note = note.clone();
+            annotated = Some(journal::JournalEntry::Note {
+                ts: chrono::Utc::now(),
+                trade_id,
+                trade_time: t.time.clone(),
+                ticker: t.ticker.clone(),
+                action: t.action.clone(),
+                price: t.price,
+                quantity: t.quantity,
+                note: note.clone(),
+            });
+        }
+    });
+    match annotated {
+        Some(entry) => journal.record(&entry),
+        None => tracing::warn!(trade_id, "trade note dropped: trade no longer in view"),
+    }
+}
+
+/// Start or stop recording a ticker's orderbook updates.
+fn handle_toggle_book_recording(
+    recorder: &book_replay::SharedBookRecorder,
+    ticker: &str,
+    state_tx: &watch::Sender<AppState>,
+) {
+    let Ok(mut rec) = recorder.lock() else {
+        return;
+    };
+    let recording = rec.toggle(ticker);
+    let active = rec.active_tickers();
+    drop(rec);
+    state_tx.send_modify(|s| {
+        s.recording_tickers = active;
+        let verb = if recording { "started" } else { "stopped" };
+        s.push_log("INFO", format!("Book recording {} for {}", verb, ticker));
+    });
+}
+
+/// Load a ticker's recorded frames into the replay view. An empty replay is
+/// still shown so the view can explain that nothing was recorded.
+fn handle_open_book_replay(
+    recorder: &book_replay::SharedBookRecorder,
+    ticker: &str,
+    state_tx: &watch::Sender<AppState>,
+) {
+    let replay = recorder
+        .lock()
+        .ok()
+        .and_then(|rec| rec.replay(ticker))
+        .unwrap_or_else(|| book_replay::BookReplay {
+            ticker: ticker.to_string(),
+            frames: Vec::new(),
+        });
+    state_tx.send_modify(|s| s.book_replay = Some(Arc::new(replay)));
+}
+
+/// Open the depth ladder for a ticker. A ticker with no book yet still opens
+/// the view, which fills in once the book arrives.
+fn handle_open_depth_ladder(
+    live_book: &LiveBook,
+    ticker: &str,
+    state_tx: &watch::Sender<AppState>,
+) {
+    let book = live_book
+        .read(ticker, DepthBook::snapshot)
+        .unwrap_or_default();
+    state_tx.send_modify(|s| {
+        s.depth_ladder = Some(tui::state::DepthLadder {
+            ticker: ticker.to_string(),
+            book,
+        })
+    });
+}
+
+/// Summarize today's P&L by sport and fair-value source into the portfolio
+/// view, from the journal plus open positions marked to the YES bid.
+fn handle_open_portfolio(journal: &journal::Journal, state_tx: &watch::Sender<AppState>) {
+    let sim = state_tx.borrow().sim_mode;
+    let since = chrono::Local::now()
+        .date_naive()
+        .and_hms_opt(0, 0, 0)
+        .and_then(|midnight| midnight.and_local_timezone(chrono::Local).earliest())
+        .map_or_else(chrono::Utc::now, |t| t.with_timezone(&chrono::Utc));
+    let read = journal.entries("order").and_then(|orders| {
+        let closed = journal.todays_closed_trades(sim)?;
+        Ok((portfolio::entry_orders(&orders, sim, since), closed))
+    });
+    let (orders, closed) = match read {
+        Ok(read) => read,
+        Err(e) => {
+            tracing::warn!(error = %e, "portfolio summary: journal read failed");
+            state_tx.send_modify(|s| s.push_log("WARN", format!("journal read failed: {:#}", e)));
+            (Vec::new(), Vec::new())
+        }
+    };
+    state_tx.send_modify(|s| {
+        let open: Vec<(String, i64)> = if sim {
+            s.sim_positions
+                .iter()
+                .map(|p| {
+                    let bid = s.live_book.get(&p.ticker).map_or(0, |&(yb, ..)| yb);
+                    let unrealized = if bid > 0 {
+                        (p.quantity * bid) as i64
+                            - (p.quantity * p.entry_price + p.entry_fee) as i64
+                    } else {
+                        0
+                    };
+                    (p.ticker.clone(), unrealized)
+                })
+                .collect()
+        } else {
+            s.positions
+                .iter()
+                .map(|p| (p.ticker.clone(), p.unrealized_pnl as i64))
+                .collect()
+        };
+        s.portfolio = Some(portfolio::summarize(&orders, &closed, &open));
+    });
+}
+
+/// Sample equity, balance plus open positions at the bid, into the curve and
+/// the journal when a sample is due. Skipped until there is a balance.
+fn record_equity(state_tx: &watch::Sender<AppState>, journal: &journal::Journal, now: Instant) {
+    let mut sample = None;
+    state_tx.send_if_modified(|s| {
+        if !s.equity.is_due(now) {
+            return false;
+        }
+        let balance = if s.sim_mode {
+            s.sim_balance_cents
+        } else {
+            s.balance_cents
+        };
+        let positions = s.positions_value_cents();
+        if balance + positions <= 0 {
+            return false;
+        }
+        s.equity.record(balance + positions, now);
+        sample = Some((s.sim_mode, balance, positions));
+        true
+    });
+    if let Some((sim, balance_cents, positions_cents)) = sample {
+        journal.record(&journal::JournalEntry::Equity {
+            ts: chrono::Utc::now(),
+            sim,
+            equity_cents: balance_cents + positions_cents,
+            balance_cents,
+            positions_cents,
+        });
+    }
+}
+
+/// Time-remaining columns in the win-prob view, before the late-game ones.
+const WIN_PROB_GRID_COLUMNS: usize = 12;
+
+/// Snapshot the score model of every score-feed sport into the win-prob view,
+/// taken on open so it follows runtime switches of `fair_value`.
+fn handle_open_win_prob(
+    sport_pipelines: &[pipeline::SportPipeline],
+    state_tx: &watch::Sender<AppState>,
+) {
+    let grids = sport_pipelines
+        .iter()
+        .filter_map(|p| p.win_prob_grid(WIN_PROB_GRID_COLUMNS))
+        .collect();
+    state_tx.send_modify(|s| s.win_prob_grids = grids);
+}
+
+/// Record a live order intent blocked after evaluation: relabel its market row
+/// with the reason and journal it alongside the signal trace.
+/// Start a profiling capture and write it out once `PROFILE_DURATION` has
+/// passed.
+fn handle_start_profile(profiler: &profiling::Profiler, state_tx: &watch::Sender<AppState>) {
+    if !profiler.start() {
+        state_tx.send_modify(|s| s.push_log("WARN", "Profile already running".to_string()));
+        return;
+    }
+    state_tx.send_modify(|s| {
+        s.profiling = true;
+        s.push_log(
+            "INFO",
+            format!(
+                "Profiling engine for {}s",
+                profiling::PROFILE_DURATION.as_secs()
+            ),
+        );
+    });
+    let profiler = profiler.clone();
+    let state_tx = state_tx.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(profiling::PROFILE_DURATION).await;
+        let folded = profiler.finish();
+        let written = profiling::write(
+            &folded,
+            Path::new(profiling::PROFILE_DIR),
+            chrono::Local::now(),
+            profiling::PROFILE_DURATION,
+        );
+        state_tx.send_modify(|s| {
+            s.profiling = false;
+            match written {
+                Ok(path) => s.push_log("INFO", format!("Profile written to {}", path.display())),
+                Err(e) => s.push_log("ERROR", format!("Profile failed: {:#}", e)),
+            }
+        });
+    });
+}
+
+/// A copy of taker entry `intent` routed to Polymarket, when the equivalent
+/// outcome token's ask is lower and deep enough for the full quantity.
+/// A lower price only widens the edge, so the signal itself still holds.
+fn route_to_better_venue(
+    intent: &pipeline::OrderIntent,
+    market_index: &matcher::MarketIndex,
+    books: &engine::book_store::BookStore<DepthBook>,
+) -> Option<pipeline::OrderIntent> {
+    if !intent.is_taker || intent.venue != matcher::Venue::Kalshi {
+        return None;
+    }
+    let token = matcher::polymarket_equivalent(market_index, &intent.ticker, &intent.side)?;
+    if token.status != "open" {
+        return None;
+    }
+    let (ask, book) = books
+        .read(&token.ticker, |book| {
+            let (_, ask, _, _) = book.best_bid_ask();
+            // Asks are stored as NO bids at 100 - ask
+            let ask_size = book.no.get(100 - ask);
+            if ask == 0 || ask >= intent.price || ask_size < intent.quantity as i64 {
+                return None;
+            }
+            Some((ask, book.snapshot()))
+        })
+        .flatten()?;
+    let mut routed = intent.clone();
+    routed.ticker = token.ticker.clone();
+    routed.side = "yes".to_string();
+    routed.edge += (intent.price - ask) as i32;
+    routed.price = ask;
+    routed.entry_cost_cents = ask * intent.quantity;
+    routed.book = Some(book);
+    routed.venue = matcher::Venue::Polymarket;
+    Some(routed)
+}
+
+/// How often edge-persistence stats and tuned thresholds are refreshed.
+const EDGE_TUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How often the memory gauge is refreshed and checked against the limit.
+const MEMORY_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Drop settled and expired games from the index, then every book and
+/// tracker keyed by their tickers. Tickers in `held` are never dropped.
+/// Returns the report and the tickers no longer tracked, to unsubscribe.
+fn compact_engine_state(
+    market_index: &mut matcher::MarketIndex,
+    live_book: &LiveBook,
+    edge_persistence: &Mutex<engine::edge_persistence::EdgePersistence>,
+    pipelines: &mut [pipeline::SportPipeline],
+    held: &HashSet<String>,
+    retention_days: u32,
+) -> (engine::compaction::CompactionReport, Vec<String>) {
+    let today = chrono::Utc::now().date_naive();
+    let before = engine::compaction::indexed_tickers(market_index);
+    let games = engine::compaction::compact_index(market_index, today, retention_days, held);
+    let mut keep = engine::compaction::indexed_tickers(market_index);
+    keep.extend(held.iter().cloned());
+    let mut dropped: Vec<String> = before.difference(&keep).cloned().collect();
+    dropped.sort();
+    let books = live_book.retain(|ticker, _| keep.contains(ticker));
+    let mut trackers: usize = pipelines
+        .iter_mut()
+        .map(|p| p.compact(market_index, &keep))
+        .sum();
+    if let Ok(mut ep) = edge_persistence.lock() {
+        trackers += ep.retain_tickers(&keep);
+    }
+    (
+        engine::compaction::CompactionReport {
+            games,
+            books,
+            trackers,
+        },
+        dropped,
+    )
+}
+
+/// Open or refresh edge-persistence tracking for this tick's evaluated rows.
+fn observe_edges(
+    sport: &str,
+    rows: &HashMap<String, MarketRow>,
+    live_book: &LiveBook,
+    edge_persistence: &Mutex<engine::edge_persistence::EdgePersistence>,
+) {
+    let asks: Vec<(&str, u32, u32, u32)> = rows
+        .values()
+        .filter(|r| {
+            !matches!(
+                r.skip_reason,
+                Some(SkipReason::Stale | SkipReason::FvOutOfBounds | SkipReason::NewsHalt)
+            )
+        })
+        .filter_map(|r| {
+            let (_, yes_ask, _, no_ask) = live_book.read(&r.ticker, DepthBook::best_bid_ask)?;
+            Some((r.ticker.as_str(), r.fair_value, yes_ask, no_ask))
+        })
+        .collect();
+    let now = Instant::now();
+    if let Ok(mut ep) = edge_persistence.lock() {
+        for (ticker, fair, yes_ask, no_ask) in asks {
+            ep.observe(sport, ticker, fair, yes_ask, no_ask, now);
+        }
+    }
+}
+
+/// Publish per-sport edge persistence, log each histogram, and apply tuned
+/// taker thresholds when auto-tuning is on.
+fn tune_taker_thresholds(
+    ep: &engine::edge_persistence::EdgePersistence,
+    sport_pipelines: &mut [pipeline::SportPipeline],
+    cfg: &config::EdgePersistenceConfig,
+    state_tx: &watch::Sender<AppState>,
+) {
+    let latency_ms = ep.execution_latency_ms(cfg.assumed_latency_ms);
+    for p in sport_pipelines.iter_mut() {
+        let Some(median_ms) = ep.median_lifetime_ms(&p.key) else {
+            continue;
+        };
+        let histogram = ep.histogram(&p.key);
+        let samples = histogram.iter().sum::<u64>() as usize;
+        let tuned = if cfg.auto_tune {
+            ep.tuned_threshold(&p.key, latency_ms, cfg.survival_target, cfg.min_samples)
+        } else {
+            None
+        };
+        let buckets: Vec<String> = engine::edge_persistence::BUCKET_UPPER_MS
+            .iter()
+            .map(|ms| format!("<{}ms", ms))
+            .chain(std::iter::once("more".to_string()))
+            .zip(histogram)
+            .map(|(label, n)| format!("{}:{}", label, n))
+            .collect();
+        tracing::info!(
+            sport = %p.key,
+            samples,
+            median_ms,
+            latency_ms,
+            histogram = %buckets.join(" "),
+            "edge persistence"
+        );
+        let changed = tuned != p.taker_threshold_floor;
+        p.taker_threshold_floor = tuned;
+        let stat = tui::state::EdgePersistenceStat {
+            samples,
+            median_ms,
+            tuned_threshold: tuned,
+        };
+        state_tx.send_modify(|s| {
+            s.edge_persistence.insert(p.key.clone(), stat);
+            if changed {
+                let msg = match tuned {
+                    Some(t) => format!(
+                        "{} taker threshold tuned to {}c (edges outlive {}ms, {} samples)",
+                        p.label, t, latency_ms, samples
+                    ),
+                    None => format!("{} taker threshold back to configured value", p.label),
+                };
+                s.push_log("INFO", msg);
+            }
+        });
+    }
+}
+
+/// Report a cross-venue arbitrage pair in the log panel.
+fn log_arb_signal(signal: &engine::cross_arb::ArbSignal, state_tx: &watch::Sender<AppState>) {
+    let leg = |l: &engine::cross_arb::ArbLeg| {
+        let venue = match l.venue {
+            matcher::Venue::Kalshi => "Kalshi",
+            matcher::Venue::Polymarket => "Polymarket",
+        };
+        format!(
+            "{} {} {} @ {}c",
+            venue,
+            l.side.to_uppercase(),
+            l.ticker,
+            l.price
+        )
+    };
+    tracing::info!(
+        team = %signal.team,
+        yes = %signal.yes.ticker,
+        no = %signal.no.ticker,
+        quantity = signal.quantity,
+        profit_cents = signal.profit_cents,
+        "cross-venue arbitrage"
+    );
+    state_tx.send_modify(|s| {
+        s.push_log(
+            "ARB",
+            format!(
+                "{} {}x: {} + {}, cost {}c, {:+}c locked",
+                signal.team,
+                signal.quantity,
+                leg(&signal.yes),
+                leg(&signal.no),
+                signal.cost_cents(),
+                signal.profit_cents
+            ),
+        );
+    });
+}
+
+/// Run the live-mode order-path preflight on the most liquid book.
+///
+/// Returns `None` when no book is deep enough for a safe 1c resting bid yet
+/// (retry next cycle), otherwise whether every preflight step passed.
+async fn run_order_preflight(
+    exec: &execution::OrderExecutor,
+    live_book: &LiveBook,
+    state_tx: &watch::Sender<AppState>,
+) -> Option<bool> {
+    let mut books = Vec::new();
+    live_book.for_each(|t, b| {
+        books.push((t.to_string(), b.best_bid_ask().0, b.total_depth()));
+    });
+    let ticker = execution::preflight::pick_preflight_ticker(
+        books
+            .iter()
+            .map(|(t, bid, depth)| (t.as_str(), *bid, *depth)),
+    )?
+    .to_string();
+
+    let report = execution::preflight::run_preflight(exec, &ticker).await;
+    let passed = report.passed();
+    let summary = match report.failure() {
+        None => format!(
+            "PREFLIGHT OK {} 1x @{}c (submit {}ms, cancel {}ms)",
+            report.ticker,
+            report.price,
+            report.latency_ms("submit").unwrap_or(0),
+            report.latency_ms("cancel").unwrap_or(0),
+        ),
+        Some(step) => format!(
+            "PREFLIGHT FAILED at {} on {}: {} - trading paused",
+            step.name, report.ticker, step.detail
+        ),
+    };
+    if passed {
+        tracing::info!(ticker = %report.ticker, "order-path preflight passed");
+    } else {
+        tracing::error!(ticker = %report.ticker, "{}", summary);
+    }
+    state_tx.send_modify(|s| {
+        s.push_log(if passed { "INFO" } else { "ERROR" }, summary);
+        s.preflight = Some(report);
+    });
+    Some(passed)
+}
+
 /// Fetch diagnostics for all enabled odds-feed pipelines and update TUI state.
 async fn handle_fetch_diagnostic(
     sport_pipelines: &mut [pipeline::SportPipeline],
-    odds_sources: &mut HashMap<String, Box<dyn OddsFeed>>,
-    api_request_times: &mut VecDeque<Instant>,
+    odds_feeds: &feed::supervisor::FeedSupervisor,
+    api_request_times: &Mutex<VecDeque<Instant>>,
     state_tx: &watch::Sender<AppState>,
     market_index: &engine::matcher::MarketIndex,
 ) {
@@ -190,26 +1146,23 @@ async fn handle_fetch_diagnostic(
         if !pipe.enabled {
             continue;
         }
-        if let Some(source) = odds_sources.get_mut(&pipe.odds_source) {
-            match source.fetch_odds(&pipe.key).await {
-                Ok(updates) => {
-                    if let Some(quota) = source.last_quota() {
-                        api_request_times.push_back(Instant::now());
-                        let one_hour_ago = Instant::now() - Duration::from_secs(3600);
-                        while api_request_times.front().is_some_and(|&t| t < one_hour_ago) {
-                            api_request_times.pop_front();
-                        }
-                        let burn_rate = api_request_times.len() as f64;
-                        state_tx.send_modify(|s| {
-                            s.api_requests_used = quota.requests_used;
-                            s.api_requests_remaining = quota.requests_remaining;
-                            s.api_burn_rate = burn_rate;
-                            s.api_hours_remaining = if burn_rate > 0.0 {
-                                quota.requests_remaining as f64 / burn_rate
-                            } else {
-                                f64::INFINITY
-                            };
-                        });
+        if odds_feeds.contains(&pipe.odds_source) {
+            match odds_feeds
+                .fetch(&pipe.odds_source, &pipe.key, Instant::now())
+                .await
+            {
+                Ok(None) => {
+                    tracing::debug!(sport = pipe.key.as_str(), "diagnostic fetch rate limited");
+                }
+                Ok(Some(feed::supervisor::Fetched { updates, quota, .. })) => {
+                    if let Some(quota) = quota {
+                        pipeline::record_api_quota(
+                            &quota,
+                            &pipe.key,
+                            feed::usage::UsagePurpose::Diagnostic,
+                            api_request_times,
+                            state_tx,
+                        );
                     }
                     pipe.commence_times = updates.iter().map(|u| u.commence_time.clone()).collect();
 
@@ -220,6 +1173,7 @@ async fn handle_fetch_diagnostic(
                         &pipe.key,
                         market_index,
                         &source_name,
+                        pipe.fuzzy_min_score,
                     ));
                 }
                 Err(e) => {
@@ -234,8 +1188,7 @@ async fn handle_fetch_diagnostic(
         if !pipe.enabled {
             continue;
         }
-        if let pipeline::FairValueSource::ScoreFeed { ref mut poller, .. } = pipe.fair_value_source
-        {
+        if let Some(poller) = pipe.score_poller.as_mut() {
             match poller.fetch().await {
                 Ok(updates) => {
                     // Determine source name based on which URL was used
@@ -252,6 +1205,7 @@ async fn handle_fetch_diagnostic(
                         &pipe.key,
                         market_index,
                         source_name,
+                        pipe.fuzzy_min_score,
                     ));
                 }
                 Err(e) => {
@@ -268,10 +1222,358 @@ async fn handle_fetch_diagnostic(
 }
 
 // Helper function to format source names
+/// Give up reconciling an order's fees after this long without a complete fill.
+const FEE_RECONCILE_MAX_AGE_SECS: u64 = 600;
+
+/// Confirm fills of submitted live orders and reconcile their expected fees
+/// against Kalshi fills data.
+/// Orders are dropped once fully filled or after `FEE_RECONCILE_MAX_AGE_SECS`.
+/// While the Kalshi WS is up, an order is only looked up over REST once the WS
+/// fill channel has reported it complete (or it has gone stale).
+async fn reconcile_fees(
+    rest: &KalshiRest,
+    expected_fees: &mut HashMap<String, engine::ExpectedFee>,
+    ws_filled: &mut HashMap<String, u32>,
+    state_tx: &watch::Sender<AppState>,
+) {
+    let ws_connected = state_tx.borrow().kalshi_ws_connected;
+    let order_ids: Vec<String> = expected_fees.keys().cloned().collect();
+    for order_id in order_ids {
+        let ws_complete = expected_fees
+            .get(&order_id)
+            .is_some_and(|e| ws_filled.get(&order_id).is_some_and(|&n| n >= e.quantity));
+        let stale = expected_fees
+            .get(&order_id)
+            .is_some_and(|e| e.submitted_at.elapsed().as_secs() > FEE_RECONCILE_MAX_AGE_SECS);
+        if ws_connected && !ws_complete && !stale {
+            continue;
+        }
+        let fills = match rest.get_fills_for_order(&order_id).await {
+            Ok(fills) => fills,
+            Err(e) => {
+                tracing::debug!(order_id = %order_id, error = %e, "fill lookup failed");
+                continue;
+            }
+        };
+        let Some(expected) = expected_fees.get(&order_id) else {
+            continue;
+        };
+        let filled: u32 = fills.iter().map(|f| f.count).sum();
+        if filled < expected.quantity && !stale {
+            continue;
+        }
+        let Some(expected) = expected_fees.remove(&order_id) else {
+            continue;
+        };
+        ws_filled.remove(&order_id);
+        let status = if filled >= expected.quantity {
+            // Already confirmed when the WS fill arrived
+            (!ws_complete).then(|| {
+                (
+                    "ORDER",
+                    format!("FILLED {}x {} ({})", filled, expected.ticker, order_id),
+                )
+            })
+        } else {
+            Some((
+                "WARN",
+                format!(
+                    "UNFILLED {}/{} {} after {}s ({})",
+                    filled,
+                    expected.quantity,
+                    expected.ticker,
+                    FEE_RECONCILE_MAX_AGE_SECS,
+                    order_id
+                ),
+            ))
+        };
+        if let Some((level, msg)) = status {
+            state_tx.send_modify(|s| s.push_log(level, msg));
+        }
+        if filled == 0 || fills.iter().any(|f| f.fee_cost.is_none()) {
+            continue;
+        }
+
+        let realized_cents: u32 = fills
+            .iter()
+            .map(|f| kalshi::types::dollars_to_cents(f.fee_cost.as_deref()))
+            .sum();
+        let sample = engine::FeeSample {
+            ticker: expected.ticker.clone(),
+            expected_cents: expected.expected_cents(filled),
+            realized_cents,
+            expected_taker: expected.is_taker,
+            realized_taker: Some(fills.iter().any(|f| f.is_taker)),
+        };
+        tracing::info!(
+            ticker = %sample.ticker,
+            order_id = %order_id,
+            filled,
+            expected = sample.expected_cents,
+            realized = sample.realized_cents,
+            "fee reconciled"
+        );
+        state_tx.send_modify(|s| {
+            if let Some(msg) = s.fee_tracker.record(&sample) {
+                tracing::warn!("{}", msg);
+                s.escalation.raise(
+                    engine::AlertKind::ReconciliationMismatch,
+                    msg.clone(),
+                    Instant::now(),
+                );
+                s.push_log("WARN", msg);
+            }
+        });
+    }
+}
+
+/// Exposure held by live positions, for the caps in `evaluate_matched_market`.
+/// Reconciled positions with no known cost are charged their full payout.
+fn live_exposure(
+    position_tracker: &engine::PositionTracker,
+    market_index: &matcher::MarketIndex,
+    pipelines: &[pipeline::SportPipeline],
+) -> engine::risk::Exposure {
+    let mut exposure = engine::risk::Exposure::default();
+    for p in position_tracker.all_positions() {
+        let sport = market_index
+            .iter()
+            .find(|(_, game)| {
+                [game.home.as_ref(), game.away.as_ref(), game.draw.as_ref()]
+                    .into_iter()
+                    .flatten()
+                    .any(|side| side.ticker == p.ticker)
+            })
+            .and_then(|(key, _)| {
+                pipelines
+                    .iter()
+                    .find(|pl| pipeline::index_sport_key(&pl.key) == key.sport)
+            })
+            .map_or("", |pl| pl.key.as_str());
+        let cost = if p.entry_cost_cents > 0 {
+            p.entry_cost_cents
+        } else {
+            p.quantity * 100
+        };
+        exposure.add(&p.ticker, sport, &p.side, cost as u64, p.quantity);
+    }
+    exposure
+}
+
+/// Live positions for the TUI, each marked to the current bid for its side
+/// on the venue it is held on.
+fn live_position_rows(
+    position_tracker: &engine::PositionTracker,
+    live_book: &LiveBook,
+) -> Vec<tui::state::PositionRow> {
+    position_tracker
+        .all_positions()
+        .into_iter()
+        .map(|p| {
+            let (leg, side) = p.order_leg();
+            let bid = live_book.read(leg, |d| d.best_bid(side)).unwrap_or(0);
+            let unrealized_pnl = if bid > 0 {
+                (p.quantity * bid) as i32 - p.entry_cost_cents as i32
+            } else {
+                0
+            };
+            tui::state::PositionRow {
+                ticker: p.ticker.clone(),
+                quantity: p.quantity,
+                entry_price: p.entry_price,
+                sell_price: p.sell_target,
+                entry_fee: p
+                    .entry_cost_cents
+                    .saturating_sub(p.quantity * p.entry_price),
+                unrealized_pnl,
+            }
+        })
+        .collect()
+}
+
+/// Best bid and ask a manual order is priced against: the bid for the side
+/// a sell would close (the held position's, else the order's), and the ask
+/// for the order's side.
+fn manual_order_touch(
+    depth: &DepthBook,
+    order: &tui::ManualOrder,
+    held_side: Option<&str>,
+) -> (u32, u32) {
+    let bid = depth.best_bid(held_side.unwrap_or(&order.side));
+    let ask = depth.best_ask(&order.side).map_or(0, |q| q.price);
+    (bid, ask)
+}
+
+/// Fill a manual order against the sim book. Only marketable orders fill,
+/// at the touch: buys at `ask` (the best ask for the order's side), sells
+/// of the whole held position at `bid` (the best bid for its side).
+fn fill_sim_manual_order(s: &mut AppState, order: &tui::ManualOrder, bid: u32, ask: u32) {
+    let now = chrono::Local::now().format("%H:%M:%S").to_string();
+    if order.is_buy {
+        if ask == 0 || order.price < ask {
+            s.push_log(
+                "ORDER",
+                format!(
+                    "SIM MANUAL BUY {} @ {}c not filled: below the {}c ask",
+                    order.ticker, order.price, ask
+                ),
+            );
+            return;
+        }
+        if s.sim_positions
+            .iter()
+            .any(|p| p.ticker == order.ticker && p.side != order.side)
+        {
+            s.push_log(
+                "ORDER",
+                format!(
+                    "SIM MANUAL BUY {} {} blocked: holding the other side",
+                    order.ticker,
+                    order.side.to_uppercase()
+                ),
+            );
+            return;
+        }
+        let fee = calculate_fee(ask, order.quantity, true);
+        let cost = order.quantity * ask + fee;
+        if s.sim_balance_cents < cost as i64 {
+            s.push_log(
+                "ORDER",
+                format!(
+                    "SIM MANUAL BUY {} not filled: insufficient balance",
+                    order.ticker
+                ),
+            );
+            return;
+        }
+        let sell_target =
+            engine::fees::break_even_sell_price(cost, order.quantity, false).unwrap_or(99);
+        s.sim_balance_cents -= cost as i64;
+        match s
+            .sim_positions
+            .iter_mut()
+            .find(|p| p.ticker == order.ticker)
+        {
+            Some(p) => {
+                p.add_lot(order.quantity, ask, fee);
+                p.sell_price = p.sell_price.max(sell_target);
+            }
+            None => s.sim_positions.push(tui::state::SimPosition {
+                ticker: order.ticker.clone(),
+                side: order.side.clone(),
+                quantity: order.quantity,
+                entry_price: ask,
+                sell_price: sell_target,
+                entry_fee: fee,
+                filled_at: Instant::now(),
+                signal_ask: ask,
+                trace: None,
+            }),
+        }
+        s.push_trade(tui::state::TradeRow {
+            id: 0,
+            time: now,
+            action: "BUY".to_string(),
+            ticker: order.ticker.clone(),
+            price: ask,
+            quantity: order.quantity,
+            order_type: "SIM".to_string(),
+            pnl: None,
+            slippage: None,
+            source: "manual".to_string(),
+            fair_value_basis: String::new(),
+            phase: String::new(),
+            note: String::new(),
+        });
+        s.push_log(
+            "TRADE",
+            format!(
+                "SIM MANUAL BUY {}x {} {} @ {}c, sell target {}c",
+                order.quantity,
+                order.ticker,
+                order.side.to_uppercase(),
+                ask,
+                sell_target
+            ),
+        );
+        return;
+    }
+
+    let Some(idx) = s
+        .sim_positions
+        .iter()
+        .position(|p| p.ticker == order.ticker)
+    else {
+        s.push_log(
+            "ORDER",
+            format!("SIM MANUAL SELL {}: no position held", order.ticker),
+        );
+        return;
+    };
+    if bid == 0 || order.price > bid {
+        s.push_log(
+            "ORDER",
+            format!(
+                "SIM MANUAL SELL {} @ {}c not filled: above the {}c bid",
+                order.ticker, order.price, bid
+            ),
+        );
+        return;
+    }
+    let pos = s.sim_positions.remove(idx);
+    let exit_revenue = (pos.quantity * bid) as i64;
+    let exit_fee = calculate_fee(bid, pos.quantity, true) as i64;
+    let entry_cost = (pos.quantity * pos.entry_price) as i64 + pos.entry_fee as i64;
+    let pnl = (exit_revenue - exit_fee) - entry_cost;
+    s.sim_balance_cents += exit_revenue - exit_fee;
+    s.realized_pnl_cents += pnl;
+    s.record_realized_edge(&pos.ticker, pnl);
+    s.total_trades += 1;
+    if pnl > 0 {
+        s.winning_trades += 1;
+    }
+    s.push_trade(tui::state::TradeRow {
+        id: 0,
+        time: now,
+        action: "MANUAL SELL".to_string(),
+        ticker: pos.ticker.clone(),
+        price: bid,
+        quantity: pos.quantity,
+        order_type: "SIM".to_string(),
+        pnl: Some(pnl as i32),
+        slippage: None,
+        source: "manual".to_string(),
+        fair_value_basis: String::new(),
+        phase: String::new(),
+        note: String::new(),
+    });
+    s.push_log(
+        "TRADE",
+        format!(
+            "SIM MANUAL SELL {}x {} @ {}c, P&L: {:+}c",
+            pos.quantity, pos.ticker, bid, pnl
+        ),
+    );
+}
+
+/// Odds source health per enabled sport, for the diagnostic view.
+fn odds_health(
+    pipelines: &[pipeline::SportPipeline],
+    odds_sources: &config::OddsSourcesConfig,
+) -> HashMap<String, feed::health::HealthSummary> {
+    let now = Instant::now();
+    pipelines
+        .iter()
+        .filter(|p| p.enabled)
+        .filter_map(|p| Some((p.key.clone(), p.odds_health(odds_sources, now)?)))
+        .collect()
+}
+
 fn format_source_name(source_key: &str) -> String {
     match source_key {
         "the-odds-api" => "TheOddsAPI".to_string(),
         "draftkings" => "DraftKings".to_string(),
+        "pinnacle" => "Pinnacle".to_string(),
         "scraped-bovada" => "Bovada".to_string(),
         other => other.to_string(),
     }
@@ -301,6 +1603,27 @@ fn persist_sport_enabled(config_path: &Path, sport_key: &str, enabled: bool) {
     }
 }
 
+/// Write `[game_filter]` to the config file.
+fn persist_game_filter(config_path: &Path, filter: &config::GameFilterConfig) -> Result<()> {
+    let content = std::fs::read_to_string(config_path)?;
+    let mut doc: toml::Value = content.parse()?;
+    let strings = |list: &[String]| {
+        toml::Value::Array(list.iter().cloned().map(toml::Value::String).collect())
+    };
+    let mut table = toml::map::Map::new();
+    table.insert("blacklist".to_string(), strings(&filter.blacklist));
+    table.insert("whitelist".to_string(), strings(&filter.whitelist));
+    table.insert(
+        "whitelist_only".to_string(),
+        toml::Value::Boolean(filter.whitelist_only),
+    );
+    doc.as_table_mut()
+        .context("config is not a table")?
+        .insert("game_filter".to_string(), toml::Value::Table(table));
+    std::fs::write(config_path, toml::to_string_pretty(&doc)?)?;
+    Ok(())
+}
+
 /// Apply a runtime config edit to in-memory pipeline state so changes take
 /// effect immediately without restarting.
 fn apply_config_update(
@@ -460,13 +1783,18 @@ fn apply_config_update(
                     risk_config.max_concurrent_markets = v;
                 }
             }
+            "min_cash_reserve_cents" => {
+                if let Ok(v) = value.parse() {
+                    risk_config.min_cash_reserve_cents = v;
+                }
+            }
             _ => {}
         },
         // Per-sport fair_value
         ["sports", sport_key, "fair_value"] => {
             if let Some(pipe) = sport_pipelines.iter_mut().find(|p| p.key == *sport_key) {
-                pipe.rebuild_fair_value_source(value);
-                // Note: odds_source is automatically set in rebuild_fair_value_source
+                pipe.rebuild_fair_value_model(value);
+                // Note: odds_source is automatically set in rebuild_fair_value_model
                 // The caller handles persisting the fair_value change to config
             }
         }
@@ -487,6 +1815,11 @@ fn apply_config_update(
                     sim_config.validate_fair_value = v;
                 }
             }
+            "fv_disagreement_threshold_cents" => {
+                if let Ok(v) = value.parse() {
+                    sim_config.fv_disagreement_threshold_cents = v;
+                }
+            }
             _ => {}
         },
         _ => {}
@@ -495,15 +1828,67 @@ fn apply_config_update(
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let cli = cli::Cli::parse();
     let log_file = std::fs::File::create("kalshi-arb.log")?;
-    tracing_subscriber::fmt()
-        .with_env_filter("kalshi_arb=warn")
-        .with_writer(log_file)
+    let profiler = profiling::Profiler::new();
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(log_file)
+                .with_filter(tracing_subscriber::EnvFilter::new(cli.log_level.filter())),
+        )
+        .with(profiler.layer())
         .init();
 
-    let sim_mode = std::env::args().any(|arg| arg == "--simulate");
+    let sim_mode = cli.simulate;
+    let ignore_season = cli.ignore_season;
+    let load_settlements_months = cli.load_settlements_months();
+    let backtest_dir = cli.backtest.clone();
+    let record = cli.record;
+    let download = match &cli.command {
+        Some(cli::Command::Download(download)) => Some(download.args()?),
+        _ => None,
+    };
+    let profile = cli.profile.clone();
+    let headless = cli.headless_sink();
 
-    let config = Config::load(Path::new("config.toml"))?;
+    // One-shot tool: check every integration without starting the engine
+    if matches!(cli.command, Some(cli::Command::Doctor)) {
+        println!();
+        println!("  Kalshi Arb Doctor");
+        println!("  =================");
+        println!();
+        return doctor::run(&cli.config).await;
+    }
+
+    let mut config = Config::load_profile(&cli.config, profile.as_deref())?;
+    cli.apply_sports(&mut config)?;
+    let network = Network::from_config(&config.network).context("invalid [network] config")?;
+    let number_format = locale::NumberFormat::from_config(&config.locale)?;
+
+    // One-shot tool: replay a recording offline, no credentials needed
+    if let Some(dir) = backtest_dir {
+        println!();
+        println!("  Kalshi Arb Backtest");
+        println!("  ===================");
+        println!();
+        backtest::run(&config, &dir).await?;
+        return Ok(());
+    }
+
+    // One-shot tool: fit win-prob parameters to settled games in the journal
+    if cli.calibrate {
+        println!();
+        println!("  Kalshi Arb Calibration");
+        println!("  ======================");
+        println!();
+        return calibrate::run(&config, &cli.config);
+    }
+
+    // One-shot tool: play a journaled session back into the TUI
+    if let Some(path) = &cli.replay {
+        return session_replay::run(path, cli.replay_speed, cli.replay_date, number_format).await;
+    }
 
     // Load saved keys from .env (real env vars take precedence)
     Config::load_env_file();
@@ -517,7 +1902,14 @@ async fn main() -> Result<()> {
     println!();
 
     if sim_mode {
-        println!("  ** SIMULATION MODE ** ($1000 virtual balance)");
+        println!(
+            "  ** SIMULATION MODE ** ({} virtual balance)",
+            number_format.money(AppState::new().sim_balance_cents)
+        );
+        println!();
+    }
+    if let Some(name) = &profile {
+        println!("  Config profile: {}", name);
         println!();
     }
 
@@ -535,16 +1927,89 @@ async fn main() -> Result<()> {
         std::env::var("ODDS_API_KEY").ok().filter(|k| !k.is_empty())
     };
 
+    let pinnacle_credentials = if config
+        .odds_sources
+        .values()
+        .any(|s| s.source_type == "pinnacle")
+    {
+        Some(Config::pinnacle_credentials()?)
+    } else {
+        None
+    };
+
+    // Polymarket trading credentials; market discovery and books need none
+    let polymarket_credentials =
+        if config.polymarket.enabled && !sim_mode && !config.execution.dry_run {
+            Some(Config::polymarket_credentials()?)
+        } else {
+            None
+        };
+
     println!();
     println!("  All keys loaded. Starting engine...");
     println!();
 
     let auth = Arc::new(KalshiAuth::new(kalshi_api_key, &pk_pem)?);
     let rest = Arc::new(
-        KalshiRest::new(auth.clone(), &config.kalshi.api_base)
-            .context("failed to create Kalshi REST client")?,
+        KalshiRest::new(
+            auth.clone(),
+            &config.kalshi.api_base,
+            network.client_builder(network::KALSHI),
+        )
+        .context("failed to create Kalshi REST client")?,
     );
 
+    // One-shot tool: download a historical dataset as a capture directory, then exit
+    if let Some(download) = download {
+        let out = download
+            .out
+            .clone()
+            .unwrap_or_else(|| history::default_out_dir(&config, &download));
+        println!(
+            "  Downloading {} to {} into {}",
+            download.from,
+            download.to,
+            out.display()
+        );
+        let summary = history::run(&config, &network, &rest, odds_api_key, &download, &out).await?;
+        println!();
+        println!(
+            "  {} markets, {} book snapshots, {} odds snapshots, {} scoreboards, {} settlements ({} requests failed)",
+            summary.markets,
+            summary.books,
+            summary.odds_snapshots,
+            summary.scoreboards,
+            summary.settlements,
+            summary.failures
+        );
+        println!("  Replay with: kalshi-arb --backtest {}", out.display());
+        return Ok(());
+    }
+
+    // One-shot tool: load historical settlement outcomes into the journal, then exit
+    if let Some(months) = load_settlements_months {
+        let mut series: Vec<String> = config
+            .sports
+            .values()
+            .map(|s| s.kalshi_series.clone())
+            .collect();
+        series.sort();
+        series.dedup();
+        println!(
+            "  Loading {} month(s) of settled markets into {}",
+            months, config.journal.path
+        );
+        let summary =
+            settlements::load_settlements(&rest, &series, months, Path::new(&config.journal.path))
+                .await?;
+        println!();
+        println!(
+            "  {} settled markets: {} recorded, {} already in journal, {} without a result",
+            summary.fetched, summary.recorded, summary.already_recorded, summary.no_result
+        );
+        return Ok(());
+    }
+
     // Pre-flight: verify authentication works before proceeding
     print!("  Verifying Kalshi authentication... ");
     {
@@ -570,10 +2035,37 @@ async fn main() -> Result<()> {
             sport_config,
             &config.strategy,
             &config.momentum,
+            &config.matching,
+            &network,
         );
         sport_pipelines.push(p);
     }
 
+    // Skip polling sports that have no games on the calendar. Runtime only:
+    // the sport hotkey re-enables it and config.toml is left untouched.
+    if config.season.auto_disable && !ignore_season {
+        let eastern = chrono::FixedOffset::west_opt(5 * 3600).unwrap();
+        let today = chrono::Utc::now().with_timezone(&eastern).date_naive();
+        for p in sport_pipelines.iter_mut().filter(|p| p.enabled) {
+            let window = config.sports[&p.key].season_window(&p.key)?;
+            let Some(status) = window.map(|w| w.status(today)) else {
+                continue;
+            };
+            if !status.is_active() {
+                p.enabled = false;
+                let why = match status {
+                    SeasonStatus::Break => "mid-season break",
+                    _ => "out of season",
+                };
+                println!(
+                    "  {} disabled: {} (--ignore-season to override)",
+                    p.label, why
+                );
+                tracing::info!(sport = %p.key, ?status, "sport auto-disabled by season calendar");
+            }
+        }
+    }
+
     // Build sport_toggles for TUI
     let sport_toggles: Vec<(String, String, char, bool)> = sport_pipelines
         .iter()
@@ -584,108 +2076,162 @@ async fn main() -> Result<()> {
     let (state_tx, state_rx) = watch::channel({
         let mut s = AppState::new();
         s.sim_mode = sim_mode;
+        s.profile = profile.clone();
         s.sport_toggles = sport_toggles;
+        s.cash_reserve_cents = config.risk.min_cash_reserve_cents;
+        s.number_format = number_format.clone();
+        s.kelly_tuner = engine::kelly_tuner::KellyTuner::new(config.kelly_tuning.clone());
+        s.game_filter = engine::game_filter::GameFilter::new(&config.game_filter);
         s
     });
     let (cmd_tx, mut cmd_rx) = mpsc::channel::<tui::TuiCommand>(16);
-    let (kalshi_ws_tx, mut kalshi_ws_rx) = mpsc::channel(512);
-
-    // --- Phase 1: Fetch Kalshi markets and build index ---
-    // Collect unique (key, series) pairs from pipelines
-    let sport_series: Vec<(String, String)> = sport_pipelines
-        .iter()
-        .map(|p| (p.key.clone(), p.series.clone()))
-        .collect();
-
-    let mut market_index: matcher::MarketIndex = HashMap::new();
-    let mut all_tickers: Vec<String> = Vec::new();
-
-    for (sport, series) in &sport_series {
-        match rest.get_markets_by_series(series).await {
-            Ok(markets) => {
-                for m in &markets {
-                    let parsed = matcher::parse_kalshi_title(&m.title)
-                        .or_else(|| matcher::parse_ufc_title(&m.title));
-                    if let Some((away, home)) = parsed {
-                        let date = matcher::parse_date_from_ticker(&m.event_ticker).or_else(|| {
-                            m.event_start_time
-                                .as_deref()
-                                .or(m.expected_expiration_time.as_deref())
-                                .or(m.close_time.as_deref())
-                                .and_then(|ts| {
-                                    chrono::DateTime::parse_from_rfc3339(ts)
-                                        .ok()
-                                        .map(|dt| dt.date_naive())
-                                })
-                        });
+    let ws_events = Arc::new(kalshi::ws_queue::WsEventQueue::default());
+    // Our own exchange fills, forwarded from the WS task to the engine
+    let (ws_fill_tx, mut ws_fill_rx) = mpsc::channel::<kalshi::types::WsFill>(256);
+
+    // Feed capture: markets, odds/score polls and orderbook updates
+    let feed_recorder = if record || config.recorder.enabled {
+        let (recorder, session) = recorder::FeedRecorder::start(Path::new(&config.recorder.dir))?;
+        println!("  Recording feeds to {}", session.display());
+        recorder
+    } else {
+        recorder::FeedRecorder::disabled()
+    };
 
-                        if let Some(date) = date {
-                            if let Some(key) = matcher::generate_key(sport, &away, &home, date) {
-                                let game = market_index.entry(key).or_insert_with(|| {
-                                    matcher::IndexedGame {
-                                        away_team: away.clone(),
-                                        home_team: home.clone(),
-                                        ..Default::default()
-                                    }
-                                });
+    // Read-only browser view of the same state the TUI draws
+    if config.web.enabled {
+        web::start(&config.web, state_rx.clone()).await?;
+        println!("  Web dashboard on http://{}/", config.web.bind);
+    }
 
-                                let side_market = matcher::SideMarket {
-                                    ticker: m.ticker.clone(),
-                                    title: m.title.clone(),
-                                    yes_bid: kalshi::types::dollars_to_cents(
-                                        m.yes_bid_dollars.as_deref(),
-                                    ),
-                                    yes_ask: kalshi::types::dollars_to_cents(
-                                        m.yes_ask_dollars.as_deref(),
-                                    ),
-                                    no_bid: kalshi::types::dollars_to_cents(
-                                        m.no_bid_dollars.as_deref(),
-                                    ),
-                                    no_ask: kalshi::types::dollars_to_cents(
-                                        m.no_ask_dollars.as_deref(),
-                                    ),
-                                    status: m.status.clone(),
-                                    close_time: m.close_time.clone(),
-                                };
+    // --- Phase 1: Spawn Kalshi WebSocket ---
+    // Tickers are subscribed as each series finishes indexing, and dropped
+    // by the engine once compaction removes their games.
+    let (ws_command_tx, ws_command_rx) = mpsc::unbounded_channel::<kalshi::ws::WsCommand>();
+    let kalshi_ws = KalshiWs::new(auth.clone(), &config.kalshi.ws_url, network.clone());
+    let ws_events_reader = ws_events.clone();
+    tokio::spawn(
+        async move { kalshi_ws.run(ws_command_rx, ws_events_reader).await }
+            .instrument(tracing::info_span!("kalshi_ws")),
+    );
 
-                                let winner_code = m.ticker.split('-').next_back().unwrap_or("");
-                                if winner_code.eq_ignore_ascii_case("TIE") {
-                                    game.draw = Some(side_market);
-                                } else {
-                                    match matcher::is_away_market(&m.ticker, &away, &home) {
-                                        Some(true) => game.away = Some(side_market),
-                                        Some(false) => game.home = Some(side_market),
-                                        None => {
-                                            if game.away.is_none() {
-                                                game.away = Some(side_market);
-                                            } else {
-                                                game.home = Some(side_market);
-                                            }
-                                        }
-                                    }
-                                }
+    // --- Phase 2: Fetch Kalshi markets concurrently and build index ---
+    // Collect unique (key, series, segment) entries from pipelines: each
+    // sport's game series plus any half/quarter winner series
+    let sport_series: Vec<(String, String, Option<GameSegment>)> = sport_pipelines
+        .iter()
+        .flat_map(|p| {
+            std::iter::once((p.key.clone(), p.series.clone(), None)).chain(
+                p.period_series
+                    .iter()
+                    .map(|(segment, series)| (p.key.clone(), series.clone(), Some(*segment))),
+            )
+        })
+        .collect();
 
-                                all_tickers.push(m.ticker.clone());
-                            }
-                        }
-                    }
-                }
+    let mut market_index: matcher::MarketIndex = HashMap::new();
+    let total_series = sport_series.len();
+    let mut fetches =
+        futures_util::stream::iter(sport_series.iter().map(|(sport, series, segment)| {
+            let rest = rest.clone();
+            async move { (sport, *segment, rest.get_markets_by_series(series).await) }
+        }))
+        .buffer_unordered(MARKET_INDEX_CONCURRENCY);
+
+    let mut indexed = 0;
+    while let Some((sport, segment, result)) = fetches.next().await {
+        indexed += 1;
+        match result {
+            Ok(markets) => {
+                let tickers = index_series_markets(&mut market_index, sport, &markets, segment);
                 tracing::debug!(
                     sport = sport.as_str(),
                     count = markets.len(),
                     "indexed Kalshi markets"
                 );
+                if !tickers.is_empty() {
+                    let _ = ws_command_tx.send(kalshi::ws::WsCommand::Subscribe(tickers));
+                }
+                if feed_recorder.is_enabled() {
+                    feed_recorder.record(backtest::RecordedData::Markets {
+                        sport: sport.clone(),
+                        markets,
+                        segment: segment.map(|s| s.label()),
+                    });
+                }
             }
             Err(e) => {
                 tracing::warn!(sport = sport.as_str(), error = %e, "failed to fetch Kalshi markets");
             }
         }
-        // Rate-limit: avoid 429 from Kalshi API when fetching multiple series
-        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        print!(
+            "\r  Indexing Kalshi markets... {}/{} series, {} games",
+            indexed,
+            total_series,
+            market_index.len()
+        );
+        {
+            use std::io::Write;
+            std::io::stdout().flush()?;
+        }
     }
+    drop(fetches);
+    println!();
 
     tracing::debug!(total = market_index.len(), "market index built (games)");
 
+    // --- Phase 2b: Find the same games on Polymarket ---
+    let mut polymarket_tokens: HashMap<String, bool> = HashMap::new();
+    let polymarket_rest = if config.polymarket.enabled {
+        let auth = match polymarket_credentials {
+            Some(c) => Some(Arc::new(PolymarketAuth::new(
+                &c.private_key,
+                c.api_key,
+                &c.api_secret,
+                c.passphrase,
+                c.funder,
+                config.polymarket.signature_type,
+            )?)),
+            None => None,
+        };
+        let poly = Arc::new(
+            PolymarketRest::new(
+                auth,
+                &config.polymarket.gamma_url,
+                &config.polymarket.clob_url,
+                network.client_builder(network::POLYMARKET),
+            )
+            .context("failed to create Polymarket REST client")?,
+        );
+        for p in &sport_pipelines {
+            let Some(tag) = config.sports[&p.key].polymarket_tag.as_deref() else {
+                continue;
+            };
+            match poly.get_events(tag).await {
+                Ok(events) => {
+                    let tokens = index_polymarket_events(&mut market_index, &p.key, &events);
+                    tracing::debug!(
+                        sport = p.key.as_str(),
+                        events = events.len(),
+                        tokens = tokens.len(),
+                        "indexed Polymarket events"
+                    );
+                    polymarket_tokens.extend(tokens);
+                }
+                Err(e) => {
+                    tracing::warn!(sport = p.key.as_str(), error = %e, "failed to fetch Polymarket events");
+                }
+            }
+        }
+        println!(
+            "  Polymarket: {} outcome tokens matched to Kalshi games",
+            polymarket_tokens.len()
+        );
+        Some(poly)
+    } else {
+        None
+    };
+
     // Fetch initial balance
     if !sim_mode {
         match rest.get_balance().await {
@@ -694,9 +2240,9 @@ async fn main() -> Result<()> {
                     s.balance_cents = balance;
                 });
                 tracing::warn!(
-                    "balance: {} cents (${:.2})",
+                    "balance: {} cents ({})",
                     balance,
-                    balance as f64 / 100.0
+                    number_format.money(balance)
                 );
             }
             Err(e) => {
@@ -705,25 +2251,72 @@ async fn main() -> Result<()> {
         }
     }
 
-    let live_book: LiveBook = Arc::new(Mutex::new(HashMap::new()));
+    let live_book = LiveBook::default();
     let live_book_ws = live_book.clone();
+    let edge_persistence = Arc::new(Mutex::new(engine::edge_persistence::EdgePersistence::new()));
+    let edge_persistence_ws = edge_persistence.clone();
     let live_book_engine = live_book.clone();
-
-    // --- Phase 2: Spawn Kalshi WebSocket ---
-    let kalshi_ws = KalshiWs::new(auth.clone(), &config.kalshi.ws_url);
-    let ws_tickers = all_tickers.clone();
-    tokio::spawn(async move {
-        if let Err(e) = kalshi_ws.run(ws_tickers, kalshi_ws_tx).await {
-            tracing::error!("kalshi WS fatal: {:#}", e);
-        }
-    });
+    let book_recorder = book_replay::SharedBookRecorder::default();
+    // Book and streamed-odds changes wake the engine before its next cycle
+    let eval_bus = Arc::new(engine::eval_bus::EvalBus::default());
+    let eval_bus_ws = eval_bus.clone();
+
+    // Polymarket books share the live book, keyed by token id
+    if !polymarket_tokens.is_empty() {
+        let (poly_ws_tx, mut poly_ws_rx) = mpsc::channel(512);
+        let poly_ws = PolymarketWs::new(&config.polymarket.ws_url, network.clone());
+        let token_ids: Vec<String> = polymarket_tokens.keys().cloned().collect();
+        tokio::spawn(async move { poly_ws.run(token_ids, poly_ws_tx).await });
+        let live_book_poly = live_book.clone();
+        let state_tx_poly = state_tx.clone();
+        tokio::spawn(async move {
+            while let Some(event) = poly_ws_rx.recv().await {
+                match event {
+                    polymarket::ws::PolymarketWsEvent::Book(book) => {
+                        let (yes, no) = book.as_yes_no();
+                        let depth = DepthBook::from_snapshot(&journal::BookSnapshot { yes, no });
+                        live_book_poly.insert(book.token_id, depth);
+                    }
+                    polymarket::ws::PolymarketWsEvent::Connected => {
+                        tracing::info!("polymarket WS connected");
+                    }
+                    polymarket::ws::PolymarketWsEvent::Disconnected(reason) => {
+                        state_tx_poly.send_modify(|s| {
+                            s.push_log("WARN", format!("Polymarket WS disconnected: {}", reason));
+                        });
+                    }
+                }
+            }
+        });
+    }
+    let book_recorder_ws = book_recorder.clone();
+    let book_recorder_engine = book_recorder.clone();
+
+    // --- Phase 2c: Periodically re-index to pick up newly listed games ---
+    let market_index: SharedIndex = Arc::new(tokio::sync::RwLock::new(market_index));
+    if config.kalshi.index_refresh_mins > 0 {
+        tokio::spawn(refresh_market_index(
+            rest.clone(),
+            market_index.clone(),
+            sport_series,
+            ws_command_tx.clone(),
+            feed_recorder.clone(),
+            state_tx.clone(),
+            Duration::from_secs(config.kalshi.index_refresh_mins * 60),
+        ));
+    }
 
     // --- Phase 3: Build shared odds sources ---
     let mut odds_sources: HashMap<String, Box<dyn OddsFeed>> = HashMap::new();
-    for (name, source_config) in &config.odds_sources {
+    let mut odds_streams: Vec<(String, Arc<StreamedOdds>)> = Vec::new();
+    // Shared by every the-odds-api source so a key rotation reaches them all
+    let shared_odds_api_key: SharedApiKey = Arc::new(std::sync::RwLock::new(
+        odds_api_key.clone().unwrap_or_default(),
+    ));
+    for (name, source_config) in config.odds_sources.iter() {
         match source_config.source_type.as_str() {
             "the-odds-api" => {
-                let key = odds_api_key.clone().expect("odds API key required");
+                assert!(odds_api_key.is_some(), "odds API key required");
                 let base_url = source_config
                     .base_url
                     .as_deref()
@@ -734,7 +2327,12 @@ async fn main() -> Result<()> {
                     .unwrap_or("draftkings,fanduel,betmgm,caesars");
                 odds_sources.insert(
                     name.clone(),
-                    Box::new(TheOddsApi::new(key, base_url, bookmakers)),
+                    Box::new(TheOddsApi::with_shared_key(
+                        shared_odds_api_key.clone(),
+                        base_url,
+                        bookmakers,
+                        network.client_builder(name),
+                    )),
                 );
             }
             "draftkings" => {
@@ -742,21 +2340,106 @@ async fn main() -> Result<()> {
                     live_poll_interval_s: source_config.live_poll_s,
                     pre_game_poll_interval_s: source_config.pre_game_poll_s,
                     request_timeout_ms: source_config.request_timeout_ms,
+                    base_url: source_config
+                        .base_url
+                        .clone()
+                        .unwrap_or_else(|| feed::draftkings::DK_BASE_URL.to_string()),
+                    event_groups: source_config.event_groups.clone(),
                 };
-                odds_sources.insert(name.clone(), Box::new(DraftKingsFeed::new(&dk_config)));
+                odds_sources.insert(
+                    name.clone(),
+                    Box::new(DraftKingsFeed::new(
+                        &dk_config,
+                        network.client_builder(name),
+                    )),
+                );
             }
-            "scraped" => {
-                let target_url = source_config.base_url.as_deref()
-                    .unwrap_or("https://www.bovada.lv/services/sports/event/coupon/events/A/description/basketball/college-basketball");
+            "pinnacle" => {
+                let (username, password) = pinnacle_credentials
+                    .clone()
+                    .expect("Pinnacle credentials required");
+                let base_url = source_config
+                    .base_url
+                    .as_deref()
+                    .unwrap_or(feed::pinnacle::PINNACLE_BASE_URL);
                 odds_sources.insert(
                     name.clone(),
-                    Box::new(ScrapedOddsFeed::new(
-                        target_url,
+                    Box::new(PinnacleFeed::new(
+                        username,
+                        password,
+                        base_url,
                         source_config.request_timeout_ms,
-                        source_config.max_retries,
+                        network.client_builder(name),
                     )),
                 );
             }
+            "scraped" => {
+                let feed = match &source_config.scrape {
+                    Some(scrape) => ScrapedOddsFeed::with_config(
+                        scrape.clone(),
+                        source_config.request_timeout_ms,
+                        source_config.max_retries,
+                        network.client_builder(name),
+                    ),
+                    None => {
+                        let target_url = source_config.base_url.as_deref()
+                            .unwrap_or("https://www.bovada.lv/services/sports/event/coupon/events/A/description/basketball/college-basketball");
+                        ScrapedOddsFeed::new(
+                            target_url,
+                            source_config.request_timeout_ms,
+                            source_config.max_retries,
+                            network.client_builder(name),
+                        )
+                    }
+                };
+                odds_sources.insert(name.clone(), Box::new(feed));
+            }
+            "websocket" => {
+                let Some(ws_url) = source_config.base_url.as_deref() else {
+                    eprintln!("  Odds source {} needs base_url (the WebSocket URL)", name);
+                    std::process::exit(1);
+                };
+                // Every sport that may read this source, as primary or backup
+                let mut sports: Vec<String> = config
+                    .sports
+                    .iter()
+                    .filter(|(_, sc)| {
+                        sc.odds_source == *name || sc.backup_odds_source.as_ref() == Some(name)
+                    })
+                    .map(|(key, _)| key.clone())
+                    .collect();
+                sports.sort();
+                let streamed = Arc::new(StreamedOdds::default());
+                let (stream_tx, mut stream_rx) = mpsc::channel(512);
+                let ws = WsOddsFeed::new(name, ws_url, network.clone());
+                tokio::spawn(async move { ws.run(sports, stream_tx).await });
+                let streamed_task = streamed.clone();
+                let state_tx_stream = state_tx.clone();
+                let source_name = name.clone();
+                let eval_bus_stream = eval_bus.clone();
+                tokio::spawn(async move {
+                    while let Some(event) = stream_rx.recv().await {
+                        let updated = match &event {
+                            OddsStreamEvent::Connected => {
+                                tracing::info!(source = %source_name, "odds stream connected");
+                                None
+                            }
+                            OddsStreamEvent::Disconnected(reason) => {
+                                let msg =
+                                    format!("Odds stream {} disconnected: {}", source_name, reason);
+                                state_tx_stream.send_modify(|s| s.push_log("WARN", msg));
+                                None
+                            }
+                            OddsStreamEvent::Update(update) => Some(update.sport.clone()),
+                        };
+                        streamed_task.apply(event);
+                        if let Some(sport) = updated {
+                            eval_bus_stream.feed_changed(&sport);
+                        }
+                    }
+                });
+                odds_streams.push((name.clone(), streamed));
+            }
             other => {
                 eprintln!("  Unknown odds source type: {}", other);
                 std::process::exit(1);
@@ -797,24 +2480,37 @@ async fn main() -> Result<()> {
     }
 
     // Set TUI source indicator
-    let source_label = if odds_sources.len() == 1 {
-        let src_type = config
-            .odds_sources
-            .values()
-            .next()
-            .map(|c| c.source_type.as_str())
-            .unwrap_or("UNKNOWN");
-        match src_type {
-            "the-odds-api" => "ODDS-API",
-            "draftkings" => "DK",
-            "scraped" => "BOVADA",
-            _ => "UNKNOWN",
+    let source_label = if odds_sources.len() + odds_streams.len() == 1 {
+        let source = config.odds_sources.values().next();
+        match source.map(|c| c.source_type.as_str()) {
+            Some("the-odds-api") => "ODDS-API".to_string(),
+            Some("draftkings") => "DK".to_string(),
+            Some("pinnacle") => "PINNACLE".to_string(),
+            Some("websocket") => "WS".to_string(),
+            Some("scraped") => source
+                .and_then(|c| c.scrape.as_ref())
+                .map_or("BOVADA".to_string(), |s| s.bookmaker.to_uppercase()),
+            _ => "UNKNOWN".to_string(),
         }
     } else {
-        "PER-SPORT"
+        "PER-SPORT".to_string()
     };
+    let mut odds_feeds = feed::supervisor::FeedSupervisor::new(
+        odds_sources,
+        &config.odds_sources,
+        config.adaptive_poll.clone(),
+        config.quota_budget.clone(),
+    );
+    for (name, streamed) in odds_streams {
+        odds_feeds.add_stream(&name, streamed);
+    }
     state_tx.send_modify(|s| {
-        s.odds_source = source_label.to_string();
+        s.odds_source = source_label;
+        s.fee_tracker = engine::FeeTracker::new(
+            config.execution.fee_alert_tolerance_pct,
+            config.execution.fee_alert_min_samples,
+        );
+        s.equity = engine::equity::EquityCurve::new(&config.equity);
     });
 
     let mut sim_config = config.simulation.clone();
@@ -822,9 +2518,76 @@ async fn main() -> Result<()> {
     let mut global_strategy = config.strategy.clone();
     let mut global_momentum = config.momentum.clone();
     let odds_source_configs = config.odds_sources.clone();
+    let adaptive_poll_config = config.adaptive_poll.clone();
+    let sprint_config = config.sprint.clone();
+    let event_driven_config = config.event_driven.clone();
+    let pipeline_tick_timeout = Duration::from_millis(config.network.pipeline_tick_timeout_ms);
     let execution_config = config.execution.clone();
+    let exit_config = config.exit.clone();
+    let escalation_config = config.escalation.clone();
+    let polymarket_config = config.polymarket.clone();
+    let edge_persistence_config = config.edge_persistence.clone();
+    let compaction_config = config.compaction.clone();
+    let degradation_config = config.degradation.clone();
+    let drawdown_config = config.drawdown.clone();
 
     let rest_for_engine = rest.clone();
+    let auth_engine = auth.clone();
+
+    let journal = if config.journal.enabled {
+        match journal::Journal::open(Path::new(&config.journal.path)) {
+            Ok(j) => j,
+            Err(e) => {
+                tracing::warn!(error = %e, "trade journal disabled");
+                journal::Journal::disabled()
+            }
+        }
+    } else {
+        journal::Journal::disabled()
+    };
+
+    // Repopulate the trades panel from today's journal rows
+    match journal.todays_trades() {
+        Ok(trades) if !trades.is_empty() => {
+            let restored = trades.len();
+            state_tx.send_modify(|s| {
+                for trade in trades {
+                    s.push_trade(trade);
+                }
+                s.push_log(
+                    "INFO",
+                    format!("Restored {} trades from today's journal", restored),
+                );
+            });
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!(error = %e, "failed to restore today's trades"),
+    }
+
+    // Persist every new trades-panel row, whichever path pushed it
+    {
+        let journal = journal.clone();
+        let mut trades_rx = state_tx.subscribe();
+        let mut persisted = trades_rx.borrow().next_trade_id;
+        tokio::spawn(async move {
+            while trades_rx.changed().await.is_ok() {
+                let (new_trades, sim): (Vec<tui::state::TradeRow>, bool) = {
+                    let s = trades_rx.borrow_and_update();
+                    let new_trades = s
+                        .trades
+                        .iter()
+                        .filter(|t| t.id >= persisted)
+                        .cloned()
+                        .collect();
+                    persisted = s.next_trade_id;
+                    (new_trades, s.sim_mode)
+                };
+                for trade in &new_trades {
+                    journal.record_trade(trade, sim);
+                }
+            }
+        });
+    }
 
     // Create shared FillSimulator for sim mode (entries and exits)
     // Using tokio::sync::Mutex to allow holding lock across await points
@@ -835,7 +2598,9 @@ async fn main() -> Result<()> {
 
     let sim_mode_engine = sim_mode;
     let state_tx_engine = state_tx.clone();
-    let config_path = Path::new("config.toml").to_path_buf();
+    let feed_recorder_engine = feed_recorder.clone();
+    let config_path = cli.config.clone();
+    let profiler_engine = profiler.clone();
     tokio::spawn(async move {
         let mut is_paused = false;
 
@@ -844,32 +2609,30 @@ async fn main() -> Result<()> {
             global_momentum.book_pressure_weight,
         );
 
-        // Initialize risk manager for live mode
-        let mut risk_manager = if !sim_mode_engine {
-            Some(crate::engine::risk::RiskManager::new(risk_config.clone()))
-        } else {
-            None
-        };
-
-        // Initialize position tracker, pending order registry, and executor for live mode
-        let mut position_tracker = if !sim_mode_engine {
-            Some(crate::engine::PositionTracker::new())
-        } else {
-            None
-        };
-
-        let mut pending_orders = if !sim_mode_engine {
-            Some(crate::engine::PendingOrderRegistry::new())
-        } else {
-            None
-        };
+        // Live order state; none in simulation
+        let mut live = (!sim_mode_engine)
+            .then(|| live::LiveSession::new(&risk_config, &execution_config));
 
-        let executor = if !sim_mode_engine {
+        // Positions held on Polymarket settle on their token's market
+        let polymarket_settle = polymarket_rest.clone();
+        let mut executor = if !sim_mode_engine {
             let dry_run = execution_config.dry_run;
-            Some(crate::execution::OrderExecutor::new(rest_for_engine.clone(), dry_run))
+            let exec = crate::execution::OrderExecutor::new(rest_for_engine.clone(), dry_run)
+                .with_duplicate_guard(
+                    Duration::from_millis(execution_config.duplicate_window_ms),
+                    execution_config.duplicate_band_cents,
+                );
+            Some(match polymarket_rest {
+                Some(poly) => exec.with_polymarket(poly, polymarket_tokens),
+                None => exec,
+            })
         } else {
             None
         };
+        // Set once the order-path preflight has passed this session
+        let mut preflight_passed = false;
+        // Cross-venue pairs already logged, so each is reported once while it lasts
+        let mut seen_arbs: HashSet<(String, String, u32, u32)> = HashSet::new();
 
         // Reconcile positions on startup (live mode only)
         if !sim_mode_engine {
@@ -888,30 +2651,8 @@ async fn main() -> Result<()> {
 
             if !positions.is_empty() {
                 tracing::warn!(count = positions.len(), "found existing positions on startup");
-                for pos in &positions {
-                    tracing::info!(
-                        ticker = %pos.ticker,
-                        position = pos.position,
-                        "existing position"
-                    );
-                    if pos.position > 0 {
-                        if let Some(ref mut rm) = risk_manager {
-                            rm.record_buy(&pos.ticker, pos.position as u32);
-                        }
-                        if let Some(ref mut pt) = position_tracker {
-                            // Conservative defaults for reconciled positions:
-                            // sell_target=99 means manual exit only (bid will never reach 99)
-                            pt.record_entry(
-                                pos.ticker.clone(),
-                                pos.position as u32,
-                                0,    // unknown entry price
-                                0,    // unknown entry cost
-                                99,   // conservative sell target (manual exit only)
-                                Instant::now(),
-                                false,
-                            );
-                        }
-                    }
+                if let Some(ref mut l) = live {
+                    l.reconcile(&positions);
                 }
                 tracing::info!("position reconciliation complete");
             } else {
@@ -921,16 +2662,40 @@ async fn main() -> Result<()> {
             tracing::info!("simulation mode: skipping position reconciliation");
         }
 
-        let mut api_request_times: VecDeque<Instant> = VecDeque::with_capacity(100);
-        let mut accumulated_rows: HashMap<String, MarketRow> = HashMap::new();
-
-        // Filter statistics
-        let mut filter_live: usize;
-        let mut filter_pre_game: usize;
-        let mut filter_closed: usize;
-        let mut earliest_commence: Option<chrono::DateTime<chrono::Utc>>;
+        // Manual orders from the TUI, carried out after the command drain
+        let mut manual_orders: Vec<tui::ManualOrder> = Vec::new();
+        let mut last_fee_reconcile = Instant::now();
+        let mut last_edge_tune = Instant::now();
+        let mut settlement_watch = settlements::SettlementWatch::new(
+            Duration::from_secs(sim_config.settlement_check_secs),
+            Duration::from_secs(sim_config.settlement_timeout_secs),
+        );
+        let mut last_memory_check = Instant::now();
+        let mut last_compaction = Instant::now();
+        let mut last_compacted = 0;
+        let mut over_memory_limit = false;
+        let mut degradation = engine::degradation::DegradationMonitor::default();
+        let mut drawdown = engine::drawdown::DrawdownMonitor::default();
+
+        let api_request_times: Mutex<VecDeque<Instant>> =
+            Mutex::new(VecDeque::with_capacity(100));
+        // Rows and filter counts from each sport's last tick
+        let mut evaluated = eval_loop::Evaluated::default();
+        // What the eval bus woke the engine for; the next cycle ticks only that
+        let mut event_focus: Option<engine::eval_bus::Dirty> = None;
+        let event_min_interval = Duration::from_millis(event_driven_config.min_interval_ms);
 
         loop {
+            // Apply exchange fills pushed over the WS
+            while let Ok(fill) = ws_fill_rx.try_recv() {
+                if let Some(ref mut l) = live {
+                    l.apply_fill(&fill, &state_tx_engine);
+                }
+                if let Some(ref exec) = executor {
+                    exec.on_fill(&fill.order_id, fill.count);
+                }
+            }
+
             // Drain TUI commands
             while let Ok(cmd) = cmd_rx.try_recv() {
                 match cmd {
@@ -946,47 +2711,95 @@ async fn main() -> Result<()> {
                     tui::TuiCommand::KillSwitch => {
                         tracing::error!("KILL SWITCH ACTIVATED - halting all trading");
 
-                        // Cancel all pending orders
-                        if let Some(ref mut po) = pending_orders {
-                            let orders = po.drain();
-                            if !orders.is_empty() {
-                                tracing::error!(count = orders.len(), "cancelling pending orders");
-                                for order in &orders {
-                                    if let Some(ref order_id) = order.order_id {
-                                        if let Some(ref exec) = executor {
-                                            if let Err(e) = exec.cancel_order(order_id).await {
-                                                tracing::error!(order_id = %order_id, error = %e, "failed to cancel order");
-                                            } else {
-                                                tracing::info!(order_id = %order_id, "order cancelled");
-                                            }
-                                        }
-                                    }
-                                }
-                            }
+                        // Cancel pending orders, resting entries and resting exits
+                        if let (Some(l), Some(exec)) = (live.as_mut(), executor.as_ref()) {
+                            l.cancel_all(exec, &rest_for_engine).await;
                         }
 
                         state_tx_engine.send_modify(|s| {
                             s.is_paused = true;
                             s.push_log("KILL", "KILL SWITCH ACTIVATED - all trading halted".to_string());
+                            s.escalation.raise(
+                                engine::AlertKind::KillSwitch,
+                                "kill switch activated, all trading halted".to_string(),
+                                Instant::now(),
+                            );
                         });
                         return Ok(()); // Exit engine loop
                     }
+                    tui::TuiCommand::AcknowledgeAlerts => {
+                        handle_acknowledge_alerts(&state_tx_engine);
+                    }
+                    tui::TuiCommand::ReloadCredentials => {
+                        handle_reload_credentials(
+                            &auth_engine,
+                            &rest_for_engine,
+                            &shared_odds_api_key,
+                            &state_tx_engine,
+                        )
+                        .await;
+                    }
                     tui::TuiCommand::ToggleSport(sport_key) => {
                         handle_toggle_sport(&mut sport_pipelines, &config_path, &sport_key);
                     }
+                    tui::TuiCommand::AnnotateTrade { trade_id, note } => {
+                        handle_annotate_trade(trade_id, note, &state_tx_engine, &journal);
+                    }
+                    tui::TuiCommand::ToggleBookRecording(ticker) => {
+                        handle_toggle_book_recording(
+                            &book_recorder_engine,
+                            &ticker,
+                            &state_tx_engine,
+                        );
+                    }
+                    tui::TuiCommand::OpenBookReplay(ticker) => {
+                        handle_open_book_replay(&book_recorder_engine, &ticker, &state_tx_engine);
+                    }
+                    tui::TuiCommand::CloseBookReplay => {
+                        state_tx_engine.send_modify(|s| s.book_replay = None);
+                    }
+                    tui::TuiCommand::OpenDepthLadder(ticker) => {
+                        handle_open_depth_ladder(&live_book_engine, &ticker, &state_tx_engine);
+                    }
+                    tui::TuiCommand::CloseDepthLadder => {
+                        state_tx_engine.send_modify(|s| s.depth_ladder = None);
+                    }
+                    tui::TuiCommand::ManualOrder(order) => manual_orders.push(order),
+                    tui::TuiCommand::ToggleBlacklist(key) => {
+                        handle_toggle_game_filter(&key, false, &config_path, &state_tx_engine);
+                    }
+                    tui::TuiCommand::ToggleWhitelist(key) => {
+                        handle_toggle_game_filter(&key, true, &config_path, &state_tx_engine);
+                    }
+                    tui::TuiCommand::OpenWinProb => {
+                        handle_open_win_prob(&sport_pipelines, &state_tx_engine);
+                    }
+                    tui::TuiCommand::CloseWinProb => {
+                        state_tx_engine.send_modify(|s| s.win_prob_grids.clear());
+                    }
+                    tui::TuiCommand::OpenPortfolio => {
+                        handle_open_portfolio(&journal, &state_tx_engine);
+                    }
+                    tui::TuiCommand::ClosePortfolio => {
+                        state_tx_engine.send_modify(|s| s.portfolio = None);
+                    }
+                    // Only a `--replay` session steps through events
+                    tui::TuiCommand::StepReplay => {}
+                    tui::TuiCommand::Profile => {
+                        handle_start_profile(&profiler_engine, &state_tx_engine);
+                    }
                     tui::TuiCommand::FetchDiagnostic => {
                         handle_fetch_diagnostic(
                             &mut sport_pipelines,
-                            &mut odds_sources,
-                            &mut api_request_times,
+                            &odds_feeds,
+                            &api_request_times,
                             &state_tx_engine,
-                            &market_index,
+                            &*market_index.read().await,
                         )
                         .await;
                     }
                     tui::TuiCommand::OpenConfig => {
-                        let available_odds_sources: Vec<String> =
-                            odds_sources.keys().cloned().collect();
+                        let available_odds_sources = odds_feeds.sources();
                         let tabs = tui::config_view::build_config_tabs(
                             &sport_pipelines,
                             &global_strategy,
@@ -994,6 +2807,7 @@ async fn main() -> Result<()> {
                             &risk_config,
                             &sim_config,
                             &available_odds_sources,
+                            &state_tx_engine.borrow().kelly_tuner,
                         );
                         let cv = tui::config_view::ConfigViewState::new(tabs);
                         state_tx_engine.send_modify(|s| {
@@ -1022,8 +2836,7 @@ async fn main() -> Result<()> {
 
                             // If changing fair_value to an odds source, also persist odds_source
                             if field_path.ends_with(".fair_value")
-                                && value != "score-feed"
-                                && value != "odds-feed"
+                                && !engine::fair_value::is_builtin(&value)
                             {
                                 let odds_source_path =
                                     field_path.replace(".fair_value", ".odds_source");
@@ -1048,26 +2861,71 @@ async fn main() -> Result<()> {
                 }
             }
 
-            // Expire stale pending orders
-            if let Some(ref mut po) = pending_orders {
-                let timeout = Duration::from_secs(execution_config.order_timeout_secs);
-                let expired = po.expire_older_than(timeout);
-                for order in expired {
-                    tracing::warn!(
-                        ticker = %order.ticker,
-                        age_secs = order.submitted_at.elapsed().as_secs(),
-                        order_id = ?order.order_id,
-                        "expired stale pending order"
-                    );
-                    // Attempt to cancel if we have an order ID
-                    if let Some(ref order_id) = order.order_id {
-                        if let Some(ref exec) = executor {
-                            if let Err(e) = exec.cancel_order(order_id).await {
-                                tracing::error!(order_id = %order_id, error = %e, "failed to cancel expired order");
-                            }
+            // Manual orders skip the strategy gates but not the risk limits.
+            // Live ones wait on the same order-path preflight as entries.
+            for order in std::mem::take(&mut manual_orders) {
+                if order.is_buy && !state_tx_engine.borrow().game_filter.allows(&order.ticker) {
+                    state_tx_engine.send_modify(|s| {
+                        s.push_log("WARN", format!("Manual buy refused: {} is blocked", order.ticker));
+                    });
+                    continue;
+                }
+                let held_side = match live.as_ref() {
+                    Some(l) => l.position_tracker.get(&order.ticker).map(|p| p.side.clone()),
+                    None => state_tx_engine
+                        .borrow()
+                        .sim_positions
+                        .iter()
+                        .find(|p| p.ticker == order.ticker)
+                        .map(|p| p.side.clone()),
+                };
+                let (mut bid, ask) = live_book_engine
+                    .read(&order.ticker, |depth| {
+                        manual_order_touch(depth, &order, held_side.as_deref())
+                    })
+                    .unwrap_or((0, 0));
+                // A position held on Polymarket sells into its token's bid
+                let venue_ticker = live
+                    .as_ref()
+                    .and_then(|l| l.position_tracker.get(&order.ticker))
+                    .and_then(|p| p.venue_ticker.clone());
+                if let Some(token) = venue_ticker.filter(|_| !order.is_buy) {
+                    bid = live_book_engine.read(&token, |d| d.best_bid("yes")).unwrap_or(0);
+                }
+                let (Some(l), Some(exec)) = (live.as_mut(), executor.as_ref()) else {
+                    state_tx_engine.send_modify(|s| fill_sim_manual_order(s, &order, bid, ask));
+                    continue;
+                };
+                if !preflight_passed && !exec.is_dry_run() {
+                    match run_order_preflight(exec, &live_book_engine, &state_tx_engine).await {
+                        Some(true) => preflight_passed = true,
+                        Some(false) => {
+                            is_paused = true;
+                            state_tx_engine.send_modify(|s| s.is_paused = true);
+                            continue;
+                        }
+                        None => {
+                            state_tx_engine.send_modify(|s| {
+                                s.push_log(
+                                    "WARN",
+                                    format!("MANUAL {} dropped: order preflight deferred", order.ticker),
+                                );
+                            });
+                            continue;
                         }
                     }
                 }
+                l.submit_manual_order(&order, bid, ask, exec, &rest_for_engine, &state_tx_engine)
+                    .await;
+            }
+
+            // Expire stale pending orders, then maintain resting entries:
+            // re-peg pegged ones to the touch, and cancel when the edge is
+            // gone, the re-peg budget is spent, or the entry times out
+            if let (Some(l), Some(exec)) = (live.as_mut(), executor.as_ref()) {
+                l.expire_pending(exec).await;
+                l.maintain_resting_entries(exec, &rest_for_engine, &live_book_engine, &state_tx_engine)
+                    .await;
             }
 
             if is_paused {
@@ -1077,441 +2935,414 @@ async fn main() -> Result<()> {
 
             let cycle_start = Instant::now();
 
-            filter_live = 0;
-            filter_pre_game = 0;
-            filter_closed = 0;
-            earliest_commence = None;
-            accumulated_rows.clear();
-
-            // Track available balance (pessimistic: reduce by pending orders)
-            let (bankroll_cents, mut available_balance_cents) = {
-                let s = state_tx_engine.borrow();
-                let total = if sim_mode_engine {
-                    s.sim_balance_cents.max(0) as u64
-                } else {
-                    s.balance_cents.max(0) as u64
-                };
-                (total, total)
-            };
-
-            let mut all_closed_tickers: Vec<(String, u32)> = Vec::new();
-            let mut all_order_intents: Vec<pipeline::OrderIntent> = Vec::new();
-
-            for pipeline in &mut sport_pipelines {
-                if !pipeline.enabled {
-                    continue;
-                }
-
-                // Lock fill_simulator for entry evaluation
-                let mut fill_sim_guard = fill_sim_engine.lock().await;
-                let result = pipeline
-                    .tick(
-                        cycle_start,
-                        &market_index,
-                        &live_book_engine,
-                        &mut odds_sources,
-                        &scorer,
-                        &risk_config,
-                        &sim_config,
-                        sim_mode_engine,
-                        &state_tx_engine,
-                        bankroll_cents,
-                        &mut api_request_times,
-                        &odds_source_configs,
-                        if sim_mode_engine {
-                            Some(&mut *fill_sim_guard)
-                        } else {
-                            None
-                        },
-                    )
-                    .await;
-                drop(fill_sim_guard);
-
-                filter_live += result.filter_live;
-                filter_pre_game += result.filter_pre_game;
-                filter_closed += result.filter_closed;
-                if let Some(ec) = result.earliest_commence {
-                    earliest_commence = Some(earliest_commence.map_or(ec, |e| e.min(ec)));
-                }
-                accumulated_rows.extend(result.rows);
-                all_closed_tickers.extend(result.closed_tickers);
-                all_order_intents.extend(result.order_intents);
-            }
-
-            // Settle sim positions on closed markets at last known fair value
-            if sim_mode_engine && !all_closed_tickers.is_empty() {
-                state_tx_engine.send_modify(|s| {
-                    for (closed_ticker, fair) in &all_closed_tickers {
-                        let idx = s
-                            .sim_positions
-                            .iter()
-                            .position(|p| &p.ticker == closed_ticker);
-                        let Some(idx) = idx else { continue };
-                        let pos = s.sim_positions.remove(idx);
-                        let settle_price = *fair;
-                        let exit_revenue = (pos.quantity * settle_price) as i64;
-                        let exit_fee = calculate_fee(settle_price, pos.quantity, false) as i64;
-                        let entry_cost =
-                            (pos.quantity * pos.entry_price) as i64 + pos.entry_fee as i64;
-                        let pnl = (exit_revenue - exit_fee) - entry_cost;
-
-                        s.sim_balance_cents += exit_revenue - exit_fee;
-                        s.realized_pnl_cents += pnl;
-                        s.total_trades += 1;
-                        if pnl > 0 {
-                            s.winning_trades += 1;
-                        }
-                        s.push_trade(tui::state::TradeRow {
-                            time: chrono::Local::now().format("%H:%M:%S").to_string(),
-                            action: "SETTLE".to_string(),
-                            ticker: pos.ticker.clone(),
-                            price: settle_price,
-                            quantity: pos.quantity,
-                            order_type: "SIM".to_string(),
-                            pnl: Some(pnl as i32),
-                            slippage: None,
-                            source: String::new(),
-                            fair_value_basis: String::new(),
-                        });
-                        s.push_log(
-                            "TRADE",
-                            format!(
-                                "SIM SETTLE {}x {} @ {}c (fair value), P&L: {:+}c",
-                                pos.quantity, pos.ticker, settle_price, pnl
-                            ),
-                        );
-                    }
-                });
-            }
-
-            // Execute order intents (live mode only)
-            if !sim_mode_engine && !all_order_intents.is_empty() {
-                if let Some(ref exec) = executor {
-                    for intent in &all_order_intents {
-                        // Gate 1: PositionTracker - skip if already holding
-                        if let Some(ref pt) = position_tracker {
-                            if pt.has_position(&intent.ticker) {
-                                tracing::warn!(
-                                    ticker = %intent.ticker,
-                                    "BLOCKED: already holding position"
-                                );
-                                continue;
-                            }
-                        }
-
-                        // Gate 2: PendingOrderRegistry - skip if already pending
-                        if let Some(ref po) = pending_orders {
-                            if po.is_pending(&intent.ticker, OrderSide::Entry) {
-                                tracing::warn!(
-                                    ticker = %intent.ticker,
-                                    "BLOCKED: order already pending"
-                                );
-                                continue;
-                            }
-                        }
-
-                        // Gate 3: RiskManager - skip if risk limits exceeded
-                        if let Some(ref rm) = risk_manager {
-                            if !rm.can_trade(&intent.ticker, intent.quantity, intent.entry_cost_cents) {
-                                tracing::warn!(
-                                    ticker = %intent.ticker,
-                                    quantity = intent.quantity,
-                                    cost = intent.entry_cost_cents,
-                                    "BLOCKED: risk limits exceeded"
-                                );
-                                continue;
-                            }
-                        }
-
-                        // Gate 4: Available balance
-                        if (intent.entry_cost_cents as u64) > available_balance_cents {
-                            tracing::warn!(
-                                ticker = %intent.ticker,
-                                cost = intent.entry_cost_cents,
-                                available = available_balance_cents,
-                                "BLOCKED: insufficient balance"
-                            );
-                            continue;
-                        }
-
-                        // Register pending order
-                        if let Some(ref mut po) = pending_orders {
-                            if !po.try_register(
-                                intent.ticker.clone(),
-                                intent.quantity,
-                                intent.price,
-                                intent.is_taker,
-                                OrderSide::Entry,
-                            ) {
-                                continue; // Race condition: another intent registered first
-                            }
-                        }
-
-                        // Deduct from available balance (pessimistic)
-                        available_balance_cents -= intent.entry_cost_cents as u64;
-
-                        // Log prominently
-                        tracing::error!(
-                            ticker = %intent.ticker,
-                            quantity = intent.quantity,
-                            price = intent.price,
-                            edge = intent.edge,
-                            net = intent.net_profit_estimate,
-                            fair_value = intent.fair_value,
-                            source = %intent.source,
-                            sell_target = intent.sell_target,
-                            is_taker = intent.is_taker,
-                            "SUBMITTING ORDER"
-                        );
-                        state_tx_engine.send_modify(|s| {
-                            s.push_log(
-                                "ORDER",
-                                format!(
-                                    "SUBMIT {}x {} @ {}c (edge {}c, FV {}c, {})",
-                                    intent.quantity,
-                                    intent.ticker,
-                                    intent.price,
-                                    intent.edge,
-                                    intent.fair_value,
-                                    if intent.is_taker { "TAKER" } else { "MAKER" },
-                                ),
-                            );
-                        });
-
-                        // Submit order
-                        match exec
-                            .submit_order(
-                                &intent.ticker,
-                                intent.quantity,
-                                intent.price,
-                                intent.is_buy,
-                                intent.is_taker,
-                                &intent.side,
-                            )
-                            .await
-                        {
-                            Ok(_order_id) => {
-                                // Update RiskManager
-                                if let Some(ref mut rm) = risk_manager {
-                                    rm.record_buy(&intent.ticker, intent.quantity);
-                                }
-                                // Update PositionTracker
-                                if let Some(ref mut pt) = position_tracker {
-                                    pt.record_entry(
-                                        intent.ticker.clone(),
-                                        intent.quantity,
-                                        intent.price,
-                                        intent.entry_cost_cents,
-                                        intent.sell_target,
-                                        Instant::now(),
-                                        intent.is_taker,
-                                    );
-                                }
-                                // Complete pending order
-                                if let Some(ref mut po) = pending_orders {
-                                    po.complete(&intent.ticker, OrderSide::Entry);
-                                }
-                                // Push trade to TUI
-                                state_tx_engine.send_modify(|s| {
-                                    s.push_trade(tui::state::TradeRow {
-                                        time: chrono::Local::now()
-                                            .format("%H:%M:%S")
-                                            .to_string(),
-                                        action: "BUY".to_string(),
-                                        ticker: intent.ticker.clone(),
-                                        price: intent.price,
-                                        quantity: intent.quantity,
-                                        order_type: if intent.is_taker {
-                                            "TAKER"
-                                        } else {
-                                            "MAKER"
-                                        }
-                                        .to_string(),
-                                        pnl: None,
-                                        slippage: None,
-                                        source: intent.source.clone(),
-                                        fair_value_basis: pipeline::format_fair_value_basis(
-                                            &intent.trace,
-                                        ),
-                                    });
-                                    s.push_log(
-                                        "ORDER",
-                                        format!(
-                                            "FILLED {}x {} @ {}c",
-                                            intent.quantity, intent.ticker, intent.price
-                                        ),
-                                    );
-                                });
-                            }
-                            Err(e) => {
-                                tracing::error!(
-                                    ticker = %intent.ticker,
-                                    error = %e,
-                                    "order submission failed"
-                                );
-                                // Release pending order
-                                if let Some(ref mut po) = pending_orders {
-                                    po.complete(&intent.ticker, OrderSide::Entry);
-                                }
-                                // Restore available balance
-                                available_balance_cents += intent.entry_cost_cents as u64;
-                                state_tx_engine.send_modify(|s| {
-                                    s.push_log(
-                                        "ERROR",
-                                        format!(
-                                            "ORDER FAILED {}: {}",
-                                            intent.ticker, e
-                                        ),
-                                    );
-                                });
-                            }
-                        }
-                    }
-                }
-            }
-
-            // Process live exits (live mode only)
-            if !sim_mode_engine {
-                if let Some(ref mut pt) = position_tracker {
-                    let max_hold_seconds = sim_config.realism.max_hold_seconds;
-                    let positions: Vec<_> = pt.all_positions().iter().map(|p| (*p).clone()).collect();
+            let focus = event_focus.take();
 
-                    for position in positions {
-                        // Skip if exit already pending
-                        if pending_orders.as_ref().map(|po| po.is_pending(&position.ticker, OrderSide::Exit)).unwrap_or(false) {
-                            continue;
-                        }
+            // Track available balance (pessimistic: reduce by pending orders),
+            // never deploying the configured cash reserve
+            let reserve_cents = risk_config.min_cash_reserve_cents;
+            state_tx_engine.send_if_modified(|s| {
+                let changed = s.cash_reserve_cents != reserve_cents;
+                s.cash_reserve_cents = reserve_cents;
+                changed
+            });
+            let drawdown_tier =
+                pipeline::observe_drawdown(&mut drawdown, &state_tx_engine, &drawdown_config);
+            record_equity(&state_tx_engine, &journal, cycle_start);
+            let (bankroll_cents, mut available_balance_cents) = {
+                let s = state_tx_engine.borrow();
+                let balance = if sim_mode_engine {
+                    s.sim_balance_cents
+                } else {
+                    s.balance_cents
+                };
+                let deployable = crate::engine::risk::deployable_cents(balance, reserve_cents);
+                // Past a soft drawdown limit entries are sized from less
+                (drawdown_tier.sizing_bankroll(deployable, &drawdown_config), deployable)
+            };
+            // Live holdings for the positions view and manual sells
+            if let Some(ref l) = live {
+                let rows = live_position_rows(&l.position_tracker, &live_book_engine);
+                state_tx_engine.send_modify(|s| s.positions = rows);
+            }
+            // Exposure caps start each cycle from what is actually held
+            let exposure = match live.as_ref() {
+                Some(l) => live_exposure(&l.position_tracker, &*market_index.read().await, &sport_pipelines),
+                None => pipeline::sim_exposure(&state_tx_engine.borrow().sim_positions),
+            };
+            state_tx_engine.send_modify(|s| s.exposure = exposure);
 
-                        // Get current bid from live book
-                        let yes_bid = live_book_engine.lock().ok()
-                            .and_then(|book| book.get(&position.ticker).map(|d| d.best_bid_ask().0))
-                            .unwrap_or(0);
+            let mut all_closed_tickers: Vec<(String, u32, pipeline::SettleBasis)> = Vec::new();
+            let mut all_order_intents: Vec<pipeline::OrderIntent> = Vec::new();
 
-                        // Check for timeout
-                        let held_secs = position.filled_at.elapsed().as_secs();
-                        let is_timeout = max_hold_seconds > 0 && held_secs > max_hold_seconds;
+            let ticks_now = |p: &pipeline::SportPipeline| {
+                p.enabled && focus.as_ref().is_none_or(|dirty| dirty.touches(&p.key))
+            };
+            let polled: Vec<_> = sport_pipelines
+                .iter()
+                .filter(|p| ticks_now(p))
+                .map(|p| (p.last_odds_poll, p.last_score_poll))
+                .collect();
+            // Each tick gets its own fill simulator so concurrent ticks never
+            // wait on one another
+            let mut fill_sims: Vec<engine::FillSimulator> = {
+                let mut parent = fill_sim_engine.lock().await;
+                polled.iter().map(|_| parent.fork()).collect()
+            };
+            // Ticks run concurrently so one sport's slow fetch doesn't hold
+            // up the rest; a tick past the timeout is dropped
+            let results = {
+                let index = market_index.read().await;
+                let ticks = sport_pipelines
+                    .iter_mut()
+                    .filter(|p| ticks_now(p))
+                    .zip(fill_sims.iter_mut())
+                    .map(|(pipeline, fill_sim)| {
+                        let span = tracing::info_span!("pipeline_tick", sport = pipeline.key.as_str());
+                        let tick = pipeline
+                            .tick(
+                                cycle_start,
+                                &index,
+                                &live_book_engine,
+                                &odds_feeds,
+                                &scorer,
+                                &risk_config,
+                                &sim_config,
+                                sim_mode_engine,
+                                &state_tx_engine,
+                                bankroll_cents,
+                                &api_request_times,
+                                &odds_source_configs,
+                                &adaptive_poll_config,
+                                &sprint_config,
+                                if sim_mode_engine { Some(fill_sim) } else { None },
+                                &journal,
+                                focus.as_ref().and_then(|dirty| dirty.tickers_for(&pipeline.key)),
+                            )
+                            .instrument(span);
+                        tokio::time::timeout(pipeline_tick_timeout, tick)
+                    });
+                futures_util::future::join_all(ticks).await
+            };
 
-                        // Exit if timeout OR bid >= sell_target
-                        if is_timeout || yes_bid >= position.sell_target {
-                            let exit_price = if is_timeout { yes_bid } else { position.sell_target };
-                            let is_taker_exit = is_timeout; // Timeout forces taker exit
+            let ticked: Vec<_> = sport_pipelines
+                .iter()
+                .filter(|p| ticks_now(p))
+                .zip(polled)
+                .zip(results)
+                .collect();
+            let timed_out: HashSet<&str> = ticked
+                .iter()
+                .filter(|(_, result)| result.is_err())
+                .map(|((pipeline, _), _)| pipeline.key.as_str())
+                .collect();
+            // Sports whose tick timed out keep their rows from the last
+            // cycle, as do sports a focused cycle didn't re-tick in full;
+            // those it re-ticked for some games get just their rows replaced
+            let carried_over = |sport: &String| {
+                timed_out.contains(sport.as_str())
+                    || focus.as_ref().is_some_and(|dirty| !dirty.in_full(sport))
+            };
+            evaluated.carry_over(carried_over);
+
+            for ((pipeline, polled), result) in ticked {
+                let Ok(result) = result else {
+                    tracing::warn!(sport = pipeline.key.as_str(), "pipeline tick timed out");
+                    let msg = format!(
+                        "{} tick timed out after {}ms; keeping last cycle's markets",
+                        pipeline.key,
+                        pipeline_tick_timeout.as_millis()
+                    );
+                    state_tx_engine.send_modify(|s| s.push_log("WARN", msg));
+                    continue;
+                };
 
-                            // Skip if price is invalid
-                            if exit_price == 0 || exit_price > 99 {
-                                continue;
-                            }
+                if feed_recorder_engine.is_enabled() {
+                    if pipeline.last_odds_poll != polled.0 {
+                        feed_recorder_engine.record(backtest::RecordedData::Odds {
+                            sport: pipeline.key.clone(),
+                            updates: pipeline.cached_odds.clone(),
+                        });
+                    }
+                    if pipeline.last_score_poll != polled.1 {
+                        feed_recorder_engine.record(backtest::RecordedData::Scores {
+                            sport: pipeline.key.clone(),
+                            updates: pipeline.cached_scores.clone(),
+                        });
+                    }
+                }
 
-                            // Register pending exit order
-                            if let Some(ref mut po) = pending_orders {
-                                if !po.try_register(
-                                    position.ticker.clone(),
-                                    position.quantity,
-                                    exit_price,
-                                    is_taker_exit,
-                                    OrderSide::Exit,
-                                ) {
-                                    continue;
-                                }
-                            }
+                observe_edges(
+                    &pipeline.key,
+                    &result.rows,
+                    &live_book_engine,
+                    &edge_persistence,
+                );
+                let filters = (!carried_over(&pipeline.key)).then_some((
+                    result.filter_live,
+                    result.filter_pre_game,
+                    result.filter_closed,
+                    result.earliest_commence,
+                ));
+                evaluated.absorb(&pipeline.key, result.rows, filters);
+                all_closed_tickers.extend(result.closed_tickers);
+                all_order_intents.extend(result.order_intents);
+            }
+            let (filter_stats, earliest_commence) = evaluated.totals(
+                sport_pipelines
+                    .iter()
+                    .filter(|p| p.enabled)
+                    .map(|p| p.key.as_str()),
+            );
+            if focus.is_none() {
+                eval_bus.watch(evaluated.sports().clone());
+            }
 
-                            tracing::error!(
-                                ticker = %position.ticker,
-                                quantity = position.quantity,
-                                exit_price = exit_price,
-                                sell_target = position.sell_target,
-                                is_timeout = is_timeout,
-                                held_secs = held_secs,
-                                "SUBMITTING EXIT ORDER"
-                            );
+            // Closed markets still holding positions wait on their result
+            settlement_loop::watch_closed(
+                &mut settlement_watch,
+                &all_closed_tickers,
+                live.as_ref(),
+                &state_tx_engine.borrow().sim_positions,
+                cycle_start,
+            );
+            settlement_loop::settle_due(
+                &mut settlement_watch,
+                &rest_for_engine,
+                polymarket_settle.as_ref(),
+                live.as_mut(),
+                &journal,
+                &state_tx_engine,
+            )
+            .await;
+
+            // Edge persistence: refresh per-sport stats and, when enabled,
+            // the auto-tuned taker threshold
+            if last_edge_tune.elapsed() >= EDGE_TUNE_INTERVAL {
+                last_edge_tune = Instant::now();
+                if let Ok(ep) = edge_persistence.lock() {
+                    tune_taker_thresholds(
+                        &ep,
+                        &mut sport_pipelines,
+                        &edge_persistence_config,
+                        &state_tx_engine,
+                    );
+                }
+            }
 
-                            // Submit sell order
-                            if let Some(ref exec) = executor {
-                                match exec.submit_order(
-                                    &position.ticker,
-                                    position.quantity,
-                                    exit_price,
-                                    false, // is_buy = false for sell
-                                    is_taker_exit,
-                                    "yes",
-                                ).await {
-                                    Ok(_order_id) => {
-                                        // Calculate P&L
-                                        let exit_revenue = (position.quantity * exit_price) as i64;
-                                        let exit_fee = calculate_fee(exit_price, position.quantity, is_taker_exit) as i64;
-                                        let pnl = (exit_revenue - exit_fee) - position.entry_cost_cents as i64;
-
-                                        // Update RiskManager
-                                        if let Some(ref mut rm) = risk_manager {
-                                            rm.record_sell(&position.ticker, position.quantity);
-                                        }
+            // Memory guard: refresh the gauge, and compact on schedule or
+            // whenever resident memory is over the configured limit
+            if last_memory_check.elapsed() >= MEMORY_CHECK_INTERVAL {
+                last_memory_check = Instant::now();
+                let rss_bytes = engine::compaction::rss_bytes();
+                let limit_bytes = compaction_config.memory_limit_mb * 1024 * 1024;
+                let over_limit = limit_bytes > 0 && rss_bytes.is_some_and(|b| b > limit_bytes);
+                if over_limit && !over_memory_limit {
+                    let msg = format!(
+                        "memory {} MB over {} MB limit, compacting",
+                        rss_bytes.unwrap_or(0) / (1024 * 1024),
+                        compaction_config.memory_limit_mb
+                    );
+                    tracing::warn!("{}", msg);
+                    state_tx_engine.send_modify(|s| s.push_log("WARN", msg));
+                }
+                over_memory_limit = over_limit;
+
+                let interval = Duration::from_secs(compaction_config.interval_secs);
+                if over_limit || last_compaction.elapsed() >= interval {
+                    last_compaction = Instant::now();
+                    let mut held: HashSet<String> = {
+                        let s = state_tx_engine.borrow();
+                        s.sim_positions
+                            .iter()
+                            .map(|p| p.ticker.clone())
+                            .chain(s.positions.iter().map(|p| p.ticker.clone()))
+                            .collect()
+                    };
+                    if let Some(ref l) = live {
+                        held.extend(l.held_tickers());
+                    }
+                    let (report, dropped) = compact_engine_state(
+                        &mut *market_index.write().await,
+                        &live_book_engine,
+                        &edge_persistence,
+                        &mut sport_pipelines,
+                        &held,
+                        compaction_config.retention_days,
+                    );
+                    if !dropped.is_empty() {
+                        tracing::info!(count = dropped.len(), "unsubscribing compacted markets");
+                        let _ = ws_command_tx.send(kalshi::ws::WsCommand::Unsubscribe(dropped));
+                    }
+                    if report.total() > 0 {
+                        tracing::info!(
+                            games = report.games,
+                            books = report.books,
+                            trackers = report.trackers,
+                            "compacted engine state"
+                        );
+                    }
+                    last_compacted = report.total();
+                }
 
-                                        // Update PositionTracker
-                                        pt.record_exit(&position.ticker);
+                let memory = tui::state::MemoryStats {
+                    rss_bytes,
+                    indexed_games: market_index.read().await.len(),
+                    books: live_book_engine.len(),
+                    trackers: sport_pipelines.iter().map(|p| p.tracked_entries()).sum(),
+                    last_compacted,
+                };
+                state_tx_engine.send_modify(|s| s.memory = memory);
+            }
 
-                                        // Complete pending exit order
-                                        if let Some(ref mut po) = pending_orders {
-                                            po.complete(&position.ticker, OrderSide::Exit);
-                                        }
+            // Cross-venue arbitrage: pairs across Kalshi and Polymarket that
+            // pay 100c whoever wins, for less than 100c after fees
+            if polymarket_config.enabled {
+                let params = engine::cross_arb::ArbParams {
+                    max_quantity: risk_config.max_contracts_per_market,
+                    min_profit_cents: polymarket_config.arb_min_profit_cents,
+                    polymarket_fee_bps: polymarket_config.taker_fee_bps,
+                };
+                let index = market_index.read().await;
+                let signals = engine::cross_arb::scan(
+                    &index,
+                    |ticker, side| live_book_engine.read(ticker, |b| b.best_ask(side)).flatten(),
+                    &params,
+                );
+                drop(index);
+                let mut current = HashSet::new();
+                for signal in &signals {
+                    let key = (
+                        signal.yes.ticker.clone(),
+                        signal.no.ticker.clone(),
+                        signal.yes.price,
+                        signal.no.price,
+                    );
+                    if !seen_arbs.contains(&key) {
+                        log_arb_signal(signal, &state_tx_engine);
+                    }
+                    current.insert(key);
+                }
+                seen_arbs = current;
+            }
 
-                                        // Push trade to TUI and update global P&L
-                                        let action = if is_timeout { "TIMEOUT" } else { "SELL" };
-                                        state_tx_engine.send_modify(|s| {
-                                            // Update global P&L counters
-                                            s.realized_pnl_cents += pnl;
-                                            s.total_trades += 1;
-                                            if pnl > 0 {
-                                                s.winning_trades += 1;
-                                            }
+            // Sprint mode: pre-build order templates for sprinting tickers and
+            // warm the API connection when the first sprint starts
+            if let Some(ref mut exec) = executor {
+                let sprint_tickers: HashSet<String> = evaluated
+                    .rows()
+                    .values()
+                    .filter(|r| r.sprint)
+                    .map(|r| r.ticker.clone())
+                    .collect();
+                let mut warmed = false;
+                for ticker in &sprint_tickers {
+                    warmed |= exec.prewarm(ticker);
+                }
+                if warmed {
+                    exec.warm_connection();
+                }
+                exec.release_templates(&sprint_tickers);
+            }
 
-                                            s.push_trade(tui::state::TradeRow {
-                                                time: chrono::Local::now().format("%H:%M:%S").to_string(),
-                                                action: action.to_string(),
-                                                ticker: position.ticker.clone(),
-                                                price: exit_price,
-                                                quantity: position.quantity,
-                                                order_type: if is_taker_exit { "TAKER" } else { "MAKER" }.to_string(),
-                                                pnl: Some(pnl as i32),
-                                                slippage: None,
-                                                source: String::new(),
-                                                fair_value_basis: String::new(),
-                                            });
-                                            s.push_log(
-                                                "ORDER",
-                                                format!(
-                                                    "{} {}x {} @ {}c, P&L: {:+}c",
-                                                    action, position.quantity, position.ticker, exit_price, pnl
-                                                ),
-                                            );
-                                        });
-                                    }
-                                    Err(e) => {
-                                        tracing::error!(
-                                            ticker = %position.ticker,
-                                            error = %e,
-                                            "exit order submission failed"
-                                        );
-                                        // Release pending exit order
-                                        if let Some(ref mut po) = pending_orders {
-                                            po.complete(&position.ticker, OrderSide::Exit);
-                                        }
-                                        state_tx_engine.send_modify(|s| {
-                                            s.push_log(
-                                                "ERROR",
-                                                format!("EXIT FAILED {}: {}", position.ticker, e),
-                                            );
-                                        });
-                                    }
-                                }
+            // Execute order intents (live mode only)
+            if !sim_mode_engine && !all_order_intents.is_empty() {
+                if let Some(ref exec) = executor {
+                    // Preflight: before the first real order, place and cancel a
+                    // far-from-market 1-lot to prove auth and both order paths.
+                    if !preflight_passed && !exec.is_dry_run() {
+                        match run_order_preflight(exec, &live_book_engine, &state_tx_engine).await
+                        {
+                            Some(true) => preflight_passed = true,
+                            Some(false) => {
+                                is_paused = true;
+                                state_tx_engine.send_modify(|s| s.is_paused = true);
                             }
+                            None => tracing::warn!(
+                                "preflight deferred: no book deep enough for a 1c resting bid"
+                            ),
                         }
                     }
+                    let live_intents: &[pipeline::OrderIntent] =
+                        if preflight_passed || exec.is_dry_run() {
+                            &all_order_intents
+                        } else {
+                            &[]
+                        };
+                    if let Some(ref mut l) = live {
+                        l.submit_entries(
+                            live_intents,
+                            exec,
+                            &rest_for_engine,
+                            &market_index,
+                            &live_book_engine,
+                            &risk_config,
+                            global_strategy.min_edge_after_fees,
+                            &mut available_balance_cents,
+                            &edge_persistence,
+                            cycle_start,
+                            &journal,
+                            &state_tx_engine,
+                        )
+                        .await;
+                    }
                 }
             }
 
-            // Check if any pipeline has live games (odds-feed via filter_live,
-            // score-feed via cached_scores since score-feed pipelines never
+            // Manage live exits: keep a resting sell at each position's target,
+            // re-price it as fair value drifts, and sell into the bid on an edge
+            // flip, stop-loss or timeout
+            if let (Some(l), Some(exec)) = (live.as_mut(), executor.as_ref()) {
+                let max_hold = Duration::from_secs(sim_config.realism.max_hold_seconds);
+                l.manage_exits(
+                    exec,
+                    &rest_for_engine,
+                    &live_book_engine,
+                    evaluated.rows(),
+                    &exit_config,
+                    &risk_config,
+                    max_hold,
+                    &state_tx_engine,
+                )
+                .await;
+            }
+
+            // Move between feed degradation tiers as feeds fail and recover
+            let feed_health = engine::degradation::FeedHealth {
+                kalshi_ws: state_tx_engine.borrow().kalshi_ws_connected,
+                scores: engine::degradation::FeedHealth::feed_up(
+                    sport_pipelines
+                        .iter()
+                        .filter(|p| {
+                            p.enabled && p.score_poller.is_some()
+                        })
+                        .map(|p| p.score_failures),
+                    degradation_config.failure_threshold,
+                ),
+                odds: engine::degradation::FeedHealth::feed_up(
+                    sport_pipelines
+                        .iter()
+                        .filter(|p| p.enabled && odds_feeds.contains(&p.odds_source))
+                        .map(|p| p.odds_failures),
+                    degradation_config.failure_threshold,
+                ),
+            };
+            if let Some(tier) = degradation.observe(
+                &feed_health,
+                Instant::now(),
+                Duration::from_secs(degradation_config.recover_secs),
+            ) {
+                let msg = format!("Feed tier {}: {}", tier.label(), feed_health.describe_down());
+                tracing::warn!("{}", msg);
+                state_tx_engine.send_modify(|s| {
+                    s.feed_tier = tier;
+                    s.push_log("WARN", msg);
+                });
+            }
+
+            // Check if any pipeline has live games (odds-feed via the live
+            // count, score-feed via cached_scores since score-feed pipelines never
             // populate commence_times).
-            let any_has_live = filter_live > 0
+            let any_has_live = filter_stats.live > 0
                 || sport_pipelines.iter().any(|p| {
                     p.enabled
                         && !p.cached_scores.is_empty()
@@ -1529,12 +3360,10 @@ async fn main() -> Result<()> {
                             .to_std()
                             .unwrap_or(Duration::from_secs(5));
                         // Cap to prevent too-long sleeps; determine shortest pre-game poll
-                        let min_pre_game_poll = odds_source_configs
-                            .values()
-                            .map(|c| c.pre_game_poll_s)
-                            .min()
-                            .unwrap_or(120);
-                        let capped_wait = wait.min(Duration::from_secs(min_pre_game_poll));
+                        let min_pre_game_poll = odds_feeds
+                            .shortest_pre_game_interval()
+                            .unwrap_or(Duration::from_secs(120));
+                        let capped_wait = wait.min(min_pre_game_poll);
 
                         // Update sport toggles before sleeping
                         let toggles: Vec<(String, String, char, bool)> = sport_pipelines
@@ -1547,16 +3376,14 @@ async fn main() -> Result<()> {
                             .iter()
                             .flat_map(|p| p.diagnostic_rows.clone())
                             .collect();
+                        let odds_health = odds_health(&sport_pipelines, &config.odds_sources);
                         state_tx_engine.send_modify(|state| {
                             state.markets = Vec::new();
                             state.live_sports = live_sports_empty;
-                            state.filter_stats = tui::state::FilterStats {
-                                live: filter_live,
-                                pre_game: filter_pre_game,
-                                closed: filter_closed,
-                            };
+                            state.filter_stats = filter_stats.clone();
                             state.next_game_start = earliest_commence;
                             state.diagnostic_rows = diag_rows;
+                            state.odds_health = odds_health;
                             state.diagnostic_snapshot = false;
                             state.sport_toggles = toggles;
                         });
@@ -1578,42 +3405,84 @@ async fn main() -> Result<()> {
                                     tui::TuiCommand::KillSwitch => {
                                         tracing::error!("KILL SWITCH ACTIVATED - halting all trading");
 
-                                        // Cancel all pending orders
-                                        if let Some(ref mut po) = pending_orders {
-                                            let orders = po.drain();
-                                            if !orders.is_empty() {
-                                                tracing::error!(count = orders.len(), "cancelling pending orders");
-                                                for order in &orders {
-                                                    if let Some(ref order_id) = order.order_id {
-                                                        if let Some(ref exec) = executor {
-                                                            if let Err(e) = exec.cancel_order(order_id).await {
-                                                                tracing::error!(order_id = %order_id, error = %e, "failed to cancel order");
-                                                            } else {
-                                                                tracing::info!(order_id = %order_id, "order cancelled");
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
+                                        // Cancel pending orders, resting entries and resting exits
+                                        if let (Some(l), Some(exec)) = (live.as_mut(), executor.as_ref()) {
+                                            l.cancel_all(exec, &rest_for_engine).await;
                                         }
 
                                         state_tx_engine.send_modify(|s| {
                                             s.is_paused = true;
                                             s.push_log("KILL", "KILL SWITCH ACTIVATED - all trading halted".to_string());
+                                            s.escalation.raise(
+                                                engine::AlertKind::KillSwitch,
+                                                "kill switch activated, all trading halted".to_string(),
+                                                Instant::now(),
+                                            );
                                         });
                                         return Ok(());
                                     }
+                                    tui::TuiCommand::AcknowledgeAlerts => {
+                                        handle_acknowledge_alerts(&state_tx_engine);
+                                    }
+                                    tui::TuiCommand::ReloadCredentials => {
+                                        handle_reload_credentials(
+                                            &auth_engine, &rest_for_engine,
+                                            &shared_odds_api_key, &state_tx_engine,
+                                        ).await;
+                                    }
                                     tui::TuiCommand::ToggleSport(sport_key) => {
                                         handle_toggle_sport(&mut sport_pipelines, &config_path, &sport_key);
                                     }
+                                    tui::TuiCommand::AnnotateTrade { trade_id, note } => {
+                                        handle_annotate_trade(trade_id, note, &state_tx_engine, &journal);
+                                    }
+                                    tui::TuiCommand::ToggleBookRecording(ticker) => {
+                                        handle_toggle_book_recording(&book_recorder_engine, &ticker, &state_tx_engine);
+                                    }
+                                    tui::TuiCommand::OpenBookReplay(ticker) => {
+                                        handle_open_book_replay(&book_recorder_engine, &ticker, &state_tx_engine);
+                                    }
+                                    tui::TuiCommand::CloseBookReplay => {
+                                        state_tx_engine.send_modify(|s| s.book_replay = None);
+                                    }
+                                    tui::TuiCommand::OpenDepthLadder(ticker) => {
+                                        handle_open_depth_ladder(&live_book_engine, &ticker, &state_tx_engine);
+                                    }
+                                    tui::TuiCommand::CloseDepthLadder => {
+                                        state_tx_engine.send_modify(|s| s.depth_ladder = None);
+                                    }
+                                    tui::TuiCommand::ManualOrder(order) => manual_orders.push(order),
+                                    tui::TuiCommand::ToggleBlacklist(key) => {
+                                        handle_toggle_game_filter(&key, false, &config_path, &state_tx_engine);
+                                    }
+                                    tui::TuiCommand::ToggleWhitelist(key) => {
+                                        handle_toggle_game_filter(&key, true, &config_path, &state_tx_engine);
+                                    }
+                                    tui::TuiCommand::OpenWinProb => {
+                                        handle_open_win_prob(&sport_pipelines, &state_tx_engine);
+                                    }
+                                    tui::TuiCommand::CloseWinProb => {
+                                        state_tx_engine.send_modify(|s| s.win_prob_grids.clear());
+                                    }
+                                    tui::TuiCommand::OpenPortfolio => {
+                                        handle_open_portfolio(&journal, &state_tx_engine);
+                                    }
+                                    tui::TuiCommand::ClosePortfolio => {
+                                        state_tx_engine.send_modify(|s| s.portfolio = None);
+                                    }
+                                    // Only a `--replay` session steps through events
+                                    tui::TuiCommand::StepReplay => {}
+                                    tui::TuiCommand::Profile => {
+                                        handle_start_profile(&profiler_engine, &state_tx_engine);
+                                    }
                                     tui::TuiCommand::FetchDiagnostic => {
                                         handle_fetch_diagnostic(
-                                            &mut sport_pipelines, &mut odds_sources,
-                                            &mut api_request_times, &state_tx_engine, &market_index,
+                                            &mut sport_pipelines, &odds_feeds,
+                                            &api_request_times, &state_tx_engine, &*market_index.read().await,
                                         ).await;
                                     }
                                     tui::TuiCommand::OpenConfig => {
-                                        let available_odds_sources: Vec<String> = odds_sources.keys().cloned().collect();
+                                        let available_odds_sources = odds_feeds.sources();
                                         let tabs = tui::config_view::build_config_tabs(
                                             &sport_pipelines,
                                             &global_strategy,
@@ -1621,6 +3490,7 @@ async fn main() -> Result<()> {
                                             &risk_config,
                                             &sim_config,
                                             &available_odds_sources,
+                                            &state_tx_engine.borrow().kelly_tuner,
                                         );
                                         let cv = tui::config_view::ConfigViewState::new(tabs);
                                         state_tx_engine.send_modify(|s| {
@@ -1645,7 +3515,7 @@ async fn main() -> Result<()> {
                                             }
 
                                             // If changing fair_value to an odds source, also persist odds_source
-                                            if field_path.ends_with(".fair_value") && value != "score-feed" && value != "odds-feed" {
+                                            if field_path.ends_with(".fair_value") && !engine::fair_value::is_builtin(&value) {
                                                 let odds_source_path = field_path.replace(".fair_value", ".odds_source");
                                                 if let Err(e) = config::persist_field(&config_path, &odds_source_path, &value) {
                                                     tracing::warn!(path = %odds_source_path, error = %e, "failed to persist odds_source");
@@ -1671,14 +3541,7 @@ async fn main() -> Result<()> {
                 }
             }
 
-            // Collect accumulated rows, sort by momentum descending then edge
-            let mut market_rows: Vec<MarketRow> = accumulated_rows.values().cloned().collect();
-            market_rows.sort_by(|a, b| {
-                b.momentum_score
-                    .partial_cmp(&a.momentum_score)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-                    .then_with(|| b.edge.cmp(&a.edge))
-            });
+            let market_rows = evaluated.sorted_rows();
 
             // Build live_sports from pipeline commence times
             let mut live_sports: Vec<String> = sport_pipelines
@@ -1710,17 +3573,15 @@ async fn main() -> Result<()> {
                 .iter()
                 .flat_map(|p| p.diagnostic_rows.clone())
                 .collect();
+            let odds_health = odds_health(&sport_pipelines, &config.odds_sources);
 
             state_tx_engine.send_modify(|state| {
                 state.markets = market_rows;
                 state.live_sports = live_sports;
-                state.filter_stats = tui::state::FilterStats {
-                    live: filter_live,
-                    pre_game: filter_pre_game,
-                    closed: filter_closed,
-                };
+                state.filter_stats = filter_stats;
                 state.next_game_start = earliest_commence;
                 state.diagnostic_rows = diag_rows;
+                state.odds_health = odds_health;
                 state.diagnostic_snapshot = false;
                 state.sport_toggles = toggles;
             });
@@ -1732,21 +3593,67 @@ async fn main() -> Result<()> {
                         s.balance_cents = balance;
                     });
                 }
+
+                // Reconcile expected vs realized fees for submitted orders
+                if let Some(ref mut l) = live {
+                    if !l.expected_fees.is_empty()
+                        && last_fee_reconcile.elapsed() >= Duration::from_secs(5)
+                    {
+                        reconcile_fees(
+                            &rest_for_engine,
+                            &mut l.expected_fees,
+                            &mut l.ws_filled,
+                            &state_tx_engine,
+                        )
+                        .await;
+                        last_fee_reconcile = Instant::now();
+                    }
+                }
             }
 
-            // Short sleep
-            tokio::time::sleep(Duration::from_secs(1)).await;
+            // Short sleep, cut short to land just after an expected scoreboard
+            // refresh or a sub-second adaptive score poll. Sprinting games
+            // re-evaluate every sprint tick.
+            let now = Instant::now();
+            let sprinting = sport_pipelines
+                .iter()
+                .any(|p| p.enabled && !p.sprint_games.is_empty());
+            let max_sleep = if sprinting {
+                Duration::from_millis(sprint_config.tick_ms)
+            } else {
+                Duration::from_secs(1)
+            };
+            let wake_at = sport_pipelines
+                .iter()
+                .filter(|p| p.enabled)
+                .flat_map(|p| [p.next_aligned_score_poll, p.next_score_poll])
+                .flatten()
+                .filter(|&t| t > now)
+                .fold(now + max_sleep, Instant::min);
+            if !event_driven_config.enabled {
+                tokio::time::sleep_until(tokio::time::Instant::from_std(wake_at)).await;
+                continue;
+            }
+            // Or wake as soon as a watched book or streamed odds change,
+            // no sooner than the minimum interval after this cycle began
+            event_focus =
+                eval_loop::wait_for_change(&eval_bus, wake_at, cycle_start + event_min_interval)
+                    .await;
         }
-    });
+    }
+    .instrument(tracing::info_span!("engine")));
 
     // --- Phase 4: Process Kalshi WS events (update orderbook) ---
     let sim_mode_ws = sim_mode;
+    let feed_recorder_ws = feed_recorder;
     let state_tx_ws = state_tx.clone();
     let fill_sim_ws = fill_simulator.clone();
+    let risk_config_ws = config.risk.clone();
+    let ws_events_rx = ws_events.clone();
 
     tokio::spawn(async move {
-        while let Some(event) = kalshi_ws_rx.recv().await {
-            match event {
+        loop {
+            match ws_events_rx.recv().await {
                 kalshi::ws::KalshiWsEvent::Connected => {
                     state_tx_ws.send_modify(|s| {
                         s.kalshi_ws_connected = true;
@@ -1759,13 +3666,55 @@ async fn main() -> Result<()> {
                         s.push_log("WARN", format!("Kalshi WS disconnected: {}", reason));
                     });
                 }
+                kalshi::ws::KalshiWsEvent::SequenceGap {
+                    tickers,
+                    expected,
+                    got,
+                } => {
+                    // Stop pricing off books that missed a delta until the
+                    // resubscribe's snapshots rebuild them
+                    for ticker in &tickers {
+                        live_book_ws.remove(ticker);
+                    }
+                    state_tx_ws.send_modify(|s| {
+                        s.push_log(
+                            "WARN",
+                            format!(
+                                "Kalshi WS seq gap (expected {}, got {}): resyncing {} books",
+                                expected,
+                                got,
+                                tickers.len()
+                            ),
+                        );
+                    });
+                }
+                kalshi::ws::KalshiWsEvent::Fill(fill) => {
+                    if !sim_mode_ws && ws_fill_tx.send(fill).await.is_err() {
+                        tracing::debug!("engine stopped, dropping WS fill");
+                    }
+                }
                 kalshi::ws::KalshiWsEvent::Snapshot(snap) => {
                     let mut depth = DepthBook::new();
                     depth.apply_snapshot(&snap);
-                    let (yes_bid, _yes_ask, _no_bid, _no_ask) = depth.best_bid_ask();
+                    let (yes_bid, yes_ask, no_bid, no_ask) = depth.best_bid_ask();
+
+                    if let Ok(mut rec) = book_recorder_ws.lock() {
+                        rec.record(&snap.market_ticker, "SNAPSHOT".to_string(), || {
+                            depth.snapshot()
+                        });
+                    }
+                    if feed_recorder_ws.is_enabled() {
+                        feed_recorder_ws.record(backtest::RecordedData::Book {
+                            ticker: snap.market_ticker.clone(),
+                            book: depth.snapshot(),
+                            update: "SNAPSHOT".to_string(),
+                        });
+                    }
 
-                    if let Ok(mut book) = live_book_ws.lock() {
-                        book.insert(snap.market_ticker.clone(), depth);
+                    live_book_ws.insert(snap.market_ticker.clone(), depth);
+                    eval_bus_ws.book_changed(&snap.market_ticker);
+                    if let Ok(mut ep) = edge_persistence_ws.lock() {
+                        ep.on_book(&snap.market_ticker, yes_ask, no_ask, Instant::now());
                     }
 
                     if sim_mode_ws {
@@ -1782,22 +3731,27 @@ async fn main() -> Result<()> {
                                 }
 
                                 s.sim_exits_attempted += 1;
+                                let bid = if pos.side == "no" { no_bid } else { yes_bid };
 
-                                // Check for timeout first
+                                // Timeout, stop-loss or take-profit force a taker exit
                                 let held_secs = pos.filled_at.elapsed().as_secs();
                                 let max_hold = fill_sim.max_hold_seconds();
+                                let forced = if max_hold > 0 && held_secs > max_hold {
+                                    Some(ExitReason::Timeout)
+                                } else {
+                                    threshold_exit(pos.entry_price, bid, &risk_config_ws)
+                                };
 
-                                let (fill_result, is_timeout) = if max_hold > 0 && held_secs > max_hold {
-                                    // Force taker exit due to timeout
-                                    (fill_sim.force_taker_exit(yes_bid), true)
+                                let fill_result = if forced.is_some() {
+                                    fill_sim.force_taker_exit(bid)
                                 } else {
                                     // Try normal maker exit
-                                    (fill_sim.try_maker_exit(pos.sell_price, yes_bid), false)
+                                    fill_sim.try_maker_exit(pos.sell_price, bid)
                                 };
 
                                 match fill_result {
                                     crate::engine::FillResult::Filled { price } => {
-                                        filled_indices.push((i, price, is_timeout));
+                                        filled_indices.push((i, price, forced));
                                     }
                                     crate::engine::FillResult::Pending => {
                                         // Not filled this tick, try again next time
@@ -1808,38 +3762,45 @@ async fn main() -> Result<()> {
                                 }
                             }
 
-                            for (i, exit_price, is_timeout) in filled_indices.iter().rev() {
+                            for (i, exit_price, forced) in filled_indices.iter().rev() {
                                 let pos = s.sim_positions.remove(*i);
                                 let exit_revenue = (pos.quantity * exit_price) as i64;
                                 let exit_fee =
-                                    calculate_fee(*exit_price, pos.quantity, *is_timeout) as i64;
+                                    calculate_fee(*exit_price, pos.quantity, forced.is_some()) as i64;
                                 let entry_cost =
                                     (pos.quantity * pos.entry_price) as i64 + pos.entry_fee as i64;
                                 let pnl = (exit_revenue - exit_fee) - entry_cost;
 
                                 s.sim_balance_cents += exit_revenue - exit_fee;
                                 s.realized_pnl_cents += pnl;
+                                s.record_realized_edge(&pos.ticker, pnl);
                                 s.total_trades += 1;
                                 s.sim_exits_filled += 1;
-                                if *is_timeout {
+                                if *forced == Some(ExitReason::Timeout) {
                                     s.sim_timeout_exits += 1;
                                 }
                                 if pnl > 0 {
                                     s.winning_trades += 1;
                                 }
-                                let (sell_source, sell_basis) = pos
+                                let (sell_source, sell_basis, sell_phase) = pos
                                     .trace
                                     .as_ref()
                                     .map(|t| {
                                         let src = match &t.fair_value_method {
                                             pipeline::FairValueMethod::ScoreFeed { .. } => "score",
                                             pipeline::FairValueMethod::OddsFeed { .. } => "odds",
+                                            pipeline::FairValueMethod::OrderFlow { .. } => "flow",
                                         };
-                                        (src.to_string(), pipeline::format_fair_value_basis(t))
+                                        (
+                                            src.to_string(),
+                                            pipeline::format_fair_value_basis(t),
+                                            pipeline::trace_phase(t),
+                                        )
                                     })
                                     .unwrap_or_default();
-                                let action = if *is_timeout { "TIMEOUT" } else { "SELL" };
+                                let action = forced.map_or("SELL", ExitReason::label);
                                 s.push_trade(tui::state::TradeRow {
+                                    id: 0,
                                     time: chrono::Local::now().format("%H:%M:%S").to_string(),
                                     action: action.to_string(),
                                     ticker: pos.ticker.clone(),
@@ -1850,6 +3811,8 @@ async fn main() -> Result<()> {
                                     slippage: None,
                                     source: sell_source,
                                     fair_value_basis: sell_basis,
+                                    phase: sell_phase,
+                                    note: String::new(),
                                 });
                                 s.push_log(
                                     "TRADE",
@@ -1863,23 +3826,42 @@ async fn main() -> Result<()> {
                     }
                 }
                 kalshi::ws::KalshiWsEvent::Delta(delta) => {
-                    let ticker = delta.market_ticker.clone();
-
-                    if let Ok(mut book) = live_book_ws.lock() {
-                        let depth = book.entry(ticker.clone()).or_insert_with(DepthBook::new);
-                        if let Some(ref pd) = delta.price_dollars {
-                            depth.apply_delta_dollars(&delta.side, pd, delta.delta);
-                        } else if delta.price > 0 {
-                            depth.apply_delta(&delta.side, delta.price, delta.delta);
+                    let ticker = delta.market_ticker.as_str();
+
+                    let (yes_ask, no_ask) = live_book_ws.upsert(ticker, DepthBook::new, |depth| {
+                        let price = delta.price_cents();
+                        if price > 0 {
+                            depth.apply_delta(&delta.side, price, delta.delta);
+                        }
+                        // Event text is only built for a recorder that keeps it
+                        let event = || book_replay::delta_event(&delta.side, price, delta.delta);
+                        if feed_recorder_ws.is_enabled() {
+                            feed_recorder_ws.record(backtest::RecordedData::Book {
+                                ticker: ticker.to_string(),
+                                book: depth.snapshot(),
+                                update: event(),
+                            });
                         }
+                        if let Ok(mut rec) = book_recorder_ws.lock() {
+                            if rec.is_recording(ticker) {
+                                rec.record(ticker, event(), || depth.snapshot());
+                            }
+                        }
+                        let (_, yes_ask, _, no_ask) = depth.best_bid_ask();
+                        (yes_ask, no_ask)
+                    });
+                    eval_bus_ws.book_changed(ticker);
+                    if let Ok(mut ep) = edge_persistence_ws.lock() {
+                        ep.on_book(ticker, yes_ask, no_ask, Instant::now());
                     }
 
                     if sim_mode_ws {
-                        let yes_bid = if let Ok(book) = live_book_ws.lock() {
-                            book.get(&ticker).map(|d| d.best_bid_ask().0).unwrap_or(0)
-                        } else {
-                            0
-                        };
+                        let (yes_bid, no_bid) = live_book_ws
+                            .read(ticker, |d| {
+                                let (yes_bid, _, no_bid, _) = d.best_bid_ask();
+                                (yes_bid, no_bid)
+                            })
+                            .unwrap_or((0, 0));
 
                         // Lock FillSimulator for exit attempts (blocking since we're in sync context)
                         let mut fill_sim = fill_sim_ws.lock().await;
@@ -1892,22 +3874,27 @@ async fn main() -> Result<()> {
                                 }
 
                                 s.sim_exits_attempted += 1;
+                                let bid = if pos.side == "no" { no_bid } else { yes_bid };
 
-                                // Check for timeout first
+                                // Timeout, stop-loss or take-profit force a taker exit
                                 let held_secs = pos.filled_at.elapsed().as_secs();
                                 let max_hold = fill_sim.max_hold_seconds();
+                                let forced = if max_hold > 0 && held_secs > max_hold {
+                                    Some(ExitReason::Timeout)
+                                } else {
+                                    threshold_exit(pos.entry_price, bid, &risk_config_ws)
+                                };
 
-                                let (fill_result, is_timeout) = if max_hold > 0 && held_secs > max_hold {
-                                    // Force taker exit due to timeout
-                                    (fill_sim.force_taker_exit(yes_bid), true)
+                                let fill_result = if forced.is_some() {
+                                    fill_sim.force_taker_exit(bid)
                                 } else {
                                     // Try normal maker exit
-                                    (fill_sim.try_maker_exit(pos.sell_price, yes_bid), false)
+                                    fill_sim.try_maker_exit(pos.sell_price, bid)
                                 };
 
                                 match fill_result {
                                     crate::engine::FillResult::Filled { price } => {
-                                        filled_indices.push((i, price, is_timeout));
+                                        filled_indices.push((i, price, forced));
                                     }
                                     crate::engine::FillResult::Pending => {
                                         // Not filled this tick, try again next time
@@ -1918,38 +3905,45 @@ async fn main() -> Result<()> {
                                 }
                             }
 
-                            for (i, exit_price, is_timeout) in filled_indices.iter().rev() {
+                            for (i, exit_price, forced) in filled_indices.iter().rev() {
                                 let pos = s.sim_positions.remove(*i);
                                 let exit_revenue = (pos.quantity * exit_price) as i64;
                                 let exit_fee =
-                                    calculate_fee(*exit_price, pos.quantity, *is_timeout) as i64;
+                                    calculate_fee(*exit_price, pos.quantity, forced.is_some()) as i64;
                                 let entry_cost =
                                     (pos.quantity * pos.entry_price) as i64 + pos.entry_fee as i64;
                                 let pnl = (exit_revenue - exit_fee) - entry_cost;
 
                                 s.sim_balance_cents += exit_revenue - exit_fee;
                                 s.realized_pnl_cents += pnl;
+                                s.record_realized_edge(&pos.ticker, pnl);
                                 s.total_trades += 1;
                                 s.sim_exits_filled += 1;
-                                if *is_timeout {
+                                if *forced == Some(ExitReason::Timeout) {
                                     s.sim_timeout_exits += 1;
                                 }
                                 if pnl > 0 {
                                     s.winning_trades += 1;
                                 }
-                                let (sell_source, sell_basis) = pos
+                                let (sell_source, sell_basis, sell_phase) = pos
                                     .trace
                                     .as_ref()
                                     .map(|t| {
                                         let src = match &t.fair_value_method {
                                             pipeline::FairValueMethod::ScoreFeed { .. } => "score",
                                             pipeline::FairValueMethod::OddsFeed { .. } => "odds",
+                                            pipeline::FairValueMethod::OrderFlow { .. } => "flow",
                                         };
-                                        (src.to_string(), pipeline::format_fair_value_basis(t))
+                                        (
+                                            src.to_string(),
+                                            pipeline::format_fair_value_basis(t),
+                                            pipeline::trace_phase(t),
+                                        )
                                     })
                                     .unwrap_or_default();
-                                let action = if *is_timeout { "TIMEOUT" } else { "SELL" };
+                                let action = forced.map_or("SELL", ExitReason::label);
                                 s.push_trade(tui::state::TradeRow {
+                                    id: 0,
                                     time: chrono::Local::now().format("%H:%M:%S").to_string(),
                                     action: action.to_string(),
                                     ticker: pos.ticker.clone(),
@@ -1960,6 +3954,8 @@ async fn main() -> Result<()> {
                                     slippage: None,
                                     source: sell_source,
                                     fair_value_basis: sell_basis,
+                                    phase: sell_phase,
+                                    note: String::new(),
                                 });
                                 s.push_log(
                                     "TRADE",
@@ -1974,29 +3970,66 @@ async fn main() -> Result<()> {
                 }
             }
         }
-    });
+    }
+    .instrument(tracing::info_span!("ws_events")));
 
     // --- Phase 4b: WS display refresh tick (200ms) ---
     let live_book_display = live_book.clone();
     let state_tx_display = state_tx.clone();
+    let ws_events_display = ws_events.clone();
     tokio::spawn(async move {
         let mut interval = tokio::time::interval(Duration::from_millis(200));
         interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut snapshot: HashMap<String, (u32, u32, u32, u32)> = HashMap::new();
+        let mut seen_epoch = None;
         loop {
             interval.tick().await;
-            let snapshot: HashMap<String, (u32, u32, u32, u32)> =
-                if let Ok(book) = live_book_display.lock() {
-                    book.iter()
-                        .map(|(k, v)| (k.clone(), v.best_bid_ask()))
-                        .collect()
-                } else {
-                    continue;
-                };
+            let ladder_ticker = state_tx_display
+                .borrow()
+                .depth_ladder
+                .as_ref()
+                .map(|l| l.ticker.clone());
+            // Only walk every book when one changed since the last tick
+            let epoch = live_book_display.epoch();
+            let changed = seen_epoch != Some(epoch);
+            if changed {
+                snapshot.clear();
+                live_book_display.for_each(|k, v| {
+                    snapshot.insert(k.to_string(), v.best_bid_ask());
+                });
+                seen_epoch = Some(epoch);
+            }
+            let ladder = ladder_ticker.and_then(|t| {
+                live_book_display
+                    .read(&t, DepthBook::snapshot)
+                    .map(|b| (t, b))
+            });
+            let ws_queue = ws_events_display.stats();
             if snapshot.is_empty() {
                 continue;
             }
             state_tx_display.send_modify(|state| {
-                state.live_book = snapshot.clone();
+                state.ws_queue = ws_queue;
+                if changed {
+                    state.live_book = snapshot.clone();
+                }
+                let now = Instant::now();
+                let held: Vec<String> =
+                    state.held_tickers().into_iter().map(String::from).collect();
+                for ticker in &held {
+                    if let Some(&(yes_bid, _, _, _)) = snapshot.get(ticker).filter(|b| b.0 > 0) {
+                        state.price_history.record(ticker, yes_bid, now);
+                    }
+                }
+                state
+                    .price_history
+                    .retain(&held.iter().map(String::as_str).collect::<Vec<_>>());
+                if let Some((ticker, book)) = ladder {
+                    // Only refresh the ladder still open, not one closed meanwhile
+                    if let Some(l) = state.depth_ladder.as_mut().filter(|l| l.ticker == ticker) {
+                        l.book = book;
+                    }
+                }
                 for row in &mut state.markets {
                     if let Some(&(yb, ya, _, _)) = snapshot.get(&row.ticker) {
                         if ya > 0 {
@@ -2010,8 +4043,71 @@ async fn main() -> Result<()> {
         }
     });
 
-    // --- Phase 5: Run TUI (blocks until quit) ---
-    tui::run_tui(state_rx, cmd_tx).await?;
+    // --- Phase 4c: Critical alert escalation (live mode) ---
+    if !sim_mode && escalation_config.enabled {
+        let state_tx_escalation = state_tx.clone();
+        let notifier = notify::Notifier::from_config(&escalation_config, &network)?;
+        if notifier.is_empty() {
+            tracing::warn!("no escalation channel configured; critical alerts stay in the TUI");
+        }
+        let ack_timeout = Duration::from_secs(escalation_config.ack_timeout_secs);
+        let ws_down_threshold = Duration::from_secs(escalation_config.ws_down_secs);
+        tokio::spawn(async move {
+            let mut feed_watch = engine::FeedDownWatch::default();
+            let mut interval = tokio::time::interval(Duration::from_secs(5));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                let now = Instant::now();
+                let mut due = Vec::new();
+                state_tx_escalation.send_modify(|s| {
+                    let live_games = s.filter_stats.live > 0;
+                    if let Some(down) = feed_watch.observe(
+                        s.kalshi_ws_connected,
+                        live_games,
+                        now,
+                        ws_down_threshold,
+                    ) {
+                        let msg = format!(
+                            "Kalshi WS down {}s with {} live game(s)",
+                            down.as_secs(),
+                            s.filter_stats.live
+                        );
+                        tracing::error!("{}", msg);
+                        s.escalation
+                            .raise(engine::AlertKind::FeedDown, msg.clone(), now);
+                        s.push_log("ERROR", msg);
+                    }
+                    due = s.escalation.due(now, ack_timeout);
+                });
+                for alert in due {
+                    let delivered = notifier.send(&alert).await;
+                    tracing::error!(
+                        kind = alert.kind.label(),
+                        delivered,
+                        "critical alert unacknowledged, escalating"
+                    );
+                    state_tx_escalation.send_modify(|s| {
+                        s.push_log(
+                            "ERROR",
+                            format!(
+                                "ESCALATED {}: {}{}",
+                                alert.kind.label(),
+                                alert.message,
+                                if delivered { "" } else { " (not delivered)" }
+                            ),
+                        );
+                    });
+                }
+            }
+        });
+    }
+
+    // --- Phase 5: Run TUI, or stream JSON events headless (blocks until quit) ---
+    match headless {
+        Some(sink) => headless::run(state_rx, cmd_tx, sink).await?,
+        None => tui::run_tui(state_rx, cmd_tx).await?,
+    }
 
     tracing::debug!("shutting down");
     Ok(())
@@ -2020,6 +4116,7 @@ async fn main() -> Result<()> {
 #[cfg(test)]
 mod depth_book_tests {
     use super::*;
+    use kalshi::types::DollarCents;
 
     #[test]
     fn test_empty_book_returns_zeros() {
@@ -2034,8 +4131,8 @@ mod depth_book_tests {
             market_ticker: "TEST".into(),
             yes: vec![],
             no: vec![],
-            yes_dollars: vec![("0.5500".into(), 10), ("0.5400".into(), 20)],
-            no_dollars: vec![("0.4800".into(), 5), ("0.4700".into(), 15)],
+            yes_dollars: vec![(DollarCents(55), 10), (DollarCents(54), 20)],
+            no_dollars: vec![(DollarCents(48), 5), (DollarCents(47), 15)],
         };
         book.apply_snapshot(&snap);
         assert_eq!(book.best_bid_ask(), (55, 52, 48, 45));
@@ -2055,6 +4152,21 @@ mod depth_book_tests {
         assert_eq!(book.best_bid_ask(), (60, 58, 42, 40));
     }
 
+    #[test]
+    fn test_best_bid_excluding_own_order() {
+        let mut book = DepthBook::new();
+        book.apply_delta("yes", 50, 3);
+        book.apply_delta("yes", 47, 10);
+        // Our 3 at 50 alone: others bid 47
+        assert_eq!(book.best_bid_excluding("yes", 50, 3), 47);
+        // Someone joined us at 50
+        book.apply_delta("yes", 50, 2);
+        assert_eq!(book.best_bid_excluding("yes", 50, 3), 50);
+        // Resting below the touch leaves it as is
+        assert_eq!(book.best_bid_excluding("yes", 47, 10), 50);
+        assert_eq!(book.best_bid_excluding("no", 50, 3), 0);
+    }
+
     #[test]
     fn test_snapshot_replaces_previous() {
         let mut book = DepthBook::new();
@@ -2062,8 +4174,8 @@ mod depth_book_tests {
             market_ticker: "TEST".into(),
             yes: vec![],
             no: vec![],
-            yes_dollars: vec![("0.9000".into(), 10)],
-            no_dollars: vec![("0.1500".into(), 5)],
+            yes_dollars: vec![(DollarCents(90), 10)],
+            no_dollars: vec![(DollarCents(15), 5)],
         };
         book.apply_snapshot(&snap1);
         assert_eq!(book.best_bid_ask().0, 90);
@@ -2072,8 +4184,8 @@ mod depth_book_tests {
             market_ticker: "TEST".into(),
             yes: vec![],
             no: vec![],
-            yes_dollars: vec![("0.5000".into(), 10)],
-            no_dollars: vec![("0.5200".into(), 5)],
+            yes_dollars: vec![(DollarCents(50), 10)],
+            no_dollars: vec![(DollarCents(52), 5)],
         };
         book.apply_snapshot(&snap2);
         assert_eq!(book.best_bid_ask().0, 50);
@@ -2086,8 +4198,8 @@ mod depth_book_tests {
             market_ticker: "TEST".into(),
             yes: vec![],
             no: vec![],
-            yes_dollars: vec![("0.5000".into(), 10)],
-            no_dollars: vec![("0.5200".into(), 5)],
+            yes_dollars: vec![(DollarCents(50), 10)],
+            no_dollars: vec![(DollarCents(52), 5)],
         };
         book.apply_snapshot(&snap);
         book.apply_delta("yes", 55, 20);
@@ -2102,8 +4214,8 @@ mod depth_book_tests {
             market_ticker: "TEST".into(),
             yes: vec![],
             no: vec![],
-            yes_dollars: vec![("0.5500".into(), 10), ("0.5000".into(), 20)],
-            no_dollars: vec![("0.4800".into(), 5)],
+            yes_dollars: vec![(DollarCents(55), 10), (DollarCents(50), 20)],
+            no_dollars: vec![(DollarCents(48), 5)],
         };
         book.apply_snapshot(&snap);
         assert_eq!(book.best_bid_ask().0, 55);
@@ -2118,11 +4230,292 @@ mod depth_book_tests {
             market_ticker: "TEST".into(),
             yes: vec![],
             no: vec![],
-            yes_dollars: vec![("0.5000".into(), 10)],
-            no_dollars: vec![("0.5200".into(), 5)],
+            yes_dollars: vec![(DollarCents(50), 10)],
+            no_dollars: vec![(DollarCents(52), 5)],
         };
         book.apply_snapshot(&snap);
-        book.apply_delta_dollars("yes", "0.5500", 20);
+        let delta: kalshi::types::OrderbookDelta = serde_json::from_str(
+            r#"{"market_ticker":"TEST","delta":20,"side":"yes","price_dollars":"0.5500"}"#,
+        )
+        .unwrap();
+        book.apply_delta(&delta.side, delta.price_cents(), delta.delta);
         assert_eq!(book.best_bid_ask().0, 55);
     }
+
+    #[test]
+    fn test_best_level_cached_across_deltas() {
+        let mut book = DepthBook::from_snapshot(&journal::BookSnapshot {
+            yes: vec![(55, 3), (50, 10)],
+            no: vec![(40, 5)],
+        });
+        assert_eq!(book.total_depth(), 18);
+
+        // Emptying the best level falls back to the next one down
+        book.apply_delta("yes", 55, -3);
+        assert_eq!(book.best_bid_ask(), (50, 60, 40, 50));
+        book.apply_delta("yes", 50, -4);
+        assert_eq!(book.yes.best(), Some((50, 6)));
+        book.apply_delta("yes", 50, -6);
+        assert_eq!(book.best_bid_ask(), (0, 60, 40, 0));
+        assert!(book.ask_levels("no").is_empty());
+
+        // Out-of-range prices are ignored rather than skewing the book
+        book.apply_delta("no", 101, 50);
+        book.apply_delta("no", 45, 2);
+        assert_eq!(book.best_ask("yes").unwrap().price, 55);
+        assert_eq!(book.ask_levels("yes"), vec![(55, 2), (60, 5)]);
+        assert_eq!(book.total_depth(), 7);
+    }
+
+    #[test]
+    fn test_snapshot_copies_all_levels_best_first() {
+        let mut book = DepthBook::new();
+        let snap = kalshi::types::OrderbookSnapshot {
+            market_ticker: "TEST".into(),
+            yes: vec![[50, 10], [55, 3], [52, 7]],
+            no: vec![[40, 5], [44, 2]],
+            yes_dollars: vec![],
+            no_dollars: vec![],
+        };
+        book.apply_snapshot(&snap);
+        let depth = book.snapshot();
+        assert_eq!(depth.yes, vec![(55, 3), (52, 7), (50, 10)]);
+        assert_eq!(depth.no, vec![(44, 2), (40, 5)]);
+    }
+}
+
+#[cfg(test)]
+mod polymarket_routing_tests {
+    use super::*;
+
+    fn kalshi_side(ticker: &str) -> matcher::SideMarket {
+        matcher::SideMarket {
+            ticker: ticker.to_string(),
+            title: "Test".to_string(),
+            yes_bid: 0,
+            yes_ask: 0,
+            no_bid: 0,
+            no_ask: 0,
+            status: "open".to_string(),
+            close_time: None,
+        }
+    }
+
+    fn indexed_game() -> matcher::MarketIndex {
+        let date = chrono::NaiveDate::from_ymd_opt(2026, 1, 19).unwrap();
+        let key = matcher::generate_key("basketball", "Los Angeles L", "Boston", date).unwrap();
+        let mut index = matcher::MarketIndex::new();
+        index.insert(
+            key,
+            matcher::IndexedGame {
+                away: Some(kalshi_side("KXNBAGAME-26JAN19LALBOS-LAL")),
+                home: Some(kalshi_side("KXNBAGAME-26JAN19LALBOS-BOS")),
+                away_team: "Los Angeles L".to_string(),
+                home_team: "Boston".to_string(),
+                ..Default::default()
+            },
+        );
+        let events: Vec<polymarket::types::GammaEvent> = serde_json::from_str(
+            r#"[{"id": "1", "slug": "nba-lal-bos-2026-01-19", "markets": [{
+                "question": "Lakers vs. Celtics", "slug": "nba-lal-bos-2026-01-19",
+                "outcomes": "[\"Lakers\", \"Celtics\"]", "clobTokenIds": "[\"111\", \"222\"]",
+                "active": true, "closed": false}]}]"#,
+        )
+        .unwrap();
+        let tokens = index_polymarket_events(&mut index, "basketball", &events);
+        assert_eq!(tokens.len(), 2);
+        index
+    }
+
+    fn intent(ticker: &str, side: &str, price: u32) -> pipeline::OrderIntent {
+        pipeline::OrderIntent {
+            ticker: ticker.to_string(),
+            quantity: 2,
+            price,
+            is_buy: true,
+            is_taker: true,
+            edge: 5,
+            net_profit_estimate: 3,
+            fair_value: price + 5,
+            source: "espn".to_string(),
+            trace: pipeline::SignalTrace {
+                sport: "basketball".to_string(),
+                ticker: ticker.to_string(),
+                timestamp: Instant::now(),
+                fair_value_method: pipeline::FairValueMethod::ScoreFeed {
+                    source: "espn".to_string(),
+                },
+                fair_value_cents: price + 5,
+                fair_value_stderr: None,
+                inputs: pipeline::FairValueInputs::Score {
+                    home_score: 50,
+                    away_score: 45,
+                    elapsed_secs: 1200,
+                    period: "3".to_string(),
+                    phase: "Q3".to_string(),
+                    win_prob: 0.6,
+                },
+                best_bid: price - 2,
+                best_ask: price,
+                edge: 5,
+                action: "TAKER BUY".to_string(),
+                net_profit_estimate: 3,
+                quantity: 2,
+                momentum_score: 80.0,
+                momentum_gated: false,
+                skip_reason: None,
+            },
+            entry_cost_cents: price * 2 + 4,
+            sell_target: price + 4,
+            side: side.to_string(),
+            time_in_force: kalshi::types::TimeInForce::ImmediateOrCancel,
+            book: None,
+            venue: matcher::Venue::Kalshi,
+        }
+    }
+
+    #[test]
+    fn test_sim_manual_order_fills_only_when_marketable() {
+        let mut s = AppState::new();
+        let order = |is_buy: bool, price: u32| tui::ManualOrder {
+            ticker: "T".to_string(),
+            side: "yes".to_string(),
+            is_buy,
+            price,
+            quantity: 2,
+        };
+
+        fill_sim_manual_order(&mut s, &order(true, 45), 44, 47);
+        assert!(s.sim_positions.is_empty());
+        // A limit through the ask fills at the ask
+        fill_sim_manual_order(&mut s, &order(true, 50), 44, 47);
+        let held = &s.sim_positions[0];
+        assert_eq!((held.quantity, held.entry_price), (2, 47));
+        let cost = 2 * 47 + calculate_fee(47, 2, true) as i64;
+        assert_eq!(s.sim_balance_cents, 100_000 - cost);
+
+        fill_sim_manual_order(&mut s, &order(false, 52), 50, 53);
+        assert_eq!(s.sim_positions.len(), 1);
+        fill_sim_manual_order(&mut s, &order(false, 48), 50, 53);
+        assert!(s.sim_positions.is_empty());
+        let pnl = 2 * 50 - calculate_fee(50, 2, true) as i64 - cost;
+        assert_eq!(s.realized_pnl_cents, pnl);
+        assert_eq!(s.trades.back().unwrap().action, "MANUAL SELL");
+    }
+
+    #[test]
+    fn test_manual_sell_of_no_position_uses_no_bid() {
+        let mut depth = DepthBook::new();
+        depth.apply_delta("yes", 30, 10);
+        depth.apply_delta("no", 65, 10);
+        let sell = tui::ManualOrder {
+            ticker: "T".to_string(),
+            side: "yes".to_string(),
+            is_buy: false,
+            price: 60,
+            quantity: 2,
+        };
+        // The NO position is priced against the NO bid, not the YES bid
+        let (bid, _) = manual_order_touch(&depth, &sell, Some("no"));
+        assert_eq!(bid, 65);
+        assert_eq!(manual_order_touch(&depth, &sell, None).0, 30);
+
+        let mut s = AppState::new();
+        s.sim_positions.push(tui::state::SimPosition {
+            ticker: "T".to_string(),
+            side: "no".to_string(),
+            quantity: 2,
+            entry_price: 55,
+            sell_price: 70,
+            entry_fee: 0,
+            filled_at: Instant::now(),
+            signal_ask: 55,
+            trace: None,
+        });
+        fill_sim_manual_order(&mut s, &sell, bid, 0);
+        assert!(s.sim_positions.is_empty());
+        let pnl = 2 * 65 - calculate_fee(65, 2, true) as i64 - 2 * 55;
+        assert_eq!(s.realized_pnl_cents, pnl);
+
+        // Open positions are marked at their own side's bid too
+        let live_book = LiveBook::default();
+        live_book.insert("T".to_string(), depth);
+        let mut pt = engine::PositionTracker::new();
+        pt.record_entry("T".to_string(), "no", 2, 55, 110, 70, Instant::now(), true);
+        let rows = live_position_rows(&pt, &live_book);
+        assert_eq!(rows[0].unrealized_pnl, 2 * 65 - 110);
+    }
+
+    #[test]
+    fn test_polymarket_events_attach_to_kalshi_games() {
+        let index = indexed_game();
+        let game = index.values().next().unwrap();
+        assert_eq!(game.polymarket_home.as_ref().unwrap().ticker, "222");
+        assert_eq!(game.polymarket_away.as_ref().unwrap().ticker, "111");
+        assert_eq!(
+            polymarket_game_date("nba-lal-bos", Some("2026-01-20 00:30:00+00")),
+            chrono::NaiveDate::from_ymd_opt(2026, 1, 19)
+        );
+    }
+
+    #[test]
+    fn test_taker_routed_to_cheaper_polymarket_ask() {
+        let index = indexed_game();
+        let token_book = |ask: u32, size: i64| {
+            let books = engine::book_store::BookStore::new();
+            books.insert(
+                "111".to_string(),
+                DepthBook::from_snapshot(&journal::BookSnapshot {
+                    yes: vec![(ask - 2, 10)],
+                    no: vec![(100 - ask, size)],
+                }),
+            );
+            books
+        };
+        // NO on Boston backs the Lakers: token 111
+        let kalshi = intent("KXNBAGAME-26JAN19LALBOS-BOS", "no", 48);
+
+        let books = token_book(46, 10);
+        let routed = route_to_better_venue(&kalshi, &index, &books).unwrap();
+        assert_eq!(routed.ticker, "111");
+        assert_eq!(routed.side, "yes");
+        assert_eq!(routed.price, 46);
+        assert_eq!(routed.edge, 7);
+        assert_eq!(routed.entry_cost_cents, 92);
+        assert_eq!(routed.venue, matcher::Venue::Polymarket);
+
+        // Not cheaper, too thin, or a maker order: stays on Kalshi
+        let books = token_book(48, 10);
+        assert!(route_to_better_venue(&kalshi, &index, &books).is_none());
+        let books = token_book(46, 1);
+        assert!(route_to_better_venue(&kalshi, &index, &books).is_none());
+        let mut maker = kalshi.clone();
+        maker.is_taker = false;
+        let books = token_book(46, 10);
+        assert!(route_to_better_venue(&maker, &index, &books).is_none());
+    }
+}
+
+#[cfg(test)]
+mod game_filter_tests {
+    use super::*;
+
+    #[test]
+    fn test_persisted_game_filter_survives_reload() {
+        let dir = std::env::temp_dir().join(format!("game-filter-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        std::fs::write(&path, "[risk]\nkelly_fraction = 0.25\n").unwrap();
+
+        let mut filter = engine::game_filter::GameFilter::default();
+        filter.toggle_blacklist("KXNBAGAME-26MAR10LALBOS");
+        filter.toggle_whitelist("KXNHLGAME-26MAR10BOSNYR");
+        persist_game_filter(&path, &filter.to_config()).unwrap();
+
+        let doc: toml::Value = std::fs::read_to_string(&path).unwrap().parse().unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(doc["risk"]["kelly_fraction"].as_float(), Some(0.25));
+        let reloaded: config::GameFilterConfig = doc["game_filter"].clone().try_into().unwrap();
+        assert_eq!(engine::game_filter::GameFilter::new(&reloaded), filter);
+    }
 }
@@ -0,0 +1,332 @@
+//! Proxy, TLS and connect-timeout settings shared by every HTTP and WebSocket
+//! client, from the `[network]` config section.
+
+use crate::config::NetworkConfig;
+use anyhow::{Context, Result};
+use base64::Engine as _;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::CertificateDer;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::{Connector, MaybeTlsStream, WebSocketStream};
+
+/// `[network.proxies]` key of the Kalshi REST and WebSocket clients.
+pub const KALSHI: &str = "kalshi";
+/// `[network.proxies]` key of the Polymarket REST and WebSocket clients.
+pub const POLYMARKET: &str = "polymarket";
+/// `[network.proxies]` key of every sport's score feed.
+pub const SCORE_FEED: &str = "score-feed";
+/// `[network.proxies]` key of the escalation webhook, SMTP and Twilio clients.
+pub const ESCALATION: &str = "escalation";
+/// `[network.proxies]` key of every sport's news feed.
+pub const NEWS: &str = "news";
+
+/// Per-feed proxy value that bypasses the global proxy.
+const DIRECT: &str = "direct";
+
+/// Upper bound on a proxy's CONNECT response headers.
+const MAX_CONNECT_RESPONSE: usize = 8192;
+
+pub type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+#[derive(Clone)]
+pub struct Network {
+    connect_timeout: Duration,
+    proxy: Option<String>,
+    proxies: HashMap<String, String>,
+    /// Extra roots from `ca_bundle`, for reqwest clients.
+    ca_certs: Vec<reqwest::Certificate>,
+    /// Built-in roots plus `ca_bundle`, for WebSockets; `None` without a bundle.
+    ws_tls: Option<Arc<rustls::ClientConfig>>,
+}
+
+impl Default for Network {
+    fn default() -> Self {
+        Self::from_config(&NetworkConfig::default()).expect("default network config is valid")
+    }
+}
+
+impl Network {
+    /// Validate proxy URLs and load the CA bundle once, at startup.
+    pub fn from_config(config: &NetworkConfig) -> Result<Self> {
+        for url in config.proxy.iter().chain(config.proxies.values()) {
+            if url != DIRECT {
+                reqwest::Proxy::all(url).with_context(|| format!("invalid proxy {:?}", url))?;
+            }
+        }
+
+        let (ca_certs, ws_tls) = match &config.ca_bundle {
+            Some(path) => {
+                let pem = std::fs::read(path)
+                    .with_context(|| format!("failed to read CA bundle {}", path))?;
+                let ders = CertificateDer::pem_slice_iter(&pem)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| anyhow::anyhow!("invalid CA bundle {}: {:?}", path, e))?;
+                if ders.is_empty() {
+                    anyhow::bail!("CA bundle {} contains no certificates", path);
+                }
+                let mut roots = rustls::RootCertStore {
+                    roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+                };
+                let mut ca_certs = Vec::with_capacity(ders.len());
+                for der in ders {
+                    ca_certs.push(
+                        reqwest::Certificate::from_der(&der)
+                            .with_context(|| format!("invalid certificate in {}", path))?,
+                    );
+                    roots
+                        .add(der)
+                        .with_context(|| format!("invalid certificate in {}", path))?;
+                }
+                (ca_certs, Some(Arc::new(client_tls(roots)?)))
+            }
+            None => (Vec::new(), None),
+        };
+
+        Ok(Self {
+            connect_timeout: Duration::from_millis(config.connect_timeout_ms),
+            proxy: config.proxy.clone(),
+            proxies: config.proxies.clone(),
+            ca_certs,
+            ws_tls,
+        })
+    }
+
+    /// Proxy URL for `feed`: its `[network.proxies]` entry, else the global
+    /// `proxy`. `None` when the feed connects directly.
+    pub fn proxy_for(&self, feed: &str) -> Option<&str> {
+        let proxy = self.proxies.get(feed).or(self.proxy.as_ref())?;
+        (proxy != DIRECT).then_some(proxy.as_str())
+    }
+
+    /// A reqwest builder for `feed` with its proxy, the extra CA roots and the
+    /// connect timeout applied. Callers add their own request timeout etc.
+    pub fn client_builder(&self, feed: &str) -> reqwest::ClientBuilder {
+        let mut builder = reqwest::Client::builder().connect_timeout(self.connect_timeout);
+        match self.proxy_for(feed) {
+            Some(url) => {
+                let proxy = reqwest::Proxy::all(url).expect("proxy validated in from_config");
+                builder = builder.proxy(proxy);
+            }
+            // An explicit "direct" also ignores HTTP(S)_PROXY from the environment
+            None if self.proxies.get(feed).is_some_and(|p| p == DIRECT) => {
+                builder = builder.no_proxy();
+            }
+            None => {}
+        }
+        for cert in &self.ca_certs {
+            builder = builder.add_root_certificate(cert.clone());
+        }
+        builder
+    }
+
+    /// Open a WebSocket for `feed` through its proxy, trusting the extra CA
+    /// roots. WebSockets support `http://` (CONNECT) and `socks5://` /
+    /// `socks5h://` proxies; the TCP and proxy handshake share the connect
+    /// timeout.
+    pub async fn connect_ws(
+        &self,
+        feed: &str,
+        request: impl IntoClientRequest + Unpin,
+    ) -> Result<WsStream> {
+        let request = request
+            .into_client_request()
+            .context("failed to build WS request")?;
+        let uri = request.uri();
+        let host = uri.host().context("WS URL has no host")?.to_string();
+        let default_port = if uri.scheme_str() == Some("wss") {
+            443
+        } else {
+            80
+        };
+        let port = uri.port_u16().unwrap_or(default_port);
+
+        let stream = self.connect_tcp(feed, &host, port).await?;
+        let connector = self.ws_tls.clone().map(Connector::Rustls);
+        let (ws, _) =
+            tokio_tungstenite::client_async_tls_with_config(request, stream, None, connector)
+                .await?;
+        Ok(ws)
+    }
+
+    /// TLS settings for raw connections: built-in roots plus `ca_bundle`.
+    pub fn tls_config(&self) -> Result<Arc<rustls::ClientConfig>> {
+        if let Some(tls) = &self.ws_tls {
+            return Ok(tls.clone());
+        }
+        let roots = rustls::RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        };
+        Ok(Arc::new(client_tls(roots)?))
+    }
+
+    /// Open a TCP connection to `host:port` for `feed` through its proxy,
+    /// within the connect timeout.
+    pub async fn connect_tcp(&self, feed: &str, host: &str, port: u16) -> Result<TcpStream> {
+        tokio::time::timeout(self.connect_timeout, self.open_tcp(feed, host, port))
+            .await
+            .with_context(|| format!("connect to {}:{} timed out", host, port))?
+    }
+
+    async fn open_tcp(&self, feed: &str, host: &str, port: u16) -> Result<TcpStream> {
+        let Some(proxy) = self.proxy_for(feed) else {
+            return TcpStream::connect((host, port))
+                .await
+                .with_context(|| format!("failed to connect to {}:{}", host, port));
+        };
+        let url =
+            reqwest::Url::parse(proxy).with_context(|| format!("invalid proxy {:?}", proxy))?;
+        let proxy_host = url.host_str().context("proxy URL has no host")?;
+        match url.scheme() {
+            "http" => {
+                let proxy_port = url.port_or_known_default().unwrap_or(80);
+                let stream = TcpStream::connect((proxy_host, proxy_port))
+                    .await
+                    .with_context(|| format!("failed to connect to proxy {}", proxy_host))?;
+                http_connect(stream, &url, host, port).await
+            }
+            "socks5" | "socks5h" => {
+                let proxy_addr = (proxy_host, url.port().unwrap_or(1080));
+                let stream = if url.username().is_empty() {
+                    tokio_socks::tcp::Socks5Stream::connect(proxy_addr, (host, port)).await
+                } else {
+                    tokio_socks::tcp::Socks5Stream::connect_with_password(
+                        proxy_addr,
+                        (host, port),
+                        url.username(),
+                        url.password().unwrap_or(""),
+                    )
+                    .await
+                }
+                .with_context(|| format!("SOCKS proxy {} refused {}:{}", proxy_host, host, port))?;
+                Ok(stream.into_inner())
+            }
+            scheme => anyhow::bail!("unsupported WebSocket proxy scheme {:?}", scheme),
+        }
+    }
+}
+
+fn client_tls(roots: rustls::RootCertStore) -> Result<rustls::ClientConfig> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    Ok(rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .context("TLS protocol setup failed")?
+        .with_root_certificates(roots)
+        .with_no_client_auth())
+}
+
+/// Ask an HTTP proxy to tunnel `stream` to `host:port`.
+async fn http_connect(
+    mut stream: TcpStream,
+    proxy: &reqwest::Url,
+    host: &str,
+    port: u16,
+) -> Result<TcpStream> {
+    let mut request = format!(
+        "CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n",
+        host = host,
+        port = port
+    );
+    if !proxy.username().is_empty() {
+        let credentials = format!("{}:{}", proxy.username(), proxy.password().unwrap_or(""));
+        let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+        request.push_str(&format!("Proxy-Authorization: Basic {}\r\n", encoded));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).await?;
+
+    // Read the response headers byte by byte so nothing past them is consumed
+    let mut response = Vec::new();
+    let mut byte = [0u8; 1];
+    while !response.ends_with(b"\r\n\r\n") {
+        if stream.read(&mut byte).await? == 0 {
+            anyhow::bail!("proxy closed the connection during CONNECT");
+        }
+        response.push(byte[0]);
+        if response.len() > MAX_CONNECT_RESPONSE {
+            anyhow::bail!("proxy CONNECT response too long");
+        }
+    }
+    let status_line = String::from_utf8_lossy(&response);
+    let status_line = status_line.lines().next().unwrap_or_default();
+    if status_line.split_whitespace().nth(1) != Some("200") {
+        anyhow::bail!(
+            "proxy refused CONNECT to {}:{}: {}",
+            host,
+            port,
+            status_line
+        );
+    }
+    Ok(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network(proxy: Option<&str>, proxies: &[(&str, &str)]) -> Network {
+        Network::from_config(&NetworkConfig {
+            proxy: proxy.map(str::to_string),
+            proxies: proxies
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            ..NetworkConfig::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_per_feed_proxy_overrides_global() {
+        let net = network(
+            Some("http://corp:8080"),
+            &[
+                ("scraped-bovada", "socks5h://residential:1080"),
+                (KALSHI, "direct"),
+            ],
+        );
+        assert_eq!(
+            net.proxy_for("scraped-bovada"),
+            Some("socks5h://residential:1080")
+        );
+        assert_eq!(net.proxy_for(KALSHI), None);
+        assert_eq!(net.proxy_for(SCORE_FEED), Some("http://corp:8080"));
+        assert_eq!(network(None, &[]).proxy_for(KALSHI), None);
+
+        let bad = NetworkConfig {
+            proxy: Some("not a url".to_string()),
+            ..NetworkConfig::default()
+        };
+        assert!(Network::from_config(&bad).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_http_connect_tunnels_through_proxy() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let proxy = tokio::spawn(async move {
+            let (mut conn, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 1024];
+            let n = conn.read(&mut buf).await.unwrap();
+            conn.write_all(b"HTTP/1.1 200 Connection established\r\n\r\nhello")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let net = network(Some(&format!("http://user:pw@{}", addr)), &[]);
+        let mut stream = net.open_tcp(KALSHI, "api.example.com", 443).await.unwrap();
+        let request = proxy.await.unwrap();
+        assert!(request.starts_with("CONNECT api.example.com:443 HTTP/1.1\r\n"));
+        assert!(request.contains("Proxy-Authorization: Basic dXNlcjpwdw==\r\n"));
+
+        // Bytes after the CONNECT response belong to the tunnel
+        let mut tunneled = [0u8; 5];
+        stream.read_exact(&mut tunneled).await.unwrap();
+        assert_eq!(&tunneled, b"hello");
+    }
+}
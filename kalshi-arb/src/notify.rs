@@ -0,0 +1,370 @@
+//! Delivery of escalated critical alerts to the channels in `[escalation]`:
+//! a JSON webhook, email over SMTP and SMS through Twilio. Every configured
+//! channel gets each alert; one failing does not stop the others.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use base64::Engine as _;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+
+use crate::config::{EscalationConfig, SmtpConfig, SmtpSecurity, TwilioConfig};
+use crate::engine::escalation::CriticalAlert;
+use crate::network::{self, Network};
+
+/// Upper bound on one delivery attempt, connect to last reply.
+const SEND_TIMEOUT: Duration = Duration::from_secs(30);
+const TWILIO_API: &str = "https://api.twilio.com/2010-04-01";
+
+pub struct Notifier {
+    client: reqwest::Client,
+    network: Network,
+    webhook_url: Option<String>,
+    smtp: Option<(SmtpConfig, Option<String>)>,
+    twilio: Option<(TwilioConfig, String)>,
+}
+
+impl Notifier {
+    /// Resolve the channels and their secrets. A channel whose secret is
+    /// missing from the environment is a startup error, not a silent gap.
+    pub fn from_config(config: &EscalationConfig, network: &Network) -> Result<Self> {
+        let env = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let webhook_url = config
+            .webhook_url
+            .clone()
+            .or_else(|| env("ESCALATION_WEBHOOK_URL"));
+        let smtp = match &config.smtp {
+            Some(smtp) => {
+                let password = env("ESCALATION_SMTP_PASSWORD");
+                if smtp.username.is_some() && password.is_none() {
+                    anyhow::bail!(
+                        "[escalation.smtp] username set but ESCALATION_SMTP_PASSWORD is not"
+                    );
+                }
+                Some((smtp.clone(), password))
+            }
+            None => None,
+        };
+        let twilio = match &config.twilio {
+            Some(twilio) => {
+                let token = env("TWILIO_AUTH_TOKEN")
+                    .context("[escalation.twilio] configured but TWILIO_AUTH_TOKEN is not set")?;
+                Some((twilio.clone(), token))
+            }
+            None => None,
+        };
+        let client = network
+            .client_builder(network::ESCALATION)
+            .timeout(SEND_TIMEOUT)
+            .build()
+            .context("failed to build escalation HTTP client")?;
+        Ok(Self {
+            client,
+            network: network.clone(),
+            webhook_url,
+            smtp,
+            twilio,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.webhook_url.is_none() && self.smtp.is_none() && self.twilio.is_none()
+    }
+
+    /// Push `alert` to every channel. True when at least one accepted it.
+    pub async fn send(&self, alert: &CriticalAlert) -> bool {
+        let text = format!(
+            "kalshi-arb: {} unacknowledged for {}s: {}",
+            alert.kind.label(),
+            alert.raised_at.elapsed().as_secs(),
+            alert.message
+        );
+        let mut delivered = false;
+        if let Some(url) = &self.webhook_url {
+            match self.send_webhook(url, alert, &text).await {
+                Ok(()) => delivered = true,
+                Err(e) => tracing::error!(error = %e, "escalation webhook failed"),
+            }
+        }
+        if let Some((smtp, password)) = &self.smtp {
+            let subject = format!("kalshi-arb: {}", alert.kind.label());
+            let sent = tokio::time::timeout(
+                SEND_TIMEOUT,
+                send_mail(&self.network, smtp, password.as_deref(), &subject, &text),
+            )
+            .await
+            .unwrap_or_else(|_| Err(anyhow::anyhow!("SMTP delivery timed out")));
+            match sent {
+                Ok(()) => delivered = true,
+                Err(e) => tracing::error!(error = %e, "escalation email failed"),
+            }
+        }
+        if let Some((twilio, token)) = &self.twilio {
+            match self.send_sms(twilio, token, &text).await {
+                Ok(()) => delivered = true,
+                Err(e) => tracing::error!(error = %e, "escalation SMS failed"),
+            }
+        }
+        delivered
+    }
+
+    /// The body carries a `text` field so chat/SMS relays can forward it as-is.
+    async fn send_webhook(&self, url: &str, alert: &CriticalAlert, text: &str) -> Result<()> {
+        self.client
+            .post(url)
+            .json(&serde_json::json!({
+                "text": text,
+                "kind": alert.kind.label(),
+                "message": alert.message,
+            }))
+            .send()
+            .await
+            .context("escalation webhook request failed")?
+            .error_for_status()
+            .context("escalation webhook rejected")?;
+        Ok(())
+    }
+
+    async fn send_sms(&self, twilio: &TwilioConfig, token: &str, text: &str) -> Result<()> {
+        let url = format!(
+            "{}/Accounts/{}/Messages.json",
+            TWILIO_API, twilio.account_sid
+        );
+        for to in &twilio.to {
+            self.client
+                .post(&url)
+                .basic_auth(&twilio.account_sid, Some(token))
+                .form(&[("To", to.as_str()), ("From", &twilio.from), ("Body", text)])
+                .send()
+                .await
+                .context("Twilio request failed")?
+                .error_for_status()
+                .with_context(|| format!("Twilio rejected SMS to {}", to))?;
+        }
+        Ok(())
+    }
+}
+
+async fn send_mail(
+    network: &Network,
+    smtp: &SmtpConfig,
+    password: Option<&str>,
+    subject: &str,
+    body: &str,
+) -> Result<()> {
+    let tcp = network
+        .connect_tcp(network::ESCALATION, &smtp.host, smtp.port)
+        .await?;
+    let message = email_message(smtp, subject, body, chrono::Utc::now());
+    match smtp.security {
+        SmtpSecurity::Tls => {
+            let tls = tls_connect(network, &smtp.host, tcp).await?;
+            let mut conn = SmtpConn::new(tls);
+            conn.expect(&[220]).await?;
+            conn.deliver(smtp, password, &message).await
+        }
+        SmtpSecurity::Starttls => {
+            let mut conn = SmtpConn::new(tcp);
+            conn.expect(&[220]).await?;
+            conn.command("EHLO kalshi-arb", &[250]).await?;
+            conn.command("STARTTLS", &[220]).await?;
+            let tls = tls_connect(network, &smtp.host, conn.into_inner()).await?;
+            SmtpConn::new(tls).deliver(smtp, password, &message).await
+        }
+        SmtpSecurity::None => {
+            let mut conn = SmtpConn::new(tcp);
+            conn.expect(&[220]).await?;
+            conn.deliver(smtp, password, &message).await
+        }
+    }
+}
+
+async fn tls_connect(
+    network: &Network,
+    host: &str,
+    tcp: tokio::net::TcpStream,
+) -> Result<tokio_rustls::client::TlsStream<tokio::net::TcpStream>> {
+    let connector = tokio_rustls::TlsConnector::from(network.tls_config()?);
+    let name = rustls::pki_types::ServerName::try_from(host.to_string())
+        .with_context(|| format!("invalid SMTP host {:?}", host))?;
+    connector
+        .connect(name, tcp)
+        .await
+        .with_context(|| format!("TLS handshake with {} failed", host))
+}
+
+/// Headers and a dot-stuffed CRLF body, ready to follow DATA.
+fn email_message(
+    smtp: &SmtpConfig,
+    subject: &str,
+    body: &str,
+    date: chrono::DateTime<chrono::Utc>,
+) -> String {
+    let mut message = format!(
+        "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n",
+        smtp.from,
+        smtp.to.join(", "),
+        subject,
+        date.to_rfc2822()
+    );
+    for line in body.lines() {
+        if line.starts_with('.') {
+            message.push('.');
+        }
+        message.push_str(line);
+        message.push_str("\r\n");
+    }
+    message
+}
+
+/// Just enough of an SMTP client to submit one message.
+struct SmtpConn<S> {
+    stream: BufReader<S>,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> SmtpConn<S> {
+    fn new(stream: S) -> Self {
+        Self {
+            stream: BufReader::new(stream),
+        }
+    }
+
+    fn into_inner(self) -> S {
+        self.stream.into_inner()
+    }
+
+    /// Read one (possibly multi-line) reply and check its code.
+    async fn expect(&mut self, ok: &[u16]) -> Result<()> {
+        let mut reply = String::new();
+        loop {
+            let mut line = String::new();
+            if self.stream.read_line(&mut line).await? == 0 {
+                anyhow::bail!("SMTP server closed the connection");
+            }
+            reply.push_str(&line);
+            // "250-..." continues, "250 ..." ends the reply
+            if line.as_bytes().get(3) != Some(&b'-') {
+                let code: u16 = line
+                    .get(..3)
+                    .and_then(|c| c.parse().ok())
+                    .with_context(|| format!("malformed SMTP reply {:?}", line.trim_end()))?;
+                if !ok.contains(&code) {
+                    anyhow::bail!("SMTP server replied {}", reply.trim_end());
+                }
+                return Ok(());
+            }
+        }
+    }
+
+    async fn command(&mut self, line: &str, ok: &[u16]) -> Result<()> {
+        self.stream
+            .write_all(format!("{}\r\n", line).as_bytes())
+            .await?;
+        self.stream.flush().await?;
+        self.expect(ok).await
+    }
+
+    /// Everything after the greeting: EHLO, optional AUTH PLAIN, the
+    /// envelope and the message.
+    async fn deliver(
+        &mut self,
+        smtp: &SmtpConfig,
+        password: Option<&str>,
+        message: &str,
+    ) -> Result<()> {
+        self.command("EHLO kalshi-arb", &[250]).await?;
+        if let Some(username) = &smtp.username {
+            let credentials = format!("\0{}\0{}", username, password.unwrap_or(""));
+            let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+            self.command(&format!("AUTH PLAIN {}", encoded), &[235])
+                .await
+                .context("SMTP login failed")?;
+        }
+        self.command(&format!("MAIL FROM:<{}>", smtp.from), &[250])
+            .await?;
+        for to in &smtp.to {
+            self.command(&format!("RCPT TO:<{}>", to), &[250, 251])
+                .await?;
+        }
+        self.command("DATA", &[354]).await?;
+        self.command(&format!("{}.", message), &[250]).await?;
+        // The message is accepted; a failed goodbye does not matter
+        let _ = self.command("QUIT", &[221]).await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+
+    fn smtp_config(port: u16) -> SmtpConfig {
+        SmtpConfig {
+            host: "127.0.0.1".to_string(),
+            port,
+            security: SmtpSecurity::None,
+            username: Some("arb".to_string()),
+            from: "arb@example.com".to_string(),
+            to: vec!["me@example.com".to_string(), "you@example.com".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_email_message_dot_stuffs_body() {
+        let date = chrono::DateTime::from_timestamp(0, 0).unwrap();
+        let message = email_message(&smtp_config(25), "alert", "one\n.two", date);
+        assert!(message.starts_with(
+            "From: arb@example.com\r\nTo: me@example.com, you@example.com\r\nSubject: alert\r\n"
+        ));
+        assert!(message.ends_with("\r\n\r\none\r\n..two\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_smtp_dialog() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            let (mut conn, _) = listener.accept().await.unwrap();
+            let replies: [&[u8]; 9] = [
+                b"220 test ready\r\n",
+                b"250-test\r\n250 AUTH PLAIN\r\n",
+                b"235 ok\r\n",
+                b"250 ok\r\n",
+                b"250 ok\r\n",
+                b"251 forwarding\r\n",
+                b"354 go ahead\r\n",
+                b"250 queued\r\n",
+                b"221 bye\r\n",
+            ];
+            let mut transcript = Vec::new();
+            let mut buf = [0u8; 4096];
+            conn.write_all(replies[0]).await.unwrap();
+            for reply in &replies[1..] {
+                let n = conn.read(&mut buf).await.unwrap();
+                transcript.push(String::from_utf8_lossy(&buf[..n]).to_string());
+                conn.write_all(reply).await.unwrap();
+            }
+            transcript
+        });
+
+        send_mail(
+            &Network::default(),
+            &smtp_config(port),
+            Some("pw"),
+            "kalshi-arb: feed down",
+            "Kalshi WS down",
+        )
+        .await
+        .unwrap();
+
+        let transcript = server.await.unwrap();
+        assert_eq!(transcript[0], "EHLO kalshi-arb\r\n");
+        assert_eq!(transcript[1], "AUTH PLAIN AGFyYgBwdw==\r\n");
+        assert_eq!(transcript[2], "MAIL FROM:<arb@example.com>\r\n");
+        assert_eq!(transcript[4], "RCPT TO:<you@example.com>\r\n");
+        assert!(transcript[6].contains("Subject: kalshi-arb: feed down\r\n"));
+        assert!(transcript[6].ends_with("Kalshi WS down\r\n.\r\n"));
+        assert_eq!(transcript[7], "QUIT\r\n");
+    }
+}
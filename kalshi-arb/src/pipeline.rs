@@ -1,35 +1,69 @@
 use crate::config::{
-    MomentumConfig, OddsSourceConfig, ScoreFeedConfig, StrategyConfig, WinProbConfig,
+    AdaptivePollConfig, BlendConfig, MatchingConfig, MomentumConfig, NewsConfig, OddsSourcesConfig,
+    OrderFlowConfig, PreGameConfig, ScoreFeedConfig, SoccerModelConfig, SprintConfig,
+    StrategyConfig, WinProbConfig,
+};
+use crate::engine::drawdown::{DrawdownMonitor, DrawdownTier};
+use crate::engine::fair_value::{
+    self, Driver, FairValueModel, FairValues, GameInputs, ModelConfigs, OddsQuote, ScoreState,
 };
 use crate::engine::fees::calculate_fee;
+use crate::engine::fv_disagreement::{DisagreementAlert, DisagreementMonitor};
 use crate::engine::momentum::{BookPressureTracker, MomentumScorer, VelocityTracker};
-use crate::engine::win_prob::WinProbTable;
-use crate::engine::{matcher, strategy};
-use crate::feed::score_feed::{ScorePoller, ScoreUpdate};
-use crate::feed::types::OddsUpdate;
-use crate::feed::OddsFeed;
-use crate::tui::state::{AppState, DiagnosticRow, MarketRow};
+use crate::engine::order_flow::OrderFlowTracker;
+use crate::engine::risk::Exposure;
+use crate::engine::strategy::SkipReason;
+use crate::engine::win_prob::{GameSegment, WinProbGrid, WinProbTable};
+use crate::engine::{depth_walk, matcher, strategy, AlertKind};
+use crate::feed::adaptive_poll::{self, Volatility};
+use crate::feed::budget::PollDemand;
+use crate::feed::health::{FailoverEvent, HealthSummary, OddsFailover};
+use crate::feed::news::{NewsHalts, NewsPoller};
+use crate::feed::score_feed::{scoreboard_parsers, GamePhase, ScorePoller, ScoreUpdate};
+use crate::feed::supervisor::{Cadence, FeedSupervisor, Fetched};
+use crate::feed::types::{ApiQuota, OddsUpdate};
+use crate::feed::usage::UsagePurpose;
+use crate::journal::{BookSnapshot, Journal, JournalEntry};
+use crate::kalshi::types::TimeInForce;
+use crate::network::{self, Network};
+use crate::tui::state::{AppState, DiagnosticRow, FvDisagreementStat, MarketRow};
 use crate::LiveBook;
-use std::collections::{HashMap, VecDeque};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Mutex, PoisonError};
 use std::time::{Duration, Instant};
 use tokio::sync::watch;
 
-/// How this sport computes fair value.
-pub enum FairValueSource {
-    /// Live score data -> win probability model -> fair value in cents.
-    ScoreFeed {
-        poller: Box<ScorePoller>,
-        win_prob: WinProbTable,
-        regulation_secs: u16,
-        live_poll_s: u64,
-        pre_game_poll_s: u64,
-    },
-    /// Sportsbook odds -> devig -> fair value in cents.
-    OddsFeed,
+/// Simulated wall clock in ms since the epoch (0 = real time), set by `--backtest`.
+static SIMULATED_NOW_MS: AtomicI64 = AtomicI64::new(0);
+
+/// Pin the pipeline's wall clock to `now` for a backtest replay, or `None`
+/// to return to real time.
+pub fn set_simulated_now(now: Option<chrono::DateTime<chrono::Utc>>) {
+    SIMULATED_NOW_MS.store(now.map_or(0, |t| t.timestamp_millis()), Ordering::Relaxed);
+}
+
+/// Failover threshold and staleness limit of an odds source.
+fn failover_limits(configs: &OddsSourcesConfig, source: &str) -> (u32, Duration) {
+    configs
+        .get(source)
+        .map_or((3, Duration::from_secs(300)), |c| {
+            (c.failover_threshold, Duration::from_secs(c.stale_after_s))
+        })
+}
+
+/// Wall clock for live/closed/game-date decisions.
+fn utc_now() -> chrono::DateTime<chrono::Utc> {
+    match SIMULATED_NOW_MS.load(Ordering::Relaxed) {
+        0 => chrono::Utc::now(),
+        ms => chrono::DateTime::from_timestamp_millis(ms).unwrap_or_else(chrono::Utc::now),
+    }
 }
 
 /// What method produced a fair value.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum FairValueMethod {
     ScoreFeed {
         #[allow(dead_code)]
@@ -39,10 +73,15 @@ pub enum FairValueMethod {
         #[allow(dead_code)]
         source: String,
     },
+    OrderFlow {
+        #[allow(dead_code)]
+        source: String,
+    },
 }
 
 /// Raw inputs that led to a fair value calculation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
 #[allow(dead_code)]
 pub enum FairValueInputs {
     Score {
@@ -50,25 +89,62 @@ pub enum FairValueInputs {
         away_score: u32,
         elapsed_secs: u32,
         period: String,
+        /// Game phase label ("Q3", "HALF", "F2M", ...), see `GamePhase::label`.
+        phase: String,
         win_prob: f64,
     },
     Odds {
         home_odds: f64,
         away_odds: f64,
         bookmakers: Vec<String>,
+        /// Consensus weight of each of `bookmakers`, from `[odds_sources.weights]`.
+        bookmaker_weights: Vec<f64>,
         devigged_prob: f64,
+        /// Priced hours before the start for pre-game positioning.
+        pre_game: bool,
+    },
+    OrderFlow {
+        mid: f64,
+        mean: f64,
+        band: f64,
+        imbalance: f64,
+        flow: f64,
+        samples: usize,
+    },
+    /// Score model and devigged odds mixed by `fair_value = "blended"`.
+    Blended {
+        home_score: u32,
+        away_score: u32,
+        elapsed_secs: u32,
+        period: String,
+        phase: String,
+        home_odds: f64,
+        away_odds: f64,
+        bookmakers: Vec<String>,
+        bookmaker_weights: Vec<f64>,
+        /// This side's probability from the score model.
+        score_prob: f64,
+        /// This side's devigged odds probability.
+        odds_prob: f64,
+        /// Weight of `score_prob` at this point of the game; the odds got the rest.
+        score_weight: f64,
+        /// The blended probability that was traded on.
+        win_prob: f64,
     },
 }
 
 /// Full provenance for a trade signal -- carried by SimPosition.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 #[allow(dead_code)]
 pub struct SignalTrace {
     pub sport: String,
     pub ticker: String,
+    #[serde(skip)]
     pub timestamp: Instant,
     pub fair_value_method: FairValueMethod,
     pub fair_value_cents: u32,
+    /// Standard error of the fair value in cents, when the model reports one.
+    pub fair_value_stderr: Option<f64>,
     pub inputs: FairValueInputs,
     pub best_bid: u32,
     pub best_ask: u32,
@@ -78,6 +154,7 @@ pub struct SignalTrace {
     pub quantity: u32,
     pub momentum_score: f64,
     pub momentum_gated: bool,
+    pub skip_reason: Option<SkipReason>,
 }
 
 /// Per-sport pipeline that owns its config, polling state, and fair-value source.
@@ -88,14 +165,38 @@ pub struct SportPipeline {
     pub hotkey: char,
     pub enabled: bool,
 
-    pub fair_value_source: FairValueSource,
+    pub fair_value_model: Box<dyn FairValueModel>,
+    /// Score feed poller, for models driven by live scores, or for the
+    /// final scores three-way games settle on under an odds-driven model.
+    pub score_poller: Option<Box<ScorePoller>>,
     pub odds_source: String,
+    /// Source health, and the switch to `backup_odds_source` while
+    /// `odds_source` is down.
+    pub odds_failover: OddsFailover,
     pub score_feed_config: Option<ScoreFeedConfig>,
     pub win_prob_config: Option<WinProbConfig>,
+    pub soccer_model_config: Option<SoccerModelConfig>,
+    pub order_flow_config: Option<OrderFlowConfig>,
+    pub blend_config: Option<BlendConfig>,
+    /// News feed poller, when `[news]` is configured.
+    pub news_poller: Option<NewsPoller>,
+    pub news_config: Option<NewsConfig>,
+    pub pre_game_config: Option<PreGameConfig>,
+    /// Games halted by breaking news.
+    pub news_halts: NewsHalts,
+    pub last_news_poll: Option<Instant>,
+    /// Kept so `rebuild_fair_value_model` builds pollers with the same proxy.
+    network: Network,
 
     // Resolved config (sport override merged over global)
     pub strategy_config: StrategyConfig,
     pub momentum_config: MomentumConfig,
+    /// Taker threshold learned from edge persistence; only ever raises
+    /// `strategy_config.taker_edge_threshold`.
+    pub taker_threshold_floor: Option<u8>,
+    /// Minimum fuzzy team-name score to accept a match; `None` when
+    /// fuzzy matching is off.
+    pub fuzzy_min_score: Option<f64>,
 
     // Polling state
     pub last_odds_poll: Option<Instant>,
@@ -103,62 +204,86 @@ pub struct SportPipeline {
     pub cached_odds: Vec<OddsUpdate>,
     pub cached_scores: Vec<ScoreUpdate>,
     pub last_score_fetch: HashMap<String, Instant>,
+    /// Consecutive failed fetches, for the feed degradation tier.
+    pub score_failures: u32,
+    pub odds_failures: u32,
+    /// The score feed was reading its fallback URL after the last fetch.
+    pub score_on_fallback: bool,
     pub diagnostic_rows: Vec<DiagnosticRow>,
     pub commence_times: Vec<String>,
     pub force_score_refetch: bool,
-
-    // Per-event trackers
-    pub velocity_trackers: HashMap<String, VelocityTracker>,
+    /// Next poll time aligned just after the score feed's expected refresh.
+    pub next_aligned_score_poll: Option<Instant>,
+    /// Next score poll on the adaptive schedule.
+    pub next_score_poll: Option<Instant>,
+    /// Volatility of the most volatile live game, driving the live poll interval.
+    pub poll_volatility: Option<Volatility>,
+    /// Mean positive edge of the last evaluation, in cents. Weighs this
+    /// sport's share of a budgeted odds quota.
+    pub mean_edge_cents: f64,
+    /// Score-feed game ids currently in end-of-game sprint mode.
+    pub sprint_games: HashSet<String>,
+
+    // Per-event trackers. Velocity is keyed by the matcher's canonical game key
+    // so history survives switching between odds (event_id) and score (game_id) sources.
+    pub velocity_trackers: HashMap<matcher::MarketKey, VelocityTracker>,
     pub book_pressure_trackers: HashMap<String, BookPressureTracker>,
+    /// Per-ticker book history for order-flow fair value.
+    pub order_flow_trackers: HashMap<String, OrderFlowTracker>,
+    /// Games currently alerted for out-of-bounds fair values (alert once per episode).
+    pub fv_bound_alerts: HashSet<String>,
+    /// Score-model vs odds-consensus FV gap, fed while validate_fair_value is on.
+    pub fv_disagreement: Option<DisagreementMonitor>,
+
+    /// Half/quarter winner series indexed alongside `series`.
+    pub period_series: Vec<(GameSegment, String)>,
+    /// Evaluate indexed period markets (score-feed fair value only).
+    pub evaluate_periods: bool,
+    /// Score when each live game entered a segment: (game_id, segment) -> (home, away).
+    pub segment_baselines: SegmentBaselines,
 }
 
-fn build_fair_value_source(
+/// Fair value model for `fair_value`, plus the score poller a score-driven
+/// model reads from.
+fn build_fair_value_model(
     key: &str,
     fair_value_str: &str,
     score_feed_config: Option<&ScoreFeedConfig>,
-    win_prob_config: Option<&WinProbConfig>,
-) -> FairValueSource {
-    match fair_value_str {
-        "score-feed" => {
-            let sf = score_feed_config.unwrap_or_else(|| {
-                panic!(
-                    "sport '{}' has fair_value=score-feed but no [score_feed] section",
-                    key
-                )
-            });
-            let wp_config = win_prob_config.unwrap_or_else(|| {
-                panic!(
-                    "sport '{}' has fair_value=score-feed but no [win_prob] section",
-                    key
-                )
-            });
-            let regulation_secs = wp_config.regulation_secs.unwrap_or(2880);
-            let poller = if let Some(ref fallback) = sf.fallback_url {
-                ScorePoller::new(
-                    &sf.primary_url,
-                    fallback,
-                    sf.request_timeout_ms,
-                    sf.failover_threshold,
-                )
-            } else {
-                ScorePoller::new(
-                    &sf.primary_url,
-                    &sf.primary_url,
-                    sf.request_timeout_ms,
-                    sf.failover_threshold,
-                )
-            };
-            FairValueSource::ScoreFeed {
-                poller: Box::new(poller),
-                win_prob: WinProbTable::from_config(wp_config),
-                regulation_secs,
-                live_poll_s: sf.live_poll_s,
-                pre_game_poll_s: sf.pre_game_poll_s,
-            }
-        }
-        // Everything else is treated as an odds source (the-odds-api, scraped-bovada, etc.)
-        _ => FairValueSource::OddsFeed,
+    configs: &ModelConfigs,
+    network: &Network,
+) -> (Box<dyn FairValueModel>, Option<Box<ScorePoller>>) {
+    let model = fair_value::build(fair_value_str, configs)
+        .unwrap_or_else(|e| panic!("sport '{}' has fair_value={}: {:#}", key, fair_value_str, e));
+    if model.driver() != Driver::Score {
+        // Odds-driven sports keep a configured score feed for final scores
+        let poller = score_feed_config.map(|sf| build_score_poller(key, sf, network));
+        return (model, poller);
     }
+    let sf = score_feed_config.unwrap_or_else(|| {
+        panic!(
+            "sport '{}' has fair_value={} but no [score_feed] section",
+            key, fair_value_str
+        )
+    });
+    (model, Some(build_score_poller(key, sf, network)))
+}
+
+fn build_score_poller(key: &str, sf: &ScoreFeedConfig, network: &Network) -> Box<ScorePoller> {
+    let (primary_parser, fallback_parser) =
+        scoreboard_parsers(&sf.feed_type).unwrap_or_else(|| {
+            panic!(
+                "sport '{}' has unknown score_feed type '{}'",
+                key, sf.feed_type
+            )
+        });
+    let poller = ScorePoller::new(
+        &sf.primary_url,
+        sf.fallback_url.as_deref().unwrap_or(&sf.primary_url),
+        sf.request_timeout_ms,
+        sf.failover_threshold,
+        network.client_builder(network::SCORE_FEED),
+    );
+    Box::new(poller.with_parsers(primary_parser, fallback_parser))
 }
 
 impl SportPipeline {
@@ -167,20 +292,45 @@ impl SportPipeline {
         sport: &crate::config::SportConfig,
         global_strategy: &StrategyConfig,
         global_momentum: &MomentumConfig,
+        matching: &MatchingConfig,
+        network: &Network,
     ) -> Self {
         let score_feed_config = sport.score_feed.clone();
         let win_prob_config = sport.win_prob.clone();
-        let fair_value_source = build_fair_value_source(
+        let soccer_model_config = sport.soccer_model.clone();
+        let order_flow_config = sport.order_flow.clone();
+        let blend_config = sport.blend.clone();
+        let (fair_value_model, score_poller) = build_fair_value_model(
             key,
             &sport.fair_value,
             score_feed_config.as_ref(),
-            win_prob_config.as_ref(),
+            &ModelConfigs {
+                win_prob: win_prob_config.as_ref(),
+                soccer_model: soccer_model_config.as_ref(),
+                order_flow: order_flow_config.as_ref(),
+                blend: blend_config.as_ref(),
+            },
+            network,
         );
 
         let hotkey = sport.hotkey.chars().next().unwrap_or('0');
 
-        // If fair_value is an odds source name (not "score-feed"), use it as odds_source
-        let odds_source = if sport.fair_value != "score-feed" && sport.fair_value != "odds-feed" {
+        let mut period_series: Vec<(GameSegment, String)> = sport
+            .period_markets
+            .iter()
+            .flat_map(|pm| &pm.series)
+            .filter_map(|(label, series)| match GameSegment::parse(label) {
+                Some(segment) => Some((segment, series.clone())),
+                None => {
+                    tracing::warn!(sport = key, label = %label, "unknown period market segment");
+                    None
+                }
+            })
+            .collect();
+        period_series.sort();
+
+        // If fair_value is an odds source name, use it as odds_source
+        let odds_source = if !fair_value::is_builtin(&sport.fair_value) {
             sport.fair_value.clone()
         } else {
             sport.odds_source.clone()
@@ -192,95 +342,415 @@ impl SportPipeline {
             label: sport.label.clone(),
             hotkey,
             enabled: sport.enabled,
-            fair_value_source,
+            fair_value_model,
+            score_poller,
             odds_source,
+            odds_failover: OddsFailover::new(sport.backup_odds_source.clone()),
             score_feed_config,
             win_prob_config,
+            soccer_model_config,
+            order_flow_config,
+            blend_config,
+            news_poller: sport.news.as_ref().map(|n| {
+                NewsPoller::new(
+                    &n.url,
+                    n.request_timeout_ms,
+                    network.client_builder(network::NEWS),
+                )
+            }),
+            news_config: sport.news.clone(),
+            pre_game_config: sport.pre_game.clone(),
+            news_halts: NewsHalts::default(),
+            last_news_poll: None,
+            network: network.clone(),
             strategy_config: global_strategy.with_override(sport.strategy.as_ref()),
             momentum_config: global_momentum.with_override(sport.momentum.as_ref()),
+            taker_threshold_floor: None,
+            fuzzy_min_score: matching.fuzzy_enabled.then_some(matching.fuzzy_min_score),
             last_odds_poll: None,
             last_score_poll: None,
             cached_odds: Vec::new(),
             cached_scores: Vec::new(),
             last_score_fetch: HashMap::new(),
+            score_failures: 0,
+            score_on_fallback: false,
+            odds_failures: 0,
             diagnostic_rows: Vec::new(),
             commence_times: Vec::new(),
             force_score_refetch: false,
+            next_aligned_score_poll: None,
+            next_score_poll: None,
+            poll_volatility: None,
+            mean_edge_cents: 0.0,
+            sprint_games: HashSet::new(),
             velocity_trackers: HashMap::new(),
             book_pressure_trackers: HashMap::new(),
+            order_flow_trackers: HashMap::new(),
+            fv_bound_alerts: HashSet::new(),
+            fv_disagreement: None,
+            period_series,
+            evaluate_periods: sport.period_markets.as_ref().is_some_and(|pm| pm.evaluate),
+            segment_baselines: HashMap::new(),
+        }
+    }
+
+    /// Heat grid of the active score model, for the win-prob view. `None`
+    /// for sports not priced off the score feed.
+    pub fn win_prob_grid(&self, columns: usize) -> Option<WinProbGrid> {
+        let mut grid = self.fair_value_model.score_model()?.grid(columns);
+        grid.sport = self.label.clone();
+        Some(grid)
+    }
+
+    /// The `fair_value` setting in effect: the model name, or the odds
+    /// source for devigged odds.
+    pub fn fair_value_name(&self) -> &str {
+        match self.fair_value_model.driver() {
+            Driver::Odds => &self.odds_source,
+            _ => self.fair_value_model.name(),
+        }
+    }
+
+    /// The odds source serving this sport: `odds_source`, or its backup
+    /// while failed over.
+    pub fn active_odds_source(&self) -> &str {
+        self.odds_failover.active(&self.odds_source)
+    }
+
+    /// Health of the odds source serving this sport, for the diagnostic view.
+    pub fn odds_health(&self, configs: &OddsSourcesConfig, now: Instant) -> Option<HealthSummary> {
+        if self.odds_source.is_empty() {
+            return None;
+        }
+        let (threshold, stale_after) = failover_limits(configs, &self.odds_source);
+        Some(
+            self.odds_failover
+                .summary(&self.odds_source, now, threshold, stale_after),
+        )
+    }
+
+    /// Poll the odds source due this cycle (see `OddsFailover`), or take
+    /// what a streaming source pushed since the last cycle, and rebuild the
+    /// diagnostic rows from what it returns.
+    #[allow(clippy::too_many_arguments)]
+    async fn poll_odds(
+        &mut self,
+        market_index: &matcher::MarketIndex,
+        feeds: &FeedSupervisor,
+        odds_source_configs: &OddsSourcesConfig,
+        usage_purpose: UsagePurpose,
+        api_request_times: &Mutex<VecDeque<Instant>>,
+        state_tx: &watch::Sender<AppState>,
+    ) {
+        let (threshold, stale_after) = failover_limits(odds_source_configs, &self.odds_source);
+        let source_key = self.odds_failover.next_source(&self.odds_source, threshold);
+        // A probe of the primary while failed over is not the sport's feed
+        let probe = source_key != self.active_odds_source();
+        if !feeds.contains(&source_key) {
+            return;
+        }
+        let result = match feeds.fetch(&source_key, &self.key, Instant::now()).await {
+            // Held back by the source's rate limit: try again next cycle
+            Ok(None) => return,
+            Ok(Some(fetched)) => {
+                publish_schema_drift(&source_key, fetched.schema_drift, state_tx);
+                Ok(fetched)
+            }
+            Err(e) => Err(e),
+        };
+        let outcome = result.as_ref().map(|_| ()).map_err(|e| format!("{:#}", e));
+        let event = self.odds_failover.record(
+            &source_key,
+            &self.odds_source,
+            outcome,
+            Instant::now(),
+            threshold,
+            stale_after,
+        );
+        match result {
+            Ok(Fetched {
+                mut updates, quota, ..
+            }) => {
+                self.last_odds_poll = Some(Instant::now());
+                self.odds_failures = 0;
+                self.commence_times = updates.iter().map(|u| u.commence_time.clone()).collect();
+                if let Some(quota) = quota {
+                    record_api_quota(
+                        &quota,
+                        &self.key,
+                        usage_purpose,
+                        api_request_times,
+                        state_tx,
+                    );
+                }
+                let source_name = format_source_name(&source_key);
+                self.diagnostic_rows = build_diagnostic_rows(
+                    &updates,
+                    &self.key,
+                    market_index,
+                    &source_name,
+                    self.fuzzy_min_score,
+                );
+                let eastern = chrono::FixedOffset::west_opt(5 * 3600).unwrap();
+                resolve_fuzzy_teams(
+                    updates.iter_mut().filter_map(|u| {
+                        let date = chrono::DateTime::parse_from_rfc3339(&u.commence_time)
+                            .ok()?
+                            .with_timezone(&eastern)
+                            .date_naive();
+                        Some((&mut u.home_team, &mut u.away_team, date))
+                    }),
+                    &self.key,
+                    market_index,
+                    self.fuzzy_min_score,
+                );
+                self.cached_odds = updates;
+            }
+            Err(e) => {
+                if !probe {
+                    self.odds_failures += 1;
+                }
+                tracing::warn!(sport = %self.key, source = %source_key, probe, error = %e, "odds fetch failed");
+            }
         }
+
+        let msg = match event {
+            Some(FailoverEvent::FailedOver { from, to, error }) => {
+                tracing::warn!(sport = %self.key, from = %from, to = %to, error = %error, "odds source failover");
+                format!(
+                    "{}: odds source {} down, failing over to {}",
+                    self.label, from, to
+                )
+            }
+            Some(FailoverEvent::Restored { to }) => {
+                tracing::info!(sport = %self.key, to = %to, "odds source restored");
+                format!("{}: odds source {} recovered", self.label, to)
+            }
+            None => return,
+        };
+        state_tx.send_modify(|s| s.push_log("WARN", msg));
     }
 
-    /// Rebuild the fair value source at runtime (e.g. switching between score-feed and odds sources).
-    /// If new_source is an odds source name (not "score-feed"), also updates odds_source field.
-    pub fn rebuild_fair_value_source(&mut self, new_source: &str) {
-        self.fair_value_source = build_fair_value_source(
+    /// Rebuild the fair value model at runtime (e.g. switching between score-feed and odds sources).
+    /// If new_source is an odds source name, also updates odds_source field.
+    pub fn rebuild_fair_value_model(&mut self, new_source: &str) {
+        let previous = self.fair_value_name().to_string();
+        (self.fair_value_model, self.score_poller) = build_fair_value_model(
             &self.key,
             new_source,
             self.score_feed_config.as_ref(),
-            self.win_prob_config.as_ref(),
+            &ModelConfigs {
+                win_prob: self.win_prob_config.as_ref(),
+                soccer_model: self.soccer_model_config.as_ref(),
+                order_flow: self.order_flow_config.as_ref(),
+                blend: self.blend_config.as_ref(),
+            },
+            &self.network,
         );
 
-        // If the new source is not "score-feed", it's an odds source name - update odds_source field
-        if new_source != "score-feed" {
+        // Migrate velocity history: trackers share canonical game keys across
+        // sources, but each source has its own level, so re-level on next sample.
+        if previous != new_source {
+            for vt in self.velocity_trackers.values_mut() {
+                vt.rebase_on_next_push();
+            }
+        }
+
+        // Anything else is an odds source name - update odds_source field
+        if !fair_value::is_builtin(new_source) {
             self.odds_source = new_source.to_string();
         }
     }
 
-    /// Run one processing cycle for this sport.
+    /// Poll the news feed when due, halting games of the teams new breaking
+    /// items mention.
+    async fn poll_news(&mut self, cycle_start: Instant, state_tx: &watch::Sender<AppState>) {
+        let (Some(poller), Some(news)) = (self.news_poller.as_mut(), self.news_config.as_ref())
+        else {
+            return;
+        };
+        if self
+            .last_news_poll
+            .is_some_and(|t| cycle_start.duration_since(t) < Duration::from_secs(news.poll_s))
+        {
+            return;
+        }
+        self.last_news_poll = Some(cycle_start);
+        let now = utc_now();
+        self.news_halts.prune(now);
+        let items = match poller.fetch().await {
+            Ok(items) => items,
+            Err(e) => {
+                tracing::warn!(sport = %self.key, error = %e, "news fetch failed");
+                return;
+            }
+        };
+        let halt_for = chrono::Duration::minutes(news.halt_minutes as i64);
+        for item in items {
+            if let Some(halt) = self.news_halts.record(item, &news.keywords, halt_for, now) {
+                let minutes = (halt.until - now).num_minutes().max(1);
+                tracing::info!(sport = %self.key, headline = %halt.item.headline, minutes, "news halt");
+                let msg = format!(
+                    "NEWS: {} halted {}m: {}",
+                    self.label, minutes, halt.item.headline
+                );
+                state_tx.send_modify(|s| s.push_log("WARN", msg));
+            }
+        }
+    }
+
+    /// Record the current poll volatility, logging when it changes.
+    fn set_poll_volatility(&mut self, volatility: Option<Volatility>) {
+        if volatility != self.poll_volatility {
+            tracing::info!(
+                sport = %self.key,
+                volatility = volatility.map_or("none", |v| v.label()),
+                "live poll volatility changed"
+            );
+            self.poll_volatility = volatility;
+        }
+    }
+
+    /// Drop per-game state for games compacted out of `index`, and for game ids
+    /// the score feed no longer reports. Returns the number of entries removed.
+    pub fn compact(&mut self, index: &matcher::MarketIndex, tickers: &HashSet<String>) -> usize {
+        let before = self.tracked_entries();
+        self.velocity_trackers
+            .retain(|key, _| index.contains_key(key));
+        self.book_pressure_trackers
+            .retain(|ticker, _| tickers.contains(ticker));
+        self.order_flow_trackers
+            .retain(|ticker, _| tickers.contains(ticker));
+        self.diagnostic_rows.retain(|row| {
+            row.kalshi_ticker
+                .as_ref()
+                .is_none_or(|t| tickers.contains(t))
+        });
+        // An empty response may just be an outage; keep live games' state until the feed reports again
+        if !self.cached_scores.is_empty() {
+            let games: HashSet<&str> = self
+                .cached_scores
+                .iter()
+                .map(|u| u.game_id.as_str())
+                .collect();
+            self.last_score_fetch
+                .retain(|id, _| games.contains(id.as_str()));
+            self.fv_bound_alerts
+                .retain(|id| games.contains(id.as_str()));
+            self.segment_baselines
+                .retain(|(id, _), _| games.contains(id.as_str()));
+        }
+        before - self.tracked_entries()
+    }
+
+    /// Per-game entries this pipeline keeps across polls, for the memory gauge.
+    pub fn tracked_entries(&self) -> usize {
+        self.velocity_trackers.len()
+            + self.book_pressure_trackers.len()
+            + self.order_flow_trackers.len()
+            + self.diagnostic_rows.len()
+            + self.last_score_fetch.len()
+            + self.fv_bound_alerts.len()
+            + self.segment_baselines.len()
+    }
+
+    /// Replace the set of sprinting games, logging games that enter or leave sprint mode.
+    fn set_sprint_games(&mut self, games: HashSet<String>, state_tx: &watch::Sender<AppState>) {
+        if games == self.sprint_games {
+            return;
+        }
+        let entered: Vec<&String> = games.difference(&self.sprint_games).collect();
+        let left = self.sprint_games.difference(&games).count();
+        for game_id in entered {
+            let label = self
+                .cached_scores
+                .iter()
+                .find(|u| &u.game_id == game_id)
+                .map(|u| {
+                    format!(
+                        "{} {}-{} {}",
+                        u.home_team, u.home_score, u.away_score, u.away_team
+                    )
+                })
+                .unwrap_or_else(|| game_id.clone());
+            tracing::info!(sport = %self.key, game = %game_id, "sprint mode on");
+            state_tx.send_modify(|s| s.push_log("INFO", format!("SPRINT: {}", label)));
+        }
+        if left > 0 {
+            tracing::info!(sport = %self.key, games = left, "sprint mode off");
+        }
+        self.sprint_games = games;
+    }
+
+    /// Strategy config with the auto-tuned taker threshold floor applied.
+    pub fn effective_strategy(&self) -> StrategyConfig {
+        let mut cfg = self.strategy_config.clone();
+        if let Some(floor) = self.taker_threshold_floor {
+            cfg.taker_edge_threshold = cfg.taker_edge_threshold.max(floor);
+        }
+        cfg
+    }
+
+    /// Run one processing cycle for this sport. With `focus`, only the games
+    /// trading those tickers are evaluated, and the result covers just them.
     #[allow(clippy::too_many_arguments)]
     pub async fn tick(
         &mut self,
         cycle_start: Instant,
         market_index: &matcher::MarketIndex,
         live_book: &LiveBook,
-        odds_sources: &mut HashMap<String, Box<dyn OddsFeed>>,
+        feeds: &FeedSupervisor,
         scorer: &MomentumScorer,
         risk_config: &crate::config::RiskConfig,
         sim_config: &crate::config::SimulationConfig,
         sim_mode: bool,
         state_tx: &watch::Sender<AppState>,
         bankroll_cents: u64,
-        api_request_times: &mut VecDeque<Instant>,
-        odds_source_configs: &HashMap<String, OddsSourceConfig>,
+        api_request_times: &Mutex<VecDeque<Instant>>,
+        odds_source_configs: &OddsSourcesConfig,
+        adaptive_poll: &AdaptivePollConfig,
+        sprint: &SprintConfig,
         fill_simulator: Option<&mut crate::engine::FillSimulator>,
+        journal: &Journal,
+        focus: Option<&HashSet<String>>,
     ) -> TickResult {
-        match &self.fair_value_source {
-            FairValueSource::ScoreFeed {
-                regulation_secs,
-                live_poll_s,
-                pre_game_poll_s,
-                ..
-            } => {
-                let regulation_secs = *regulation_secs;
-                let live_poll_s = *live_poll_s;
-                let pre_game_poll_s = *pre_game_poll_s;
+        let games = focus.map(|tickers| matcher::games_trading(market_index, tickers));
+        let focus = games.as_ref();
+        // Kelly fraction scaled down when this sport's realized edge has
+        // fallen short of what its fair values predicted
+        let mut risk_config = risk_config.clone();
+        risk_config.kelly_fraction *= state_tx.borrow().kelly_tuner.multiplier(&self.key);
+        let risk_config = &risk_config;
+        self.poll_news(cycle_start, state_tx).await;
+        let result = match self.fair_value_model.driver() {
+            Driver::Score => {
                 self.tick_score_feed(
                     cycle_start,
                     market_index,
                     live_book,
-                    odds_sources,
+                    feeds,
                     scorer,
                     risk_config,
                     sim_config,
                     sim_mode,
                     state_tx,
                     bankroll_cents,
-                    regulation_secs,
-                    live_poll_s,
-                    pre_game_poll_s,
                     api_request_times,
                     odds_source_configs,
+                    adaptive_poll,
+                    sprint,
                     fill_simulator,
+                    journal,
+                    focus,
                 )
                 .await
             }
-            FairValueSource::OddsFeed => {
+            Driver::Odds => {
                 self.tick_odds_feed(
                     cycle_start,
                     market_index,
                     live_book,
-                    odds_sources,
+                    feeds,
                     scorer,
                     risk_config,
                     sim_config,
@@ -289,10 +759,40 @@ impl SportPipeline {
                     bankroll_cents,
                     api_request_times,
                     odds_source_configs,
+                    adaptive_poll,
                     fill_simulator,
+                    journal,
+                    focus,
                 )
                 .await
             }
+            Driver::Book => self.tick_order_flow(
+                cycle_start,
+                market_index,
+                live_book,
+                scorer,
+                risk_config,
+                sim_config,
+                sim_mode,
+                state_tx,
+                bankroll_cents,
+                fill_simulator,
+                journal,
+                focus,
+            ),
+        };
+        // A focused tick's rows are only part of the sport
+        if focus.is_none() {
+            self.mean_edge_cents = mean_positive_edge(&result.rows);
+        }
+        result
+    }
+
+    /// What this sport asks of its odds source's quota budget.
+    fn poll_demand(&self, live_games: usize) -> PollDemand {
+        PollDemand {
+            live_games,
+            edge_cents: self.mean_edge_cents,
         }
     }
 
@@ -303,95 +803,107 @@ impl SportPipeline {
         cycle_start: Instant,
         market_index: &matcher::MarketIndex,
         live_book: &LiveBook,
-        odds_sources: &mut HashMap<String, Box<dyn OddsFeed>>,
+        feeds: &FeedSupervisor,
         scorer: &MomentumScorer,
         risk_config: &crate::config::RiskConfig,
         sim_config: &crate::config::SimulationConfig,
         sim_mode: bool,
         state_tx: &watch::Sender<AppState>,
         bankroll_cents: u64,
-        regulation_secs: u16,
-        live_poll_s: u64,
-        pre_game_poll_s: u64,
-        api_request_times: &mut VecDeque<Instant>,
-        odds_source_configs: &HashMap<String, OddsSourceConfig>,
+        api_request_times: &Mutex<VecDeque<Instant>>,
+        odds_source_configs: &OddsSourcesConfig,
+        adaptive_poll: &AdaptivePollConfig,
+        sprint: &SprintConfig,
         fill_simulator: Option<&mut crate::engine::FillSimulator>,
+        journal: &Journal,
+        focus: Option<&HashSet<matcher::MarketKey>>,
     ) -> TickResult {
+        let regulation_secs = self
+            .fair_value_model
+            .score_model()
+            .map_or(2880, |m| m.regulation_secs);
+        let (live_poll_s, pre_game_poll_s) = self
+            .score_feed_config
+            .as_ref()
+            .map_or((1, 60), |c| (c.live_poll_s, c.pre_game_poll_s));
+        let blend_odds = self.fair_value_model.wants_odds();
+
         // Poll odds feed for diagnostic rows (pre-game interval to avoid
         // burning API quota — the score feed drives actual fair value).
-        // When validate_fair_value is on, use live_poll_s for faster updates.
-        let diag_poll_s = odds_source_configs
-            .get(&self.odds_source)
-            .map(|c| {
-                if sim_config.validate_fair_value {
-                    c.live_poll_s
-                } else {
-                    c.pre_game_poll_s
-                }
-            })
-            .unwrap_or(120);
-        let should_fetch_odds = match self.last_odds_poll {
-            Some(last) => cycle_start.duration_since(last) >= Duration::from_secs(diag_poll_s),
-            None => true,
+        // When validate_fair_value is on, or the model prices off odds too,
+        // poll at the live rate for faster updates.
+        let diag_cadence = if sim_config.validate_fair_value || blend_odds {
+            Cadence::Live(Volatility::Close)
+        } else {
+            Cadence::PreGame
         };
-        if should_fetch_odds {
-            if let Some(source) = odds_sources.get_mut(&self.odds_source) {
-                match source.fetch_odds(&self.key).await {
-                    Ok(updates) => {
-                        self.last_odds_poll = Some(Instant::now());
-                        self.commence_times =
-                            updates.iter().map(|u| u.commence_time.clone()).collect();
-                        if let Some(quota) = source.last_quota() {
-                            api_request_times.push_back(Instant::now());
-                            let one_hour_ago = Instant::now() - Duration::from_secs(3600);
-                            while api_request_times.front().is_some_and(|&t| t < one_hour_ago) {
-                                api_request_times.pop_front();
-                            }
-                            let burn_rate = api_request_times.len() as f64;
-                            state_tx.send_modify(|s| {
-                                s.api_requests_used = quota.requests_used;
-                                s.api_requests_remaining = quota.requests_remaining;
-                                s.api_burn_rate = burn_rate;
-                                s.api_hours_remaining = if burn_rate > 0.0 {
-                                    quota.requests_remaining as f64 / burn_rate
-                                } else {
-                                    f64::INFINITY
-                                };
-                            });
-                        }
-                        let source_name = format_source_name(&self.odds_source);
-                        self.diagnostic_rows =
-                            build_diagnostic_rows(&updates, &self.key, market_index, &source_name);
-                        self.cached_odds = updates;
-                    }
-                    Err(e) => {
-                        tracing::warn!(sport = %self.key, error = %e, "diagnostic odds fetch failed");
-                    }
-                }
-            }
-        }
-
-        // Determine poll interval from cached state
-        let has_live = self
+        let usage_purpose = if blend_odds {
+            UsagePurpose::LiveEval
+        } else if sim_config.validate_fair_value {
+            UsagePurpose::Validation
+        } else {
+            UsagePurpose::PreGameScan
+        };
+        let live_games = self
             .cached_scores
             .iter()
-            .any(|u| u.game_status == crate::feed::score_feed::GameStatus::Live);
+            .filter(|u| u.game_status == crate::feed::score_feed::GameStatus::Live)
+            .count();
+        if feeds.is_due(
+            self.active_odds_source(),
+            &self.key,
+            diag_cadence,
+            self.poll_demand(live_games),
+            self.last_odds_poll,
+            cycle_start,
+        ) {
+            self.poll_odds(
+                market_index,
+                feeds,
+                odds_source_configs,
+                usage_purpose,
+                api_request_times,
+                state_tx,
+            )
+            .await;
+        }
 
-        let score_interval = if has_live {
-            Duration::from_secs(live_poll_s)
-        } else {
-            Duration::from_secs(pre_game_poll_s)
+        // Determine poll interval from cached state: live games scale the
+        // live interval by how volatile the most volatile game is.
+        let volatility = score_feed_volatility(adaptive_poll, &self.cached_scores, regulation_secs);
+        self.set_poll_volatility(volatility);
+        let has_live = volatility.is_some();
+
+        let mut score_interval = match volatility {
+            Some(v) => {
+                adaptive_poll::live_interval(adaptive_poll, Duration::from_secs(live_poll_s), v)
+            }
+            None => Duration::from_secs(pre_game_poll_s),
         };
 
+        // Close games in their final minutes poll at the sprint rate
+        let sprint_games = sprint_game_ids(sprint, &self.cached_scores, regulation_secs);
+        self.set_sprint_games(sprint_games, state_tx);
+        if !self.sprint_games.is_empty() {
+            score_interval = score_interval.min(Duration::from_millis(sprint.poll_interval_ms));
+        }
+        self.next_score_poll = self.last_score_poll.map(|t| t + score_interval);
+
+        let aligned_due = has_live
+            && self
+                .next_aligned_score_poll
+                .is_some_and(|t| cycle_start >= t);
         let should_fetch = self.force_score_refetch
+            || aligned_due
             || match self.last_score_poll {
                 Some(last) => cycle_start.duration_since(last) >= score_interval,
                 None => true,
             };
 
         if should_fetch {
-            if let FairValueSource::ScoreFeed { ref mut poller, .. } = self.fair_value_source {
+            if let Some(poller) = self.score_poller.as_mut() {
                 self.force_score_refetch = false;
+                self.next_aligned_score_poll = None;
                 match poller.fetch().await {
                     Ok(mut updates) => {
                         // For college sports (regulation_secs <= 2400), recompute
@@ -403,16 +915,56 @@ impl SportPipeline {
                             }
                         }
                         self.last_score_poll = Some(Instant::now());
+                        self.score_failures = 0;
+                        self.next_score_poll = Some(Instant::now() + score_interval);
+                        self.next_aligned_score_poll = self
+                            .score_feed_config
+                            .as_ref()
+                            .filter(|c| c.align_to_cadence)
+                            .and_then(|c| {
+                                poller
+                                    .next_expected_refresh(Instant::now())
+                                    .map(|t| t + Duration::from_millis(c.cadence_offset_ms))
+                            });
                         for u in &updates {
                             self.last_score_fetch
                                 .insert(u.game_id.clone(), Instant::now());
                         }
+                        let eastern = chrono::FixedOffset::west_opt(5 * 3600).unwrap();
+                        let today = utc_now().with_timezone(&eastern).date_naive();
+                        resolve_fuzzy_teams(
+                            updates
+                                .iter_mut()
+                                .map(|u| (&mut u.home_team, &mut u.away_team, today)),
+                            &self.key,
+                            market_index,
+                            self.fuzzy_min_score,
+                        );
                         self.cached_scores = updates;
                     }
                     Err(e) => {
+                        self.score_failures += 1;
                         tracing::warn!(sport = %self.key, error = %e, "score feed fetch failed");
                     }
                 }
+                let on_fallback = poller.on_fallback();
+                if on_fallback && !self.score_on_fallback {
+                    let msg = format!(
+                        "{} score feed failed over to {}",
+                        self.key,
+                        poller.fallback_url()
+                    );
+                    tracing::error!("{}", msg);
+                    state_tx.send_modify(|s| {
+                        s.escalation.raise(
+                            AlertKind::ScoreFeedFailover,
+                            msg.clone(),
+                            Instant::now(),
+                        );
+                        s.push_log("ERROR", msg);
+                    });
+                }
+                self.score_on_fallback = on_fallback;
             }
         }
 
@@ -430,32 +982,115 @@ impl SportPipeline {
             };
         }
 
-        process_score_updates(
-            &self.cached_scores,
+        let focused: Vec<ScoreUpdate>;
+        let updates = match focus {
+            Some(games) => {
+                focused = self
+                    .cached_scores
+                    .iter()
+                    .filter(|u| updates_game(&self.key, &u.home_team, &u.away_team, games))
+                    .cloned()
+                    .collect();
+                &focused
+            }
+            None => &self.cached_scores,
+        };
+        let result = process_score_updates(
+            updates,
             &self.key,
             regulation_secs,
             market_index,
             live_book,
-            &self.strategy_config,
+            &self.effective_strategy(),
             &self.momentum_config,
             &mut self.velocity_trackers,
             &mut self.book_pressure_trackers,
+            &mut self.fv_bound_alerts,
+            &self.news_halts,
             scorer,
             sim_mode,
             state_tx,
             cycle_start,
             &self.last_score_fetch,
+            &self.sprint_games,
+            sprint.stale_threshold_secs,
             sim_config,
-            &self.fair_value_source,
+            self.fair_value_model.as_ref(),
             risk_config,
             bankroll_cents,
-            if sim_config.validate_fair_value {
+            if sim_config.validate_fair_value || blend_odds {
                 &self.cached_odds
             } else {
                 &[]
             },
+            odds_source_configs,
+            self.evaluate_periods.then_some(&mut self.segment_baselines),
             fill_simulator,
-        )
+            journal,
+        );
+        if sim_config.validate_fair_value {
+            self.track_fv_disagreement(&result.rows, cycle_start, sim_config, state_tx);
+        } else if self.fv_disagreement.take().is_some() {
+            state_tx.send_modify(|s| {
+                s.fv_disagreement.remove(&self.key);
+            });
+        }
+        result
+    }
+
+    /// Feed this tick's score-model vs odds FV pairs into the disagreement
+    /// monitor and alert when the windowed average crosses the threshold.
+    fn track_fv_disagreement(
+        &mut self,
+        rows: &HashMap<String, MarketRow>,
+        at: Instant,
+        sim_config: &crate::config::SimulationConfig,
+        state_tx: &watch::Sender<AppState>,
+    ) {
+        let window = Duration::from_secs(sim_config.fv_disagreement_window_secs);
+        let monitor = self
+            .fv_disagreement
+            .get_or_insert_with(|| DisagreementMonitor::new(window));
+        for row in rows.values() {
+            if let Some(odds_fv) = row.odds_api_fair_value {
+                monitor.record(at, row.fair_value, odds_fv);
+            }
+        }
+        let threshold = sim_config.fv_disagreement_threshold_cents;
+        let alert = monitor.check(threshold);
+        let stat = monitor
+            .avg_abs()
+            .zip(monitor.bias())
+            .map(|(avg_abs, bias)| FvDisagreementStat {
+                avg_abs,
+                bias,
+                alerting: monitor.is_alerting(),
+            });
+        let samples = monitor.sample_count();
+        match alert {
+            Some(DisagreementAlert::Raised { avg_abs, bias }) => {
+                let msg = format!(
+                    "{} FV disagreement: score model vs odds avg {:.1}c (bias {:+.1}c, {} samples) exceeds {:.1}c; check model or matching",
+                    self.label, avg_abs, bias, samples, threshold
+                );
+                tracing::warn!(sport = %self.key, "{}", msg);
+                state_tx.send_modify(|s| s.push_log("WARN", msg));
+            }
+            Some(DisagreementAlert::Cleared { avg_abs }) => {
+                let msg = format!(
+                    "{} FV disagreement back to {:.1}c (threshold {:.1}c)",
+                    self.label, avg_abs, threshold
+                );
+                tracing::info!(sport = %self.key, "{}", msg);
+                state_tx.send_modify(|s| s.push_log("INFO", msg));
+            }
+            None => {}
+        }
+        if let Some(stat) = stat {
+            state_tx.send_modify(|s| {
+                s.fv_disagreement.insert(self.key.clone(), stat);
+            });
+        }
     }
 
     /// Odds-feed pipeline tick: poll odds, build diagnostic rows, evaluate.
@@ -465,96 +1100,87 @@ impl SportPipeline {
         cycle_start: Instant,
         market_index: &matcher::MarketIndex,
         live_book: &LiveBook,
-        odds_sources: &mut HashMap<String, Box<dyn OddsFeed>>,
+        feeds: &FeedSupervisor,
         scorer: &MomentumScorer,
         risk_config: &crate::config::RiskConfig,
         sim_config: &crate::config::SimulationConfig,
         sim_mode: bool,
         state_tx: &watch::Sender<AppState>,
         bankroll_cents: u64,
-        api_request_times: &mut VecDeque<Instant>,
-        odds_source_configs: &HashMap<String, OddsSourceConfig>,
+        api_request_times: &Mutex<VecDeque<Instant>>,
+        odds_source_configs: &OddsSourcesConfig,
+        adaptive_poll: &AdaptivePollConfig,
         fill_simulator: Option<&mut crate::engine::FillSimulator>,
+        journal: &Journal,
+        focus: Option<&HashSet<matcher::MarketKey>>,
     ) -> TickResult {
         // Determine if any event is live (from commence times)
-        let is_live = self.commence_times.iter().any(|ct| {
-            chrono::DateTime::parse_from_rfc3339(ct)
-                .ok()
-                .is_some_and(|dt| dt < chrono::Utc::now())
-        });
-
-        // Determine polling intervals from the odds source config
-        let source_config = odds_source_configs.get(&self.odds_source);
-        let live_poll_s = source_config.map(|c| c.live_poll_s).unwrap_or(20);
-        let pre_game_poll_s = source_config.map(|c| c.pre_game_poll_s).unwrap_or(120);
-        let quota_warning = source_config
-            .and_then(|c| c.quota_warning_threshold)
-            .unwrap_or(100);
-
-        let quota_low = !api_request_times.is_empty()
-            && state_tx.borrow().api_requests_remaining < quota_warning;
-        let interval = if quota_low || !is_live {
-            Duration::from_secs(pre_game_poll_s)
+        let live_games = self
+            .commence_times
+            .iter()
+            .filter(|ct| {
+                chrono::DateTime::parse_from_rfc3339(ct)
+                    .ok()
+                    .is_some_and(|dt| dt < utc_now())
+            })
+            .count();
+        let is_live = live_games > 0;
+
+        let volatility = if is_live {
+            odds_feed_volatility(
+                adaptive_poll,
+                &self.cached_odds,
+                odds_source_configs,
+                utc_now(),
+            )
         } else {
-            Duration::from_secs(live_poll_s)
+            None
         };
-
-        let should_fetch = match self.last_odds_poll {
-            Some(last) => cycle_start.duration_since(last) >= interval,
-            None => true,
+        self.set_poll_volatility(volatility);
+        // The supervisor falls back to the pre-game interval while the
+        // source's quota is low
+        let cadence = if is_live {
+            Cadence::Live(volatility.unwrap_or(Volatility::Close))
+        } else {
+            Cadence::PreGame
+        };
+        let should_fetch = feeds.is_due(
+            self.active_odds_source(),
+            &self.key,
+            cadence,
+            self.poll_demand(live_games),
+            self.last_odds_poll,
+            cycle_start,
+        );
+        let usage_purpose = if is_live {
+            UsagePurpose::LiveEval
+        } else {
+            UsagePurpose::PreGameScan
         };
 
         // Always fetch odds + build diagnostic rows on schedule, even when no
         // Kalshi markets are open.  The diagnostic view needs all games.
         if should_fetch {
-            if let Some(source) = odds_sources.get_mut(&self.odds_source) {
-                match source.fetch_odds(&self.key).await {
-                    Ok(updates) => {
-                        self.last_odds_poll = Some(Instant::now());
-                        let ctimes: Vec<String> =
-                            updates.iter().map(|u| u.commence_time.clone()).collect();
-                        self.commence_times = ctimes;
-
-                        // Update API quota
-                        if let Some(quota) = source.last_quota() {
-                            api_request_times.push_back(Instant::now());
-                            let one_hour_ago = Instant::now() - Duration::from_secs(3600);
-                            while api_request_times.front().is_some_and(|&t| t < one_hour_ago) {
-                                api_request_times.pop_front();
-                            }
-                            let burn_rate = api_request_times.len() as f64;
-                            state_tx.send_modify(|s| {
-                                s.api_requests_used = quota.requests_used;
-                                s.api_requests_remaining = quota.requests_remaining;
-                                s.api_burn_rate = burn_rate;
-                                s.api_hours_remaining = if burn_rate > 0.0 {
-                                    quota.requests_remaining as f64 / burn_rate
-                                } else {
-                                    f64::INFINITY
-                                };
-                            });
-                        }
+            self.poll_odds(
+                market_index,
+                feeds,
+                odds_source_configs,
+                usage_purpose,
+                api_request_times,
+                state_tx,
+            )
+            .await;
+        }
 
-                        let source_name = format_source_name(&self.odds_source);
-                        self.diagnostic_rows =
-                            build_diagnostic_rows(&updates, &self.key, market_index, &source_name);
-                        self.cached_odds = updates;
-                    }
-                    Err(e) => {
-                        tracing::warn!(sport = %self.key, error = %e, "odds fetch failed");
-                    }
-                }
-            }
+        // Three-way games settle on the final score: read a configured
+        // score feed while sim positions in this sport wait on a result
+        if sim_mode && self.holds_sim_positions(market_index, state_tx) {
+            self.poll_final_scores(cycle_start, market_index).await;
         }
 
         // Pre-check: does this sport have any game that COULD be live?
-        let now_utc_precheck = chrono::Utc::now();
-        let sport_key_normalized: String = self
-            .key
-            .to_uppercase()
-            .chars()
-            .filter(|c| c.is_ascii_alphabetic())
-            .collect();
+        let now_utc_precheck = utc_now();
+        let sport_key_normalized = index_sport_key(&self.key);
         let sport_has_eligible_games = market_index.iter().any(|(key, game)| {
             if key.sport != sport_key_normalized {
                 return false;
@@ -580,19 +1206,8 @@ impl SportPipeline {
 
             // In sim mode, check for open positions on this sport's tickers
             // so process_sport_updates can detect closure and settle them.
-            let has_unsettled_positions = sim_mode && {
-                let positions = &state_tx.borrow().sim_positions;
-                !positions.is_empty()
-                    && market_index
-                        .iter()
-                        .filter(|(k, _)| k.sport == sport_key_normalized)
-                        .any(|(_, game)| {
-                            [game.home.as_ref(), game.away.as_ref(), game.draw.as_ref()]
-                                .into_iter()
-                                .flatten()
-                                .any(|side| positions.iter().any(|p| p.ticker == side.ticker))
-                        })
-            };
+            let has_unsettled_positions =
+                sim_mode && self.holds_sim_positions(market_index, state_tx);
 
             if !has_unsettled_positions {
                 return TickResult {
@@ -624,15 +1239,30 @@ impl SportPipeline {
             };
         }
 
+        let focused: Vec<OddsUpdate>;
+        let updates = match focus {
+            Some(games) => {
+                focused = self
+                    .cached_odds
+                    .iter()
+                    .filter(|u| updates_game(&self.key, &u.home_team, &u.away_team, games))
+                    .cloned()
+                    .collect();
+                &focused
+            }
+            None => &self.cached_odds,
+        };
         process_sport_updates(
-            &self.cached_odds,
+            updates,
             &self.key,
+            self.fair_value_model.as_ref(),
             market_index,
             live_book,
-            &self.strategy_config,
+            &self.effective_strategy(),
             &self.momentum_config,
             &mut self.velocity_trackers,
             &mut self.book_pressure_trackers,
+            &self.news_halts,
             scorer,
             sim_mode,
             state_tx,
@@ -641,31 +1271,354 @@ impl SportPipeline {
             sim_config,
             risk_config,
             bankroll_cents,
+            odds_source_configs,
+            self.pre_game_config.as_ref(),
+            &self.cached_scores,
             fill_simulator,
+            journal,
         )
     }
-}
 
-/// Results from one pipeline tick.
-pub struct TickResult {
-    pub filter_live: usize,
-    pub filter_pre_game: usize,
-    pub filter_closed: usize,
-    pub earliest_commence: Option<chrono::DateTime<chrono::Utc>>,
-    pub rows: HashMap<String, MarketRow>,
-    #[allow(dead_code)]
-    pub has_live_games: bool,
-    /// Tickers detected as closed this cycle, with their last fair value (for sim settlement).
-    pub closed_tickers: Vec<(String, u32)>,
-    /// Order intents produced by evaluation in live mode.
-    pub order_intents: Vec<OrderIntent>,
-}
+    /// Whether the sim holds a position on any of this sport's markets.
+    fn holds_sim_positions(
+        &self,
+        market_index: &matcher::MarketIndex,
+        state_tx: &watch::Sender<AppState>,
+    ) -> bool {
+        let sport_key_normalized = index_sport_key(&self.key);
+        let positions = &state_tx.borrow().sim_positions;
+        !positions.is_empty()
+            && market_index
+                .iter()
+                .filter(|(k, _)| k.sport == sport_key_normalized)
+                .any(|(_, game)| {
+                    [game.home.as_ref(), game.away.as_ref(), game.draw.as_ref()]
+                        .into_iter()
+                        .flatten()
+                        .any(|side| positions.iter().any(|p| p.ticker == side.ticker))
+                })
+    }
 
-// ── Moved helper functions ─────────────────────────────────────────────
+    /// Refresh `cached_scores` at the live poll rate, for an odds-driven
+    /// sport whose closed three-way games settle on the final score.
+    async fn poll_final_scores(
+        &mut self,
+        cycle_start: Instant,
+        market_index: &matcher::MarketIndex,
+    ) {
+        let interval =
+            Duration::from_secs(self.score_feed_config.as_ref().map_or(1, |c| c.live_poll_s));
+        if self
+            .last_score_poll
+            .is_some_and(|last| cycle_start.duration_since(last) < interval)
+        {
+            return;
+        }
+        let Some(poller) = self.score_poller.as_mut() else {
+            return;
+        };
+        self.last_score_poll = Some(cycle_start);
+        match poller.fetch().await {
+            Ok(mut updates) => {
+                let eastern = chrono::FixedOffset::west_opt(5 * 3600).unwrap();
+                let today = utc_now().with_timezone(&eastern).date_naive();
+                resolve_fuzzy_teams(
+                    updates
+                        .iter_mut()
+                        .map(|u| (&mut u.home_team, &mut u.away_team, today)),
+                    &self.key,
+                    market_index,
+                    self.fuzzy_min_score,
+                );
+                self.cached_scores = updates;
+            }
+            Err(e) => {
+                tracing::warn!(sport = %self.key, error = %e, "final score fetch failed");
+            }
+        }
+    }
 
-/// Result of evaluating a single matched market through the common pipeline.
-#[allow(clippy::large_enum_variant)]
-pub enum EvalOutcome {
+    /// Order-flow tick: price every open market of this sport off its own
+    /// book. Markets are evaluated once their tracker has warmed up.
+    #[allow(clippy::too_many_arguments)]
+    fn tick_order_flow(
+        &mut self,
+        cycle_start: Instant,
+        market_index: &matcher::MarketIndex,
+        live_book: &LiveBook,
+        scorer: &MomentumScorer,
+        risk_config: &crate::config::RiskConfig,
+        sim_config: &crate::config::SimulationConfig,
+        sim_mode: bool,
+        state_tx: &watch::Sender<AppState>,
+        bankroll_cents: u64,
+        mut fill_simulator: Option<&mut crate::engine::FillSimulator>,
+        journal: &Journal,
+        focus: Option<&HashSet<matcher::MarketKey>>,
+    ) -> TickResult {
+        let sport_key_normalized = index_sport_key(&self.key);
+        let now_utc = utc_now();
+        let strategy_config = self.effective_strategy();
+        let config = self
+            .fair_value_model
+            .order_flow()
+            .cloned()
+            .unwrap_or_default();
+        let mut result = TickResult {
+            filter_live: 0,
+            filter_pre_game: 0,
+            filter_closed: 0,
+            earliest_commence: None,
+            rows: HashMap::new(),
+            has_live_games: false,
+            closed_tickers: Vec::new(),
+            order_intents: Vec::new(),
+        };
+
+        // Sorted so sim fills (and backtests) don't depend on hash order
+        let mut sides: Vec<(&matcher::MarketKey, &matcher::SideMarket, bool)> = market_index
+            .iter()
+            .filter(|(key, _)| key.sport == sport_key_normalized)
+            .filter(|(key, _)| focus.is_none_or(|games| games.contains(*key)))
+            .flat_map(|(key, game)| {
+                [(&game.home, true), (&game.away, false), (&game.draw, false)]
+                    .into_iter()
+                    .filter_map(move |(side, is_home)| side.as_ref().map(|s| (key, s, is_home)))
+            })
+            .collect();
+        sides.sort_by(|a, b| a.1.ticker.cmp(&b.1.ticker));
+
+        for (game_key, side, is_home) in sides {
+            let sample = live_book
+                .read(&side.ticker, |depth| {
+                    let (yes_bid, yes_ask, _, _) = depth.best_bid_ask();
+                    let (bid_depth, ask_depth) = depth.depth_near_touch(config.depth_band_cents);
+                    (yes_bid > 0 && yes_ask > 0).then_some((yes_bid, yes_ask, bid_depth, ask_depth))
+                })
+                .flatten();
+            let tracker = self
+                .order_flow_trackers
+                .entry(side.ticker.clone())
+                .or_insert_with(|| OrderFlowTracker::new(config.window_size));
+            if let Some((yes_bid, yes_ask, bid_depth, ask_depth)) = sample {
+                tracker.push(yes_bid, yes_ask, bid_depth, ask_depth);
+            }
+            let Some(estimate) = tracker.estimate(&config) else {
+                continue;
+            };
+            let Some(fv) = self.fair_value_model.fair_values(&GameInputs {
+                book: Some(&estimate),
+                ..GameInputs::default()
+            }) else {
+                continue;
+            };
+
+            // One velocity series per game, from the home market's mid
+            let velocity_score = track_velocity(
+                &mut self.velocity_trackers,
+                Some(game_key.clone()),
+                self.momentum_config.velocity_window_size,
+                is_home.then_some(estimate.mid / 100.0),
+                cycle_start,
+            );
+
+            let fair = fv.home;
+            let fv_inputs = FairValueInputs::OrderFlow {
+                mid: estimate.mid,
+                mean: estimate.mean,
+                band: estimate.band,
+                imbalance: estimate.imbalance,
+                flow: estimate.flow,
+                samples: estimate.samples,
+            };
+            match evaluate_matched_market(
+                &side.ticker,
+                fair,
+                None,
+                side.yes_bid,
+                side.yes_ask,
+                false,
+                velocity_score,
+                None,
+                false,
+                false,
+                false,
+                Some(side),
+                now_utc,
+                live_book,
+                &strategy_config,
+                &self.momentum_config,
+                &mut self.book_pressure_trackers,
+                scorer,
+                sim_mode,
+                state_tx,
+                cycle_start,
+                "order_flow",
+                sim_config,
+                risk_config,
+                bankroll_cents,
+                &self.key,
+                FairValueMethod::OrderFlow {
+                    source: "kalshi-book".to_string(),
+                },
+                fv_inputs,
+                None,
+                fill_simulator.as_deref_mut(),
+                journal,
+            ) {
+                EvalOutcome::Closed => {
+                    result.filter_closed += 1;
+                    if sim_mode {
+                        result.closed_tickers.push((
+                            side.ticker.clone(),
+                            fair,
+                            SettleBasis::FairValue,
+                        ));
+                    }
+                }
+                EvalOutcome::Evaluated(row, intent) => {
+                    result.filter_live += 1;
+                    result.has_live_games = true;
+                    result.order_intents.extend(intent);
+                    result.rows.insert(side.ticker.clone(), row);
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Mean edge of the markets showing a positive one, 0 when none do.
+fn mean_positive_edge(rows: &HashMap<String, MarketRow>) -> f64 {
+    let edges: Vec<i32> = rows.values().map(|r| r.edge).filter(|&e| e > 0).collect();
+    if edges.is_empty() {
+        return 0.0;
+    }
+    edges.iter().map(|&e| f64::from(e)).sum::<f64>() / edges.len() as f64
+}
+
+/// Volatility of the most volatile live game in a score feed, or `None` when
+/// no game is live.
+fn score_feed_volatility(
+    cfg: &AdaptivePollConfig,
+    scores: &[ScoreUpdate],
+    regulation_secs: u16,
+) -> Option<Volatility> {
+    scores
+        .iter()
+        .filter(|u| u.game_status == crate::feed::score_feed::GameStatus::Live)
+        .map(|u| {
+            let margin = (u.home_score as i32 - u.away_score as i32).unsigned_abs();
+            let remaining = regulation_secs.saturating_sub(u.total_elapsed_seconds);
+            adaptive_poll::score_volatility(cfg, margin, remaining as u32)
+        })
+        .max()
+}
+
+/// Seconds left in regulation, or in the current overtime period.
+fn remaining_secs(update: &ScoreUpdate, regulation_secs: u16) -> u16 {
+    if update.total_elapsed_seconds >= regulation_secs {
+        update.clock_seconds
+    } else {
+        regulation_secs - update.total_elapsed_seconds
+    }
+}
+
+/// Live score-feed games currently in their end-of-game sprint.
+fn sprint_game_ids(
+    cfg: &SprintConfig,
+    scores: &[ScoreUpdate],
+    regulation_secs: u16,
+) -> HashSet<String> {
+    scores
+        .iter()
+        .filter(|u| u.game_status == crate::feed::score_feed::GameStatus::Live)
+        .filter(|u| {
+            let margin = (u.home_score as i32 - u.away_score as i32).unsigned_abs();
+            adaptive_poll::in_sprint(cfg, margin, remaining_secs(u, regulation_secs) as u32)
+        })
+        .map(|u| u.game_id.clone())
+        .collect()
+}
+
+/// Whether a feed update for `home` v `away` is for one of `games`. Dates
+/// are not compared, so the same teams on another day are evaluated too.
+fn updates_game(sport: &str, home: &str, away: &str, games: &HashSet<matcher::MarketKey>) -> bool {
+    let (home, away) = if sport == "mma" {
+        (crate::last_name(home), crate::last_name(away))
+    } else {
+        (home, away)
+    };
+    matcher::generate_key(sport, home, away, chrono::NaiveDate::MIN).is_some_and(|key| {
+        games
+            .iter()
+            .any(|g| g.sport == key.sport && g.teams == key.teams)
+    })
+}
+
+/// Volatility of the most volatile started event in an odds feed, from the
+/// devigged consensus price.
+fn odds_feed_volatility(
+    cfg: &AdaptivePollConfig,
+    odds: &[OddsUpdate],
+    odds_sources: &OddsSourcesConfig,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<Volatility> {
+    odds.iter()
+        .filter(|u| {
+            chrono::DateTime::parse_from_rfc3339(&u.commence_time)
+                .ok()
+                .is_some_and(|dt| dt < now)
+        })
+        .filter_map(|u| {
+            let (home_odds, away_odds, _, _, _, _) =
+                average_bookmaker_odds(&u.bookmakers, odds_sources)?;
+            let (home_prob, _) = strategy::devig(home_odds, away_odds);
+            Some(adaptive_poll::odds_volatility(cfg, home_prob))
+        })
+        .max()
+}
+
+/// Results from one pipeline tick.
+pub struct TickResult {
+    pub filter_live: usize,
+    pub filter_pre_game: usize,
+    pub filter_closed: usize,
+    pub earliest_commence: Option<chrono::DateTime<chrono::Utc>>,
+    pub rows: HashMap<String, MarketRow>,
+    #[allow(dead_code)]
+    pub has_live_games: bool,
+    /// Tickers detected as closed this cycle, with the price (and its basis)
+    /// their sim positions settle at if Kalshi's result never arrives.
+    pub closed_tickers: Vec<(String, u32, SettleBasis)>,
+    /// Order intents produced by evaluation in live mode.
+    pub order_intents: Vec<OrderIntent>,
+}
+
+/// What a closed market's fallback settlement price was derived from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettleBasis {
+    FairValue,
+    FinalScore,
+    /// Closing odds of a three-way game with no final score on hand.
+    ClosingOdds,
+}
+
+impl SettleBasis {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::FairValue => "fair value",
+            Self::FinalScore => "final score",
+            Self::ClosingOdds => "closing odds",
+        }
+    }
+}
+
+// ── Moved helper functions ─────────────────────────────────────────────
+
+/// Result of evaluating a single matched market through the common pipeline.
+#[allow(clippy::large_enum_variant)]
+pub enum EvalOutcome {
     /// Market is closed or filtered out.
     Closed,
     /// Market was evaluated successfully, with an optional order intent for live mode.
@@ -688,6 +1641,146 @@ pub struct OrderIntent {
     pub entry_cost_cents: u32,
     pub sell_target: u32,
     pub side: String, // "yes" or "no"
+    /// Kalshi time-in-force; the sport's `taker_time_in_force` for takers.
+    pub time_in_force: TimeInForce,
+    /// Orderbook depth when the signal fired.
+    pub book: Option<BookSnapshot>,
+    /// Exchange the order goes to; entries are signalled on Kalshi and may be
+    /// rerouted to a cheaper venue before submission.
+    pub venue: matcher::Venue,
+}
+
+/// A sport key as it appears in `MarketKey::sport` ("college-basketball" ->
+/// "COLLEGEBASKETBALL").
+pub fn index_sport_key(key: &str) -> String {
+    key.to_uppercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .collect()
+}
+
+/// Exposure held by sim positions, each charged its entry cost and fee.
+pub fn sim_exposure(positions: &[crate::tui::state::SimPosition]) -> Exposure {
+    let mut exposure = Exposure::default();
+    for p in positions {
+        let sport = p.trace.as_ref().map_or("", |t| t.sport.as_str());
+        exposure.add(
+            &p.ticker,
+            sport,
+            &p.side,
+            (p.quantity * p.entry_price + p.entry_fee) as u64,
+            p.quantity,
+        );
+    }
+    exposure
+}
+
+/// Track session drawdown from realized P&L and move between drawdown
+/// tiers, logging each change. Run once per cycle before sizing.
+pub fn observe_drawdown(
+    monitor: &mut DrawdownMonitor,
+    state_tx: &watch::Sender<AppState>,
+    config: &crate::config::DrawdownConfig,
+) -> DrawdownTier {
+    let pnl = state_tx.borrow().realized_pnl_cents;
+    let changed = monitor.observe(pnl, config);
+    let drawdown_cents = monitor.drawdown_cents();
+    state_tx.send_modify(|s| {
+        s.drawdown_cents = drawdown_cents;
+        if let Some(tier) = changed {
+            let msg = format!(
+                "Drawdown {}: {} ({})",
+                s.number_format.money(drawdown_cents as i64),
+                tier.label(),
+                tier.describe()
+            );
+            tracing::warn!("{}", msg);
+            s.drawdown_tier = tier;
+            if tier == DrawdownTier::Halted {
+                s.escalation
+                    .raise(AlertKind::LossLimit, msg.clone(), Instant::now());
+            }
+            s.push_log("WARN", msg);
+        }
+    });
+    state_tx.borrow().drawdown_tier
+}
+
+/// Rename teams whose exact key misses the index to the indexed names
+/// of their closest same-day game, when it scores at least `min_score` and
+/// is clear of its rivals. Every later `generate_key` lookup for the game
+/// then hits. MMA keys on fighter last names and is left alone.
+fn resolve_fuzzy_teams<'a>(
+    games: impl IntoIterator<Item = (&'a mut String, &'a mut String, chrono::NaiveDate)>,
+    sport: &str,
+    market_index: &matcher::MarketIndex,
+    min_score: Option<f64>,
+) {
+    let Some(min_score) = min_score.filter(|_| sport != "mma") else {
+        return;
+    };
+    for (home, away, date) in games {
+        let exact = matcher::generate_key(sport, home, away, date)
+            .is_none_or(|k| market_index.contains_key(&k));
+        if exact {
+            continue;
+        }
+        let Some(m) = matcher::fuzzy_match(market_index, sport, home, away, date) else {
+            continue;
+        };
+        if !m.accepted(min_score) {
+            tracing::debug!(sport, home = %home, away = %away, closest_home = %m.home_team,
+                closest_away = %m.away_team, score = m.score, margin = m.margin,
+                "no market match");
+            continue;
+        }
+        tracing::debug!(sport, home = %home, away = %away, matched_home = %m.home_team,
+            matched_away = %m.away_team, score = m.score, "fuzzy market match");
+        *home = m.home_team;
+        *away = m.away_team;
+    }
+}
+
+/// Indexed game for a diagnostic row. Falls back to fuzzy matching on an
+/// exact miss; the note carries the fuzzy score, or the closest game and
+/// its score when nothing clears `fuzzy_min_score` or the closest game is
+/// too near a rival.
+fn diagnostic_game<'a>(
+    market_index: &'a matcher::MarketIndex,
+    sport: &str,
+    home: &str,
+    away: &str,
+    date: chrono::NaiveDate,
+    fuzzy_min_score: Option<f64>,
+) -> (Option<&'a matcher::IndexedGame>, Option<String>) {
+    let exact = matcher::generate_key(sport, home, away, date).and_then(|k| market_index.get(&k));
+    if exact.is_some() || sport == "mma" {
+        return (exact, None);
+    }
+    let Some(min_score) = fuzzy_min_score else {
+        return (None, None);
+    };
+    match matcher::fuzzy_match(market_index, sport, home, away, date) {
+        Some(m) if m.accepted(min_score) => (
+            market_index.get(&m.key),
+            Some(format!("fuzzy {:.2}", m.score)),
+        ),
+        Some(m) if m.score >= min_score => (
+            None,
+            Some(format!(
+                "ambiguous {} vs {}, {:.2} leads by {:.2}",
+                m.away_team, m.home_team, m.score, m.margin
+            )),
+        ),
+        Some(m) => (
+            None,
+            Some(format!(
+                "closest {} vs {}, {:.2}",
+                m.away_team, m.home_team, m.score
+            )),
+        ),
+        None => (None, None),
+    }
 }
 
 /// Build diagnostic rows from all odds updates for a given sport.
@@ -696,10 +1789,11 @@ pub fn build_diagnostic_rows(
     sport: &str,
     market_index: &matcher::MarketIndex,
     source_name: &str,
+    fuzzy_min_score: Option<f64>,
 ) -> Vec<DiagnosticRow> {
     let eastern = chrono::FixedOffset::west_opt(5 * 3600)
         .unwrap_or_else(|| chrono::FixedOffset::west_opt(0).unwrap());
-    let now_utc = chrono::Utc::now();
+    let now_utc = utc_now();
 
     updates
         .iter()
@@ -744,10 +1838,17 @@ pub fn build_diagnostic_rows(
                 (update.home_team.clone(), update.away_team.clone())
             };
 
-            let matched_game = date.and_then(|d| {
-                matcher::generate_key(sport, &lookup_home, &lookup_away, d)
-                    .and_then(|k| market_index.get(&k))
-            });
+            let (matched_game, match_note) = match date {
+                Some(d) => diagnostic_game(
+                    market_index,
+                    sport,
+                    &lookup_home,
+                    &lookup_away,
+                    d,
+                    fuzzy_min_score,
+                ),
+                None => (None, None),
+            };
 
             let (kalshi_ticker, market_status, reason) = match matched_game {
                 Some(game) => {
@@ -776,6 +1877,10 @@ pub fn build_diagnostic_rows(
                 }
                 None => (None, None, "No match found".to_string()),
             };
+            let reason = match match_note {
+                Some(note) => format!("{} ({})", reason, note),
+                None => reason,
+            };
 
             DiagnosticRow {
                 sport: sport.to_string(),
@@ -797,6 +1902,7 @@ pub fn build_diagnostic_rows_from_scores(
     sport: &str,
     market_index: &matcher::MarketIndex,
     source_name: &str,
+    fuzzy_min_score: Option<f64>,
 ) -> Vec<DiagnosticRow> {
     let eastern = chrono::FixedOffset::west_opt(5 * 3600)
         .unwrap_or_else(|| chrono::FixedOffset::west_opt(0).unwrap());
@@ -828,7 +1934,7 @@ pub fn build_diagnostic_rows_from_scores(
 
             // Try to match against Kalshi markets
             // We don't have a date from ScoreUpdate, so we'll use today's date
-            let today = chrono::Utc::now().with_timezone(&eastern).date_naive();
+            let today = utc_now().with_timezone(&eastern).date_naive();
 
             let (lookup_home, lookup_away) = if sport == "mma" {
                 (
@@ -839,8 +1945,14 @@ pub fn build_diagnostic_rows_from_scores(
                 (update.home_team.clone(), update.away_team.clone())
             };
 
-            let matched_game = matcher::generate_key(sport, &lookup_home, &lookup_away, today)
-                .and_then(|k| market_index.get(&k));
+            let (matched_game, match_note) = diagnostic_game(
+                market_index,
+                sport,
+                &lookup_home,
+                &lookup_away,
+                today,
+                fuzzy_min_score,
+            );
 
             let (kalshi_ticker, market_status, reason) = match matched_game {
                 Some(game) => {
@@ -873,6 +1985,10 @@ pub fn build_diagnostic_rows_from_scores(
                 }
                 None => (None, None, "No match found".to_string()),
             };
+            let reason = match match_note {
+                Some(note) => format!("{} ({})", reason, note),
+                None => reason,
+            };
 
             DiagnosticRow {
                 sport: sport.to_string(),
@@ -890,7 +2006,7 @@ pub fn build_diagnostic_rows_from_scores(
 
 /// Format fair value basis from SignalTrace inputs for display.
 pub fn format_fair_value_basis(trace: &SignalTrace) -> String {
-    match &trace.inputs {
+    let basis = match &trace.inputs {
         FairValueInputs::Score {
             home_score,
             away_score,
@@ -902,14 +2018,128 @@ pub fn format_fair_value_basis(trace: &SignalTrace) -> String {
         FairValueInputs::Odds { devigged_prob, .. } => {
             format!("devig p={:.2}", devigged_prob)
         }
+        FairValueInputs::OrderFlow {
+            mean, imbalance, ..
+        } => {
+            format!("flow mean={:.1} imb={:+.2}", mean, imbalance)
+        }
+        FairValueInputs::Blended {
+            home_score,
+            away_score,
+            score_prob,
+            odds_prob,
+            score_weight,
+            win_prob,
+            ..
+        } => {
+            format!(
+                "{}-{} blend {:.2} (wp={:.2} x{:.2}, devig={:.2})",
+                home_score, away_score, win_prob, score_prob, score_weight, odds_prob
+            )
+        }
+    };
+    match trace.fair_value_stderr {
+        Some(se) => format!("{} ±{:.1}c", basis, se),
+        None => basis,
+    }
+}
+
+/// Game phase label for a set of fair-value inputs. Odds feeds only evaluate
+/// in-play games but carry no period/clock, so they are tagged "LIVE". Order
+/// flow knows nothing of the game and is tagged "BOOK".
+fn trace_inputs_phase(inputs: &FairValueInputs) -> String {
+    match inputs {
+        FairValueInputs::Score { phase, .. } | FairValueInputs::Blended { phase, .. } => {
+            phase.clone()
+        }
+        FairValueInputs::Odds { pre_game: true, .. } => GamePhase::PreGame.label(),
+        FairValueInputs::Odds { .. } => GamePhase::Live.label(),
+        FairValueInputs::OrderFlow { .. } => "BOOK".to_string(),
     }
 }
 
+/// Game phase at signal time, for tagging trades.
+pub fn trace_phase(trace: &SignalTrace) -> String {
+    trace_inputs_phase(&trace.inputs)
+}
+
+/// Publish the quota returned by an odds fetch and attribute the request's
+/// credits to `sport` and `purpose`. `api_request_times` holds the last
+/// hour of requests for the burn rate.
+pub fn record_api_quota(
+    quota: &ApiQuota,
+    sport: &str,
+    purpose: UsagePurpose,
+    api_request_times: &Mutex<VecDeque<Instant>>,
+    state_tx: &watch::Sender<AppState>,
+) {
+    let now = Instant::now();
+    let burn_rate = {
+        let mut times = api_request_times
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        times.push_back(now);
+        let one_hour_ago = now - Duration::from_secs(3600);
+        while times.front().is_some_and(|&t| t < one_hour_ago) {
+            times.pop_front();
+        }
+        times.len() as f64
+    };
+    // Odds requests cost at least one credit; the cost header may be missing
+    let credits = quota.requests_last.max(1);
+    state_tx.send_modify(|s| {
+        // Raised on the fetch that used the last request, or on the first
+        // fetch of a session that starts with none left
+        let had_quota = s.api_requests_remaining > 0 || s.api_requests_used == 0;
+        if quota.requests_remaining == 0 && had_quota {
+            let msg = format!(
+                "Odds API quota exhausted ({} requests used)",
+                quota.requests_used
+            );
+            tracing::error!("{}", msg);
+            s.escalation
+                .raise(AlertKind::QuotaExhausted, msg.clone(), now);
+            s.push_log("ERROR", msg);
+        }
+        s.api_requests_used = quota.requests_used;
+        s.api_requests_remaining = quota.requests_remaining;
+        s.api_burn_rate = burn_rate;
+        s.api_hours_remaining = if burn_rate > 0.0 {
+            quota.requests_remaining as f64 / burn_rate
+        } else {
+            f64::INFINITY
+        };
+        s.api_usage.record(sport, purpose, credits, now);
+    });
+}
+
 /// Helper function to format source names for display.
+/// Publish an odds source's schema-drift counter, logging when it grows.
+fn publish_schema_drift(source_key: &str, count: u64, state_tx: &watch::Sender<AppState>) {
+    if count == 0 {
+        return;
+    }
+    state_tx.send_modify(|s| {
+        let prev = s.odds_schema_drift.insert(source_key.to_string(), count).unwrap_or(0);
+        if count > prev {
+            s.push_log(
+                "WARN",
+                format!(
+                    "{}: {} event(s) failed schema validation ({} total)",
+                    source_key,
+                    count - prev,
+                    count
+                ),
+            );
+        }
+    });
+}
+
 fn format_source_name(source_key: &str) -> String {
     match source_key {
         "the-odds-api" => "TheOddsAPI".to_string(),
         "draftkings" => "DraftKings".to_string(),
+        "pinnacle" => "Pinnacle".to_string(),
         "scraped-bovada" => "Bovada".to_string(),
         other => other.to_string(),
     }
@@ -917,15 +2147,19 @@ fn format_source_name(source_key: &str) -> String {
 
 /// Common evaluation pipeline for a matched Kalshi market.
 #[allow(clippy::too_many_arguments)]
+#[tracing::instrument(skip_all)]
 pub fn evaluate_matched_market(
     ticker: &str,
     fair: u32,
+    fair_value_stderr: Option<f64>,
     fallback_bid: u32,
     fallback_ask: u32,
     is_inverse: bool,
     velocity_score: f64,
     staleness_secs: Option<u64>,
     is_stale: bool,
+    fv_out_of_bounds: bool,
+    news_halted: bool,
     side_market: Option<&matcher::SideMarket>,
     now_utc: chrono::DateTime<chrono::Utc>,
     live_book_engine: &LiveBook,
@@ -945,6 +2179,7 @@ pub fn evaluate_matched_market(
     fair_value_inputs: FairValueInputs,
     odds_api_fair_value: Option<u32>,
     fill_simulator: Option<&mut crate::engine::FillSimulator>,
+    journal: &Journal,
 ) -> EvalOutcome {
     // Check market is open
     let market_open = side_market.is_some_and(|sm| {
@@ -960,36 +2195,22 @@ pub fn evaluate_matched_market(
     }
 
     // Get live bid/ask from orderbook - BOTH SIDES
-    let (yes_bid, yes_ask, no_bid, no_ask) = if let Ok(book) = live_book_engine.lock() {
-        if let Some(depth) = book.get(ticker) {
-            let (yb, ya, nb, na) = depth.best_bid_ask();
-            if ya > 0 {
-                (yb, ya, nb, na)
-            } else {
-                // Fallback: use fallback values for YES, derive NO from complement
-                (
-                    fallback_bid,
-                    fallback_ask,
-                    100u32.saturating_sub(fallback_ask),
-                    100u32.saturating_sub(fallback_bid),
-                )
+    let mut liquidity_cap = None;
+    let (yes_bid, yes_ask, no_bid, no_ask) = live_book_engine
+        .with_mut(ticker, |depth| {
+            if let Some(cfg) = &risk_config.liquidity_cap {
+                liquidity_cap = depth.sample_liquidity(cfg);
             }
-        } else {
-            (
-                fallback_bid,
-                fallback_ask,
-                100u32.saturating_sub(fallback_ask),
-                100u32.saturating_sub(fallback_bid),
-            )
-        }
-    } else {
-        (
+            depth.best_bid_ask()
+        })
+        .filter(|&(_, ya, _, _)| ya > 0)
+        // Fallback: use fallback values for YES, derive NO from complement
+        .unwrap_or((
             fallback_bid,
             fallback_ask,
             100u32.saturating_sub(fallback_ask),
             100u32.saturating_sub(fallback_bid),
-        )
-    };
+        ));
 
     // Book pressure (use yes_bid already extracted)
     let bpt = book_pressure_trackers
@@ -998,7 +2219,7 @@ pub fn evaluate_matched_market(
     bpt.push(
         yes_bid as u64,
         100u64.saturating_sub(yes_bid as u64),
-        Instant::now(),
+        cycle_start,
     );
     let pressure_score = bpt.score();
     let momentum = scorer.composite(velocity_score, pressure_score);
@@ -1006,41 +2227,77 @@ pub fn evaluate_matched_market(
     let fv_source = match &fair_value_method {
         FairValueMethod::OddsFeed { source } => source.clone(),
         FairValueMethod::ScoreFeed { source } => source.clone(),
+        FairValueMethod::OrderFlow { source } => source.clone(),
     };
-
-    // CRITICAL: Skip stale data before strategy evaluation
-    if is_stale {
+    let phase = trace_inputs_phase(&fair_value_inputs);
+
+    // CRITICAL: Skip blocked games, stale data, out-of-bounds fair values and
+    // news-halted games before strategy evaluation
+    let blocked = !state_tx.borrow().game_filter.allows(ticker);
+    if blocked || is_stale || fv_out_of_bounds || news_halted {
+        let reason = if blocked {
+            SkipReason::Blocked
+        } else if is_stale {
+            SkipReason::Stale
+        } else if fv_out_of_bounds {
+            SkipReason::FvOutOfBounds
+        } else {
+            SkipReason::NewsHalt
+        };
         let row = MarketRow {
             ticker: ticker.to_string(),
             fair_value: fair,
             bid: yes_bid,
             ask: yes_ask,
             edge: 0,
-            action: "STALE".to_string(),
+            action: reason.label().to_string(),
+            skip_reason: Some(reason),
             latency_ms: Some(cycle_start.elapsed().as_millis() as u64),
             momentum_score: momentum,
             staleness_secs,
             odds_api_fair_value,
             fair_value_source: fv_source,
+            phase,
+            sprint: false,
             smoothed_bid: yes_bid as f64,
             smoothed_ask: yes_ask as f64,
         };
         return EvalOutcome::Evaluated(row, None);
     }
 
-    // Evaluate strategy - BOTH SIDES
+    // Evaluate strategy - BOTH SIDES. With stderr multiples configured, the
+    // edge has to clear the fair value's own noise instead of a fixed cent.
+    let held = state_tx
+        .borrow()
+        .exposure
+        .held(ticker)
+        .map(|(side, contracts)| (side.to_string(), contracts));
+    let taker_threshold = strategy::stderr_edge_threshold(
+        strategy_config.taker_edge_threshold,
+        strategy_config.taker_stderr_multiple,
+        fair_value_stderr,
+    );
+    let maker_threshold = strategy::stderr_edge_threshold(
+        strategy_config.maker_edge_threshold,
+        strategy_config.maker_stderr_multiple,
+        fair_value_stderr,
+    );
     let dual = strategy::evaluate_best_side(
         fair,
         yes_bid,
         yes_ask,
         no_bid,
         no_ask,
-        strategy_config.taker_edge_threshold,
-        strategy_config.maker_edge_threshold,
+        taker_threshold,
+        maker_threshold,
         strategy_config.min_edge_after_fees,
         bankroll_cents,
         risk_config.kelly_fraction,
-        risk_config.max_contracts_per_market,
+        liquidity_cap.map_or(risk_config.max_contracts_per_market, |cap| {
+            cap.min(risk_config.max_contracts_per_market)
+        }),
+        held.as_ref()
+            .map(|(side, contracts)| (side.as_str(), *contracts)),
         strategy_config.slippage_buffer_cents,
     );
     let mut signal = dual.signal;
@@ -1066,9 +2323,9 @@ pub fn evaluate_matched_market(
     let momentum_gated = pre_gate_action != signal.action && !bypass_momentum;
 
     // Max edge gate: skip suspiciously high edges
-    if signal.edge > strategy_config.max_edge_threshold as i32
-        && signal.action != strategy::TradeAction::Skip
-    {
+    let skip_reason = if signal.action == strategy::TradeAction::Skip {
+        Some(signal.skip_reason.unwrap_or(SkipReason::BelowThreshold))
+    } else if signal.edge > strategy_config.max_edge_threshold as i32 {
         tracing::warn!(
             ticker = %ticker,
             edge = signal.edge,
@@ -1078,37 +2335,26 @@ pub fn evaluate_matched_market(
             threshold = strategy_config.max_edge_threshold,
             "skipping trade: edge exceeds max threshold"
         );
-        let row = MarketRow {
-            ticker: ticker.to_string(),
-            fair_value: fair,
-            bid,
-            ask,
-            edge: signal.edge,
-            action: "MAX_EDGE".to_string(),
-            latency_ms: Some(cycle_start.elapsed().as_millis() as u64),
-            momentum_score: momentum,
-            staleness_secs,
-            odds_api_fair_value,
-            fair_value_source: fv_source,
-            smoothed_bid: bid as f64,
-            smoothed_ask: ask as f64,
-        };
-        return EvalOutcome::Evaluated(row, None);
-    }
+        Some(SkipReason::MaxEdge)
+    } else {
+        None
+    };
 
-    let action_str = match &signal.action {
-        strategy::TradeAction::TakerBuy => "TAKER",
-        strategy::TradeAction::MakerBuy { .. } => "MAKER",
-        strategy::TradeAction::Skip => "SKIP",
+    let action_str = match (&signal.action, skip_reason) {
+        (_, Some(reason)) => reason.label(),
+        (strategy::TradeAction::TakerBuy, None) => "TAKER",
+        (strategy::TradeAction::MakerBuy { .. }, None) => "MAKER",
+        (strategy::TradeAction::Skip, None) => unreachable!(),
     };
 
     // Build signal trace for provenance
-    let trace = SignalTrace {
+    let mut trace = SignalTrace {
         sport: sport.to_string(),
         ticker: ticker.to_string(),
         timestamp: Instant::now(),
         fair_value_method,
         fair_value_cents: fair,
+        fair_value_stderr,
         inputs: fair_value_inputs,
         best_bid: bid,
         best_ask: ask,
@@ -1118,25 +2364,121 @@ pub fn evaluate_matched_market(
         quantity: signal.quantity,
         momentum_score: momentum,
         momentum_gated,
+        skip_reason,
     };
 
-    let row = MarketRow {
+    let mut row = MarketRow {
         ticker: ticker.to_string(),
         fair_value: fair,
         bid,
         ask,
         edge: signal.edge,
         action: action_str.to_string(),
+        skip_reason,
         latency_ms: Some(cycle_start.elapsed().as_millis() as u64),
         momentum_score: momentum,
         staleness_secs,
         odds_api_fair_value,
         fair_value_source: fv_source,
+        phase,
+        sprint: false,
         smoothed_bid: bid as f64,
         smoothed_ask: ask as f64,
     };
 
-    if signal.action != strategy::TradeAction::Skip {
+    // Journal signals that had a tradeable edge but were rejected
+    let reject = |reason: SkipReason, row: &mut MarketRow, trace: &mut SignalTrace| {
+        row.action = reason.label().to_string();
+        row.skip_reason = Some(reason);
+        trace.action = reason.label().to_string();
+        trace.skip_reason = Some(reason);
+        journal.record(&JournalEntry::Reject {
+            ts: chrono::Utc::now(),
+            ticker: ticker.to_string(),
+            side: trade_side.to_string(),
+            reason,
+            sim: sim_mode,
+            trace: trace.clone(),
+        });
+    };
+
+    if skip_reason == Some(SkipReason::MaxEdge) {
+        reject(SkipReason::MaxEdge, &mut row, &mut trace);
+        return EvalOutcome::Evaluated(row, None);
+    }
+
+    // Book too thin for even one contract under the liquidity cap
+    if skip_reason.is_none() && liquidity_cap == Some(0) {
+        reject(SkipReason::RiskCap, &mut row, &mut trace);
+        return EvalOutcome::Evaluated(row, None);
+    }
+
+    // With feeds down, the degradation tier limits or stops new entries
+    if skip_reason.is_none() {
+        let is_taker = matches!(signal.action, strategy::TradeAction::TakerBuy);
+        if !state_tx.borrow().feed_tier.allows_entry(is_taker) {
+            reject(SkipReason::FeedDegraded, &mut row, &mut trace);
+            return EvalOutcome::Evaluated(row, None);
+        }
+        // Past a soft drawdown limit, taker entries stop before all entries do
+        if !state_tx.borrow().drawdown_tier.allows_entry(is_taker) {
+            reject(SkipReason::Drawdown, &mut row, &mut trace);
+            return EvalOutcome::Evaluated(row, None);
+        }
+    }
+
+    // A taker sweeps the ask side: price the size against every level it
+    // would take, and cut it to the depth that keeps the edge
+    let mut depth_fill = None;
+    if skip_reason.is_none()
+        && strategy_config.walk_taker_depth
+        && signal.action == strategy::TradeAction::TakerBuy
+        && signal.quantity > 0
+    {
+        let asks = live_book_engine
+            .read(ticker, |d| d.ask_levels(trade_side))
+            .unwrap_or_default();
+        // No book yet: sized against the fallback quote as before
+        if !asks.is_empty() {
+            let side_fair = if trade_side == "yes" {
+                fair
+            } else {
+                100u32.saturating_sub(fair)
+            };
+            match depth_walk::size_to_depth(
+                &asks,
+                side_fair,
+                signal.quantity,
+                strategy_config.min_edge_after_fees as i32,
+                strategy_config.slippage_buffer_cents,
+            ) {
+                Some(fill) => {
+                    if fill.quantity < signal.quantity {
+                        tracing::info!(
+                            ticker = %ticker,
+                            wanted = signal.quantity,
+                            quantity = fill.quantity,
+                            worst_price = fill.worst_price,
+                            "taker size cut to book depth"
+                        );
+                    }
+                    signal.quantity = fill.quantity;
+                    signal.price = fill.worst_price;
+                    signal.net_profit_estimate =
+                        fill.net_profit(side_fair, strategy_config.slippage_buffer_cents);
+                    trace.quantity = signal.quantity;
+                    trace.net_profit_estimate = signal.net_profit_estimate;
+                    depth_fill = Some(fill);
+                }
+                None => {
+                    reject(SkipReason::ThinBook, &mut row, &mut trace);
+                    return EvalOutcome::Evaluated(row, None);
+                }
+            }
+        }
+    }
+
+    if skip_reason.is_none() {
         let mode_label = if sim_mode { "sim" } else { "live" };
         tracing::warn!(
             ticker = %ticker,
@@ -1154,30 +2496,55 @@ pub fn evaluate_matched_market(
     }
 
     // Common break-even validation for both sim and live
-    if signal.action != strategy::TradeAction::Skip {
+    if skip_reason.is_none() {
         let fill_price = match &signal.action {
-            strategy::TradeAction::TakerBuy => ask,
+            strategy::TradeAction::TakerBuy => depth_fill.map_or(ask, |f| f.worst_price),
             strategy::TradeAction::MakerBuy { bid_price } => *bid_price,
             strategy::TradeAction::Skip => unreachable!(),
         };
 
         let qty = signal.quantity;
         let is_taker = matches!(signal.action, strategy::TradeAction::TakerBuy);
-        let entry_cost = (qty * fill_price) as i64;
-        let entry_fee = calculate_fee(fill_price, qty, is_taker) as i64;
+        let (entry_cost, entry_fee) = match depth_fill {
+            Some(f) => (f.cost_cents as i64, f.entry_fee_cents as i64),
+            None => (
+                (qty * fill_price) as i64,
+                calculate_fee(fill_price, qty, is_taker) as i64,
+            ),
+        };
         let total_cost = entry_cost + entry_fee;
         let entry_cost_total = entry_cost + entry_fee;
 
-        // Validate break-even is achievable before entering
-        if let Some(be_price) =
-            crate::engine::fees::break_even_sell_price(entry_cost_total as u32, qty, true)
-        {
-            if be_price > 95 {
-                tracing::warn!(
-                    ticker = %ticker,
-                    break_even = be_price,
+        // One hot game or sport can't take the whole bankroll
+        let exposure_cap = state_tx.borrow().exposure.check(
+            ticker,
+            sport,
+            entry_cost_total as u64,
+            risk_config,
+        );
+        if let Some(cap) = exposure_cap {
+            tracing::warn!(
+                ticker = %ticker,
+                sport = sport,
+                cost = entry_cost_total,
+                cap = cap.label(),
+                "skipping trade: exposure cap"
+            );
+            reject(SkipReason::RiskCap, &mut row, &mut trace);
+            return EvalOutcome::Evaluated(row, None);
+        }
+
+        // Validate break-even is achievable before entering
+        if let Some(be_price) =
+            crate::engine::fees::break_even_sell_price(entry_cost_total as u32, qty, true)
+        {
+            if be_price > 95 {
+                tracing::warn!(
+                    ticker = %ticker,
+                    break_even = be_price,
                     "skipping trade: break-even too high (>95c)"
                 );
+                reject(SkipReason::BreakEvenInfeasible, &mut row, &mut trace);
                 return EvalOutcome::Evaluated(row, None);
             }
         } else {
@@ -1187,11 +2554,12 @@ pub fn evaluate_matched_market(
                 quantity = qty,
                 "skipping trade: impossible to break even"
             );
+            reject(SkipReason::BreakEvenInfeasible, &mut row, &mut trace);
             return EvalOutcome::Evaluated(row, None);
         }
 
         let sell_target = if sim_config.use_break_even_exit {
-            let total_entry = (qty * fill_price) + calculate_fee(fill_price, qty, is_taker);
+            let total_entry = entry_cost_total as u32;
             match crate::engine::fees::break_even_sell_price(total_entry, qty, false) {
                 Some(price) => price,
                 None => {
@@ -1201,6 +2569,7 @@ pub fn evaluate_matched_market(
                         quantity = qty,
                         "skipping trade: no viable sell target"
                     );
+                    reject(SkipReason::BreakEvenInfeasible, &mut row, &mut trace);
                     return EvalOutcome::Evaluated(row, None);
                 }
             }
@@ -1208,6 +2577,9 @@ pub fn evaluate_matched_market(
             fair
         };
 
+        // Full depth at decision time, journaled alongside the trace
+        let book_snapshot = live_book_engine.read(ticker, |d| d.snapshot());
+
         if sim_mode {
             // Simulation mode: mutate state directly
             let signal_ask = ask;
@@ -1248,7 +2620,11 @@ pub fn evaluate_matched_market(
                         if s.sim_balance_cents < actual_total_cost {
                             return;
                         }
-                        if s.sim_positions.iter().any(|p| p.ticker == ticker_owned) {
+                        // The other side of a held ticker is never entered
+                        if s.sim_positions
+                            .iter()
+                            .any(|p| p.ticker == ticker_owned && p.side != trade_side)
+                        {
                             return;
                         }
 
@@ -1256,17 +2632,29 @@ pub fn evaluate_matched_market(
 
                         s.sim_balance_cents -= actual_total_cost;
                         s.sim_entries_filled += 1;
-                        s.sim_positions.push(crate::tui::state::SimPosition {
-                            ticker: ticker_owned.clone(),
-                            quantity: qty,
-                            entry_price: actual_price,
-                            sell_price: actual_sell_target,
-                            entry_fee: actual_entry_fee as u32,
-                            filled_at: std::time::Instant::now(),
-                            signal_ask,
-                            trace: Some(trace_clone.clone()),
-                        });
+                        s.exposure.add(&ticker_owned, sport, trade_side, actual_total_cost as u64, qty);
+                        s.kelly_tuner.record_entry(&ticker_owned, sport, signal.net_profit_estimate as i64);
+                        // A top-up joins the held position at the higher of the two targets
+                        let mut actual_sell_target = actual_sell_target;
+                        if let Some(p) = s.sim_positions.iter_mut().find(|p| p.ticker == ticker_owned) {
+                            p.add_lot(qty, actual_price, actual_entry_fee as u32);
+                            p.sell_price = p.sell_price.max(actual_sell_target);
+                            actual_sell_target = p.sell_price;
+                        } else {
+                            s.sim_positions.push(crate::tui::state::SimPosition {
+                                ticker: ticker_owned.clone(),
+                                side: trade_side.to_string(),
+                                quantity: qty,
+                                entry_price: actual_price,
+                                sell_price: actual_sell_target,
+                                entry_fee: actual_entry_fee as u32,
+                                filled_at: cycle_start,
+                                signal_ask,
+                                trace: Some(trace_clone.clone()),
+                            });
+                        }
                         s.push_trade(crate::tui::state::TradeRow {
+                            id: 0,
                             time: chrono::Local::now().format("%H:%M:%S").to_string(),
                             action: "BUY".to_string(),
                             ticker: ticker_owned.clone(),
@@ -1277,6 +2665,8 @@ pub fn evaluate_matched_market(
                             slippage: Some(slippage),
                             source: source_owned.clone(),
                             fair_value_basis: format_fair_value_basis(&trace_clone),
+                            phase: trace_phase(&trace_clone),
+                            note: String::new(),
                         });
                         s.push_log(
                             "TRADE",
@@ -1286,6 +2676,31 @@ pub fn evaluate_matched_market(
                             ),
                         );
                         s.total_slippage_cents += slippage as i64;
+
+                        journal.record(&JournalEntry::Order {
+                            ts: chrono::Utc::now(),
+                            ticker: ticker_owned.clone(),
+                            side: trade_side.to_string(),
+                            action: "buy".to_string(),
+                            quantity: qty,
+                            price: actual_price,
+                            is_taker,
+                            sim: true,
+                            source: source_owned.clone(),
+                            trace: trace_clone.clone(),
+                            book: book_snapshot.clone(),
+                        });
+
+                        let fee_alert = s.fee_tracker.record(&crate::engine::FeeSample {
+                            ticker: ticker_owned.clone(),
+                            expected_cents: entry_fee as u32,
+                            realized_cents: actual_entry_fee as u32,
+                            expected_taker: is_taker,
+                            realized_taker: Some(is_taker),
+                        });
+                        if let Some(msg) = fee_alert {
+                            s.push_log("WARN", msg);
+                        }
                     }
                     crate::engine::FillResult::Missed => {
                         s.sim_entries_missed += 1;
@@ -1330,7 +2745,19 @@ pub fn evaluate_matched_market(
                 entry_cost_cents: total_cost as u32,
                 sell_target,
                 side: trade_side.to_string(),
+                time_in_force: if is_taker {
+                    strategy_config.taker_time_in_force
+                } else {
+                    TimeInForce::GoodTillCanceled
+                },
+                book: book_snapshot,
+                venue: matcher::Venue::Kalshi,
             };
+            // Counted until the engine refreshes exposure next cycle
+            state_tx.send_modify(|s| {
+                s.exposure
+                    .add(ticker, sport, trade_side, total_cost as u64, qty)
+            });
             return EvalOutcome::Evaluated(row, Some(intent));
         }
     }
@@ -1338,6 +2765,27 @@ pub fn evaluate_matched_market(
     EvalOutcome::Evaluated(row, None)
 }
 
+/// Record a fair-value sample (if any) in the game's velocity tracker and
+/// return its score. Games the matcher cannot key have no velocity.
+fn track_velocity(
+    velocity_trackers: &mut HashMap<matcher::MarketKey, VelocityTracker>,
+    game_key: Option<matcher::MarketKey>,
+    window_size: usize,
+    sample: Option<f64>,
+    now: Instant,
+) -> f64 {
+    let Some(game_key) = game_key else {
+        return 0.0;
+    };
+    let vt = velocity_trackers
+        .entry(game_key)
+        .or_insert_with(|| VelocityTracker::new(window_size));
+    if let Some(prob) = sample {
+        vt.push(prob, now);
+    }
+    vt.score()
+}
+
 /// Process score feed updates through the fair-value/matching/evaluation pipeline.
 /// Unified for all sports: uses `regulation_secs` to determine OT threshold.
 #[allow(clippy::too_many_arguments)]
@@ -1349,19 +2797,26 @@ fn process_score_updates(
     live_book_engine: &LiveBook,
     strategy_config: &StrategyConfig,
     momentum_config: &MomentumConfig,
-    velocity_trackers: &mut HashMap<String, VelocityTracker>,
+    velocity_trackers: &mut HashMap<matcher::MarketKey, VelocityTracker>,
     book_pressure_trackers: &mut HashMap<String, BookPressureTracker>,
+    fv_bound_alerts: &mut HashSet<String>,
+    news_halts: &NewsHalts,
     scorer: &MomentumScorer,
     sim_mode: bool,
     state_tx: &watch::Sender<AppState>,
     cycle_start: Instant,
     last_score_fetch: &HashMap<String, Instant>,
+    sprint_games: &HashSet<String>,
+    sprint_stale_threshold_secs: u64,
     sim_config: &crate::config::SimulationConfig,
-    fair_value_source: &FairValueSource,
+    model: &dyn FairValueModel,
     risk_config: &crate::config::RiskConfig,
     bankroll_cents: u64,
-    cached_odds_for_validation: &[OddsUpdate],
+    cached_odds: &[OddsUpdate],
+    odds_sources: &OddsSourcesConfig,
+    mut segment_baselines: Option<&mut SegmentBaselines>,
     mut fill_simulator: Option<&mut crate::engine::FillSimulator>,
+    journal: &Journal,
 ) -> TickResult {
     let mut filter_live: usize = 0;
     let mut filter_pre_game: usize = 0;
@@ -1369,52 +2824,51 @@ fn process_score_updates(
     let earliest_commence: Option<chrono::DateTime<chrono::Utc>> = None;
     let mut rows: HashMap<String, MarketRow> = HashMap::new();
     let mut has_live_games = false;
-    let mut closed_tickers: Vec<(String, u32)> = Vec::new();
+    let mut closed_tickers: Vec<(String, u32, SettleBasis)> = Vec::new();
     let mut order_intents: Vec<OrderIntent> = Vec::new();
-    let now_utc = chrono::Utc::now();
-
-    // Get win_prob_table from fair_value_source
-    let win_prob_table = match fair_value_source {
-        FairValueSource::ScoreFeed { win_prob, .. } => win_prob,
-        _ => {
-            return TickResult {
-                filter_live: 0,
-                filter_pre_game: 0,
-                filter_closed: 0,
-                earliest_commence: None,
-                rows: HashMap::new(),
-                has_live_games: false,
-                closed_tickers: Vec::new(),
-                order_intents: Vec::new(),
-            }
-        }
+    let now_utc = utc_now();
+
+    let Some(score_model) = model.score_model() else {
+        return TickResult {
+            filter_live: 0,
+            filter_pre_game: 0,
+            filter_closed: 0,
+            earliest_commence: None,
+            rows: HashMap::new(),
+            has_live_games: false,
+            closed_tickers: Vec::new(),
+            order_intents: Vec::new(),
+        };
     };
+    let win_prob_table = &score_model.table;
+    let soccer_model = score_model.soccer.as_ref();
 
-    // Build odds-api fair value lookup from cached odds (for validation mode).
-    // Maps (normalized_home, normalized_away) -> home_fair_value_cents.
-    let odds_api_fv_lookup: HashMap<(String, String), u32> = if !cached_odds_for_validation.is_empty()
-    {
-        cached_odds_for_validation
-            .iter()
-            .filter_map(|ou| {
-                let (home_fv, _) = {
-                    let avg = average_bookmaker_odds(&ou.bookmakers)?;
-                    let (home_odds, away_odds, _, _, _) = avg;
-                    let (hfv, _afv) = strategy::devig(home_odds, away_odds);
-                    (strategy::fair_value_cents(hfv), strategy::fair_value_cents(_afv))
-                };
-                let home_norm = ou.home_team.to_uppercase();
-                let away_norm = ou.away_team.to_uppercase();
-                Some(((home_norm, away_norm), home_fv))
-            })
-            .collect()
-    } else {
-        HashMap::new()
-    };
+    // Consensus odds from cached odds (validation mode, or models that blend
+    // in odds). Maps (normalized_home, normalized_away) -> consensus.
+    let odds_lookup: HashMap<(String, String), ConsensusOdds> = cached_odds
+        .iter()
+        .filter_map(|ou| {
+            let (home, away, draw, _, bookmakers, bookmaker_weights) =
+                average_bookmaker_odds(&ou.bookmakers, odds_sources)?;
+            let home_norm = ou.home_team.to_uppercase();
+            let away_norm = ou.away_team.to_uppercase();
+            let consensus = ConsensusOdds {
+                quote: OddsQuote {
+                    home,
+                    away,
+                    draw,
+                    stderr: bookmaker_devig_stderr(&ou.bookmakers, odds_sources),
+                },
+                bookmakers,
+                bookmaker_weights,
+            };
+            Some(((home_norm, away_norm), consensus))
+        })
+        .collect();
 
     // OT period threshold: for 2-half sports (regulation <= 2400), OT at period > 2.
     // For 4-quarter sports (regulation > 2400), OT at period > 4.
-    let ot_period_threshold: u8 = if regulation_secs <= 2400 { 2 } else { 4 };
+    let ot_period_threshold = win_prob_table.regulation_periods();
 
     for update in updates {
         match update.game_status {
@@ -1424,10 +2878,49 @@ fn process_score_updates(
             }
             crate::feed::score_feed::GameStatus::Finished => {
                 filter_closed += 1;
+                // Home, away and draw settle together on the final score
+                if let (true, Some(_)) = (sim_mode, soccer_model) {
+                    let eastern = chrono::FixedOffset::west_opt(5 * 3600).unwrap();
+                    let today = utc_now().with_timezone(&eastern).date_naive();
+                    let game =
+                        matcher::generate_key(sport, &update.home_team, &update.away_team, today)
+                            .and_then(|k| market_index.get(&k));
+                    if let Some(game) = game {
+                        let score_diff = update.home_score as i32 - update.away_score as i32;
+                        let result = ThreeWayResult::from_score_diff(score_diff);
+                        let prices = three_way_settlement(Some(result), (0.0, 0.0, 0.0));
+                        for (side, price) in
+                            [&game.home, &game.away, &game.draw].into_iter().zip(prices)
+                        {
+                            if let Some(side) = side {
+                                closed_tickers.push((
+                                    side.ticker.clone(),
+                                    price,
+                                    SettleBasis::FinalScore,
+                                ));
+                            }
+                        }
+                    }
+                    continue;
+                }
                 // Record closed ticker with fair value for sim settlement
                 if sim_mode {
                     let score_diff = update.home_score as i32 - update.away_score as i32;
-                    let (home_fair, _) = if update.period > ot_period_threshold {
+                    // A decided game settles on the final score: an overtime that
+                    // ends early (NFL) or has no clock (college football) leaves
+                    // the clock-based model short of certainty.
+                    let basis = if score_diff != 0 {
+                        SettleBasis::FinalScore
+                    } else {
+                        SettleBasis::FairValue
+                    };
+                    let (home_fair, _) = if score_diff != 0 {
+                        if score_diff > 0 {
+                            (100, 0)
+                        } else {
+                            (0, 100)
+                        }
+                    } else if update.period > ot_period_threshold {
                         let ot_elapsed =
                             update.total_elapsed_seconds.saturating_sub(regulation_secs);
                         win_prob_table.fair_value_overtime(score_diff, ot_elapsed)
@@ -1435,7 +2928,7 @@ fn process_score_updates(
                         win_prob_table.fair_value(score_diff, update.total_elapsed_seconds)
                     };
                     let eastern = chrono::FixedOffset::west_opt(5 * 3600).unwrap();
-                    let today = chrono::Utc::now().with_timezone(&eastern).date_naive();
+                    let today = utc_now().with_timezone(&eastern).date_naive();
                     if let Some(mkt) = matcher::find_match(
                         market_index,
                         sport,
@@ -1443,8 +2936,39 @@ fn process_score_updates(
                         &update.away_team,
                         today,
                     ) {
-                        closed_tickers.push((mkt.ticker.clone(), home_fair));
+                        closed_tickers.push((mkt.ticker.clone(), home_fair, basis));
+                    }
+                }
+                // Period markets settle on each segment's final margin
+                if let Some(baselines) = segment_baselines.as_deref_mut() {
+                    let eastern = chrono::FixedOffset::west_opt(5 * 3600).unwrap();
+                    let today = utc_now().with_timezone(&eastern).date_naive();
+                    let game =
+                        matcher::generate_key(sport, &update.home_team, &update.away_team, today)
+                            .and_then(|k| market_index.get(&k));
+                    if let (true, Some(game)) = (sim_mode, game) {
+                        for pf in period_fair_values(
+                            game,
+                            update,
+                            regulation_secs,
+                            win_prob_table,
+                            baselines,
+                        ) {
+                            for (side, fair) in [
+                                (&pf.period.home, pf.home_fair),
+                                (&pf.period.away, pf.away_fair),
+                            ] {
+                                if let Some(side) = side {
+                                    closed_tickers.push((
+                                        side.ticker.clone(),
+                                        fair,
+                                        SettleBasis::FairValue,
+                                    ));
+                                }
+                            }
+                        }
                     }
+                    baselines.retain(|(game_id, _), _| game_id != &update.game_id);
                 }
                 continue;
             }
@@ -1456,24 +2980,175 @@ fn process_score_updates(
         let staleness_secs = last_score_fetch
             .get(&update.game_id)
             .map(|t| cycle_start.duration_since(*t).as_secs());
-        let is_stale = staleness_secs.is_some_and(|s| s > 10);
+        let sprinting = sprint_games.contains(&update.game_id);
+        let stale_threshold_secs = if sprinting {
+            sprint_stale_threshold_secs
+        } else {
+            10
+        };
+        let is_stale = staleness_secs.is_some_and(|s| s > stale_threshold_secs);
+        let news_halted = news_halts
+            .halt_for(&update.home_team, &update.away_team, now_utc)
+            .is_some();
 
         let score_diff = update.home_score as i32 - update.away_score as i32;
-        let (home_fair, _away_fair) = if update.period > ot_period_threshold {
-            let ot_elapsed = update.total_elapsed_seconds.saturating_sub(regulation_secs);
-            win_prob_table.fair_value_overtime(score_diff, ot_elapsed)
-        } else {
-            win_prob_table.fair_value(score_diff, update.total_elapsed_seconds)
+        let odds = odds_lookup.get(&(
+            update.home_team.to_uppercase(),
+            update.away_team.to_uppercase(),
+        ));
+        let Some(fv) = model.fair_values(&GameInputs {
+            score: Some(ScoreState {
+                score_diff,
+                total_elapsed_secs: update.total_elapsed_seconds,
+                period: update.period,
+                red_cards: (update.home_red_cards, update.away_red_cards),
+            }),
+            odds: odds.map(|c| c.quote),
+            book: None,
+        }) else {
+            continue;
         };
 
-        let vt = velocity_trackers
-            .entry(update.game_id.clone())
-            .or_insert_with(|| VelocityTracker::new(momentum_config.velocity_window_size));
-        vt.push(home_fair as f64 / 100.0, Instant::now());
-        let velocity_score = vt.score();
+        if let (Some(_), Some(draw_fair)) = (soccer_model, fv.draw) {
+            let eastern = chrono::FixedOffset::west_opt(5 * 3600).unwrap();
+            let today = utc_now().with_timezone(&eastern).date_naive();
+            let (home_fair, away_fair) = (fv.home, fv.away);
+            let game_key =
+                matcher::generate_key(sport, &update.home_team, &update.away_team, today);
+            let velocity_score = track_velocity(
+                velocity_trackers,
+                game_key.clone(),
+                momentum_config.velocity_window_size,
+                Some(home_fair as f64 / 100.0),
+                cycle_start,
+            );
+            let Some(game) = game_key.and_then(|k| market_index.get(&k)) else {
+                continue;
+            };
+            let phase = GamePhase::from_update(update, ot_period_threshold).label();
+
+            let mut game_closed = false;
+            for (side, fair, label) in [
+                (&game.home, home_fair, "HOME"),
+                (&game.away, away_fair, "AWAY"),
+                (&game.draw, draw_fair, "DRAW"),
+            ] {
+                let Some(side) = side else { continue };
+                let fv_method = FairValueMethod::ScoreFeed {
+                    source: model.name().to_string(),
+                };
+                let fv_inputs = score_fv_inputs(update, phase.clone(), fair, &fv, label, odds);
+                match evaluate_matched_market(
+                    &side.ticker,
+                    fair,
+                    fv.stderr,
+                    side.yes_bid,
+                    side.yes_ask,
+                    false,
+                    velocity_score,
+                    staleness_secs,
+                    is_stale,
+                    false,
+                    news_halted,
+                    Some(side),
+                    now_utc,
+                    live_book_engine,
+                    strategy_config,
+                    momentum_config,
+                    book_pressure_trackers,
+                    scorer,
+                    sim_mode,
+                    state_tx,
+                    cycle_start,
+                    label,
+                    sim_config,
+                    risk_config,
+                    bankroll_cents,
+                    sport,
+                    fv_method,
+                    fv_inputs,
+                    None,
+                    fill_simulator.as_deref_mut(),
+                    journal,
+                ) {
+                    EvalOutcome::Closed => {
+                        filter_closed += 1;
+                        game_closed = true;
+                    }
+                    EvalOutcome::Evaluated(mut row, intent) => {
+                        filter_live += 1;
+                        if let Some(i) = intent {
+                            order_intents.push(i);
+                        }
+                        row.sprint = sprinting;
+                        rows.insert(side.ticker.clone(), row);
+                    }
+                }
+            }
+
+            // Settle all three sides together, as the odds path does. The
+            // feed hasn't called the game final yet, so this is the model's
+            // fair value until the Finished update replaces it with the score.
+            if sim_mode && game_closed {
+                let probs = (
+                    home_fair as f64 / 100.0,
+                    away_fair as f64 / 100.0,
+                    draw_fair as f64 / 100.0,
+                );
+                let result = ThreeWayResult::from_probabilities(probs.0, probs.1, probs.2);
+                let prices = three_way_settlement(result, probs);
+                for (side, price) in [&game.home, &game.away, &game.draw].into_iter().zip(prices) {
+                    if let Some(side) = side {
+                        closed_tickers.push((side.ticker.clone(), price, SettleBasis::FairValue));
+                    }
+                }
+            }
+            continue;
+        }
+
+        let is_overtime = update.period > ot_period_threshold;
+        let mut home_fair = fv.home;
+
+        // Sanity bounds: clamp absurd model output, skip signaling, alert once per game
+        let remaining_secs = if is_overtime {
+            update.clock_seconds
+        } else {
+            regulation_secs.saturating_sub(update.total_elapsed_seconds)
+        };
+        let bound_violation = win_prob_table.check_bounds(score_diff, remaining_secs, home_fair);
+        if let Some(v) = &bound_violation {
+            home_fair = v.clamped_home_fair;
+            if fv_bound_alerts.insert(update.game_id.clone()) {
+                let msg = format!(
+                    "FV out of bounds: {} {}-{} {} with {}s left, model {}c (bound: lead {}+ within {}s >= {}c), clamped to {}c, not trading",
+                    update.home_team,
+                    update.home_score,
+                    update.away_score,
+                    update.away_team,
+                    remaining_secs,
+                    v.raw_home_fair,
+                    v.bound.min_lead,
+                    v.bound.max_remaining_secs,
+                    v.bound.min_fair,
+                    v.clamped_home_fair,
+                );
+                tracing::warn!(sport, game = %update.game_id, "{}", msg);
+                state_tx.send_modify(|s| s.push_log("WARN", msg));
+            }
+        } else {
+            fv_bound_alerts.remove(&update.game_id);
+        }
 
         let eastern = chrono::FixedOffset::west_opt(5 * 3600).unwrap();
-        let today = chrono::Utc::now().with_timezone(&eastern).date_naive();
+        let today = utc_now().with_timezone(&eastern).date_naive();
+
+        let velocity_score = track_velocity(
+            velocity_trackers,
+            matcher::generate_key(sport, &update.home_team, &update.away_team, today),
+            momentum_config.velocity_window_size,
+            Some(home_fair as f64 / 100.0),
+            cycle_start,
+        );
 
         if let Some(mkt) = matcher::find_match(
             market_index,
@@ -1494,37 +3169,34 @@ fn process_score_updates(
                 }
             });
 
-            // Look up odds-api fair value for this game (validation mode)
-            let oa_fv = if !odds_api_fv_lookup.is_empty() {
-                let home_norm = update.home_team.to_uppercase();
-                let away_norm = update.away_team.to_uppercase();
-                odds_api_fv_lookup
-                    .get(&(home_norm, away_norm))
-                    .copied()
-            } else {
-                None
-            };
+            // Odds-api fair value for this game (validation mode)
+            let oa_fv = odds
+                .map(|c| strategy::fair_value_cents(strategy::devig(c.quote.home, c.quote.away).0));
 
             let fv_method = FairValueMethod::ScoreFeed {
-                source: "score-feed".to_string(),
-            };
-            let fv_inputs = FairValueInputs::Score {
-                home_score: update.home_score as u32,
-                away_score: update.away_score as u32,
-                elapsed_secs: update.total_elapsed_seconds as u32,
-                period: format!("{}", update.period),
-                win_prob: home_fair as f64 / 100.0,
+                source: model.name().to_string(),
             };
+            let fv_inputs = score_fv_inputs(
+                update,
+                GamePhase::from_update(update, ot_period_threshold).label(),
+                home_fair,
+                &fv,
+                "HOME",
+                odds,
+            );
 
             match evaluate_matched_market(
                 &mkt.ticker,
                 fair,
+                fv.stderr,
                 mkt.best_bid,
                 mkt.best_ask,
                 mkt.is_inverse,
                 velocity_score,
                 staleness_secs,
                 is_stale,
+                bound_violation.is_some(),
+                news_halted,
                 side_market,
                 now_utc,
                 live_book_engine,
@@ -1543,23 +3215,106 @@ fn process_score_updates(
                 fv_method,
                 fv_inputs,
                 oa_fv,
-                fill_simulator.as_deref_mut()
+                fill_simulator.as_deref_mut(),
+                journal,
             ) {
                 EvalOutcome::Closed => {
                     filter_closed += 1;
                     if sim_mode {
-                        closed_tickers.push((mkt.ticker.clone(), fair));
+                        closed_tickers.push((mkt.ticker.clone(), fair, SettleBasis::FairValue));
                     }
                 }
-                EvalOutcome::Evaluated(row, intent) => {
+                EvalOutcome::Evaluated(mut row, intent) => {
                     filter_live += 1;
                     if let Some(i) = intent {
                         order_intents.push(i);
                     }
+                    row.sprint = sprinting;
                     rows.insert(mkt.ticker.clone(), row);
                 }
             }
         }
+
+        // Half/quarter winner markets, priced on the margin within each segment
+        let Some(baselines) = segment_baselines.as_deref_mut() else {
+            continue;
+        };
+        let Some(game) = matcher::generate_key(sport, &update.home_team, &update.away_team, today)
+            .and_then(|k| market_index.get(&k))
+        else {
+            continue;
+        };
+        for pf in period_fair_values(game, update, regulation_secs, win_prob_table, baselines) {
+            let label = pf.period.segment.label();
+            for (side, fair) in [
+                (&pf.period.home, pf.home_fair),
+                (&pf.period.away, pf.away_fair),
+            ] {
+                let Some(side) = side else { continue };
+                let fv_inputs = FairValueInputs::Score {
+                    home_score: pf.home_points as u32,
+                    away_score: pf.away_points as u32,
+                    elapsed_secs: pf.segment_elapsed as u32,
+                    period: label.clone(),
+                    phase: GamePhase::from_update(update, ot_period_threshold).label(),
+                    win_prob: fair as f64 / 100.0,
+                };
+                match evaluate_matched_market(
+                    &side.ticker,
+                    fair,
+                    None,
+                    side.yes_bid,
+                    side.yes_ask,
+                    false,
+                    velocity_score,
+                    staleness_secs,
+                    is_stale,
+                    false,
+                    news_halted,
+                    Some(side),
+                    now_utc,
+                    live_book_engine,
+                    strategy_config,
+                    momentum_config,
+                    book_pressure_trackers,
+                    scorer,
+                    sim_mode,
+                    state_tx,
+                    cycle_start,
+                    "score_feed",
+                    sim_config,
+                    risk_config,
+                    bankroll_cents,
+                    sport,
+                    FairValueMethod::ScoreFeed {
+                        source: "score-feed".to_string(),
+                    },
+                    fv_inputs,
+                    None,
+                    fill_simulator.as_deref_mut(),
+                    journal,
+                ) {
+                    EvalOutcome::Closed => {
+                        filter_closed += 1;
+                        if sim_mode {
+                            closed_tickers.push((
+                                side.ticker.clone(),
+                                fair,
+                                SettleBasis::FairValue,
+                            ));
+                        }
+                    }
+                    EvalOutcome::Evaluated(mut row, intent) => {
+                        filter_live += 1;
+                        if let Some(i) = intent {
+                            order_intents.push(i);
+                        }
+                        row.sprint = sprinting;
+                        rows.insert(side.ticker.clone(), row);
+                    }
+                }
+            }
+        }
     }
 
     TickResult {
@@ -1574,38 +3329,341 @@ fn process_score_updates(
     }
 }
 
-/// Average odds across all bookmakers for better fair value estimation.
-/// Returns (avg_home_odds, avg_away_odds, avg_draw_odds_if_any, last_update, bookmaker_names).
+/// Weighted bookmaker consensus for one game, with the books behind it.
+struct ConsensusOdds {
+    quote: OddsQuote,
+    bookmakers: Vec<String>,
+    bookmaker_weights: Vec<f64>,
+}
+
+/// Trace inputs for one side ("HOME", "AWAY" or "DRAW") of a score-priced
+/// game: the score alone, or the score and odds a blended model mixed.
+fn score_fv_inputs(
+    update: &ScoreUpdate,
+    phase: String,
+    fair: u32,
+    fv: &FairValues,
+    side: &str,
+    odds: Option<&ConsensusOdds>,
+) -> FairValueInputs {
+    let pick = |(home, away, draw): (f64, f64, Option<f64>)| match side {
+        "HOME" => home,
+        "AWAY" => away,
+        _ => draw.unwrap_or(0.0),
+    };
+    match (fv.blend, odds) {
+        (Some(parts), Some(odds)) => FairValueInputs::Blended {
+            home_score: update.home_score as u32,
+            away_score: update.away_score as u32,
+            elapsed_secs: update.total_elapsed_seconds as u32,
+            period: format!("{}", update.period),
+            phase,
+            home_odds: odds.quote.home,
+            away_odds: odds.quote.away,
+            bookmakers: odds.bookmakers.clone(),
+            bookmaker_weights: odds.bookmaker_weights.clone(),
+            score_prob: pick(parts.score),
+            odds_prob: pick(parts.odds),
+            score_weight: parts.score_weight,
+            win_prob: fair as f64 / 100.0,
+        },
+        _ => FairValueInputs::Score {
+            home_score: update.home_score as u32,
+            away_score: update.away_score as u32,
+            elapsed_secs: update.total_elapsed_seconds as u32,
+            period: format!("{}", update.period),
+            phase,
+            win_prob: fair as f64 / 100.0,
+        },
+    }
+}
+
+/// A segment entered this many game-clock seconds ago or less still gets a
+/// starting score; the points since are attributed to it.
+const SEGMENT_BASELINE_MAX_LAG_SECS: u16 = 30;
+
+/// Score at which each (game id, segment) was entered.
+type SegmentBaselines = HashMap<(String, GameSegment), (u16, u16)>;
+
+/// Fair values for one period market of a live or finished game.
+struct PeriodFairValue<'a> {
+    period: &'a matcher::PeriodMarket,
+    home_fair: u32,
+    away_fair: u32,
+    /// Points each team has scored within the segment.
+    home_points: u16,
+    away_points: u16,
+    segment_elapsed: u16,
+}
+
+/// Fair values for every started segment of `game` with a known starting
+/// score. The score is recorded in `baselines` as the game enters each
+/// segment; one entered before the engine was watching has no baseline and
+/// is skipped rather than priced on the whole-game margin.
+fn period_fair_values<'a>(
+    game: &'a matcher::IndexedGame,
+    update: &ScoreUpdate,
+    regulation_secs: u16,
+    table: &WinProbTable,
+    baselines: &mut SegmentBaselines,
+) -> Vec<PeriodFairValue<'a>> {
+    let elapsed = update.total_elapsed_seconds.min(regulation_secs);
+    let mut values = Vec::new();
+    for period in &game.periods {
+        let (start, end) = period.segment.bounds(regulation_secs);
+        if elapsed < start {
+            continue;
+        }
+        let key = (update.game_id.clone(), period.segment);
+        let baseline = match baselines.get(&key) {
+            Some(b) => *b,
+            None if start == 0 => (0, 0),
+            None if elapsed - start <= SEGMENT_BASELINE_MAX_LAG_SECS => {
+                (update.home_score, update.away_score)
+            }
+            None => continue,
+        };
+        baselines.insert(key, baseline);
+        let home_points = update.home_score.saturating_sub(baseline.0);
+        let away_points = update.away_score.saturating_sub(baseline.1);
+        let segment_elapsed = elapsed.min(end) - start;
+        let (home_fair, away_fair) = table.segment_fair_value(
+            home_points as i32 - away_points as i32,
+            end - start,
+            segment_elapsed,
+        );
+        values.push(PeriodFairValue {
+            period,
+            home_fair,
+            away_fair,
+            home_points,
+            away_points,
+            segment_elapsed,
+        });
+    }
+    values
+}
+
+/// Weighted average of odds across bookmakers for better fair value estimation.
+/// Each book counts by its `[odds_sources.weights]` entry (default 1.0); books
+/// weighted 0 are left out.
+/// Returns (avg_home_odds, avg_away_odds, avg_draw_odds_if_any, last_update,
+/// bookmaker_names, bookmaker_weights) over the books that were included.
 #[allow(clippy::type_complexity)]
 fn average_bookmaker_odds(
     bookmakers: &[crate::feed::types::BookmakerOdds],
-) -> Option<(f64, f64, Option<f64>, String, Vec<String>)> {
-    if bookmakers.is_empty() {
+    odds_sources: &OddsSourcesConfig,
+) -> Option<(f64, f64, Option<f64>, String, Vec<String>, Vec<f64>)> {
+    let weighted: Vec<(&crate::feed::types::BookmakerOdds, f64)> = bookmakers
+        .iter()
+        .map(|b| (b, odds_sources.bookmaker_weight(&b.name)))
+        .filter(|(_, w)| *w > 0.0)
+        .collect();
+    if weighted.is_empty() {
         return None;
     }
 
-    let count = bookmakers.len() as f64;
-    let avg_home = bookmakers.iter().map(|b| b.home_odds).sum::<f64>() / count;
-    let avg_away = bookmakers.iter().map(|b| b.away_odds).sum::<f64>() / count;
+    let total: f64 = weighted.iter().map(|(_, w)| w).sum();
+    let avg_home = weighted.iter().map(|(b, w)| b.home_odds * w).sum::<f64>() / total;
+    let avg_away = weighted.iter().map(|(b, w)| b.away_odds * w).sum::<f64>() / total;
 
     // Average draw odds if all bookmakers have them
-    let avg_draw = if bookmakers.iter().all(|b| b.draw_odds.is_some()) {
-        Some(bookmakers.iter().filter_map(|b| b.draw_odds).sum::<f64>() / count)
+    let avg_draw = if weighted.iter().all(|(b, _)| b.draw_odds.is_some()) {
+        Some(
+            weighted
+                .iter()
+                .filter_map(|(b, w)| b.draw_odds.map(|d| d * w))
+                .sum::<f64>()
+                / total,
+        )
     } else {
         None
     };
 
     // Use the most recent last_update timestamp
-    let last_update = bookmakers
+    let last_update = weighted
         .iter()
-        .map(|b| &b.last_update)
+        .map(|(b, _)| &b.last_update)
         .max()
         .cloned()
         .unwrap_or_default();
 
-    let bookmaker_names = bookmakers.iter().map(|b| b.name.clone()).collect();
+    let bookmaker_names = weighted.iter().map(|(b, _)| b.name.clone()).collect();
+    let bookmaker_weights = weighted.iter().map(|(_, w)| *w).collect();
+
+    Some((
+        avg_home,
+        avg_away,
+        avg_draw,
+        last_update,
+        bookmaker_names,
+        bookmaker_weights,
+    ))
+}
+
+/// Standard error of the weighted consensus, from each book's own devigged
+/// probabilities; the widest of the outcomes.
+fn bookmaker_devig_stderr(
+    bookmakers: &[crate::feed::types::BookmakerOdds],
+    odds_sources: &OddsSourcesConfig,
+) -> Option<f64> {
+    let weighted: Vec<(&crate::feed::types::BookmakerOdds, f64)> = bookmakers
+        .iter()
+        .map(|b| (b, odds_sources.bookmaker_weight(&b.name)))
+        .filter(|(_, w)| *w > 0.0)
+        .collect();
+    let three_way = weighted.iter().all(|(b, _)| b.draw_odds.is_some());
+    let probs: Vec<([f64; 3], f64)> = weighted
+        .iter()
+        .map(|(b, w)| {
+            let probs = match b.draw_odds {
+                Some(draw) if three_way => {
+                    let (home, away, draw) = strategy::devig_3way(b.home_odds, b.away_odds, draw);
+                    [home, away, draw]
+                }
+                _ => {
+                    let (home, away) = strategy::devig(b.home_odds, b.away_odds);
+                    [home, away, 0.0]
+                }
+            };
+            (probs, *w)
+        })
+        .collect();
+    (0..3)
+        .filter_map(|i| {
+            let outcome: Vec<(f64, f64)> = probs.iter().map(|(p, w)| (p[i], *w)).collect();
+            strategy::devig_stderr(&outcome)
+        })
+        .reduce(f64::max)
+}
+
+/// Devigged probability at which a closed three-way game counts as decided.
+const THREE_WAY_DECISIVE_PROB: f64 = 0.95;
+
+/// Final result of a three-way (home/away/draw) game. Exactly one of the
+/// three markets settles YES.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreeWayResult {
+    Home,
+    Away,
+    Draw,
+}
+
+impl ThreeWayResult {
+    /// Result implied by closing odds: the side the market has priced as
+    /// near-certain, if any.
+    pub fn from_probabilities(home: f64, away: f64, draw: f64) -> Option<Self> {
+        [(Self::Home, home), (Self::Away, away), (Self::Draw, draw)]
+            .into_iter()
+            .find(|&(_, p)| p >= THREE_WAY_DECISIVE_PROB)
+            .map(|(result, _)| result)
+    }
+
+    /// Result of a finished game from its final score.
+    pub fn from_score_diff(score_diff: i32) -> Self {
+        match score_diff.signum() {
+            1 => Self::Home,
+            -1 => Self::Away,
+            _ => Self::Draw,
+        }
+    }
+}
+
+/// Settlement prices (cents) for the home, away and draw markets of a closed
+/// three-way game. A decided game pays 100 on the result and 0 elsewhere;
+/// otherwise the devigged probabilities are rounded so the three still sum to
+/// 100, as a mutually exclusive set must.
+fn three_way_settlement(
+    result: Option<ThreeWayResult>,
+    (home, away, draw): (f64, f64, f64),
+) -> [u32; 3] {
+    match result {
+        Some(ThreeWayResult::Home) => [100, 0, 0],
+        Some(ThreeWayResult::Away) => [0, 100, 0],
+        Some(ThreeWayResult::Draw) => [0, 0, 100],
+        None => {
+            let total = home + away + draw;
+            if total <= 0.0 {
+                return [33, 33, 34];
+            }
+            let home_cents = (home / total * 100.0).round() as u32;
+            let away_cents = ((away / total * 100.0).round() as u32).min(100 - home_cents);
+            [home_cents, away_cents, 100 - home_cents - away_cents]
+        }
+    }
+}
 
-    Some((avg_home, avg_away, avg_draw, last_update, bookmaker_names))
+/// Fallback settlement of a closed three-way game: its final score when the
+/// score feed has reported one, otherwise its closing odds.
+fn three_way_close(
+    final_scores: &[ScoreUpdate],
+    sport: &str,
+    (home, away): (&str, &str),
+    date: chrono::NaiveDate,
+    probs: (f64, f64, f64),
+) -> ([u32; 3], SettleBasis) {
+    let game_key = matcher::generate_key(sport, home, away, date);
+    let score_diff = final_scores
+        .iter()
+        .filter(|s| s.game_status == crate::feed::score_feed::GameStatus::Finished)
+        .find(|s| matcher::generate_key(sport, &s.home_team, &s.away_team, date) == game_key)
+        .map(|s| {
+            let diff = s.home_score as i32 - s.away_score as i32;
+            // The score feed may list the teams the other way round
+            if matcher::normalize_team(sport, &s.home_team) == matcher::normalize_team(sport, home)
+            {
+                diff
+            } else {
+                -diff
+            }
+        });
+    match score_diff.filter(|_| game_key.is_some()) {
+        Some(diff) => (
+            three_way_settlement(Some(ThreeWayResult::from_score_diff(diff)), probs),
+            SettleBasis::FinalScore,
+        ),
+        None => (
+            three_way_settlement(
+                ThreeWayResult::from_probabilities(probs.0, probs.1, probs.2),
+                probs,
+            ),
+            SettleBasis::ClosingOdds,
+        ),
+    }
+}
+
+/// Trade source of pre-game positioning entries, in the trades table and journal.
+pub const PRE_GAME_SOURCE: &str = "pregame";
+
+/// Whether a game starting at `commence` is inside the pre-game positioning window.
+fn in_pre_game_window(
+    config: &PreGameConfig,
+    commence: chrono::DateTime<chrono::Utc>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> bool {
+    let hours_before = (commence - now).num_seconds() as f64 / 3600.0;
+    hours_before >= config.min_hours_before && hours_before <= config.max_hours_before
+}
+
+/// Strategy, momentum and risk settings for pre-game entries: maker bids
+/// clearing `edge_margin`, no takers, no momentum gate (nothing moves before
+/// the start) and at most `max_contracts` per market.
+fn pre_game_entry_configs(
+    config: &PreGameConfig,
+    strategy: &StrategyConfig,
+    momentum: &MomentumConfig,
+    risk: &crate::config::RiskConfig,
+) -> (StrategyConfig, MomentumConfig, crate::config::RiskConfig) {
+    let mut strategy = strategy.clone();
+    strategy.taker_edge_threshold = u8::MAX;
+    strategy.taker_stderr_multiple = None;
+    strategy.maker_edge_threshold = config.edge_margin;
+    strategy.maker_stderr_multiple = None;
+    let mut momentum = momentum.clone();
+    momentum.maker_momentum_threshold = 0;
+    let mut risk = risk.clone();
+    risk.max_contracts_per_market = risk.max_contracts_per_market.min(config.max_contracts);
+    (strategy, momentum, risk)
 }
 
 /// Process odds updates for a single sport through the filter/matching/evaluation pipeline.
@@ -1613,12 +3671,14 @@ fn average_bookmaker_odds(
 fn process_sport_updates(
     updates: &[OddsUpdate],
     sport: &str,
+    model: &dyn FairValueModel,
     market_index: &matcher::MarketIndex,
     live_book_engine: &LiveBook,
     strategy_config: &StrategyConfig,
     momentum_config: &MomentumConfig,
-    velocity_trackers: &mut HashMap<String, VelocityTracker>,
+    velocity_trackers: &mut HashMap<matcher::MarketKey, VelocityTracker>,
     book_pressure_trackers: &mut HashMap<String, BookPressureTracker>,
+    news_halts: &NewsHalts,
     scorer: &MomentumScorer,
     sim_mode: bool,
     state_tx: &watch::Sender<AppState>,
@@ -1627,7 +3687,11 @@ fn process_sport_updates(
     sim_config: &crate::config::SimulationConfig,
     risk_config: &crate::config::RiskConfig,
     bankroll_cents: u64,
+    odds_sources: &OddsSourcesConfig,
+    pre_game: Option<&PreGameConfig>,
+    final_scores: &[ScoreUpdate],
     mut fill_simulator: Option<&mut crate::engine::FillSimulator>,
+    journal: &Journal,
 ) -> TickResult {
     let mut filter_live: usize = 0;
     let mut filter_pre_game: usize = 0;
@@ -1635,16 +3699,23 @@ fn process_sport_updates(
     let mut earliest_commence: Option<chrono::DateTime<chrono::Utc>> = None;
     let mut rows: HashMap<String, MarketRow> = HashMap::new();
     let mut has_live_games = false;
-    let mut closed_tickers: Vec<(String, u32)> = Vec::new();
+    let mut closed_tickers: Vec<(String, u32, SettleBasis)> = Vec::new();
     let mut order_intents: Vec<OrderIntent> = Vec::new();
 
     for update in updates {
         // Average odds across all bookmakers for better fair value estimation
-        let Some((home_odds, away_odds, draw_odds, last_update, bookmaker_names)) =
-            average_bookmaker_odds(&update.bookmakers)
+        let Some((
+            home_odds,
+            away_odds,
+            draw_odds,
+            last_update,
+            bookmaker_names,
+            bookmaker_weights,
+        )) = average_bookmaker_odds(&update.bookmakers, odds_sources)
         else {
             continue;
         };
+        let odds_stderr = bookmaker_devig_stderr(&update.bookmakers, odds_sources);
 
         let eastern = chrono::FixedOffset::west_opt(5 * 3600).unwrap();
         let date = chrono::DateTime::parse_from_rfc3339(&update.commence_time)
@@ -1653,12 +3724,16 @@ fn process_sport_updates(
 
         let Some(date) = date else { continue };
 
-        let now_utc = chrono::Utc::now();
+        let now_utc = utc_now();
         let commence_dt = chrono::DateTime::parse_from_rfc3339(&update.commence_time)
             .ok()
             .map(|dt| dt.with_timezone(&chrono::Utc));
 
         let game_started = commence_dt.is_some_and(|ct| ct <= now_utc);
+        let pre_game_entry = match (pre_game, commence_dt) {
+            (Some(pg), Some(ct)) if !game_started => in_pre_game_window(pg, ct, now_utc),
+            _ => false,
+        };
 
         if !game_started {
             filter_pre_game += 1;
@@ -1668,10 +3743,24 @@ fn process_sport_updates(
                     None => ct,
                 });
             }
-            continue;
+            if !pre_game_entry {
+                continue;
+            }
+        } else {
+            has_live_games = true;
         }
 
-        has_live_games = true;
+        // Pre-game games are evaluated for maker entries only, sized small
+        let pre_game_configs = pre_game
+            .filter(|_| pre_game_entry)
+            .map(|pg| pre_game_entry_configs(pg, strategy_config, momentum_config, risk_config));
+        let (strategy_config, momentum_config, risk_config) = match &pre_game_configs {
+            Some((strategy, momentum, risk)) => (strategy, momentum, risk),
+            None => (strategy_config, momentum_config, risk_config),
+        };
+        let news_halted = news_halts
+            .halt_for(&update.home_team, &update.away_team, now_utc)
+            .is_some();
 
         let (lookup_home, lookup_away) = if sport == "mma" {
             (
@@ -1684,48 +3773,50 @@ fn process_sport_updates(
 
         let is_3way = sport.starts_with("soccer");
 
-        let vt = velocity_trackers
-            .entry(update.event_id.clone())
-            .or_insert_with(|| VelocityTracker::new(momentum_config.velocity_window_size));
+        let game_key = matcher::generate_key(sport, &lookup_home, &lookup_away, date);
 
         if is_3way {
             let Some(draw_odds_val) = draw_odds else {
                 tracing::warn!(sport, home = %update.home_team, "skipping soccer event: missing draw odds");
                 continue;
             };
+            let Some(fv) = model.fair_values(&GameInputs {
+                odds: Some(OddsQuote {
+                    home: home_odds,
+                    away: away_odds,
+                    draw: Some(draw_odds_val),
+                    stderr: odds_stderr,
+                }),
+                ..GameInputs::default()
+            }) else {
+                continue;
+            };
             let (home_fv, away_fv, draw_fv) =
-                strategy::devig_3way(home_odds, away_odds, draw_odds_val);
+                (fv.home_prob, fv.away_prob, fv.draw_prob.unwrap_or(0.0));
 
-            if !is_replay {
-                vt.push(home_fv, Instant::now());
-            }
-            let velocity_score = vt.score();
+            let velocity_score = track_velocity(
+                velocity_trackers,
+                game_key.clone(),
+                momentum_config.velocity_window_size,
+                (!is_replay).then_some(home_fv),
+                cycle_start,
+            );
 
-            let key = matcher::generate_key(sport, &lookup_home, &lookup_away, date);
-            let game = key.and_then(|k| market_index.get(&k));
+            let game = game_key.as_ref().and_then(|k| market_index.get(k));
 
             if let Some(game) = game {
                 let sides: Vec<(Option<&matcher::SideMarket>, u32, &str, f64)> = vec![
-                    (
-                        game.home.as_ref(),
-                        strategy::fair_value_cents(home_fv),
-                        "HOME",
-                        home_fv,
-                    ),
-                    (
-                        game.away.as_ref(),
-                        strategy::fair_value_cents(away_fv),
-                        "AWAY",
-                        away_fv,
-                    ),
+                    (game.home.as_ref(), fv.home, "HOME", home_fv),
+                    (game.away.as_ref(), fv.away, "AWAY", away_fv),
                     (
                         game.draw.as_ref(),
-                        strategy::fair_value_cents(draw_fv),
+                        fv.draw.unwrap_or(0),
                         "DRAW",
                         draw_fv,
                     ),
                 ];
 
+                let mut game_closed = false;
                 for (side_opt, fair, label, devigged_prob) in sides {
                     let Some(side) = side_opt else { continue };
 
@@ -1743,18 +3834,23 @@ fn process_sport_updates(
                         home_odds,
                         away_odds,
                         bookmakers: bookmaker_names.clone(),
+                        bookmaker_weights: bookmaker_weights.clone(),
                         devigged_prob,
+                        pre_game: pre_game_entry,
                     };
 
                     match evaluate_matched_market(
                         &side.ticker,
                         fair,
+                        fv.stderr,
                         side.yes_bid,
                         side.yes_ask,
                         false,
                         velocity_score,
                         staleness_secs,
                         false,
+                        false,
+                        news_halted,
                         Some(side),
                         now_utc,
                         live_book_engine,
@@ -1765,7 +3861,11 @@ fn process_sport_updates(
                         sim_mode,
                         state_tx,
                         cycle_start,
-                        label,
+                        if pre_game_entry {
+                            PRE_GAME_SOURCE
+                        } else {
+                            label
+                        },
                         sim_config,
                         risk_config,
                         bankroll_cents,
@@ -1773,16 +3873,17 @@ fn process_sport_updates(
                         fv_method,
                         fv_inputs,
                         None, // odds-feed sports don't need comparison FV
-                        fill_simulator.as_deref_mut()
+                        fill_simulator.as_deref_mut(),
+                        journal,
                     ) {
                         EvalOutcome::Closed => {
                             filter_closed += 1;
-                            if sim_mode {
-                                closed_tickers.push((side.ticker.clone(), fair));
-                            }
+                            game_closed = true;
                         }
                         EvalOutcome::Evaluated(row, intent) => {
-                            filter_live += 1;
+                            if !pre_game_entry {
+                                filter_live += 1;
+                            }
                             if let Some(i) = intent {
                                 order_intents.push(i);
                             }
@@ -1790,15 +3891,50 @@ fn process_sport_updates(
                         }
                     }
                 }
+
+                // Home, away and draw are mutually exclusive: once any of
+                // them closes, settle all three from the same result so the
+                // sim never pays out on two sides (or none) of one game. The
+                // final score decides it; closing odds only stand in when the
+                // score feed hasn't reported one.
+                if sim_mode && game_closed {
+                    let (prices, basis) = three_way_close(
+                        final_scores,
+                        sport,
+                        (&lookup_home, &lookup_away),
+                        date,
+                        (home_fv, away_fv, draw_fv),
+                    );
+                    for (side, price) in
+                        [&game.home, &game.away, &game.draw].into_iter().zip(prices)
+                    {
+                        if let Some(side) = side {
+                            closed_tickers.push((side.ticker.clone(), price, basis));
+                        }
+                    }
+                }
             }
         } else {
-            let (home_fv, _away_fv) = strategy::devig(home_odds, away_odds);
-            let home_cents = strategy::fair_value_cents(home_fv);
+            let Some(fv) = model.fair_values(&GameInputs {
+                odds: Some(OddsQuote {
+                    home: home_odds,
+                    away: away_odds,
+                    draw: None,
+                    stderr: odds_stderr,
+                }),
+                ..GameInputs::default()
+            }) else {
+                continue;
+            };
+            let (home_fv, home_cents) = (fv.home_prob, fv.home);
 
-            if !is_replay {
-                vt.push(home_fv, Instant::now());
-            }
-            let velocity_score = vt.score();
+            let velocity_score = track_velocity(
+                velocity_trackers,
+                game_key,
+                momentum_config.velocity_window_size,
+                (!is_replay).then_some(home_fv),
+                cycle_start,
+            );
 
             if let Some(mkt) =
                 matcher::find_match(market_index, sport, &lookup_home, &lookup_away, date)
@@ -1830,18 +3966,23 @@ fn process_sport_updates(
                     home_odds,
                     away_odds,
                     bookmakers: bookmaker_names.clone(),
+                    bookmaker_weights: bookmaker_weights.clone(),
                     devigged_prob: home_fv,
+                    pre_game: pre_game_entry,
                 };
 
                 match evaluate_matched_market(
                     &mkt.ticker,
                     fair,
+                    fv.stderr,
                     mkt.best_bid,
                     mkt.best_ask,
                     mkt.is_inverse,
                     velocity_score,
                     staleness_secs,
                     false,
+                    false,
+                    news_halted,
                     side_market,
                     now_utc,
                     live_book_engine,
@@ -1852,7 +3993,11 @@ fn process_sport_updates(
                     sim_mode,
                     state_tx,
                     cycle_start,
-                    "odds_api",
+                    if pre_game_entry {
+                        PRE_GAME_SOURCE
+                    } else {
+                        "odds_api"
+                    },
                     sim_config,
                     risk_config,
                     bankroll_cents,
@@ -1860,16 +4005,19 @@ fn process_sport_updates(
                     fv_method,
                     fv_inputs,
                     None, // odds-feed sports don't need comparison FV
-                    fill_simulator.as_deref_mut()
+                    fill_simulator.as_deref_mut(),
+                    journal,
                 ) {
                     EvalOutcome::Closed => {
                         filter_closed += 1;
                         if sim_mode {
-                            closed_tickers.push((mkt.ticker.clone(), fair));
+                            closed_tickers.push((mkt.ticker.clone(), fair, SettleBasis::FairValue));
                         }
                     }
                     EvalOutcome::Evaluated(row, intent) => {
-                        filter_live += 1;
+                        if !pre_game_entry {
+                            filter_live += 1;
+                        }
                         if let Some(i) = intent {
                             order_intents.push(i);
                         }
@@ -1896,6 +4044,7 @@ fn process_sport_updates(
 mod tests {
     use super::*;
     use crate::config::*;
+    use crate::engine::win_prob::SOCCER_REGULATION_SECS;
 
     fn test_global_strategy() -> StrategyConfig {
         StrategyConfig {
@@ -1904,6 +4053,10 @@ mod tests {
             min_edge_after_fees: 1,
             slippage_buffer_cents: 0,
             max_edge_threshold: 15,
+            taker_time_in_force: TimeInForce::GoodTillCanceled,
+            walk_taker_depth: true,
+            taker_stderr_multiple: None,
+            maker_stderr_multiple: None,
         }
     }
 
@@ -1929,20 +4082,100 @@ mod tests {
             hotkey: "4".into(),
             fair_value: "odds-feed".into(),
             odds_source: "the-odds-api".into(),
+            polymarket_tag: None,
             score_feed: None,
             win_prob: None,
             strategy: None,
             momentum: None,
+            season: None,
+            period_markets: None,
+            soccer_model: None,
+            order_flow: None,
+            blend: None,
+            news: None,
+            pre_game: None,
+            backup_odds_source: None,
         };
         let pipe = SportPipeline::from_config(
             "ice-hockey",
             &sport_config,
             &test_global_strategy(),
             &test_global_momentum(),
+            &MatchingConfig::default(),
+            &Network::default(),
         );
         assert_eq!(pipe.strategy_config.taker_edge_threshold, 5);
         assert_eq!(pipe.momentum_config.taker_momentum_threshold, 75);
-        assert!(matches!(pipe.fair_value_source, FairValueSource::OddsFeed));
+        assert_eq!(pipe.fair_value_model.driver(), Driver::Odds);
+        assert!(pipe.score_poller.is_none());
+    }
+
+    #[test]
+    fn test_velocity_history_survives_source_switch() {
+        let sport_config = SportConfig {
+            enabled: true,
+            kalshi_series: "KXNHLGAME".into(),
+            label: "NHL".into(),
+            hotkey: "4".into(),
+            fair_value: "odds-feed".into(),
+            odds_source: "the-odds-api".into(),
+            polymarket_tag: None,
+            score_feed: None,
+            win_prob: None,
+            strategy: None,
+            momentum: None,
+            season: None,
+            period_markets: None,
+            soccer_model: None,
+            order_flow: None,
+            blend: None,
+            news: None,
+            pre_game: None,
+            backup_odds_source: None,
+        };
+        let mut pipe = SportPipeline::from_config(
+            "ice-hockey",
+            &sport_config,
+            &test_global_strategy(),
+            &test_global_momentum(),
+            &MatchingConfig::default(),
+            &Network::default(),
+        );
+        let date = chrono::NaiveDate::from_ymd_opt(2026, 1, 19).unwrap();
+        let key = |home: &str, away: &str| matcher::generate_key("ice-hockey", home, away, date);
+
+        // Odds feed samples under one team ordering...
+        track_velocity(
+            &mut pipe.velocity_trackers,
+            key("Bruins", "Rangers"),
+            10,
+            Some(0.60),
+            Instant::now(),
+        );
+        std::thread::sleep(Duration::from_millis(5));
+        let before = track_velocity(
+            &mut pipe.velocity_trackers,
+            key("Bruins", "Rangers"),
+            10,
+            Some(0.61),
+            Instant::now(),
+        );
+
+        pipe.rebuild_fair_value_model("draftkings");
+
+        // ...continue under the same canonical key with a different source level.
+        let after = track_velocity(
+            &mut pipe.velocity_trackers,
+            key("Rangers", "Bruins"),
+            10,
+            Some(0.52),
+            Instant::now(),
+        );
+        assert_eq!(pipe.velocity_trackers.len(), 1);
+        assert!(
+            (after - before).abs() < 1e-9,
+            "before {before}, after {after}"
+        );
     }
 
     #[test]
@@ -1954,13 +4187,17 @@ mod tests {
             hotkey: "1".into(),
             fair_value: "score-feed".into(),
             odds_source: "the-odds-api".into(),
+            polymarket_tag: None,
             score_feed: Some(ScoreFeedConfig {
+                feed_type: "basketball".into(),
                 primary_url: "https://cdn.nba.com/test".into(),
                 fallback_url: Some("https://espn.com/test".into()),
                 live_poll_s: 1,
                 pre_game_poll_s: 60,
                 failover_threshold: 3,
                 request_timeout_ms: 5000,
+                align_to_cadence: true,
+                cadence_offset_ms: 150,
             }),
             win_prob: Some(WinProbConfig {
                 home_advantage: 2.5,
@@ -1969,12 +4206,23 @@ mod tests {
                 ot_k_start: 0.10,
                 ot_k_range: 1.0,
                 regulation_secs: Some(2880),
+                ot_period_secs: None,
+                regulation_periods: None,
+                tied_home_prob: None,
+                sudden_death: false,
+                shootout_home_prob: None,
+                sanity_bounds: Vec::new(),
+                k_rel_stderr: 0.1,
+                home_advantage_stderr: 1.0,
             }),
             strategy: Some(StrategyOverride {
                 taker_edge_threshold: Some(3),
                 maker_edge_threshold: Some(1),
                 min_edge_after_fees: None,
                 max_edge_threshold: None,
+                taker_time_in_force: None,
+                taker_stderr_multiple: None,
+                maker_stderr_multiple: None,
             }),
             momentum: Some(MomentumOverride {
                 taker_momentum_threshold: Some(0),
@@ -1985,19 +4233,677 @@ mod tests {
                 velocity_window_size: None,
                 cancel_check_interval_ms: None,
             }),
+            season: None,
+            period_markets: None,
+            soccer_model: None,
+            order_flow: None,
+            blend: None,
+            news: None,
+            pre_game: None,
+            backup_odds_source: None,
         };
         let pipe = SportPipeline::from_config(
             "basketball",
             &sport_config,
             &test_global_strategy(),
             &test_global_momentum(),
+            &MatchingConfig::default(),
+            &Network::default(),
         );
         assert_eq!(pipe.strategy_config.taker_edge_threshold, 3);
         assert_eq!(pipe.strategy_config.min_edge_after_fees, 1); // inherited
         assert_eq!(pipe.momentum_config.taker_momentum_threshold, 0);
-        assert!(matches!(
-            pipe.fair_value_source,
-            FairValueSource::ScoreFeed { .. }
-        ));
+        assert_eq!(pipe.fair_value_name(), "score-feed");
+        assert!(pipe.score_poller.is_some());
+    }
+
+    #[test]
+    fn test_score_feed_volatility_uses_most_volatile_live_game() {
+        use crate::feed::score_feed::{GameStatus, ScoreSource};
+        let game = |home_score, away_score, elapsed, game_status| ScoreUpdate {
+            game_id: String::new(),
+            home_team: String::new(),
+            away_team: String::new(),
+            home_score,
+            away_score,
+            period: 2,
+            clock_seconds: 0,
+            total_elapsed_seconds: elapsed,
+            game_status,
+            home_red_cards: 0,
+            away_red_cards: 0,
+            source: ScoreSource::Espn,
+        };
+        let cfg = AdaptivePollConfig::default();
+        let mut scores = vec![
+            game(80, 50, 2300, GameStatus::Live),
+            game(60, 59, 2300, GameStatus::Finished),
+        ];
+        assert_eq!(
+            score_feed_volatility(&cfg, &scores, 2400),
+            Some(Volatility::Blowout)
+        );
+        scores.push(game(40, 37, 1200, GameStatus::Live));
+        assert_eq!(
+            score_feed_volatility(&cfg, &scores, 2400),
+            Some(Volatility::Close)
+        );
+        assert_eq!(score_feed_volatility(&cfg, &scores[1..2], 2400), None);
+    }
+
+    #[test]
+    fn test_sprint_games_close_and_late_only() {
+        use crate::feed::score_feed::{GameStatus, ScoreSource};
+        let game = |id: &str, home_score, away_score, elapsed, clock| ScoreUpdate {
+            game_id: id.to_string(),
+            home_team: String::new(),
+            away_team: String::new(),
+            home_score,
+            away_score,
+            period: 2,
+            clock_seconds: clock,
+            total_elapsed_seconds: elapsed,
+            game_status: GameStatus::Live,
+            home_red_cards: 0,
+            away_red_cards: 0,
+            source: ScoreSource::Espn,
+        };
+        let scores = vec![
+            game("late-close", 70, 68, 2310, 90),
+            game("late-blowout", 80, 60, 2310, 90),
+            game("early-close", 30, 29, 1200, 0),
+            // Overtime uses the clock of the current OT period.
+            game("ot-early", 75, 75, 2460, 240),
+            game("ot-late", 77, 75, 2640, 60),
+        ];
+        let sprinting = sprint_game_ids(&SprintConfig::default(), &scores, 2400);
+        let mut ids: Vec<&str> = sprinting.iter().map(String::as_str).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["late-close", "ot-late"]);
+    }
+
+    #[test]
+    fn test_updates_game_matches_focused_games() {
+        let d = chrono::NaiveDate::from_ymd_opt(2026, 1, 19).unwrap();
+        let key = matcher::generate_key("basketball", "Lakers", "Celtics", d).unwrap();
+        let games = HashSet::from([key]);
+        let focused = |home, away| updates_game("basketball", home, away, &games);
+        // Either order, and Kalshi's names for the same teams
+        assert!(focused("Celtics", "Lakers"));
+        assert!(focused("Boston", "Los Angeles L"));
+        assert!(!focused("Heat", "Knicks"));
+        assert!(!updates_game("ice-hockey", "Lakers", "Celtics", &games));
+    }
+
+    #[test]
+    fn test_bookmaker_weights_shape_consensus() {
+        let book = |name: &str, home: f64, away: f64| crate::feed::types::BookmakerOdds {
+            name: name.to_string(),
+            home_odds: home,
+            away_odds: away,
+            draw_odds: None,
+            last_update: "2026-01-20T00:00:00Z".to_string(),
+        };
+        let books = vec![
+            book("Pinnacle", -150.0, 130.0),
+            book("DraftKings", -130.0, 110.0),
+            book("Bovada", -110.0, -110.0),
+        ];
+        let sources = OddsSourcesConfig {
+            weights: HashMap::from([("pinnacle".to_string(), 3.0), ("bovada".to_string(), 0.0)]),
+            ..Default::default()
+        };
+        let (home, away, draw, _, names, weights) =
+            average_bookmaker_odds(&books, &sources).unwrap();
+        // (3 × -150 + 1 × -130) / 4; Bovada is excluded
+        assert_eq!(home, -145.0);
+        assert_eq!(away, 125.0);
+        assert_eq!(draw, None);
+        assert_eq!(names, vec!["Pinnacle", "DraftKings"]);
+        assert_eq!(weights, vec![3.0, 1.0]);
+
+        // Unweighted config keeps the flat average
+        let (home, ..) = average_bookmaker_odds(&books, &OddsSourcesConfig::default()).unwrap();
+        assert!((home - (-130.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_three_way_settlement_pays_exactly_one_side() {
+        let decided = ThreeWayResult::from_probabilities(0.02, 0.01, 0.97);
+        assert_eq!(decided, Some(ThreeWayResult::Draw));
+        assert_eq!(
+            three_way_settlement(decided, (0.02, 0.01, 0.97)),
+            [0, 0, 100]
+        );
+
+        // Undecided: rounded fair values still sum to 100 across the set
+        let probs = (0.456, 0.286, 0.258);
+        assert_eq!(
+            ThreeWayResult::from_probabilities(0.456, 0.286, 0.258),
+            None
+        );
+        let prices = three_way_settlement(None, probs);
+        assert_eq!(prices.iter().sum::<u32>(), 100);
+        assert_eq!(prices, [46, 29, 25]);
+
+        assert_eq!(ThreeWayResult::from_score_diff(-2), ThreeWayResult::Away);
+        assert_eq!(ThreeWayResult::from_score_diff(0), ThreeWayResult::Draw);
+    }
+
+    #[test]
+    fn test_three_way_close_prefers_final_score() {
+        use crate::feed::score_feed::GameStatus::{Finished, Live};
+        use crate::feed::score_feed::ScoreSource;
+        let date = chrono::NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        let game = |home: &str, away: &str, home_score, away_score, game_status| ScoreUpdate {
+            game_id: "1".to_string(),
+            home_team: home.to_string(),
+            away_team: away.to_string(),
+            home_score,
+            away_score,
+            period: 2,
+            clock_seconds: 0,
+            total_elapsed_seconds: 5400,
+            game_status,
+            home_red_cards: 0,
+            away_red_cards: 0,
+            source: ScoreSource::Espn,
+        };
+        let teams = ("Arsenal", "Chelsea");
+        // Closing odds price the home side at 0.97, but the game finished a draw
+        let probs = (0.97, 0.01, 0.02);
+        let scores = [game("Arsenal", "Chelsea", 1, 1, Finished)];
+        assert_eq!(
+            three_way_close(&scores, "soccer-epl", teams, date, probs),
+            ([0, 0, 100], SettleBasis::FinalScore)
+        );
+        // Teams listed the other way round still settle on the right side
+        let scores = [game("Chelsea", "Arsenal", 2, 0, Finished)];
+        assert_eq!(
+            three_way_close(&scores, "soccer-epl", teams, date, probs),
+            ([0, 100, 0], SettleBasis::FinalScore)
+        );
+        // No final score yet: closing odds stand in, labelled as such
+        let scores = [game("Arsenal", "Chelsea", 1, 1, Live)];
+        assert_eq!(
+            three_way_close(&scores, "soccer-epl", teams, date, probs),
+            ([100, 0, 0], SettleBasis::ClosingOdds)
+        );
+        assert_eq!(SettleBasis::ClosingOdds.label(), "closing odds");
+    }
+
+    #[test]
+    fn test_soccer_score_feed_uses_three_way_model() {
+        let sport_config = SportConfig {
+            enabled: true,
+            kalshi_series: "KXEPLGAME".into(),
+            label: "EPL".into(),
+            hotkey: "7".into(),
+            fair_value: "score-feed".into(),
+            odds_source: "the-odds-api".into(),
+            polymarket_tag: None,
+            score_feed: Some(ScoreFeedConfig {
+                feed_type: "soccer".into(),
+                primary_url: "https://espn.com/test".into(),
+                fallback_url: None,
+                live_poll_s: 2,
+                pre_game_poll_s: 60,
+                failover_threshold: 3,
+                request_timeout_ms: 1000,
+                align_to_cadence: false,
+                cadence_offset_ms: 0,
+            }),
+            win_prob: None,
+            strategy: None,
+            momentum: None,
+            season: None,
+            period_markets: None,
+            soccer_model: Some(SoccerModelConfig::default()),
+            order_flow: None,
+            blend: None,
+            news: None,
+            pre_game: None,
+            backup_odds_source: None,
+        };
+        let pipe = SportPipeline::from_config(
+            "soccer-epl",
+            &sport_config,
+            &test_global_strategy(),
+            &test_global_momentum(),
+            &MatchingConfig::default(),
+            &Network::default(),
+        );
+        let model = pipe
+            .fair_value_model
+            .score_model()
+            .expect("expected a score model");
+        assert!(model.soccer.is_some());
+        assert_eq!(model.regulation_secs, SOCCER_REGULATION_SECS);
+        assert_eq!(model.table.regulation_periods(), 2);
+    }
+
+    #[test]
+    fn test_order_flow_prices_off_the_book_alone() {
+        let sport_config = SportConfig {
+            enabled: true,
+            kalshi_series: "KXNBAGAME".into(),
+            label: "NBA".into(),
+            hotkey: "1".into(),
+            fair_value: "order-flow".into(),
+            odds_source: String::new(),
+            polymarket_tag: None,
+            score_feed: None,
+            win_prob: None,
+            strategy: None,
+            momentum: None,
+            season: None,
+            period_markets: None,
+            soccer_model: None,
+            order_flow: Some(OrderFlowConfig {
+                min_samples: 5,
+                ..OrderFlowConfig::default()
+            }),
+            blend: None,
+            news: None,
+            pre_game: None,
+            backup_odds_source: None,
+        };
+        let mut pipe = SportPipeline::from_config(
+            "basketball",
+            &sport_config,
+            &test_global_strategy(),
+            &test_global_momentum(),
+            &MatchingConfig::default(),
+            &Network::default(),
+        );
+        assert!(pipe.odds_source.is_empty());
+        assert_eq!(pipe.fair_value_model.driver(), Driver::Book);
+        pipe.momentum_config.taker_momentum_threshold = 0;
+        pipe.momentum_config.maker_momentum_threshold = 0;
+
+        let ticker = "KXNBAGAME-26MAR10LALBOS-BOS";
+        let mut index = matcher::MarketIndex::new();
+        index.insert(
+            matcher::generate_key(
+                "basketball",
+                "Boston Celtics",
+                "Los Angeles Lakers",
+                chrono::NaiveDate::from_ymd_opt(2026, 3, 10).unwrap(),
+            )
+            .unwrap(),
+            matcher::IndexedGame {
+                home: Some(matcher::SideMarket {
+                    ticker: ticker.to_string(),
+                    title: String::new(),
+                    yes_bid: 0,
+                    yes_ask: 0,
+                    no_bid: 0,
+                    no_ask: 0,
+                    status: "active".to_string(),
+                    close_time: None,
+                }),
+                ..Default::default()
+            },
+        );
+        let live_book: LiveBook = Default::default();
+        let (state_tx, _) = watch::channel(AppState::new());
+        let mut tick = |yes_bid: u32, yes_ask: u32| {
+            live_book.insert(
+                ticker.to_string(),
+                crate::DepthBook::from_snapshot(&BookSnapshot {
+                    yes: vec![(yes_bid, 100)],
+                    no: vec![(100 - yes_ask, 100)],
+                }),
+            );
+            pipe.tick_order_flow(
+                Instant::now(),
+                &index,
+                &live_book,
+                &MomentumScorer::new(0.6, 0.4),
+                &RiskConfig::default(),
+                &SimulationConfig::default(),
+                true,
+                &state_tx,
+                100_000,
+                None,
+                &Journal::disabled(),
+                None,
+            )
+        };
+
+        // Warming up: nothing is evaluated until min_samples distinct books
+        for i in 0..4 {
+            assert!(tick(49 + i % 2, 51 + i % 2).rows.is_empty());
+        }
+        let quiet = tick(49, 51);
+        assert_eq!(quiet.rows[ticker].fair_value, 50);
+        assert_eq!(
+            quiet.rows[ticker].skip_reason,
+            Some(SkipReason::BelowThreshold)
+        );
+
+        // A sell-off far outside the band is bought back toward the mean
+        let dip = tick(40, 42);
+        let row = &dip.rows[ticker];
+        assert!(row.fair_value >= 47, "fair {}", row.fair_value);
+        assert_eq!(row.action, "TAKER");
+        assert_eq!(row.phase, "BOOK");
+        assert_eq!(state_tx.borrow().sim_entries_filled, 1);
+    }
+
+    #[test]
+    fn test_period_fair_values_track_segment_baselines() {
+        use crate::feed::score_feed::{GameStatus, ScoreSource};
+        let table = WinProbTable::from_config(&WinProbConfig {
+            home_advantage: 2.5,
+            k_start: 0.065,
+            k_range: 0.25,
+            ot_k_start: 0.10,
+            ot_k_range: 1.0,
+            regulation_secs: Some(2880),
+            ot_period_secs: None,
+            regulation_periods: None,
+            tied_home_prob: None,
+            sudden_death: false,
+            shootout_home_prob: None,
+            sanity_bounds: Vec::new(),
+            k_rel_stderr: 0.1,
+            home_advantage_stderr: 1.0,
+        });
+        let side = |ticker: &str| matcher::SideMarket {
+            ticker: ticker.to_string(),
+            title: String::new(),
+            yes_bid: 0,
+            yes_ask: 0,
+            no_bid: 0,
+            no_ask: 0,
+            status: "open".to_string(),
+            close_time: None,
+        };
+        let game = matcher::IndexedGame {
+            periods: ["1H", "2H"]
+                .into_iter()
+                .map(|label| matcher::PeriodMarket {
+                    segment: GameSegment::parse(label).unwrap(),
+                    away: Some(side(&format!("{label}-LAL"))),
+                    home: Some(side(&format!("{label}-BOS"))),
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let update = |id: &str, home_score, away_score, elapsed| ScoreUpdate {
+            game_id: id.to_string(),
+            home_team: String::new(),
+            away_team: String::new(),
+            home_score,
+            away_score,
+            period: 2,
+            clock_seconds: 0,
+            total_elapsed_seconds: elapsed,
+            game_status: GameStatus::Live,
+            home_red_cards: 0,
+            away_red_cards: 0,
+            source: ScoreSource::Espn,
+        };
+        let mut baselines = HashMap::new();
+        let mut values = |u: &ScoreUpdate| -> Vec<(String, u32, u16, u16)> {
+            period_fair_values(&game, u, 2880, &table, &mut baselines)
+                .into_iter()
+                .map(|pf| {
+                    (
+                        pf.period.segment.label(),
+                        pf.home_fair,
+                        pf.home_points,
+                        pf.away_points,
+                    )
+                })
+                .collect()
+        };
+
+        // First half only, priced on the whole score so far
+        let first = values(&update("g", 50, 40, 1200));
+        assert_eq!(first.len(), 1);
+        assert_eq!((first[0].2, first[0].3), (50, 40));
+        assert!(first[0].1 > 50);
+
+        // Halftime: the first half is decided, the second half baselines here
+        let half = values(&update("g", 55, 50, 1440));
+        assert_eq!(half[0].1, 100);
+        assert_eq!(half[1], ("2H".to_string(), half[1].1, 0, 0));
+
+        // Second-half points only count from the baseline
+        let later = values(&update("g", 60, 62, 1500));
+        assert_eq!((later[1].2, later[1].3), (5, 12));
+        assert!(later[1].1 < 50);
+
+        // Joined mid-half: no baseline, so the second half is skipped
+        let joined = values(&update("h", 70, 60, 1700));
+        assert!(joined.iter().all(|v| v.0 == "1H"));
+    }
+
+    #[test]
+    fn test_blended_trace_records_both_inputs() {
+        use crate::feed::score_feed::{GameStatus, ScoreSource};
+        let update = ScoreUpdate {
+            game_id: "g".into(),
+            home_team: "BOS".into(),
+            away_team: "LAL".into(),
+            home_score: 80,
+            away_score: 70,
+            period: 4,
+            clock_seconds: 300,
+            total_elapsed_seconds: 2580,
+            game_status: GameStatus::Live,
+            home_red_cards: 0,
+            away_red_cards: 0,
+            source: ScoreSource::Espn,
+        };
+        let odds = ConsensusOdds {
+            quote: OddsQuote {
+                home: -400.0,
+                away: 300.0,
+                draw: None,
+                stderr: None,
+            },
+            bookmakers: vec!["Pinnacle".into()],
+            bookmaker_weights: vec![1.0],
+        };
+        let mut fv = FairValues::from_probs(0.9, 0.1, None);
+        let inputs = score_fv_inputs(&update, "Q4".into(), 90, &fv, "AWAY", Some(&odds));
+        assert!(matches!(inputs, FairValueInputs::Score { .. }));
+
+        fv.blend = Some(fair_value::BlendParts {
+            score_weight: 0.8,
+            score: (0.95, 0.05, None),
+            odds: (0.8, 0.2, None),
+        });
+        let FairValueInputs::Blended {
+            score_prob,
+            odds_prob,
+            score_weight,
+            bookmakers,
+            ..
+        } = score_fv_inputs(&update, "Q4".into(), 10, &fv, "AWAY", Some(&odds))
+        else {
+            panic!("expected blended inputs");
+        };
+        assert_eq!((score_prob, odds_prob, score_weight), (0.05, 0.2, 0.8));
+        assert_eq!(bookmakers, vec!["Pinnacle"]);
+    }
+    #[test]
+    fn test_stderr_multiple_filters_noisy_edges() {
+        let strategy_config = StrategyConfig {
+            taker_stderr_multiple: Some(2.0),
+            maker_stderr_multiple: Some(2.0),
+            ..test_global_strategy()
+        };
+        let momentum_config = MomentumConfig {
+            taker_momentum_threshold: 0,
+            maker_momentum_threshold: 0,
+            ..test_global_momentum()
+        };
+        let side = matcher::SideMarket {
+            ticker: "KXNBAGAME-26MAR10LALBOS-BOS".to_string(),
+            title: String::new(),
+            yes_bid: 53,
+            yes_ask: 55,
+            no_bid: 45,
+            no_ask: 47,
+            status: "active".to_string(),
+            close_time: None,
+        };
+        let eval_halted = |stderr: Option<f64>, news_halted: bool| {
+            // Fresh state each time: an entry would count as held inventory
+            let (state_tx, _) = watch::channel(AppState::new());
+            let EvalOutcome::Evaluated(row, _) = evaluate_matched_market(
+                &side.ticker,
+                60,
+                stderr,
+                side.yes_bid,
+                side.yes_ask,
+                false,
+                0.0,
+                None,
+                false,
+                false,
+                news_halted,
+                Some(&side),
+                chrono::Utc::now(),
+                &LiveBook::default(),
+                &strategy_config,
+                &momentum_config,
+                &mut HashMap::new(),
+                &MomentumScorer::new(0.6, 0.4),
+                false,
+                &state_tx,
+                Instant::now(),
+                "score_feed",
+                &SimulationConfig::default(),
+                &RiskConfig::default(),
+                100_000,
+                "basketball",
+                FairValueMethod::ScoreFeed {
+                    source: "score-feed".to_string(),
+                },
+                FairValueInputs::Odds {
+                    home_odds: -150.0,
+                    away_odds: 130.0,
+                    bookmakers: Vec::new(),
+                    bookmaker_weights: Vec::new(),
+                    devigged_prob: 0.6,
+                    pre_game: false,
+                },
+                None,
+                None,
+                &Journal::disabled(),
+            ) else {
+                panic!("market should be open");
+            };
+            row
+        };
+        let eval = |stderr| eval_halted(stderr, false);
+
+        // A 5c edge clears two standard errors of 1c, not of 3c
+        assert_eq!(eval(Some(1.0)).action, "TAKER");
+        assert_eq!(
+            eval(Some(3.0)).skip_reason,
+            Some(SkipReason::BelowThreshold)
+        );
+        // No stderr: the fixed 5c taker threshold applies
+        assert_eq!(eval(None).action, "TAKER");
+        // Breaking news about either team skips even a clear edge
+        let halted = eval_halted(Some(1.0), true);
+        assert_eq!(halted.skip_reason, Some(SkipReason::NewsHalt));
+        assert_eq!(halted.action, "NEWS");
+    }
+
+    #[test]
+    fn test_bookmaker_devig_stderr_spreads_with_disagreement() {
+        let book = |name: &str, home: f64, away: f64| crate::feed::types::BookmakerOdds {
+            name: name.to_string(),
+            home_odds: home,
+            away_odds: away,
+            draw_odds: None,
+            last_update: "2026-01-20T00:00:00Z".to_string(),
+        };
+        let sources = OddsSourcesConfig::default();
+        let one = [book("Pinnacle", -150.0, 130.0)];
+        assert_eq!(bookmaker_devig_stderr(&one, &sources), None);
+        let agree = [
+            book("Pinnacle", -150.0, 130.0),
+            book("DraftKings", -150.0, 130.0),
+        ];
+        assert_eq!(bookmaker_devig_stderr(&agree, &sources), Some(0.0));
+        let split = [
+            book("Pinnacle", -200.0, 170.0),
+            book("DraftKings", -110.0, -110.0),
+        ];
+        let se = bookmaker_devig_stderr(&split, &sources).unwrap();
+        assert!((0.05..0.1).contains(&se), "got {se}");
+    }
+
+    #[test]
+    fn test_pre_game_window_and_entry_configs() {
+        let config = PreGameConfig {
+            max_hours_before: 12.0,
+            min_hours_before: 1.0,
+            edge_margin: 6,
+            max_contracts: 3,
+        };
+        let now = chrono::Utc::now();
+        let hours = |h: i64| now + chrono::Duration::hours(h);
+        assert!(in_pre_game_window(&config, hours(6), now));
+        assert!(!in_pre_game_window(&config, hours(13), now));
+        // Too close to the start, and already started
+        assert!(!in_pre_game_window(&config, now, now));
+        assert!(!in_pre_game_window(&config, hours(-1), now));
+
+        let risk = RiskConfig::default();
+        let (strategy, momentum, risk) = pre_game_entry_configs(
+            &config,
+            &test_global_strategy(),
+            &test_global_momentum(),
+            &risk,
+        );
+        assert_eq!(strategy.taker_edge_threshold, u8::MAX);
+        assert_eq!(strategy.maker_edge_threshold, 6);
+        assert_eq!(momentum.maker_momentum_threshold, 0);
+        assert_eq!(risk.max_contracts_per_market, 3);
+    }
+
+    #[test]
+    fn test_resolve_fuzzy_teams_renames_to_indexed_names() {
+        let sport = "college-basketball";
+        let date = chrono::NaiveDate::from_ymd_opt(2026, 2, 7).unwrap();
+        let mut index = matcher::MarketIndex::new();
+        index.insert(
+            matcher::generate_key(sport, "Miami FL", "Louisville", date).unwrap(),
+            matcher::IndexedGame {
+                away_team: "Miami FL".to_string(),
+                home_team: "Louisville".to_string(),
+                ..Default::default()
+            },
+        );
+
+        let mut games = vec![
+            ("Louisville".to_string(), "Miami".to_string()),
+            ("Kansas".to_string(), "Gonzaga".to_string()),
+        ];
+        let resolve = |games: &mut Vec<(String, String)>, min_score| {
+            resolve_fuzzy_teams(
+                games.iter_mut().map(|(h, a)| (h, a, date)),
+                sport,
+                &index,
+                min_score,
+            )
+        };
+        resolve(&mut games, None);
+        assert_eq!(games[0].1, "Miami");
+
+        resolve(&mut games, Some(0.9));
+        assert_eq!(games[0], ("Louisville".to_string(), "Miami FL".to_string()));
+        assert_eq!(games[1], ("Kansas".to_string(), "Gonzaga".to_string()));
+        assert!(matcher::generate_key(sport, &games[0].0, &games[0].1, date)
+            .is_some_and(|k| index.contains_key(&k)));
     }
 }
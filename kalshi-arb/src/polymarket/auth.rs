@@ -0,0 +1,309 @@
+use anyhow::{Context, Result};
+use base64::Engine as _;
+use k256::ecdsa::SigningKey;
+use ring::hmac;
+use sha3::{Digest, Keccak256};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Polygon mainnet.
+const CHAIN_ID: u64 = 137;
+/// CTF Exchange, which settles standard binary markets.
+const CTF_EXCHANGE: &str = "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E";
+/// Exchange for negative-risk (multi-outcome) markets.
+const NEG_RISK_CTF_EXCHANGE: &str = "0xC5d563A36AE78145C45a50134d48A1215220f80a";
+
+/// Open order: any taker may fill it.
+const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+const DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+const ORDER_TYPE: &str = "Order(uint256 salt,address maker,address signer,address taker,\
+uint256 tokenId,uint256 makerAmount,uint256 takerAmount,uint256 expiration,uint256 nonce,\
+uint256 feeRateBps,uint8 side,uint8 signatureType)";
+
+/// Fields of a CLOB order that go into its EIP-712 hash.
+#[derive(Debug, Clone)]
+pub struct OrderFields {
+    pub salt: u64,
+    pub token_id: String,
+    pub maker_amount: u64,
+    pub taker_amount: u64,
+    pub is_buy: bool,
+    pub neg_risk: bool,
+}
+
+/// Polymarket credentials: the wallet key signs orders (EIP-712), the L2 API
+/// key/secret/passphrase authenticate REST calls (HMAC-SHA256).
+pub struct PolymarketAuth {
+    signing_key: SigningKey,
+    address: String,
+    /// Wallet holding the funds; differs from `address` for proxy wallets.
+    funder: String,
+    signature_type: u8,
+    api_key: String,
+    api_secret: Vec<u8>,
+    passphrase: String,
+}
+
+impl PolymarketAuth {
+    pub fn new(
+        private_key_hex: &str,
+        api_key: String,
+        api_secret: &str,
+        passphrase: String,
+        funder: Option<String>,
+        signature_type: u8,
+    ) -> Result<Self> {
+        let key_bytes = hex::decode(private_key_hex.trim().trim_start_matches("0x"))
+            .context("Polymarket private key is not valid hex")?;
+        let signing_key = SigningKey::from_slice(&key_bytes)
+            .map_err(|e| anyhow::anyhow!("invalid Polymarket private key: {}", e))?;
+        let address = address_of(&signing_key);
+        let api_secret = base64::engine::general_purpose::URL_SAFE
+            .decode(api_secret.trim())
+            .context("Polymarket API secret is not valid base64")?;
+        let funder = funder
+            .filter(|f| !f.is_empty())
+            .unwrap_or_else(|| address.clone());
+        Ok(Self {
+            signing_key,
+            address,
+            funder,
+            signature_type,
+            api_key: api_key.trim().to_string(),
+            api_secret,
+            passphrase: passphrase.trim().to_string(),
+        })
+    }
+
+    pub fn api_key(&self) -> &str {
+        &self.api_key
+    }
+
+    #[allow(dead_code)]
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    fn timestamp_secs() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    /// L2 HMAC signature over `timestamp + method + path + body`.
+    fn hmac_signature(&self, timestamp: u64, method: &str, path: &str, body: &str) -> String {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, &self.api_secret);
+        let message = format!("{}{}{}{}", timestamp, method, path, body);
+        let tag = hmac::sign(&key, message.as_bytes());
+        base64::engine::general_purpose::URL_SAFE.encode(tag.as_ref())
+    }
+
+    /// Build L2 auth headers for a request. `body` must be the exact bytes sent.
+    pub fn headers(&self, method: &str, path: &str, body: &str) -> Vec<(String, String)> {
+        let timestamp = Self::timestamp_secs();
+        vec![
+            ("POLY_ADDRESS".to_string(), self.address.clone()),
+            (
+                "POLY_SIGNATURE".to_string(),
+                self.hmac_signature(timestamp, method, path, body),
+            ),
+            ("POLY_TIMESTAMP".to_string(), timestamp.to_string()),
+            ("POLY_API_KEY".to_string(), self.api_key.clone()),
+            ("POLY_PASSPHRASE".to_string(), self.passphrase.clone()),
+        ]
+    }
+
+    /// EIP-712 digest of an order against the exchange that settles it.
+    fn order_digest(&self, fields: &OrderFields) -> Result<[u8; 32]> {
+        let exchange = if fields.neg_risk {
+            NEG_RISK_CTF_EXCHANGE
+        } else {
+            CTF_EXCHANGE
+        };
+        let side: u64 = if fields.is_buy { 0 } else { 1 };
+
+        let domain = keccak(
+            &[
+                keccak(DOMAIN_TYPE.as_bytes()),
+                keccak(b"Polymarket CTF Exchange"),
+                keccak(b"1"),
+                u256(CHAIN_ID),
+                address_word(exchange)?,
+            ]
+            .concat(),
+        );
+        let order = keccak(
+            &[
+                keccak(ORDER_TYPE.as_bytes()),
+                u256(fields.salt),
+                address_word(&self.funder)?,
+                address_word(&self.address)?,
+                address_word(ZERO_ADDRESS)?,
+                u256_decimal(&fields.token_id)?,
+                u256(fields.maker_amount),
+                u256(fields.taker_amount),
+                u256(0), // expiration: none
+                u256(0), // nonce
+                u256(0), // fee rate bps
+                u256(side),
+                u256(self.signature_type as u64),
+            ]
+            .concat(),
+        );
+        Ok(keccak(&[&[0x19, 0x01][..], &domain, &order].concat()))
+    }
+
+    /// Sign an order and return the signed body for `POST /order`.
+    pub fn sign_order(&self, fields: &OrderFields) -> Result<super::types::SignedOrder> {
+        let digest = self.order_digest(fields)?;
+        let (signature, recovery) = self
+            .signing_key
+            .sign_prehash_recoverable(&digest)
+            .map_err(|e| anyhow::anyhow!("order signing failed: {}", e))?;
+        let mut sig = signature.to_bytes().to_vec();
+        sig.push(27 + recovery.to_byte());
+
+        Ok(super::types::SignedOrder {
+            salt: fields.salt,
+            maker: self.funder.clone(),
+            signer: self.address.clone(),
+            taker: ZERO_ADDRESS.to_string(),
+            token_id: fields.token_id.clone(),
+            maker_amount: fields.maker_amount.to_string(),
+            taker_amount: fields.taker_amount.to_string(),
+            expiration: "0".to_string(),
+            nonce: "0".to_string(),
+            fee_rate_bps: "0".to_string(),
+            side: if fields.is_buy { "BUY" } else { "SELL" }.to_string(),
+            signature_type: self.signature_type,
+            signature: format!("0x{}", hex::encode(sig)),
+        })
+    }
+}
+
+fn keccak(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+/// EIP-55 checksummed address for a signing key.
+fn address_of(key: &SigningKey) -> String {
+    let point = key.verifying_key().to_encoded_point(false);
+    let hash = keccak(&point.as_bytes()[1..]);
+    let lower = hex::encode(&hash[12..]);
+    let check = hex::encode(keccak(lower.as_bytes()));
+    let checksummed: String = lower
+        .chars()
+        .zip(check.chars())
+        .map(|(c, h)| {
+            if c.is_ascii_alphabetic() && h >= '8' {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("0x{}", checksummed)
+}
+
+fn u256(value: u64) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+/// Big-endian 256-bit word from a decimal string (token ids exceed u128).
+fn u256_decimal(decimal: &str) -> Result<[u8; 32]> {
+    let mut word = [0u8; 32];
+    for digit in decimal.trim().chars() {
+        let mut carry = digit
+            .to_digit(10)
+            .with_context(|| format!("invalid decimal token id: {}", decimal))?;
+        for byte in word.iter_mut().rev() {
+            let v = (*byte as u32) * 10 + carry;
+            *byte = v as u8;
+            carry = v >> 8;
+        }
+        if carry != 0 {
+            anyhow::bail!("token id overflows uint256: {}", decimal);
+        }
+    }
+    Ok(word)
+}
+
+fn address_word(address: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(address.trim_start_matches("0x"))
+        .with_context(|| format!("invalid address: {}", address))?;
+    if bytes.len() != 20 {
+        anyhow::bail!("address must be 20 bytes: {}", address);
+    }
+    let mut word = [0u8; 32];
+    word[12..].copy_from_slice(&bytes);
+    Ok(word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_auth() -> PolymarketAuth {
+        PolymarketAuth::new(
+            "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+            "test-api-key".to_string(),
+            "c2VjcmV0LWtleS1mb3ItdGVzdHM=",
+            "pass".to_string(),
+            None,
+            0,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_address_derived_from_private_key() {
+        assert_eq!(
+            test_auth().address(),
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+        );
+    }
+
+    #[test]
+    fn test_l2_signature_and_token_id_encoding() {
+        let auth = test_auth();
+        // HMAC-SHA256("secret-key-for-tests", "1700000000POST/order{}"), URL-safe base64
+        assert_eq!(
+            auth.hmac_signature(1_700_000_000, "POST", "/order", "{}"),
+            "wYvGAmCMQRLPhZnY7L-MM7Z-LHRLcYBMRJSbNQDFAEw="
+        );
+
+        let word = u256_decimal("340282366920938463463374607431768211456").unwrap(); // 2^128
+        assert_eq!(word[15], 1);
+        assert!(word.iter().enumerate().all(|(i, &b)| i == 15 || b == 0));
+        assert!(u256_decimal("12a").is_err());
+    }
+
+    #[test]
+    fn test_signed_order_recovers_to_signer() {
+        let auth = test_auth();
+        let fields = OrderFields {
+            salt: 42,
+            token_id: "1234567890".to_string(),
+            maker_amount: 4_700_000,
+            taker_amount: 10_000_000,
+            is_buy: true,
+            neg_risk: false,
+        };
+        let order = auth.sign_order(&fields).unwrap();
+        assert_eq!(order.side, "BUY");
+        assert_eq!(order.maker, auth.address());
+        let sig = hex::decode(order.signature.trim_start_matches("0x")).unwrap();
+        assert_eq!(sig.len(), 65);
+        let recovered = k256::ecdsa::VerifyingKey::recover_from_prehash(
+            &auth.order_digest(&fields).unwrap(),
+            &k256::ecdsa::Signature::from_slice(&sig[..64]).unwrap(),
+            k256::ecdsa::RecoveryId::from_byte(sig[64] - 27).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(&recovered, auth.signing_key.verifying_key());
+    }
+}
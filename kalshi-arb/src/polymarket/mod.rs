@@ -0,0 +1,4 @@
+pub mod auth;
+pub mod rest;
+pub mod types;
+pub mod ws;
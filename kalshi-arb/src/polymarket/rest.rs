@@ -0,0 +1,181 @@
+use super::auth::{OrderFields, PolymarketAuth};
+use super::types::*;
+use anyhow::{Context, Result};
+use reqwest::Client;
+use std::sync::Arc;
+
+/// Events per Gamma page.
+const EVENTS_PAGE_SIZE: usize = 100;
+
+/// Polymarket REST: Gamma for market discovery, the CLOB for orders.
+/// Discovery needs no credentials; trading requires `auth`.
+pub struct PolymarketRest {
+    client: Client,
+    auth: Option<Arc<PolymarketAuth>>,
+    gamma_url: String,
+    clob_url: String,
+}
+
+impl PolymarketRest {
+    pub fn new(
+        auth: Option<Arc<PolymarketAuth>>,
+        gamma_url: &str,
+        clob_url: &str,
+        http: reqwest::ClientBuilder,
+    ) -> Result<Self> {
+        let client = http
+            .pool_max_idle_per_host(4)
+            .build()
+            .context("failed to build HTTP client")?;
+        Ok(Self {
+            client,
+            auth,
+            gamma_url: gamma_url.trim_end_matches('/').to_string(),
+            clob_url: clob_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    fn auth(&self) -> Result<&PolymarketAuth> {
+        self.auth
+            .as_deref()
+            .context("Polymarket credentials are not configured")
+    }
+
+    /// Fetch all open events carrying `tag_slug` (e.g. "nba"). Paginates automatically.
+    pub async fn get_events(&self, tag_slug: &str) -> Result<Vec<GammaEvent>> {
+        let mut all_events = Vec::new();
+        loop {
+            let url = format!(
+                "{}/events?tag_slug={}&active=true&closed=false&limit={}&offset={}",
+                self.gamma_url,
+                tag_slug,
+                EVENTS_PAGE_SIZE,
+                all_events.len()
+            );
+            let resp = self
+                .client
+                .get(&url)
+                .send()
+                .await
+                .context("GET events failed")?;
+            let status = resp.status();
+            if !status.is_success() {
+                let body = resp.text().await.unwrap_or_default();
+                anyhow::bail!("GET events failed ({}): {}", status, body);
+            }
+            let page: Vec<GammaEvent> = resp
+                .json()
+                .await
+                .context("failed to parse events response")?;
+            let done = page.len() < EVENTS_PAGE_SIZE;
+            all_events.extend(page);
+            if done {
+                break;
+            }
+        }
+        Ok(all_events)
+    }
+
+    /// Fetch the market trading `token_id`, closed or not. None if Gamma
+    /// does not know the token.
+    pub async fn get_market_by_token(&self, token_id: &str) -> Result<Option<GammaMarket>> {
+        let url = format!("{}/markets?clob_token_ids={}", self.gamma_url, token_id);
+        let resp = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("GET markets failed")?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("GET markets failed ({}): {}", status, body);
+        }
+        let markets: Vec<GammaMarket> = resp
+            .json()
+            .await
+            .context("failed to parse markets response")?;
+        Ok(markets.into_iter().next())
+    }
+
+    /// Sign and place an order for `quantity` shares of `token_id` at `price` cents.
+    /// Takers are fill-or-kill; makers rest until cancelled.
+    pub async fn create_order(
+        &self,
+        token_id: &str,
+        quantity: u32,
+        price: u32,
+        is_buy: bool,
+        is_taker: bool,
+        neg_risk: bool,
+    ) -> Result<PostOrderResponse> {
+        let auth = self.auth()?;
+        // USDC and outcome shares both use 6 decimals
+        let shares = quantity as u64 * 1_000_000;
+        let usdc = quantity as u64 * price as u64 * 10_000;
+        let (maker_amount, taker_amount) = if is_buy {
+            (usdc, shares)
+        } else {
+            (shares, usdc)
+        };
+        let order = auth.sign_order(&OrderFields {
+            salt: rand::random::<u32>() as u64,
+            token_id: token_id.to_string(),
+            maker_amount,
+            taker_amount,
+            is_buy,
+            neg_risk,
+        })?;
+        let body = serde_json::to_string(&PostOrderRequest {
+            order,
+            owner: auth.api_key().to_string(),
+            order_type: if is_taker { "FOK" } else { "GTC" }.to_string(),
+        })?;
+
+        let path = "/order";
+        let mut req = self
+            .client
+            .post(format!("{}{}", self.clob_url, path))
+            .header("Content-Type", "application/json")
+            .body(body.clone());
+        for (k, v) in auth.headers("POST", path, &body) {
+            req = req.header(k, v);
+        }
+        let resp = req.send().await.context("order request failed")?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("order failed ({}): {}", status, body);
+        }
+        let parsed: PostOrderResponse = resp
+            .json()
+            .await
+            .context("failed to parse order response")?;
+        if !parsed.success {
+            anyhow::bail!("order rejected: {}", parsed.error_msg);
+        }
+        Ok(parsed)
+    }
+
+    /// Cancel a resting order by its id (order hash).
+    pub async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        let auth = self.auth()?;
+        let body = serde_json::json!({ "orderID": order_id }).to_string();
+        let path = "/order";
+        let mut req = self
+            .client
+            .delete(format!("{}{}", self.clob_url, path))
+            .header("Content-Type", "application/json")
+            .body(body.clone());
+        for (k, v) in auth.headers("DELETE", path, &body) {
+            req = req.header(k, v);
+        }
+        let resp = req.send().await.context("cancel request failed")?;
+        let status = resp.status();
+        if !status.is_success() {
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("cancel failed ({}): {}", status, body);
+        }
+        Ok(())
+    }
+}
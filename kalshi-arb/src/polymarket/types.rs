@@ -0,0 +1,295 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// A Gamma API event (one game) with its markets.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+pub struct GammaEvent {
+    pub id: String,
+    #[serde(default)]
+    pub slug: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub start_date: Option<String>,
+    #[serde(default)]
+    pub markets: Vec<GammaMarket>,
+}
+
+/// A Gamma API market. List fields arrive as JSON-encoded strings.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+pub struct GammaMarket {
+    #[serde(default)]
+    pub question: String,
+    #[serde(default)]
+    pub condition_id: String,
+    #[serde(default)]
+    pub slug: String,
+    #[serde(default, deserialize_with = "json_string_list")]
+    pub outcomes: Vec<String>,
+    #[serde(default, deserialize_with = "json_string_list")]
+    pub clob_token_ids: Vec<String>,
+    /// Per-outcome prices in dollars; once resolved, the winner is "1".
+    #[serde(default, deserialize_with = "json_string_list")]
+    pub outcome_prices: Vec<String>,
+    #[serde(default)]
+    pub active: bool,
+    #[serde(default)]
+    pub closed: bool,
+    #[serde(default)]
+    pub end_date: Option<String>,
+    #[serde(default)]
+    pub game_start_time: Option<String>,
+    #[serde(default)]
+    pub neg_risk: bool,
+    #[serde(default)]
+    pub sports_market_type: Option<String>,
+}
+
+impl GammaMarket {
+    /// Head-to-head winner market: two team outcomes (not Yes/No), and either
+    /// tagged `moneyline` or the event's own slug (spreads and totals get suffixes).
+    pub fn is_moneyline(&self, event_slug: &str) -> bool {
+        if self.outcomes.len() != 2 || self.clob_token_ids.len() != 2 {
+            return false;
+        }
+        if self.outcomes.iter().any(|o| o.eq_ignore_ascii_case("yes")) {
+            return false;
+        }
+        match self.sports_market_type.as_deref() {
+            Some(t) => t == "moneyline",
+            None => self.slug == event_slug,
+        }
+    }
+
+    /// Status in Kalshi terms, so the index treats both venues alike.
+    pub fn status(&self) -> &'static str {
+        if self.active && !self.closed {
+            "open"
+        } else {
+            "closed"
+        }
+    }
+
+    /// Settlement payout in cents for one share of `token_id`: 100 if its
+    /// outcome won, 0 if it lost. None until the market is closed with a
+    /// definite result.
+    pub fn token_payout(&self, token_id: &str) -> Option<u32> {
+        if !self.closed {
+            return None;
+        }
+        let i = self.clob_token_ids.iter().position(|t| t == token_id)?;
+        let price: f64 = self.outcome_prices.get(i)?.parse().ok()?;
+        if price == 1.0 {
+            Some(100)
+        } else if price == 0.0 {
+            Some(0)
+        } else {
+            None
+        }
+    }
+}
+
+/// Gamma encodes lists as a JSON string (`"[\"Lakers\", \"Celtics\"]"`).
+fn json_string_list<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<String>, D::Error> {
+    let raw: Option<serde_json::Value> = Option::deserialize(d)?;
+    match raw {
+        Some(serde_json::Value::String(s)) => {
+            serde_json::from_str(&s).map_err(serde::de::Error::custom)
+        }
+        Some(v @ serde_json::Value::Array(_)) => {
+            serde_json::from_value(v).map_err(serde::de::Error::custom)
+        }
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Order body for `POST /order`. Amounts are 6-decimal fixed point strings.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedOrder {
+    pub salt: u64,
+    pub maker: String,
+    pub signer: String,
+    pub taker: String,
+    pub token_id: String,
+    pub maker_amount: String,
+    pub taker_amount: String,
+    pub expiration: String,
+    pub nonce: String,
+    pub fee_rate_bps: String,
+    pub side: String, // "BUY" or "SELL"
+    pub signature_type: u8,
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostOrderRequest {
+    pub order: SignedOrder,
+    /// API key of the order owner.
+    pub owner: String,
+    /// "GTC" (resting) or "FOK" (fill-or-kill).
+    pub order_type: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+pub struct PostOrderResponse {
+    #[serde(default)]
+    pub success: bool,
+    #[serde(default)]
+    pub error_msg: String,
+    #[serde(default, rename = "orderID")]
+    pub order_id: String,
+    #[serde(default)]
+    pub status: String,
+}
+
+/// One price level as sent by the CLOB (`{"price": "0.48", "size": "30"}`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct BookLevel {
+    pub price: String,
+    pub size: String,
+}
+
+impl BookLevel {
+    /// (price in cents, whole shares).
+    pub fn parse(&self) -> Option<(u32, i64)> {
+        let price = self.price.parse::<f64>().ok()?;
+        let size = self.size.parse::<f64>().ok()?;
+        Some(((price * 100.0).round() as u32, size.floor() as i64))
+    }
+}
+
+/// Market channel message. `book` is a full snapshot; `price_change`
+/// sets the absolute size at one level (size 0 removes it).
+#[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
+pub struct WsMarketMessage {
+    pub event_type: String,
+    #[serde(default)]
+    pub asset_id: String,
+    #[serde(default)]
+    pub bids: Vec<BookLevel>,
+    #[serde(default)]
+    pub asks: Vec<BookLevel>,
+    /// Current format: one entry per changed level, each with its asset.
+    #[serde(default)]
+    pub price_changes: Vec<WsPriceChange>,
+    /// Legacy format: changes for `asset_id`.
+    #[serde(default)]
+    pub changes: Vec<WsPriceChange>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WsPriceChange {
+    #[serde(default)]
+    pub asset_id: String,
+    pub price: String,
+    pub size: String,
+    /// "BUY" (bid) or "SELL" (ask).
+    pub side: String,
+}
+
+/// (price in cents, shares) levels, best first.
+pub type PriceLevels = Vec<(u32, i64)>;
+
+/// Full book for one outcome token after the latest update.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TokenBook {
+    pub token_id: String,
+    pub bids: PriceLevels,
+    pub asks: PriceLevels,
+}
+
+impl TokenBook {
+    /// Levels in Kalshi's YES/NO bid form: bids are YES bids, and an ask at
+    /// `p` is a NO bid at `100 - p`, so the usual best bid/ask math applies.
+    pub fn as_yes_no(&self) -> (PriceLevels, PriceLevels) {
+        let no = self
+            .asks
+            .iter()
+            .filter(|&&(p, _)| p > 0 && p < 100)
+            .map(|&(p, q)| (100 - p, q))
+            .collect();
+        (self.bids.clone(), no)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gamma_event_parses_string_encoded_lists() {
+        let json = r#"{
+            "id": "123",
+            "slug": "nba-lal-bos-2026-01-19",
+            "title": "Lakers vs. Celtics",
+            "markets": [{
+                "question": "Lakers vs. Celtics",
+                "conditionId": "0xabc",
+                "slug": "nba-lal-bos-2026-01-19",
+                "outcomes": "[\"Lakers\", \"Celtics\"]",
+                "clobTokenIds": "[\"111\", \"222\"]",
+                "active": true,
+                "closed": false,
+                "gameStartTime": "2026-01-20 00:30:00+00"
+            }, {
+                "question": "Spread: Lakers (-5.5)",
+                "slug": "nba-lal-bos-2026-01-19-spread-away-5pt5",
+                "outcomes": "[\"Lakers\", \"Celtics\"]",
+                "clobTokenIds": "[\"333\", \"444\"]",
+                "active": true
+            }]
+        }"#;
+        let event: GammaEvent = serde_json::from_str(json).unwrap();
+        let m = &event.markets[0];
+        assert_eq!(m.outcomes, vec!["Lakers", "Celtics"]);
+        assert_eq!(m.clob_token_ids, vec!["111", "222"]);
+        assert_eq!(m.status(), "open");
+        assert!(m.is_moneyline(&event.slug));
+        assert!(!event.markets[1].is_moneyline(&event.slug));
+    }
+
+    #[test]
+    fn test_token_book_maps_asks_to_no_bids() {
+        let book = TokenBook {
+            token_id: "111".to_string(),
+            bids: vec![(47, 100)],
+            asks: vec![(49, 50), (52, 10)],
+        };
+        let (yes, no) = book.as_yes_no();
+        assert_eq!(yes, vec![(47, 100)]);
+        assert_eq!(no, vec![(51, 50), (48, 10)]);
+        let level = BookLevel {
+            price: "0.49".to_string(),
+            size: "12.7".to_string(),
+        };
+        assert_eq!(level.parse(), Some((49, 12)));
+    }
+
+    #[test]
+    fn test_token_payout_only_once_resolved() {
+        let json = r#"{
+            "outcomes": "[\"Lakers\", \"Celtics\"]",
+            "clobTokenIds": "[\"111\", \"222\"]",
+            "outcomePrices": "[\"0\", \"1\"]",
+            "closed": true
+        }"#;
+        let mut m: GammaMarket = serde_json::from_str(json).unwrap();
+        assert_eq!(m.token_payout("111"), Some(0));
+        assert_eq!(m.token_payout("222"), Some(100));
+        assert_eq!(m.token_payout("333"), None);
+
+        m.outcome_prices = vec!["0.5".to_string(), "0.5".to_string()];
+        assert_eq!(m.token_payout("222"), None);
+        m.closed = false;
+        m.outcome_prices = vec!["0".to_string(), "1".to_string()];
+        assert_eq!(m.token_payout("222"), None);
+    }
+}
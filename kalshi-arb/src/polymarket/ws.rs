@@ -0,0 +1,233 @@
+use super::types::{TokenBook, WsMarketMessage, WsPriceChange};
+use crate::network::{self, Network};
+use anyhow::{Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+
+/// The market channel drops connections that stay silent for longer than this.
+const PING_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Events emitted by the Polymarket market-channel WebSocket.
+#[derive(Debug, Clone)]
+pub enum PolymarketWsEvent {
+    /// Full book for one token after a snapshot or level change.
+    Book(TokenBook),
+    Connected,
+    Disconnected(String),
+}
+
+/// Bid and ask sizes by price (cents) for one token.
+#[derive(Debug, Default)]
+struct Levels {
+    bids: HashMap<u32, i64>,
+    asks: HashMap<u32, i64>,
+}
+
+impl Levels {
+    fn to_book(&self, token_id: &str) -> TokenBook {
+        let sorted = |side: &HashMap<u32, i64>, best_high: bool| {
+            let mut v: Vec<(u32, i64)> = side.iter().map(|(&p, &q)| (p, q)).collect();
+            if best_high {
+                v.sort_unstable_by_key(|&(p, _)| std::cmp::Reverse(p));
+            } else {
+                v.sort_unstable_by_key(|&(p, _)| p);
+            }
+            v
+        };
+        TokenBook {
+            token_id: token_id.to_string(),
+            bids: sorted(&self.bids, true),
+            asks: sorted(&self.asks, false),
+        }
+    }
+}
+
+pub struct PolymarketWs {
+    ws_url: String,
+    network: Network,
+}
+
+impl PolymarketWs {
+    pub fn new(ws_url: &str, network: Network) -> Self {
+        Self {
+            ws_url: ws_url.to_string(),
+            network,
+        }
+    }
+
+    /// Connect and stream books for `token_ids`, reconnecting on failure.
+    pub async fn run(&self, token_ids: Vec<String>, tx: mpsc::Sender<PolymarketWsEvent>) {
+        loop {
+            match self.connect_and_listen(&token_ids, &tx).await {
+                Ok(()) => tracing::warn!("polymarket WS closed cleanly, reconnecting..."),
+                Err(e) => {
+                    tracing::error!("polymarket WS error: {:#}, reconnecting in 2s...", e);
+                    let _ = tx
+                        .send(PolymarketWsEvent::Disconnected(format!("{:#}", e)))
+                        .await;
+                }
+            }
+            tokio::time::sleep(Duration::from_secs(2)).await;
+        }
+    }
+
+    async fn connect_and_listen(
+        &self,
+        token_ids: &[String],
+        tx: &mpsc::Sender<PolymarketWsEvent>,
+    ) -> Result<()> {
+        let ws_stream = self
+            .network
+            .connect_ws(network::POLYMARKET, self.ws_url.as_str())
+            .await
+            .context("polymarket WS connection failed")?;
+        let (mut write, mut read) = ws_stream.split();
+        let _ = tx.send(PolymarketWsEvent::Connected).await;
+
+        let sub = serde_json::json!({
+            "assets_ids": token_ids,
+            "type": "market",
+        });
+        write
+            .send(Message::Text(sub.to_string()))
+            .await
+            .context("polymarket WS subscribe failed")?;
+        tracing::debug!(count = token_ids.len(), "subscribed to Polymarket tokens");
+
+        let mut books: HashMap<String, Levels> = HashMap::new();
+        let mut ping = tokio::time::interval(PING_INTERVAL);
+        loop {
+            tokio::select! {
+                msg = read.next() => {
+                    let Some(msg) = msg else {
+                        break;
+                    };
+                    match msg.context("polymarket WS read error")? {
+                        Message::Text(text) => {
+                            for book in handle_text(&mut books, &text) {
+                                let _ = tx.send(PolymarketWsEvent::Book(book)).await;
+                            }
+                        }
+                        Message::Ping(data) => {
+                            write.send(Message::Pong(data)).await?;
+                        }
+                        Message::Close(_) => {
+                            tracing::debug!("polymarket WS received close frame");
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                _ = ping.tick() => {
+                    write.send(Message::Text("PING".to_string())).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Apply one text frame (a message or an array of them) and return the
+/// books that changed.
+fn handle_text(books: &mut HashMap<String, Levels>, text: &str) -> Vec<TokenBook> {
+    if text == "PONG" {
+        return Vec::new();
+    }
+    let messages: Vec<WsMarketMessage> = match serde_json::from_str::<serde_json::Value>(text) {
+        Ok(serde_json::Value::Array(items)) => items
+            .into_iter()
+            .filter_map(|v| serde_json::from_value(v).ok())
+            .collect(),
+        Ok(v) => serde_json::from_value(v).into_iter().collect(),
+        Err(e) => {
+            tracing::warn!("polymarket WS message parse error: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut changed: Vec<String> = Vec::new();
+    for msg in &messages {
+        match msg.event_type.as_str() {
+            "book" => {
+                let levels = books.entry(msg.asset_id.clone()).or_default();
+                levels.bids = msg.bids.iter().filter_map(|l| l.parse()).collect();
+                levels.asks = msg.asks.iter().filter_map(|l| l.parse()).collect();
+                levels.bids.retain(|_, q| *q > 0);
+                levels.asks.retain(|_, q| *q > 0);
+                changed.push(msg.asset_id.clone());
+            }
+            "price_change" => {
+                for change in msg.price_changes.iter().chain(&msg.changes) {
+                    let asset = if change.asset_id.is_empty() {
+                        &msg.asset_id
+                    } else {
+                        &change.asset_id
+                    };
+                    // Levels for a token whose snapshot hasn't arrived are dropped
+                    if let Some(levels) = books.get_mut(asset) {
+                        apply_change(levels, change);
+                        changed.push(asset.clone());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    changed.sort();
+    changed.dedup();
+    changed
+        .iter()
+        .filter_map(|id| books.get(id).map(|levels| levels.to_book(id)))
+        .collect()
+}
+
+fn apply_change(levels: &mut Levels, change: &WsPriceChange) {
+    let level = super::types::BookLevel {
+        price: change.price.clone(),
+        size: change.size.clone(),
+    };
+    let Some((price, size)) = level.parse() else {
+        return;
+    };
+    let side = if change.side.eq_ignore_ascii_case("BUY") {
+        &mut levels.bids
+    } else {
+        &mut levels.asks
+    };
+    if size > 0 {
+        side.insert(price, size);
+    } else {
+        side.remove(&price);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_book_then_price_change_updates_levels() {
+        let mut books = HashMap::new();
+        let snapshot = r#"[{"event_type": "book", "asset_id": "111", "market": "0xabc",
+            "bids": [{"price": "0.47", "size": "100"}, {"price": "0.46", "size": "20"}],
+            "asks": [{"price": "0.49", "size": "50"}]}]"#;
+        let out = handle_text(&mut books, snapshot);
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].bids, vec![(47, 100), (46, 20)]);
+        assert_eq!(out[0].asks, vec![(49, 50)]);
+
+        let change = r#"{"event_type": "price_change", "market": "0xabc", "price_changes": [
+            {"asset_id": "111", "price": "0.47", "size": "0", "side": "BUY"},
+            {"asset_id": "111", "price": "0.48", "size": "15", "side": "SELL"},
+            {"asset_id": "999", "price": "0.10", "size": "5", "side": "BUY"}]}"#;
+        let out = handle_text(&mut books, change);
+        assert_eq!(out.len(), 1, "unknown token ignored");
+        assert_eq!(out[0].bids, vec![(46, 20)]);
+        assert_eq!(out[0].asks, vec![(48, 15), (49, 50)]);
+
+        assert!(handle_text(&mut books, "PONG").is_empty());
+    }
+}
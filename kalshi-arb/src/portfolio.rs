@@ -0,0 +1,317 @@
+//! Portfolio summary: P&L broken down by sport and by fair-value source.
+//!
+//! Built on demand for the portfolio view from today's journal. Entry orders
+//! tie each ticker to its sport and source and carry the edge and entry fee;
+//! closed trades carry the realized P&L. Open positions add unrealized P&L
+//! marked to the YES bid. Exit fees are not journaled, so they are estimated
+//! from each exit's price and whether it crossed the spread.
+
+use crate::engine::fees::calculate_fee;
+use crate::tui::state::TradeRow;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+
+/// Label for trades with no journaled entry order.
+const UNATTRIBUTED: &str = "other";
+
+/// An entry order read back from the journal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryOrder {
+    pub ticker: String,
+    pub sport: String,
+    /// Fair-value source, as shown in the positions view's Src column.
+    pub source: String,
+    /// Edge at entry, in cents.
+    pub edge: i32,
+    pub fee_cents: u32,
+}
+
+impl EntryOrder {
+    /// Parse a journal `order` entry.
+    pub fn from_journal(body: &serde_json::Value) -> Option<Self> {
+        let trace = &body["trace"];
+        let price = body["price"].as_u64()? as u32;
+        let quantity = body["quantity"].as_u64()? as u32;
+        let is_taker = body["is_taker"].as_bool().unwrap_or(false);
+        Some(Self {
+            ticker: body["ticker"].as_str()?.to_string(),
+            sport: trace["sport"].as_str().unwrap_or(UNATTRIBUTED).to_string(),
+            source: source_label(trace).to_string(),
+            edge: trace["edge"].as_i64().unwrap_or(0) as i32,
+            fee_cents: calculate_fee(price, quantity, is_taker),
+        })
+    }
+}
+
+/// Journal `order` entries at or after `since` in the given mode.
+pub fn entry_orders(
+    entries: &[serde_json::Value],
+    sim: bool,
+    since: DateTime<Utc>,
+) -> Vec<EntryOrder> {
+    entries
+        .iter()
+        .filter(|e| e["sim"].as_bool() == Some(sim))
+        .filter(|e| {
+            e["ts"]
+                .as_str()
+                .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+                .is_some_and(|ts| ts >= since)
+        })
+        .filter_map(EntryOrder::from_journal)
+        .collect()
+}
+
+fn source_label(trace: &serde_json::Value) -> &'static str {
+    if trace["inputs"]["odds"]["pre_game"].as_bool() == Some(true) {
+        return "pre";
+    }
+    let method = trace["fair_value_method"]
+        .as_object()
+        .and_then(|m| m.keys().next());
+    match method.map(String::as_str) {
+        Some("score_feed") => "score",
+        Some("odds_feed") => "odds",
+        Some("order_flow") => "flow",
+        _ => UNATTRIBUTED,
+    }
+}
+
+/// P&L figures for one sport, one source, or the whole portfolio.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PnlRow {
+    pub label: String,
+    pub realized_cents: i64,
+    pub unrealized_cents: i64,
+    /// Closed trades, and how many of them made money.
+    pub closed: u32,
+    pub wins: u32,
+    pub closed_contracts: u32,
+    pub entries: u32,
+    pub edge_total: i64,
+    /// Entry fees plus estimated exit fees.
+    pub fees_cents: i64,
+}
+
+impl PnlRow {
+    fn new(label: &str) -> Self {
+        Self {
+            label: label.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn win_rate(&self) -> Option<f64> {
+        (self.closed > 0).then(|| self.wins as f64 / self.closed as f64)
+    }
+
+    /// Mean edge at entry, in cents.
+    pub fn avg_edge(&self) -> Option<f64> {
+        (self.entries > 0).then(|| self.edge_total as f64 / self.entries as f64)
+    }
+
+    /// Realized P&L per closed contract, in cents: the edge actually kept.
+    pub fn captured(&self) -> Option<f64> {
+        (self.closed_contracts > 0)
+            .then(|| self.realized_cents as f64 / self.closed_contracts as f64)
+    }
+
+    fn add_entry(&mut self, order: &EntryOrder) {
+        self.entries += 1;
+        self.edge_total += order.edge as i64;
+        self.fees_cents += order.fee_cents as i64;
+    }
+
+    fn add_exit(&mut self, trade: &TradeRow, pnl: i64) {
+        self.realized_cents += pnl;
+        self.closed += 1;
+        if pnl > 0 {
+            self.wins += 1;
+        }
+        self.closed_contracts += trade.quantity;
+        self.fees_cents += exit_fee(trade) as i64;
+    }
+}
+
+/// Estimated fee on an exit. Resting sells pay the maker rate, forced and
+/// manual exits the taker rate; settlement at 0 or 100 pays nothing.
+fn exit_fee(trade: &TradeRow) -> u32 {
+    let is_taker = match trade.order_type.as_str() {
+        "TAKER" => true,
+        "MAKER" => false,
+        _ => trade.action != "SELL",
+    };
+    calculate_fee(trade.price, trade.quantity, is_taker)
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PortfolioSummary {
+    pub total: PnlRow,
+    /// Sorted by label.
+    pub by_sport: Vec<PnlRow>,
+    pub by_source: Vec<PnlRow>,
+}
+
+/// Summarize today's entry orders, closed trades (rows with a P&L) and open
+/// positions as (ticker, unrealized cents). A ticker's sport and source come
+/// from its latest entry order.
+pub fn summarize(
+    orders: &[EntryOrder],
+    trades: &[TradeRow],
+    open: &[(String, i64)],
+) -> PortfolioSummary {
+    let attribution: HashMap<&str, (&str, &str)> = orders
+        .iter()
+        .map(|o| (o.ticker.as_str(), (o.sport.as_str(), o.source.as_str())))
+        .collect();
+    let attribute = |ticker: &str| {
+        attribution
+            .get(ticker)
+            .copied()
+            .unwrap_or((UNATTRIBUTED, UNATTRIBUTED))
+    };
+
+    let mut total = PnlRow::new("Total");
+    let mut by_sport: BTreeMap<&str, PnlRow> = BTreeMap::new();
+    let mut by_source: BTreeMap<&str, PnlRow> = BTreeMap::new();
+    let mut rows = |ticker: &str, apply: &dyn Fn(&mut PnlRow)| {
+        let (sport, source) = attribute(ticker);
+        apply(&mut total);
+        apply(by_sport.entry(sport).or_insert_with(|| PnlRow::new(sport)));
+        apply(
+            by_source
+                .entry(source)
+                .or_insert_with(|| PnlRow::new(source)),
+        );
+    };
+
+    for order in orders {
+        rows(&order.ticker, &|r| r.add_entry(order));
+    }
+    for trade in trades {
+        if let Some(pnl) = trade.pnl {
+            rows(&trade.ticker, &|r| r.add_exit(trade, pnl as i64));
+        }
+    }
+    for (ticker, unrealized) in open {
+        rows(ticker, &|r| r.unrealized_cents += unrealized);
+    }
+
+    PortfolioSummary {
+        total,
+        by_sport: by_sport.into_values().collect(),
+        by_source: by_source.into_values().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order_entry(
+        ticker: &str,
+        sport: &str,
+        method: &str,
+        edge: i32,
+        ts: &str,
+    ) -> serde_json::Value {
+        serde_json::json!({
+            "kind": "order",
+            "ts": ts,
+            "ticker": ticker,
+            "price": 40,
+            "quantity": 10,
+            "is_taker": true,
+            "sim": true,
+            "trace": {
+                "sport": sport,
+                "edge": edge,
+                "fair_value_method": { method: { "source": "x" } },
+                "inputs": { "odds": { "pre_game": false } },
+            },
+        })
+    }
+
+    fn exit(ticker: &str, action: &str, price: u32, pnl: i32) -> TradeRow {
+        TradeRow {
+            id: 0,
+            time: "20:00:00".to_string(),
+            action: action.to_string(),
+            ticker: ticker.to_string(),
+            price,
+            quantity: 10,
+            order_type: "SIM".to_string(),
+            pnl: Some(pnl),
+            slippage: None,
+            source: String::new(),
+            fair_value_basis: String::new(),
+            phase: String::new(),
+            note: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_entry_orders_filter_mode_and_day() {
+        let since = "2026-10-18T04:00:00Z".parse().unwrap();
+        let mut live = order_entry("C", "nba", "score_feed", 4, "2026-10-18T05:00:00Z");
+        live["sim"] = serde_json::json!(false);
+        let entries = vec![
+            order_entry("A", "nba", "score_feed", 4, "2026-10-18T05:00:00.25Z"),
+            order_entry("B", "nba", "odds_feed", 4, "2026-10-17T23:00:00Z"),
+            live,
+        ];
+        let orders = entry_orders(&entries, true, since);
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].source, "score");
+        assert_eq!(orders[0].fee_cents, calculate_fee(40, 10, true));
+    }
+
+    #[test]
+    fn test_summarize_by_sport_and_source() {
+        let ts = "2026-10-18T05:00:00Z";
+        let orders: Vec<EntryOrder> = [
+            order_entry("NBA-A", "nba", "score_feed", 6, ts),
+            order_entry("NBA-B", "nba", "odds_feed", 2, ts),
+            order_entry("NHL-A", "nhl", "odds_feed", 4, ts),
+        ]
+        .iter()
+        .filter_map(EntryOrder::from_journal)
+        .collect();
+        let trades = vec![
+            exit("NBA-A", "SETTLE", 100, 550),
+            exit("NBA-B", "STOP", 35, -80),
+            // The buy row itself carries no P&L
+            TradeRow {
+                pnl: None,
+                ..exit("NHL-A", "BUY", 40, 0)
+            },
+        ];
+        let summary = summarize(&orders, &trades, &[("NHL-A".to_string(), 30)]);
+
+        let total = &summary.total;
+        assert_eq!(total.realized_cents, 470);
+        assert_eq!(total.unrealized_cents, 30);
+        assert_eq!(total.win_rate(), Some(0.5));
+        assert_eq!(total.avg_edge(), Some(4.0));
+        assert_eq!(total.captured(), Some(23.5));
+        let entry_fees = 3 * calculate_fee(40, 10, true) as i64;
+        assert_eq!(
+            total.fees_cents,
+            entry_fees + calculate_fee(35, 10, true) as i64
+        );
+
+        let labels: Vec<&str> = summary.by_sport.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, ["nba", "nhl"]);
+        assert_eq!(summary.by_sport[0].realized_cents, 470);
+        assert_eq!(summary.by_sport[1].unrealized_cents, 30);
+        let odds = summary
+            .by_source
+            .iter()
+            .find(|r| r.label == "odds")
+            .unwrap();
+        assert_eq!(
+            (odds.entries, odds.realized_cents, odds.unrealized_cents),
+            (2, -80, 30)
+        );
+    }
+}
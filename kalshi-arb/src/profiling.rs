@@ -0,0 +1,233 @@
+//! On-demand engine profiling (`F` in the TUI). While a capture runs, a
+//! tracing layer times every entered span (engine cycle, per-sport pipeline
+//! ticks, market evaluation, WS event handling) and charges the self time to
+//! its span stack. The result is written as folded stacks plus a flamegraph
+//! SVG, for finding what stutters under multi-game load.
+
+use anyhow::{Context as _, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::span::Id;
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::{LookupSpan, SpanRef};
+
+/// Directory profiles are written to, relative to the working directory.
+pub const PROFILE_DIR: &str = "profiles";
+/// Length of one capture.
+pub const PROFILE_DURATION: Duration = Duration::from_secs(10);
+
+#[derive(Default)]
+struct Inner {
+    active: AtomicBool,
+    /// Folded span stack -> self time in microseconds.
+    stacks: Mutex<HashMap<String, i64>>,
+}
+
+/// Handle shared by the tracing layer and whoever starts captures.
+#[derive(Clone, Default)]
+pub struct Profiler {
+    inner: Arc<Inner>,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The layer to install in the subscriber. It only sees spans, and does
+    /// nothing but an atomic load while no capture is running.
+    pub fn layer<S>(&self) -> impl Layer<S>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        ProfileLayer {
+            inner: self.inner.clone(),
+        }
+        .with_filter(tracing_subscriber::filter::filter_fn(|meta| meta.is_span()))
+    }
+
+    /// Begin a capture. `false` if one is already running.
+    pub fn start(&self) -> bool {
+        if self.inner.active.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+        if let Ok(mut stacks) = self.inner.stacks.lock() {
+            stacks.clear();
+        }
+        true
+    }
+
+    /// End the capture and return its folded stacks, heaviest first.
+    pub fn finish(&self) -> Vec<(String, u64)> {
+        self.inner.active.store(false, Ordering::SeqCst);
+        let stacks = self
+            .inner
+            .stacks
+            .lock()
+            .map(|mut s| std::mem::take(&mut *s))
+            .unwrap_or_default();
+        let mut folded: Vec<(String, u64)> = stacks
+            .into_iter()
+            .filter(|&(_, micros)| micros > 0)
+            .map(|(stack, micros)| (stack, micros as u64))
+            .collect();
+        folded.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        folded
+    }
+}
+
+/// When the span was last entered, kept in its extensions.
+struct EnteredAt(Instant);
+
+struct ProfileLayer {
+    inner: Arc<Inner>,
+}
+
+fn stack_of<S>(span: &SpanRef<'_, S>) -> String
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    span.scope()
+        .from_root()
+        .map(|s| s.name())
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+impl<S> Layer<S> for ProfileLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if !self.inner.active.load(Ordering::Relaxed) {
+            return;
+        }
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().replace(EnteredAt(Instant::now()));
+        }
+    }
+
+    /// Charge the time since entry to this span's stack and take it back
+    /// from the parent's, so each stack holds self time as folded stacks
+    /// expect.
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let Some(EnteredAt(at)) = span.extensions_mut().remove::<EnteredAt>() else {
+            return;
+        };
+        if !self.inner.active.load(Ordering::Relaxed) {
+            return;
+        }
+        let micros = at.elapsed().as_micros() as i64;
+        let Ok(mut stacks) = self.inner.stacks.lock() else {
+            return;
+        };
+        *stacks.entry(stack_of(&span)).or_default() += micros;
+        if let Some(parent) = span.parent() {
+            *stacks.entry(stack_of(&parent)).or_default() -= micros;
+        }
+    }
+}
+
+/// Write `<dir>/flame-<timestamp>.folded` and, when anything was captured,
+/// the matching `.svg`. Returns the path of the SVG, or of the folded file
+/// for an empty capture.
+pub fn write(
+    folded: &[(String, u64)],
+    dir: &Path,
+    now: chrono::DateTime<chrono::Local>,
+    duration: Duration,
+) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let stem = dir.join(format!("flame-{}", now.format("%Y%m%d-%H%M%S")));
+    let lines: Vec<String> = folded
+        .iter()
+        .map(|(stack, micros)| format!("{} {}", stack, micros))
+        .collect();
+
+    let folded_path = stem.with_extension("folded");
+    std::fs::write(&folded_path, lines.join("\n") + "\n")
+        .with_context(|| format!("failed to write {}", folded_path.display()))?;
+    if lines.is_empty() {
+        return Ok(folded_path);
+    }
+
+    let svg_path = stem.with_extension("svg");
+    let file = std::fs::File::create(&svg_path)
+        .with_context(|| format!("failed to create {}", svg_path.display()))?;
+    let mut options = inferno::flamegraph::Options::default();
+    options.title = format!(
+        "kalshi-arb engine, {}s from {}",
+        duration.as_secs(),
+        now.format("%H:%M:%S")
+    );
+    options.count_name = "us".to_string();
+    inferno::flamegraph::from_lines(
+        &mut options,
+        lines.iter().map(String::as_str),
+        std::io::BufWriter::new(file),
+    )
+    .map_err(|e| anyhow::anyhow!("failed to render flamegraph: {}", e))?;
+    Ok(svg_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_captures_self_time_per_stack() {
+        let profiler = Profiler::new();
+        let subscriber = tracing_subscriber::registry().with(profiler.layer());
+        tracing::subscriber::with_default(subscriber, || {
+            let outer = tracing::info_span!("engine_cycle");
+            // Nothing is recorded before a capture starts
+            outer.in_scope(|| std::thread::sleep(Duration::from_millis(2)));
+            assert!(profiler.start());
+            assert!(!profiler.start());
+            outer.in_scope(|| {
+                std::thread::sleep(Duration::from_millis(5));
+                tracing::info_span!("evaluate").in_scope(|| {
+                    std::thread::sleep(Duration::from_millis(20));
+                });
+            });
+        });
+
+        let folded: HashMap<String, u64> = profiler.finish().into_iter().collect();
+        assert_eq!(folded.len(), 2);
+        let inner = folded["engine_cycle;evaluate"];
+        let outer = folded["engine_cycle"];
+        assert!(inner >= 20_000);
+        // The child's time is not double-counted in the parent
+        assert!((5_000..inner).contains(&outer), "outer self time {}", outer);
+        assert!(profiler.start());
+    }
+
+    #[test]
+    fn test_writes_folded_and_svg() {
+        let dir = std::env::temp_dir().join(format!("kalshi-arb-profile-{}", std::process::id()));
+        let now = chrono::Local::now();
+        let folded = vec![
+            ("engine;pipeline_tick".to_string(), 900),
+            ("engine".to_string(), 100),
+        ];
+        let svg = write(&folded, &dir, now, PROFILE_DURATION).unwrap();
+        assert_eq!(svg.extension().unwrap(), "svg");
+        assert!(std::fs::read_to_string(&svg)
+            .unwrap()
+            .contains("pipeline_tick"));
+        let text = std::fs::read_to_string(svg.with_extension("folded")).unwrap();
+        assert_eq!(text, "engine;pipeline_tick 900\nengine 100\n");
+
+        let empty = write(&[], &dir, now, PROFILE_DURATION).unwrap();
+        assert_eq!(empty.extension().unwrap(), "folded");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,159 @@
+//! Feed capture for `--record` / `[recorder] enabled`.
+//!
+//! Every Kalshi market listing, odds poll, score poll and orderbook update is
+//! written as a [`RecordedEvent`] line to a per-session capture directory
+//! (`<dir>/<YYYYMMDD-HHMMSS>/`), one JSONL file per kind. The directory can be
+//! passed straight to `--backtest` or kept to reproduce a bug.
+
+use crate::backtest::{RecordedData, RecordedEvent};
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+
+/// Cheaply cloneable capture handle. A disabled recorder drops all events.
+#[derive(Clone, Default)]
+pub struct FeedRecorder {
+    tx: Option<mpsc::Sender<RecordedEvent>>,
+}
+
+impl FeedRecorder {
+    /// Create a session directory under `dir` and start the writer thread.
+    /// Returns the recorder and the session directory.
+    pub fn start(dir: &Path) -> Result<(Self, PathBuf)> {
+        let session = dir.join(Utc::now().format("%Y%m%d-%H%M%S").to_string());
+        std::fs::create_dir_all(&session)
+            .with_context(|| format!("failed to create {}", session.display()))?;
+        let (tx, rx) = mpsc::channel();
+        let writer_dir = session.clone();
+        std::thread::Builder::new()
+            .name("feed-recorder".to_string())
+            .spawn(move || write_events(&writer_dir, rx))
+            .context("failed to start feed recorder")?;
+        Ok((Self { tx: Some(tx) }, session))
+    }
+
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.tx.is_some()
+    }
+
+    /// Capture `data`, timestamped now. Never blocks on disk.
+    pub fn record(&self, data: RecordedData) {
+        if let Some(tx) = &self.tx {
+            let _ = tx.send(RecordedEvent {
+                at: Utc::now(),
+                data,
+            });
+        }
+    }
+}
+
+/// Capture file for each kind of event.
+fn file_name(data: &RecordedData) -> &'static str {
+    match data {
+        RecordedData::Markets { .. } => "markets.jsonl",
+        RecordedData::Book { .. } => "books.jsonl",
+        RecordedData::Odds { .. } => "odds.jsonl",
+        RecordedData::Scores { .. } => "scores.jsonl",
+        RecordedData::Settlement { .. } => "settlements.jsonl",
+    }
+}
+
+/// Writer loop: append events as they arrive, flushing whenever the queue drains.
+fn write_events(dir: &Path, rx: mpsc::Receiver<RecordedEvent>) {
+    let mut files: HashMap<&'static str, BufWriter<File>> = HashMap::new();
+    while let Ok(first) = rx.recv() {
+        for event in std::iter::once(first).chain(rx.try_iter()) {
+            if let Err(e) = write_event(dir, &mut files, &event) {
+                tracing::warn!(error = %e, "failed to write recorded event");
+            }
+        }
+        for file in files.values_mut() {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Write `events` to `dir` as one complete capture, in the same layout as a
+/// recorded session. Used by the historical downloader.
+pub fn write_capture(dir: &Path, events: &[RecordedEvent]) -> Result<()> {
+    std::fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let mut files = HashMap::new();
+    for event in events {
+        write_event(dir, &mut files, event)?;
+    }
+    for file in files.values_mut() {
+        file.flush()?;
+    }
+    Ok(())
+}
+
+fn write_event(
+    dir: &Path,
+    files: &mut HashMap<&'static str, BufWriter<File>>,
+    event: &RecordedEvent,
+) -> Result<()> {
+    let name = file_name(&event.data);
+    let file = match files.entry(name) {
+        std::collections::hash_map::Entry::Occupied(e) => e.into_mut(),
+        std::collections::hash_map::Entry::Vacant(e) => {
+            let path = dir.join(name);
+            let file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .with_context(|| format!("failed to open {}", path.display()))?;
+            e.insert(BufWriter::new(file))
+        }
+    };
+    serde_json::to_writer(&mut *file, event)?;
+    file.write_all(b"\n")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::BookSnapshot;
+
+    #[test]
+    fn test_recorded_session_replays_through_backtest_loader() {
+        let dir = std::env::temp_dir().join(format!("recorder-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let (recorder, session) = FeedRecorder::start(&dir).unwrap();
+        recorder.record(RecordedData::Book {
+            ticker: "T-1".to_string(),
+            book: BookSnapshot {
+                yes: vec![(52, 10)],
+                no: vec![(46, 5)],
+            },
+            update: "SNAPSHOT".to_string(),
+        });
+        recorder.record(RecordedData::Odds {
+            sport: "basketball".to_string(),
+            updates: Vec::new(),
+        });
+        drop(recorder);
+
+        // The writer thread exits once every handle is dropped and the queue drains.
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        let mut events = Vec::new();
+        while events.len() < 2 && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            events = crate::backtest::load_events(&session).unwrap_or_default();
+        }
+        assert_eq!(events.len(), 2);
+        assert!(session.join("books.jsonl").exists());
+        assert!(session.join("odds.jsonl").exists());
+        assert!(!FeedRecorder::disabled().is_enabled());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,564 @@
+//! Session replay from a trade journal.
+//!
+//! `--replay <journal>` rebuilds one day's session from the journal and
+//! plays it back into the TUI: trades fill and close positions, orders,
+//! rejects, notes and settlements go to the log, and equity samples resync
+//! the balance and redraw the equity curve. Events play at their recorded
+//! pace times `--replay-speed`, with idle stretches cut to `MAX_GAP`. `p`
+//! pauses, `r` resumes and `.` steps one event at a time.
+
+use crate::engine::fees::calculate_fee;
+use crate::journal::Journal;
+use crate::locale::NumberFormat;
+use crate::portfolio::{self, EntryOrder, PortfolioSummary};
+use crate::tui::state::{AppState, LogEntry, PositionRow, SimPosition, TradeRow};
+use crate::tui::{self, TuiCommand};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, watch};
+
+/// Longest real-time wait between two events, whatever the recorded gap.
+const MAX_GAP: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone)]
+pub enum ReplayKind {
+    Trade(TradeRow),
+    Order {
+        order: EntryOrder,
+        message: String,
+    },
+    Log {
+        level: &'static str,
+        message: String,
+    },
+    Equity {
+        equity_cents: i64,
+        balance_cents: i64,
+    },
+}
+
+/// One journaled event, in the order it happened.
+#[derive(Debug, Clone)]
+pub struct ReplayEvent {
+    pub ts: DateTime<Utc>,
+    pub kind: ReplayKind,
+}
+
+/// One day's events in one mode, sim or live.
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub date: NaiveDate,
+    pub sim: bool,
+    pub events: Vec<ReplayEvent>,
+}
+
+/// The event for a journal entry, with its mode when it has one.
+fn entry_event(body: &serde_json::Value) -> Option<(ReplayEvent, Option<bool>)> {
+    let ts = DateTime::parse_from_rfc3339(body["ts"].as_str()?)
+        .ok()?
+        .with_timezone(&Utc);
+    let ticker = body["ticker"].as_str().unwrap_or_default();
+    let sim = body["sim"].as_bool();
+    let kind = match body["kind"].as_str()? {
+        "order" => ReplayKind::Order {
+            order: EntryOrder::from_journal(body)?,
+            message: format!(
+                "ORDER {} {} {} {}x @ {}c",
+                body["action"].as_str().unwrap_or_default(),
+                body["side"].as_str().unwrap_or_default(),
+                ticker,
+                body["quantity"],
+                body["price"]
+            ),
+        },
+        "reject" => ReplayKind::Log {
+            level: "WARN",
+            message: format!(
+                "REJECT {}: {}",
+                ticker,
+                body["reason"].as_str().unwrap_or_default()
+            ),
+        },
+        "note" => ReplayKind::Log {
+            level: "INFO",
+            message: format!(
+                "NOTE {} {}: {}",
+                body["action"].as_str().unwrap_or_default(),
+                ticker,
+                body["note"].as_str().unwrap_or_default()
+            ),
+        },
+        "settlement" => ReplayKind::Log {
+            level: "INFO",
+            message: format!(
+                "SETTLED {}: {}",
+                ticker,
+                body["result"].as_str().unwrap_or_default()
+            ),
+        },
+        "equity" => ReplayKind::Equity {
+            equity_cents: body["equity_cents"].as_i64()?,
+            balance_cents: body["balance_cents"].as_i64()?,
+        },
+        _ => return None,
+    };
+    Some((ReplayEvent { ts, kind }, sim))
+}
+
+/// Build the session for `date` (local), or for the journal's latest day.
+/// A day with both modes replays the mode of its last event.
+pub fn load_session(journal: &Journal, date: Option<NaiveDate>) -> Result<Session> {
+    let mut events: Vec<(ReplayEvent, Option<bool>)> = journal
+        .all_entries()?
+        .iter()
+        .filter_map(entry_event)
+        .collect();
+    events.extend(journal.stored_trades()?.into_iter().map(|t| {
+        (
+            ReplayEvent {
+                ts: t.ts,
+                kind: ReplayKind::Trade(t.trade),
+            },
+            Some(t.sim),
+        )
+    }));
+    events.sort_by_key(|(e, _)| e.ts);
+
+    let local_date = |e: &ReplayEvent| e.ts.with_timezone(&Local).date_naive();
+    let date = match date {
+        Some(date) => date,
+        None => events
+            .last()
+            .map(|(e, _)| local_date(e))
+            .context("the journal has no events to replay")?,
+    };
+    events.retain(|(e, _)| local_date(e) == date);
+    let sim = events
+        .iter()
+        .rev()
+        .find_map(|(_, sim)| *sim)
+        .with_context(|| format!("no trades or orders journaled on {}", date))?;
+    Ok(Session {
+        date,
+        sim,
+        events: events
+            .into_iter()
+            .filter(|(_, mode)| mode.is_none_or(|m| m == sim))
+            .map(|(e, _)| e)
+            .collect(),
+    })
+}
+
+/// Plays a session into an `AppState`, one event per step.
+pub struct Replayer {
+    session: Session,
+    cursor: usize,
+    orders: Vec<EntryOrder>,
+    closed: Vec<TradeRow>,
+}
+
+impl Replayer {
+    pub fn new(session: Session) -> Self {
+        Self {
+            session,
+            cursor: 0,
+            orders: Vec::new(),
+            closed: Vec::new(),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.cursor >= self.session.events.len()
+    }
+
+    /// Real time to wait before the next event at `speed`x, or `None` once
+    /// every event has played.
+    pub fn next_delay(&self, speed: f64) -> Option<Duration> {
+        let next = self.session.events.get(self.cursor)?;
+        let Some(prev) = self.cursor.checked_sub(1).map(|i| &self.session.events[i]) else {
+            return Some(Duration::ZERO);
+        };
+        let gap = (next.ts - prev.ts).to_std().unwrap_or_default();
+        Some(gap.div_f64(speed).min(MAX_GAP))
+    }
+
+    /// Apply the next event to `state`. Returns false once finished.
+    pub fn step(&mut self, state: &mut AppState) -> bool {
+        let Some(event) = self.session.events.get(self.cursor) else {
+            return false;
+        };
+        self.cursor += 1;
+        match &event.kind {
+            ReplayKind::Trade(trade) => {
+                apply_trade(state, trade, event.ts);
+                if trade.pnl.is_some() {
+                    self.closed.push(trade.clone());
+                }
+            }
+            ReplayKind::Order { order, message } => {
+                self.orders.push(order.clone());
+                log_at(state, event.ts, "INFO", message.clone());
+            }
+            ReplayKind::Log { level, message } => log_at(state, event.ts, level, message.clone()),
+            ReplayKind::Equity {
+                equity_cents,
+                balance_cents,
+            } => {
+                if state.sim_mode {
+                    state.sim_balance_cents = *balance_cents;
+                } else {
+                    state.balance_cents = *balance_cents;
+                }
+                state.equity.record(*equity_cents, Instant::now());
+            }
+        }
+        if self.is_finished() {
+            log_at(state, event.ts, "INFO", "Replay finished".to_string());
+        }
+        true
+    }
+
+    /// Portfolio summary of what has played so far.
+    pub fn portfolio(&self, state: &AppState) -> PortfolioSummary {
+        let open: Vec<(String, i64)> = if state.sim_mode {
+            state
+                .sim_positions
+                .iter()
+                .map(|p| (p.ticker.clone(), 0))
+                .collect()
+        } else {
+            state
+                .positions
+                .iter()
+                .map(|p| (p.ticker.clone(), p.unrealized_pnl as i64))
+                .collect()
+        };
+        portfolio::summarize(&self.orders, &self.closed, &open)
+    }
+}
+
+/// Push a log line stamped with the event's recorded time.
+fn log_at(state: &mut AppState, ts: DateTime<Utc>, level: &str, message: String) {
+    if state.logs.len() >= 200 {
+        state.logs.pop_front();
+    }
+    state.logs.push_back(LogEntry {
+        time: ts.with_timezone(&Local).format("%H:%M:%S%.3f").to_string(),
+        level: level.to_string(),
+        message,
+    });
+}
+
+/// Book a journaled trade: buys open or add to a position, rows with a P&L
+/// close contracts and realize it. The balance follows in between equity
+/// samples.
+fn apply_trade(state: &mut AppState, trade: &TradeRow, ts: DateTime<Utc>) {
+    let sim = state.sim_mode;
+    match trade.pnl {
+        None if trade.action.ends_with("BUY") => {
+            let fee = calculate_fee(trade.price, trade.quantity, trade.order_type == "TAKER");
+            let cost = (trade.price * trade.quantity + fee) as i64;
+            if sim {
+                state.sim_balance_cents -= cost;
+                match state
+                    .sim_positions
+                    .iter_mut()
+                    .find(|p| p.ticker == trade.ticker)
+                {
+                    Some(p) => p.add_lot(trade.quantity, trade.price, fee),
+                    None => state.sim_positions.push(SimPosition {
+                        ticker: trade.ticker.clone(),
+                        // Trade rows don't record the side
+                        side: String::new(),
+                        quantity: trade.quantity,
+                        entry_price: trade.price,
+                        sell_price: 0,
+                        entry_fee: fee,
+                        filled_at: Instant::now(),
+                        signal_ask: trade.price,
+                        trace: None,
+                    }),
+                }
+            } else {
+                state.balance_cents -= cost;
+                match state
+                    .positions
+                    .iter_mut()
+                    .find(|p| p.ticker == trade.ticker)
+                {
+                    Some(p) => {
+                        let total = p.quantity * p.entry_price + trade.quantity * trade.price;
+                        p.quantity += trade.quantity;
+                        p.entry_price = total / p.quantity;
+                        p.entry_fee += fee + total % p.quantity;
+                    }
+                    None => state.positions.push(PositionRow {
+                        ticker: trade.ticker.clone(),
+                        quantity: trade.quantity,
+                        entry_price: trade.price,
+                        sell_price: 0,
+                        entry_fee: fee,
+                        unrealized_pnl: 0,
+                    }),
+                }
+            }
+        }
+        None => {}
+        Some(pnl) => {
+            let pnl = pnl as i64;
+            let cost = close_contracts(state, &trade.ticker, trade.quantity);
+            if sim {
+                state.sim_balance_cents += cost + pnl;
+            } else {
+                state.balance_cents += cost + pnl;
+            }
+            state.realized_pnl_cents += pnl;
+            state.total_trades += 1;
+            if pnl > 0 {
+                state.winning_trades += 1;
+            }
+        }
+    }
+    state.push_trade(trade.clone());
+    let pnl = trade
+        .pnl
+        .map_or(String::new(), |p| format!(", P&L: {:+}c", p));
+    log_at(
+        state,
+        ts,
+        "TRADE",
+        format!(
+            "{} {}x {} @ {}c{}",
+            trade.action, trade.quantity, trade.ticker, trade.price, pnl
+        ),
+    );
+}
+
+/// Take `quantity` contracts off the held position in `ticker`, returning
+/// their share of its cost.
+fn close_contracts(state: &mut AppState, ticker: &str, quantity: u32) -> i64 {
+    let take = |held: &mut u32, entry_price: u32, entry_fee: &mut u32| {
+        let closing = quantity.min(*held);
+        if closing == 0 {
+            return 0;
+        }
+        let fee_share = *entry_fee * closing / *held;
+        *held -= closing;
+        *entry_fee -= fee_share;
+        (closing * entry_price + fee_share) as i64
+    };
+    if state.sim_mode {
+        let Some(idx) = state.sim_positions.iter().position(|p| p.ticker == ticker) else {
+            return 0;
+        };
+        let p = &mut state.sim_positions[idx];
+        let cost = take(&mut p.quantity, p.entry_price, &mut p.entry_fee);
+        if p.quantity == 0 {
+            state.sim_positions.remove(idx);
+        }
+        cost
+    } else {
+        let Some(idx) = state.positions.iter().position(|p| p.ticker == ticker) else {
+            return 0;
+        };
+        let p = &mut state.positions[idx];
+        let cost = take(&mut p.quantity, p.entry_price, &mut p.entry_fee);
+        if p.quantity == 0 {
+            state.positions.remove(idx);
+        }
+        cost
+    }
+}
+
+/// Replay the session in the journal at `path` into the TUI until quit.
+pub async fn run(
+    path: &Path,
+    speed: f64,
+    date: Option<NaiveDate>,
+    number_format: NumberFormat,
+) -> Result<()> {
+    anyhow::ensure!(speed > 0.0, "--replay-speed must be positive");
+    anyhow::ensure!(path.exists(), "journal {} not found", path.display());
+    let session = load_session(&Journal::open(path)?, date)?;
+
+    let mut state = AppState::new();
+    state.sim_mode = session.sim;
+    state.number_format = number_format;
+    state.odds_source = format!("REPLAY {}x", speed);
+    log_at(
+        &mut state,
+        Utc::now(),
+        "INFO",
+        format!(
+            "Replaying {} ({} {}, {} events) at {}x: [p]ause, [r]esume, [.] step",
+            path.display(),
+            session.date,
+            if session.sim { "sim" } else { "live" },
+            session.events.len(),
+            speed
+        ),
+    );
+    let (state_tx, state_rx) = watch::channel(state);
+    let (cmd_tx, mut cmd_rx) = mpsc::channel::<TuiCommand>(16);
+    let tui = tokio::spawn(tui::run_tui(state_rx, cmd_tx));
+
+    let mut replayer = Replayer::new(session);
+    let mut paused = false;
+    loop {
+        let delay = if paused {
+            None
+        } else {
+            replayer.next_delay(speed)
+        };
+        tokio::select! {
+            cmd = cmd_rx.recv() => match cmd {
+                None | Some(TuiCommand::Quit) | Some(TuiCommand::KillSwitch) => break,
+                Some(TuiCommand::Pause) => {
+                    paused = true;
+                    state_tx.send_modify(|s| s.is_paused = true);
+                }
+                Some(TuiCommand::Resume) => {
+                    paused = false;
+                    state_tx.send_modify(|s| s.is_paused = false);
+                }
+                Some(TuiCommand::StepReplay) => {
+                    state_tx.send_if_modified(|s| replayer.step(s));
+                }
+                Some(TuiCommand::OpenPortfolio) => {
+                    state_tx.send_modify(|s| s.portfolio = Some(replayer.portfolio(s)));
+                }
+                Some(TuiCommand::ClosePortfolio) => {
+                    state_tx.send_modify(|s| s.portfolio = None);
+                }
+                // Everything else needs a running engine
+                Some(_) => {}
+            },
+            _ = tokio::time::sleep(delay.unwrap_or_default()), if delay.is_some() => {
+                state_tx.send_if_modified(|s| replayer.step(s));
+            }
+        }
+    }
+    tui.await.context("TUI task failed")?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::journal::JournalEntry;
+
+    fn trade(action: &str, ticker: &str, price: u32, quantity: u32, pnl: Option<i32>) -> TradeRow {
+        TradeRow {
+            id: 0,
+            time: "20:00:00".to_string(),
+            action: action.to_string(),
+            ticker: ticker.to_string(),
+            price,
+            quantity,
+            order_type: "MAKER".to_string(),
+            pnl,
+            slippage: None,
+            source: String::new(),
+            fair_value_basis: String::new(),
+            phase: String::new(),
+            note: String::new(),
+        }
+    }
+
+    fn session(events: Vec<(i64, ReplayKind)>) -> Session {
+        let start: DateTime<Utc> = "2026-10-18T00:00:00Z".parse().unwrap();
+        Session {
+            date: start.date_naive(),
+            sim: true,
+            events: events
+                .into_iter()
+                .map(|(secs, kind)| ReplayEvent {
+                    ts: start + chrono::Duration::seconds(secs),
+                    kind,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_replay_opens_and_closes_positions() {
+        let mut state = AppState::new();
+        state.sim_mode = true;
+        let start_balance = state.sim_balance_cents;
+        let mut replayer = Replayer::new(session(vec![
+            (0, ReplayKind::Trade(trade("BUY", "T", 40, 10, None))),
+            (10, ReplayKind::Trade(trade("SELL", "T", 50, 4, Some(38)))),
+            (
+                90,
+                ReplayKind::Equity {
+                    equity_cents: 10_000,
+                    balance_cents: 9_800,
+                },
+            ),
+        ]));
+
+        assert_eq!(replayer.next_delay(10.0), Some(Duration::ZERO));
+        assert!(replayer.step(&mut state));
+        let fee = calculate_fee(40, 10, false) as i64;
+        assert_eq!(state.sim_positions[0].quantity, 10);
+        assert_eq!(state.sim_balance_cents, start_balance - 400 - fee);
+
+        assert_eq!(replayer.next_delay(10.0), Some(Duration::from_secs(1)));
+        assert!(replayer.step(&mut state));
+        assert_eq!(state.sim_positions[0].quantity, 6);
+        assert_eq!(state.realized_pnl_cents, 38);
+        assert_eq!((state.total_trades, state.winning_trades), (1, 1));
+        assert_eq!(state.trades.len(), 2);
+        assert_eq!(replayer.portfolio(&state).total.realized_cents, 38);
+
+        // An 80s gap is cut to MAX_GAP
+        assert_eq!(replayer.next_delay(10.0), Some(MAX_GAP));
+        assert!(replayer.step(&mut state));
+        assert_eq!(state.sim_balance_cents, 9_800);
+        assert_eq!(state.equity.stats().unwrap().current_cents, 10_000);
+        assert!(replayer.is_finished());
+        assert!(!replayer.step(&mut state));
+        assert_eq!(replayer.next_delay(10.0), None);
+    }
+
+    #[test]
+    fn test_load_session_picks_latest_day_and_mode() {
+        let dir = std::env::temp_dir().join(format!("session-replay-{}", std::process::id()));
+        let path = dir.join("journal.db");
+        let _ = std::fs::remove_dir_all(&dir);
+        let journal = Journal::open(&path).unwrap();
+        journal.record_trade(&trade("BUY", "SIM-T", 40, 1, None), true);
+        journal.record_trade(&trade("BUY", "LIVE-T", 40, 1, None), false);
+        journal.record(&JournalEntry::Equity {
+            ts: Utc::now(),
+            sim: true,
+            equity_cents: 100,
+            balance_cents: 100,
+            positions_cents: 0,
+        });
+        journal.record(&JournalEntry::Settlement {
+            ts: Utc::now(),
+            series: "S".to_string(),
+            ticker: "LIVE-T".to_string(),
+            event_ticker: "E".to_string(),
+            title: String::new(),
+            result: "yes".to_string(),
+            settlement_value: 100,
+            last_price: 99,
+            close_time: None,
+        });
+
+        // The last mode-bearing event is the sim equity sample
+        let s = load_session(&journal, None).unwrap();
+        assert!(s.sim);
+        assert_eq!(s.date, Local::now().date_naive());
+        assert_eq!(s.events.len(), 3);
+
+        let yesterday = s.date.pred_opt().unwrap();
+        assert!(load_session(&journal, Some(yesterday)).is_err());
+        assert!(load_session(&Journal::disabled(), None).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,238 @@
+//! The engine loop's settlement stage: closed markets still holding a
+//! position are watched until their result is in, then the position is
+//! booked at it, or at its last fair value once the watch times out.
+
+use crate::journal::Journal;
+use crate::kalshi::rest::KalshiRest;
+use crate::live::LiveSession;
+use crate::pipeline::SettleBasis;
+use crate::polymarket::rest::PolymarketRest;
+use crate::settlements::{self, SettlementWatch};
+use crate::tui::state::{AppState, SimPosition, TradeRow};
+use futures_util::StreamExt;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::watch;
+
+/// Concurrent market lookups when checking closed markets for their result.
+const SETTLEMENT_CHECK_CONCURRENCY: usize = 8;
+
+/// Watch the markets that closed this cycle and still hold a position, live
+/// or simulated.
+pub fn watch_closed(
+    settlement_watch: &mut SettlementWatch,
+    closed: &[(String, u32, SettleBasis)],
+    live: Option<&LiveSession>,
+    sim_positions: &[SimPosition],
+    now: Instant,
+) {
+    for (ticker, fair, basis) in closed {
+        let held = sim_positions.iter().any(|p| &p.ticker == ticker)
+            || live.is_some_and(|l| l.position_tracker.get(ticker).is_some());
+        if held {
+            settlement_watch.watch(ticker, *fair, basis.label(), now);
+        }
+    }
+}
+
+/// Check the watched markets that are due and settle those with a result;
+/// sim positions that waited too long settle at their last fair value.
+/// Positions held on Polymarket settle on their token's market.
+pub async fn settle_due(
+    settlement_watch: &mut SettlementWatch,
+    rest: &Arc<KalshiRest>,
+    polymarket: Option<&Arc<PolymarketRest>>,
+    mut live: Option<&mut LiveSession>,
+    journal: &Journal,
+    state_tx: &watch::Sender<AppState>,
+) {
+    // Due markets are fetched concurrently so a slate of closes
+    // doesn't stall the cycle one request at a time. Each yields the
+    // YES value and, for a Kalshi result, its journal entry.
+    let checks: Vec<_> =
+        futures_util::stream::iter(settlement_watch.due(Instant::now()).into_iter().map(
+            |ticker| {
+                let rest = rest.clone();
+                let venue_leg = live
+                    .as_ref()
+                    .and_then(|l| l.position_tracker.get(&ticker))
+                    .and_then(|p| Some((p.venue_ticker.clone()?, p.side.clone())))
+                    .zip(polymarket.cloned());
+                async move {
+                    let settled = match venue_leg {
+                        Some(((token, side), poly)) => {
+                            poly.get_market_by_token(&token).await.map(|m| {
+                                m.and_then(|m| {
+                                    settlements::polymarket_settlement_value(&m, &token, &side)
+                                })
+                                .map(|value| (value, None))
+                            })
+                        }
+                        None => rest.get_market(&ticker).await.map(|market| {
+                            let entry = settlements::settlement_entry(
+                                settlements::series_of(&market),
+                                &market,
+                                chrono::Utc::now(),
+                            )?;
+                            Some((settlements::settlement_value(&market)?, Some(entry)))
+                        }),
+                    };
+                    (ticker, settled)
+                }
+            },
+        ))
+        .buffer_unordered(SETTLEMENT_CHECK_CONCURRENCY)
+        .collect()
+        .await;
+    for (ticker, settled) in checks {
+        let settled = match settled {
+            Ok(s) => s,
+            Err(e) => {
+                tracing::warn!(ticker = %ticker, error = %e, "settlement check failed");
+                continue;
+            }
+        };
+        let Some((value, entry)) = settled else {
+            continue;
+        };
+        if let Some(entry) = entry {
+            journal.record(&entry);
+        }
+        settlement_watch.resolve(&ticker);
+        match live.as_deref_mut() {
+            Some(live) => live.settle(&ticker, value, state_tx),
+            None => state_tx.send_modify(|s| settle_sim_position(s, &ticker, value, None)),
+        }
+    }
+    // No result in time: settle at last known fair value
+    for (ticker, fair, basis) in settlement_watch.expired(Instant::now()) {
+        tracing::warn!(ticker = %ticker, basis, "no settlement result, using fallback price");
+        if live.is_none() {
+            state_tx.send_modify(|s| settle_sim_position(s, &ticker, fair, Some(basis)));
+        }
+    }
+}
+
+/// Settle a sim position on a closed market at `price`: the market's result
+/// (no fee), or with a `fallback` basis an estimate charged as a taker exit.
+/// `price` is the YES value; a NO position settles at its complement.
+pub fn settle_sim_position(s: &mut AppState, ticker: &str, price: u32, fallback: Option<&str>) {
+    let Some(idx) = s.sim_positions.iter().position(|p| p.ticker == ticker) else {
+        return;
+    };
+    let pos = s.sim_positions.remove(idx);
+    let price = if pos.side == "no" {
+        100u32.saturating_sub(price)
+    } else {
+        price
+    };
+    let exit_revenue = (pos.quantity * price) as i64;
+    let exit_fee = if fallback.is_some() {
+        crate::engine::fees::calculate_fee(price, pos.quantity, false) as i64
+    } else {
+        0
+    };
+    let entry_cost = (pos.quantity * pos.entry_price) as i64 + pos.entry_fee as i64;
+    let pnl = (exit_revenue - exit_fee) - entry_cost;
+
+    s.sim_balance_cents += exit_revenue - exit_fee;
+    s.realized_pnl_cents += pnl;
+    s.record_realized_edge(&pos.ticker, pnl);
+    s.total_trades += 1;
+    if pnl > 0 {
+        s.winning_trades += 1;
+    }
+    let basis = fallback.unwrap_or("result");
+    s.push_trade(TradeRow {
+        id: 0,
+        time: chrono::Local::now().format("%H:%M:%S").to_string(),
+        action: "SETTLE".to_string(),
+        ticker: pos.ticker.clone(),
+        price,
+        quantity: pos.quantity,
+        order_type: "SIM".to_string(),
+        pnl: Some(pnl as i32),
+        slippage: None,
+        source: String::new(),
+        fair_value_basis: basis.to_string(),
+        phase: crate::feed::score_feed::GamePhase::Final.label(),
+        note: String::new(),
+    });
+    s.push_log(
+        "TRADE",
+        format!(
+            "SIM SETTLE {}x {} @ {}c ({}), P&L: {:+}c",
+            pos.quantity, pos.ticker, price, basis, pnl
+        ),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ExecutionConfig, RiskConfig};
+    use crate::engine::fees::calculate_fee;
+    use std::time::Duration;
+
+    fn sim_position(ticker: &str, side: &str) -> SimPosition {
+        SimPosition {
+            ticker: ticker.to_string(),
+            side: side.to_string(),
+            quantity: 2,
+            entry_price: 40,
+            sell_price: 50,
+            entry_fee: 2,
+            filled_at: Instant::now(),
+            signal_ask: 40,
+            trace: None,
+        }
+    }
+
+    #[test]
+    fn test_watches_only_closed_markets_still_held() {
+        let mut watch = SettlementWatch::new(Duration::from_secs(30), Duration::from_secs(600));
+        let closed = |ticker: &str| (ticker.to_string(), 60, SettleBasis::FairValue);
+        let now = Instant::now();
+
+        watch_closed(
+            &mut watch,
+            &[closed("SIM"), closed("LIVE"), closed("NONE")],
+            None,
+            &[sim_position("SIM", "yes")],
+            now,
+        );
+        assert_eq!(watch.due(now), ["SIM"]);
+
+        let mut live = LiveSession::new(&RiskConfig::default(), &ExecutionConfig::default());
+        live.position_tracker
+            .record_entry("LIVE".to_string(), "yes", 1, 40, 41, 50, now, true);
+        watch_closed(
+            &mut watch,
+            &[closed("LIVE"), closed("NONE")],
+            Some(&live),
+            &[],
+            now,
+        );
+        assert_eq!(watch.due(now), ["LIVE"]);
+    }
+
+    #[test]
+    fn test_sim_settlement_at_result_and_fallback() {
+        let mut s = AppState::new();
+        s.sim_positions.push(sim_position("A", "no"));
+        s.sim_positions.push(sim_position("B", "yes"));
+        let balance = s.sim_balance_cents;
+
+        // A NO position settles at the complement of the YES result, fee-free
+        settle_sim_position(&mut s, "A", 0, None);
+        assert_eq!(s.realized_pnl_cents, 2 * 100 - (2 * 40 + 2));
+        assert_eq!(s.sim_balance_cents, balance + 200);
+
+        // A fallback estimate is charged as a taker exit
+        settle_sim_position(&mut s, "B", 70, Some("fair value"));
+        let fee = calculate_fee(70, 2, false) as i64;
+        assert_eq!(s.trades.back().unwrap().fair_value_basis, "fair value");
+        assert_eq!(s.sim_balance_cents, balance + 200 + 140 - fee);
+        assert!(s.sim_positions.is_empty());
+    }
+}
@@ -0,0 +1,347 @@
+//! Historical settlement loader.
+//!
+//! `--load-settlements[=MONTHS]` downloads settled Kalshi markets for every
+//! configured series and records their final outcomes in the journal, giving
+//! ground truth for win-prob calibration and backtest validation. Tickers
+//! already in the journal are skipped, so the loader can be re-run to top up.
+//!
+//! While the engine runs, `SettlementWatch` tracks closed markets that still
+//! hold positions until Kalshi posts their result, or, for positions held
+//! on Polymarket, until their token's market resolves.
+
+use crate::journal::{Journal, JournalEntry};
+use crate::kalshi::rest::KalshiRest;
+use crate::kalshi::types::{dollars_to_cents, Market};
+use crate::polymarket::types::GammaMarket;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Lookback when `--load-settlements` is given without a month count.
+pub const DEFAULT_MONTHS: u32 = 3;
+
+#[derive(Debug, Default)]
+pub struct LoadSummary {
+    pub fetched: usize,
+    pub recorded: usize,
+    pub already_recorded: usize,
+    /// Settled markets without a yes/no result (e.g. voided).
+    pub no_result: usize,
+}
+
+/// A market's yes/no result, or `None` until it has one.
+fn market_result(market: &Market) -> Option<&str> {
+    market
+        .result
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| *r == "yes" || *r == "no")
+}
+
+/// What a YES contract paid out (cents), or `None` if the market has no
+/// yes/no result yet.
+pub fn settlement_value(market: &Market) -> Option<u32> {
+    let result = market_result(market)?;
+    Some(match market.settlement_value_dollars.as_deref() {
+        Some(v) if !v.is_empty() => dollars_to_cents(Some(v)),
+        _ if result == "yes" => 100,
+        _ => 0,
+    })
+}
+
+/// What a YES on a Kalshi outcome paid out (cents), for a position on its
+/// `side` held in Polymarket token `token_id`, or `None` until the token's
+/// market has resolved. The token pays out on the side held, so a NO
+/// position's YES value is the complement.
+pub fn polymarket_settlement_value(
+    market: &GammaMarket,
+    token_id: &str,
+    side: &str,
+) -> Option<u32> {
+    let payout = market.token_payout(token_id)?;
+    Some(if side == "no" { 100 - payout } else { payout })
+}
+
+/// Series ticker of a market: its event ticker up to the first dash.
+pub fn series_of(market: &Market) -> &str {
+    market
+        .event_ticker
+        .split('-')
+        .next()
+        .unwrap_or(&market.event_ticker)
+}
+
+/// Journal entry for a settled market, or `None` if it has no yes/no result.
+pub fn settlement_entry(series: &str, market: &Market, now: DateTime<Utc>) -> Option<JournalEntry> {
+    let result = market_result(market)?;
+    let settlement_value = settlement_value(market)?;
+    Some(JournalEntry::Settlement {
+        ts: now,
+        series: series.to_string(),
+        ticker: market.ticker.clone(),
+        event_ticker: market.event_ticker.clone(),
+        title: market.title.clone(),
+        result: result.to_string(),
+        settlement_value,
+        last_price: dollars_to_cents(market.last_price_dollars.as_deref()),
+        close_time: market.close_time.clone(),
+    })
+}
+
+/// Download settled markets that closed in the last `months` months for each
+/// series and record the new ones in the journal at `journal_path`.
+pub async fn load_settlements(
+    rest: &KalshiRest,
+    series: &[String],
+    months: u32,
+    journal_path: &Path,
+) -> Result<LoadSummary> {
+    let journal = Journal::open(journal_path)?;
+    let mut recorded = journal.recorded_tickers("settlement")?;
+    let now = Utc::now();
+    let since = now - chrono::Duration::days(30 * months as i64);
+    let mut summary = LoadSummary::default();
+
+    for series_ticker in series {
+        let markets = match rest
+            .get_settled_markets(series_ticker, since.timestamp())
+            .await
+        {
+            Ok(markets) => markets,
+            Err(e) => {
+                println!("  {}: FAILED ({:#})", series_ticker, e);
+                tracing::warn!(series = %series_ticker, error = %e, "settlement fetch failed");
+                continue;
+            }
+        };
+        let before = summary.recorded;
+        summary.fetched += markets.len();
+        for market in &markets {
+            if recorded.contains(&market.ticker) {
+                summary.already_recorded += 1;
+                continue;
+            }
+            match settlement_entry(series_ticker, market, now) {
+                Some(entry) => {
+                    journal.record(&entry);
+                    recorded.insert(market.ticker.clone());
+                    summary.recorded += 1;
+                }
+                None => summary.no_result += 1,
+            }
+        }
+        println!(
+            "  {}: {} settled, {} new",
+            series_ticker,
+            markets.len(),
+            summary.recorded - before
+        );
+    }
+
+    Ok(summary)
+}
+
+#[derive(Debug)]
+struct PendingSettlement {
+    /// Last fair value seen, the fallback price if no result arrives.
+    fair: u32,
+    /// What `fair` was derived from, e.g. "final score" or "closing odds".
+    basis: &'static str,
+    since: Instant,
+    checked: Option<Instant>,
+}
+
+/// Closed markets still holding positions, waiting on Kalshi's result.
+/// Each is re-checked every `recheck`; one without a result after `timeout`
+/// is given up on so its position can be settled at fair value instead.
+#[derive(Debug)]
+pub struct SettlementWatch {
+    pending: HashMap<String, PendingSettlement>,
+    recheck: Duration,
+    timeout: Duration,
+}
+
+impl SettlementWatch {
+    pub fn new(recheck: Duration, timeout: Duration) -> Self {
+        Self {
+            pending: HashMap::new(),
+            recheck,
+            timeout,
+        }
+    }
+
+    /// Start watching `ticker`, or refresh its fallback fair value.
+    pub fn watch(&mut self, ticker: &str, fair: u32, basis: &'static str, now: Instant) {
+        self.pending
+            .entry(ticker.to_string())
+            .and_modify(|p| {
+                p.fair = fair;
+                p.basis = basis;
+            })
+            .or_insert(PendingSettlement {
+                fair,
+                basis,
+                since: now,
+                checked: None,
+            });
+    }
+
+    /// Tickers due a result check, sorted. They count as checked from `now`.
+    pub fn due(&mut self, now: Instant) -> Vec<String> {
+        let mut due: Vec<String> = self
+            .pending
+            .iter_mut()
+            .filter(|(_, p)| {
+                p.checked
+                    .is_none_or(|at| now.saturating_duration_since(at) >= self.recheck)
+            })
+            .map(|(ticker, p)| {
+                p.checked = Some(now);
+                ticker.clone()
+            })
+            .collect();
+        due.sort();
+        due
+    }
+
+    /// Stop watching `ticker` once its result is in.
+    pub fn resolve(&mut self, ticker: &str) {
+        self.pending.remove(ticker);
+    }
+
+    /// Tickers that waited past the timeout with their last fair value and
+    /// its basis, no longer watched.
+    pub fn expired(&mut self, now: Instant) -> Vec<(String, u32, &'static str)> {
+        let mut expired: Vec<(String, u32, &'static str)> = self
+            .pending
+            .iter()
+            .filter(|(_, p)| now.saturating_duration_since(p.since) >= self.timeout)
+            .map(|(ticker, p)| (ticker.clone(), p.fair, p.basis))
+            .collect();
+        for (ticker, _, _) in &expired {
+            self.pending.remove(ticker);
+        }
+        expired.sort();
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn market(ticker: &str, result: Option<&str>) -> Market {
+        Market {
+            ticker: ticker.to_string(),
+            event_ticker: "KXNBAGAME-26OCT17LALBOS".to_string(),
+            title: "Los Angeles L at Boston Winner?".to_string(),
+            status: "settled".to_string(),
+            yes_bid_dollars: None,
+            yes_ask_dollars: None,
+            no_bid_dollars: None,
+            no_ask_dollars: None,
+            volume: 1000,
+            open_interest: 0,
+            close_time: Some("2026-10-18T02:30:00Z".to_string()),
+            expected_expiration_time: None,
+            event_start_time: None,
+            result: result.map(str::to_string),
+            last_price_dollars: Some("0.9700".to_string()),
+            settlement_value_dollars: None,
+        }
+    }
+
+    #[test]
+    fn test_settlement_entry_from_market() {
+        let m = market("KXNBAGAME-26OCT17LALBOS-BOS", Some("yes"));
+        let v =
+            serde_json::to_value(settlement_entry("KXNBAGAME", &m, Utc::now()).unwrap()).unwrap();
+        assert_eq!(v["kind"], "settlement");
+        assert_eq!(v["result"], "yes");
+        assert_eq!(v["settlement_value"], 100);
+        assert_eq!(v["last_price"], 97);
+        assert_eq!(v["series"], "KXNBAGAME");
+
+        assert!(settlement_entry("KXNBAGAME", &market("X", Some("")), Utc::now()).is_none());
+        assert!(settlement_entry("KXNBAGAME", &market("X", None), Utc::now()).is_none());
+    }
+
+    #[test]
+    fn test_settlement_value_and_series() {
+        let mut m = market("KXNBAGAME-26OCT17LALBOS-BOS", Some("yes"));
+        assert_eq!(settlement_value(&m), Some(100));
+        assert_eq!(series_of(&m), "KXNBAGAME");
+        m.result = Some("no".to_string());
+        assert_eq!(settlement_value(&m), Some(0));
+        m.result = None;
+        assert_eq!(settlement_value(&m), None);
+    }
+
+    #[test]
+    fn test_polymarket_settlement_value_by_side() {
+        let mut m: GammaMarket = serde_json::from_str(
+            r#"{"clobTokenIds": "[\"111\", \"222\"]", "outcomePrices": "[\"1\", \"0\"]", "closed": true}"#,
+        )
+        .unwrap();
+        // YES on the winner, NO on the loser (held as the winner's token)
+        assert_eq!(polymarket_settlement_value(&m, "111", "yes"), Some(100));
+        assert_eq!(polymarket_settlement_value(&m, "111", "no"), Some(0));
+        assert_eq!(polymarket_settlement_value(&m, "222", "yes"), Some(0));
+        assert_eq!(polymarket_settlement_value(&m, "222", "no"), Some(100));
+        m.closed = false;
+        assert_eq!(polymarket_settlement_value(&m, "111", "yes"), None);
+    }
+
+    #[test]
+    fn test_settlement_watch_rechecks_and_times_out() {
+        let start = Instant::now();
+        let mut watch = SettlementWatch::new(Duration::from_secs(30), Duration::from_secs(600));
+        watch.watch("A", 90, "fair value", start);
+        watch.watch("B", 10, "fair value", start);
+        assert_eq!(watch.due(start), vec!["A", "B"]);
+        // Not due again until the recheck interval passes
+        assert!(watch.due(start + Duration::from_secs(10)).is_empty());
+        assert_eq!(watch.due(start + Duration::from_secs(30)).len(), 2);
+
+        watch.resolve("B");
+        watch.watch("A", 95, "final score", start + Duration::from_secs(60));
+        assert!(watch.expired(start + Duration::from_secs(599)).is_empty());
+        assert_eq!(
+            watch.expired(start + Duration::from_secs(600)),
+            vec![("A".to_string(), 95, "final score")]
+        );
+        assert!(watch.due(start + Duration::from_secs(900)).is_empty());
+    }
+
+    #[test]
+    fn test_recorded_settlements_reads_journal() {
+        let dir = std::env::temp_dir().join(format!("settlements-test-{}", std::process::id()));
+        let path = dir.join("journal.db");
+        let _ = std::fs::remove_dir_all(&dir);
+        let journal = Journal::open(&path).unwrap();
+        let m = market("KXNBAGAME-26OCT17LALBOS-BOS", Some("no"));
+        journal.record(&settlement_entry("KXNBAGAME", &m, Utc::now()).unwrap());
+        journal.record(&JournalEntry::Note {
+            ts: Utc::now(),
+            trade_id: 1,
+            trade_time: "20:15:03".to_string(),
+            ticker: "OTHER".to_string(),
+            action: "BUY".to_string(),
+            price: 50,
+            quantity: 1,
+            note: String::new(),
+        });
+
+        let recorded = journal.recorded_tickers("settlement").unwrap();
+        assert_eq!(recorded.len(), 1);
+        assert!(recorded.contains("KXNBAGAME-26OCT17LALBOS-BOS"));
+        assert!(Journal::disabled()
+            .recorded_tickers("settlement")
+            .unwrap()
+            .is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::config::{MomentumConfig, RiskConfig, SimulationConfig, StrategyConfig};
-use crate::pipeline::{FairValueSource, SportPipeline};
+use crate::engine::kelly_tuner::KellyTuner;
+use crate::pipeline::SportPipeline;
 
 #[derive(Debug, Clone)]
 pub struct ConfigField {
@@ -59,6 +60,7 @@ pub fn build_config_tabs(
     risk: &RiskConfig,
     sim: &SimulationConfig,
     available_odds_sources: &[String],
+    kelly_tuner: &KellyTuner,
 ) -> Vec<ConfigTab> {
     let mut tabs = Vec::new();
 
@@ -79,6 +81,7 @@ pub fn build_config_tabs(
                 global_strategy,
                 global_momentum,
                 available_odds_sources,
+                kelly_tuner,
             ),
         });
     }
@@ -159,6 +162,14 @@ fn build_global_fields(
             config_path: "risk.max_concurrent_markets".to_string(),
             read_only: false,
         },
+        ConfigField {
+            label: "risk.min_cash_reserve_cents".to_string(),
+            value: risk.min_cash_reserve_cents.to_string(),
+            field_type: FieldType::U64,
+            is_override: false,
+            config_path: "risk.min_cash_reserve_cents".to_string(),
+            read_only: false,
+        },
         // Momentum
         ConfigField {
             label: "momentum.taker_momentum_threshold".to_string(),
@@ -241,6 +252,14 @@ fn build_global_fields(
             config_path: "simulation.validate_fair_value".to_string(),
             read_only: false,
         },
+        ConfigField {
+            label: "simulation.fv_disagreement_threshold_cents".to_string(),
+            value: sim.fv_disagreement_threshold_cents.to_string(),
+            field_type: FieldType::F64,
+            is_override: false,
+            config_path: "simulation.fv_disagreement_threshold_cents".to_string(),
+            read_only: false,
+        },
     ]
 }
 
@@ -249,21 +268,26 @@ fn build_sport_fields(
     global_strategy: &StrategyConfig,
     global_momentum: &MomentumConfig,
     available_odds_sources: &[String],
+    kelly_tuner: &KellyTuner,
 ) -> Vec<ConfigField> {
     let key = &pipe.key;
     let mut fields = Vec::new();
 
     // Header: fair_value as editable Enum with all available sources
-    let fv_str = match &pipe.fair_value_source {
-        FairValueSource::ScoreFeed { .. } => "score-feed",
-        FairValueSource::OddsFeed => &pipe.odds_source,
-    };
+    let fv_str = pipe.fair_value_name();
 
-    // Build list of valid sources: score-feed (if available) + all odds sources
+    // Build list of valid sources: score-feed and blended (if available),
+    // order-flow (always; it needs only the Kalshi book) + all odds sources
     let mut valid_sources = Vec::new();
-    if pipe.score_feed_config.is_some() && pipe.win_prob_config.is_some() {
+    if pipe.score_feed_config.is_some()
+        && (pipe.win_prob_config.is_some() || pipe.soccer_model_config.is_some())
+    {
         valid_sources.push("score-feed".to_string());
+        if !pipe.odds_source.is_empty() {
+            valid_sources.push("blended".to_string());
+        }
     }
+    valid_sources.push("order-flow".to_string());
     for source in available_odds_sources {
         valid_sources.push(source.clone());
     }
@@ -328,6 +352,24 @@ fn build_sport_fields(
         read_only: false,
     });
 
+    // Auto-tuned Kelly multiplier, with the trades it was measured over
+    let trades = kelly_tuner.trades(key);
+    let min_trades = kelly_tuner.config().min_trades;
+    fields.push(ConfigField {
+        label: "risk.kelly_multiplier".to_string(),
+        value: if !kelly_tuner.config().auto_tune {
+            "off".to_string()
+        } else if trades < min_trades {
+            format!("1.00 ({}/{} trades)", trades, min_trades)
+        } else {
+            format!("{:.2} ({} trades)", kelly_tuner.multiplier(key), trades)
+        },
+        field_type: FieldType::F64,
+        is_override: kelly_tuner.multiplier(key) < 1.0,
+        config_path: "kelly_tuning.auto_tune".to_string(),
+        read_only: true,
+    });
+
     // Momentum fields
     let m = &pipe.momentum_config;
     fields.push(ConfigField {
@@ -356,13 +398,7 @@ fn build_sport_fields(
     });
 
     // Score feed fields (if applicable)
-    if let FairValueSource::ScoreFeed {
-        poller,
-        live_poll_s,
-        pre_game_poll_s,
-        ..
-    } = &pipe.fair_value_source
-    {
+    if let (Some(poller), Some(sf)) = (&pipe.score_poller, &pipe.score_feed_config) {
         fields.push(ConfigField {
             label: "score_feed.primary_url".to_string(),
             value: poller.primary_url().to_string(),
@@ -373,7 +409,7 @@ fn build_sport_fields(
         });
         fields.push(ConfigField {
             label: "score_feed.live_poll_s".to_string(),
-            value: live_poll_s.to_string(),
+            value: sf.live_poll_s.to_string(),
             field_type: FieldType::U64,
             is_override: false,
             config_path: format!("sports.{}.score_feed.live_poll_s", key),
@@ -381,7 +417,7 @@ fn build_sport_fields(
         });
         fields.push(ConfigField {
             label: "score_feed.pre_game_poll_s".to_string(),
-            value: pre_game_poll_s.to_string(),
+            value: sf.pre_game_poll_s.to_string(),
             field_type: FieldType::U64,
             is_override: false,
             config_path: format!("sports.{}.score_feed.pre_game_poll_s", key),
@@ -1,5 +1,6 @@
 pub mod config_view;
 pub mod render;
+pub mod snapshot;
 pub mod state;
 
 use anyhow::Result;
@@ -31,8 +32,54 @@ pub enum TuiCommand {
         value: String,
     },
     KillSwitch,
+    /// Acknowledge open critical alerts so they are not escalated.
+    AcknowledgeAlerts,
+    /// Re-read Kalshi and Odds API credentials without restarting.
+    ReloadCredentials,
+    /// Attach a review note/tag to a trade (empty note clears it).
+    AnnotateTrade { trade_id: u64, note: String },
+    /// Start/stop recording every orderbook update for a ticker.
+    ToggleBookRecording(String),
+    /// Load a ticker's recorded book frames into the replay view.
+    OpenBookReplay(String),
+    CloseBookReplay,
+    /// Show a ticker's live depth ladder, kept fresh by the engine.
+    OpenDepthLadder(String),
+    CloseDepthLadder,
+    /// Snapshot every score-feed sport's model into the win-prob view.
+    OpenWinProb,
+    CloseWinProb,
+    /// Summarize P&L by sport and source into the portfolio view.
+    OpenPortfolio,
+    ClosePortfolio,
+    /// Capture a short engine profile and write a flamegraph.
+    Profile,
+    /// Place an order the strategy did not signal; risk limits still apply.
+    ManualOrder(ManualOrder),
+    /// Blacklist an event or market ticker, or lift it. Saved to the config.
+    ToggleBlacklist(String),
+    /// Whitelist an event or market ticker, or lift it. Saved to the config.
+    ToggleWhitelist(String),
+    /// Apply the next journaled event. Only `--replay` acts on it.
+    StepReplay,
 }
 
+/// An order entered by hand from the markets view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManualOrder {
+    pub ticker: String,
+    pub side: String, // "yes" or "no"
+    pub is_buy: bool,
+    pub price: u32,
+    pub quantity: u32,
+}
+
+/// Quick tags cycled with Tab while editing a trade note.
+const NOTE_PRESETS: [&str; 4] = ["model lag", "bad match", "fat finger", "stale feed"];
+const MAX_NOTE_LEN: usize = 40;
+/// How long the capture path stays on screen.
+const SNAPSHOT_NOTICE_SECS: u64 = 5;
+
 /// Run the TUI. Reads state from `state_rx`, sends commands on `cmd_tx`.
 pub async fn run_tui(
     state_rx: watch::Receiver<AppState>,
@@ -65,12 +112,24 @@ async fn tui_loop(
     let mut market_scroll_offset: usize = 0;
     let mut position_focus = false;
     let mut position_scroll_offset: usize = 0;
+    let mut position_detail: Option<String> = None;
     let mut trade_focus = false;
     let mut trade_scroll_offset: usize = 0;
+    let mut phase_filter: Option<String> = None;
+    let mut note_edit: Option<(u64, String)> = None;
+    let mut order_ticket: Option<state::OrderTicket> = None;
     let mut diagnostic_focus = false;
     let mut diagnostic_scroll_offset: usize = 0;
     let mut config_focus = false;
     let mut config_view: Option<config_view::ConfigViewState> = None;
+    let mut replay_focus = false;
+    let mut replay_cursor: usize = 0;
+    let mut depth_focus = false;
+    let mut win_prob_focus = false;
+    let mut win_prob_sport: usize = 0;
+    let mut portfolio_focus = false;
+    let mut snapshot_pending = false;
+    let mut snapshot_notice: Option<(String, std::time::Instant)> = None;
 
     loop {
         // Render current state with UI-local overrides
@@ -86,14 +145,47 @@ async fn tui_loop(
             state.market_scroll_offset = market_scroll_offset;
             state.position_focus = position_focus;
             state.position_scroll_offset = position_scroll_offset;
+            state.position_detail = position_detail.clone();
             state.trade_focus = trade_focus;
             state.trade_scroll_offset = trade_scroll_offset;
+            state.phase_filter = phase_filter.clone();
+            state.trade_note_edit = note_edit.as_ref().map(|(_, buf)| buf.clone());
+            state.order_ticket = order_ticket.clone();
             state.diagnostic_focus = diagnostic_focus;
             state.diagnostic_scroll_offset = diagnostic_scroll_offset;
             state.config_focus = config_focus;
+            if !replay_focus {
+                state.book_replay = None;
+            }
+            state.replay_cursor = replay_cursor;
+            if !depth_focus {
+                state.depth_ladder = None;
+            }
+            state.win_prob_focus = win_prob_focus;
+            state.win_prob_sport = win_prob_sport;
+            state.portfolio_focus = portfolio_focus;
+            snapshot_notice = snapshot_notice.filter(|(_, at)| {
+                at.elapsed() < std::time::Duration::from_secs(SNAPSHOT_NOTICE_SECS)
+            });
+            // Captures are taken without the notice of the previous one
+            if !snapshot_pending {
+                state.snapshot_notice = snapshot_notice.as_ref().map(|(msg, _)| msg.clone());
+            }
             // Move config_view into state for rendering, then take it back
             state.config_view = config_view.take();
-            terminal.draw(|f| render::draw(f, &state, spinner_frame))?;
+            let frame = terminal.draw(|f| render::draw(f, &state, spinner_frame))?;
+            if snapshot_pending {
+                snapshot_pending = false;
+                let msg = match snapshot::save(
+                    frame.buffer,
+                    std::path::Path::new(snapshot::SNAPSHOT_DIR),
+                    chrono::Local::now(),
+                ) {
+                    Ok(path) => format!("Saved {} (+ .ans, .svg)", path.display()),
+                    Err(e) => format!("Snapshot failed: {:#}", e),
+                };
+                snapshot_notice = Some((msg, std::time::Instant::now()));
+            }
             config_view = state.config_view.take();
         }
 
@@ -109,7 +201,157 @@ async fn tui_loop(
                             let _ = cmd_tx.send(TuiCommand::KillSwitch).await;
                             return Ok(());
                         }
-                        if config_focus {
+                        // Capture the current view from anywhere but a text field
+                        let typing = note_edit.is_some()
+                            || order_ticket.is_some()
+                            || config_view.as_ref().is_some_and(|cv| cv.editing);
+                        if key.code == KeyCode::Char('S') && !typing {
+                            snapshot_pending = true;
+                            continue;
+                        }
+                        if let Some((trade_id, buffer)) = &mut note_edit {
+                            match key.code {
+                                KeyCode::Enter => {
+                                    let _ = cmd_tx.send(TuiCommand::AnnotateTrade {
+                                        trade_id: *trade_id,
+                                        note: buffer.trim().to_string(),
+                                    }).await;
+                                    note_edit = None;
+                                }
+                                KeyCode::Esc => {
+                                    note_edit = None;
+                                }
+                                KeyCode::Tab => {
+                                    let next = NOTE_PRESETS
+                                        .iter()
+                                        .position(|p| p == buffer)
+                                        .map_or(0, |i| (i + 1) % NOTE_PRESETS.len());
+                                    *buffer = NOTE_PRESETS[next].to_string();
+                                }
+                                KeyCode::Backspace => {
+                                    buffer.pop();
+                                }
+                                KeyCode::Char(c) if buffer.chars().count() < MAX_NOTE_LEN => {
+                                    buffer.push(c);
+                                }
+                                _ => {}
+                            }
+                        } else if let Some(ticket) = &mut order_ticket {
+                            match key.code {
+                                KeyCode::Enter => {
+                                    if let Some(order) = ticket.order() {
+                                        let _ = cmd_tx.send(TuiCommand::ManualOrder(order)).await;
+                                        order_ticket = None;
+                                    }
+                                }
+                                KeyCode::Esc => {
+                                    order_ticket = None;
+                                }
+                                KeyCode::Tab => ticket.next_field(),
+                                KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right
+                                    if ticket.field == state::TicketField::Side =>
+                                {
+                                    ticket.toggle_side();
+                                }
+                                KeyCode::Backspace => ticket.backspace(),
+                                KeyCode::Char(c) if c.is_ascii_digit() => ticket.push_digit(c),
+                                _ => {}
+                            }
+                        } else if win_prob_focus {
+                            let sports = state_rx.borrow().win_prob_grids.len().max(1);
+                            match key.code {
+                                KeyCode::Esc | KeyCode::Char('w') => {
+                                    win_prob_focus = false;
+                                    let _ = cmd_tx.send(TuiCommand::CloseWinProb).await;
+                                }
+                                KeyCode::Right | KeyCode::Char('l') | KeyCode::Tab => {
+                                    win_prob_sport = (win_prob_sport + 1) % sports;
+                                }
+                                KeyCode::Left | KeyCode::Char('h') => {
+                                    win_prob_sport = (win_prob_sport + sports - 1) % sports;
+                                }
+                                KeyCode::Char('q') => {
+                                    let _ = cmd_tx.send(TuiCommand::Quit).await;
+                                    return Ok(());
+                                }
+                                _ => {}
+                            }
+                        } else if portfolio_focus {
+                            match key.code {
+                                KeyCode::Esc | KeyCode::Char('s') => {
+                                    portfolio_focus = false;
+                                    let _ = cmd_tx.send(TuiCommand::ClosePortfolio).await;
+                                }
+                                KeyCode::Char('r') => {
+                                    let _ = cmd_tx.send(TuiCommand::OpenPortfolio).await;
+                                }
+                                KeyCode::Char('q') => {
+                                    let _ = cmd_tx.send(TuiCommand::Quit).await;
+                                    return Ok(());
+                                }
+                                _ => {}
+                            }
+                        } else if position_detail.is_some() {
+                            match key.code {
+                                KeyCode::Esc | KeyCode::Enter => {
+                                    position_detail = None;
+                                }
+                                KeyCode::Char('q') => {
+                                    let _ = cmd_tx.send(TuiCommand::Quit).await;
+                                    return Ok(());
+                                }
+                                _ => {}
+                            }
+                        } else if depth_focus {
+                            match key.code {
+                                KeyCode::Esc | KeyCode::Enter => {
+                                    depth_focus = false;
+                                    let _ = cmd_tx.send(TuiCommand::CloseDepthLadder).await;
+                                }
+                                KeyCode::Char('q') => {
+                                    let _ = cmd_tx.send(TuiCommand::Quit).await;
+                                    return Ok(());
+                                }
+                                _ => {}
+                            }
+                        } else if replay_focus {
+                            let frames = state_rx
+                                .borrow()
+                                .book_replay
+                                .as_ref()
+                                .map_or(0, |r| r.frames.len());
+                            let last = frames.saturating_sub(1);
+                            match key.code {
+                                KeyCode::Esc | KeyCode::Char('v') => {
+                                    replay_focus = false;
+                                    replay_cursor = 0;
+                                    let _ = cmd_tx.send(TuiCommand::CloseBookReplay).await;
+                                }
+                                KeyCode::Right | KeyCode::Char('l') => {
+                                    replay_cursor = (replay_cursor + 1).min(last);
+                                }
+                                KeyCode::Left | KeyCode::Char('h') => {
+                                    replay_cursor = replay_cursor.saturating_sub(1);
+                                }
+                                KeyCode::PageDown => {
+                                    replay_cursor = (replay_cursor + 50).min(last);
+                                }
+                                KeyCode::PageUp => {
+                                    replay_cursor = replay_cursor.saturating_sub(50);
+                                }
+                                KeyCode::Char('g') => {
+                                    replay_cursor = 0;
+                                }
+                                KeyCode::Char('G') => {
+                                    replay_cursor = last;
+                                }
+                                KeyCode::Char('q') => {
+                                    let _ = cmd_tx.send(TuiCommand::Quit).await;
+                                    return Ok(());
+                                }
+                                _ => {}
+                            }
+                        } else if config_focus {
                             if let Some(ref mut cv) = config_view {
                                 if cv.editing {
                                     match key.code {
@@ -158,9 +400,7 @@ async fn tui_loop(
                                             cv.selected_field = 0;
                                         }
                                         KeyCode::Up | KeyCode::Char('k') => {
-                                            if cv.selected_field > 0 {
-                                                cv.selected_field -= 1;
-                                            }
+                                            cv.selected_field = cv.selected_field.saturating_sub(1);
                                         }
                                         KeyCode::Down | KeyCode::Char('j') => {
                                             let max = cv.tabs[cv.active_tab].fields.len().saturating_sub(1);
@@ -244,7 +484,7 @@ async fn tui_loop(
                                     let _ = cmd_tx.send(TuiCommand::Quit).await;
                                     return Ok(());
                                 }
-                                KeyCode::Char(c @ '1'..='8') => {
+                                KeyCode::Char(c @ '1'..='9') => {
                                     let key = state_rx.borrow().sport_toggles.iter()
                                         .find(|(_, _, h, _)| *h == c)
                                         .map(|(k, _, _, _)| k.clone());
@@ -273,11 +513,77 @@ async fn tui_loop(
                                 KeyCode::Char('g') => {
                                     market_scroll_offset = 0;
                                 }
+                                KeyCode::Char('f') => {
+                                    phase_filter = state_rx
+                                        .borrow()
+                                        .next_phase_filter(phase_filter.as_deref());
+                                    market_scroll_offset = 0;
+                                }
+                                KeyCode::Char('r') => {
+                                    let ticker = state_rx
+                                        .borrow()
+                                        .selected_market(phase_filter.as_deref(), market_scroll_offset)
+                                        .map(|m| m.ticker.clone());
+                                    if let Some(t) = ticker {
+                                        let _ = cmd_tx.send(TuiCommand::ToggleBookRecording(t)).await;
+                                    }
+                                }
+                                KeyCode::Char('b') => {
+                                    order_ticket = state_rx
+                                        .borrow()
+                                        .selected_market(phase_filter.as_deref(), market_scroll_offset)
+                                        .map(state::OrderTicket::buy);
+                                }
+                                KeyCode::Char('s') => {
+                                    let state = state_rx.borrow();
+                                    order_ticket = state
+                                        .selected_market(phase_filter.as_deref(), market_scroll_offset)
+                                        .and_then(|m| {
+                                            let held = state.held_quantity(&m.ticker)?;
+                                            Some(state::OrderTicket::sell(m, held))
+                                        });
+                                }
+                                KeyCode::Char('v') => {
+                                    let ticker = state_rx
+                                        .borrow()
+                                        .selected_market(phase_filter.as_deref(), market_scroll_offset)
+                                        .map(|m| m.ticker.clone());
+                                    if let Some(t) = ticker {
+                                        let _ = cmd_tx.send(TuiCommand::OpenBookReplay(t)).await;
+                                        replay_focus = true;
+                                        replay_cursor = 0;
+                                    }
+                                }
+                                KeyCode::Enter => {
+                                    let ticker = state_rx
+                                        .borrow()
+                                        .selected_market(phase_filter.as_deref(), market_scroll_offset)
+                                        .map(|m| m.ticker.clone());
+                                    if let Some(t) = ticker {
+                                        let _ = cmd_tx.send(TuiCommand::OpenDepthLadder(t)).await;
+                                        depth_focus = true;
+                                    }
+                                }
+                                KeyCode::Char(c @ ('x' | 'X' | 'a')) => {
+                                    let ticker = state_rx
+                                        .borrow()
+                                        .selected_market(phase_filter.as_deref(), market_scroll_offset)
+                                        .map(|m| m.ticker.clone());
+                                    if let Some(t) = ticker {
+                                        let game = crate::engine::risk::game_event(&t).to_string();
+                                        let cmd = match c {
+                                            'x' => TuiCommand::ToggleBlacklist(game),
+                                            'X' => TuiCommand::ToggleBlacklist(t),
+                                            _ => TuiCommand::ToggleWhitelist(game),
+                                        };
+                                        let _ = cmd_tx.send(cmd).await;
+                                    }
+                                }
                                 KeyCode::Char('q') => {
                                     let _ = cmd_tx.send(TuiCommand::Quit).await;
                                     return Ok(());
                                 }
-                                KeyCode::Char(c @ '1'..='8') => {
+                                KeyCode::Char(c @ '1'..='9') => {
                                     let key = state_rx.borrow().sport_toggles.iter()
                                         .find(|(_, _, h, _)| *h == c)
                                         .map(|(k, _, _, _)| k.clone());
@@ -311,11 +617,19 @@ async fn tui_loop(
                                 KeyCode::Char('g') => {
                                     position_scroll_offset = 0;
                                 }
+                                KeyCode::Enter => {
+                                    let state = state_rx.borrow();
+                                    let held = state.held_tickers();
+                                    position_detail = held
+                                        .len()
+                                        .checked_sub(1)
+                                        .map(|last| held[position_scroll_offset.min(last)].to_string());
+                                }
                                 KeyCode::Char('q') => {
                                     let _ = cmd_tx.send(TuiCommand::Quit).await;
                                     return Ok(());
                                 }
-                                KeyCode::Char(c @ '1'..='8') => {
+                                KeyCode::Char(c @ '1'..='9') => {
                                     let key = state_rx.borrow().sport_toggles.iter()
                                         .find(|(_, _, h, _)| *h == c)
                                         .map(|(k, _, _, _)| k.clone());
@@ -344,11 +658,23 @@ async fn tui_loop(
                                 KeyCode::Char('g') => {
                                     trade_scroll_offset = 0;
                                 }
+                                KeyCode::Char('f') => {
+                                    phase_filter = state_rx
+                                        .borrow()
+                                        .next_phase_filter(phase_filter.as_deref());
+                                    trade_scroll_offset = 0;
+                                }
+                                KeyCode::Char('n') => {
+                                    note_edit = state_rx
+                                        .borrow()
+                                        .selected_trade(phase_filter.as_deref(), trade_scroll_offset)
+                                        .map(|t| (t.id, t.note.clone()));
+                                }
                                 KeyCode::Char('q') => {
                                     let _ = cmd_tx.send(TuiCommand::Quit).await;
                                     return Ok(());
                                 }
-                                KeyCode::Char(c @ '1'..='8') => {
+                                KeyCode::Char(c @ '1'..='9') => {
                                     let key = state_rx.borrow().sport_toggles.iter()
                                         .find(|(_, _, h, _)| *h == c)
                                         .map(|(k, _, _, _)| k.clone());
@@ -377,11 +703,26 @@ async fn tui_loop(
                                 KeyCode::Char('g') => {
                                     diagnostic_scroll_offset = 0;
                                 }
+                                KeyCode::Char(c @ ('x' | 'a')) => {
+                                    let game = state_rx
+                                        .borrow()
+                                        .selected_diagnostic(diagnostic_scroll_offset)
+                                        .and_then(|r| r.kalshi_ticker.as_deref())
+                                        .map(|t| crate::engine::risk::game_event(t).to_string());
+                                    if let Some(game) = game {
+                                        let cmd = if c == 'x' {
+                                            TuiCommand::ToggleBlacklist(game)
+                                        } else {
+                                            TuiCommand::ToggleWhitelist(game)
+                                        };
+                                        let _ = cmd_tx.send(cmd).await;
+                                    }
+                                }
                                 KeyCode::Char('q') => {
                                     let _ = cmd_tx.send(TuiCommand::Quit).await;
                                     return Ok(());
                                 }
-                                KeyCode::Char(c @ '1'..='8') => {
+                                KeyCode::Char(c @ '1'..='9') => {
                                     let key = state_rx.borrow().sport_toggles.iter()
                                         .find(|(_, _, h, _)| *h == c)
                                         .map(|(k, _, _, _)| k.clone());
@@ -403,6 +744,18 @@ async fn tui_loop(
                                 KeyCode::Char('r') => {
                                     let _ = cmd_tx.send(TuiCommand::Resume).await;
                                 }
+                                KeyCode::Char('a') => {
+                                    let _ = cmd_tx.send(TuiCommand::AcknowledgeAlerts).await;
+                                }
+                                KeyCode::Char('R') => {
+                                    let _ = cmd_tx.send(TuiCommand::ReloadCredentials).await;
+                                }
+                                KeyCode::Char('F') => {
+                                    let _ = cmd_tx.send(TuiCommand::Profile).await;
+                                }
+                                KeyCode::Char('.') => {
+                                    let _ = cmd_tx.send(TuiCommand::StepReplay).await;
+                                }
                                 KeyCode::Char('l') => {
                                     log_focus = true;
                                     log_scroll_offset = 0;
@@ -431,7 +784,16 @@ async fn tui_loop(
                                     let _ = cmd_tx.send(TuiCommand::OpenConfig).await;
                                     config_focus = true;
                                 }
-                                KeyCode::Char(c @ '1'..='8') => {
+                                KeyCode::Char('w') => {
+                                    let _ = cmd_tx.send(TuiCommand::OpenWinProb).await;
+                                    win_prob_focus = true;
+                                    win_prob_sport = 0;
+                                }
+                                KeyCode::Char('s') => {
+                                    let _ = cmd_tx.send(TuiCommand::OpenPortfolio).await;
+                                    portfolio_focus = true;
+                                }
+                                KeyCode::Char(c @ '1'..='9') => {
                                     let key = state_rx.borrow().sport_toggles.iter()
                                         .find(|(_, _, h, _)| *h == c)
                                         .map(|(k, _, _, _)| k.clone());
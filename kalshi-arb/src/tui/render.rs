@@ -1,40 +1,186 @@
 use std::borrow::Cow;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use super::config_view;
-use super::state::AppState;
+use super::state::{ladder_levels, AppState, DepthLadder, OrderTicket, TicketField, TradeRow};
+use crate::engine::degradation::FeedTier;
+use crate::engine::drawdown::DrawdownTier;
 use crate::engine::fees::calculate_fee;
+use crate::engine::risk::deployable_cents;
+use crate::locale::NumberFormat;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Tabs},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Sparkline, Table, Tabs},
     Frame,
 };
 
 const SPINNER_FRAMES: &[char] = &['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+/// Price levels shown per side in the depth ladder.
+const DEPTH_LADDER_LEVELS: usize = 15;
 
 pub fn draw(f: &mut Frame, state: &AppState, spinner_frame: u8) {
+    draw_view(f, state, spinner_frame);
+    draw_drawdown_banner(f, state);
+    if let Some(ticket) = &state.order_ticket {
+        draw_order_ticket(f, ticket);
+    }
+    if let Some(notice) = &state.snapshot_notice {
+        let area = f.area();
+        let width = (notice.width() as u16 + 2).min(area.width);
+        let rect = Rect::new(area.right() - width, area.top(), width, 1.min(area.height));
+        f.render_widget(Clear, rect);
+        f.render_widget(
+            Paragraph::new(format!(" {} ", notice))
+                .style(Style::default().fg(Color::Black).bg(Color::Green)),
+            rect,
+        );
+    }
+}
+
+/// Manual order dialog, centered over the current view.
+fn draw_order_ticket(f: &mut Frame, ticket: &OrderTicket) {
+    let area = f.area();
+    let width = 44.min(area.width);
+    let height = 7.min(area.height);
+    let rect = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+    let field = |label: &str, value: String, this: TicketField| {
+        let style = if ticket.field == this {
+            Style::default().fg(Color::Black).bg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+        Line::from(vec![
+            Span::raw(format!(" {:<9}", label)),
+            Span::styled(format!(" {} ", value), style),
+        ])
+    };
+    let valid = ticket.order().is_some();
+    let lines = vec![
+        field("Side", ticket.side.to_uppercase(), TicketField::Side),
+        field("Price", format!("{}c", ticket.price), TicketField::Price),
+        field("Quantity", ticket.quantity.clone(), TicketField::Quantity),
+        Line::from(""),
+        Line::from(Span::styled(
+            if valid {
+                " [Tab] field  [Enter] send  [Esc] cancel"
+            } else {
+                " price 1-99c, quantity > 0  [Esc] cancel"
+            },
+            Style::default().fg(if valid { Color::DarkGray } else { Color::Red }),
+        )),
+    ];
+    let (verb, color) = if ticket.is_buy {
+        ("BUY", Color::Green)
+    } else {
+        ("SELL", Color::Red)
+    };
+    f.render_widget(Clear, rect);
+    f.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .title(format!(" {} {} ", verb, ticket.ticker))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color)),
+        ),
+        rect,
+    );
+}
+
+/// Persistent banner across the top row while a drawdown limit is in force.
+fn draw_drawdown_banner(f: &mut Frame, state: &AppState) {
+    if state.drawdown_tier == DrawdownTier::Normal {
+        return;
+    }
+    let area = f.area();
+    if area.height == 0 {
+        return;
+    }
+    let bg = if state.drawdown_tier == DrawdownTier::Halted {
+        Color::Red
+    } else {
+        Color::Yellow
+    };
+    let text = format!(
+        " DRAWDOWN {} | {}: {} ",
+        state.number_format.money(state.drawdown_cents as i64),
+        state.drawdown_tier.label(),
+        state.drawdown_tier.describe()
+    );
+    let rect = Rect::new(area.left(), area.top(), area.width, 1);
+    f.render_widget(Clear, rect);
+    f.render_widget(
+        Paragraph::new(text).alignment(Alignment::Center).style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(bg)
+                .add_modifier(Modifier::BOLD),
+        ),
+        rect,
+    );
+}
+
+fn draw_view(f: &mut Frame, state: &AppState, spinner_frame: u8) {
     if state.config_focus {
         render_config(f, state);
         return;
     }
+    if let Some(replay) = state.book_replay.as_deref() {
+        draw_book_replay(f, replay, state.replay_cursor);
+        return;
+    }
+    if let Some(ticker) = &state.position_detail {
+        draw_position_detail(f, state, ticker);
+        return;
+    }
+    if let Some(ladder) = &state.depth_ladder {
+        draw_depth_ladder(f, state, ladder);
+        return;
+    }
+    if state.win_prob_focus {
+        draw_win_prob(f, state);
+        return;
+    }
+    if state.portfolio_focus {
+        draw_portfolio(f, state);
+        return;
+    }
 
     let width = f.area().width.saturating_sub(2) as usize;
 
-    let bal = format!("${:.2}", state.balance_cents as f64 / 100.0);
-    let exp = format!("${:.2}", state.total_exposure_cents as f64 / 100.0);
-    let pnl_val = format!("${:.2}", state.realized_pnl_cents as f64 / 100.0);
+    let bal_cents = if state.sim_mode {
+        state.sim_balance_cents
+    } else {
+        state.balance_cents
+    };
+    let fmt = &state.number_format;
+    let bal = balance_label(fmt, bal_cents, state.cash_reserve_cents);
+    let exp = fmt.money(state.total_exposure_cents);
+    let pnl_val = fmt.money(state.realized_pnl_cents);
     let uptime = state.uptime();
-    let row1_width = 1 + 5 + bal.len() + 3 + 5 + exp.len() + 3 + 5 + pnl_val.len();
-    let full_width = row1_width + 3 + 4 + 4 + 3 + 4 + uptime.len() + 8;
+    let row1_width = 1 + 5 + bal.width() + 3 + 5 + exp.width() + 3 + 5 + pnl_val.width();
+    let full_width = row1_width + 3 + 4 + 4 + 3 + 4 + uptime.width() + 8;
     let header_height = if full_width > width { 4 } else { 3 };
 
     if state.diagnostic_focus {
+        let usage = state.api_usage.breakdown(std::time::Instant::now());
+        let usage_height = if usage.is_empty() {
+            0
+        } else {
+            (usage.len() as u16 + 3).min(10)
+        };
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(3),
                 Constraint::Min(0),
+                Constraint::Length(usage_height),
                 Constraint::Length(1),
                 Constraint::Length(1),
             ])
@@ -42,8 +188,11 @@ pub fn draw(f: &mut Frame, state: &AppState, spinner_frame: u8) {
 
         draw_diagnostic_header(f, state, chunks[0]);
         draw_diagnostic(f, state, chunks[1]);
-        draw_diagnostic_footer(f, chunks[2]);
-        draw_sport_legend(f, state, chunks[3]);
+        if !usage.is_empty() {
+            draw_api_usage(f, &usage, state.api_usage.total_credits(), chunks[2]);
+        }
+        draw_diagnostic_footer(f, chunks[3]);
+        draw_sport_legend(f, state, chunks[4]);
     } else if state.log_focus {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -105,6 +254,7 @@ pub fn draw(f: &mut Frame, state: &AppState, spinner_frame: u8) {
         draw_footer(f, state, chunks[2]);
         draw_sport_legend(f, state, chunks[3]);
     } else {
+        let preflight_height = if state.preflight.is_some() { 3 } else { 0 };
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -112,6 +262,7 @@ pub fn draw(f: &mut Frame, state: &AppState, spinner_frame: u8) {
                 Constraint::Min(8),
                 Constraint::Length(6),
                 Constraint::Length(6),
+                Constraint::Length(preflight_height),
                 Constraint::Min(5),
                 Constraint::Length(1),
                 Constraint::Length(1),
@@ -123,13 +274,24 @@ pub fn draw(f: &mut Frame, state: &AppState, spinner_frame: u8) {
         draw_markets(f, state, chunks[1]);
         draw_positions(f, state, chunks[2]);
         draw_trades(f, state, chunks[3]);
-        draw_logs(f, state, chunks[4]);
-        draw_api_status(f, state, chunks[5]);
-        draw_footer(f, state, chunks[6]);
-        draw_sport_legend(f, state, chunks[7]);
+        draw_preflight(f, state, chunks[4]);
+        draw_logs(f, state, chunks[5]);
+        draw_api_status(f, state, chunks[6]);
+        draw_footer(f, state, chunks[7]);
+        draw_sport_legend(f, state, chunks[8]);
     }
 }
 
+/// Header balance: total, or deployable vs total when a cash reserve is set.
+fn balance_label(fmt: &NumberFormat, total_cents: i64, reserve_cents: u64) -> String {
+    let total = fmt.money(total_cents);
+    if reserve_cents == 0 {
+        return total;
+    }
+    let deployable = deployable_cents(total_cents, reserve_cents);
+    format!("{} deployable / {}", fmt.money(deployable as i64), total)
+}
+
 fn draw_header(f: &mut Frame, state: &AppState, area: Rect, spinner_frame: u8) {
     let kalshi_status = if state.kalshi_ws_connected {
         Span::styled("OK", Style::default().fg(Color::Green))
@@ -137,6 +299,13 @@ fn draw_header(f: &mut Frame, state: &AppState, area: Rect, spinner_frame: u8) {
         Span::styled("DOWN", Style::default().fg(Color::Red))
     };
 
+    let tier_color = match state.feed_tier {
+        FeedTier::Full => Color::Green,
+        FeedTier::Degraded => Color::Yellow,
+        FeedTier::Minimal => Color::Red,
+    };
+    let tier_span = Span::styled(state.feed_tier.label(), Style::default().fg(tier_color));
+
     let activity_indicator = if state.is_paused {
         Span::styled(
             " PAUSED",
@@ -168,9 +337,10 @@ fn draw_header(f: &mut Frame, state: &AppState, area: Rect, spinner_frame: u8) {
         )
     };
 
-    let bal = format!("${:.2}", bal_cents as f64 / 100.0);
-    let exp = format!("${:.2}", exp_cents as f64 / 100.0);
-    let pnl_val = format!("${:.2}", pnl_cents as f64 / 100.0);
+    let fmt = &state.number_format;
+    let bal = balance_label(fmt, bal_cents, state.cash_reserve_cents);
+    let exp = fmt.money(exp_cents);
+    let pnl_val = fmt.money(pnl_cents);
     let uptime = state.uptime();
 
     let num_color = if state.sim_mode {
@@ -185,18 +355,11 @@ fn draw_header(f: &mut Frame, state: &AppState, area: Rect, spinner_frame: u8) {
 
     // Build sim stats spans (only shown in sim mode)
     let sim_stats_spans: Vec<Span> = if state.sim_mode {
-        if state.sim_entries_attempted == 0 {
-            vec![
-                Span::styled(" | Entries: ", Style::default().fg(Color::DarkGray)),
-                Span::styled("0", Style::default().fg(Color::DarkGray)),
-            ]
-        } else {
-            let fill_rate = state.sim_entries_filled * 100 / state.sim_entries_attempted;
-            let win_pct = if state.total_trades > 0 {
-                state.winning_trades * 100 / state.total_trades
-            } else {
-                0
-            };
+        let fill_rate = (state.sim_entries_filled * 100).checked_div(state.sim_entries_attempted);
+        if let Some(fill_rate) = fill_rate {
+            let win_pct = (state.winning_trades * 100)
+                .checked_div(state.total_trades)
+                .unwrap_or(0);
             let avg_slip = if state.sim_entries_filled > 0 {
                 state.total_slippage_cents as f64 / state.sim_entries_filled as f64
             } else {
@@ -243,14 +406,20 @@ fn draw_header(f: &mut Frame, state: &AppState, area: Rect, spinner_frame: u8) {
                     Style::default().fg(slip_color),
                 ),
             ]
+        } else {
+            vec![
+                Span::styled(" | Entries: ", Style::default().fg(Color::DarkGray)),
+                Span::styled("0", Style::default().fg(Color::DarkGray)),
+            ]
         }
     } else {
         vec![]
     };
 
-    let row1_width = 1 + 5 + bal.len() + 3 + 5 + exp.len() + 3 + 5 + pnl_val.len();
+    let row1_width = 1 + 5 + bal.width() + 3 + 5 + exp.width() + 3 + 5 + pnl_val.width();
     let inner_width = area.width.saturating_sub(2) as usize;
-    let needs_wrap = row1_width + 3 + 4 + 4 + 3 + 4 + uptime.len() + 8 > inner_width;
+    let tier_width = 10 + state.feed_tier.label().len();
+    let needs_wrap = row1_width + 3 + 4 + 4 + tier_width + 3 + 4 + uptime.width() + 8 > inner_width;
 
     let bal_exp_prefix = if state.sim_mode {
         vec![
@@ -270,6 +439,8 @@ fn draw_header(f: &mut Frame, state: &AppState, area: Rect, spinner_frame: u8) {
             Line::from(vec![
                 Span::raw(" WS: "),
                 kalshi_status,
+                Span::raw(" | Feeds: "),
+                tier_span,
                 Span::raw(format!(" | Up: {}", uptime)),
                 activity_indicator,
             ]),
@@ -283,6 +454,8 @@ fn draw_header(f: &mut Frame, state: &AppState, area: Rect, spinner_frame: u8) {
                 vec![
                     Span::raw(" | WS: "),
                     kalshi_status,
+                    Span::raw(" | Feeds: "),
+                    tier_span,
                     Span::raw(format!(" | Up: {}", uptime)),
                     activity_indicator,
                 ],
@@ -291,10 +464,18 @@ fn draw_header(f: &mut Frame, state: &AppState, area: Rect, spinner_frame: u8) {
         )]
     };
 
+    let profile = state
+        .profile
+        .as_ref()
+        .map(|p| format!("[profile: {}] ", p))
+        .unwrap_or_default();
     let title = if state.sim_mode {
-        format!(" Kalshi Arb Engine [SIMULATION] [{}] ", state.odds_source)
+        format!(
+            " Kalshi Arb Engine [SIMULATION] {}[{}] ",
+            profile, state.odds_source
+        )
     } else {
-        format!(" Kalshi Arb Engine [{}] ", state.odds_source)
+        format!(" Kalshi Arb Engine {}[{}] ", profile, state.odds_source)
     };
 
     let title_style = if state.sim_mode {
@@ -413,11 +594,12 @@ fn draw_markets(f: &mut Frame, state: &AppState, area: Rect) {
                 true,
             )
         } else {
-            let fixed_with_mom = fixed_cols_full + 5 + 7; // +mom +stale columns
+            let fixed_with_mom = fixed_cols_full + 5 + 7 + 6; // +mom +stale +phase columns
             let ticker_w = inner_width.saturating_sub(fixed_with_mom).max(4);
             (
                 vec![
-                    "Ticker", "Fair", "Bid", "Ask", "Edge", "Mom", "Stale", "Action", "Latency",
+                    "Ticker", "Fair", "Bid", "Ask", "Edge", "Mom", "Stale", "Phase", "Action",
+                    "Latency",
                 ],
                 vec![
                     Constraint::Length(ticker_w as u16),
@@ -427,6 +609,7 @@ fn draw_markets(f: &mut Frame, state: &AppState, area: Rect) {
                     Constraint::Length(6),
                     Constraint::Length(5),
                     Constraint::Length(7),
+                    Constraint::Length(6),
                     Constraint::Length(8),
                     Constraint::Length(8),
                 ],
@@ -439,10 +622,21 @@ fn draw_markets(f: &mut Frame, state: &AppState, area: Rect) {
 
     let header = Row::new(headers).style(Style::default().add_modifier(Modifier::BOLD));
 
+    let market_count = state
+        .markets
+        .iter()
+        .filter(|m| phase_filter_matches(state, &m.phase))
+        .count();
+    let selected = state
+        .market_scroll_offset
+        .min(market_count.saturating_sub(1));
+
     let rows: Vec<Row> = state
         .markets
         .iter()
-        .map(|m| {
+        .filter(|m| phase_filter_matches(state, &m.phase))
+        .enumerate()
+        .map(|(i, m)| {
             let edge_color = if m.edge > 0 { Color::Green } else { Color::Red };
             let ticker = truncate_with_ellipsis(&m.ticker, ticker_w);
             let mom_color = if m.momentum_score >= 75.0 {
@@ -461,8 +655,21 @@ fn draw_markets(f: &mut Frame, state: &AppState, area: Rect) {
             } else {
                 m.fair_value.to_string()
             };
+            let ticker_style = if state.recording_tickers.contains(&m.ticker) {
+                Style::default().fg(Color::Red)
+            } else if !state.game_filter.allows(&m.ticker) {
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::CROSSED_OUT)
+            } else if m.sprint {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
             let mut cells = vec![
-                Cell::from(ticker.into_owned()),
+                Cell::from(ticker.into_owned()).style(ticker_style),
                 Cell::from(fv_display),
                 Cell::from(m.bid.to_string()),
                 Cell::from(m.ask.to_string()),
@@ -482,9 +689,17 @@ fn draw_markets(f: &mut Frame, state: &AppState, area: Rect) {
                     None => Color::DarkGray,
                 };
                 cells.push(Cell::from(stale_text).style(Style::default().fg(stale_color)));
+                // Phase shares the Stale column's breakpoint
+                let phase_style = Style::default().fg(Color::Cyan);
+                cells.push(Cell::from(phase_text(&m.phase)).style(phase_style));
             }
             if !drop_action {
-                cells.push(Cell::from(m.action.clone()));
+                let action_color = if m.skip_reason.is_some() {
+                    Color::DarkGray
+                } else {
+                    Color::Green
+                };
+                cells.push(Cell::from(m.action.clone()).style(Style::default().fg(action_color)));
             }
             if !drop_latency {
                 cells.push(Cell::from(
@@ -493,7 +708,11 @@ fn draw_markets(f: &mut Frame, state: &AppState, area: Rect) {
                         .unwrap_or_else(|| "--".to_string()),
                 ));
             }
-            Row::new(cells)
+            if state.market_focus && i == selected {
+                Row::new(cells).style(Style::default().add_modifier(Modifier::REVERSED))
+            } else {
+                Row::new(cells)
+            }
         })
         .collect();
 
@@ -509,14 +728,32 @@ fn draw_markets(f: &mut Frame, state: &AppState, area: Rect) {
 
     let rows: Vec<Row> = rows.into_iter().skip(offset).take(visible_lines).collect();
 
+    let sprint_count = state.markets.iter().filter(|m| m.sprint).count();
+    let sprint_suffix = if sprint_count == 0 {
+        String::new()
+    } else {
+        format!(" [SPRINT {}]", sprint_count)
+    };
+    let rec_suffix = if state.recording_tickers.is_empty() {
+        sprint_suffix
+    } else {
+        format!(" [REC {}]{}", state.recording_tickers.len(), sprint_suffix)
+    };
+    let rec_suffix = if state.game_filter.whitelist_only() {
+        format!(" [WHITELIST ONLY]{}", rec_suffix)
+    } else {
+        rec_suffix
+    };
     let title = if state.market_focus {
         format!(
-            " Live Markets [{}/{} rows] ",
+            " Live Markets{}{} [{}/{} rows] ",
+            phase_filter_suffix(state),
+            rec_suffix,
             (offset + rows.len()).min(total),
             total,
         )
     } else {
-        " Live Markets ".to_string()
+        format!(" Live Markets{}{} ", phase_filter_suffix(state), rec_suffix)
     };
 
     let table = Table::new(rows, constraints)
@@ -537,6 +774,187 @@ fn format_age(elapsed: std::time::Duration) -> String {
     }
 }
 
+/// "score (espn)", "odds (draftkings)", ...
+fn fair_value_method_label(method: &crate::pipeline::FairValueMethod) -> String {
+    match method {
+        crate::pipeline::FairValueMethod::ScoreFeed { source } => format!("score ({})", source),
+        crate::pipeline::FairValueMethod::OddsFeed { source } => format!("odds ({})", source),
+        crate::pipeline::FairValueMethod::OrderFlow { source } => format!("flow ({})", source),
+    }
+}
+
+fn draw_position_detail(f: &mut Frame, state: &AppState, ticker: &str) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(10),
+            Constraint::Min(4),
+            Constraint::Length(1),
+        ])
+        .split(f.area());
+    let footer = Line::from(vec![
+        Span::styled("  [Enter/Esc]", Style::default().fg(Color::Yellow)),
+        Span::raw(" close  "),
+    ]);
+    f.render_widget(Paragraph::new(footer), chunks[2]);
+
+    let sim = state.sim_positions.iter().find(|p| p.ticker == ticker);
+    let live = state.positions.iter().find(|p| p.ticker == ticker);
+    let (quantity, entry_price, entry_fee, sell_price, filled_at, trace) = match (sim, live) {
+        (Some(p), _) => (
+            p.quantity,
+            p.entry_price,
+            p.entry_fee,
+            p.sell_price,
+            Some(p.filled_at),
+            p.trace.as_ref(),
+        ),
+        (None, Some(p)) => (
+            p.quantity,
+            p.entry_price,
+            p.entry_fee,
+            p.sell_price,
+            None,
+            None,
+        ),
+        (None, None) => {
+            let para = Paragraph::new(Line::from(Span::styled(
+                format!(" {} is no longer held", ticker),
+                Style::default().fg(Color::Yellow),
+            )))
+            .block(Block::default().title(" Position ").borders(Borders::ALL));
+            f.render_widget(para, chunks[0]);
+            return;
+        }
+    };
+
+    let fmt = &state.number_format;
+    let yes_bid = state.live_book.get(ticker).map_or(0, |&(yb, ..)| yb);
+    let entry_cost = (entry_price * quantity + entry_fee) as i64;
+    let pnl_span = |pnl: i64| {
+        let color = if pnl >= 0 { Color::Green } else { Color::Red };
+        Span::styled(fmt.money(pnl), Style::default().fg(color))
+    };
+    let mut position = vec![
+        Line::from(format!(
+            " {} contracts YES{}",
+            quantity,
+            filled_at.map_or(String::new(), |at| format!(
+                ", held {}",
+                format_age(at.elapsed())
+            ))
+        )),
+        Line::from(format!(
+            " Entry    {}c  fee {}c  cost {}",
+            entry_price,
+            entry_fee,
+            fmt.money(entry_cost)
+        )),
+    ];
+    if yes_bid > 0 {
+        // Marked as a sale into the bid, like the Mkt column
+        let exit_fee = calculate_fee(yes_bid, quantity, true) as i64;
+        position.push(Line::from(format!(
+            " Mark     {}c (YES bid)  exit fee {}c",
+            yes_bid, exit_fee
+        )));
+        position.push(Line::from(vec![
+            Span::raw(" Mkt P&L  "),
+            pnl_span((yes_bid * quantity) as i64 - exit_fee - entry_cost),
+        ]));
+    } else {
+        position.push(Line::from(" Mark     -- (no bid)"));
+    }
+    let target_fee = calculate_fee(sell_price, quantity, false) as i64;
+    position.push(Line::from(vec![
+        Span::raw(format!(
+            " Target   {}c  exit fee {}c  P&L ",
+            sell_price, target_fee
+        )),
+        pnl_span((sell_price * quantity) as i64 - target_fee - entry_cost),
+    ]));
+
+    let provenance = match trace {
+        Some(t) => vec![
+            Line::from(format!(
+                " {}  {}",
+                t.sport,
+                fair_value_method_label(&t.fair_value_method)
+            )),
+            Line::from(format!(
+                " Fair value {}c  {}",
+                t.fair_value_cents,
+                crate::pipeline::format_fair_value_basis(t)
+            )),
+            Line::from(format!(" Phase {}", crate::pipeline::trace_phase(t))),
+            Line::from(format!(
+                " Signal   {}/{}c  edge {:+}c",
+                t.best_bid, t.best_ask, t.edge
+            )),
+            Line::from(format!(
+                " {} {}x  net est {:+}c",
+                t.action, t.quantity, t.net_profit_estimate
+            )),
+            Line::from(format!(
+                " Momentum {:.0}{}",
+                t.momentum_score,
+                if t.momentum_gated { " (gated)" } else { "" }
+            )),
+        ],
+        None => vec![Line::from(Span::styled(
+            " No signal trace (manual or reconciled entry)",
+            Style::default().fg(Color::DarkGray),
+        ))],
+    };
+
+    let panels = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[0]);
+    f.render_widget(
+        Paragraph::new(position).block(
+            Block::default()
+                .title(format!(" Position {} ", ticker))
+                .borders(Borders::ALL),
+        ),
+        panels[0],
+    );
+    f.render_widget(
+        Paragraph::new(provenance).block(
+            Block::default()
+                .title(" Signal Provenance ")
+                .borders(Borders::ALL),
+        ),
+        panels[1],
+    );
+
+    // Offset the bids so the chart spans their range rather than 0-100c
+    let width = chunks[1].width.saturating_sub(2) as usize;
+    let bids = state.price_history.downsample(ticker, width);
+    let (lo, hi) = (
+        bids.iter().copied().min().unwrap_or(0),
+        bids.iter().copied().max().unwrap_or(0),
+    );
+    let data: Vec<u64> = bids.iter().map(|&b| (b - lo + 1) as u64).collect();
+    let title = match state.price_history.since(ticker) {
+        Some(since) if !bids.is_empty() => format!(
+            " YES bid, last {}: {}-{}c, now {}c ",
+            format_age(since.elapsed()),
+            lo,
+            hi,
+            bids.last().copied().unwrap_or(0)
+        ),
+        _ => " YES bid: no samples yet ".to_string(),
+    };
+    f.render_widget(
+        Sparkline::default()
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .data(&data)
+            .style(Style::default().fg(Color::Cyan)),
+        chunks[1],
+    );
+}
+
 fn draw_positions(f: &mut Frame, state: &AppState, area: Rect) {
     let inner_width = area.width.saturating_sub(2) as usize;
 
@@ -610,9 +1028,13 @@ fn draw_positions(f: &mut Frame, state: &AppState, area: Rect) {
     // TODO: use state.positions when real mode is implemented
     let positions = &state.sim_positions;
 
+    let selected = state
+        .position_scroll_offset
+        .min(positions.len().saturating_sub(1));
     let rows: Vec<Row> = positions
         .iter()
-        .map(|sp| {
+        .enumerate()
+        .map(|(i, sp)| {
             let ticker = truncate_with_ellipsis(&sp.ticker, ticker_w);
 
             // Look up live prices
@@ -707,8 +1129,10 @@ fn draw_positions(f: &mut Frame, state: &AppState, area: Rect) {
                     .trace
                     .as_ref()
                     .map(|t| match &t.fair_value_method {
+                        _ if sp.is_pre_game() => "pre",
                         crate::pipeline::FairValueMethod::ScoreFeed { .. } => "score",
                         crate::pipeline::FairValueMethod::OddsFeed { .. } => "odds",
+                        crate::pipeline::FairValueMethod::OrderFlow { .. } => "flow",
                     })
                     .unwrap_or("\u{2014}");
                 cells.push(
@@ -716,7 +1140,11 @@ fn draw_positions(f: &mut Frame, state: &AppState, area: Rect) {
                 );
             }
 
-            Row::new(cells)
+            if state.position_focus && i == selected {
+                Row::new(cells).style(Style::default().add_modifier(Modifier::REVERSED))
+            } else {
+                Row::new(cells)
+            }
         })
         .collect();
 
@@ -732,14 +1160,22 @@ fn draw_positions(f: &mut Frame, state: &AppState, area: Rect) {
 
     let rows: Vec<Row> = rows.into_iter().skip(offset).take(visible_lines).collect();
 
+    // Pre-game positions are counted apart from live ones
+    let pre_game = positions.iter().filter(|p| p.is_pre_game()).count();
+    let pre_game_suffix = if pre_game > 0 {
+        format!("({} pre-game) ", pre_game)
+    } else {
+        String::new()
+    };
     let title = if state.position_focus {
         format!(
-            " Open Positions [{}/{}] ",
+            " Open Positions {}[{}/{}] ",
+            pre_game_suffix,
             (offset + rows.len()).min(total),
             total,
         )
     } else {
-        " Open Positions ".to_string()
+        format!(" Open Positions {}", pre_game_suffix)
     };
 
     let table = Table::new(rows, constraints)
@@ -753,7 +1189,12 @@ fn draw_trades(f: &mut Frame, state: &AppState, area: Rect) {
     let inner_width = area.width.saturating_sub(2) as usize;
     let visible_lines = area.height.saturating_sub(4) as usize; // borders + header + padding
 
-    let total = state.trades.len();
+    let trades: Vec<&TradeRow> = state
+        .trades
+        .iter()
+        .filter(|t| phase_filter_matches(state, &t.phase))
+        .collect();
+    let total = trades.len();
 
     let offset = if state.trade_focus {
         state
@@ -764,10 +1205,16 @@ fn draw_trades(f: &mut Frame, state: &AppState, area: Rect) {
     };
 
     // Fixed column widths: Time=8 Action=4 Price=6 Qty=4 Type=5 P&L=7 Slip=6 = 40
-    // Optional: SRC=6
+    // Optional: SRC=6, Phase=6
     let base_fixed: usize = 8 + 4 + 6 + 4 + 5 + 7 + 6; // 40
     let show_src = inner_width >= base_fixed + 6 + 8; // need room for SRC + reasonable ticker
-    let fixed_cols = base_fixed + if show_src { 6 } else { 0 };
+    let show_phase = inner_width >= base_fixed + 6 + 6 + 8;
+    // Notes are only worth the width while reviewing in the focused view
+    let show_note = state.trade_focus && inner_width >= base_fixed + 6 + 6 + 14 + 8;
+    let fixed_cols = base_fixed
+        + if show_src { 6 } else { 0 }
+        + if show_phase { 6 } else { 0 }
+        + if show_note { 14 } else { 0 };
     let ticker_w = inner_width.saturating_sub(fixed_cols).max(4);
 
     let mut headers = vec![
@@ -776,6 +1223,12 @@ fn draw_trades(f: &mut Frame, state: &AppState, area: Rect) {
     if show_src {
         headers.push("SRC");
     }
+    if show_phase {
+        headers.push("Phase");
+    }
+    if show_note {
+        headers.push("Note");
+    }
     let header = Row::new(headers).style(Style::default().add_modifier(Modifier::BOLD));
 
     let mut constraints = vec![
@@ -791,14 +1244,21 @@ fn draw_trades(f: &mut Frame, state: &AppState, area: Rect) {
     if show_src {
         constraints.push(Constraint::Length(6));
     }
+    if show_phase {
+        constraints.push(Constraint::Length(6));
+    }
+    if show_note {
+        constraints.push(Constraint::Length(14));
+    }
+    let selected = state.trade_scroll_offset.min(total.saturating_sub(1));
 
-    let rows: Vec<Row> = state
-        .trades
-        .iter()
+    let rows: Vec<Row> = trades
+        .into_iter()
         .rev()
+        .enumerate()
         .skip(offset)
         .take(if state.trade_focus { visible_lines } else { 4 })
-        .map(|t| {
+        .map(|(i, t)| {
             let pnl_cell = match t.pnl {
                 Some(p) if p > 0 => {
                     Cell::from(format!("{:+}c", p)).style(Style::default().fg(Color::Green))
@@ -841,7 +1301,19 @@ fn draw_trades(f: &mut Frame, state: &AppState, area: Rect) {
                 };
                 cells.push(Cell::from(src_text).style(Style::default().fg(Color::DarkGray)));
             }
-            Row::new(cells)
+            if show_phase {
+                let phase_style = Style::default().fg(Color::Cyan);
+                cells.push(Cell::from(phase_text(&t.phase)).style(phase_style));
+            }
+            if show_note {
+                let note = truncate_with_ellipsis(&t.note, 14).into_owned();
+                cells.push(Cell::from(note).style(Style::default().fg(Color::Magenta)));
+            }
+            if state.trade_focus && i == selected {
+                Row::new(cells).style(Style::default().add_modifier(Modifier::REVERSED))
+            } else {
+                Row::new(cells)
+            }
         })
         .collect();
 
@@ -849,12 +1321,13 @@ fn draw_trades(f: &mut Frame, state: &AppState, area: Rect) {
 
     let title = if state.trade_focus {
         format!(
-            " Recent Trades [{}/{}] ",
+            " Recent Trades{} [{}/{}] ",
+            phase_filter_suffix(state),
             (offset + shown).min(total),
             total,
         )
     } else {
-        " Recent Trades ".to_string()
+        format!(" Recent Trades{} ", phase_filter_suffix(state))
     };
 
     let table = Table::new(rows, constraints)
@@ -864,6 +1337,26 @@ fn draw_trades(f: &mut Frame, state: &AppState, area: Rect) {
     f.render_widget(table, area);
 }
 
+fn phase_filter_matches(state: &AppState, phase: &str) -> bool {
+    state.phase_filter.as_deref().is_none_or(|f| f == phase)
+}
+
+fn phase_filter_suffix(state: &AppState) -> String {
+    state
+        .phase_filter
+        .as_deref()
+        .map(|p| format!(" ({})", p))
+        .unwrap_or_default()
+}
+
+fn phase_text(phase: &str) -> String {
+    if phase.is_empty() {
+        "\u{2014}".to_string()
+    } else {
+        phase.to_string()
+    }
+}
+
 fn draw_logs(f: &mut Frame, state: &AppState, area: Rect) {
     let max_width = area.width.saturating_sub(2) as usize; // borders
     let visible_lines = area.height.saturating_sub(2) as usize;
@@ -888,10 +1381,11 @@ fn draw_logs(f: &mut Frame, state: &AppState, area: Rect) {
                 "ERROR" => Color::Red,
                 "WARN" => Color::Yellow,
                 "TRADE" => Color::Cyan,
+                "ARB" => Color::Magenta,
                 _ => Color::DarkGray,
             };
             let prefix = format!(" {} [{}] ", l.time, l.level);
-            let prefix_len = prefix.len();
+            let prefix_len = prefix.width();
             let msg_max = max_width.saturating_sub(prefix_len);
             let msg = truncate_with_ellipsis(&l.message, msg_max);
             Line::from(vec![
@@ -916,6 +1410,59 @@ fn draw_logs(f: &mut Frame, state: &AppState, area: Rect) {
     f.render_widget(para, area);
 }
 
+fn draw_preflight(f: &mut Frame, state: &AppState, area: Rect) {
+    let Some(report) = state.preflight.as_ref() else {
+        return;
+    };
+
+    let (verdict, color) = if report.passed() {
+        ("PASS", Color::Green)
+    } else {
+        ("FAIL", Color::Red)
+    };
+    let mut spans = vec![
+        Span::styled(
+            format!(" {} ", verdict),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!(
+            "{} 1x @{}c at {}",
+            report.ticker,
+            report.price,
+            report.ran_at.format("%H:%M:%S")
+        )),
+    ];
+    for step in &report.steps {
+        let latency = step
+            .latency_ms
+            .map(|ms| format!(" {}ms", ms))
+            .unwrap_or_default();
+        let step_color = if step.ok { Color::Green } else { Color::Red };
+        spans.push(Span::styled(
+            format!(
+                " | {} {}{}",
+                step.name,
+                if step.ok { "ok" } else { "FAIL" },
+                latency
+            ),
+            Style::default().fg(step_color),
+        ));
+    }
+    if let Some(step) = report.failure() {
+        spans.push(Span::styled(
+            format!(" | {}", step.detail),
+            Style::default().fg(Color::Red),
+        ));
+    }
+
+    let para = Paragraph::new(Line::from(spans)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Order-Path Preflight "),
+    );
+    f.render_widget(para, area);
+}
+
 fn draw_api_status(f: &mut Frame, state: &AppState, area: Rect) {
     let hours_left = if state.api_hours_remaining.is_infinite() {
         "\u{221e}".to_string()
@@ -943,27 +1490,98 @@ fn draw_api_status(f: &mut Frame, state: &AppState, area: Rect) {
         Color::DarkGray
     };
 
-    let line = Line::from(vec![
+    let mut spans = vec![
         Span::styled(quota_str, Style::default().fg(color)),
         Span::styled(filter_str, Style::default().fg(Color::DarkGray)),
-    ]);
-    let para = Paragraph::new(line);
+    ];
+
+    let schema_drift: u64 = state.odds_schema_drift.values().sum();
+    if schema_drift > 0 {
+        spans.push(Span::styled(
+            format!(" | Schema drift: {}", schema_drift),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
+    let fees = state.fee_tracker.stats();
+    if fees.samples > 0 {
+        let fee_color = if fees.divergence_cents() > 0 {
+            Color::Yellow
+        } else {
+            Color::DarkGray
+        };
+        spans.push(Span::styled(
+            format!(
+                " | Fees: {}c exp / {}c real ({:+.1}%)",
+                fees.expected_cents,
+                fees.realized_cents,
+                fees.divergence_pct()
+            ),
+            Style::default().fg(fee_color),
+        ));
+    }
+
+    let para = Paragraph::new(Line::from(spans));
     f.render_widget(para, area);
 }
 
 fn draw_footer(f: &mut Frame, state: &AppState, area: Rect) {
-    let line = if state.log_focus || state.market_focus || state.position_focus || state.trade_focus
-    {
+    let line = if let Some(buffer) = &state.trade_note_edit {
         Line::from(vec![
+            Span::styled("  Note: ", Style::default().fg(Color::Yellow)),
+            Span::raw(format!("{}_", buffer)),
+            Span::styled(
+                "  [Tab] preset  [Enter] save  [Esc] cancel",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ])
+    } else if state.log_focus || state.market_focus || state.position_focus || state.trade_focus
+    {
+        let mut spans = vec![
             Span::styled("  [Esc]", Style::default().fg(Color::Yellow)),
             Span::raw(" back  "),
             Span::styled("[j/k]", Style::default().fg(Color::Yellow)),
             Span::raw(" scroll  "),
             Span::styled("[g/G]", Style::default().fg(Color::Yellow)),
             Span::raw(" top/bottom  "),
-        ])
+        ];
+        if state.market_focus || state.trade_focus {
+            spans.push(Span::styled("[f]", Style::default().fg(Color::Yellow)));
+            spans.push(Span::raw(" phase  "));
+        }
+        if state.market_focus {
+            spans.push(Span::styled("[b/s]", Style::default().fg(Color::Yellow)));
+            spans.push(Span::raw(" buy/sell  "));
+            spans.push(Span::styled("[r]", Style::default().fg(Color::Yellow)));
+            spans.push(Span::raw(" record book  "));
+            spans.push(Span::styled("[v]", Style::default().fg(Color::Yellow)));
+            spans.push(Span::raw(" replay  "));
+            spans.push(Span::styled("[Enter]", Style::default().fg(Color::Yellow)));
+            spans.push(Span::raw(" depth  "));
+            spans.push(Span::styled("[x/X]", Style::default().fg(Color::Yellow)));
+            spans.push(Span::raw(" block game/market  "));
+            spans.push(Span::styled("[a]", Style::default().fg(Color::Yellow)));
+            spans.push(Span::raw("llow game  "));
+        }
+        if state.position_focus {
+            spans.push(Span::styled("[Enter]", Style::default().fg(Color::Yellow)));
+            spans.push(Span::raw(" detail  "));
+        }
+        if state.trade_focus {
+            spans.push(Span::styled("[n]", Style::default().fg(Color::Yellow)));
+            spans.push(Span::raw("ote  "));
+        }
+        Line::from(spans)
     } else {
-        Line::from(vec![
+        let mut spans = Vec::new();
+        let open_alerts = state.escalation.unacknowledged();
+        if open_alerts > 0 {
+            spans.push(Span::styled(
+                format!("  {} CRITICAL [a]ck", open_alerts),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
+        spans.extend([
             Span::styled("  [q]", Style::default().fg(Color::Yellow)),
             Span::raw("uit  "),
             Span::styled("[p]", Style::default().fg(Color::Yellow)),
@@ -982,7 +1600,29 @@ fn draw_footer(f: &mut Frame, state: &AppState, area: Rect) {
             Span::raw("iag  "),
             Span::styled("[c]", Style::default().fg(Color::Yellow)),
             Span::raw("onfig  "),
-        ])
+            Span::styled("[w]", Style::default().fg(Color::Yellow)),
+            Span::raw("in-prob  "),
+            Span::styled("[s]", Style::default().fg(Color::Yellow)),
+            Span::raw("ummary  "),
+            Span::styled("[R]", Style::default().fg(Color::Yellow)),
+            Span::raw("eload-keys  "),
+            Span::styled("[S]", Style::default().fg(Color::Yellow)),
+            Span::raw("napshot  "),
+        ]);
+        if state.profiling {
+            spans.push(Span::styled(
+                "PROFILING",
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        } else {
+            spans.extend([
+                Span::styled("[F]", Style::default().fg(Color::Yellow)),
+                Span::raw("lame  "),
+            ]);
+        }
+        Line::from(spans)
     };
     let para = Paragraph::new(line);
     f.render_widget(para, area);
@@ -991,7 +1631,7 @@ fn draw_footer(f: &mut Frame, state: &AppState, area: Rect) {
 fn draw_sport_legend(f: &mut Frame, state: &AppState, area: Rect) {
     let mut spans: Vec<Span> = vec![Span::raw("  ")];
 
-    for (_key, label, hotkey, enabled) in &state.sport_toggles {
+    for (key, label, hotkey, enabled) in &state.sport_toggles {
         let style = if *enabled {
             Style::default().fg(Color::Green)
         } else {
@@ -1003,6 +1643,34 @@ fn draw_sport_legend(f: &mut Frame, state: &AppState, area: Rect) {
             Style::default().fg(Color::Yellow),
         ));
         spans.push(Span::styled(label.as_str(), style));
+        // Score-model vs odds FV gap, shown while validate_fair_value is on
+        if let Some(d) = state.fv_disagreement.get(key) {
+            let color = if d.alerting {
+                Color::Red
+            } else {
+                Color::DarkGray
+            };
+            spans.push(Span::styled(
+                format!(" \u{0394}{:.1}c({:+.0})", d.avg_abs, d.bias),
+                Style::default().fg(color),
+            ));
+        }
+        // Median edge lifetime on the book, and the tuned taker threshold
+        if let Some(e) = state.edge_persistence.get(key) {
+            let tuned = e
+                .tuned_threshold
+                .map(|t| format!(" T\u{2265}{}", t))
+                .unwrap_or_default();
+            spans.push(Span::styled(
+                format!(
+                    " \u{29d7}{:.1}s/{}{}",
+                    e.median_ms as f64 / 1000.0,
+                    e.samples,
+                    tuned
+                ),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
         spans.push(Span::raw(" "));
     }
 
@@ -1011,6 +1679,491 @@ fn draw_sport_legend(f: &mut Frame, state: &AppState, area: Rect) {
     f.render_widget(para, area);
 }
 
+fn draw_book_replay(f: &mut Frame, replay: &crate::book_replay::BookReplay, cursor: usize) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(f.area());
+
+    let Some(idx) = replay
+        .frames
+        .len()
+        .checked_sub(1)
+        .map(|last| cursor.min(last))
+    else {
+        let para = Paragraph::new(Line::from(Span::styled(
+            format!(
+                " No frames recorded for {} \u{2014} press [r] in the markets view to record",
+                replay.ticker
+            ),
+            Style::default().fg(Color::Yellow),
+        )))
+        .block(
+            Block::default()
+                .title(" Book Replay ")
+                .borders(Borders::ALL),
+        );
+        f.render_widget(para, chunks[0]);
+        draw_book_replay_footer(f, chunks[2]);
+        return;
+    };
+    let frame = &replay.frames[idx];
+
+    let yes_bid = frame.book.yes.first().map_or(0, |(p, _)| *p);
+    let no_bid = frame.book.no.first().map_or(0, |(p, _)| *p);
+    let yes_ask = if no_bid > 0 { 100 - no_bid } else { 0 };
+    let since_start = frame.at - replay.frames[0].at;
+    let header = Line::from(vec![
+        Span::styled(
+            format!(" {} ", replay.ticker),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!("frame {}/{}  ", idx + 1, replay.frames.len())),
+        Span::raw(format!(
+            "{} (+{:.3}s)  ",
+            frame.at.format("%H:%M:%S%.3f"),
+            since_start.num_milliseconds() as f64 / 1000.0
+        )),
+        Span::styled(
+            frame.event.clone(),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("  YES {}/{}", yes_bid, yes_ask),
+            Style::default().fg(Color::Cyan),
+        ),
+    ]);
+    let para = Paragraph::new(header).block(
+        Block::default()
+            .title(" Book Replay ")
+            .borders(Borders::ALL),
+    );
+    f.render_widget(para, chunks[0]);
+
+    let sides = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+    for (area, side, levels) in [
+        (sides[0], "yes", &frame.book.yes),
+        (sides[1], "no", &frame.book.no),
+    ] {
+        let rows: Vec<Row> = levels
+            .iter()
+            .map(|&(price, qty)| {
+                let change = replay.level_change(idx, side, price);
+                let change_cell = if change == 0 {
+                    Cell::from("")
+                } else {
+                    let color = if change > 0 { Color::Green } else { Color::Red };
+                    Cell::from(format!("{:+}", change)).style(Style::default().fg(color))
+                };
+                let row = Row::new(vec![
+                    Cell::from(format!("{}c", price)),
+                    Cell::from(qty.to_string()),
+                    change_cell,
+                ]);
+                if change != 0 {
+                    row.style(Style::default().add_modifier(Modifier::BOLD))
+                } else {
+                    row
+                }
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(6),
+                Constraint::Length(8),
+                Constraint::Length(8),
+            ],
+        )
+        .header(
+            Row::new(vec!["Price", "Qty", "Chg"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .block(
+            Block::default()
+                .title(format!(" {} Bids ", side.to_uppercase()))
+                .borders(Borders::ALL),
+        );
+        f.render_widget(table, area);
+    }
+
+    draw_book_replay_footer(f, chunks[2]);
+}
+
+fn draw_book_replay_footer(f: &mut Frame, area: Rect) {
+    let line = Line::from(vec![
+        Span::styled("  [v/Esc]", Style::default().fg(Color::Yellow)),
+        Span::raw(" close  "),
+        Span::styled("[h/l]", Style::default().fg(Color::Yellow)),
+        Span::raw(" step  "),
+        Span::styled("[PgUp/PgDn]", Style::default().fg(Color::Yellow)),
+        Span::raw(" \u{00b1}50  "),
+        Span::styled("[g/G]", Style::default().fg(Color::Yellow)),
+        Span::raw(" first/last  "),
+    ]);
+    let para = Paragraph::new(line);
+    f.render_widget(para, area);
+}
+
+fn draw_depth_ladder(f: &mut Frame, state: &AppState, ladder: &DepthLadder) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(f.area());
+
+    let yes_bid = ladder.book.yes.first().map_or(0, |(p, _)| *p);
+    let no_bid = ladder.book.no.first().map_or(0, |(p, _)| *p);
+    let yes_ask = if no_bid > 0 { 100 - no_bid } else { 0 };
+    let mut header = vec![
+        Span::styled(
+            format!(" {} ", ladder.ticker),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(" YES {}/{}", yes_bid, yes_ask),
+            Style::default().fg(Color::Cyan),
+        ),
+    ];
+    if let Some(m) = state.markets.iter().find(|m| m.ticker == ladder.ticker) {
+        header.push(Span::raw(format!(
+            "  fair {}c  edge {:+}c  {}",
+            m.fair_value, m.edge, m.action
+        )));
+    }
+    if ladder.book.yes.is_empty() && ladder.book.no.is_empty() {
+        header.push(Span::styled(
+            "  no book yet",
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    let para = Paragraph::new(Line::from(header)).block(
+        Block::default()
+            .title(" Depth Ladder ")
+            .borders(Borders::ALL),
+    );
+    f.render_widget(para, chunks[0]);
+
+    // Buying YES lifts NO bids and vice versa, so each table's title names
+    // the asks it offers too
+    let sides = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+    for (area, title, levels) in [
+        (sides[0], " YES Bids (NO asks at 100-p) ", &ladder.book.yes),
+        (sides[1], " NO Bids (YES asks at 100-p) ", &ladder.book.no),
+    ] {
+        let rows: Vec<Row> = ladder_levels(levels, DEPTH_LADDER_LEVELS)
+            .into_iter()
+            .map(|(price, qty, cumulative)| {
+                Row::new(vec![
+                    Cell::from(format!("{}c", price)),
+                    Cell::from(format!("{}c", 100 - price)),
+                    Cell::from(qty.to_string()),
+                    Cell::from(cumulative.to_string()),
+                ])
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(6),
+                Constraint::Length(6),
+                Constraint::Length(8),
+                Constraint::Length(8),
+            ],
+        )
+        .header(
+            Row::new(vec!["Bid", "Ask", "Qty", "Cum"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .block(Block::default().title(title).borders(Borders::ALL));
+        f.render_widget(table, area);
+    }
+
+    let line = Line::from(vec![
+        Span::styled("  [Enter/Esc]", Style::default().fg(Color::Yellow)),
+        Span::raw(" close  "),
+    ]);
+    f.render_widget(Paragraph::new(line), chunks[2]);
+}
+
+/// Heat color for a home fair value: green as home nears a sure win, red as
+/// away does, gray around a coin flip.
+fn win_prob_color(fair: u32) -> Color {
+    match fair {
+        90.. => Color::Green,
+        70..=89 => Color::LightGreen,
+        56..=69 => Color::LightYellow,
+        45..=55 => Color::Gray,
+        31..=44 => Color::Yellow,
+        11..=30 => Color::LightRed,
+        _ => Color::Red,
+    }
+}
+
+/// "mm:ss" of regulation time remaining.
+fn format_remaining(secs: u16) -> String {
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+fn draw_win_prob(f: &mut Frame, state: &AppState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(f.area());
+
+    let selected = state
+        .win_prob_sport
+        .min(state.win_prob_grids.len().saturating_sub(1));
+    let Some(grid) = state.win_prob_grids.get(selected) else {
+        let para = Paragraph::new(Line::from(Span::styled(
+            " No score-feed sports \u{2014} set fair_value = \"score-feed\" with a [win_prob] section",
+            Style::default().fg(Color::Yellow),
+        )))
+        .block(Block::default().title(" Win Prob ").borders(Borders::ALL));
+        f.render_widget(para, chunks[0]);
+        draw_win_prob_footer(f, chunks[2]);
+        return;
+    };
+
+    let titles: Vec<Line> = state
+        .win_prob_grids
+        .iter()
+        .map(|g| Line::from(g.sport.clone()))
+        .collect();
+    let tabs = Tabs::new(titles)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Win Prob (home fair, score diff \u{00d7} time left) "),
+        )
+        .highlight_style(Style::default().fg(Color::Yellow))
+        .select(selected);
+    f.render_widget(tabs, chunks[0]);
+
+    let header = std::iter::once(Cell::from("Diff"))
+        .chain(
+            grid.remaining_secs
+                .iter()
+                .map(|&r| Cell::from(format_remaining(r))),
+        )
+        .collect::<Vec<_>>();
+    let rows: Vec<Row> = grid
+        .score_diffs
+        .iter()
+        .zip(&grid.cells)
+        .map(|(diff, cells)| {
+            let mut row = vec![Cell::from(format!("{:+}", diff))];
+            row.extend(cells.iter().map(|&(fair, clamped)| {
+                let style = Style::default().fg(Color::Black).bg(win_prob_color(fair));
+                if clamped {
+                    Cell::from(format!("{:>3}!", fair))
+                        .style(style.fg(Color::Magenta).add_modifier(Modifier::BOLD))
+                } else {
+                    Cell::from(format!("{:>3}", fair)).style(style)
+                }
+            }));
+            Row::new(row)
+        })
+        .collect();
+    let widths = std::iter::once(Constraint::Length(5))
+        .chain(grid.remaining_secs.iter().map(|_| Constraint::Length(6)))
+        .collect::<Vec<_>>();
+    let table = Table::new(rows, widths)
+        .header(Row::new(header).style(Style::default().add_modifier(Modifier::BOLD)))
+        .block(
+            Block::default()
+                .title(" ! = clamped by a sanity bound ")
+                .borders(Borders::ALL),
+        );
+    f.render_widget(table, chunks[1]);
+
+    draw_win_prob_footer(f, chunks[2]);
+}
+
+fn draw_win_prob_footer(f: &mut Frame, area: Rect) {
+    let line = Line::from(vec![
+        Span::styled("  [w/Esc]", Style::default().fg(Color::Yellow)),
+        Span::raw(" close  "),
+        Span::styled("[h/l]", Style::default().fg(Color::Yellow)),
+        Span::raw(" sport  "),
+    ]);
+    let para = Paragraph::new(line);
+    f.render_widget(para, area);
+}
+
+fn draw_portfolio(f: &mut Frame, state: &AppState) {
+    let footer = Line::from(vec![
+        Span::styled("  [s/Esc]", Style::default().fg(Color::Yellow)),
+        Span::raw(" close  "),
+        Span::styled("[r]", Style::default().fg(Color::Yellow)),
+        Span::raw(" refresh  "),
+    ]);
+    let Some(summary) = &state.portfolio else {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(f.area());
+        let para = Paragraph::new(Line::from(Span::styled(
+            " Reading the journal...",
+            Style::default().fg(Color::Yellow),
+        )))
+        .block(Block::default().title(" Portfolio ").borders(Borders::ALL));
+        f.render_widget(para, chunks[0]);
+        f.render_widget(Paragraph::new(footer), chunks[2]);
+        return;
+    };
+
+    let table_height = |rows: usize| rows as u16 + 3;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(table_height(1)),
+            Constraint::Length(8),
+            Constraint::Length(table_height(summary.by_sport.len())),
+            Constraint::Min(table_height(summary.by_source.len())),
+            Constraint::Length(1),
+        ])
+        .split(f.area());
+
+    let fmt = &state.number_format;
+    let title = if state.sim_mode {
+        " Portfolio Today (sim) "
+    } else {
+        " Portfolio Today "
+    };
+    let sections: [(&str, &[crate::portfolio::PnlRow]); 3] = [
+        (title, std::slice::from_ref(&summary.total)),
+        (" By Sport ", &summary.by_sport),
+        (" By Fair-Value Source ", &summary.by_source),
+    ];
+    let tables = [chunks[0], chunks[2], chunks[3]];
+    for ((title, rows), area) in sections.into_iter().zip(tables) {
+        draw_pnl_table(f, fmt, title, rows, area);
+    }
+    draw_equity_curve(f, state, chunks[1]);
+    f.render_widget(Paragraph::new(footer), chunks[4]);
+}
+
+fn draw_equity_curve(f: &mut Frame, state: &AppState, area: Rect) {
+    let fmt = &state.number_format;
+    let Some(stats) = state.equity.stats() else {
+        let para = Paragraph::new(Line::from(Span::styled(
+            format!(
+                " First sample within {}",
+                format_age(state.equity.interval())
+            ),
+            Style::default().fg(Color::DarkGray),
+        )))
+        .block(Block::default().title(" Equity ").borders(Borders::ALL));
+        f.render_widget(para, area);
+        return;
+    };
+    // Offset the curve so the chart spans its range rather than from zero
+    let points = state
+        .equity
+        .downsample(area.width.saturating_sub(2) as usize);
+    let lo = points.iter().copied().min().unwrap_or(0);
+    let data: Vec<u64> = points.iter().map(|&e| (e - lo + 1) as u64).collect();
+    let title = format!(
+        " Equity {} (peak {}), max drawdown {} ({:.1}%), Sharpe {} ",
+        fmt.money(stats.current_cents),
+        fmt.money(stats.peak_cents),
+        fmt.money(stats.max_drawdown_cents),
+        stats.max_drawdown_pct * 100.0,
+        stats
+            .sharpe
+            .map_or("\u{2014}".to_string(), |s| format!("{:.2}", s)),
+    );
+    let color = if stats.current_cents >= stats.start_cents {
+        Color::Green
+    } else {
+        Color::Red
+    };
+    f.render_widget(
+        Sparkline::default()
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .data(&data)
+            .style(Style::default().fg(color)),
+        area,
+    );
+}
+
+fn draw_pnl_table(
+    f: &mut Frame,
+    fmt: &crate::locale::NumberFormat,
+    title: &str,
+    rows: &[crate::portfolio::PnlRow],
+    area: Rect,
+) {
+    let pnl_cell = |cents: i64| {
+        let color = match cents {
+            c if c > 0 => Color::Green,
+            c if c < 0 => Color::Red,
+            _ => Color::DarkGray,
+        };
+        Cell::from(fmt.money(cents)).style(Style::default().fg(color))
+    };
+    let optional = |v: Option<String>| v.unwrap_or_else(|| "\u{2014}".to_string());
+    let header = [
+        "",
+        "Realized",
+        "Unrealized",
+        "Closed",
+        "Win%",
+        "Avg Edge",
+        "Captured",
+        "Fee Drag",
+    ]
+    .into_iter()
+    .map(Cell::from)
+    .collect::<Vec<_>>();
+    let table_rows: Vec<Row> = rows
+        .iter()
+        .map(|r| {
+            Row::new(vec![
+                Cell::from(r.label.clone()),
+                pnl_cell(r.realized_cents),
+                pnl_cell(r.unrealized_cents),
+                Cell::from(r.closed.to_string()),
+                Cell::from(optional(r.win_rate().map(|w| format!("{:.0}%", w * 100.0)))),
+                Cell::from(optional(r.avg_edge().map(|e| format!("{:.1}c", e)))),
+                Cell::from(optional(r.captured().map(|c| format!("{:+.1}c", c)))),
+                Cell::from(fmt.money(r.fees_cents)),
+            ])
+        })
+        .collect();
+    let widths = std::iter::once(Constraint::Length(12))
+        .chain(std::iter::repeat_n(Constraint::Length(11), 7))
+        .collect::<Vec<_>>();
+    let table = Table::new(table_rows, widths)
+        .header(Row::new(header).style(Style::default().add_modifier(Modifier::BOLD)))
+        .block(Block::default().title(title).borders(Borders::ALL));
+    f.render_widget(table, area);
+}
+
 fn draw_diagnostic_header(f: &mut Frame, state: &AppState, area: Rect) {
     let mode_tag = if state.diagnostic_snapshot {
         Span::styled(" (Snapshot)", Style::default().fg(Color::Yellow))
@@ -1024,6 +2177,31 @@ fn draw_diagnostic_header(f: &mut Frame, state: &AppState, area: Rect) {
         Style::default().fg(Color::DarkGray),
     );
 
+    let mem = &state.memory;
+    let rss = mem
+        .rss_bytes
+        .map_or("n/a".to_string(), |b| format!("{} MB", b / (1024 * 1024)));
+    let memory_span = Span::styled(
+        format!(
+            "  mem {} | {} games, {} books, {} trackers | last compaction -{}",
+            rss, mem.indexed_games, mem.books, mem.trackers, mem.last_compacted
+        ),
+        Style::default().fg(Color::DarkGray),
+    );
+
+    let ws = &state.ws_queue;
+    let ws_span = Span::styled(
+        format!(
+            "  | ws queue {} (peak {}), {} coalesced, {} dropped, {} stalls",
+            ws.depth, ws.peak_depth, ws.coalesced, ws.dropped, ws.stalls
+        ),
+        Style::default().fg(if ws.stalls > 0 {
+            Color::Yellow
+        } else {
+            Color::DarkGray
+        }),
+    );
+
     let title_line = Line::from(vec![
         Span::styled(
             " All Games from All Sources",
@@ -1031,6 +2209,8 @@ fn draw_diagnostic_header(f: &mut Frame, state: &AppState, area: Rect) {
         ),
         mode_tag,
         count_span,
+        memory_span,
+        ws_span,
     ]);
 
     let block = Block::default()
@@ -1080,12 +2260,15 @@ fn draw_diagnostic(f: &mut Frame, state: &AppState, area: Rect) {
     }
 
     // Responsive column widths
-    // Full columns: Matchup + Commence(14) + Status(10) + Ticker(16) + Market(8) + Reason(18) + Source(10)
+    // Full columns: Matchup + Commence(14) + Status(10) + Ticker(16) + Market(8) + Reason(18) + Source(10) + Health(9)
     let show_source = inner_width >= 96; // Need enough width for source column
-    let fixed_cols = 14 + 10 + 16 + 8 + 18 + if show_source { 10 } else { 0 };
+    let show_health = show_source && inner_width >= 105;
+    let fixed_cols =
+        14 + 10 + 16 + 8 + 18 + if show_source { 10 } else { 0 } + if show_health { 9 } else { 0 };
     let matchup_w = inner_width.saturating_sub(fixed_cols).max(10);
 
     // Build display lines: sport headers + data rows
+    let selected = state.selected_diagnostic(state.diagnostic_scroll_offset);
     let mut display_rows: Vec<Row> = Vec::new();
     for (sport, rows) in &by_sport {
         // Sport header row
@@ -1105,6 +2288,9 @@ fn draw_diagnostic(f: &mut Frame, state: &AppState, area: Rect) {
         if show_source {
             header_cells.push(Cell::from(""));
         }
+        if show_health {
+            header_cells.push(Cell::from(""));
+        }
         display_rows.push(Row::new(header_cells));
 
         for row in rows {
@@ -1120,6 +2306,13 @@ fn draw_diagnostic(f: &mut Frame, state: &AppState, area: Rect) {
                 _ => Style::default().fg(Color::DarkGray),
             };
 
+            let filter = &state.game_filter;
+            let ticker_style = match row.kalshi_ticker.as_deref() {
+                Some(t) if filter.is_blacklisted(t) => Style::default().fg(Color::Red),
+                Some(t) if filter.is_whitelisted(t) => Style::default().fg(Color::Green),
+                _ => Style::default(),
+            };
+
             let reason_style = if row.reason.contains("tradeable") {
                 Style::default()
                     .fg(Color::Green)
@@ -1139,7 +2332,8 @@ fn draw_diagnostic(f: &mut Frame, state: &AppState, area: Rect) {
                         .as_deref()
                         .map(|t| truncate_with_ellipsis(t, 16).into_owned())
                         .unwrap_or_else(|| "\u{2014}".to_string()),
-                ),
+                )
+                .style(ticker_style),
                 Cell::from(
                     row.market_status
                         .as_deref()
@@ -1153,8 +2347,18 @@ fn draw_diagnostic(f: &mut Frame, state: &AppState, area: Rect) {
             if show_source {
                 cells.push(Cell::from(row.source.clone()).style(Style::default().fg(Color::Cyan)));
             }
+            if show_health {
+                cells.push(match state.odds_health.get(&row.sport) {
+                    Some(health) => health_cell(health),
+                    None => Cell::from(""),
+                });
+            }
 
-            display_rows.push(Row::new(cells));
+            let mut display_row = Row::new(cells);
+            if state.diagnostic_focus && selected.is_some_and(|sel| std::ptr::eq(sel, *row)) {
+                display_row = display_row.style(Style::default().add_modifier(Modifier::REVERSED));
+            }
+            display_rows.push(display_row);
         }
     }
 
@@ -1183,6 +2387,9 @@ fn draw_diagnostic(f: &mut Frame, state: &AppState, area: Rect) {
     if show_source {
         header_labels.push("Source");
     }
+    if show_health {
+        header_labels.push("Health");
+    }
 
     let table_header = Row::new(header_labels).style(Style::default().add_modifier(Modifier::BOLD));
 
@@ -1197,6 +2404,9 @@ fn draw_diagnostic(f: &mut Frame, state: &AppState, area: Rect) {
     if show_source {
         constraints.push(Constraint::Length(10));
     }
+    if show_health {
+        constraints.push(Constraint::Length(9));
+    }
 
     let table = Table::new(visible_rows, constraints)
         .header(table_header)
@@ -1213,6 +2423,81 @@ fn draw_diagnostic(f: &mut Frame, state: &AppState, area: Rect) {
     f.render_widget(table, area);
 }
 
+/// Odds source health: green when answering, yellow while failing or on the
+/// backup source ("B " prefix), red once down.
+fn health_cell(health: &crate::feed::health::HealthSummary) -> Cell<'static> {
+    use crate::feed::health::SourceStatus;
+    let color = match health.status {
+        SourceStatus::Down => Color::Red,
+        SourceStatus::Degraded => Color::Yellow,
+        SourceStatus::Healthy if health.on_backup => Color::Yellow,
+        SourceStatus::Healthy => Color::Green,
+    };
+    let label = if health.on_backup {
+        format!("B {}", health.label)
+    } else {
+        health.label.clone()
+    };
+    Cell::from(label).style(Style::default().fg(color))
+}
+
+/// Odds API credits by sport and purpose, most expensive first.
+fn draw_api_usage(
+    f: &mut Frame,
+    usage: &[crate::feed::usage::UsageRow],
+    total_credits: u64,
+    area: Rect,
+) {
+    let rows: Vec<Row> = usage
+        .iter()
+        .map(|u| {
+            let share = if total_credits > 0 {
+                u.credits as f64 * 100.0 / total_credits as f64
+            } else {
+                0.0
+            };
+            Row::new(vec![
+                Cell::from(u.sport.clone()),
+                Cell::from(u.purpose.label()),
+                Cell::from(format!("{:>8}", u.requests)),
+                Cell::from(format!("{:>7}", u.credits)),
+                Cell::from(format!("{:>9}", u.credits_last_hour)),
+                Cell::from(format!("{:>5.0}%", share)),
+            ])
+        })
+        .collect();
+    let header = Row::new(vec![
+        "Sport",
+        "Purpose",
+        "Requests",
+        "Credits",
+        "Last hour",
+        "Share",
+    ])
+    .style(Style::default().add_modifier(Modifier::BOLD));
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(26),
+            Constraint::Length(14),
+            Constraint::Length(9),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(6),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(format!(
+                " Odds API usage: {} credits this session ",
+                total_credits
+            ))
+            .borders(Borders::ALL),
+    );
+    f.render_widget(table, area);
+}
+
 fn draw_diagnostic_footer(f: &mut Frame, area: Rect) {
     let line = Line::from(vec![
         Span::styled("  [d/Esc]", Style::default().fg(Color::Yellow)),
@@ -1221,21 +2506,31 @@ fn draw_diagnostic_footer(f: &mut Frame, area: Rect) {
         Span::raw(" scroll  "),
         Span::styled("[g/G]", Style::default().fg(Color::Yellow)),
         Span::raw(" top/bottom  "),
+        Span::styled("[x]", Style::default().fg(Color::Yellow)),
+        Span::raw(" block game  "),
+        Span::styled("[a]", Style::default().fg(Color::Yellow)),
+        Span::raw("llow game  "),
     ]);
     let para = Paragraph::new(line);
     f.render_widget(para, area);
 }
 
+/// Truncate `s` to at most `max_width` terminal columns, ending in "..." when
+/// cut. Wide characters (CJK, most emoji) count as two columns.
 fn truncate_with_ellipsis(s: &str, max_width: usize) -> Cow<'_, str> {
-    let char_count = s.chars().count();
-    if char_count <= max_width {
+    if s.width() <= max_width {
         Cow::Borrowed(s)
     } else if max_width <= 3 {
         Cow::Owned(".".repeat(max_width))
     } else {
+        let budget = max_width - 3;
+        let mut used = 0;
         let end = s
             .char_indices()
-            .nth(max_width - 3)
+            .find(|&(_, c)| {
+                used += c.width().unwrap_or(0);
+                used > budget
+            })
             .map(|(i, _)| i)
             .unwrap_or(s.len());
         Cow::Owned(format!("{}...", &s[..end]))
@@ -1341,6 +2636,22 @@ fn render_config(f: &mut Frame, state: &AppState) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_balance_label_shows_deployable_with_reserve() {
+        let us = NumberFormat::default();
+        assert_eq!(balance_label(&us, 10_000, 0), "$100.00");
+        assert_eq!(
+            balance_label(&us, 10_000, 2_500),
+            "$75.00 deployable / $100.00"
+        );
+        assert_eq!(
+            balance_label(&us, 1_000, 2_500),
+            "$0.00 deployable / $10.00"
+        );
+        // Large simulated bankrolls stay readable
+        assert_eq!(balance_label(&us, 123_456_789, 0), "$1,234,567.89");
+    }
+
     #[test]
     fn test_truncate_short_string_unchanged() {
         assert_eq!(truncate_with_ellipsis("hello", 10), "hello");
@@ -1394,6 +2705,19 @@ mod tests {
         assert!(result.chars().count() <= 72);
     }
 
+    #[test]
+    fn test_truncate_wide_chars_by_display_width() {
+        // Each CJK character occupies two terminal columns
+        assert_eq!(truncate_with_ellipsis("日本語", 6), "日本語");
+        assert_eq!(truncate_with_ellipsis("日本語チーム", 7), "日本...");
+        // A wide char that would straddle the budget is dropped, not split
+        assert_eq!(truncate_with_ellipsis("日本語チーム", 6), "日...");
+        let emoji = "\u{1F3C0} Lakers @ Celtics";
+        let result = truncate_with_ellipsis(emoji, 10);
+        assert_eq!(result, "\u{1F3C0} Lake...");
+        assert!(result.width() <= 10);
+    }
+
     #[test]
     fn test_format_age_seconds() {
         assert_eq!(format_age(std::time::Duration::from_secs(0)), "0s");
@@ -1413,4 +2737,10 @@ mod tests {
         assert_eq!(format_age(std::time::Duration::from_secs(3600)), "1h00m");
         assert_eq!(format_age(std::time::Duration::from_secs(7380)), "2h03m");
     }
+
+    #[test]
+    fn test_format_remaining() {
+        assert_eq!(format_remaining(2880), "48:00");
+        assert_eq!(format_remaining(30), "0:30");
+    }
 }
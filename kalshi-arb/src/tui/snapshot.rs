@@ -0,0 +1,297 @@
+//! Timestamped captures of the rendered TUI (`S` from any view), for keeping
+//! evidence of market states and engine decisions without an external
+//! screenshot tool. Each capture is written as plain text, ANSI (view with
+//! `cat` or `less -R`) and SVG.
+
+use anyhow::{Context, Result};
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use unicode_width::UnicodeWidthStr;
+
+/// Directory captures are written to, relative to the working directory.
+pub const SNAPSHOT_DIR: &str = "snapshots";
+
+/// SVG cell size in pixels.
+const SVG_CELL_WIDTH: u32 = 9;
+const SVG_CELL_HEIGHT: u32 = 18;
+const SVG_FOREGROUND: &str = "#d0d0d0";
+const SVG_BACKGROUND: &str = "#1c1c1c";
+
+/// Write `buf` to `<dir>/tui-<timestamp>.{txt,ans,svg}`. Returns the path
+/// of the text capture.
+pub fn save(buf: &Buffer, dir: &Path, now: chrono::DateTime<chrono::Local>) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let stem = dir.join(format!("tui-{}", now.format("%Y%m%d-%H%M%S-%3f")));
+    let header = now.format("%Y-%m-%d %H:%M:%S%.3f %Z").to_string();
+
+    let text_path = stem.with_extension("txt");
+    for (path, contents) in [
+        (&text_path, format!("# {}\n{}", header, to_text(buf))),
+        (&stem.with_extension("ans"), to_ansi(buf)),
+        (&stem.with_extension("svg"), to_svg(buf, &header)),
+    ] {
+        std::fs::write(path, contents)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+    Ok(text_path)
+}
+
+/// One row of the buffer as (symbol, cell) pairs, skipping the cells hidden
+/// under wide graphemes.
+fn row_cells(buf: &Buffer, y: u16) -> Vec<(&str, &ratatui::buffer::Cell)> {
+    let area = buf.area;
+    let mut cells = Vec::with_capacity(area.width as usize);
+    let mut hidden = 0;
+    for x in area.left()..area.right() {
+        let cell = &buf[(x, y)];
+        if hidden > 0 {
+            hidden -= 1;
+            continue;
+        }
+        let symbol = cell.symbol();
+        hidden = symbol.width().saturating_sub(1);
+        cells.push((symbol, cell));
+    }
+    cells
+}
+
+/// Plain text, one line per row with trailing blanks trimmed.
+pub fn to_text(buf: &Buffer) -> String {
+    let mut out = String::new();
+    for y in buf.area.top()..buf.area.bottom() {
+        let line: String = row_cells(buf, y).into_iter().map(|(s, _)| s).collect();
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// Text with SGR escapes for colors and modifiers; each line ends reset.
+pub fn to_ansi(buf: &Buffer) -> String {
+    let mut out = String::new();
+    for y in buf.area.top()..buf.area.bottom() {
+        let mut current = None;
+        for (symbol, cell) in row_cells(buf, y) {
+            let style = (cell.fg, cell.bg, cell.modifier);
+            if current != Some(style) {
+                out.push_str(&sgr(cell.fg, cell.bg, cell.modifier));
+                current = Some(style);
+            }
+            out.push_str(symbol);
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+fn sgr(fg: Color, bg: Color, modifier: Modifier) -> String {
+    let mut codes = vec!["0".to_string()];
+    for (flag, code) in [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::REVERSED, "7"),
+        (Modifier::CROSSED_OUT, "9"),
+    ] {
+        if modifier.contains(flag) {
+            codes.push(code.to_string());
+        }
+    }
+    if let Some(code) = ansi_color(fg, 30) {
+        codes.push(code);
+    }
+    if let Some(code) = ansi_color(bg, 40) {
+        codes.push(code);
+    }
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+/// SGR parameter for `color`; `base` is 30 for foreground, 40 for background.
+fn ansi_color(color: Color, base: u8) -> Option<String> {
+    let code = match color {
+        Color::Reset => return None,
+        Color::Black => base,
+        Color::Red => base + 1,
+        Color::Green => base + 2,
+        Color::Yellow => base + 3,
+        Color::Blue => base + 4,
+        Color::Magenta => base + 5,
+        Color::Cyan => base + 6,
+        Color::Gray => base + 7,
+        Color::DarkGray => base + 60,
+        Color::LightRed => base + 61,
+        Color::LightGreen => base + 62,
+        Color::LightYellow => base + 63,
+        Color::LightBlue => base + 64,
+        Color::LightMagenta => base + 65,
+        Color::LightCyan => base + 66,
+        Color::White => base + 67,
+        Color::Indexed(i) => return Some(format!("{};5;{}", base + 8, i)),
+        Color::Rgb(r, g, b) => return Some(format!("{};2;{};{};{}", base + 8, r, g, b)),
+    };
+    Some(code.to_string())
+}
+
+/// SVG rendering on a fixed cell grid: a background rect and a text run per
+/// span of identically styled cells.
+pub fn to_svg(buf: &Buffer, title: &str) -> String {
+    let area = buf.area;
+    let (width, height) = (
+        area.width as u32 * SVG_CELL_WIDTH,
+        area.height as u32 * SVG_CELL_HEIGHT,
+    );
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="monospace" font-size="15">"#
+    );
+    let _ = writeln!(out, "<title>{}</title>", xml_escape(title));
+    let _ = writeln!(
+        out,
+        r#"<rect width="100%" height="100%" fill="{SVG_BACKGROUND}"/>"#
+    );
+
+    for (row, y) in (area.top()..area.bottom()).enumerate() {
+        let top = row as u32 * SVG_CELL_HEIGHT;
+        let mut col = 0u32;
+        let cells = row_cells(buf, y);
+        let mut i = 0;
+        while i < cells.len() {
+            let (_, first) = cells[i];
+            let style = (first.fg, first.bg, first.modifier);
+            let start = col;
+            let mut text = String::new();
+            while i < cells.len() && (cells[i].1.fg, cells[i].1.bg, cells[i].1.modifier) == style {
+                text.push_str(cells[i].0);
+                col += cells[i].0.width().max(1) as u32;
+                i += 1;
+            }
+            let (mut fg, mut bg) = (
+                svg_color(style.0).unwrap_or(SVG_FOREGROUND.to_string()),
+                svg_color(style.1),
+            );
+            if style.2.contains(Modifier::REVERSED) {
+                let reversed_bg = fg.clone();
+                fg = bg.unwrap_or(SVG_BACKGROUND.to_string());
+                bg = Some(reversed_bg);
+            }
+            let x = start * SVG_CELL_WIDTH;
+            let span_width = (col - start) * SVG_CELL_WIDTH;
+            if let Some(bg) = bg {
+                let _ = writeln!(
+                    out,
+                    r#"<rect x="{x}" y="{top}" width="{span_width}" height="{SVG_CELL_HEIGHT}" fill="{bg}"/>"#
+                );
+            }
+            if text.trim().is_empty() {
+                continue;
+            }
+            let mut attrs = String::new();
+            if style.2.contains(Modifier::BOLD) {
+                attrs.push_str(r#" font-weight="bold""#);
+            }
+            if style.2.contains(Modifier::ITALIC) {
+                attrs.push_str(r#" font-style="italic""#);
+            }
+            if style.2.contains(Modifier::UNDERLINED) {
+                attrs.push_str(r#" text-decoration="underline""#);
+            }
+            let _ = writeln!(
+                out,
+                r#"<text x="{x}" y="{}" fill="{fg}" textLength="{span_width}" lengthAdjust="spacingAndGlyphs" xml:space="preserve"{attrs}>{}</text>"#,
+                top + SVG_CELL_HEIGHT - 4,
+                xml_escape(&text)
+            );
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Hex color for SVG, using the xterm default palette; `None` for `Reset`.
+fn svg_color(color: Color) -> Option<String> {
+    const PALETTE: [&str; 16] = [
+        "#000000", "#cd0000", "#00cd00", "#cdcd00", "#0000ee", "#cd00cd", "#00cdcd", "#e5e5e5",
+        "#7f7f7f", "#ff0000", "#00ff00", "#ffff00", "#5c5cff", "#ff00ff", "#00ffff", "#ffffff",
+    ];
+    let index = match color {
+        Color::Reset => return None,
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+        Color::Rgb(r, g, b) => return Some(format!("#{:02x}{:02x}{:02x}", r, g, b)),
+        Color::Indexed(i) if i < 16 => i as usize,
+        Color::Indexed(i) if i < 232 => {
+            // 6x6x6 color cube
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let i = i - 16;
+            return Some(format!(
+                "#{:02x}{:02x}{:02x}",
+                level(i / 36),
+                level(i / 6 % 6),
+                level(i % 6)
+            ));
+        }
+        Color::Indexed(i) => {
+            let v = 8 + (i - 232) * 10;
+            return Some(format!("#{:02x}{:02x}{:02x}", v, v, v));
+        }
+    };
+    Some(PALETTE[index].to_string())
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    fn buffer() -> Buffer {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 12, 2));
+        buf.set_string(0, 0, "EDGE", Style::default().fg(Color::Green));
+        buf.set_string(5, 0, "<7c>", Style::default().add_modifier(Modifier::BOLD));
+        buf.set_string(0, 1, "日本 ok", Style::default());
+        buf
+    }
+
+    #[test]
+    fn test_exports_text_ansi_and_svg() {
+        let buf = buffer();
+        assert_eq!(to_text(&buf), "EDGE <7c>\n日本 ok\n");
+
+        let ansi = to_ansi(&buf);
+        assert!(ansi.starts_with("\x1b[0;32mEDGE\x1b[0m "));
+        assert!(ansi.contains("\x1b[0;1m<7c>"));
+        assert_eq!(ansi.matches("\x1b[0m\n").count(), 2);
+
+        let svg = to_svg(&buf, "2026-10-18 & co");
+        assert!(svg.contains(r#"width="108" height="36""#));
+        assert!(svg.contains(">&lt;7c&gt;</text>"));
+        assert!(svg.contains("<title>2026-10-18 &amp; co</title>"));
+        // The wide glyphs take two cells each
+        assert!(svg.contains(r#"textLength="108" lengthAdjust="spacingAndGlyphs" xml:space="preserve">日本 ok     </text>"#));
+    }
+}
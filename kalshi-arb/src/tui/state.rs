@@ -44,13 +44,31 @@ pub struct AppState {
     pub position_scroll_offset: usize,
     pub trade_focus: bool,
     pub trade_scroll_offset: usize,
+    /// When set, the markets and trades tables only show rows in this game phase.
+    pub phase_filter: Option<String>,
+    /// Note being typed for the selected trade (UI-local, set by the TUI loop).
+    pub trade_note_edit: Option<String>,
+    /// Manual order being entered for the selected market (UI-local, set by
+    /// the TUI loop).
+    pub order_ticket: Option<OrderTicket>,
+    pub next_trade_id: u64,
     pub sim_mode: bool,
+    /// Config profile selected with `--profile`, shown in the header.
+    pub profile: Option<String>,
     pub sim_balance_cents: i64,
+    /// `risk.min_cash_reserve_cents`, shown as deployable vs total balance.
+    pub cash_reserve_cents: u64,
     pub sim_positions: Vec<SimPosition>,
     /// Unified P&L tracking (used for both sim and live modes)
     pub total_trades: u32,
     pub winning_trades: u32,
     pub total_slippage_cents: i64,
+    /// Expected vs realized fee divergence across reconciled trades
+    pub fee_tracker: crate::engine::FeeTracker,
+    /// Open critical alerts awaiting acknowledgement (`a` in the TUI).
+    pub escalation: crate::engine::EscalationPolicy,
+    /// Live-mode order-path preflight result (None until it has run).
+    pub preflight: Option<crate::execution::PreflightReport>,
     pub sim_entries_attempted: u32,
     pub sim_entries_filled: u32,
     pub sim_entries_missed: u32,
@@ -62,10 +80,14 @@ pub struct AppState {
     pub api_requests_remaining: u64,
     pub api_burn_rate: f64,
     pub api_hours_remaining: f64,
+    /// Per odds source count of response items that failed schema validation
+    pub odds_schema_drift: HashMap<String, u64>,
     pub live_sports: Vec<String>,
     pub filter_stats: FilterStats,
     pub next_game_start: Option<DateTime<Utc>>,
     pub diagnostic_rows: Vec<DiagnosticRow>,
+    /// Health of each sport's odds source, by sport key.
+    pub odds_health: HashMap<String, crate::feed::health::HealthSummary>,
     pub diagnostic_snapshot: bool,
     pub diagnostic_focus: bool,
     pub diagnostic_scroll_offset: usize,
@@ -75,6 +97,88 @@ pub struct AppState {
     pub odds_source: String,
     pub config_focus: bool,
     pub config_view: Option<crate::tui::config_view::ConfigViewState>,
+    /// Tickers whose orderbook updates are being recorded.
+    pub recording_tickers: Vec<String>,
+    /// Recorded frames opened in the replay view (set by the engine).
+    pub book_replay: Option<std::sync::Arc<crate::book_replay::BookReplay>>,
+    /// Frame shown in the replay view (UI-local, set by the TUI loop).
+    pub replay_cursor: usize,
+    /// Ticker of the position drilled into from the positions view (UI-local,
+    /// set by the TUI loop).
+    pub position_detail: Option<String>,
+    /// YES bid history of held tickers, for the position chart.
+    pub price_history: PriceHistory,
+    /// Depth book of the market drilled into from the markets view,
+    /// refreshed by the engine while the view is open.
+    pub depth_ladder: Option<DepthLadder>,
+    /// Score-model vs odds FV disagreement per sport key (validate_fair_value only).
+    pub fv_disagreement: HashMap<String, FvDisagreementStat>,
+    /// Edge persistence per sport key, refreshed by the engine.
+    pub edge_persistence: HashMap<String, EdgePersistenceStat>,
+    /// Memory gauge shown in the diagnostic view, refreshed by the engine.
+    pub memory: MemoryStats,
+    /// Kalshi WS event queue backlog and backpressure counts.
+    pub ws_queue: crate::kalshi::ws_queue::QueueStats,
+    /// Score-model heat grids for the win-prob view (set by the engine on open).
+    pub win_prob_grids: Vec<crate::engine::win_prob::WinProbGrid>,
+    pub win_prob_focus: bool,
+    /// Index into `win_prob_grids` (UI-local, set by the TUI loop).
+    pub win_prob_sport: usize,
+    /// P&L breakdown for the portfolio view (set by the engine on open).
+    pub portfolio: Option<crate::portfolio::PortfolioSummary>,
+    pub portfolio_focus: bool,
+    /// Equity samples for the portfolio view, recorded by the engine.
+    pub equity: crate::engine::equity::EquityCurve,
+    /// Result of the last view capture, shown briefly (UI-local).
+    pub snapshot_notice: Option<String>,
+    /// Feed degradation tier, limiting new entries while feeds are down.
+    pub feed_tier: crate::engine::degradation::FeedTier,
+    /// Drawdown tier, cutting sizing and then entries as session P&L falls.
+    pub drawdown_tier: crate::engine::drawdown::DrawdownTier,
+    /// Drop in session realized P&L from its peak.
+    pub drawdown_cents: u64,
+    /// Formats dollar amounts for display, from `[locale]`.
+    pub number_format: crate::locale::NumberFormat,
+    /// Realized vs predicted edge per sport, scaling the Kelly fraction.
+    pub kelly_tuner: crate::engine::kelly_tuner::KellyTuner,
+    /// An engine profile capture is running.
+    pub profiling: bool,
+    /// Cost held per market and sport, refreshed each engine cycle, for the
+    /// exposure caps.
+    pub exposure: crate::engine::risk::Exposure,
+    /// Odds API credits by sport and purpose, for the diagnostic view.
+    pub api_usage: crate::feed::usage::ApiUsage,
+    /// Games and markets blacklisted or whitelisted from the TUI.
+    pub game_filter: crate::engine::game_filter::GameFilter,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FvDisagreementStat {
+    /// Mean absolute gap in cents over the window.
+    pub avg_abs: f64,
+    /// Mean signed gap (score model minus odds) in cents.
+    pub bias: f64,
+    pub alerting: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct EdgePersistenceStat {
+    pub samples: usize,
+    pub median_ms: u64,
+    /// Auto-tuned taker threshold in force, if any.
+    pub tuned_threshold: Option<u8>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MemoryStats {
+    /// Resident set size, where the platform reports it.
+    pub rss_bytes: Option<u64>,
+    pub indexed_games: usize,
+    pub books: usize,
+    /// Per-game tracker entries across all sport pipelines.
+    pub trackers: usize,
+    /// Entries dropped by the most recent compaction pass.
+    pub last_compacted: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -84,13 +188,23 @@ pub struct MarketRow {
     pub bid: u32,
     pub ask: u32,
     pub edge: i32,
+    /// Trade action ("TAKER"/"MAKER") or the skip reason label.
     pub action: String,
+    pub skip_reason: Option<crate::engine::strategy::SkipReason>,
     pub latency_ms: Option<u64>,
     pub momentum_score: f64,
     pub staleness_secs: Option<u64>,
     pub odds_api_fair_value: Option<u32>,
     /// Which source produced the primary fair value: "odds-api", "score-feed", etc.
     pub fair_value_source: String,
+    /// Game phase label at evaluation time ("Q3", "F2M", "LIVE", ...).
+    pub phase: String,
+    /// Close game in its final minutes (end-of-game sprint mode).
+    pub sprint: bool,
+    #[allow(dead_code)]
+    pub smoothed_bid: f64,
+    #[allow(dead_code)]
+    pub smoothed_ask: f64,
 }
 
 #[derive(Debug, Clone)]
@@ -100,11 +214,14 @@ pub struct PositionRow {
     pub quantity: u32,
     pub entry_price: u32,
     pub sell_price: u32,
+    pub entry_fee: u32,
     pub unrealized_pnl: i32,
 }
 
 #[derive(Debug, Clone)]
 pub struct TradeRow {
+    /// Session-unique id, assigned by `AppState::push_trade`.
+    pub id: u64,
     pub time: String,
     pub action: String,
     pub ticker: String,
@@ -116,11 +233,16 @@ pub struct TradeRow {
     pub source: String,
     #[allow(dead_code)]
     pub fair_value_basis: String,
+    /// Game phase label when the position was entered (empty if unknown).
+    pub phase: String,
+    /// Free-form review note or tag attached from the trades view.
+    pub note: String,
 }
 
 #[derive(Debug, Clone)]
 pub struct SimPosition {
     pub ticker: String,
+    pub side: String, // "yes" or "no"
     pub quantity: u32,
     pub entry_price: u32,
     pub sell_price: u32,
@@ -131,6 +253,207 @@ pub struct SimPosition {
     pub trace: Option<crate::pipeline::SignalTrace>,
 }
 
+impl SimPosition {
+    /// Entered by pre-game positioning rather than live trading.
+    pub fn is_pre_game(&self) -> bool {
+        self.trace.as_ref().is_some_and(|t| {
+            matches!(
+                t.inputs,
+                crate::pipeline::FairValueInputs::Odds { pre_game: true, .. }
+            )
+        })
+    }
+
+    /// Add a filled lot. The entry price becomes the average, with the
+    /// rounding remainder folded into the fee so the total cost stays exact.
+    pub fn add_lot(&mut self, quantity: u32, price: u32, fee: u32) {
+        let cost = self.quantity * self.entry_price + quantity * price;
+        self.quantity += quantity;
+        self.entry_price = cost / self.quantity;
+        self.entry_fee += fee + cost % self.quantity;
+    }
+}
+
+/// Samples kept per ticker: an hour at one a second.
+const PRICE_HISTORY_CAP: usize = 3600;
+const PRICE_HISTORY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// YES bid samples per held ticker, oldest first. A ticker's series starts
+/// on the first sample after it is bought and is dropped once it is no
+/// longer held.
+#[derive(Debug, Clone, Default)]
+pub struct PriceHistory {
+    series: HashMap<String, VecDeque<(Instant, u32)>>,
+}
+
+impl PriceHistory {
+    /// Sample `bid` for `ticker`, at most once per interval.
+    pub fn record(&mut self, ticker: &str, bid: u32, now: Instant) {
+        let series = self.series.entry(ticker.to_string()).or_default();
+        if series
+            .back()
+            .is_some_and(|&(at, _)| now.duration_since(at) < PRICE_HISTORY_INTERVAL)
+        {
+            return;
+        }
+        if series.len() >= PRICE_HISTORY_CAP {
+            series.pop_front();
+        }
+        series.push_back((now, bid));
+    }
+
+    /// Drop the series of tickers no longer held.
+    pub fn retain(&mut self, held: &[&str]) {
+        self.series.retain(|t, _| held.contains(&t.as_str()));
+    }
+
+    /// The series squeezed into at most `width` points, each the mean of
+    /// its bucket of samples.
+    pub fn downsample(&self, ticker: &str, width: usize) -> Vec<u32> {
+        let Some(series) = self.series.get(ticker) else {
+            return Vec::new();
+        };
+        if width == 0 || series.is_empty() {
+            return Vec::new();
+        }
+        let bucket = series.len().div_ceil(width);
+        let bids: Vec<u32> = series.iter().map(|&(_, bid)| bid).collect();
+        bids.chunks(bucket)
+            .map(|c| c.iter().sum::<u32>() / c.len() as u32)
+            .collect()
+    }
+
+    /// When the series for `ticker` started.
+    pub fn since(&self, ticker: &str) -> Option<Instant> {
+        self.series.get(ticker)?.front().map(|&(at, _)| at)
+    }
+}
+
+/// Fields of the manual order dialog, in Tab order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TicketField {
+    Side,
+    Price,
+    Quantity,
+}
+
+/// Manual order dialog opened on a market row. Buys default to one YES
+/// contract at the ask. Sells close the whole held position, so their side
+/// and quantity are fixed and only the price is edited.
+#[derive(Debug, Clone)]
+pub struct OrderTicket {
+    pub ticker: String,
+    pub is_buy: bool,
+    pub side: String,
+    pub price: String,
+    pub quantity: String,
+    pub field: TicketField,
+    /// YES bid and ask when the dialog opened, for pricing the other side.
+    yes_bid: u32,
+    yes_ask: u32,
+}
+
+impl OrderTicket {
+    pub fn buy(market: &MarketRow) -> Self {
+        Self {
+            ticker: market.ticker.clone(),
+            is_buy: true,
+            side: "yes".to_string(),
+            price: market.ask.to_string(),
+            quantity: "1".to_string(),
+            field: TicketField::Price,
+            yes_bid: market.bid,
+            yes_ask: market.ask,
+        }
+    }
+
+    /// Sell `held` contracts into the bid.
+    pub fn sell(market: &MarketRow, held: u32) -> Self {
+        Self {
+            is_buy: false,
+            price: market.bid.to_string(),
+            quantity: held.to_string(),
+            ..Self::buy(market)
+        }
+    }
+
+    pub fn next_field(&mut self) {
+        self.field = match (self.field, self.is_buy) {
+            (_, false) => TicketField::Price,
+            (TicketField::Side, true) => TicketField::Price,
+            (TicketField::Price, true) => TicketField::Quantity,
+            (TicketField::Quantity, true) => TicketField::Side,
+        };
+    }
+
+    /// Flip a buy between YES and NO, repricing at the new side's ask.
+    pub fn toggle_side(&mut self) {
+        if !self.is_buy {
+            return;
+        }
+        let (side, ask) = if self.side == "yes" {
+            ("no", 100u32.saturating_sub(self.yes_bid))
+        } else {
+            ("yes", self.yes_ask)
+        };
+        self.side = side.to_string();
+        self.price = ask.to_string();
+    }
+
+    pub fn push_digit(&mut self, c: char) {
+        match self.field {
+            TicketField::Price if self.price.len() < 2 => self.price.push(c),
+            TicketField::Quantity if self.quantity.len() < 5 => self.quantity.push(c),
+            _ => {}
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        match self.field {
+            TicketField::Price => {
+                self.price.pop();
+            }
+            TicketField::Quantity => {
+                self.quantity.pop();
+            }
+            TicketField::Side => {}
+        }
+    }
+
+    /// The order to send, or `None` while the price is outside 1-99c or the
+    /// quantity is zero.
+    pub fn order(&self) -> Option<crate::tui::ManualOrder> {
+        let price: u32 = self.price.parse().ok().filter(|p| (1..=99).contains(p))?;
+        let quantity: u32 = self.quantity.parse().ok().filter(|&q| q > 0)?;
+        Some(crate::tui::ManualOrder {
+            ticker: self.ticker.clone(),
+            side: self.side.clone(),
+            is_buy: self.is_buy,
+            price,
+            quantity,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DepthLadder {
+    pub ticker: String,
+    pub book: crate::journal::BookSnapshot,
+}
+
+/// The best `n` levels of one side, as (price, contracts, cumulative
+/// contracts from the touch).
+pub fn ladder_levels(levels: &[(u32, i64)], n: usize) -> Vec<(u32, i64, i64)> {
+    levels
+        .iter()
+        .take(n)
+        .scan(0, |cumulative, &(price, qty)| {
+            *cumulative += qty;
+            Some((price, qty, *cumulative))
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone)]
 pub struct LogEntry {
     pub time: String,
@@ -160,12 +483,21 @@ impl AppState {
             position_scroll_offset: 0,
             trade_focus: false,
             trade_scroll_offset: 0,
+            phase_filter: None,
+            trade_note_edit: None,
+            order_ticket: None,
+            next_trade_id: 1,
             sim_mode: false,
+            profile: None,
             sim_balance_cents: 100_000,
+            cash_reserve_cents: 0,
             sim_positions: Vec::new(),
             total_trades: 0,
             winning_trades: 0,
             total_slippage_cents: 0,
+            fee_tracker: crate::engine::FeeTracker::default(),
+            escalation: crate::engine::EscalationPolicy::default(),
+            preflight: None,
             sim_entries_attempted: 0,
             sim_entries_filled: 0,
             sim_entries_missed: 0,
@@ -177,10 +509,12 @@ impl AppState {
             api_requests_remaining: 0,
             api_burn_rate: 0.0,
             api_hours_remaining: 0.0,
+            odds_schema_drift: HashMap::new(),
             live_sports: Vec::new(),
             filter_stats: FilterStats::default(),
             next_game_start: None,
             diagnostic_rows: Vec::new(),
+            odds_health: HashMap::new(),
             diagnostic_snapshot: false,
             diagnostic_focus: false,
             diagnostic_scroll_offset: 0,
@@ -189,6 +523,32 @@ impl AppState {
             odds_source: "ODDS-API".to_string(),
             config_focus: false,
             config_view: None,
+            recording_tickers: Vec::new(),
+            book_replay: None,
+            replay_cursor: 0,
+            position_detail: None,
+            price_history: PriceHistory::default(),
+            depth_ladder: None,
+            fv_disagreement: HashMap::new(),
+            edge_persistence: HashMap::new(),
+            memory: MemoryStats::default(),
+            ws_queue: Default::default(),
+            win_prob_grids: Vec::new(),
+            win_prob_focus: false,
+            win_prob_sport: 0,
+            portfolio: None,
+            portfolio_focus: false,
+            equity: Default::default(),
+            snapshot_notice: None,
+            profiling: false,
+            feed_tier: Default::default(),
+            drawdown_tier: Default::default(),
+            drawdown_cents: 0,
+            number_format: Default::default(),
+            kelly_tuner: Default::default(),
+            exposure: Default::default(),
+            api_usage: Default::default(),
+            game_filter: Default::default(),
         }
     }
 
@@ -204,14 +564,145 @@ impl AppState {
         });
     }
 
+    /// Feed a closed position's realized P&L to the Kelly tuner, logging
+    /// when its sport's multiplier changes.
+    pub fn record_realized_edge(&mut self, ticker: &str, pnl_cents: i64) {
+        if let Some((sport, multiplier)) = self.kelly_tuner.record_exit(ticker, pnl_cents) {
+            let msg = format!(
+                "{} Kelly multiplier {:.2} (realized vs predicted edge over {} trades)",
+                sport,
+                multiplier,
+                self.kelly_tuner.trades(&sport)
+            );
+            tracing::info!("{}", msg);
+            self.push_log("RISK", msg);
+        }
+    }
+
     #[allow(dead_code)]
-    pub fn push_trade(&mut self, trade: TradeRow) {
+    pub fn push_trade(&mut self, mut trade: TradeRow) {
         if self.trades.len() >= 100 {
             self.trades.pop_front();
         }
+        trade.id = self.next_trade_id;
+        self.next_trade_id += 1;
         self.trades.push_back(trade);
     }
 
+    /// Trade under the cursor in the trades view: the `offset`-th most recent
+    /// trade matching the phase filter, clamped to the oldest one.
+    pub fn selected_trade(&self, phase_filter: Option<&str>, offset: usize) -> Option<&TradeRow> {
+        let trades: Vec<&TradeRow> = self
+            .trades
+            .iter()
+            .rev()
+            .filter(|t| phase_filter.is_none_or(|f| f == t.phase))
+            .collect();
+        let idx = offset.min(trades.len().checked_sub(1)?);
+        trades.get(idx).copied()
+    }
+
+    /// Market under the cursor in the markets view: the `offset`-th row matching
+    /// the phase filter, clamped to the last one.
+    pub fn selected_market(&self, phase_filter: Option<&str>, offset: usize) -> Option<&MarketRow> {
+        let markets: Vec<&MarketRow> = self
+            .markets
+            .iter()
+            .filter(|m| phase_filter.is_none_or(|f| f == m.phase))
+            .collect();
+        let idx = offset.min(markets.len().checked_sub(1)?);
+        markets.get(idx).copied()
+    }
+
+    /// Diagnostic row under the cursor. Rows are shown grouped by sport in
+    /// commence-time order, each group under a header line, so `offset`
+    /// counts display lines; a header selects the first row under it.
+    pub fn selected_diagnostic(&self, offset: usize) -> Option<&DiagnosticRow> {
+        let mut by_sport: std::collections::BTreeMap<&str, Vec<&DiagnosticRow>> =
+            std::collections::BTreeMap::new();
+        for row in &self.diagnostic_rows {
+            by_sport.entry(&row.sport).or_default().push(row);
+        }
+        let mut lines: Vec<Option<&DiagnosticRow>> = Vec::new();
+        for rows in by_sport.values_mut() {
+            rows.sort_by(|a, b| a.commence_time.cmp(&b.commence_time));
+            lines.push(None);
+            lines.extend(rows.iter().map(|r| Some(*r)));
+        }
+        let idx = offset.min(lines.len().checked_sub(1)?);
+        lines[idx..].iter().flatten().next().copied()
+    }
+
+    /// Tickers held: sim positions in sim mode, tracked positions otherwise.
+    pub fn held_tickers(&self) -> Vec<&str> {
+        if self.sim_mode {
+            self.sim_positions
+                .iter()
+                .map(|p| p.ticker.as_str())
+                .collect()
+        } else {
+            self.positions.iter().map(|p| p.ticker.as_str()).collect()
+        }
+    }
+
+    /// Open positions marked to the YES bid, at cost where there is no bid:
+    /// sim positions in sim mode, tracked positions otherwise.
+    pub fn positions_value_cents(&self) -> i64 {
+        if self.sim_mode {
+            self.sim_positions
+                .iter()
+                .map(|p| match self.live_book.get(&p.ticker) {
+                    Some(&(bid, ..)) if bid > 0 => (p.quantity * bid) as i64,
+                    _ => (p.quantity * p.entry_price + p.entry_fee) as i64,
+                })
+                .sum()
+        } else {
+            // Tracked unrealized P&L is zero without a bid, leaving the cost
+            self.positions
+                .iter()
+                .map(|p| {
+                    (p.quantity * p.entry_price + p.entry_fee) as i64 + p.unrealized_pnl as i64
+                })
+                .sum()
+        }
+    }
+
+    /// Contracts held in `ticker`: sim positions in sim mode, tracked
+    /// positions otherwise.
+    pub fn held_quantity(&self, ticker: &str) -> Option<u32> {
+        if self.sim_mode {
+            self.sim_positions
+                .iter()
+                .find(|p| p.ticker == ticker)
+                .map(|p| p.quantity)
+        } else {
+            self.positions
+                .iter()
+                .find(|p| p.ticker == ticker)
+                .map(|p| p.quantity)
+        }
+    }
+
+    /// Phase filter following `current`: the next phase present in the markets or
+    /// trades tables (in `GamePhase::LABELS` order), wrapping back to "all phases".
+    pub fn next_phase_filter(&self, current: Option<&str>) -> Option<String> {
+        let present: Vec<&str> = crate::feed::score_feed::GamePhase::LABELS
+            .into_iter()
+            .filter(|label| {
+                self.markets.iter().any(|m| m.phase == *label)
+                    || self.trades.iter().any(|t| t.phase == *label)
+            })
+            .collect();
+        let next = match current {
+            None => present.first(),
+            Some(current) => present
+                .iter()
+                .position(|label| *label == current)
+                .and_then(|i| present.get(i + 1)),
+        };
+        next.map(|label| label.to_string())
+    }
+
     pub fn uptime(&self) -> String {
         let secs = self.start_time.elapsed().as_secs();
         let h = secs / 3600;
@@ -232,4 +723,187 @@ mod tests {
         assert_eq!(state.filter_stats.closed, 0);
         assert!(state.next_game_start.is_none());
     }
+
+    fn trade(phase: &str) -> TradeRow {
+        TradeRow {
+            id: 0,
+            time: String::new(),
+            action: "BUY".to_string(),
+            ticker: "T".to_string(),
+            price: 50,
+            quantity: 1,
+            order_type: "SIM".to_string(),
+            pnl: None,
+            slippage: None,
+            source: String::new(),
+            fair_value_basis: String::new(),
+            phase: phase.to_string(),
+            note: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_next_phase_filter_cycles_present_phases() {
+        let mut state = AppState::new();
+        state.push_trade(trade("F2M"));
+        state.push_trade(trade("Q2"));
+        state.push_trade(trade(""));
+
+        assert_eq!(state.next_phase_filter(None).as_deref(), Some("Q2"));
+        assert_eq!(state.next_phase_filter(Some("Q2")).as_deref(), Some("F2M"));
+        assert_eq!(state.next_phase_filter(Some("F2M")), None);
+        // A filter whose phase has aged out resets to "all phases"
+        assert_eq!(state.next_phase_filter(Some("OT")), None);
+    }
+
+    #[test]
+    fn test_push_trade_assigns_ids_and_selection_follows_offset() {
+        let mut state = AppState::new();
+        state.push_trade(trade("Q1"));
+        state.push_trade(trade("Q2"));
+        state.push_trade(trade("Q1"));
+        let ids: Vec<u64> = state.trades.iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+
+        assert_eq!(state.selected_trade(None, 0).map(|t| t.id), Some(3));
+        assert_eq!(state.selected_trade(None, 1).map(|t| t.id), Some(2));
+        assert_eq!(state.selected_trade(None, 50).map(|t| t.id), Some(1));
+        assert_eq!(state.selected_trade(Some("Q1"), 1).map(|t| t.id), Some(1));
+        assert!(state.selected_trade(Some("OT"), 0).is_none());
+    }
+
+    fn market(bid: u32, ask: u32) -> MarketRow {
+        MarketRow {
+            ticker: "T".to_string(),
+            fair_value: 60,
+            bid,
+            ask,
+            edge: 0,
+            action: "SKIP".to_string(),
+            skip_reason: None,
+            latency_ms: None,
+            momentum_score: 0.0,
+            staleness_secs: None,
+            odds_api_fair_value: None,
+            fair_value_source: String::new(),
+            phase: String::new(),
+            sprint: false,
+            smoothed_bid: 0.0,
+            smoothed_ask: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_order_ticket_edits_and_validates() {
+        let mut ticket = OrderTicket::buy(&market(44, 47));
+        let order = ticket.order().unwrap();
+        assert_eq!(
+            (order.side.as_str(), order.price, order.quantity),
+            ("yes", 47, 1)
+        );
+
+        // NO is priced at its own ask, 100 - YES bid
+        ticket.toggle_side();
+        assert_eq!((ticket.side.as_str(), ticket.price.as_str()), ("no", "56"));
+
+        ticket.next_field();
+        ticket.push_digit('0');
+        assert_eq!(ticket.order().unwrap().quantity, 10);
+        ticket.field = TicketField::Price;
+        ticket.backspace();
+        ticket.backspace();
+        assert!(ticket.order().is_none());
+        ticket.push_digit('0');
+        assert!(ticket.order().is_none(), "0c is not a price");
+
+        // Sells close the held position: only the price moves
+        let mut sell = OrderTicket::sell(&market(44, 47), 3);
+        sell.toggle_side();
+        sell.next_field();
+        let order = sell.order().unwrap();
+        assert!(!order.is_buy);
+        assert_eq!(
+            (order.side.as_str(), order.price, order.quantity),
+            ("yes", 44, 3)
+        );
+    }
+
+    #[test]
+    fn test_price_history_samples_and_downsamples() {
+        let mut history = PriceHistory::default();
+        let start = Instant::now();
+        let at = |ms: u64| start + std::time::Duration::from_millis(ms);
+        history.record("T", 40, at(0));
+        history.record("T", 99, at(200));
+        for (i, bid) in [42, 44, 46, 48, 50].into_iter().enumerate() {
+            history.record("T", bid, at(1000 * (i as u64 + 1)));
+        }
+        // The 200ms sample was inside the interval
+        assert_eq!(history.downsample("T", 10), vec![40, 42, 44, 46, 48, 50]);
+        assert_eq!(history.downsample("T", 3), vec![41, 45, 49]);
+        assert_eq!(history.since("T"), Some(start));
+
+        history.record("U", 10, at(0));
+        history.retain(&["U"]);
+        assert!(history.downsample("T", 10).is_empty());
+        assert_eq!(history.downsample("U", 10), vec![10]);
+    }
+
+    #[test]
+    fn test_ladder_levels_accumulate_from_touch() {
+        let bids = [(45, 10), (44, 5), (40, 20)];
+        assert_eq!(ladder_levels(&bids, 2), vec![(45, 10, 10), (44, 5, 15)]);
+        assert_eq!(ladder_levels(&bids, 10).last(), Some(&(40, 20, 35)));
+        assert!(ladder_levels(&[], 10).is_empty());
+    }
+
+    #[test]
+    fn test_add_lot_averages_entry_and_keeps_cost() {
+        let mut p = SimPosition {
+            ticker: "TEST".to_string(),
+            side: "yes".to_string(),
+            quantity: 2,
+            entry_price: 50,
+            sell_price: 55,
+            entry_fee: 2,
+            filled_at: Instant::now(),
+            signal_ask: 50,
+            trace: None,
+        };
+        p.add_lot(1, 53, 1);
+        assert_eq!((p.quantity, p.entry_price), (3, 51));
+        // 153c of contracts plus 3c of fees
+        assert_eq!(p.quantity * p.entry_price + p.entry_fee, 156);
+    }
+
+    #[test]
+    fn test_selected_diagnostic_skips_sport_headers() {
+        let row = |sport: &str, commence: &str, ticker: &str| DiagnosticRow {
+            sport: sport.to_string(),
+            matchup: String::new(),
+            commence_time: commence.to_string(),
+            game_status: String::new(),
+            kalshi_ticker: Some(ticker.to_string()),
+            market_status: None,
+            reason: String::new(),
+            source: String::new(),
+        };
+        let mut state = AppState::new();
+        state.diagnostic_rows = vec![
+            row("nhl", "19:00", "NHL-A"),
+            row("nba", "20:00", "NBA-B"),
+            row("nba", "19:30", "NBA-A"),
+        ];
+        // Lines: NBA header, NBA-A, NBA-B, NHL header, NHL-A
+        let ticker = |offset| {
+            state
+                .selected_diagnostic(offset)
+                .and_then(|r| r.kalshi_ticker.clone())
+        };
+        assert_eq!(ticker(0).as_deref(), Some("NBA-A"));
+        assert_eq!(ticker(2).as_deref(), Some("NBA-B"));
+        assert_eq!(ticker(3).as_deref(), Some("NHL-A"));
+        assert_eq!(ticker(50).as_deref(), Some("NHL-A"));
+        assert!(AppState::new().selected_diagnostic(0).is_none());
+    }
 }
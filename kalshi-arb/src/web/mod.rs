@@ -0,0 +1,283 @@
+//! Read-only browser dashboard. One page, plus a WebSocket at `/ws` that
+//! pushes the markets, positions and recent trades as JSON once a second,
+//! so the engine can be watched from a phone while the TUI runs elsewhere.
+//!
+//! The HTTP side is just enough for those two routes: the request line is
+//! peeked to pick one, and the WebSocket handshake reads the request itself.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use serde::Serialize;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::config::WebConfig;
+use crate::headless::{self, SnapshotPosition};
+use crate::tui::state::AppState;
+
+const INDEX_HTML: &str = include_str!("index.html");
+/// How often connected browsers get a new frame.
+const PUSH_INTERVAL: Duration = Duration::from_secs(1);
+/// Trades sent per frame, newest first.
+const RECENT_TRADES: usize = 25;
+
+/// Bind `config.bind` and serve in the background.
+pub async fn start(config: &WebConfig, state_rx: watch::Receiver<AppState>) -> Result<()> {
+    let listener = TcpListener::bind(&config.bind)
+        .await
+        .with_context(|| format!("failed to bind web dashboard to {}", config.bind))?;
+    let token = config.token.clone();
+    tokio::spawn(async move {
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    tracing::warn!(error = %e, "web dashboard accept failed");
+                    continue;
+                }
+            };
+            let state_rx = state_rx.clone();
+            let token = token.clone();
+            tokio::spawn(async move {
+                if let Err(e) = serve(stream, state_rx, token.as_deref()).await {
+                    tracing::debug!(error = %e, "web dashboard connection ended");
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Route {
+    Page,
+    Socket,
+    Unauthorized,
+    NotFound,
+}
+
+/// Pick a route from the start of a request ("GET /ws?token=... HTTP/1.1").
+fn route(request: &str, token: Option<&str>) -> Route {
+    let mut parts = request.split_whitespace();
+    let (Some("GET"), Some(target)) = (parts.next(), parts.next()) else {
+        return Route::NotFound;
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let route = match path {
+        "/" => Route::Page,
+        "/ws" => Route::Socket,
+        _ => return Route::NotFound,
+    };
+    match token {
+        Some(token) if !query.split('&').any(|kv| kv == format!("token={}", token)) => {
+            Route::Unauthorized
+        }
+        _ => route,
+    }
+}
+
+async fn serve(
+    mut stream: TcpStream,
+    state_rx: watch::Receiver<AppState>,
+    token: Option<&str>,
+) -> Result<()> {
+    let mut buf = [0u8; 2048];
+    let n = stream.peek(&mut buf).await?;
+    let request = String::from_utf8_lossy(&buf[..n]);
+    let (status, body) = match route(&request, token) {
+        Route::Socket => return push_frames(stream, state_rx).await,
+        Route::Page => ("200 OK", INDEX_HTML),
+        Route::Unauthorized => ("401 Unauthorized", "missing or wrong ?token=\n"),
+        Route::NotFound => ("404 Not Found", "not found\n"),
+    };
+    // Drain the request so closing the socket does not reset the reply
+    let _ = stream.read(&mut buf).await?;
+    let content_type = if status == "200 OK" {
+        "text/html; charset=utf-8"
+    } else {
+        "text/plain; charset=utf-8"
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+async fn push_frames(stream: TcpStream, state_rx: watch::Receiver<AppState>) -> Result<()> {
+    let ws = tokio_tungstenite::accept_async(stream).await?;
+    let (mut tx, mut rx) = ws.split();
+    let mut tick = tokio::time::interval(PUSH_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = tick.tick() => {
+                let json = serde_json::to_string(&Frame::new(&state_rx.borrow()))?;
+                tx.send(Message::Text(json)).await?;
+            }
+            msg = rx.next() => match msg {
+                Some(Ok(Message::Close(_))) | None => return Ok(()),
+                Some(Err(e)) => return Err(e.into()),
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+/// One WebSocket message: everything the page shows.
+#[derive(Debug, Serialize)]
+struct Frame<'a> {
+    sim: bool,
+    paused: bool,
+    balance_cents: i64,
+    exposure_cents: i64,
+    realized_pnl_cents: i64,
+    total_trades: u32,
+    winning_trades: u32,
+    kalshi_ws_connected: bool,
+    markets: Vec<FrameMarket<'a>>,
+    positions: Vec<SnapshotPosition<'a>>,
+    trades: Vec<FrameTrade<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct FrameMarket<'a> {
+    ticker: &'a str,
+    fair_value: u32,
+    bid: u32,
+    ask: u32,
+    edge: i32,
+    action: &'a str,
+    phase: &'a str,
+    signal: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct FrameTrade<'a> {
+    time: &'a str,
+    action: &'a str,
+    ticker: &'a str,
+    price: u32,
+    quantity: u32,
+    pnl: Option<i32>,
+}
+
+impl<'a> Frame<'a> {
+    fn new(state: &'a AppState) -> Self {
+        Self {
+            sim: state.sim_mode,
+            paused: state.is_paused,
+            balance_cents: headless::balance_cents(state),
+            exposure_cents: state.total_exposure_cents,
+            realized_pnl_cents: state.realized_pnl_cents,
+            total_trades: state.total_trades,
+            winning_trades: state.winning_trades,
+            kalshi_ws_connected: state.kalshi_ws_connected,
+            markets: state
+                .markets
+                .iter()
+                .map(|m| FrameMarket {
+                    ticker: &m.ticker,
+                    fair_value: m.fair_value,
+                    bid: m.bid,
+                    ask: m.ask,
+                    edge: m.edge,
+                    action: &m.action,
+                    phase: &m.phase,
+                    signal: m.skip_reason.is_none(),
+                })
+                .collect(),
+            positions: headless::positions(state),
+            trades: state
+                .trades
+                .iter()
+                .rev()
+                .take(RECENT_TRADES)
+                .map(|t| FrameTrade {
+                    time: &t.time,
+                    action: &t.action,
+                    ticker: &t.ticker,
+                    price: t.price,
+                    quantity: t.quantity,
+                    pnl: t.pnl,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route() {
+        assert_eq!(route("GET / HTTP/1.1\r\n", None), Route::Page);
+        assert_eq!(route("GET /ws HTTP/1.1\r\n", None), Route::Socket);
+        assert_eq!(
+            route("GET /favicon.ico HTTP/1.1\r\n", None),
+            Route::NotFound
+        );
+        assert_eq!(route("POST / HTTP/1.1\r\n", None), Route::NotFound);
+        assert_eq!(route("", None), Route::NotFound);
+
+        let token = Some("s3cret");
+        assert_eq!(route("GET / HTTP/1.1", token), Route::Unauthorized);
+        assert_eq!(
+            route("GET /?token=nope HTTP/1.1", token),
+            Route::Unauthorized
+        );
+        assert_eq!(route("GET /?token=s3cret HTTP/1.1", token), Route::Page);
+        assert_eq!(
+            route("GET /ws?x=1&token=s3cret HTTP/1.1", token),
+            Route::Socket
+        );
+        assert_eq!(
+            route("GET /nope?token=s3cret HTTP/1.1", token),
+            Route::NotFound
+        );
+    }
+
+    #[tokio::test]
+    async fn test_serves_page_and_frames() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut state = AppState::new();
+        state.sim_mode = true;
+        let (_state_tx, state_rx) = watch::channel(state);
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(serve(stream, state_rx.clone(), None));
+            }
+        });
+
+        let mut http = TcpStream::connect(addr).await.unwrap();
+        http.write_all(b"GET / HTTP/1.1\r\nHost: x\r\n\r\n")
+            .await
+            .unwrap();
+        let mut page = String::new();
+        http.read_to_string(&mut page).await.unwrap();
+        assert!(page.starts_with("HTTP/1.1 200 OK"));
+        assert!(page.contains("<html"));
+
+        let (mut ws, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws", addr))
+            .await
+            .unwrap();
+        let Some(Ok(Message::Text(json))) = ws.next().await else {
+            panic!("expected a text frame");
+        };
+        let frame: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(frame["sim"], true);
+        assert_eq!(frame["balance_cents"], 100_000);
+        assert_eq!(frame["markets"], serde_json::json!([]));
+    }
+}
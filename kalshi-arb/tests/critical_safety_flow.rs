@@ -11,18 +11,18 @@ use std::time::{Duration, Instant};
 fn test_full_safety_gate_flow() {
     // 1. Risk manager allows initial trade
     let risk_config = RiskConfig {
-        max_contracts_per_market: 10,
         max_total_exposure_cents: 1000,
         max_concurrent_markets: 3,
-        kelly_fraction: 0.25,
+        ..RiskConfig::default()
     };
     let risk_manager = RiskManager::new(risk_config);
-    assert!(risk_manager.can_trade("TEST-1", 5, 500));
+    assert!(risk_manager.can_trade("TEST-1", "yes", 5, 500, None));
 
-    // 2. Position tracker prevents duplicate
+    // 2. Position tracker caps top-ups at the per-market limit
     let mut position_tracker = PositionTracker::new();
-    position_tracker.record_entry("TEST-1".to_string(), 5, 50, 520, 55, Instant::now(), true);
-    assert!(position_tracker.has_position("TEST-1"));
+    position_tracker.record_entry("TEST-1".to_string(), "yes", 5, 50, 520, 55, Instant::now(), true);
+    assert!(position_tracker.can_enter("TEST-1", "yes", 5, 10));
+    assert!(!position_tracker.can_enter("TEST-1", "yes", 6, 10));
 
     // 3. Pending order registry prevents duplicate submission
     let mut pending_orders = PendingOrderRegistry::new();
@@ -35,7 +35,7 @@ fn test_full_safety_gate_flow() {
 
     // 5. Slippage buffer affects strategy
     // Edge of 5 with 3-cent buffer -> effective edge of 2 -> maker only
-    let signal = evaluate_with_slippage(65, 58, 60, 5, 2, 1, 100_000, 0.25, 100, 3);
+    let signal = evaluate_with_slippage(65, 58, 60, 5, 2, 1, 100_000, 0.25, 100, 0, 3);
     assert!(matches!(signal.action, TradeAction::MakerBuy { .. }));
 
     // 6. Order timeout expiration (immediate check won't expire fresh orders)
@@ -43,6 +43,21 @@ fn test_full_safety_gate_flow() {
     assert!(expired.is_empty(), "fresh orders should not expire with 30s threshold");
 }
 
+#[test]
+fn test_opposite_side_entry_on_held_ticker_is_rejected() {
+    let mut tracker = PositionTracker::new();
+    tracker.record_entry("TEST-1".to_string(), "yes", 5, 50, 520, 55, Instant::now(), true);
+
+    // Gate 1 blocks the other side of a held ticker
+    assert!(!tracker.can_enter("TEST-1", "no", 1, 10));
+
+    // And a NO fill that gets through anyway is never averaged into YES
+    tracker.record_entry("TEST-1".to_string(), "no", 3, 45, 140, 50, Instant::now(), true);
+    let pos = tracker.get("TEST-1").unwrap();
+    assert_eq!(pos.side, "yes");
+    assert_eq!((pos.quantity, pos.entry_price, pos.entry_cost_cents, pos.sell_target), (5, 50, 520, 55));
+}
+
 #[test]
 fn test_drain_returns_all_orders() {
     let mut registry = PendingOrderRegistry::new();
@@ -74,17 +89,17 @@ fn test_all_order_ids_for_bulk_cancel() {
 #[test]
 fn test_slippage_buffer_downgrades_taker_to_maker() {
     // Without slippage: edge 5 >= taker_threshold 5 -> TAKER
-    let signal_no_slip = evaluate_with_slippage(65, 58, 60, 5, 2, 1, 100_000, 0.25, 100, 0);
+    let signal_no_slip = evaluate_with_slippage(65, 58, 60, 5, 2, 1, 100_000, 0.25, 100, 0, 0);
     assert_eq!(signal_no_slip.action, TradeAction::TakerBuy);
 
     // With 1-cent slippage: effective_edge 4 < taker_threshold 5 -> MAKER
-    let signal_with_slip = evaluate_with_slippage(65, 58, 60, 5, 2, 1, 100_000, 0.25, 100, 1);
+    let signal_with_slip = evaluate_with_slippage(65, 58, 60, 5, 2, 1, 100_000, 0.25, 100, 0, 1);
     assert!(matches!(signal_with_slip.action, TradeAction::MakerBuy { .. }));
 }
 
 #[test]
 fn test_slippage_buffer_can_skip_trade() {
     // Edge of 2, slippage of 2 -> effective edge 0 < maker_threshold 2 -> SKIP
-    let signal = evaluate_with_slippage(62, 58, 60, 5, 2, 1, 100_000, 0.25, 100, 2);
+    let signal = evaluate_with_slippage(62, 58, 60, 5, 2, 1, 100_000, 0.25, 100, 0, 2);
     assert_eq!(signal.action, TradeAction::Skip);
 }
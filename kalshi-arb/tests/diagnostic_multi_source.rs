@@ -49,6 +49,8 @@ mod tests {
             clock_seconds: 420,
             total_elapsed_seconds: 2100,
             game_status: GameStatus::Live,
+            home_red_cards: 0,
+            away_red_cards: 0,
             source: ScoreSource::Espn,
         };
 
@@ -96,6 +98,8 @@ mod tests {
             clock_seconds: 600,
             total_elapsed_seconds: 1800,
             game_status: GameStatus::Live,
+            home_red_cards: 0,
+            away_red_cards: 0,
             source: ScoreSource::Espn,
         };
 
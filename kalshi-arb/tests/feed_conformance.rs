@@ -0,0 +1,477 @@
+// Conformance suite for odds and score feeds. Each adapter is run against a
+// mock HTTP server and must meet what the pipeline assumes of every feed:
+// quota reported when the source meters one, a failed poll surfaced as an
+// error that the next poll recovers from, one malformed event skipped rather
+// than failing the batch, RFC 3339 UTC timestamps, and both team names set.
+// A new adapter gets a case here before it is wired into `main`.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use chrono::DateTime;
+use kalshi_arb::config::DraftKingsFeedConfig;
+use kalshi_arb::feed::draftkings::DraftKingsFeed;
+use kalshi_arb::feed::pinnacle::PinnacleFeed;
+use kalshi_arb::feed::score_feed::{ScorePoller, ScoreUpdate};
+use kalshi_arb::feed::scraped::ScrapedOddsFeed;
+use kalshi_arb::feed::the_odds_api::TheOddsApi;
+use kalshi_arb::feed::types::OddsUpdate;
+use kalshi_arb::feed::OddsFeed;
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+// ── Mock HTTP server ─────────────────────────────────────────────────
+
+struct MockResponse {
+    status: u16,
+    headers: Vec<(&'static str, String)>,
+    body: String,
+}
+
+impl MockResponse {
+    fn json(body: Value) -> Self {
+        Self {
+            status: 200,
+            headers: Vec::new(),
+            body: body.to_string(),
+        }
+    }
+
+    fn error(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: "upstream error".to_string(),
+        }
+    }
+
+    fn header(mut self, name: &'static str, value: &str) -> Self {
+        self.headers.push((name, value.to_string()));
+        self
+    }
+}
+
+/// Answers each request with the next queued response, in order, and closes
+/// the connection; 404 once the queue is empty.
+struct MockServer {
+    url: String,
+    queue: Arc<Mutex<VecDeque<MockResponse>>>,
+}
+
+impl MockServer {
+    async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let queue: Arc<Mutex<VecDeque<MockResponse>>> = Arc::default();
+        let responses = queue.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut conn, _)) = listener.accept().await else {
+                    return;
+                };
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match conn.read(&mut buf).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let resp = responses
+                    .lock()
+                    .unwrap()
+                    .pop_front()
+                    .unwrap_or_else(|| MockResponse::error(404));
+                let mut head = format!(
+                    "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+                    resp.status,
+                    resp.body.len()
+                );
+                for (name, value) in &resp.headers {
+                    head.push_str(&format!("{}: {}\r\n", name, value));
+                }
+                head.push_str("\r\n");
+                let _ = conn.write_all(head.as_bytes()).await;
+                let _ = conn.write_all(resp.body.as_bytes()).await;
+                let _ = conn.shutdown().await;
+            }
+        });
+        Self { url, queue }
+    }
+
+    fn push(&self, responses: Vec<MockResponse>) {
+        self.queue.lock().unwrap().extend(responses);
+    }
+}
+
+// ── Odds feed conformance ────────────────────────────────────────────
+
+/// One adapter's fixtures and what it is expected to report.
+struct OddsCase {
+    sport: &'static str,
+    /// Responses for one poll returning `events` complete events.
+    good: fn() -> Vec<MockResponse>,
+    /// The same poll with one more event, malformed.
+    one_malformed: fn() -> Vec<MockResponse>,
+    events: usize,
+    /// `(used, remaining, last)` from the good poll's quota headers, for
+    /// sources that meter requests.
+    quota: Option<(u64, u64, u64)>,
+    /// Whether the adapter counts skipped events in `schema_drift_count`.
+    counts_drift: bool,
+}
+
+fn assert_rfc3339_utc(field: &str, value: &str) {
+    let parsed = DateTime::parse_from_rfc3339(value)
+        .unwrap_or_else(|e| panic!("{} {:?} is not RFC 3339: {}", field, value, e));
+    assert_eq!(
+        parsed.offset().local_minus_utc(),
+        0,
+        "{} {:?} is not UTC",
+        field,
+        value
+    );
+}
+
+fn assert_well_formed(update: &OddsUpdate, sport: &str) {
+    assert!(!update.event_id.is_empty(), "event_id missing");
+    assert_eq!(update.sport, sport);
+    assert!(!update.home_team.trim().is_empty(), "home_team missing");
+    assert!(!update.away_team.trim().is_empty(), "away_team missing");
+    assert_ne!(update.home_team, update.away_team);
+    assert_rfc3339_utc("commence_time", &update.commence_time);
+    assert!(!update.bookmakers.is_empty(), "no bookmaker odds");
+    for bm in &update.bookmakers {
+        assert!(!bm.name.is_empty(), "bookmaker name missing");
+        assert_rfc3339_utc("last_update", &bm.last_update);
+        for odds in [bm.home_odds, bm.away_odds] {
+            assert!(
+                odds.is_finite() && odds.abs() >= 100.0,
+                "{} is not American odds",
+                odds
+            );
+        }
+    }
+}
+
+async fn assert_odds_feed_conforms<F>(case: OddsCase, make: F)
+where
+    F: Fn(&str) -> Box<dyn OddsFeed>,
+{
+    let server = MockServer::start().await;
+
+    let mut feed = make(&server.url);
+    server.push((case.good)());
+    let updates = feed.fetch_odds(case.sport).await.expect("good poll");
+    assert_eq!(updates.len(), case.events);
+    for update in &updates {
+        assert_well_formed(update, case.sport);
+    }
+    match (case.quota, feed.last_quota()) {
+        (Some((used, remaining, last)), Some(quota)) => assert_eq!(
+            (
+                quota.requests_used,
+                quota.requests_remaining,
+                quota.requests_last
+            ),
+            (used, remaining, last)
+        ),
+        (None, None) => {}
+        (expected, reported) => panic!(
+            "quota: expected {:?}, feed reported {:?}",
+            expected, reported
+        ),
+    }
+
+    // A failed poll is an error, not an empty batch, and leaves nothing
+    // behind that stops the next poll from returning everything
+    let mut feed = make(&server.url);
+    server.push(vec![MockResponse::error(500)]);
+    assert!(feed.fetch_odds(case.sport).await.is_err());
+    server.push((case.good)());
+    let updates = feed.fetch_odds(case.sport).await.expect("recovery poll");
+    assert_eq!(updates.len(), case.events);
+
+    let mut feed = make(&server.url);
+    server.push((case.one_malformed)());
+    let updates = feed
+        .fetch_odds(case.sport)
+        .await
+        .expect("one malformed event must not fail the poll");
+    assert_eq!(updates.len(), case.events);
+    for update in &updates {
+        assert_well_formed(update, case.sport);
+    }
+    if case.counts_drift {
+        assert_eq!(feed.schema_drift_count(), 1);
+    }
+}
+
+fn odds_api_events(malformed: bool) -> Vec<MockResponse> {
+    let event = |id: &str, home: &str, away: &str| {
+        json!({
+            "id": id,
+            "sport_key": "basketball_nba",
+            "home_team": home,
+            "away_team": away,
+            "commence_time": "2026-03-11T00:00:00Z",
+            "bookmakers": [{
+                "key": "draftkings",
+                "title": "DraftKings",
+                "last_update": "2026-03-10T22:15:04Z",
+                "markets": [{
+                    "key": "h2h",
+                    "outcomes": [
+                        {"name": home, "price": -150},
+                        {"name": away, "price": 130}
+                    ]
+                }]
+            }]
+        })
+    };
+    let mut events = vec![
+        event("evt-1", "Boston Celtics", "Los Angeles Lakers"),
+        event("evt-2", "Denver Nuggets", "Miami Heat"),
+    ];
+    if malformed {
+        events.insert(1, json!({"id": "evt-bad", "home_team": 7}));
+    }
+    vec![MockResponse::json(Value::Array(events))
+        .header("x-requests-used", "120")
+        .header("x-requests-remaining", "19880.0")
+        .header("x-requests-last", "1")]
+}
+
+#[tokio::test]
+async fn the_odds_api_conforms() {
+    let case = OddsCase {
+        sport: "basketball",
+        good: || odds_api_events(false),
+        one_malformed: || odds_api_events(true),
+        events: 2,
+        quota: Some((120, 19_880, 1)),
+        counts_drift: true,
+    };
+    assert_odds_feed_conforms(case, |url| {
+        Box::new(TheOddsApi::new(
+            "test-key".to_string(),
+            url,
+            "draftkings",
+            reqwest::Client::builder(),
+        ))
+    })
+    .await;
+}
+
+fn pinnacle_polls(malformed: bool) -> Vec<MockResponse> {
+    let mut fixtures = vec![
+        json!({"id": 101, "starts": "2026-03-11T00:00:00Z", "home": "Boston Celtics", "away": "Los Angeles Lakers"}),
+        json!({"id": 102, "starts": "2026-03-11T02:30:00Z", "home": "Denver Nuggets", "away": "Miami Heat"}),
+    ];
+    if malformed {
+        fixtures.insert(1, json!({"id": "103", "starts": null}));
+    }
+    let odds = |id: u64| json!({"id": id, "periods": [{"number": 0, "status": 1, "moneyline": {"home": -150, "away": 135}}]});
+    vec![
+        MockResponse::json(json!({"last": 1, "league": [{"events": fixtures}]})),
+        MockResponse::json(json!({"last": 1, "leagues": [{"events": [odds(101), odds(102)]}]})),
+    ]
+}
+
+#[tokio::test]
+async fn pinnacle_conforms() {
+    let case = OddsCase {
+        sport: "basketball",
+        good: || pinnacle_polls(false),
+        one_malformed: || pinnacle_polls(true),
+        events: 2,
+        quota: None,
+        counts_drift: false,
+    };
+    assert_odds_feed_conforms(case, |url| {
+        Box::new(PinnacleFeed::new(
+            "user".to_string(),
+            "pass".to_string(),
+            url,
+            5_000,
+            reqwest::Client::builder(),
+        ))
+    })
+    .await;
+}
+
+fn draftkings_group(malformed: bool) -> Vec<MockResponse> {
+    let mut events = vec![
+        json!({"eventId": 1, "startDate": "2026-03-11T00:00:00.0000000Z", "teamName1": "Boston Celtics", "teamName2": "Los Angeles Lakers"}),
+        json!({"eventId": 2, "startDate": "2026-03-11T02:30:00.0000000Z", "teamName1": "Denver Nuggets", "teamName2": "Miami Heat"}),
+    ];
+    if malformed {
+        events.insert(1, json!({"eventId": "three", "teamName1": ["?"]}));
+    }
+    let offer = |id: u64, home: &str, away: &str| {
+        json!({"eventId": id, "isSuspended": false, "outcomes": [
+            {"label": home, "oddsAmerican": "-150"},
+            {"label": away, "oddsAmerican": "+130"}
+        ]})
+    };
+    let offers = json!([[
+        offer(1, "Boston Celtics", "Los Angeles Lakers"),
+        offer(2, "Denver Nuggets", "Miami Heat"),
+        offer(3, "Chicago Bulls", "Utah Jazz")
+    ]]);
+    // Category discovery, then the moneyline subcategory itself
+    vec![
+        MockResponse::json(json!({"eventGroup": {
+            "events": events,
+            "offerCategories": [{"offerCategoryId": 487, "name": "Game Lines", "offerSubcategoryDescriptors": [
+                {"subcategoryId": 4518, "name": "Moneyline"}
+            ]}]
+        }})),
+        MockResponse::json(json!({"eventGroup": {
+            "events": events,
+            "offerCategories": [{"offerCategoryId": 487, "name": "Game Lines", "offerSubcategoryDescriptors": [
+                {"subcategoryId": 4518, "name": "Moneyline", "offerSubcategory": {"offers": offers}}
+            ]}]
+        }})),
+    ]
+}
+
+#[tokio::test]
+async fn draftkings_conforms() {
+    let case = OddsCase {
+        sport: "basketball",
+        good: || draftkings_group(false),
+        one_malformed: || draftkings_group(true),
+        events: 2,
+        quota: None,
+        counts_drift: false,
+    };
+    assert_odds_feed_conforms(case, |url| {
+        let config = DraftKingsFeedConfig {
+            live_poll_interval_s: 0,
+            base_url: url.to_string(),
+            ..DraftKingsFeedConfig::default()
+        };
+        Box::new(DraftKingsFeed::new(&config, reqwest::Client::builder()))
+    })
+    .await;
+}
+
+fn bovada_sections(malformed: bool) -> Vec<MockResponse> {
+    let event = |id: &str, home: &str, away: &str, start: i64| {
+        json!({
+            "id": id,
+            "startTime": start,
+            "competitors": [{"name": home, "home": true}, {"name": away, "home": false}],
+            "displayGroups": [{"markets": [{"key": "2W-12", "outcomes": [
+                {"description": home, "price": {"american": "-150"}},
+                {"description": away, "price": {"american": "EVEN"}}
+            ]}]}]
+        })
+    };
+    let mut events = vec![
+        event(
+            "201",
+            "Duke Blue Devils",
+            "North Carolina Tar Heels",
+            1_773_187_200_000,
+        ),
+        event("202", "Kansas Jayhawks", "Baylor Bears", 1_773_196_200_000),
+    ];
+    if malformed {
+        events.insert(1, json!({"id": 203, "startTime": "tonight"}));
+    }
+    vec![MockResponse::json(json!([{"events": events}]))]
+}
+
+#[tokio::test]
+async fn scraped_conforms() {
+    let case = OddsCase {
+        sport: "college-basketball",
+        good: || bovada_sections(false),
+        one_malformed: || bovada_sections(true),
+        events: 2,
+        quota: None,
+        counts_drift: false,
+    };
+    assert_odds_feed_conforms(case, |url| {
+        Box::new(ScrapedOddsFeed::new(
+            url,
+            5_000,
+            0,
+            reqwest::Client::builder(),
+        ))
+    })
+    .await;
+}
+
+// ── Score feed conformance ───────────────────────────────────────────
+
+fn assert_score_well_formed(update: &ScoreUpdate) {
+    assert!(!update.game_id.is_empty(), "game_id missing");
+    assert!(!update.home_team.trim().is_empty(), "home_team missing");
+    assert!(!update.away_team.trim().is_empty(), "away_team missing");
+    assert_ne!(update.home_team, update.away_team);
+}
+
+fn nba_scoreboard() -> MockResponse {
+    MockResponse::json(json!({"scoreboard": {"games": [{
+        "gameId": "0022400567",
+        "gameStatus": 2,
+        "homeTeam": {"teamName": "Celtics", "teamCity": "Boston", "score": 55},
+        "awayTeam": {"teamName": "Lakers", "teamCity": "Los Angeles", "score": 50},
+        "period": 2,
+        "gameClock": "PT05M30.00S"
+    }]}}))
+}
+
+fn espn_scoreboard() -> MockResponse {
+    let competitor = |side: &str, name: &str, score: &str| json!({"homeAway": side, "team": {"id": "1", "displayName": name}, "score": score});
+    MockResponse::json(json!({"events": [{
+        "id": "401671800",
+        "competitions": [{
+            "competitors": [
+                competitor("home", "Boston Celtics", "55"),
+                competitor("away", "Los Angeles Lakers", "50")
+            ],
+            "status": {"type": {"id": "2"}, "period": 2, "displayClock": "5:30"}
+        }]
+    }]}))
+}
+
+#[tokio::test]
+async fn score_poller_conforms() {
+    let server = MockServer::start().await;
+    let mut poller = ScorePoller::new(
+        &format!("{}/nba", server.url),
+        &format!("{}/espn", server.url),
+        5_000,
+        3,
+        reqwest::Client::builder(),
+    );
+
+    for (primary, expected) in [
+        (nba_scoreboard(), "Boston Celtics"),
+        // A failed primary falls back to the secondary scoreboard
+        (MockResponse::error(500), "Boston Celtics"),
+    ] {
+        let fallback = primary.status != 200;
+        server.push(vec![primary]);
+        if fallback {
+            server.push(vec![espn_scoreboard()]);
+        }
+        let updates = poller.fetch().await.expect("score poll");
+        assert_eq!(updates.len(), 1);
+        assert_score_well_formed(&updates[0]);
+        assert_eq!(updates[0].home_team, expected);
+    }
+
+    // Both scoreboards down is an error, and the next poll recovers
+    server.push(vec![MockResponse::error(500), MockResponse::error(503)]);
+    assert!(poller.fetch().await.is_err());
+    server.push(vec![nba_scoreboard()]);
+    let updates = poller.fetch().await.expect("recovery poll");
+    assert_eq!(updates.len(), 1);
+    assert_score_well_formed(&updates[0]);
+}
@@ -10,21 +10,20 @@ mod tests {
     #[test]
     fn test_risk_manager_enforces_limits() {
         let config = RiskConfig {
-            max_contracts_per_market: 10,
             max_total_exposure_cents: 1000, // $10 max
             max_concurrent_markets: 3,
-            kelly_fraction: 0.25,
+            ..RiskConfig::default()
         };
         let manager = RiskManager::new(config);
 
         // Should allow first trade
-        assert!(manager.can_trade("TEST-1", 5, 500));
+        assert!(manager.can_trade("TEST-1", "yes", 5, 500, None));
     }
 
     #[test]
     fn test_position_tracker_prevents_duplicates() {
         let tracker = PositionTracker::new();
-        assert!(!tracker.has_position("TEST"));
+        assert!(tracker.get("TEST").is_none());
     }
 
     #[test]